@@ -895,10 +895,14 @@ pub mod payment_command {
     use super::*;
 
     command!(CommandMetadata::build("payment", "Send request for doing tokens payment.")
-                .add_required_param("inputs","The list of UTXO inputs")
+                .add_optional_param("inputs","The list of UTXO inputs. If omitted inputs are selected from UTXOs of wallet payment addresses")
                 .add_required_param("outputs","The list of outputs")
+                .add_optional_param("change_address","Payment address to send change to. Required if inputs are omitted")
+                .add_optional_param("fee","Amount of fee tokens to cover by selected inputs (0 by default)")
+                .add_optional_param("selection","Inputs selection strategy: largest_first (default) or smallest_sufficient")
                 .add_example("ledger payment inputs=txo:sov:111_rBuQo2A1sc9jrJg outputs=(pay:sov:FYmoFw55GeQH7SRFa37dkx1d2dZ3zUF8ckg7wmL7ofN4,100,extradata)")
                 .add_example("ledger payment inputs=txo:sov:111_rBuQo2A1sc9jrJg,txo:sov:222_aEwACvA1sc9jrJg outputs=(pay:sov:FYmoFw55GeQH7SRFa37dkx1d2dZ3zUF8ckg7wmL7ofN4,100,extradata),(pay:sov:ABABefwrhscbaAShva7dkx1d2dZ3zUF8ckg7wmL7ofN4,5)")
+                .add_example("ledger payment outputs=(pay:sov:FYmoFw55GeQH7SRFa37dkx1d2dZ3zUF8ckg7wmL7ofN4,100) change_address=pay:sov:ABABefwrhscbaAShva7dkx1d2dZ3zUF8ckg7wmL7ofN4")
                 .finalize()
     );
 
@@ -909,12 +913,23 @@ pub mod payment_command {
         let (wallet_handle, wallet_name) = ensure_opened_wallet(&ctx)?;
         let submitter_did = ensure_active_did(&ctx)?;
 
-        let inputs = get_str_array_param("inputs", params).map_err(error_err!())?;
+        let inputs = get_opt_str_array_param("inputs", params).map_err(error_err!())?;
         let outputs = get_str_tuple_array_param("outputs", params).map_err(error_err!())?;
+        let change_address = get_opt_str_param("change_address", params).map_err(error_err!())?;
+        let fee = get_opt_number_param::<u64>("fee", params).map_err(error_err!())?.unwrap_or(0);
+        let selection = get_opt_str_param("selection", params).map_err(error_err!())?;
 
-        let inputs = parse_payment_inputs(&inputs).map_err(error_err!())?;
         let outputs = parse_payment_outputs(&outputs).map_err(error_err!())?;
 
+        let (inputs, outputs) = match inputs {
+            Some(inputs) => (parse_payment_inputs(&inputs).map_err(error_err!())?, outputs),
+            None => {
+                let change_address = change_address
+                    .ok_or_else(|| println_err!("Either inputs or change_address must be specified"))?;
+                prepare_payment_inputs(pool_handle, wallet_handle, &submitter_did, &outputs, fee, change_address, selection)?
+            }
+        };
+
         let (request, payment_method) = Payment::build_payment_req(wallet_handle, &submitter_did, &inputs, &outputs)
             .map_err(|err| handle_payment_error(err, None))?;
 
@@ -1136,8 +1151,8 @@ fn parse_payment_outputs(outputs: &Vec<String>) -> Result<String, ()> {
                         "amount": parts.get(1)
                                     .ok_or(())
                                     .map_err(|_| println_err!("Invalid format of Outputs: Amount not found"))
-                                    .and_then(|amount| amount.parse::<i32>()
-                                        .map_err(|_| println_err!("Invalid format of Outputs: Amount must be integer")))?,
+                                    .and_then(|amount| amount.parse::<u64>()
+                                        .map_err(|_| println_err!("Invalid format of Outputs: Amount must be non-negative integer")))?,
                         "extra": if parts.len() > 1 {Some(parts[2..].join(":"))} else { None }
                     }));
     }
@@ -1146,8 +1161,43 @@ fn parse_payment_outputs(outputs: &Vec<String>) -> Result<String, ()> {
         .map_err(|_| println_err!("Wrong data has been received"))
 }
 
+fn prepare_payment_inputs(pool_handle: i32, wallet_handle: i32, submitter_did: &str, outputs: &str, fee: u64,
+                          change_address: &str, selection: Option<&str>) -> Result<(String, String), ()> {
+    let mut outputs: Vec<serde_json::Value> = serde_json::from_str(outputs)
+        .map_err(|_| println_err!("Wrong data has been received"))?;
+
+    if outputs.is_empty() {
+        return Err(println_err!("Outputs list is empty"));
+    }
+
+    let amount = outputs.iter()
+        .fold(Some(0u64), |total, output| total.and_then(|total| total.checked_add(output["amount"].as_u64().unwrap_or(0))))
+        .ok_or_else(|| println_err!("Total amount of Outputs is too big"))?;
+
+    let payment_method = outputs[0]["paymentAddress"].as_str()
+        .and_then(|address| address.split(":").nth(1))
+        .ok_or_else(|| println_err!("Invalid format of Outputs: Payment Address must be in the format pay:<method>:<address>"))?
+        .to_string();
+
+    let fees = json!({"payment": fee}).to_string();
+    let config = selection.map(|strategy| json!({"strategy": strategy}).to_string());
+
+    let (inputs, change) = Payment::prepare_payment(wallet_handle, pool_handle, submitter_did, &payment_method, amount,
+                                                    &fees, change_address, config.as_ref().map(String::as_str))
+        .map_err(|err| handle_payment_error(err, Some(&payment_method)))?;
+
+    let change: Vec<serde_json::Value> = serde_json::from_str(&change)
+        .map_err(|_| println_err!("Wrong data has been received"))?;
+    outputs.extend(change);
+
+    let outputs = serde_json::to_string(&outputs)
+        .map_err(|_| println_err!("Wrong data has been received"))?;
+
+    Ok((inputs, outputs))
+}
+
 fn parse_payment_fees(fees: &Vec<&str>) -> Result<String, ()> {
-    let mut fees_map: HashMap<String, i32> = HashMap::new();
+    let mut fees_map: HashMap<String, u64> = HashMap::new();
 
     for fee in fees {
         let parts = fee.split(":").collect::<Vec<&str>>();
@@ -1160,8 +1210,8 @@ fn parse_payment_fees(fees: &Vec<&str>) -> Result<String, ()> {
         let amount = parts.get(1)
             .ok_or(())
             .map_err(|_| println_err!("Invalid format of Fees: Amount not found"))
-            .and_then(|amount| amount.parse::<i32>()
-                .map_err(|_| println_err!("Invalid format of Fees: Amount must be integer")))?;
+            .and_then(|amount| amount.parse::<u64>()
+                .map_err(|_| println_err!("Invalid format of Fees: Amount must be non-negative integer")))?;
 
         fees_map.insert(type_, amount);
     }
@@ -2819,6 +2869,52 @@ pub mod tests {
             TestUtils::cleanup_storage();
         }
 
+        #[test]
+        pub fn payment_works_for_selected_inputs() {
+            TestUtils::cleanup_storage();
+            let ctx = CommandContext::new();
+
+            create_and_connect_pool(&ctx);
+            create_and_open_wallet(&ctx);
+            load_null_payment_plugin(&ctx);
+            new_did(&ctx, SEED_TRUSTEE);
+            use_did(&ctx, DID_TRUSTEE);
+
+            let payment_address_from = create_address_and_mint_tokens(&ctx);
+            {
+                let cmd = payment_command::new();
+                let mut params = CommandParams::new();
+                params.insert("outputs", format!("({},{})", PAYMENT_ADDRESS, 10));
+                params.insert("change_address", payment_address_from);
+                params.insert("selection", "smallest_sufficient".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            close_and_delete_wallet(&ctx);
+            disconnect_and_delete_pool(&ctx);
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn payment_works_for_no_inputs_and_no_change_address() {
+            TestUtils::cleanup_storage();
+            let ctx = CommandContext::new();
+
+            create_and_connect_pool(&ctx);
+            create_and_open_wallet(&ctx);
+            load_null_payment_plugin(&ctx);
+            new_did(&ctx, SEED_TRUSTEE);
+            use_did(&ctx, DID_TRUSTEE);
+            {
+                let cmd = payment_command::new();
+                let mut params = CommandParams::new();
+                params.insert("outputs", format!("({},{})", PAYMENT_ADDRESS, 10));
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            close_and_delete_wallet(&ctx);
+            disconnect_and_delete_pool(&ctx);
+            TestUtils::cleanup_storage();
+        }
+
         #[test]
         pub fn payment_works_for_multiple_inputs() {
             TestUtils::cleanup_storage();
@@ -4,7 +4,7 @@ extern crate chrono;
 use command_executor::{Command, CommandContext, CommandMetadata, CommandParams, CommandGroup, CommandGroupMetadata};
 use commands::*;
 
-use libindy::ErrorCode;
+use libindy::{ErrorCode, ErrorDetails};
 use libindy::payment::Payment;
 
 use serde_json::Value as JSONValue;
@@ -99,6 +99,7 @@ pub fn handle_payment_error(err: ErrorCode, payment_method: Option<&str>) {
     match err {
         ErrorCode::PaymentUnknownMethodError => println_err!("Unknown payment method {}", payment_method.unwrap_or("")),
         ErrorCode::PaymentIncompatibleMethodsError => println_err!("No method were scraped or more than one were scraped"),
+        ErrorCode::PaymentMixedMethodsError => println_err!("Inputs and outputs of several payment methods were mixed: {}", ErrorDetails::last().unwrap_or_default()),
         ErrorCode::PaymentInsufficientFundsError => println_err!("Insufficient funds on inputs"),
         ErrorCode::CommonInvalidStructure => println_err!("Invalid format of command params. Please check format of posted JSONs, Keys, DIDs and etc..."),
         err => println_err!("Indy SDK error occurred {:?}", err)
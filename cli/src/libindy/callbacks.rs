@@ -1,4 +1,4 @@
-use super::ErrorCode;
+use super::{ErrorCode, ErrorDetails};
 
 use utils::sequence::SequenceUtils;
 
@@ -25,6 +25,7 @@ pub fn _closure_to_cb_ec() -> (Receiver<ErrorCode>, i32,
     });
 
     extern "C" fn _callback(command_handle: i32, err: ErrorCode) {
+        ErrorDetails::store();
         let mut callbacks = CALLBACKS.lock().unwrap();
         let mut cb = callbacks.remove(&command_handle).unwrap();
         cb(err)
@@ -51,6 +52,7 @@ pub fn _closure_to_cb_ec_i32() -> (Receiver<(ErrorCode, i32)>, i32,
     });
 
     extern "C" fn _callback(command_handle: i32, err: ErrorCode, c_i32: i32) {
+        ErrorDetails::store();
         let mut callbacks = CALLBACKS.lock().unwrap();
         let mut cb = callbacks.remove(&command_handle).unwrap();
         cb(err, c_i32)
@@ -78,6 +80,7 @@ pub fn _closure_to_cb_ec_string() -> (Receiver<(ErrorCode, String)>, i32,
     });
 
     extern "C" fn _callback(command_handle: i32, err: ErrorCode, c_str: *const c_char) {
+        ErrorDetails::store();
         let mut callbacks = CALLBACKS.lock().unwrap();
         let mut cb = callbacks.remove(&command_handle).unwrap();
         let metadata = unsafe { CStr::from_ptr(c_str).to_str().unwrap().to_string() };
@@ -107,6 +110,7 @@ pub fn _closure_to_cb_ec_string_string() -> (Receiver<(ErrorCode, String, String
     });
 
     extern "C" fn _callback(command_handle: i32, err: ErrorCode, str1: *const c_char, str2: *const c_char) {
+        ErrorDetails::store();
         let mut callbacks = CALLBACKS.lock().unwrap();
         let mut cb = callbacks.remove(&command_handle).unwrap();
         let str1 = unsafe { CStr::from_ptr(str1).to_str().unwrap().to_string() };
@@ -140,6 +144,7 @@ pub fn _closure_to_cb_ec_message58() -> (Receiver<(ErrorCode, String)>, i32,
     });
 
     extern "C" fn _callback(command_handle: i32, err: ErrorCode, msg: *const u8, len: u32) {
+        ErrorDetails::store();
         let mut callbacks = CALLBACKS.lock().unwrap();
         let mut cb = callbacks.remove(&command_handle).unwrap();
         let str = unsafe {
@@ -174,6 +179,7 @@ pub fn _closure_to_cb_ec_message() -> (Receiver<(ErrorCode, String)>, i32,
     });
 
     extern "C" fn _callback(command_handle: i32, err: ErrorCode, msg: *const u8, len: u32) {
+        ErrorDetails::store();
         let mut callbacks = CALLBACKS.lock().unwrap();
         let mut cb = callbacks.remove(&command_handle).unwrap();
         let str = unsafe {
@@ -209,6 +215,7 @@ pub fn _closure_to_cb_ec_message_withkey() -> (Receiver<(ErrorCode, String,Strin
     });
 
     extern "C" fn _callback(command_handle: i32, err: ErrorCode,  their_vk: *const c_char,  msg: *const u8, len: u32) {
+        ErrorDetails::store();
         let mut callbacks = CALLBACKS.lock().unwrap();
         let mut cb = callbacks.remove(&command_handle).unwrap();
         let msg = unsafe {
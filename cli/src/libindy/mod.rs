@@ -7,6 +7,12 @@ pub mod crypto;
 mod callbacks;
 mod results;
 
+use libc::c_char;
+
+use std::ffi::CStr;
+use std::ptr;
+use std::sync::Mutex;
+
 pub type IndyHandle = i32;
 
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -63,6 +69,27 @@ pub enum ErrorCode
     // IO Error
     CommonIOError = 114,
 
+    // Caller passed invalid value as param 13 (null, invalid json and etc..)
+    CommonInvalidParam13 = 115,
+
+    // Caller passed invalid value as param 14 (null, invalid json and etc..)
+    CommonInvalidParam14 = 116,
+
+    // Caller passed invalid value as param 15 (null, invalid json and etc..)
+    CommonInvalidParam15 = 117,
+
+    // Caller passed invalid value as param 16 (null, invalid json and etc..)
+    CommonInvalidParam16 = 118,
+
+    // Caller passed invalid value as param 17 (null, invalid json and etc..)
+    CommonInvalidParam17 = 119,
+
+    // Caller passed invalid value as param 18 (null, invalid json and etc..)
+    CommonInvalidParam18 = 120,
+
+    // Caller passed invalid value as param 19 (null, invalid json and etc..)
+    CommonInvalidParam19 = 121,
+
     // Wallet errors
     // Caller passed invalid wallet handle
     WalletInvalidHandle = 200,
@@ -109,6 +136,9 @@ pub enum ErrorCode
     // Returned if provided wallet query is invalid
     WalletQueryError = 214,
 
+    // Returned if wallet record was changed since the expected version
+    WalletConflict = 215,
+
     // Ledger errors
     // Trying to open pool ledger that wasn't created before
     PoolLedgerNotCreatedError = 300,
@@ -168,6 +198,15 @@ pub enum ErrorCode
 
     // Insufficient funds on inputs
     PaymentInsufficientFundsError = 702,
+
+    // Unknown payment source (receipt or input) was given
+    PaymentSourceDoesNotExistError = 703,
+
+    // Payment method doesn't support requested operation
+    PaymentOperationNotSupportedError = 704,
+
+    // Inputs and outputs of request belong to several payment methods
+    PaymentMixedMethodsError = 705,
 }
 
 impl ErrorCode {
@@ -188,6 +227,13 @@ impl ErrorCode {
             CommonInvalidParam10 => "Caller passed invalid value as param 10",
             CommonInvalidParam11 => "Caller passed invalid value as param 11",
             CommonInvalidParam12 => "Caller passed invalid value as param 12",
+            CommonInvalidParam13 => "Caller passed invalid value as param 13",
+            CommonInvalidParam14 => "Caller passed invalid value as param 14",
+            CommonInvalidParam15 => "Caller passed invalid value as param 15",
+            CommonInvalidParam16 => "Caller passed invalid value as param 16",
+            CommonInvalidParam17 => "Caller passed invalid value as param 17",
+            CommonInvalidParam18 => "Caller passed invalid value as param 18",
+            CommonInvalidParam19 => "Caller passed invalid value as param 19",
             CommonInvalidState => "Invalid library state was detected in runtime. It signals library bug",
             CommonInvalidStructure => "Object (json, config, key, credential and etc...) passed by library caller has invalid structure",
             CommonIOError => "IO Error",
@@ -206,6 +252,7 @@ impl ErrorCode {
             WalletItemNotFound => "Requested wallet item not found",
             WalletItemAlreadyExists => "Returned if wallet's add_record operation is used with record name that already exists",
             WalletQueryError => "Returned if provided wallet query is invalid",
+            WalletConflict => "Returned if wallet record was changed since the expected version",
             PoolLedgerNotCreatedError => "Trying to open pool ledger that wasn't created before",
             PoolLedgerInvalidPoolHandle => "Caller passed invalid pool ledger handle",
             PoolLedgerTerminated => "Pool ledger terminated",
@@ -226,6 +273,41 @@ impl ErrorCode {
             PaymentUnknownMethodError => "Unknown payment method was given",
             PaymentIncompatibleMethodsError => "Multiple different payment methods were specified",
             PaymentInsufficientFundsError => "Insufficient funds on inputs",
+            PaymentSourceDoesNotExistError => "Unknown payment source was given",
+            PaymentOperationNotSupportedError => "Payment method doesn't support requested operation",
+            PaymentMixedMethodsError => "Inputs and outputs of several payment methods were mixed",
         }
     }
 }
+
+lazy_static! {
+    static ref LAST_ERROR_DETAILS: Mutex<Option<String>> = Default::default();
+}
+
+pub struct ErrorDetails {}
+
+impl ErrorDetails {
+    /// Saves details of the error passed to callback.
+    /// Libindy keeps them in the thread it calls callbacks in, so it must be called inside of callback.
+    pub fn store() {
+        let mut details_ptr: *const c_char = ptr::null();
+        unsafe { indy_get_current_error_details(&mut details_ptr); }
+
+        let details = if details_ptr.is_null() {
+            None
+        } else {
+            unsafe { CStr::from_ptr(details_ptr).to_str().ok().map(String::from) }
+        };
+
+        *LAST_ERROR_DETAILS.lock().unwrap() = details;
+    }
+
+    pub fn last() -> Option<String> {
+        LAST_ERROR_DETAILS.lock().unwrap().clone()
+    }
+}
+
+extern {
+    #[no_mangle]
+    fn indy_get_current_error_details(error_details_json_p: *mut *const c_char);
+}
@@ -2,6 +2,7 @@ use super::ErrorCode;
 
 use libc::c_char;
 use std::ffi::CString;
+use std::ptr::null;
 
 pub struct Payment {}
 
@@ -201,6 +202,33 @@ impl Payment {
 
         super::results::result_to_string(err, receiver)
     }
+
+    pub fn prepare_payment(wallet_handle: i32, pool_handle: i32, submitter_did: &str, payment_method: &str, amount: u64,
+                           fees: &str, change_address: &str, config: Option<&str>) -> Result<(String, String), ErrorCode> {
+        let (receiver, command_handle, cb) =
+            super::callbacks::_closure_to_cb_ec_string_string();
+
+        let submitter_did = CString::new(submitter_did).unwrap();
+        let payment_method = CString::new(payment_method).unwrap();
+        let fees = CString::new(fees).unwrap();
+        let change_address = CString::new(change_address).unwrap();
+        let config_str = config.map(|s| CString::new(s).unwrap()).unwrap_or(CString::new("").unwrap());
+
+        let err = unsafe {
+            indy_prepare_payment(command_handle,
+                                 wallet_handle,
+                                 pool_handle,
+                                 submitter_did.as_ptr(),
+                                 payment_method.as_ptr(),
+                                 amount,
+                                 fees.as_ptr(),
+                                 change_address.as_ptr(),
+                                 if config.is_some() { config_str.as_ptr() } else { null() },
+                                 cb)
+        };
+
+        super::results::result_to_string_string(err, receiver)
+    }
 }
 
 extern {
@@ -305,4 +333,19 @@ extern {
                                         cb: Option<extern fn(command_handle_: i32,
                                                              err: ErrorCode,
                                                              fees_json: *const c_char)>) -> ErrorCode;
+
+    #[no_mangle]
+    fn indy_prepare_payment(command_handle: i32,
+                            wallet_handle: i32,
+                            pool_handle: i32,
+                            submitter_did: *const c_char,
+                            payment_method: *const c_char,
+                            amount: u64,
+                            fees_json: *const c_char,
+                            change_address: *const c_char,
+                            config_json: *const c_char,
+                            cb: Option<extern fn(command_handle_: i32,
+                                                 err: ErrorCode,
+                                                 inputs_json: *const c_char,
+                                                 outputs_json: *const c_char)>) -> ErrorCode;
 }
@@ -7,6 +7,165 @@ extern "C" {
 
 
 
+    /// Registers custom crypto type implementation.
+    ///
+    /// Registered crypto type can be used as "crypto_type" in indy_create_key and indy_create_and_store_my_did.
+    /// Verkeys of such keys have ":<crypto_type_name>" suffix, so indy_crypto_sign, indy_crypto_verify,
+    /// indy_crypto_anon_crypt and indy_crypto_anon_decrypt are routed to the registered handlers.
+    /// Authenticated encryption isn't supported for custom crypto types.
+    ///
+    /// Built-in crypto types are "ed25519" (default) and "secp256k1" (ECDSA over SHA-256 with DER encoded signatures).
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// crypto_type_name: Crypto type name. Can't be empty or contain ':' symbol.
+    /// create_key: CryptoType create key operation handler
+    /// sign: CryptoType sign operation handler
+    /// verify: CryptoType verify operation handler
+    /// encrypt_sealed: CryptoType anonymous encryption operation handler
+    /// decrypt_sealed: CryptoType anonymous decryption operation handler
+    /// free: Handler that allows to de-allocate buffers allocated in caller code
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_register_crypto_type(indy_handle_t     command_handle,
+                                                  const char *const crypto_type_name,
+
+                                                  indy_error_t (*create_key)(const indy_u8_t *seed,
+                                                                             indy_u32_t       seed_len,
+                                                                             const indy_u8_t **verkey_p,
+                                                                             indy_u32_t       *verkey_len_p,
+                                                                             const indy_u8_t **signkey_p,
+                                                                             indy_u32_t       *signkey_len_p),
+
+                                                  indy_error_t (*sign)(const indy_u8_t *signkey,
+                                                                       indy_u32_t       signkey_len,
+                                                                       const indy_u8_t *message,
+                                                                       indy_u32_t       message_len,
+                                                                       const indy_u8_t **signature_p,
+                                                                       indy_u32_t       *signature_len_p),
+
+                                                  indy_error_t (*verify)(const indy_u8_t *verkey,
+                                                                         indy_u32_t       verkey_len,
+                                                                         const indy_u8_t *message,
+                                                                         indy_u32_t       message_len,
+                                                                         const indy_u8_t *signature,
+                                                                         indy_u32_t       signature_len,
+                                                                         indy_bool_t      *valid_p),
+
+                                                  indy_error_t (*encrypt_sealed)(const indy_u8_t *verkey,
+                                                                                 indy_u32_t       verkey_len,
+                                                                                 const indy_u8_t *message,
+                                                                                 indy_u32_t       message_len,
+                                                                                 const indy_u8_t **encrypted_p,
+                                                                                 indy_u32_t       *encrypted_len_p),
+
+                                                  indy_error_t (*decrypt_sealed)(const indy_u8_t *verkey,
+                                                                                 indy_u32_t       verkey_len,
+                                                                                 const indy_u8_t *signkey,
+                                                                                 indy_u32_t       signkey_len,
+                                                                                 const indy_u8_t *encrypted,
+                                                                                 indy_u32_t       encrypted_len,
+                                                                                 const indy_u8_t **decrypted_p,
+                                                                                 indy_u32_t       *decrypted_len_p),
+
+                                                  indy_error_t (*free)(const indy_u8_t *data,
+                                                                       indy_u32_t       data_len),
+
+                                                  void              (*cb)(indy_handle_t command_handle,
+                                                                          indy_error_t  err)
+                                                 );
+
+    /// Registers external signer that holds private keys outside of the wallet (HSM, secure enclave, etc).
+    ///
+    /// Keys of external signer are added to the wallet by indy_create_external_key or by
+    /// indy_create_and_store_my_did with "external_key" field. Wallet stores only reference to such key.
+    /// indy_crypto_sign, indy_sign_request, indy_crypto_auth_crypt, indy_crypto_auth_decrypt and
+    /// indy_crypto_anon_decrypt calls for these keys are forwarded to the signer handlers.
+    /// Only ed25519 keys are supported.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// signer_name: External signer name.
+    /// sign: ExternalSigner sign operation handler
+    /// precompute: ExternalSigner handler that computes crypto_box shared key (crypto_box_beforenm) for
+    ///             curve25519 form of the key and their curve25519 public key
+    /// decrypt_sealed: ExternalSigner sealed box (crypto_box_seal) decryption handler
+    /// free: Handler that allows to de-allocate buffers allocated in caller code
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_register_external_signer(indy_handle_t     command_handle,
+                                                      const char *const signer_name,
+
+                                                      indy_error_t (*sign)(const char *const key_ref,
+                                                                           const indy_u8_t  *message,
+                                                                           indy_u32_t        message_len,
+                                                                           const indy_u8_t  **signature_p,
+                                                                           indy_u32_t        *signature_len_p),
+
+                                                      indy_error_t (*precompute)(const char *const key_ref,
+                                                                                 const indy_u8_t  *their_pk,
+                                                                                 indy_u32_t        their_pk_len,
+                                                                                 const indy_u8_t  **precomputed_key_p,
+                                                                                 indy_u32_t        *precomputed_key_len_p),
+
+                                                      indy_error_t (*decrypt_sealed)(const char *const key_ref,
+                                                                                     const indy_u8_t  *encrypted,
+                                                                                     indy_u32_t        encrypted_len,
+                                                                                     const indy_u8_t  **decrypted_p,
+                                                                                     indy_u32_t        *decrypted_len_p),
+
+                                                      indy_error_t (*free)(const indy_u8_t *data,
+                                                                           indy_u32_t       data_len),
+
+                                                      void              (*cb)(indy_handle_t command_handle,
+                                                                              indy_error_t  err)
+                                                     );
+
+    /// Stores reference to the key held by registered external signer in the wallet.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// key_json: External key information as json. Example:
+    /// {
+    ///     "signer": string, // Name of external signer registered by indy_register_external_signer.
+    ///     "key_ref": string, // Key reference that is passed to the signer handlers.
+    ///     "verkey": string, // Ed25519 verkey of the key.
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - xcommand_handle: command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - verkey: Ver key of external key, also used as key identifier
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_create_external_key(indy_handle_t     command_handle,
+                                                 indy_handle_t     wallet_handle,
+                                                 const char *const key_json,
+
+                                                 void              (*cb)(indy_handle_t     command_handle,
+                                                                         indy_error_t      err,
+                                                                         const char *const vk)
+                                                );
+
     /// Creates keys pair and stores in the wallet.
     ///
     /// #Params
@@ -15,7 +174,7 @@ extern "C" {
     /// key_json: Key information as json. Example:
     /// {
     ///     "seed": string, // Optional (if not set random one will be used); Seed information that allows deterministic key creation.
-    ///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+    ///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Supported values are 'ed25519', 'secp256k1' and types registered by indy_register_crypto_type.
     /// }
     /// cb: Callback that takes command result as parameter.
     ///
@@ -39,6 +198,77 @@ extern "C" {
                                                                 const char *const vk)
                                        );
 
+    /// Creates a master seed for deterministic key derivation and stores it in the wallet.
+    /// Keys are derived from the master seed by indy_create_key_from_path
+    /// and indy_create_and_store_my_did according to SLIP-0010 (ed25519, hardened derivation only).
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// master_seed_id: (optional, if not present random one will be generated) new master seed id.
+    /// config_json: (optional, if not present random master seed will be generated) master seed source as json:
+    /// {
+    ///     "seed": string, // Optional; Seed information (32 symbols string or base64 encoded bytes ending with "=").
+    ///     "mnemonic": string, // Optional; BIP-0039 mnemonic sentence (English word list, valid checksum) to restore master seed from. Can't be combined with seed.
+    ///     "passphrase": string, // Optional; BIP-0039 passphrase used with mnemonic.
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - xcommand_handle: command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - out_master_seed_id: Id of generated master seed.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_create_master_seed(indy_handle_t     command_handle,
+                                                indy_handle_t     wallet_handle,
+                                                const char *const master_seed_id,
+                                                const char *const config_json,
+
+                                                void              (*cb)(indy_handle_t     command_handle,
+                                                                        indy_error_t      err,
+                                                                        const char *const out_master_seed_id)
+                                               );
+
+    /// Derives ed25519 keys pair from the master seed stored in the wallet and stores it in the wallet.
+    /// The same master seed and path always produce the same key.
+    /// Derivation info is stored as the key metadata.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// key_json: Key derivation information as json. Example:
+    /// {
+    ///     "master_seed_id": string, // Id of master seed created by indy_create_master_seed.
+    ///     "path": string, // Derivation path with hardened indexes only, e.g. "m/44'/0'/1'".
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - xcommand_handle: command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - verkey: Ver key of derived key pair, also used as key identifier
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_create_key_from_path(indy_handle_t     command_handle,
+                                                  indy_handle_t     wallet_handle,
+                                                  const char *const key_json,
+
+                                                  void              (*cb)(indy_handle_t     command_handle,
+                                                                          indy_error_t      err,
+                                                                          const char *const vk)
+                                                 );
+
     /// Saves/replaces the meta information for the giving key in the wallet.
     ///
     /// #Params
@@ -316,6 +546,145 @@ extern "C" {
                                                                       indy_u32_t        decrypted_msg_len)
                                                  );
 
+    /// Packs a message for one or more recipients into JWE-like JSON envelope.
+    ///
+    /// The message is encrypted once with random content encryption key (ChaCha20-Poly1305),
+    /// and this key is wrapped for every recipient verkey. If sender verkey is provided
+    /// the key is wrapped by authenticated-encryption scheme and recipients can learn who sent the message,
+    /// otherwise anonymous-encryption scheme is used.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// message: a pointer to first byte of message to be packed (UTF8 string is expected)
+    /// message_len: a message length
+    /// receiver_keys: a json array of recipient verkeys: ["verkey1", "verkey2"]
+    /// sender(optional): id (verkey) of my key. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// a packed message (JWE json) as a pointer to array of bytes
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_pack_message(indy_handle_t      command_handle,
+                                          indy_handle_t      wallet_handle,
+                                          const indy_u8_t*   message,
+                                          indy_u32_t         message_len,
+                                          const char *       receiver_keys,
+                                          const char *       sender,
+
+                                          void           (*cb)(indy_handle_t     xcommand_handle,
+                                                               indy_error_t      err,
+                                                               const indy_u8_t*  jwe_data,
+                                                               indy_u32_t        jwe_len)
+                                          );
+
+    /// Unpacks a message packed by indy_pack_message.
+    ///
+    /// The first recipient which key is stored in the wallet is used to decrypt the message.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// jwe_data: a pointer to first byte of packed message (JWE json)
+    /// jwe_len: a packed message length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// unpacked message json as a pointer to array of bytes:
+    /// {
+    ///     "message": string, // decrypted message
+    ///     "recipient_verkey": string, // verkey of the wallet key used to decrypt the message
+    ///     "sender_verkey": string, // sender verkey (only if message was packed with sender)
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_unpack_message(indy_handle_t      command_handle,
+                                            indy_handle_t      wallet_handle,
+                                            const indy_u8_t*   jwe_data,
+                                            indy_u32_t         jwe_len,
+
+                                            void           (*cb)(indy_handle_t     xcommand_handle,
+                                                                 indy_error_t      err,
+                                                                 const indy_u8_t*  res_json_data,
+                                                                 indy_u32_t        res_json_len)
+                                            );
+
+    /// Signs JSON payload as JSON Web Signature (JWS) in compact serialization with a key stored in the wallet.
+    ///
+    /// JWS protected header is {"alg":"EdDSA","kid":<kid>}, so only ed25519 keys can be used.
+    /// Signature is calculated over ASCII(BASE64URL(header) || '.' || BASE64URL(payload)).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// signer_vk: id (verkey) of my key. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+    /// payload_json: JSON payload to be signed
+    /// options_json: (optional) signing options:
+    /// {
+    ///     "kid": string, // Optional (if not set then signer_vk is used); Key id to put into JWS header, e.g. DID of the signer.
+    ///     "detached": bool, // Optional (false by default); If true then payload is omitted from JWS ("<header>..<signature>").
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// jws: JWS in compact serialization
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_crypto_sign_jws(indy_handle_t      command_handle,
+                                             indy_handle_t      wallet_handle,
+                                             const char *       signer_vk,
+                                             const char *       payload_json,
+                                             const char *       options_json,
+
+                                             void           (*cb)(indy_handle_t     xcommand_handle,
+                                                                  indy_error_t      err,
+                                                                  const char *const jws)
+                                            );
+
+    /// Verifies JSON Web Signature (JWS) in compact serialization created by indy_crypto_sign_jws.
+    ///
+    /// If signer_vk isn't provided the "kid" of JWS header is resolved to verkey through their or my DIDs
+    /// stored in the wallet (DID URL fragment like "#key-1" is ignored). If there is no such DID then "kid"
+    /// must be a verkey of key stored in the wallet. Unknown "kid" causes WalletItemNotFound error.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// jws: JWS in compact serialization
+    /// detached_payload_json: (optional) JSON payload for detached JWS ("<header>..<signature>")
+    /// signer_vk: (optional) expected signer verkey. If provided JWS is verified with this key and "kid" is ignored.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// valid: true - if signature is valid, false - otherwise
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_crypto_verify_jws(indy_handle_t      command_handle,
+                                               indy_handle_t      wallet_handle,
+                                               const char *       jws,
+                                               const char *       detached_payload_json,
+                                               const char *       signer_vk,
+
+                                               void           (*cb)(indy_handle_t xcommand_handle,
+                                                                    indy_error_t  err,
+                                                                    indy_bool_t   valid)
+                                              );
+
 #ifdef __cplusplus
 }
 #endif
@@ -22,8 +22,19 @@ extern "C" {
     ///             if provided, then keys will be replaced - key rotation use case)
     ///     "seed": string, (optional; if not provide then a random one will be created)
     ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-    ///               currently only 'ed25519' value is supported for this field)
+    ///               supported values are 'ed25519', 'secp256k1' and types registered by indy_register_crypto_type)
     ///     "cid": bool, (optional; if not set then false is used;)
+    ///     "method_name": string, (optional; if set then fully qualified DID did:<method_name>:<id> will be created;
+    ///               method name can contain lowercase letters and digits only)
+    ///     "external_key": {  (optional; if set then DID keys are held by external signer, seed and crypto_type can't be used)
+    ///         "signer": string, (name of external signer registered by indy_register_external_signer)
+    ///         "key_ref": string, (key reference that is passed to the signer handlers)
+    ///         "verkey": string, (ed25519 verkey of the key)
+    ///     }
+    ///     "derivation": {  (optional; if set then DID keys are derived from wallet master seed, seed and external_key can't be used)
+    ///         "master_seed_id": string, (id of master seed created by indy_create_master_seed)
+    ///         "path": string, (derivation path with hardened indexes only, e.g. "m/44'/0'/1'")
+    ///     }
     /// }
     /// cb: Callback that takes command result as parameter.
     ///
@@ -60,7 +71,7 @@ extern "C" {
     /// {
     ///     "seed": string, (optional; if not provide then a random one will be created)
     ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-    ///               currently only 'ed25519' value is supported for this field)
+    ///               supported values are 'ed25519', 'secp256k1' and types registered by indy_register_crypto_type)
     /// }
     /// cb: Callback that takes command result as parameter.
     ///
@@ -114,6 +125,56 @@ extern "C" {
                                                                      indy_error_t  err)
                                                );
 
+    /// Rotates the key of an existing DID (owned by the caller of the library) on the ledger.
+    ///
+    /// Runs the whole rotation sequence in one call: generates temporary key (as "indy_replace_keys_start"),
+    /// sends NYM transaction with the new verkey signed by the current key, checks with GET_NYM request
+    /// that the ledger has applied the new verkey and only then applies the key in the wallet
+    /// (as "indy_replace_keys_apply").
+    ///
+    /// If the ledger rejects the transaction the temporary key is discarded.
+    /// If the result is unknown (for example, on pool timeout or if GET_NYM doesn't confirm the new verkey)
+    /// the temporary key is kept in the wallet. In this case check the ledger and call
+    /// "indy_replace_keys_apply" if the new verkey has been written. The next rotation
+    /// for the DID isn't allowed until the previous one is completed.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// did: DID stored in the wallet and written to the ledger
+    /// key_json: Key information as json. Example:
+    /// {
+    ///     "seed": string, (optional; if not provide then a random one will be created)
+    ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
+    ///               supported values are 'ed25519', 'secp256k1' and types registered by indy_register_crypto_type)
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - xcommand_handle: Command handle to map callback to caller context.
+    /// - err: Error code.
+    ///   verkey: The DIDs new verification key
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    /// Crypto*
+
+    extern indy_error_t indy_rotate_did_key_on_ledger(indy_handle_t command_handle,
+                                                      indy_handle_t pool_handle,
+                                                      indy_handle_t wallet_handle,
+                                                      const char *  did,
+                                                      const char *  key_json,
+
+                                                      void           (*cb)(indy_handle_t xcommand_handle,
+                                                                           indy_error_t  err,
+                                                                           const char *const verkey)
+                                                     );
+
     /// Saves their DID for a pairwise connection in a secured Wallet,
     /// so that it can be used to verify transaction.
     ///
@@ -149,8 +210,8 @@ extern "C" {
     /// Returns ver key (key id) for the given DID.
     ///
     /// "indy_key_for_did" call follow the idea that we resolve information about their DID from
-    /// the ledger with cache in the local wallet. The "indy_open_wallet" call has freshness parameter
-    /// that is used for checking the freshness of cached pool value.
+    /// the ledger with cache in the local wallet. Cached value is used without freshness checking,
+    /// see "indy_key_for_did_with_options" to control cache behaviour.
     ///
     /// Note if you don't want to resolve their DID info from the ledger you can use
     /// "indy_key_for_local_did" call instead that will look only to the local wallet and skip
@@ -186,6 +247,88 @@ extern "C" {
                                                                  const char *const key)
                                         );
 
+    /// Returns ver key (key id) for the given DID with control of ledger cache behaviour.
+    ///
+    /// Works like "indy_key_for_did", but their DID info cached in the local wallet is used only
+    /// if it satisfies options. Otherwise it is fetched from the ledger again.
+    ///
+    /// Cache options affect their DIDs only. Their DIDs stored by "indy_store_their_did" call
+    /// are not resolved from the ledger, so "maxAge" isn't applied to them.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// pool_handle:   Pool handle (created by open_pool).
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// did - The DID to resolve key.
+    /// options_json: (optional) cache options json:
+    ///  {
+    ///     "noCache": (optional, false by default) skip usage of cache,
+    ///     "noStore": (optional, false by default) don't store fetched DID info in the wallet,
+    ///     "maxAge": (optional, no limit by default) max age of cached DID info in seconds,
+    ///  }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - xcommand_handle: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - key - The DIDs ver key (key id).
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_key_for_did_with_options(indy_handle_t     command_handle,
+                                                      indy_handle_t     pool_handle,
+                                                      indy_handle_t     wallet_handle,
+                                                      const char *const did,
+                                                      const char *const options_json,
+
+                                                      void              (*cb)(indy_handle_t     command_handle,
+                                                                              indy_error_t      err,
+                                                                              const char *const key)
+                                                     );
+
+    /// Forces resolving of their DID from the ledger.
+    ///
+    /// Fetches actual DID info from the ledger and replaces DID info stored in the wallet.
+    /// Use it when you know that DID owner has rotated the key.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// pool_handle:   Pool handle (created by open_pool).
+    /// did - Their DID to refresh.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - xcommand_handle: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - their_did_json - resolved DID info:
+    ///   {
+    ///     "did": string,
+    ///     "verkey": string,
+    ///     "resolved_at": int, // time of resolution in seconds since epoch
+    ///   }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    /// Crypto*
+    extern indy_error_t indy_refresh_their_did(indy_handle_t     command_handle,
+                                               indy_handle_t     wallet_handle,
+                                               indy_handle_t     pool_handle,
+                                               const char *const did,
+
+                                               void              (*cb)(indy_handle_t     command_handle,
+                                                                       indy_error_t      err,
+                                                                       const char *const their_did_json)
+                                              );
+
     /// Returns ver key (key id) for the given DID.
     ///
     /// "indy_key_for_local_did" call looks data stored in the local wallet only and skips freshness
@@ -284,6 +427,50 @@ extern "C" {
                                                                           const char *const transport_vk)
                                                  );
 
+    /// Returns endpoint information for the given DID with control of ledger cache behaviour.
+    ///
+    /// Works like "indy_get_endpoint_for_did", but endpoint cached in the local wallet is used only
+    /// if it satisfies options. Otherwise it is fetched from the ledger again.
+    /// Endpoints stored by "indy_set_endpoint_for_did" call are not resolved from the ledger,
+    /// so "maxAge" isn't applied to them.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// pool_handle:   Pool handle (created by open_pool).
+    /// did - The DID to resolve endpoint.
+    /// options_json: (optional) cache options json:
+    ///  {
+    ///     "noCache": (optional, false by default) skip usage of cache,
+    ///     "noStore": (optional, false by default) don't store fetched endpoint in the wallet,
+    ///     "maxAge": (optional, no limit by default) max age of cached endpoint in seconds,
+    ///  }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - xcommand_handle: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - endpoint - The DIDs endpoint.
+    /// - transport_vk - The DIDs transport key (ver key, key id).
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_get_endpoint_for_did_with_options(indy_handle_t     command_handle,
+                                                               indy_handle_t     wallet_handle,
+                                                               indy_handle_t     pool_handle,
+                                                               const char *const did,
+                                                               const char *const options_json,
+
+                                                               void              (*cb)(indy_handle_t     command_handle,
+                                                                                       indy_error_t      err,
+                                                                                       const char *const address,
+                                                                                       const char *const transport_vk)
+                                                              );
+
     /// Saves/replaces the meta information for the giving DID in the wallet.
     ///
     /// #Params
@@ -422,6 +609,85 @@ extern "C" {
                                                                  const char *const verkey)
                                             );
 
+    /// Resolves W3C DID Document for the given DID (did:sov method) from the ledger.
+    ///
+    /// DID Document is assembled from NYM transaction (verification key) and endpoint ATTRIB
+    /// transaction (service). Abbreviated verkey is expanded to the full one.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// pool_handle: Pool handle (created by open_pool).
+    /// did: DID to resolve. Both "did:sov:<did>" and "<did>" forms are accepted.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - xcommand_handle: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - did_doc_json: DID Document json:
+    /// {
+    ///     "@context": "https://w3id.org/did/v1",
+    ///     "id": "did:sov:<did>",
+    ///     "publicKey": [{
+    ///         "id": "did:sov:<did>#key-1",
+    ///         "type": "Ed25519VerificationKey2018",
+    ///         "controller": "did:sov:<did>",
+    ///         "publicKeyBase58": string, // full verkey
+    ///     }],
+    ///     "authentication": [{
+    ///         "type": "Ed25519SignatureAuthentication2018",
+    ///         "publicKey": "did:sov:<did>#key-1"
+    ///     }],
+    ///     "service": [{ // empty if no endpoint is set on the ledger
+    ///         "id": "did:sov:<did>;indy",
+    ///         "type": "IndyAgent",
+    ///         "serviceEndpoint": string, // endpoint address
+    ///         "recipientKeys": [string], // endpoint transport key if set
+    ///     }]
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+    extern indy_error_t indy_resolve_did_document(indy_handle_t command_handle,
+                                                  indy_handle_t pool_handle,
+                                                  const char *const did,
+                                                  void          (*fn)(indy_handle_t xcommand_handle,
+                                                                      indy_error_t err,
+                                                                      const char *const did_doc_json)
+                                                 );
+
+    /// Builds fully qualified DID did:<method_name>:<id> for the given DID.
+    ///
+    /// Qualified DIDs can be used in all DID, pairwise and ledger functions. Requests sent to the ledger
+    /// always contain unqualified DIDs.
+    /// Note that wallet records are not changed by this call: DID stored in the wallet in unqualified form
+    /// stays accessible by unqualified DID only.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// did: DID to qualify. If DID is already qualified then its method is replaced.
+    /// method_name: DID method name (lowercase letters and digits), for example "sov".
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - xcommand_handle: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - full_qualified_did: did:<method_name>:<id>
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_qualify_did(indy_handle_t command_handle,
+                                         const char *const did,
+                                         const char *const method_name,
+                                         void          (*fn)(indy_handle_t xcommand_handle,
+                                                             indy_error_t err,
+                                                             const char *const full_qualified_did)
+                                        );
+
 #ifdef __cplusplus
 }
 #endif
@@ -42,6 +42,57 @@ extern "C" {
                                                                           const char*   request_result_json)
                                                      );
     
+    /// Signs and submits request message to validator pool paying fees for it.
+    ///
+    /// Gets fees schedule from the ledger (it's cached per pool handle and payment method for a minute,
+    /// until the pool is closed or new fees are built with indy_build_set_txn_fees_req),
+    /// computes the fee of the request with payment method "get_request_fee" handler
+    /// (or takes flat fee of the request txn type from the schedule if payment method doesn't provide it),
+    /// selects inputs from UTXOs of the wallet payment addresses to cover them (see indy_prepare_payment),
+    /// adds fees info to the request (see indy_add_request_fees), signs and sends the request to validator pool.
+    /// If the txn type has no fees the request is sent as is.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// submitter_did: Id of Identity stored in secured Wallet.
+    /// request_json: Request data json.
+    /// payment_method: payment method to pay fees with.
+    /// change_address: payment address to send change to.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// request_result_json: Request result as json.
+    /// receipts_json: The list of UTXO created by fees transaction (see indy_parse_response_with_fees):
+    ///   [{
+    ///      input: <str>, // UTXO input
+    ///      amount: <int>, // amount of tokens in this input
+    ///      extra: <str>, // optional data from payment transaction
+    ///   }]
+    ///   Empty list if no fees were paid.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    /// Crypto*
+    /// Payment*
+    
+    extern indy_error_t indy_sign_and_submit_request_with_fees(indy_handle_t command_handle,
+                                                               indy_handle_t pool_handle,
+                                                               indy_handle_t wallet_handle,
+                                                               const char *  submitter_did,
+                                                               const char *  request_json,
+                                                               const char *  payment_method,
+                                                               const char *  change_address,
+
+                                                               void           (*cb)(indy_handle_t xcommand_handle,
+                                                                                    indy_error_t  err,
+                                                                                    const char*   request_result_json,
+                                                                                    const char*   receipts_json)
+                                                               );
+    
     /// Publishes request message to validator pool (no signing, unlike sign_and_submit_request).
     ///
     /// The request is sent to the validator pool as is. It's assumed that it's already prepared.
@@ -52,6 +52,27 @@ typedef enum
     // IO Error
     CommonIOError = 114,
 
+    // Caller passed invalid value as param 13 (null, invalid json and etc..)
+    CommonInvalidParam13 = 115,
+
+    // Caller passed invalid value as param 14 (null, invalid json and etc..)
+    CommonInvalidParam14 = 116,
+
+    // Caller passed invalid value as param 15 (null, invalid json and etc..)
+    CommonInvalidParam15 = 117,
+
+    // Caller passed invalid value as param 16 (null, invalid json and etc..)
+    CommonInvalidParam16 = 118,
+
+    // Caller passed invalid value as param 17 (null, invalid json and etc..)
+    CommonInvalidParam17 = 119,
+
+    // Caller passed invalid value as param 18 (null, invalid json and etc..)
+    CommonInvalidParam18 = 120,
+
+    // Caller passed invalid value as param 19 (null, invalid json and etc..)
+    CommonInvalidParam19 = 121,
+
     // Wallet errors
     // Caller passed invalid wallet handle
     WalletInvalidHandle = 200,
@@ -98,6 +119,9 @@ typedef enum
     // Returned if provided wallet query is invalid
     WalletQueryError = 214,
 
+    // Returned if wallet record was changed since the expected version
+    WalletConflict = 215,
+
     // Ledger errors
     // Trying to open pool ledger that wasn't created before
     PoolLedgerNotCreatedError = 300,
@@ -142,6 +166,12 @@ typedef enum
     // Unknown format of DID entity keys
     UnknownCryptoTypeError = 500,
 
+    // Attempt to register crypto type with name used for another registered crypto type
+    CryptoTypeAlreadyRegisteredError = 501,
+
+    // Attempt to register external signer with name used for another registered external signer
+    ExternalSignerAlreadyRegisteredError = 502,
+
     // Attempt to create duplicate did
     DidAlreadyExistsError = 600,
 
@@ -152,9 +182,44 @@ typedef enum
     PaymentIncompatibleMethodsError = 701,
 
     // Insufficient funds on inputs
-    PaymentInsufficientFundsError = 702
+    PaymentInsufficientFundsError = 702,
+
+    // Unknown payment source (receipt or input) was given
+    PaymentSourceDoesNotExistError = 703,
+
+    // Payment method doesn't support requested operation
+    PaymentOperationNotSupportedError = 704,
+
+    // Inputs and outputs of request belong to several payment methods
+    // Offending entries can be obtained with indy_get_current_error_details
+    PaymentMixedMethodsError = 705
 
 } indy_error_t;
 
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+    /// Gets structured details of the last error returned by libindy in the current thread.
+    ///
+    /// Details are set for errors that have them (PaymentMixedMethodsError for now) and reset
+    /// by every successful result. Libindy calls callbacks in its own thread, so this function
+    /// must be called inside of the callback to get details of the error passed to it.
+    ///
+    /// #Params
+    /// error_details_json_p: Reference that will contain details json or null if last error has no details.
+    ///   The pointer is valid until next call of libindy in the same thread.
+    ///   For PaymentMixedMethodsError:
+    ///   {
+    ///     inputs: {<payment method>: [<input>, ...], ...}, // inputs grouped by payment method
+    ///     outputs: {<payment method>: [<payment address>, ...], ...}, // output addresses grouped by payment method
+    ///   }
+
+    extern void indy_get_current_error_details(const char ** error_details_json_p);
+
+#ifdef __cplusplus
+}
+#endif
+
 #endif
 
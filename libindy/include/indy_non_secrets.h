@@ -80,6 +80,76 @@ extern "C" {
                                                                             indy_error_t err)
                                                       );
 
+    /// Update a non-secret wallet record value only if the record wasn't changed
+    /// since the expected version (see retrieveVersion option of indy_get_wallet_record)
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// type_: allows to separate different record types collections
+    /// id: the id of record
+    /// value: the new value of record
+    /// expected_version: the record version the new value is based on
+    ///
+    /// #Errors
+    /// WalletConflict if the record was changed since the expected version
+
+    extern indy_error_t indy_update_wallet_record_value_with_version(indy_handle_t  command_handle,
+                                                                     indy_handle_t  wallet_handle,
+                                                                     const char*    type_,
+                                                                     const char*    id,
+                                                                     const char*    value,
+                                                                     long long      expected_version,
+                                                                     void           (*fn)(indy_handle_t xcommand_handle,
+                                                                                          indy_error_t err)
+                                                                    );
+
+    /// Update a non-secret wallet record tags only if the record wasn't changed
+    /// since the expected version (see retrieveVersion option of indy_get_wallet_record)
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// type_: allows to separate different record types collections
+    /// id: the id of record
+    /// tags_json: the record tags json (see indy_update_wallet_record_tags)
+    /// expected_version: the record version the new tags are based on
+    ///
+    /// #Errors
+    /// WalletConflict if the record was changed since the expected version
+
+    extern indy_error_t indy_update_wallet_record_tags_with_version(indy_handle_t  command_handle,
+                                                                    indy_handle_t  wallet_handle,
+                                                                    const char*    type_,
+                                                                    const char*    id,
+                                                                    const char*    tags_json,
+                                                                    long long      expected_version,
+                                                                    void           (*fn)(indy_handle_t xcommand_handle,
+                                                                                         indy_error_t err)
+                                                                   );
+
+    /// Set or remove a non-secret wallet record expiry time
+    ///
+    /// Expired record isn't returned by get and search calls and will be deleted
+    /// on next wallet opening or indy_wallet_purge_expired call.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// type_: allows to separate different record types collections
+    /// id: the id of record
+    /// expires_at: unix timestamp in seconds after that record expires
+    ///             or -1 to remove record expiry
+
+    extern indy_error_t indy_update_wallet_record_expiry(indy_handle_t  command_handle,
+                                                         indy_handle_t  wallet_handle,
+                                                         const char*    type_,
+                                                         const char*    id,
+                                                         long long      expires_at,
+                                                         void           (*fn)(indy_handle_t xcommand_handle,
+                                                                              indy_error_t err)
+                                                        );
+
     /// Add new tags to the wallet record
     ///
     /// #Params
@@ -155,7 +225,8 @@ extern "C" {
     ///  {
     ///    retrieveType: (optional, false by default) Retrieve record type,
     ///    retrieveValue: (optional, true by default) Retrieve record value,
-    ///    retrieveTags: (optional, true by default) Retrieve record tags
+    ///    retrieveTags: (optional, true by default) Retrieve record tags,
+    ///    retrieveVersion: (optional, false by default) Retrieve record version
     ///  }
     /// #Returns
     /// wallet record json:
@@ -164,6 +235,7 @@ extern "C" {
     ///   type: "Some type", // present only if retrieveType set to true
     ///   value: "Some value", // present only if retrieveValue set to true
     ///   tags: <tags json>, // present only if retrieveTags set to true
+    ///   version: <int>, // present only if retrieveVersion set to true
     /// }
 
     extern indy_error_t indy_get_wallet_record(indy_handle_t  command_handle,
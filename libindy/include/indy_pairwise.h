@@ -94,6 +94,11 @@ extern "C" {
     ///
     /// #Returns
     /// pairwise_info_json: did info associated with their did
+    /// {
+    ///     "my_did": string, // current my DID
+    ///     "my_dids": [string], // all my DIDs used in this pairwise, present only if more than one my DID was used
+    ///     "metadata": string, // present only if set
+    /// }
     ///
     /// #Errors
     /// Common*
@@ -135,6 +140,148 @@ extern "C" {
                                                   );
 
 
+    /// Adds my DID to the pairwise associated with their DID and makes it the current one.
+    /// Previously used my DIDs stay in the pairwise "my_dids" list, so rotated connection DIDs can be tracked.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// command_handle: command handle to map callback to user context.
+    /// their_did: encoded Did
+    /// my_did: encoded Did stored in the wallet
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_add_pairwise_my_did(indy_handle_t command_handle,
+                                                 indy_handle_t wallet_handle,
+                                                 const char *  their_did,
+                                                 const char *  my_did,
+
+                                                 void          (*cb)(indy_handle_t  xcommand_handle,
+                                                                     indy_error_t   err)
+                                                );
+
+
+    /// Deletes pairwise associated with their DID.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// command_handle: command handle to map callback to user context.
+    /// their_did: encoded Did
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_delete_pairwise(indy_handle_t command_handle,
+                                             indy_handle_t wallet_handle,
+                                             const char *  their_did,
+
+                                             void          (*cb)(indy_handle_t  xcommand_handle,
+                                                                 indy_error_t   err)
+                                            );
+
+
+    /// Search for pairwise stored in the wallet.
+    ///
+    /// Pairwise records created by older versions of libindy get their tags when the wallet is opened in read-write mode.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// command_handle: command handle to map callback to user context.
+    /// query_json: Wql query filter for pairwise searching based on tags:
+    /// {
+    ///     "my_did": string, // current my DID
+    ///     "their_did": string,
+    ///     "metadata": string, // exact metadata value
+    /// }
+    /// See wallet search wql documentation for query syntax.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// search_handle: Search handle that can be used later to fetch records by small batches
+    ///     (with indy_fetch_pairwise_search_next_records)
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_search_pairwise(indy_handle_t command_handle,
+                                             indy_handle_t wallet_handle,
+                                             const char *  query_json,
+
+                                             void          (*cb)(indy_handle_t  xcommand_handle,
+                                                                 indy_error_t   err,
+                                                                 indy_handle_t  search_handle)
+                                            );
+
+
+    /// Fetch next pairwise records for pairwise search.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// search_handle: pairwise search handle (created by indy_search_pairwise)
+    /// count: Count of records to fetch
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// pairwise records json:
+    /// {
+    ///   totalCount: <int>,
+    ///   records: [{ // not present if there are no more records
+    ///       my_did: string, // current my DID
+    ///       their_did: string,
+    ///       my_dids: [string], // all my DIDs used in this pairwise
+    ///       metadata: string, // present only if set
+    ///   }],
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_fetch_pairwise_search_next_records(indy_handle_t command_handle,
+                                                                indy_handle_t search_handle,
+                                                                indy_u32_t    count,
+
+                                                                void          (*cb)(indy_handle_t  xcommand_handle,
+                                                                                    indy_error_t   err,
+                                                                                    const char*    pairwise_json)
+                                                               );
+
+
+    /// Close pairwise search (make search handle invalid)
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// search_handle: pairwise search handle (created by indy_search_pairwise)
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_close_pairwise_search(indy_handle_t command_handle,
+                                                   indy_handle_t search_handle,
+
+                                                   void          (*cb)(indy_handle_t  xcommand_handle,
+                                                                       indy_error_t   err)
+                                                  );
+
+
+
 #ifdef __cplusplus
 }
 #endif
@@ -11,6 +11,7 @@ extern "C" {
     /// Register custom payment implementation.
     ///
     /// It allows library user to provide custom payment method implementation as set of handlers.
+    /// Use indy_register_payment_method_ex to register optional handlers too.
     ///
     /// #Params
     /// command_handle: Command handle to map callback to caller context.
@@ -54,6 +55,100 @@ extern "C" {
                                                      void           (*fn)(indy_handle_t xcommand_handle, indy_error_t err)
                                                      );
 
+    /// Register custom payment implementation with optional handlers.
+    ///
+    /// It allows library user to provide custom payment method implementation as set of handlers.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// payment_method: The type of payment method also used as sub-prefix for fully resolvable payment address format ("sov" - for example)
+    /// create_payment_address: "create_payment_address" operation handler
+    /// add_request_fees: "add_request_fees" operation handler
+    /// build_get_utxo_request: "build_get_utxo_request" operation handler
+    /// parse_get_utxo_response: "parse_get_utxo_response" operation handler
+    /// build_payment_req: "build_payment_req" operation handler
+    /// build_mint_req: "build_mint_req" operation handler
+    /// build_verify_payment_req: optional "build_verify_payment_req" operation handler
+    /// parse_verify_payment_response: optional "parse_verify_payment_response" operation handler
+    ///   Both handlers should be passed to support payment verification, NULL for both otherwise
+    /// sign_with_address: optional "sign_with_address" operation handler
+    /// verify_with_address: optional "verify_with_address" operation handler
+    ///   Both handlers should be passed to support signing with payment address, NULL for both otherwise
+    /// get_request_fee: optional "get_request_fee" operation handler
+    ///   If NULL the fee of request is taken from fees schedule by txn type, so schedule must contain flat amounts only
+    ///
+    /// #Returns
+    /// Error code
+
+    extern indy_error_t indy_register_payment_method_ex(indy_handle_t  command_handle,
+                                                        const char*    payment_method,
+                                                        indy_error_t (*createPaymentAddress)(const char* config,
+                                                                                             const char ** const payment_address_ptr),
+                                                        indy_error_t (*addRequestFees)(const char* req_json,
+                                                                                       const char* inputs_json,
+                                                                                       const char* outputs_json,
+                                                                                       const char ** const req_with_fees_ptr),
+                                                        indy_error_t (*parseResponseWithFees)(const char* resp_json,
+                                                                                              const char ** const utxo_ptr),
+                                                        indy_error_t (*buildGetUtxoRequest)(const char* payment_address,
+                                                                                            const char ** const get_utxo_txn_ptr),
+                                                        indy_error_t (*parseGetUtxoResponse)(const char* resp_json,
+                                                                                             const char ** const utxo_ptr),
+                                                        indy_error_t (*buildPaymentReq)(const char* inputs_json,
+                                                                                        const char* outputs_json,
+                                                                                        const char ** const payment_req_ptr),
+                                                        indy_error_t (*parsePaymentResponse)(const char* resp_json,
+                                                                                             const char ** const utxo_ptr),
+                                                        indy_error_t (*buildMintReq)(const char* outputs_json,
+                                                                                     const char ** const mint_req_ptr),
+                                                        indy_error_t (*buildSetTxnFeesReq)(const char* fees_json,
+                                                                                           const char ** const set_txn_fees_ptr),
+                                                        indy_error_t (*buildGetTxnFeesReq)(const char ** const get_txn_fees_ptr),
+                                                        indy_error_t (*parseGetTxnFeesResponse)(const char* resp_json,
+                                                                                                const char ** const fees_ptr),
+                                                        indy_error_t (*buildVerifyPaymentReq)(const char* receipt,
+                                                                                              const char ** const verify_txn_ptr),
+                                                        indy_error_t (*parseVerifyPaymentResponse)(const char* resp_json,
+                                                                                                   const char ** const txn_ptr),
+                                                        indy_error_t (*signWithAddress)(const char* address,
+                                                                                        const indy_u8_t* message_raw,
+                                                                                        indy_u32_t message_len,
+                                                                                        const indy_u8_t ** const signature_raw_ptr,
+                                                                                        indy_u32_t * const signature_len_ptr),
+                                                        indy_error_t (*verifyWithAddress)(const char* address,
+                                                                                          const indy_u8_t* message_raw,
+                                                                                          indy_u32_t message_len,
+                                                                                          const indy_u8_t* signature_raw,
+                                                                                          indy_u32_t signature_len,
+                                                                                          indy_bool_t * const valid_ptr),
+                                                        indy_error_t (*getRequestFee)(const char* submitter_did,
+                                                                                      const char* req_json,
+                                                                                      const char* fees_json,
+                                                                                      indy_u64_t * const fee_ptr),
+                                                        void           (*fn)(indy_handle_t xcommand_handle, indy_error_t err)
+                                                        );
+
+    /// Lists payment methods registered in libindy with operations each of them supports.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to context
+    ///
+    /// #Returns
+    /// payment_methods_json - json array of registered payment methods ordered by name:
+    ///   [{
+    ///     method: <str>, // name of payment method
+    ///     capabilities: [<str>, ...], // names of operations payment method supports, for example
+    ///                                 // "build_verify_payment_req", "parse_verify_payment_response",
+    ///                                 // "sign_with_address", "verify_with_address", "get_request_fee" for optional ones
+    ///   }]
+
+    extern indy_error_t indy_list_payment_methods(indy_handle_t command_handle,
+
+                                                  void           (*cb)(indy_handle_t xcommand_handle,
+                                                                       indy_error_t  err,
+                                                                       const char*   payment_methods_json)
+                                                  );
+
     /// Create the payment address for specified payment method
     ///
     ///
@@ -228,6 +323,10 @@ extern "C" {
     /// Format of inputs is specific for payment method. Usually it should reference payment transaction
     /// with at least one output that corresponds to payment address that user owns.
     ///
+    /// All inputs and outputs must belong to the same payment method, otherwise PaymentMixedMethodsError
+    /// is returned and offending entries can be obtained with indy_get_current_error_details inside of callback.
+    /// Use indy_build_split_payment_req to build one request per payment method instead.
+    ///
     /// #Params
     /// wallet_handle: wallet handle where keys for signature are stored
     /// inputs_json: The list of UTXO inputs as json array:
@@ -256,6 +355,43 @@ extern "C" {
                                                                     const char*   payment_method)
                                                );
 
+    /// Builds Indy requests for doing tokens payment with inputs and outputs of several payment methods.
+    ///
+    /// Inputs and outputs are split by payment method and request for each part is built
+    /// in the same way as indy_build_payment_req does. Each payment method should have both
+    /// inputs and outputs, otherwise PaymentMixedMethodsError is returned with details of
+    /// the offending entries available through indy_get_current_error_details.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle where keys for signature are stored
+    /// inputs_json: The list of UTXO inputs as json array:
+    ///   ["input1", ...]
+    ///   Note that each input should reference paymentAddress
+    /// outputs_json: The list of UTXO outputs as json array:
+    ///   [{
+    ///     paymentAddress: <str>, // payment address used as output
+    ///     amount: <int>, // amount of tokens to transfer to this payment address
+    ///     extra: <str>, // optional data
+    ///   }]
+    ///
+    /// #Returns
+    /// payment_reqs_json - Indy requests for doing tokens payment ordered by payment method:
+    ///   [{
+    ///     paymentMethod: <str>, // payment method to use for submitting and parsing response of request
+    ///     request: <str>, // Indy request for doing tokens payment of this payment method
+    ///   }]
+
+    extern indy_error_t indy_build_split_payment_req(indy_handle_t command_handle,
+                                                     indy_handle_t wallet_handle,
+                                                     const char *  submitter_did,
+                                                     const char *  inputs_json,
+                                                     const char *  outputs_json,
+
+                                                     void           (*cb)(indy_handle_t xcommand_handle,
+                                                                          indy_error_t  err,
+                                                                          const char*   payment_reqs_json)
+                                                     );
+
     /// Parses response for Indy request for payment txn.
     ///
     /// #Params
@@ -281,6 +417,123 @@ extern "C" {
                                                                          const char*   utxo_json)
                                                     );
 
+    /// Parses response for Indy request for payment txn and records
+    /// the resulting payments in the wallet payment history.
+    ///
+    /// History is recorded only if the payment method parsed the response successfully.
+    /// Each UTXO the payment method parsed from the response becomes a history entry: UTXOs of
+    /// addresses owned by the wallet are recorded as "received" (change included), all other
+    /// UTXOs are recorded as "sent". UTXOs without paymentAddress can't be attributed and are skipped.
+    ///
+    /// Entries are identified by payment method and UTXO, so parsing the same response again doesn't
+    /// record the payment twice. The payment has already happened on the ledger, so utxo_json is returned
+    /// even if history couldn't be recorded; parsing the response again records the missing entries.
+    ///
+    /// #Params
+    /// command_handle
+    /// wallet_handle: wallet handle where to record payment history
+    /// payment_method
+    /// resp_json: response for Indy request for payment txn
+    /// history_json: context of the payment as json:
+    ///   {
+    ///     submitterDid: <str>, // DID of request sender
+    ///     txnType: <str>, // optional type of the transaction
+    ///   }
+    ///   Amounts and addresses are taken from the parsed response, ledger sequence number from resp_json.
+    ///
+    /// #Returns
+    /// utxo_json - parsed (payment method and node version agnostic) utxo info as json:
+    ///   [{
+    ///      input: <str>, // UTXO input
+    ///      amount: <int>, // amount of tokens in this input
+    ///      extra: <str>, // optional data from payment transaction
+    ///      paymentAddress: <str>, // payment address of the UTXO, required to record it in history
+    ///   }]
+
+    extern indy_error_t indy_parse_payment_response_with_history(indy_handle_t command_handle,
+                                                                 indy_handle_t wallet_handle,
+                                                                 const char *  payment_method,
+                                                                 const char *  resp_json,
+                                                                 const char *  history_json,
+
+                                                                 void           (*cb)(indy_handle_t xcommand_handle,
+                                                                                      indy_error_t  err,
+                                                                                      const char*   utxo_json)
+                                                                 );
+
+    /// Parses response for Indy request with fees and records
+    /// the resulting payments in the wallet payment history.
+    ///
+    /// See indy_parse_payment_response_with_history for the way history entries are built.
+    ///
+    /// #Params
+    /// command_handle
+    /// wallet_handle: wallet handle where to record payment history
+    /// payment_method
+    /// resp_json: response for Indy request with fees
+    /// history_json: context of the payment as json (see indy_parse_payment_response_with_history)
+    ///
+    /// #Returns
+    /// utxo_json - parsed (payment method and node version agnostic) utxo info as json:
+    ///   [{
+    ///      input: <str>, // UTXO input
+    ///      amount: <int>, // amount of tokens in this input
+    ///      extra: <str>, // optional data from payment transaction
+    ///      paymentAddress: <str>, // payment address of the UTXO, required to record it in history
+    ///   }]
+
+    extern indy_error_t indy_parse_response_with_fees_with_history(indy_handle_t command_handle,
+                                                                   indy_handle_t wallet_handle,
+                                                                   const char *  payment_method,
+                                                                   const char *  resp_json,
+                                                                   const char *  history_json,
+
+                                                                   void           (*cb)(indy_handle_t xcommand_handle,
+                                                                                        indy_error_t  err,
+                                                                                        const char*   utxo_json)
+                                                                   );
+
+    /// Searches payment history recorded in the wallet.
+    ///
+    /// #Params
+    /// command_handle
+    /// wallet_handle: wallet handle
+    /// query_json: Wallet query (WQL) over payment history tags:
+    ///   {
+    ///     "paymentMethod": <str>,
+    ///     "direction": "sent" | "received",
+    ///     "input": <str>,
+    ///     "paymentAddress": <str>,
+    ///     "txnType": <str>,
+    ///     "seqNo": <str>,
+    ///     "amount": <str>,
+    ///     "submitterDid": <str>,
+    ///   }
+    ///   Use "{}" to get the whole history.
+    ///
+    /// #Returns
+    /// history_json - list of payment history entries:
+    ///   [{
+    ///      paymentMethod: <str>,
+    ///      direction: <str>,
+    ///      input: <str>, // UTXO created by the payment
+    ///      paymentAddress: <str>, // payment address of the UTXO
+    ///      amount: <int>,
+    ///      txnType: <str>, // optional
+    ///      seqNo: <int>, // optional
+    ///      submitterDid: <str>,
+    ///      extra: <str>, // optional
+    ///   }]
+
+    extern indy_error_t indy_search_payment_history(indy_handle_t command_handle,
+                                                    indy_handle_t wallet_handle,
+                                                    const char *  query_json,
+
+                                                    void           (*cb)(indy_handle_t xcommand_handle,
+                                                                         indy_error_t  err,
+                                                                         const char*   history_json)
+                                                    );
+
     /// Builds Indy request for doing tokens minting
     /// according to this payment method.
     ///
@@ -320,6 +573,7 @@ extern "C" {
     ///   .................
     ///   txnTypeN: amountN,
     /// }
+    ///   Payment method may support its own fee rules object in place of amount.
     /// # Return
     /// set_txn_fees_json - Indy request for setting fees for transactions in the ledger
 
@@ -368,6 +622,7 @@ extern "C" {
     ///   .................
     ///   txnTypeN: amountN,
     /// }
+    ///   Payment method may support its own fee rules object in place of amount.
 
     extern indy_error_t indy_parse_get_txn_fees_response(indy_handle_t command_handle,
                                                          const char *  payment_method,
@@ -378,6 +633,167 @@ extern "C" {
                                                                               const char*   fees_json)
                                                          );
 
+    /// Builds Indy request for information to verify the payment receipt
+    ///
+    /// # Params
+    /// command_handle
+    /// wallet_handle: wallet handle
+    /// submitter_did : DID of request sender
+    /// receipt: payment receipt to verify
+    ///   Note: this param will be used to determine payment_method
+    ///
+    /// # Return
+    /// verify_txn_json: Indy request for verification receipt
+    /// payment_method: used payment method
+
+    extern indy_error_t indy_build_verify_payment_req(indy_handle_t command_handle,
+                                                      indy_handle_t wallet_handle,
+                                                      const char *  submitter_did,
+                                                      const char *  receipt,
+
+                                                      void           (*cb)(indy_handle_t xcommand_handle,
+                                                                           indy_error_t  err,
+                                                                           const char*   verify_txn_json,
+                                                                           const char*   payment_method)
+                                                      );
+
+    /// Parses Indy response with information to verify receipt
+    ///
+    /// # Params
+    /// command_handle
+    /// payment_method: payment method to use
+    /// resp_json: response of the ledger for verify txn
+    ///
+    /// # Return
+    /// txn_json: {
+    ///     sources: [<str>, ]
+    ///     receipts: [ {
+    ///         recipient: <str>, // payment address of recipient
+    ///         receipt: <str>, // receipt that can be used for payment referencing and verification
+    ///         amount: <int>, // amount
+    ///         extra: <str>, // optional data from payment transaction
+    ///     }, ]
+    ///     extra: <str>, //optional data
+    /// }
+
+    extern indy_error_t indy_parse_verify_payment_response(indy_handle_t command_handle,
+                                                           const char *  payment_method,
+                                                           const char *  resp_json,
+
+                                                           void           (*cb)(indy_handle_t xcommand_handle,
+                                                                                indy_error_t  err,
+                                                                                const char*   txn_json)
+                                                           );
+
+    /// Signs a message with the key of payment address.
+    ///
+    /// Allows to prove control of the payment address (for example, by signing a challenge)
+    /// without moving tokens. Payment method is resolved from the address.
+    ///
+    /// # Params
+    /// command_handle
+    /// wallet_handle: wallet handle where keys of payment address are stored
+    /// address: payment address to sign with
+    /// message_raw: a pointer to first byte of message to be signed
+    /// message_len: a message length
+    ///
+    /// # Return
+    /// signature_raw: a pointer to first byte of signature
+    /// signature_len: a signature length
+
+    extern indy_error_t indy_sign_with_address(indy_handle_t      command_handle,
+                                               indy_handle_t      wallet_handle,
+                                               const char *       address,
+                                               const indy_u8_t *  message_raw,
+                                               indy_u32_t         message_len,
+
+                                               void               (*cb)(indy_handle_t     xcommand_handle,
+                                                                        indy_error_t      err,
+                                                                        const indy_u8_t*  signature_raw,
+                                                                        indy_u32_t        signature_len)
+                                               );
+
+    /// Verifies a message signature made with the key of payment address.
+    ///
+    /// Payment method is resolved from the address.
+    ///
+    /// # Params
+    /// command_handle
+    /// address: payment address of the signer
+    /// message_raw: a pointer to first byte of message that has been signed
+    /// message_len: a message length
+    /// signature_raw: a pointer to first byte of signature to be verified
+    /// signature_len: a signature length
+    ///
+    /// # Return
+    /// valid: true - if signature is valid, false - otherwise
+
+    extern indy_error_t indy_verify_with_address(indy_handle_t      command_handle,
+                                                 const char *       address,
+                                                 const indy_u8_t *  message_raw,
+                                                 indy_u32_t         message_len,
+                                                 const indy_u8_t *  signature_raw,
+                                                 indy_u32_t         signature_len,
+
+                                                 void               (*cb)(indy_handle_t xcommand_handle,
+                                                                          indy_error_t  err,
+                                                                          indy_bool_t   valid)
+                                                 );
+
+    /// Prepares inputs and change output for a payment of the given amount.
+    /// Collects UTXO of all payment addresses of the payment method stored in the wallet
+    /// and selects inputs enough to cover amount and fees.
+    ///
+    /// Note that this function sends GET_UTXO requests to the ledger for each payment address.
+    ///
+    /// # Params
+    /// command_handle
+    /// wallet_handle: wallet handle
+    /// pool_handle: pool handle (created by open_pool_ledger)
+    /// submitter_did : DID of request sender
+    /// payment_method: payment method to use
+    /// amount: amount of tokens to pay (without fees)
+    /// fees_json: fees schedule as json object (for example the result of parse_get_txn_fees_response).
+    ///   Only the fee of the payment txn type is covered in addition to amount:
+    ///   {
+    ///     txnType: <int>, // amount of tokens for txn type
+    ///   }
+    ///   The fee of the payment txn type must be flat amount: fee rules (per role, per request size)
+    ///   depend on the payment request, so PaymentOperationNotSupportedError is returned for them.
+    /// change_address: payment address to send change to
+    /// config_json: (optional) preparation config:
+    ///   {
+    ///     strategy: <str>, // "largest_first" (default) or "smallest_sufficient"
+    ///     txnType: <str>, // (optional) txn type of the payment, its fee is taken from fees_json (0 if absent).
+    ///                     // Required if fees_json contains several entries.
+    ///   }
+    ///
+    /// # Return
+    /// inputs_json: The list of UTXO inputs as json array:
+    ///   ["input1", ...]
+    /// outputs_json: The change output as json array (empty if there is no change):
+    ///   [{
+    ///     paymentAddress: <str>, // change address
+    ///     amount: <int>, // amount of change tokens
+    ///   }]
+    ///   Caller has to add own payment outputs totalling amount.
+
+    extern indy_error_t indy_prepare_payment(indy_handle_t command_handle,
+                                             indy_handle_t wallet_handle,
+                                             indy_handle_t pool_handle,
+                                             const char *  submitter_did,
+                                             const char *  payment_method,
+                                             indy_u64_t    amount,
+                                             const char *  fees_json,
+                                             const char *  change_address,
+                                             const char *  config_json,
+
+                                             void           (*cb)(indy_handle_t xcommand_handle,
+                                                                  indy_error_t  err,
+                                                                  const char*   inputs_json,
+                                                                  const char*   outputs_json)
+                                             );
+
 #ifdef __cplusplus
 }
 #endif
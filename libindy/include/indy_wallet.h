@@ -161,6 +161,102 @@ extern "C" {
                                            void           (*fn)(indy_handle_t xcommand_handle, indy_error_t err)
                                           );
 
+    /// Returns wallet change log entries recorded after the given sequence number.
+    ///
+    /// Change log must be enabled by "change_log" option of runtime config passed to indy_open_wallet
+    /// at least once. It is supported only by default wallet storage. Changes made while the wallet was opened
+    /// without "change_log" option aren't recorded: such periods start with "log_disabled" entry
+    /// and end with "log_enabled" one.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet.
+    /// seq: sequence number of the last known change (0 to get all changes).
+    ///
+    /// #Returns
+    /// changes_json: list of changes ordered by sequence number:
+    ///   [{
+    ///       "seq": int, // monotonically increasing sequence number
+    ///       "type": Option<string>, // record type, null for "log_enabled" and "log_disabled" entries
+    ///       "idHash": Option<string>, // hex encoded SHA-256 hash of UTF-8 record id, null for "log_enabled" and "log_disabled" entries
+    ///       "operation": string, // one of "add", "update", "add_tags", "update_tags", "delete_tags", "delete",
+    ///                            // "update_expiry", "expire", "quarantine", "log_enabled", "log_disabled"
+    ///       "timestamp": int, // time of change as unix timestamp
+    ///   }]
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_wallet_get_changes_since(indy_handle_t  command_handle,
+                                                      indy_handle_t  wallet_handle,
+                                                      long long      seq,
+                                                      void           (*fn)(indy_handle_t xcommand_handle,
+                                                                           indy_error_t err,
+                                                                           const char*   changes_json)
+                                                     );
+
+    /// Deletes all wallet records with expiry time in the past.
+    ///
+    /// Expired records are never returned by get and search calls, but stay in the storage
+    /// until they are purged. Default wallet storage also purges them on every opening.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet.
+    ///
+    /// #Returns
+    /// purged_count: number of deleted records
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_wallet_purge_expired(indy_handle_t  command_handle,
+                                                  indy_handle_t  wallet_handle,
+                                                  void           (*fn)(indy_handle_t xcommand_handle,
+                                                                       indy_error_t err,
+                                                                       indy_i32_t   purged_count)
+                                                 );
+
+    /// Verifies integrity of all wallet records.
+    ///
+    /// Every record is decrypted with wallet keys and HMAC of its type, id and tags is checked,
+    /// so records damaged in the storage are found before somebody tries to read them.
+    /// Optionally broken records can be moved to quarantine. Quarantined records aren't visible
+    /// for get and search calls anymore, but are kept in the storage for further investigation.
+    /// Quarantine is supported only by default wallet storage.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet.
+    /// options_json(optional): verification options json:
+    ///   {
+    ///       "quarantine": Optional<bool> // move corrupted records to quarantine, false by default
+    ///   }
+    ///
+    /// #Returns
+    /// report_json: verification report:
+    ///   {
+    ///       "checkedCount": int, // number of checked records
+    ///       "corrupted": [{
+    ///           "type": Optional<string>, // record type if it can be decrypted
+    ///           "id": Optional<string>, // record id if it can be decrypted
+    ///           "idHash": string, // hex encoded hash of record id (the same as in wallet changes)
+    ///           "errors": [string], // description of found problems
+    ///       }],
+    ///       "quarantinedCount": int, // number of records moved to quarantine
+    ///   }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_verify_wallet(indy_handle_t  command_handle,
+                                           indy_handle_t  wallet_handle,
+                                           const char*    options_json,
+                                           void           (*fn)(indy_handle_t xcommand_handle,
+                                                                indy_error_t err,
+                                                                const char*  report_json)
+                                          );
+
 #ifdef __cplusplus
 }
 #endif
@@ -10,6 +10,202 @@ use utils::byte_array::vec_to_pointer;
 use self::libc::c_char;
 
 
+/// Registers custom crypto type implementation.
+///
+/// Registered crypto type can be used as "crypto_type" in indy_create_key and indy_create_and_store_my_did.
+/// Verkeys of such keys have ":<crypto_type_name>" suffix, so indy_crypto_sign, indy_crypto_verify,
+/// indy_crypto_anon_crypt and indy_crypto_anon_decrypt are routed to the registered handlers.
+/// Authenticated encryption isn't supported for custom crypto types.
+///
+/// Built-in crypto types are "ed25519" (default) and "secp256k1" (ECDSA over SHA-256 with DER encoded signatures).
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// crypto_type_name: Crypto type name. Can't be empty or contain ':' symbol.
+/// create_key: CryptoType create key operation handler
+/// sign: CryptoType sign operation handler
+/// verify: CryptoType verify operation handler
+/// encrypt_sealed: CryptoType anonymous encryption operation handler
+/// decrypt_sealed: CryptoType anonymous decryption operation handler
+/// free: Handler that allows to de-allocate buffers allocated in caller code
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_register_crypto_type(command_handle: i32,
+                                        crypto_type_name: *const c_char,
+                                        create_key: Option<CryptoCreateKey>,
+                                        sign: Option<CryptoSign>,
+                                        verify: Option<CryptoVerify>,
+                                        encrypt_sealed: Option<CryptoEncryptSealed>,
+                                        decrypt_sealed: Option<CryptoDecryptSealed>,
+                                        free: Option<CryptoFree>,
+                                        cb: Option<extern fn(xcommand_handle: i32,
+                                                             err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_crypto_type: >>> crypto_type_name: {:?}", crypto_type_name);
+
+    check_useful_c_str!(crypto_type_name, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(create_key, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(sign, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(verify, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(encrypt_sealed, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(decrypt_sealed, ErrorCode::CommonInvalidParam7);
+    check_useful_c_callback!(free, ErrorCode::CommonInvalidParam8);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam9);
+
+    trace!("indy_register_crypto_type: entities >>> crypto_type_name: {:?}", crypto_type_name);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(
+            CryptoCommand::RegisterCryptoType(
+                crypto_type_name,
+                create_key,
+                sign,
+                verify,
+                encrypt_sealed,
+                decrypt_sealed,
+                free,
+                Box::new(move |result| {
+                    let err = result_to_err_code!(result);
+                    trace!("indy_register_crypto_type:");
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_register_crypto_type: <<< res: {:?}", res);
+
+    res
+}
+/// Registers external signer that holds private keys outside of the wallet (HSM, secure enclave, etc).
+///
+/// Keys of external signer are added to the wallet by indy_create_external_key or by
+/// indy_create_and_store_my_did with "external_key" field. Wallet stores only reference to such key.
+/// indy_crypto_sign, indy_sign_request, indy_crypto_auth_crypt, indy_crypto_auth_decrypt and
+/// indy_crypto_anon_decrypt calls for these keys are forwarded to the signer handlers.
+/// Only ed25519 keys are supported.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// signer_name: External signer name.
+/// sign: ExternalSigner sign operation handler
+/// precompute: ExternalSigner handler that computes crypto_box shared key (crypto_box_beforenm) for
+///             curve25519 form of the key and their curve25519 public key
+/// decrypt_sealed: ExternalSigner sealed box (crypto_box_seal) decryption handler
+/// free: Handler that allows to de-allocate buffers allocated in caller code
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_register_external_signer(command_handle: i32,
+                                            signer_name: *const c_char,
+                                            sign: Option<ExternalSignerSign>,
+                                            precompute: Option<ExternalSignerPrecompute>,
+                                            decrypt_sealed: Option<ExternalSignerDecryptSealed>,
+                                            free: Option<CryptoFree>,
+                                            cb: Option<extern fn(xcommand_handle: i32,
+                                                                 err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_external_signer: >>> signer_name: {:?}", signer_name);
+
+    check_useful_c_str!(signer_name, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(sign, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(precompute, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(decrypt_sealed, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(free, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_register_external_signer: entities >>> signer_name: {:?}", signer_name);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(
+            CryptoCommand::RegisterExternalSigner(
+                signer_name,
+                sign,
+                precompute,
+                decrypt_sealed,
+                free,
+                Box::new(move |result| {
+                    let err = result_to_err_code!(result);
+                    trace!("indy_register_external_signer:");
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_register_external_signer: <<< res: {:?}", res);
+
+    res
+}
+
+/// Stores reference to the key held by registered external signer in the wallet.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// key_json: External key information as json. Example:
+/// {
+///     "signer": string, // Name of external signer registered by indy_register_external_signer.
+///     "key_ref": string, // Key reference that is passed to the signer handlers.
+///     "verkey": string, // Ed25519 verkey of the key.
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - xcommand_handle: command handle to map callback to caller context.
+/// - err: Error code.
+/// - verkey: Ver key of external key, also used as key identifier
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_create_external_key(command_handle: i32,
+                                       wallet_handle: i32,
+                                       key_json: *const c_char,
+                                       cb: Option<extern fn(xcommand_handle: i32,
+                                                            err: ErrorCode,
+                                                            verkey: *const c_char)>) -> ErrorCode {
+    trace!("indy_create_external_key: >>> wallet_handle: {:?}, key_json: {:?}", wallet_handle, key_json);
+
+    check_useful_c_str!(key_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_create_external_key: entities >>> wallet_handle: {:?}, key_json: {:?}", wallet_handle, key_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::CreateExternalKey(
+            wallet_handle,
+            key_json,
+            Box::new(move |result| {
+                let (err, verkey) = result_to_err_code_1!(result, String::new());
+                trace!("indy_create_external_key: verkey: {:?}", verkey);
+                let verkey = CStringUtils::string_to_cstring(verkey);
+                cb(command_handle, err, verkey.as_ptr())
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_create_external_key: <<< res: {:?}", res);
+
+    res
+}
+
 /// Creates keys pair and stores in the wallet.
 ///
 /// #Params
@@ -18,7 +214,7 @@ use self::libc::c_char;
 /// key_json: Key information as json. Example:
 /// {
 ///     "seed": string, // Optional (if not set random one will be used); Seed information that allows deterministic key creation.
-///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Supported values are 'ed25519', 'secp256k1' and types registered by indy_register_crypto_type.
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -66,6 +262,127 @@ pub  extern fn indy_create_key(command_handle: i32,
     res
 }
 
+/// Creates a master seed for deterministic key derivation and stores it in the wallet.
+/// Keys are derived from the master seed by indy_create_key_from_path
+/// and indy_create_and_store_my_did according to SLIP-0010 (ed25519, hardened derivation only).
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// master_seed_id: (optional, if not present random one will be generated) new master seed id.
+/// config_json: (optional, if not present random master seed will be generated) master seed source as json:
+/// {
+///     "seed": string, // Optional; Seed information (32 symbols string or base64 encoded bytes ending with "=").
+///     "mnemonic": string, // Optional; BIP-0039 mnemonic sentence (English word list, valid checksum) to restore master seed from. Can't be combined with seed.
+///     "passphrase": string, // Optional; BIP-0039 passphrase used with mnemonic.
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - xcommand_handle: command handle to map callback to caller context.
+/// - err: Error code.
+/// - out_master_seed_id: Id of generated master seed.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_create_master_seed(command_handle: i32,
+                                      wallet_handle: i32,
+                                      master_seed_id: *const c_char,
+                                      config_json: *const c_char,
+                                      cb: Option<extern fn(xcommand_handle: i32,
+                                                           err: ErrorCode,
+                                                           out_master_seed_id: *const c_char)>) -> ErrorCode {
+    trace!("indy_create_master_seed: >>> wallet_handle: {:?}, master_seed_id: {:?}", wallet_handle, master_seed_id);
+
+    check_useful_opt_c_str!(master_seed_id, ErrorCode::CommonInvalidParam3);
+    check_useful_opt_c_str!(config_json, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_create_master_seed: entities >>> wallet_handle: {:?}, master_seed_id: {:?}", wallet_handle, master_seed_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::CreateMasterSeed(
+            wallet_handle,
+            master_seed_id,
+            config_json,
+            Box::new(move |result| {
+                let (err, out_master_seed_id) = result_to_err_code_1!(result, String::new());
+                trace!("indy_create_master_seed: out_master_seed_id: {:?}", out_master_seed_id);
+                let out_master_seed_id = CStringUtils::string_to_cstring(out_master_seed_id);
+                cb(command_handle, err, out_master_seed_id.as_ptr())
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_create_master_seed: <<< res: {:?}", res);
+
+    res
+}
+
+/// Derives ed25519 keys pair from the master seed stored in the wallet and stores it in the wallet.
+/// The same master seed and path always produce the same key.
+/// Derivation info is stored as the key metadata.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// key_json: Key derivation information as json. Example:
+/// {
+///     "master_seed_id": string, // Id of master seed created by indy_create_master_seed.
+///     "path": string, // Derivation path with hardened indexes only, e.g. "m/44'/0'/1'".
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - xcommand_handle: command handle to map callback to caller context.
+/// - err: Error code.
+/// - verkey: Ver key of derived key pair, also used as key identifier
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_create_key_from_path(command_handle: i32,
+                                        wallet_handle: i32,
+                                        key_json: *const c_char,
+                                        cb: Option<extern fn(xcommand_handle: i32,
+                                                             err: ErrorCode,
+                                                             verkey: *const c_char)>) -> ErrorCode {
+    trace!("indy_create_key_from_path: >>> wallet_handle: {:?}, key_json: {:?}", wallet_handle, key_json);
+
+    check_useful_c_str!(key_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_create_key_from_path: entities >>> wallet_handle: {:?}, key_json: {:?}", wallet_handle, key_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::CreateKeyFromPath(
+            wallet_handle,
+            key_json,
+            Box::new(move |result| {
+                let (err, verkey) = result_to_err_code_1!(result, String::new());
+                trace!("indy_create_key_from_path: verkey: {:?}", verkey);
+                let verkey = CStringUtils::string_to_cstring(verkey);
+                cb(command_handle, err, verkey.as_ptr())
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_create_key_from_path: <<< res: {:?}", res);
+
+    res
+}
+
 /// Saves/replaces the meta information for the giving key in the wallet.
 ///
 /// #Params
@@ -554,4 +871,407 @@ pub  extern fn indy_crypto_anon_decrypt(command_handle: i32,
     trace!("indy_crypto_anon_decrypt: <<< res: {:?}", res);
 
     res
-}
\ No newline at end of file
+}
+/// Packs a message for one or more recipients into JWE-like JSON envelope.
+///
+/// The message is encrypted once with random content encryption key (ChaCha20-Poly1305),
+/// and this key is wrapped for every recipient verkey. If sender verkey is provided
+/// the key is wrapped by authenticated-encryption scheme and recipients can learn who sent the message,
+/// otherwise anonymous-encryption scheme is used.
+///
+/// Note to use DID keys with this function you can call indy_key_for_did to get key id (verkey)
+/// for specific DID.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// message: a pointer to first byte of message to be packed (UTF8 string is expected)
+/// message_len: a message length
+/// receiver_keys: a json array of recipient verkeys: ["verkey1", "verkey2"]
+/// sender(optional): id (verkey) of my key. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// a packed message (JWE json) as a pointer to array of bytes:
+/// {
+///     "protected": string, // base64url encoded json with used algorithms and recipients:
+///                          // {
+///                          //     "enc": "chacha20poly1305_ietf",
+///                          //     "typ": "JWM/1.0",
+///                          //     "alg": "Authcrypt" or "Anoncrypt",
+///                          //     "recipients": [{
+///                          //         "encrypted_key": string, // base64url encoded wrapped content encryption key
+///                          //         "header": {
+///                          //             "kid": string, // recipient verkey
+///                          //             "iv": string, // base64url encoded nonce (only for Authcrypt)
+///                          //             "sender": string, // base64url encoded anonymously encrypted sender verkey (only for Authcrypt)
+///                          //         }
+///                          //     }]
+///                          // }
+///     "iv": string, // base64url encoded nonce
+///     "ciphertext": string, // base64url encoded encrypted message
+///     "tag": string, // base64url encoded authentication tag
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_pack_message(command_handle: i32,
+                                wallet_handle: i32,
+                                message: *const u8,
+                                message_len: u32,
+                                receiver_keys: *const c_char,
+                                sender: *const c_char,
+                                cb: Option<extern fn(command_handle_: i32,
+                                                     err: ErrorCode,
+                                                     jwe_data: *const u8,
+                                                     jwe_len: u32)>) -> ErrorCode {
+    trace!("indy_pack_message: >>> wallet_handle: {:?}, message: {:?}, message_len: {:?}, receiver_keys: {:?}, sender: {:?}",
+           wallet_handle, message, message_len, receiver_keys, sender);
+
+    check_useful_c_byte_array!(message, message_len, ErrorCode::CommonInvalidParam3, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(receiver_keys, ErrorCode::CommonInvalidParam5);
+    check_useful_opt_c_str!(sender, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_pack_message: entities >>> wallet_handle: {:?}, message: {:?}, message_len: {:?}, receiver_keys: {:?}, sender: {:?}",
+           wallet_handle, message, message_len, receiver_keys, sender);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::PackMessage(
+            wallet_handle,
+            message,
+            receiver_keys,
+            sender,
+            Box::new(move |result| {
+                let (err, jwe) = result_to_err_code_1!(result, Vec::new());
+                trace!("indy_pack_message: jwe: {:?}", jwe);
+                let (jwe_data, jwe_len) = vec_to_pointer(&jwe);
+                cb(command_handle, err, jwe_data, jwe_len)
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_pack_message: <<< res: {:?}", res);
+
+    res
+}
+
+/// Unpacks a message packed by indy_pack_message.
+///
+/// The first recipient which key is stored in the wallet is used to decrypt the message.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// jwe_data: a pointer to first byte of packed message (JWE json)
+/// jwe_len: a packed message length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// unpacked message json as a pointer to array of bytes:
+/// {
+///     "message": string, // decrypted message
+///     "recipient_verkey": string, // verkey of the wallet key used to decrypt the message
+///     "sender_verkey": string, // sender verkey (only if message was packed with sender)
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_unpack_message(command_handle: i32,
+                                  wallet_handle: i32,
+                                  jwe_data: *const u8,
+                                  jwe_len: u32,
+                                  cb: Option<extern fn(command_handle_: i32,
+                                                       err: ErrorCode,
+                                                       res_json_data: *const u8,
+                                                       res_json_len: u32)>) -> ErrorCode {
+    trace!("indy_unpack_message: >>> wallet_handle: {:?}, jwe_data: {:?}, jwe_len: {:?}", wallet_handle, jwe_data, jwe_len);
+
+    check_useful_c_byte_array!(jwe_data, jwe_len, ErrorCode::CommonInvalidParam3, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_unpack_message: entities >>> wallet_handle: {:?}, jwe_data: {:?}, jwe_len: {:?}", wallet_handle, jwe_data, jwe_len);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::UnpackMessage(
+            wallet_handle,
+            jwe_data,
+            Box::new(move |result| {
+                let (err, res_json) = result_to_err_code_1!(result, Vec::new());
+                trace!("indy_unpack_message: res_json: {:?}", res_json);
+                let (res_json_data, res_json_len) = vec_to_pointer(&res_json);
+                cb(command_handle, err, res_json_data, res_json_len)
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_unpack_message: <<< res: {:?}", res);
+
+    res
+}
+
+/// Signs JSON payload as JSON Web Signature (JWS) in compact serialization with a key stored in the wallet.
+///
+/// JWS protected header is {"alg":"EdDSA","kid":<kid>}, so only ed25519 keys can be used.
+/// Signature is calculated over ASCII(BASE64URL(header) || '.' || BASE64URL(payload)).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// signer_vk: id (verkey) of my key. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+/// payload_json: JSON payload to be signed
+/// options_json: (optional) signing options:
+/// {
+///     "kid": string, // Optional (if not set then signer_vk is used); Key id to put into JWS header, e.g. DID of the signer.
+///     "detached": bool, // Optional (false by default); If true then payload is omitted from JWS ("<header>..<signature>").
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// jws: JWS in compact serialization
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_crypto_sign_jws(command_handle: i32,
+                                   wallet_handle: i32,
+                                   signer_vk: *const c_char,
+                                   payload_json: *const c_char,
+                                   options_json: *const c_char,
+                                   cb: Option<extern fn(xcommand_handle: i32,
+                                                        err: ErrorCode,
+                                                        jws: *const c_char)>) -> ErrorCode {
+    trace!("indy_crypto_sign_jws: >>> wallet_handle: {:?}, signer_vk: {:?}, payload_json: {:?}, options_json: {:?}", wallet_handle, signer_vk, payload_json, options_json);
+
+    check_useful_c_str!(signer_vk, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(payload_json, ErrorCode::CommonInvalidParam4);
+    check_useful_opt_c_str!(options_json, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_crypto_sign_jws: entities >>> wallet_handle: {:?}, signer_vk: {:?}, payload_json: {:?}, options_json: {:?}", wallet_handle, signer_vk, payload_json, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::SignJws(
+            wallet_handle,
+            signer_vk,
+            payload_json,
+            options_json,
+            Box::new(move |result| {
+                let (err, jws) = result_to_err_code_1!(result, String::new());
+                trace!("indy_crypto_sign_jws: jws: {:?}", jws);
+                let jws = CStringUtils::string_to_cstring(jws);
+                cb(command_handle, err, jws.as_ptr())
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_crypto_sign_jws: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verifies JSON Web Signature (JWS) in compact serialization created by indy_crypto_sign_jws.
+///
+/// If signer_vk isn't provided the "kid" of JWS header is resolved to verkey through their or my DIDs
+/// stored in the wallet (DID URL fragment like "#key-1" is ignored). If there is no such DID then "kid"
+/// must be a verkey of key stored in the wallet. Unknown "kid" causes WalletItemNotFound error.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// jws: JWS in compact serialization
+/// detached_payload_json: (optional) JSON payload for detached JWS ("<header>..<signature>")
+/// signer_vk: (optional) expected signer verkey. If provided JWS is verified with this key and "kid" is ignored.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// valid: true - if signature is valid, false - otherwise
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_crypto_verify_jws(command_handle: i32,
+                                     wallet_handle: i32,
+                                     jws: *const c_char,
+                                     detached_payload_json: *const c_char,
+                                     signer_vk: *const c_char,
+                                     cb: Option<extern fn(xcommand_handle: i32,
+                                                          err: ErrorCode,
+                                                          valid: bool)>) -> ErrorCode {
+    trace!("indy_crypto_verify_jws: >>> wallet_handle: {:?}, jws: {:?}, detached_payload_json: {:?}, signer_vk: {:?}", wallet_handle, jws, detached_payload_json, signer_vk);
+
+    check_useful_c_str!(jws, ErrorCode::CommonInvalidParam3);
+    check_useful_opt_c_str!(detached_payload_json, ErrorCode::CommonInvalidParam4);
+    check_useful_opt_c_str!(signer_vk, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_crypto_verify_jws: entities >>> wallet_handle: {:?}, jws: {:?}, detached_payload_json: {:?}, signer_vk: {:?}", wallet_handle, jws, detached_payload_json, signer_vk);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::VerifyJws(
+            wallet_handle,
+            jws,
+            detached_payload_json,
+            signer_vk,
+            Box::new(move |result| {
+                let (err, valid) = result_to_err_code_1!(result, false);
+                trace!("indy_crypto_verify_jws: valid: {:?}", valid);
+                cb(command_handle, err, valid)
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_crypto_verify_jws: <<< res: {:?}", res);
+
+    res
+}
+
+/// Creates key pair of registered crypto type
+///
+/// #Params
+/// seed: seed bytes or null if random key pair must be created
+/// seed_len: seed length
+/// verkey_p: pointer to store verkey bytes allocated in caller code
+/// verkey_len_p: pointer to store verkey length
+/// signkey_p: pointer to store signkey bytes allocated in caller code
+/// signkey_len_p: pointer to store signkey length
+pub type CryptoCreateKey = extern fn(seed: *const u8,
+                                     seed_len: u32,
+                                     verkey_p: *mut *const u8,
+                                     verkey_len_p: *mut u32,
+                                     signkey_p: *mut *const u8,
+                                     signkey_len_p: *mut u32) -> ErrorCode;
+
+/// Signs message with signkey of registered crypto type
+///
+/// #Params
+/// signkey: signkey bytes
+/// signkey_len: signkey length
+/// message: message bytes
+/// message_len: message length
+/// signature_p: pointer to store signature bytes allocated in caller code
+/// signature_len_p: pointer to store signature length
+pub type CryptoSign = extern fn(signkey: *const u8,
+                                signkey_len: u32,
+                                message: *const u8,
+                                message_len: u32,
+                                signature_p: *mut *const u8,
+                                signature_len_p: *mut u32) -> ErrorCode;
+
+/// Verifies signature with verkey of registered crypto type
+///
+/// #Params
+/// verkey: verkey bytes
+/// verkey_len: verkey length
+/// message: message bytes
+/// message_len: message length
+/// signature: signature bytes
+/// signature_len: signature length
+/// valid_p: pointer to store verification result
+pub type CryptoVerify = extern fn(verkey: *const u8,
+                                  verkey_len: u32,
+                                  message: *const u8,
+                                  message_len: u32,
+                                  signature: *const u8,
+                                  signature_len: u32,
+                                  valid_p: *mut bool) -> ErrorCode;
+
+/// Encrypts message for verkey of registered crypto type
+///
+/// #Params
+/// verkey: verkey bytes
+/// verkey_len: verkey length
+/// message: message bytes
+/// message_len: message length
+/// encrypted_p: pointer to store encrypted message bytes allocated in caller code
+/// encrypted_len_p: pointer to store encrypted message length
+pub type CryptoEncryptSealed = extern fn(verkey: *const u8,
+                                         verkey_len: u32,
+                                         message: *const u8,
+                                         message_len: u32,
+                                         encrypted_p: *mut *const u8,
+                                         encrypted_len_p: *mut u32) -> ErrorCode;
+
+/// Decrypts message with key pair of registered crypto type
+///
+/// #Params
+/// verkey: verkey bytes
+/// verkey_len: verkey length
+/// signkey: signkey bytes
+/// signkey_len: signkey length
+/// encrypted: encrypted message bytes
+/// encrypted_len: encrypted message length
+/// decrypted_p: pointer to store decrypted message bytes allocated in caller code
+/// decrypted_len_p: pointer to store decrypted message length
+pub type CryptoDecryptSealed = extern fn(verkey: *const u8,
+                                         verkey_len: u32,
+                                         signkey: *const u8,
+                                         signkey_len: u32,
+                                         encrypted: *const u8,
+                                         encrypted_len: u32,
+                                         decrypted_p: *mut *const u8,
+                                         decrypted_len_p: *mut u32) -> ErrorCode;
+
+/// Frees buffer allocated in caller code by one of crypto type handlers
+///
+/// #Params
+/// data: buffer returned by handler
+/// data_len: buffer length returned by handler
+pub type CryptoFree = extern fn(data: *const u8,
+                                data_len: u32) -> ErrorCode;
+
+/// Signs message with the key held by external signer
+///
+/// #Params
+/// key_ref: key reference passed to indy_create_external_key
+/// message: message bytes
+/// message_len: message length
+/// signature_p: pointer to store ed25519 signature bytes allocated in caller code
+/// signature_len_p: pointer to store signature length
+pub type ExternalSignerSign = extern fn(key_ref: *const c_char,
+                                        message: *const u8,
+                                        message_len: u32,
+                                        signature_p: *mut *const u8,
+                                        signature_len_p: *mut u32) -> ErrorCode;
+
+/// Computes crypto_box shared key (crypto_box_beforenm) for curve25519 form of the key held by external signer
+///
+/// #Params
+/// key_ref: key reference passed to indy_create_external_key
+/// their_pk: their curve25519 public key bytes
+/// their_pk_len: their public key length
+/// precomputed_key_p: pointer to store shared key bytes allocated in caller code
+/// precomputed_key_len_p: pointer to store shared key length
+pub type ExternalSignerPrecompute = extern fn(key_ref: *const c_char,
+                                              their_pk: *const u8,
+                                              their_pk_len: u32,
+                                              precomputed_key_p: *mut *const u8,
+                                              precomputed_key_len_p: *mut u32) -> ErrorCode;
+
+/// Decrypts sealed box (crypto_box_seal) with the key held by external signer
+///
+/// #Params
+/// key_ref: key reference passed to indy_create_external_key
+/// encrypted: encrypted message bytes
+/// encrypted_len: encrypted message length
+/// decrypted_p: pointer to store decrypted message bytes allocated in caller code
+/// decrypted_len_p: pointer to store decrypted message length
+pub type ExternalSignerDecryptSealed = extern fn(key_ref: *const c_char,
+                                                 encrypted: *const u8,
+                                                 encrypted_len: u32,
+                                                 decrypted_p: *mut *const u8,
+                                                 decrypted_len_p: *mut u32) -> ErrorCode;
@@ -27,8 +27,19 @@ use std::ptr;
 ///             if provided, then keys will be replaced - key rotation use case)
 ///     "seed": string, (optional; if not provide then a random one will be created)
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-///               currently only 'ed25519' value is supported for this field)
+///               supported values are 'ed25519', 'secp256k1' and types registered by indy_register_crypto_type)
 ///     "cid": bool, (optional; if not set then false is used;)
+///     "method_name": string, (optional; if set then fully qualified DID did:<method_name>:<id> will be created;
+///               method name can contain lowercase letters and digits only)
+///     "external_key": {  (optional; if set then DID keys are held by external signer, seed and crypto_type can't be used)
+///         "signer": string, (name of external signer registered by indy_register_external_signer)
+///         "key_ref": string, (key reference that is passed to the signer handlers)
+///         "verkey": string, (ed25519 verkey of the key)
+///     }
+///     "derivation": {  (optional; if set then DID keys are derived from wallet master seed, seed and external_key can't be used)
+///         "master_seed_id": string, (id of master seed created by indy_create_master_seed)
+///         "path": string, (derivation path with hardened indexes only, e.g. "m/44'/0'/1'")
+///     }
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -89,7 +100,7 @@ pub  extern fn indy_create_and_store_my_did(command_handle: i32,
 /// {
 ///     "seed": string, (optional; if not provide then a random one will be created)
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-///               currently only 'ed25519' value is supported for this field)
+///               supported values are 'ed25519', 'secp256k1' and types registered by indy_register_crypto_type)
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -190,6 +201,84 @@ pub  extern fn indy_replace_keys_apply(command_handle: i32,
     res
 }
 
+/// Rotates the key of an existing DID (owned by the caller of the library) on the ledger.
+///
+/// Runs the whole rotation sequence in one call: generates temporary key (as "indy_replace_keys_start"),
+/// sends NYM transaction with the new verkey signed by the current key, checks with GET_NYM request
+/// that the ledger has applied the new verkey and only then applies the key in the wallet
+/// (as "indy_replace_keys_apply").
+///
+/// If the ledger rejects the transaction the temporary key is discarded.
+/// If the result is unknown (for example, on pool timeout or if GET_NYM doesn't confirm the new verkey)
+/// the temporary key is kept in the wallet. In this case check the ledger and call
+/// "indy_replace_keys_apply" if the new verkey has been written. The next rotation
+/// for the DID isn't allowed until the previous one is completed.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// wallet_handle: wallet handler (created by open_wallet).
+/// did: DID stored in the wallet and written to the ledger
+/// key_json: Key information as json. Example:
+/// {
+///     "seed": string, (optional; if not provide then a random one will be created)
+///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
+///               supported values are 'ed25519', 'secp256k1' and types registered by indy_register_crypto_type)
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - xcommand_handle: Command handle to map callback to caller context.
+/// - err: Error code.
+///   verkey: The DIDs new verification key
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_rotate_did_key_on_ledger(command_handle: i32,
+                                            pool_handle: i32,
+                                            wallet_handle: i32,
+                                            did: *const c_char,
+                                            key_json: *const c_char,
+                                            cb: Option<extern fn(xcommand_handle: i32,
+                                                                 err: ErrorCode,
+                                                                 verkey: *const c_char)>) -> ErrorCode {
+    trace!("indy_rotate_did_key_on_ledger: >>> pool_handle: {:?}, wallet_handle: {:?}, did: {:?}, key_json: {:?}",
+           pool_handle, wallet_handle, did, key_json);
+
+    check_useful_c_str!(did, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(key_json, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_rotate_did_key_on_ledger: entities >>> pool_handle: {:?}, wallet_handle: {:?}, did: {:?}, key_json: {:?}",
+           pool_handle, wallet_handle, did, key_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::RotateKeyOnLedger(
+            pool_handle,
+            wallet_handle,
+            did,
+            key_json,
+            Box::new(move |result| {
+                let (err, verkey) = result_to_err_code_1!(result, String::new());
+                trace!("indy_rotate_did_key_on_ledger: verkey: {:?}", verkey);
+                let verkey = CStringUtils::string_to_cstring(verkey);
+                cb(command_handle, err, verkey.as_ptr())
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_rotate_did_key_on_ledger: <<< res: {:?}", res);
+
+    res
+}
+
 /// Saves their DID for a pairwise connection in a secured Wallet,
 /// so that it can be used to verify transaction.
 ///
@@ -247,8 +336,8 @@ pub  extern fn indy_store_their_did(command_handle: i32,
 /// Returns ver key (key id) for the given DID.
 ///
 /// "indy_key_for_did" call follow the idea that we resolve information about their DID from
-/// the ledger with cache in the local wallet. The "indy_open_wallet" call has freshness parameter
-/// that is used for checking the freshness of cached pool value.
+/// the ledger with cache in the local wallet. Cached value is used without freshness checking,
+/// see "indy_key_for_did_with_options" to control cache behaviour.
 ///
 /// Note if you don't want to resolve their DID info from the ledger you can use
 /// "indy_key_for_local_did" call instead that will look only to the local wallet and skip
@@ -295,6 +384,7 @@ pub extern fn indy_key_for_did(command_handle: i32,
             pool_handle,
             wallet_handle,
             did,
+            None,
             Box::new(move |result| {
                 let (err, key) = result_to_err_code_1!(result, String::new());
                 trace!("indy_key_for_did: key: {:?}", key);
@@ -310,6 +400,142 @@ pub extern fn indy_key_for_did(command_handle: i32,
     res
 }
 
+/// Returns ver key (key id) for the given DID with control of ledger cache behaviour.
+///
+/// Works like "indy_key_for_did", but their DID info cached in the local wallet is used only
+/// if it satisfies options. Otherwise it is fetched from the ledger again.
+///
+/// Cache options affect their DIDs only. Their DIDs stored by "indy_store_their_did" call
+/// are not resolved from the ledger, so "maxAge" isn't applied to them.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// pool_handle:   Pool handle (created by open_pool).
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// did - The DID to resolve key.
+/// options_json: (optional) cache options json:
+///  {
+///     "noCache": (optional, false by default) skip usage of cache,
+///     "noStore": (optional, false by default) don't store fetched DID info in the wallet,
+///     "maxAge": (optional, no limit by default) max age of cached DID info in seconds,
+///  }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - xcommand_handle: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - key - The DIDs ver key (key id).
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_key_for_did_with_options(command_handle: i32,
+                                            pool_handle: i32,
+                                            wallet_handle: i32,
+                                            did: *const c_char,
+                                            options_json: *const c_char,
+                                            cb: Option<extern fn(xcommand_handle: i32,
+                                                                 err: ErrorCode,
+                                                                 key: *const c_char)>) -> ErrorCode {
+    trace!("indy_key_for_did_with_options: >>> pool_handle: {:?}, wallet_handle: {:?}, did: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, did, options_json);
+
+    check_useful_c_str!(did, ErrorCode::CommonInvalidParam4);
+    check_useful_opt_c_str!(options_json, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_key_for_did_with_options: entities >>> pool_handle: {:?}, wallet_handle: {:?}, did: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, did, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::KeyForDid(
+            pool_handle,
+            wallet_handle,
+            did,
+            options_json,
+            Box::new(move |result| {
+                let (err, key) = result_to_err_code_1!(result, String::new());
+                trace!("indy_key_for_did_with_options: key: {:?}", key);
+                let key = CStringUtils::string_to_cstring(key);
+                cb(command_handle, err, key.as_ptr())
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_key_for_did_with_options: <<< res: {:?}", res);
+
+    res
+}
+
+/// Forces resolving of their DID from the ledger.
+///
+/// Fetches actual DID info from the ledger and replaces DID info stored in the wallet.
+/// Use it when you know that DID owner has rotated the key.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// pool_handle:   Pool handle (created by open_pool).
+/// did - Their DID to refresh.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - xcommand_handle: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - their_did_json - resolved DID info:
+///   {
+///     "did": string,
+///     "verkey": string,
+///     "resolved_at": int, // time of resolution in seconds since epoch
+///   }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_refresh_their_did(command_handle: i32,
+                                     wallet_handle: i32,
+                                     pool_handle: i32,
+                                     did: *const c_char,
+                                     cb: Option<extern fn(xcommand_handle: i32,
+                                                          err: ErrorCode,
+                                                          their_did_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_refresh_their_did: >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}", wallet_handle, pool_handle, did);
+
+    check_useful_c_str!(did, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_refresh_their_did: entities >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}", wallet_handle, pool_handle, did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::RefreshTheirDid(
+            wallet_handle,
+            pool_handle,
+            did,
+            Box::new(move |result| {
+                let (err, their_did_json) = result_to_err_code_1!(result, String::new());
+                trace!("indy_refresh_their_did: their_did_json: {:?}", their_did_json);
+                let their_did_json = CStringUtils::string_to_cstring(their_did_json);
+                cb(command_handle, err, their_did_json.as_ptr())
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_refresh_their_did: <<< res: {:?}", res);
+
+    res
+}
+
 /// Returns ver key (key id) for the given DID.
 ///
 /// "indy_key_for_local_did" call looks data stored in the local wallet only and skips freshness
@@ -470,6 +696,7 @@ pub extern fn indy_get_endpoint_for_did(command_handle: i32,
             wallet_handle,
             pool_handle,
             did,
+            None,
             Box::new(move |result| {
                 let (err, address, transport_vk) = result_to_err_code_2!(result, String::new(), None);
                 trace!("indy_get_endpoint_for_did: address: {:?}, transport_vk: {:?}", address, transport_vk);
@@ -487,6 +714,81 @@ pub extern fn indy_get_endpoint_for_did(command_handle: i32,
     res
 }
 
+/// Returns endpoint information for the given DID with control of ledger cache behaviour.
+///
+/// Works like "indy_get_endpoint_for_did", but endpoint cached in the local wallet is used only
+/// if it satisfies options. Otherwise it is fetched from the ledger again.
+/// Endpoints stored by "indy_set_endpoint_for_did" call are not resolved from the ledger,
+/// so "maxAge" isn't applied to them.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// pool_handle:   Pool handle (created by open_pool).
+/// did - The DID to resolve endpoint.
+/// options_json: (optional) cache options json:
+///  {
+///     "noCache": (optional, false by default) skip usage of cache,
+///     "noStore": (optional, false by default) don't store fetched endpoint in the wallet,
+///     "maxAge": (optional, no limit by default) max age of cached endpoint in seconds,
+///  }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - xcommand_handle: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - endpoint - The DIDs endpoint.
+/// - transport_vk - The DIDs transport key (ver key, key id).
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_get_endpoint_for_did_with_options(command_handle: i32,
+                                                     wallet_handle: i32,
+                                                     pool_handle: i32,
+                                                     did: *const c_char,
+                                                     options_json: *const c_char,
+                                                     cb: Option<extern fn(command_handle_: i32,
+                                                                          err: ErrorCode,
+                                                                          address: *const c_char,
+                                                                          transport_vk: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_endpoint_for_did_with_options: >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}, options_json: {:?}",
+           wallet_handle, pool_handle, did, options_json);
+
+    check_useful_c_str!(did, ErrorCode::CommonInvalidParam4);
+    check_useful_opt_c_str!(options_json, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_get_endpoint_for_did_with_options: entities >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}, options_json: {:?}",
+           wallet_handle, pool_handle, did, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::GetEndpointForDid(
+            wallet_handle,
+            pool_handle,
+            did,
+            options_json,
+            Box::new(move |result| {
+                let (err, address, transport_vk) = result_to_err_code_2!(result, String::new(), None);
+                trace!("indy_get_endpoint_for_did_with_options: address: {:?}, transport_vk: {:?}", address, transport_vk);
+                let address = CStringUtils::string_to_cstring(address);
+                let transport_vk = transport_vk.map(CStringUtils::string_to_cstring);
+                cb(command_handle, err, address.as_ptr(),
+                   transport_vk.as_ref().map(|vk| vk.as_ptr()).unwrap_or(ptr::null()));
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_get_endpoint_for_did_with_options: <<< res: {:?}", res);
+
+    res
+}
+
 /// Saves/replaces the meta information for the giving DID in the wallet.
 ///
 /// #Params
@@ -750,4 +1052,133 @@ pub  extern fn indy_abbreviate_verkey(command_handle: i32,
     trace!("indy_abbreviate_verkey: <<< res: {:?}", res);
 
     res
-}
\ No newline at end of file
+}
+/// Resolves W3C DID Document for the given DID (did:sov method) from the ledger.
+///
+/// DID Document is assembled from NYM transaction (verification key) and endpoint ATTRIB
+/// transaction (service). Abbreviated verkey is expanded to the full one.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// pool_handle: Pool handle (created by open_pool).
+/// did: DID to resolve. Both "did:sov:<did>" and "<did>" forms are accepted.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - xcommand_handle: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - did_doc_json: DID Document json:
+/// {
+///     "@context": "https://w3id.org/did/v1",
+///     "id": "did:sov:<did>",
+///     "publicKey": [{
+///         "id": "did:sov:<did>#key-1",
+///         "type": "Ed25519VerificationKey2018",
+///         "controller": "did:sov:<did>",
+///         "publicKeyBase58": string, // full verkey
+///     }],
+///     "authentication": [{
+///         "type": "Ed25519SignatureAuthentication2018",
+///         "publicKey": "did:sov:<did>#key-1"
+///     }],
+///     "service": [{ // empty if no endpoint is set on the ledger
+///         "id": "did:sov:<did>;indy",
+///         "type": "IndyAgent",
+///         "serviceEndpoint": string, // endpoint address
+///         "recipientKeys": [string], // endpoint transport key if set
+///     }]
+/// }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_resolve_did_document(command_handle: i32,
+                                        pool_handle: i32,
+                                        did: *const c_char,
+                                        cb: Option<extern fn(xcommand_handle: i32,
+                                                             err: ErrorCode,
+                                                             did_doc_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_resolve_did_document: >>> pool_handle: {:?}, did: {:?}", pool_handle, did);
+
+    check_useful_c_str!(did, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_resolve_did_document: entities >>> pool_handle: {:?}, did: {:?}", pool_handle, did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::ResolveDidDocument(
+            pool_handle,
+            did,
+            Box::new(move |result| {
+                let (err, did_doc_json) = result_to_err_code_1!(result, String::new());
+                trace!("indy_resolve_did_document: did_doc_json: {:?}", did_doc_json);
+                let did_doc_json = CStringUtils::string_to_cstring(did_doc_json);
+                cb(command_handle, err, did_doc_json.as_ptr())
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_resolve_did_document: <<< res: {:?}", res);
+
+    res
+}
+
+/// Builds fully qualified DID did:<method_name>:<id> for the given DID.
+///
+/// Qualified DIDs can be used in all DID, pairwise and ledger functions. Requests sent to the ledger
+/// always contain unqualified DIDs.
+/// Note that wallet records are not changed by this call: DID stored in the wallet in unqualified form
+/// stays accessible by unqualified DID only.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// did: DID to qualify. If DID is already qualified then its method is replaced.
+/// method_name: DID method name (lowercase letters and digits), for example "sov".
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - xcommand_handle: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - full_qualified_did: did:<method_name>:<id>
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_qualify_did(command_handle: i32,
+                               did: *const c_char,
+                               method_name: *const c_char,
+                               cb: Option<extern fn(xcommand_handle: i32,
+                                                    err: ErrorCode,
+                                                    full_qualified_did: *const c_char)>) -> ErrorCode {
+    trace!("indy_qualify_did: >>> did: {:?}, method_name: {:?}", did, method_name);
+
+    check_useful_c_str!(did, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(method_name, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_qualify_did: entities >>> did: {:?}, method_name: {:?}", did, method_name);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::QualifyDid(
+            did,
+            method_name,
+            Box::new(move |result| {
+                let (err, full_qualified_did) = result_to_err_code_1!(result, String::new());
+                trace!("indy_qualify_did: full_qualified_did: {:?}", full_qualified_did);
+                let full_qualified_did = CStringUtils::string_to_cstring(full_qualified_did);
+                cb(command_handle, err, full_qualified_did.as_ptr())
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_qualify_did: <<< res: {:?}", res);
+
+    res
+}
@@ -70,6 +70,90 @@ pub extern fn indy_sign_and_submit_request(command_handle: i32,
     res
 }
 
+/// Signs and submits request message to validator pool paying fees for it.
+///
+/// Gets fees schedule from the ledger (it's cached per pool handle and payment method for a minute,
+/// until the pool is closed or new fees are built with indy_build_set_txn_fees_req),
+/// computes the fee of the request with payment method "get_request_fee" handler
+/// (or takes flat fee of the request txn type from the schedule if payment method doesn't provide it),
+/// selects inputs from UTXOs of the wallet payment addresses to cover them (see indy_prepare_payment),
+/// adds fees info to the request (see indy_add_request_fees), signs and sends the request to validator pool.
+/// If the txn type has no fees the request is sent as is.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// wallet_handle: wallet handle (created by open_wallet).
+/// submitter_did: Id of Identity stored in secured Wallet.
+/// request_json: Request data json.
+/// payment_method: payment method to pay fees with.
+/// change_address: payment address to send change to.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// request_result_json: Request result as json.
+/// receipts_json: The list of UTXO created by fees transaction (see indy_parse_response_with_fees):
+///   [{
+///      input: <str>, // UTXO input
+///      amount: <int>, // amount of tokens in this input
+///      extra: <str>, // optional data from payment transaction
+///   }]
+///   Empty list if no fees were paid.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+/// Crypto*
+/// Payment*
+#[no_mangle]
+pub extern fn indy_sign_and_submit_request_with_fees(command_handle: i32,
+                                                     pool_handle: i32,
+                                                     wallet_handle: i32,
+                                                     submitter_did: *const c_char,
+                                                     request_json: *const c_char,
+                                                     payment_method: *const c_char,
+                                                     change_address: *const c_char,
+                                                     cb: Option<extern fn(xcommand_handle: i32,
+                                                                          err: ErrorCode,
+                                                                          request_result_json: *const c_char,
+                                                                          receipts_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_sign_and_submit_request_with_fees: >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, request_json: {:?}, payment_method: {:?}, change_address: {:?}",
+           pool_handle, wallet_handle, submitter_did, request_json, payment_method, change_address);
+
+    check_useful_c_str!(submitter_did, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(request_json, ErrorCode::CommonInvalidParam5);
+    check_useful_c_str!(payment_method, ErrorCode::CommonInvalidParam6);
+    check_useful_c_str!(change_address, ErrorCode::CommonInvalidParam7);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_sign_and_submit_request_with_fees: entities >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, request_json: {:?}, payment_method: {:?}, change_address: {:?}",
+           pool_handle, wallet_handle, submitter_did, request_json, payment_method, change_address);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::SignAndSubmitRequestWithFees(
+            pool_handle,
+            wallet_handle,
+            submitter_did,
+            request_json,
+            payment_method,
+            change_address,
+            Box::new(move |result| {
+                let (err, request_result_json, receipts_json) = result_to_err_code_2!(result, String::new(), String::new());
+                trace!("indy_sign_and_submit_request_with_fees: request_result_json: {:?}, receipts_json: {:?}", request_result_json, receipts_json);
+                let request_result_json = CStringUtils::string_to_cstring(request_result_json);
+                let receipts_json = CStringUtils::string_to_cstring(receipts_json);
+                cb(command_handle, err, request_result_json.as_ptr(), receipts_json.as_ptr())
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_sign_and_submit_request_with_fees: <<< res: {:?}", res);
+
+    res
+}
+
 /// Publishes request message to validator pool (no signing, unlike sign_and_submit_request).
 ///
 /// The request is sent to the validator pool as is. It's assumed that it's already prepared.
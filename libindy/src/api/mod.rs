@@ -11,6 +11,10 @@ pub mod blob_storage;
 pub mod non_secrets;
 pub mod payments;
 
+use errors;
+
+use self::libc::c_char;
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 #[repr(i32)]
 pub enum ErrorCode
@@ -70,6 +74,21 @@ pub enum ErrorCode
     // Caller passed invalid value as param 14 (null, invalid json and etc..)
     CommonInvalidParam14 = 116,
 
+    // Caller passed invalid value as param 15 (null, invalid json and etc..)
+    CommonInvalidParam15 = 117,
+
+    // Caller passed invalid value as param 16 (null, invalid json and etc..)
+    CommonInvalidParam16 = 118,
+
+    // Caller passed invalid value as param 17 (null, invalid json and etc..)
+    CommonInvalidParam17 = 119,
+
+    // Caller passed invalid value as param 18 (null, invalid json and etc..)
+    CommonInvalidParam18 = 120,
+
+    // Caller passed invalid value as param 19 (null, invalid json and etc..)
+    CommonInvalidParam19 = 121,
+
     // Wallet errors
     // Caller passed invalid wallet handle
     WalletInvalidHandle = 200,
@@ -116,6 +135,9 @@ pub enum ErrorCode
     // Returned if provided wallet query is invalid
     WalletQueryError = 214,
 
+    // Returned if wallet record was changed since the expected version
+    WalletConflict = 215,
+
     // Ledger errors
     // Trying to open pool ledger that wasn't created before
     PoolLedgerNotCreatedError = 300,
@@ -160,6 +182,12 @@ pub enum ErrorCode
     // Unknown format of DID entity keys
     UnknownCryptoTypeError = 500,
 
+    // Attempt to register crypto type with name used for another registered crypto type
+    CryptoTypeAlreadyRegisteredError = 501,
+
+    // Attempt to register external signer with name used for another registered external signer
+    ExternalSignerAlreadyRegisteredError = 502,
+
     // Attempt to create duplicate did
     DidAlreadyExistsError = 600,
 
@@ -171,4 +199,37 @@ pub enum ErrorCode
 
     // Insufficient funds on inputs
     PaymentInsufficientFundsError = 702,
+
+    // Unknown payment source (receipt or input) was given
+    PaymentSourceDoesNotExistError = 703,
+
+    // Payment method doesn't support requested operation
+    PaymentOperationNotSupportedError = 704,
+
+    // Inputs and outputs of request belong to several payment methods
+    // Offending entries can be obtained with indy_get_current_error_details
+    PaymentMixedMethodsError = 705,
+}
+
+/// Gets structured details of the last error returned by libindy in the current thread.
+///
+/// Details are set for errors that have them (PaymentMixedMethodsError for now) and reset
+/// by every successful result. Libindy calls callbacks in its own thread, so this function
+/// must be called inside of the callback to get details of the error passed to it.
+///
+/// #Params
+/// error_details_json_p: Reference that will contain details json or null if last error has no details.
+///   The pointer is valid until next call of libindy in the same thread.
+///   For PaymentMixedMethodsError:
+///   {
+///     inputs: {<payment method>: [<input>, ...], ...}, // inputs grouped by payment method
+///     outputs: {<payment method>: [<payment address>, ...], ...}, // output addresses grouped by payment method
+///   }
+#[no_mangle]
+pub extern fn indy_get_current_error_details(error_details_json_p: *mut *const c_char) {
+    if error_details_json_p.is_null() {
+        return;
+    }
+
+    unsafe { *error_details_json_p = errors::get_current_error_details(); }
 }
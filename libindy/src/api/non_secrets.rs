@@ -99,6 +99,7 @@ pub extern fn indy_update_wallet_record_value(command_handle: i32,
                 type_,
                 id,
                 value,
+                None,
                 Box::new(move |result| {
                     let err = result_to_err_code!(result);
                     trace!("indy_update_wallet_record_value:");
@@ -153,6 +154,7 @@ pub extern fn indy_update_wallet_record_tags(command_handle: i32,
                 type_,
                 id,
                 tags_json,
+                None,
                 Box::new(move |result| {
                     let err = result_to_err_code!(result);
                     trace!("indy_update_wallet_record_tags:");
@@ -167,6 +169,178 @@ pub extern fn indy_update_wallet_record_tags(command_handle: i32,
     res
 }
 
+/// Update a non-secret wallet record value only if the record wasn't changed
+/// since the expected version (see retrieveVersion option of indy_get_wallet_record)
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// type_: allows to separate different record types collections
+/// id: the id of record
+/// value: the new value of record
+/// expected_version: the record version the new value is based on
+///
+/// #Errors
+/// WalletConflict if the record was changed since the expected version
+#[no_mangle]
+pub extern fn indy_update_wallet_record_value_with_version(command_handle: i32,
+                                                           wallet_handle: i32,
+                                                           type_: *const c_char,
+                                                           id: *const c_char,
+                                                           value: *const c_char,
+                                                           expected_version: i64,
+                                                           cb: Option<extern fn(command_handle_: i32, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_update_wallet_record_value_with_version: >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, value: {:?}, expected_version: {:?}",
+           wallet_handle, type_, id, value, expected_version);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(id, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(value, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    if expected_version < 1 {
+        return ErrorCode::CommonInvalidParam6;
+    }
+
+    trace!("indy_update_wallet_record_value_with_version: entities >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, value: {:?}, expected_version: {:?}",
+           wallet_handle, type_, id, value, expected_version);
+
+    let result = CommandExecutor::instance()
+        .send(Command::NonSecrets(
+            NonSecretsCommand::UpdateRecordValue(
+                wallet_handle,
+                type_,
+                id,
+                value,
+                Some(expected_version),
+                Box::new(move |result| {
+                    let err = result_to_err_code!(result);
+                    trace!("indy_update_wallet_record_value_with_version:");
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_update_wallet_record_value_with_version: <<< res: {:?}", res);
+
+    res
+}
+
+/// Update a non-secret wallet record tags only if the record wasn't changed
+/// since the expected version (see retrieveVersion option of indy_get_wallet_record)
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// type_: allows to separate different record types collections
+/// id: the id of record
+/// tags_json: the record tags json (see indy_update_wallet_record_tags)
+/// expected_version: the record version the new tags are based on
+///
+/// #Errors
+/// WalletConflict if the record was changed since the expected version
+#[no_mangle]
+pub extern fn indy_update_wallet_record_tags_with_version(command_handle: i32,
+                                                          wallet_handle: i32,
+                                                          type_: *const c_char,
+                                                          id: *const c_char,
+                                                          tags_json: *const c_char,
+                                                          expected_version: i64,
+                                                          cb: Option<extern fn(command_handle_: i32, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_update_wallet_record_tags_with_version: >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, tags_json: {:?}, expected_version: {:?}",
+           wallet_handle, type_, id, tags_json, expected_version);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(id, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(tags_json, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    if expected_version < 1 {
+        return ErrorCode::CommonInvalidParam6;
+    }
+
+    trace!("indy_update_wallet_record_tags_with_version: entities >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, tags_json: {:?}, expected_version: {:?}",
+           wallet_handle, type_, id, tags_json, expected_version);
+
+    let result = CommandExecutor::instance()
+        .send(Command::NonSecrets(
+            NonSecretsCommand::UpdateRecordTags(
+                wallet_handle,
+                type_,
+                id,
+                tags_json,
+                Some(expected_version),
+                Box::new(move |result| {
+                    let err = result_to_err_code!(result);
+                    trace!("indy_update_wallet_record_tags_with_version:");
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_update_wallet_record_tags_with_version: <<< res: {:?}", res);
+
+    res
+}
+
+/// Set or remove a non-secret wallet record expiry time
+///
+/// Expired record isn't returned by get and search calls and will be deleted
+/// on next wallet opening or indy_wallet_purge_expired call.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// type_: allows to separate different record types collections
+/// id: the id of record
+/// expires_at: unix timestamp in seconds after that record expires
+///             or -1 to remove record expiry
+#[no_mangle]
+pub extern fn indy_update_wallet_record_expiry(command_handle: i32,
+                                               wallet_handle: i32,
+                                               type_: *const c_char,
+                                               id: *const c_char,
+                                               expires_at: i64,
+                                               cb: Option<extern fn(command_handle_: i32, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_update_wallet_record_expiry: >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, expires_at: {:?}", wallet_handle, type_, id, expires_at);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(id, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    let expires_at = if expires_at == -1 {
+        None
+    } else if expires_at >= 0 {
+        Some(expires_at)
+    } else {
+        return ErrorCode::CommonInvalidParam5;
+    };
+
+    trace!("indy_update_wallet_record_expiry: entities >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, expires_at: {:?}", wallet_handle, type_, id, expires_at);
+
+    let result = CommandExecutor::instance()
+        .send(Command::NonSecrets(
+            NonSecretsCommand::UpdateRecordExpiry(
+                wallet_handle,
+                type_,
+                id,
+                expires_at,
+                Box::new(move |result| {
+                    let err = result_to_err_code!(result);
+                    trace!("indy_update_wallet_record_expiry:");
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_update_wallet_record_expiry: <<< res: {:?}", res);
+
+    res
+}
+
 /// Add new tags to the wallet record
 ///
 /// #Params
@@ -321,7 +495,8 @@ pub extern fn indy_delete_wallet_record(command_handle: i32,
 ///  {
 ///    retrieveType: (optional, false by default) Retrieve record type,
 ///    retrieveValue: (optional, true by default) Retrieve record value,
-///    retrieveTags: (optional, false by default) Retrieve record tags
+///    retrieveTags: (optional, false by default) Retrieve record tags,
+///    retrieveVersion: (optional, false by default) Retrieve record version
 ///  }
 /// #Returns
 /// wallet record json:
@@ -330,6 +505,7 @@ pub extern fn indy_delete_wallet_record(command_handle: i32,
 ///   type: "Some type", // present only if retrieveType set to true
 ///   value: "Some value", // present only if retrieveValue set to true
 ///   tags: <tags json>, // present only if retrieveTags set to true
+///   version: <int>, // present only if retrieveVersion set to true
 /// }
 #[no_mangle]
 pub  extern fn indy_get_wallet_record(command_handle: i32,
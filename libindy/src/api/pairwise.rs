@@ -161,6 +161,11 @@ pub  extern fn indy_list_pairwise(command_handle: i32,
 ///
 /// #Returns
 /// pairwise_info_json: did info associated with their did
+/// {
+///     "my_did": string, // current my DID
+///     "my_dids": [string], // all my DIDs used in this pairwise, present only if more than one my DID was used
+///     "metadata": string, // present only if set
+/// }
 ///
 /// #Errors
 /// Common*
@@ -245,4 +250,250 @@ pub  extern fn indy_set_pairwise_metadata(command_handle: i32,
     trace!("indy_set_pairwise_metadata: <<< res: {:?}", res);
 
     res
-}
\ No newline at end of file
+}
+
+/// Adds my DID to the pairwise associated with their DID and makes it the current one.
+/// Previously used my DIDs stay in the pairwise "my_dids" list, so rotated connection DIDs can be tracked.
+///
+/// #Params
+/// wallet_handle: wallet handler (created by open_wallet).
+/// command_handle: command handle to map callback to user context.
+/// their_did: encoded Did
+/// my_did: encoded Did stored in the wallet
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_add_pairwise_my_did(command_handle: i32,
+                                        wallet_handle: i32,
+                                        their_did: *const c_char,
+                                        my_did: *const c_char,
+                                        cb: Option<extern fn(xcommand_handle: i32,
+                                                             err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_add_pairwise_my_did: >>> wallet_handle: {:?}, their_did: {:?}, my_did: {:?}", wallet_handle, their_did, my_did);
+
+    check_useful_c_str!(their_did, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(my_did, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_add_pairwise_my_did: entities >>> wallet_handle: {:?}, their_did: {:?}, my_did: {:?}", wallet_handle, their_did, my_did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pairwise(PairwiseCommand::AddPairwiseMyDid(
+            wallet_handle,
+            their_did,
+            my_did,
+            Box::new(move |result| {
+                let err = result_to_err_code!(result);
+                trace!("indy_add_pairwise_my_did:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_add_pairwise_my_did: <<< res: {:?}", res);
+
+    res
+}
+
+/// Deletes pairwise associated with their DID.
+///
+/// #Params
+/// wallet_handle: wallet handler (created by open_wallet).
+/// command_handle: command handle to map callback to user context.
+/// their_did: encoded Did
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_delete_pairwise(command_handle: i32,
+                                    wallet_handle: i32,
+                                    their_did: *const c_char,
+                                    cb: Option<extern fn(xcommand_handle: i32,
+                                                         err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_delete_pairwise: >>> wallet_handle: {:?}, their_did: {:?}", wallet_handle, their_did);
+
+    check_useful_c_str!(their_did, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_delete_pairwise: entities >>> wallet_handle: {:?}, their_did: {:?}", wallet_handle, their_did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pairwise(PairwiseCommand::DeletePairwise(
+            wallet_handle,
+            their_did,
+            Box::new(move |result| {
+                let err = result_to_err_code!(result);
+                trace!("indy_delete_pairwise:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_delete_pairwise: <<< res: {:?}", res);
+
+    res
+}
+
+/// Search for pairwise stored in the wallet.
+///
+/// Pairwise records created by older versions of libindy get their tags when the wallet is opened in read-write mode.
+///
+/// #Params
+/// wallet_handle: wallet handler (created by open_wallet).
+/// command_handle: command handle to map callback to user context.
+/// query_json: Wql query filter for pairwise searching based on tags:
+/// {
+///     "my_did": string, // current my DID
+///     "their_did": string,
+///     "metadata": string, // exact metadata value
+/// }
+/// See wallet search wql documentation for query syntax.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// search_handle: Search handle that can be used later to fetch records by small batches
+///     (with indy_fetch_pairwise_search_next_records)
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_search_pairwise(command_handle: i32,
+                                    wallet_handle: i32,
+                                    query_json: *const c_char,
+                                    cb: Option<extern fn(xcommand_handle: i32,
+                                                         err: ErrorCode,
+                                                         search_handle: i32)>) -> ErrorCode {
+    trace!("indy_search_pairwise: >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+    check_useful_c_str!(query_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_search_pairwise: entities >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pairwise(PairwiseCommand::SearchPairwise(
+            wallet_handle,
+            query_json,
+            Box::new(move |result| {
+                let (err, search_handle) = result_to_err_code_1!(result, 0);
+                trace!("indy_search_pairwise: search_handle: {:?}", search_handle);
+                cb(command_handle, err, search_handle)
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_search_pairwise: <<< res: {:?}", res);
+
+    res
+}
+
+/// Fetch next pairwise records for pairwise search.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// search_handle: pairwise search handle (created by indy_search_pairwise)
+/// count: Count of records to fetch
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// pairwise records json:
+/// {
+///   totalCount: <int>,
+///   records: [{ // not present if there are no more records
+///       my_did: string, // current my DID
+///       their_did: string,
+///       my_dids: [string], // all my DIDs used in this pairwise
+///       metadata: string, // present only if set
+///   }],
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_fetch_pairwise_search_next_records(command_handle: i32,
+                                                       search_handle: i32,
+                                                       count: usize,
+                                                       cb: Option<extern fn(xcommand_handle: i32,
+                                                                            err: ErrorCode,
+                                                                            pairwise_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_fetch_pairwise_search_next_records: >>> search_handle: {:?}, count: {:?}", search_handle, count);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_fetch_pairwise_search_next_records: entities >>> search_handle: {:?}, count: {:?}", search_handle, count);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pairwise(PairwiseCommand::FetchPairwiseSearchNextRecords(
+            search_handle,
+            count,
+            Box::new(move |result| {
+                let (err, pairwise_json) = result_to_err_code_1!(result, String::new());
+                trace!("indy_fetch_pairwise_search_next_records: pairwise_json: {:?}", pairwise_json);
+                let pairwise_json = CStringUtils::string_to_cstring(pairwise_json);
+                cb(command_handle, err, pairwise_json.as_ptr())
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_fetch_pairwise_search_next_records: <<< res: {:?}", res);
+
+    res
+}
+
+/// Close pairwise search (make search handle invalid)
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// search_handle: pairwise search handle (created by indy_search_pairwise)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_close_pairwise_search(command_handle: i32,
+                                          search_handle: i32,
+                                          cb: Option<extern fn(xcommand_handle: i32,
+                                                               err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_close_pairwise_search: >>> search_handle: {:?}", search_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_close_pairwise_search: entities >>> search_handle: {:?}", search_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pairwise(PairwiseCommand::ClosePairwiseSearch(
+            search_handle,
+            Box::new(move |result| {
+                let err = result_to_err_code!(result);
+                trace!("indy_close_pairwise_search:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_close_pairwise_search: <<< res: {:?}", res);
+
+    res
+}
@@ -7,6 +7,7 @@ use commands::payments::PaymentsCommand;
 use errors::ToErrorCode;
 use services::payments::PaymentsMethodCBs;
 use utils::cstring::CStringUtils;
+use utils::byte_array::vec_to_pointer;
 
 /// Create the payment address for this payment method.
 ///
@@ -84,6 +85,7 @@ pub type AddRequestFeesCB = extern fn(command_handle: i32,
 ///      input: <str>, // UTXO input
 ///      amount: <int>, // amount of tokens in this input
 ///      extra: <str>, // optional data from payment transaction
+///      paymentAddress: <str>, // optional payment address of the UTXO, UTXOs without it aren't recorded in payment history
 ///   }]
 pub type ParseResponseWithFeesCB = extern fn(command_handle: i32,
                                              resp_json: *const c_char,
@@ -135,6 +137,10 @@ pub type ParseGetUTXOResponseCB = extern fn(command_handle: i32,
 /// Format of inputs is specific for payment method. Usually it should reference payment transaction
 /// with at least one output that corresponds to payment address that user owns.
 ///
+/// All inputs and outputs must belong to the same payment method, otherwise PaymentMixedMethodsError
+/// is returned and offending entries can be obtained with indy_get_current_error_details inside of callback.
+/// Use indy_build_split_payment_req to build one request per payment method instead.
+///
 /// #Params
 /// wallet_handle: wallet handle
 /// submitter_did : DID of request sender
@@ -172,6 +178,7 @@ pub type BuildPaymentReqCB = extern fn(command_handle: i32,
 ///      input: <str>, // UTXO input
 ///      amount: <int>, // amount of tokens in this input
 ///      extra: <str>, // optional data from payment transaction
+///      paymentAddress: <str>, // optional payment address of the UTXO, UTXOs without it aren't recorded in payment history
 ///   }]
 pub type ParsePaymentResponseCB = extern fn(command_handle: i32,
                                             resp_json: *const c_char,
@@ -214,6 +221,7 @@ pub type BuildMintReqCB = extern fn(command_handle: i32,
 ///   .................
 ///   txnTypeN: amountN,
 /// }
+///   Payment method may support its own fee rules object in place of amount.
 ///
 /// # Return
 /// set_txn_fees_json - Indy request for setting fees for transactions in the ledger
@@ -255,15 +263,124 @@ pub type BuildGetTxnFeesReqCB = extern fn(command_handle: i32,
 ///   .................
 ///   txnTypeN: amountN,
 /// }
+///   Payment method may support its own fee rules object in place of amount.
 pub type ParseGetTxnFeesResponseCB = extern fn(command_handle: i32,
                                                resp_json: *const c_char,
                                                cb: Option<extern fn(command_handle_: i32,
                                                                     err: ErrorCode,
                                                                     fees_json: *const c_char) -> ErrorCode>) -> ErrorCode;
 
+/// Builds Indy request for information to verify the payment receipt
+///
+/// # Params
+/// command_handle
+/// wallet_handle: wallet handle
+/// submitter_did : DID of request sender
+/// receipt: payment receipt to verify
+///
+/// # Return
+/// verify_txn_json - Indy request for verification receipt
+pub type BuildVerifyPaymentReqCB = extern fn(command_handle: i32,
+                                             wallet_handle: i32,
+                                             submitter_did: *const c_char,
+                                             receipt: *const c_char,
+                                             cb: Option<extern fn(command_handle_: i32,
+                                                                  err: ErrorCode,
+                                                                  verify_txn_json: *const c_char) -> ErrorCode>) -> ErrorCode;
+
+/// Parses Indy response with information to verify receipt
+///
+/// # Params
+/// command_handle
+/// resp_json: response of the ledger for verify txn
+///
+/// # Return
+/// txn_json: {
+///     sources: [<str>, ]
+///     receipts: [ {
+///         recipient: <str>, // payment address of recipient
+///         receipt: <str>, // receipt that can be used for payment referencing and verification
+///         amount: <int>, // amount
+///         extra: <str>, // optional data from payment transaction
+///     }, ]
+///     extra: <str>, //optional data
+/// }
+pub type ParseVerifyPaymentResponseCB = extern fn(command_handle: i32,
+                                                  resp_json: *const c_char,
+                                                  cb: Option<extern fn(command_handle_: i32,
+                                                                       err: ErrorCode,
+                                                                       txn_json: *const c_char) -> ErrorCode>) -> ErrorCode;
+
+/// Signs a message with the key of payment address to prove control of the address.
+///
+/// # Params
+/// command_handle
+/// wallet_handle: wallet handle where keys of payment address are stored
+/// address: payment address to sign with
+/// message_raw: a pointer to first byte of message to be signed
+/// message_len: a message length
+///
+/// # Return
+/// signature_raw: a pointer to first byte of signature
+/// signature_len: a signature length
+pub type SignWithAddressCB = extern fn(command_handle: i32,
+                                       wallet_handle: i32,
+                                       address: *const c_char,
+                                       message_raw: *const u8,
+                                       message_len: u32,
+                                       cb: Option<extern fn(command_handle_: i32,
+                                                            err: ErrorCode,
+                                                            signature_raw: *const u8,
+                                                            signature_len: u32) -> ErrorCode>) -> ErrorCode;
+
+/// Verifies a message signature made with the key of payment address.
+///
+/// # Params
+/// command_handle
+/// address: payment address of the signer
+/// message_raw: a pointer to first byte of message that has been signed
+/// message_len: a message length
+/// signature_raw: a pointer to first byte of signature to be verified
+/// signature_len: a signature length
+///
+/// # Return
+/// valid: true - if signature is valid, false - otherwise
+pub type VerifyWithAddressCB = extern fn(command_handle: i32,
+                                         address: *const c_char,
+                                         message_raw: *const u8,
+                                         message_len: u32,
+                                         signature_raw: *const u8,
+                                         signature_len: u32,
+                                         cb: Option<extern fn(command_handle_: i32,
+                                                              err: ErrorCode,
+                                                              valid: bool) -> ErrorCode>) -> ErrorCode;
+
+/// Computes the fee the ledger will charge for the request.
+/// Allows payment methods to apply fee rules that depend on the request
+/// (for example on submitter role or request size) instead of flat fee of txn type.
+///
+/// # Params
+/// command_handle
+/// wallet_handle: wallet handle
+/// submitter_did: DID of request sender
+/// req_json: initial transaction request as json
+/// fees_json: fees schedule as returned by parse_get_txn_fees_response
+///
+/// # Return
+/// fee: amount of tokens to pay for the request
+pub type GetRequestFeeCB = extern fn(command_handle: i32,
+                                     wallet_handle: i32,
+                                     submitter_did: *const c_char,
+                                     req_json: *const c_char,
+                                     fees_json: *const c_char,
+                                     cb: Option<extern fn(command_handle_: i32,
+                                                          err: ErrorCode,
+                                                          fee: u64) -> ErrorCode>) -> ErrorCode;
+
 /// Register custom payment implementation.
 ///
 /// It allows library user to provide custom payment method implementation as set of handlers.
+/// Use indy_register_payment_method_ex to register optional handlers too.
 ///
 /// #Params
 /// command_handle: Command handle to map callback to caller context.
@@ -295,6 +412,126 @@ pub extern fn indy_register_payment_method(command_handle: i32,
                                                                 err: ErrorCode)>) -> ErrorCode {
     trace!("indy_register_payment_method: >>> payment_method: {:?}", payment_method);
 
+    let res = _register_payment_method(command_handle,
+                                       payment_method,
+                                       create_payment_address,
+                                       add_request_fees,
+                                       parse_response_with_fees,
+                                       build_get_utxo_request,
+                                       parse_get_utxo_response,
+                                       build_payment_req,
+                                       parse_payment_response,
+                                       build_mint_req,
+                                       build_set_txn_fees_req,
+                                       build_get_txn_fees_req,
+                                       parse_get_txn_fees_response,
+                                       None,
+                                       None,
+                                       None,
+                                       None,
+                                       None,
+                                       cb,
+                                       ErrorCode::CommonInvalidParam14);
+
+    trace!("indy_register_payment_method: <<< res: {:?}", res);
+
+    res
+}
+
+/// Register custom payment implementation with optional handlers.
+///
+/// It allows library user to provide custom payment method implementation as set of handlers.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// payment_method: The type of payment method also used as sub-prefix for fully resolvable payment address format ("sov" - for example)
+/// create_payment_address: "create_payment_address" operation handler
+/// add_request_fees: "add_request_fees" operation handler
+/// build_get_utxo_request: "build_get_utxo_request" operation handler
+/// parse_get_utxo_response: "parse_get_utxo_response" operation handler
+/// build_payment_req: "build_payment_req" operation handler
+/// build_mint_req: "build_mint_req" operation handler
+/// build_verify_payment_req: optional "build_verify_payment_req" operation handler
+/// parse_verify_payment_response: optional "parse_verify_payment_response" operation handler
+///   Both handlers should be passed to support payment verification, NULL for both otherwise
+/// sign_with_address: optional "sign_with_address" operation handler
+/// verify_with_address: optional "verify_with_address" operation handler
+///   Both handlers should be passed to support signing with payment address, NULL for both otherwise
+/// get_request_fee: optional "get_request_fee" operation handler
+///   If NULL the fee of request is taken from fees schedule by txn type, so schedule must contain flat amounts only
+///
+/// #Returns
+/// Error code
+#[no_mangle]
+pub extern fn indy_register_payment_method_ex(command_handle: i32,
+                                              payment_method: *const c_char,
+                                              create_payment_address: Option<CreatePaymentAddressCB>,
+                                              add_request_fees: Option<AddRequestFeesCB>,
+                                              parse_response_with_fees: Option<ParseResponseWithFeesCB>,
+                                              build_get_utxo_request: Option<BuildGetUTXORequestCB>,
+                                              parse_get_utxo_response: Option<ParseGetUTXOResponseCB>,
+                                              build_payment_req: Option<BuildPaymentReqCB>,
+                                              parse_payment_response: Option<ParsePaymentResponseCB>,
+                                              build_mint_req: Option<BuildMintReqCB>,
+                                              build_set_txn_fees_req: Option<BuildSetTxnFeesReqCB>,
+                                              build_get_txn_fees_req: Option<BuildGetTxnFeesReqCB>,
+                                              parse_get_txn_fees_response: Option<ParseGetTxnFeesResponseCB>,
+                                              build_verify_payment_req: Option<BuildVerifyPaymentReqCB>,
+                                              parse_verify_payment_response: Option<ParseVerifyPaymentResponseCB>,
+                                              sign_with_address: Option<SignWithAddressCB>,
+                                              verify_with_address: Option<VerifyWithAddressCB>,
+                                              get_request_fee: Option<GetRequestFeeCB>,
+                                              cb: Option<extern fn(command_handle_: i32,
+                                                                   err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_payment_method_ex: >>> payment_method: {:?}", payment_method);
+
+    let res = _register_payment_method(command_handle,
+                                       payment_method,
+                                       create_payment_address,
+                                       add_request_fees,
+                                       parse_response_with_fees,
+                                       build_get_utxo_request,
+                                       parse_get_utxo_response,
+                                       build_payment_req,
+                                       parse_payment_response,
+                                       build_mint_req,
+                                       build_set_txn_fees_req,
+                                       build_get_txn_fees_req,
+                                       parse_get_txn_fees_response,
+                                       build_verify_payment_req,
+                                       parse_verify_payment_response,
+                                       sign_with_address,
+                                       verify_with_address,
+                                       get_request_fee,
+                                       cb,
+                                       ErrorCode::CommonInvalidParam19);
+
+    trace!("indy_register_payment_method_ex: <<< res: {:?}", res);
+
+    res
+}
+
+fn _register_payment_method(command_handle: i32,
+                            payment_method: *const c_char,
+                            create_payment_address: Option<CreatePaymentAddressCB>,
+                            add_request_fees: Option<AddRequestFeesCB>,
+                            parse_response_with_fees: Option<ParseResponseWithFeesCB>,
+                            build_get_utxo_request: Option<BuildGetUTXORequestCB>,
+                            parse_get_utxo_response: Option<ParseGetUTXOResponseCB>,
+                            build_payment_req: Option<BuildPaymentReqCB>,
+                            parse_payment_response: Option<ParsePaymentResponseCB>,
+                            build_mint_req: Option<BuildMintReqCB>,
+                            build_set_txn_fees_req: Option<BuildSetTxnFeesReqCB>,
+                            build_get_txn_fees_req: Option<BuildGetTxnFeesReqCB>,
+                            parse_get_txn_fees_response: Option<ParseGetTxnFeesResponseCB>,
+                            build_verify_payment_req: Option<BuildVerifyPaymentReqCB>,
+                            parse_verify_payment_response: Option<ParseVerifyPaymentResponseCB>,
+                            sign_with_address: Option<SignWithAddressCB>,
+                            verify_with_address: Option<VerifyWithAddressCB>,
+                            get_request_fee: Option<GetRequestFeeCB>,
+                            cb: Option<extern fn(command_handle_: i32,
+                                                 err: ErrorCode)>,
+                            cb_err: ErrorCode) -> ErrorCode {
     check_useful_c_str!(payment_method, ErrorCode::CommonInvalidParam2);
     check_useful_c_callback!(create_payment_address, ErrorCode::CommonInvalidParam3);
     check_useful_c_callback!(add_request_fees, ErrorCode::CommonInvalidParam4);
@@ -307,9 +544,17 @@ pub extern fn indy_register_payment_method(command_handle: i32,
     check_useful_c_callback!(build_set_txn_fees_req, ErrorCode::CommonInvalidParam11);
     check_useful_c_callback!(build_get_txn_fees_req, ErrorCode::CommonInvalidParam12);
     check_useful_c_callback!(parse_get_txn_fees_response, ErrorCode::CommonInvalidParam13);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam14);
+    check_useful_c_callback!(cb, cb_err);
 
-    trace!("indy_register_payment_method: entities >>> payment_method: {:?}", payment_method);
+    if build_verify_payment_req.is_some() != parse_verify_payment_response.is_some() {
+        return if build_verify_payment_req.is_none() { ErrorCode::CommonInvalidParam14 } else { ErrorCode::CommonInvalidParam15 };
+    }
+
+    if sign_with_address.is_some() != verify_with_address.is_some() {
+        return if sign_with_address.is_none() { ErrorCode::CommonInvalidParam16 } else { ErrorCode::CommonInvalidParam17 };
+    }
+
+    trace!("_register_payment_method: entities >>> payment_method: {:?}", payment_method);
 
     let cbs = PaymentsMethodCBs::new(
         create_payment_address,
@@ -322,7 +567,12 @@ pub extern fn indy_register_payment_method(command_handle: i32,
         build_mint_req,
         build_set_txn_fees_req,
         build_get_txn_fees_req,
-        parse_get_txn_fees_response
+        parse_get_txn_fees_response,
+        build_verify_payment_req,
+        parse_verify_payment_response,
+        sign_with_address,
+        verify_with_address,
+        get_request_fee
     );
     let result =
         CommandExecutor::instance().send(
@@ -335,9 +585,48 @@ pub extern fn indy_register_payment_method(command_handle: i32,
                     }))
             ));
 
+    result_to_err_code!(result)
+}
+
+/// Lists payment methods registered in libindy with operations each of them supports.
+///
+/// #Params
+/// command_handle: command handle to map callback to context
+///
+/// #Returns
+/// payment_methods_json - json array of registered payment methods ordered by name:
+///   [{
+///     method: <str>, // name of payment method
+///     capabilities: [<str>, ...], // names of operations payment method supports, for example
+///                                 // "build_verify_payment_req", "parse_verify_payment_response",
+///                                 // "sign_with_address", "verify_with_address", "get_request_fee" for optional ones
+///   }]
+#[no_mangle]
+pub extern fn indy_list_payment_methods(command_handle: i32,
+                                        cb: Option<extern fn(command_handle_: i32,
+                                                             err: ErrorCode,
+                                                             payment_methods_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_list_payment_methods: >>>");
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam2);
+
+    trace!("indy_list_payment_methods: entities >>>");
+
+    let result =
+        CommandExecutor::instance().send(
+            Command::Payments(
+                PaymentsCommand::ListMethods(
+                    Box::new(move |result| {
+                        let (err, payment_methods_json) = result_to_err_code_1!(result, String::new());
+                        trace!("indy_list_payment_methods: payment_methods_json: {:?}", payment_methods_json);
+                        let payment_methods_json = CStringUtils::string_to_cstring(payment_methods_json);
+                        cb(command_handle, err, payment_methods_json.as_ptr());
+                    }))
+            ));
+
     let res = result_to_err_code!(result);
 
-    trace!("indy_register_payment_method: <<< res: {:?}", res);
+    trace!("indy_list_payment_methods: <<< res: {:?}", res);
 
     res
 }
@@ -729,6 +1018,72 @@ pub extern fn indy_build_payment_req(command_handle: i32,
     res
 }
 
+/// Builds Indy requests for doing tokens payment with inputs and outputs of several payment methods.
+///
+/// Inputs and outputs are split by payment method and request for each part is built
+/// in the same way as indy_build_payment_req does. Each payment method should have both
+/// inputs and outputs, otherwise PaymentMixedMethodsError is returned with details of
+/// the offending entries available through indy_get_current_error_details.
+///
+/// #Params
+/// wallet_handle: wallet handle
+/// submitter_did : DID of request sender
+/// inputs_json: The list of UTXO inputs as json array:
+///   ["input1", ...]
+///   Note that each input should reference paymentAddress
+/// outputs_json: The list of UTXO outputs as json array:
+///   [{
+///     paymentAddress: <str>, // payment address used as output
+///     amount: <int>, // amount of tokens to transfer to this payment address
+///     extra: <str>, // optional data
+///   }]
+///
+/// #Returns
+/// payment_reqs_json - Indy requests for doing tokens payment ordered by payment method:
+///   [{
+///     paymentMethod: <str>, // payment method to use for submitting and parsing response of request
+///     request: <str>, // Indy request for doing tokens payment of this payment method
+///   }]
+#[no_mangle]
+pub extern fn indy_build_split_payment_req(command_handle: i32,
+                                           wallet_handle: i32,
+                                           submitter_did: *const c_char,
+                                           inputs_json: *const c_char,
+                                           outputs_json: *const c_char,
+                                           cb: Option<extern fn(command_handle_: i32,
+                                                                err: ErrorCode,
+                                                                payment_reqs_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_build_split_payment_req: >>> wallet_handle: {:?}, submitter_did: {:?}, inputs_json: {:?}, outputs_json: {:?}", wallet_handle, submitter_did, inputs_json, outputs_json);
+    check_useful_c_str!(submitter_did, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(inputs_json, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(outputs_json, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_build_split_payment_req: entities >>> wallet_handle: {:?}, submitter_did: {:?}, inputs_json: {:?}, outputs_json: {:?}", wallet_handle, submitter_did, inputs_json, outputs_json);
+
+    let result =
+        CommandExecutor::instance().send(
+            Command::Payments(
+                PaymentsCommand::BuildSplitPaymentReq(
+                    wallet_handle,
+                    submitter_did,
+                    inputs_json,
+                    outputs_json,
+                    Box::new(move |result| {
+                        let (err, payment_reqs_json) = result_to_err_code_1!(result, String::new());
+                        trace!("indy_build_split_payment_req: payment_reqs_json: {:?}", payment_reqs_json);
+                        let payment_reqs_json = CStringUtils::string_to_cstring(payment_reqs_json);
+                        cb(command_handle, err, payment_reqs_json.as_ptr());
+                    }))
+            ));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_build_split_payment_req: <<< res: {:?}", res);
+
+    res
+}
+
 /// Parses response for Indy request for payment txn.
 ///
 /// #Params
@@ -779,6 +1134,207 @@ pub extern fn indy_parse_payment_response(command_handle: i32,
     res
 }
 
+/// Parses response for Indy request for payment txn and records
+/// the resulting payments in the wallet payment history.
+///
+/// History is recorded only if the payment method parsed the response successfully.
+/// Each UTXO the payment method parsed from the response becomes a history entry: UTXOs of
+/// addresses owned by the wallet are recorded as "received" (change included), all other
+/// UTXOs are recorded as "sent". UTXOs without paymentAddress can't be attributed and are skipped.
+///
+/// Entries are identified by payment method and UTXO, so parsing the same response again doesn't
+/// record the payment twice. The payment has already happened on the ledger, so utxo_json is returned
+/// even if history couldn't be recorded; parsing the response again records the missing entries.
+///
+/// #Params
+/// command_handle
+/// wallet_handle: wallet handle where to record payment history
+/// payment_method
+/// resp_json: response for Indy request for payment txn
+/// history_json: context of the payment as json:
+///   {
+///     submitterDid: <str>, // DID of request sender
+///     txnType: <str>, // optional type of the transaction
+///   }
+///   Amounts and addresses are taken from the parsed response, ledger sequence number from resp_json.
+///
+/// #Returns
+/// utxo_json - parsed (payment method and node version agnostic) utxo info as json:
+///   [{
+///      input: <str>, // UTXO input
+///      amount: <int>, // amount of tokens in this input
+///      extra: <str>, // optional data from payment transaction
+///      paymentAddress: <str>, // payment address of the UTXO, required to record it in history
+///   }]
+#[no_mangle]
+pub extern fn indy_parse_payment_response_with_history(command_handle: i32,
+                                                       wallet_handle: i32,
+                                                       payment_method: *const c_char,
+                                                       resp_json: *const c_char,
+                                                       history_json: *const c_char,
+                                                       cb: Option<extern fn(command_handle_: i32,
+                                                                            err: ErrorCode,
+                                                                            utxo_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_parse_payment_response_with_history: >>> wallet_handle: {:?}, payment_method: {:?}, resp_json: {:?}, history_json: {:?}",
+           wallet_handle, payment_method, resp_json, history_json);
+    check_useful_c_str!(payment_method, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(resp_json, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(history_json, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_parse_payment_response_with_history: entities >>> wallet_handle: {:?}, payment_method: {:?}, resp_json: {:?}, history_json: {:?}",
+           wallet_handle, payment_method, resp_json, history_json);
+
+    let result =
+        CommandExecutor::instance().send(
+            Command::Payments(
+                PaymentsCommand::ParsePaymentResponseWithHistory(
+                    wallet_handle,
+                    payment_method,
+                    resp_json,
+                    history_json,
+                    Box::new(move |result| {
+                        let (err, utxo_json) = result_to_err_code_1!(result, String::new());
+                        trace!("indy_parse_payment_response_with_history: utxo_json: {:?}", utxo_json);
+                        let utxo_json = CStringUtils::string_to_cstring(utxo_json);
+                        cb(command_handle, err, utxo_json.as_ptr());
+                    }))
+            ));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_parse_payment_response_with_history: <<< res: {:?}", res);
+
+    res
+}
+
+/// Parses response for Indy request with fees and records
+/// the resulting payments in the wallet payment history.
+///
+/// See indy_parse_payment_response_with_history for the way history entries are built.
+///
+/// #Params
+/// command_handle
+/// wallet_handle: wallet handle where to record payment history
+/// payment_method
+/// resp_json: response for Indy request with fees
+/// history_json: context of the payment as json (see indy_parse_payment_response_with_history)
+///
+/// #Returns
+/// utxo_json - parsed (payment method and node version agnostic) utxo info as json:
+///   [{
+///      input: <str>, // UTXO input
+///      amount: <int>, // amount of tokens in this input
+///      extra: <str>, // optional data from payment transaction
+///      paymentAddress: <str>, // payment address of the UTXO, required to record it in history
+///   }]
+#[no_mangle]
+pub extern fn indy_parse_response_with_fees_with_history(command_handle: i32,
+                                                         wallet_handle: i32,
+                                                         payment_method: *const c_char,
+                                                         resp_json: *const c_char,
+                                                         history_json: *const c_char,
+                                                         cb: Option<extern fn(command_handle_: i32,
+                                                                              err: ErrorCode,
+                                                                              utxo_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_parse_response_with_fees_with_history: >>> wallet_handle: {:?}, payment_method: {:?}, resp_json: {:?}, history_json: {:?}",
+           wallet_handle, payment_method, resp_json, history_json);
+    check_useful_c_str!(payment_method, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(resp_json, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(history_json, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_parse_response_with_fees_with_history: entities >>> wallet_handle: {:?}, payment_method: {:?}, resp_json: {:?}, history_json: {:?}",
+           wallet_handle, payment_method, resp_json, history_json);
+
+    let result =
+        CommandExecutor::instance().send(
+            Command::Payments(
+                PaymentsCommand::ParseResponseWithFeesWithHistory(
+                    wallet_handle,
+                    payment_method,
+                    resp_json,
+                    history_json,
+                    Box::new(move |result| {
+                        let (err, utxo_json) = result_to_err_code_1!(result, String::new());
+                        trace!("indy_parse_response_with_fees_with_history: utxo_json: {:?}", utxo_json);
+                        let utxo_json = CStringUtils::string_to_cstring(utxo_json);
+                        cb(command_handle, err, utxo_json.as_ptr());
+                    }))
+            ));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_parse_response_with_fees_with_history: <<< res: {:?}", res);
+
+    res
+}
+
+/// Searches payment history recorded in the wallet.
+///
+/// #Params
+/// command_handle
+/// wallet_handle: wallet handle
+/// query_json: Wallet query (WQL) over payment history tags:
+///   {
+///     "paymentMethod": <str>,
+///     "direction": "sent" | "received",
+///     "input": <str>,
+///     "paymentAddress": <str>,
+///     "txnType": <str>,
+///     "seqNo": <str>,
+///     "amount": <str>,
+///     "submitterDid": <str>,
+///   }
+///   Use "{}" to get the whole history.
+///
+/// #Returns
+/// history_json - list of payment history entries:
+///   [{
+///      paymentMethod: <str>,
+///      direction: <str>,
+///      input: <str>, // UTXO created by the payment
+///      paymentAddress: <str>, // payment address of the UTXO
+///      amount: <int>,
+///      txnType: <str>, // optional
+///      seqNo: <int>, // optional
+///      submitterDid: <str>,
+///      extra: <str>, // optional
+///   }]
+#[no_mangle]
+pub extern fn indy_search_payment_history(command_handle: i32,
+                                          wallet_handle: i32,
+                                          query_json: *const c_char,
+                                          cb: Option<extern fn(command_handle_: i32,
+                                                               err: ErrorCode,
+                                                               history_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_search_payment_history: >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+    check_useful_c_str!(query_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_search_payment_history: entities >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+    let result =
+        CommandExecutor::instance().send(
+            Command::Payments(
+                PaymentsCommand::SearchPaymentHistory(
+                    wallet_handle,
+                    query_json,
+                    Box::new(move |result| {
+                        let (err, history_json) = result_to_err_code_1!(result, String::new());
+                        trace!("indy_search_payment_history: history_json: {:?}", history_json);
+                        let history_json = CStringUtils::string_to_cstring(history_json);
+                        cb(command_handle, err, history_json.as_ptr());
+                    }))
+            ));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_search_payment_history: <<< res: {:?}", res);
+
+    res
+}
+
 /// Builds Indy request for doing tokens minting
 /// according to this payment method.
 ///
@@ -847,6 +1403,7 @@ pub extern fn indy_build_mint_req(command_handle: i32,
 ///   .................
 ///   txnTypeN: amountN,
 /// }
+///   Payment method may support its own fee rules object in place of amount.
 /// # Return
 /// set_txn_fees_json - Indy request for setting fees for transactions in the ledger
 #[no_mangle]
@@ -950,6 +1507,7 @@ pub extern fn indy_build_get_txn_fees_req(command_handle: i32,
 ///   .................
 ///   txnTypeN: amountN,
 /// }
+///   Payment method may support its own fee rules object in place of amount.
 #[no_mangle]
 pub extern fn indy_parse_get_txn_fees_response(command_handle: i32,
                                                payment_method: *const c_char,
@@ -978,4 +1536,276 @@ pub extern fn indy_parse_get_txn_fees_response(command_handle: i32,
     trace!("indy_parse_get_txn_fees_response: <<< res: {:?}", res);
 
     res
-}
\ No newline at end of file
+}
+
+/// Builds Indy request for information to verify the payment receipt
+///
+/// # Params
+/// command_handle
+/// wallet_handle: wallet handle
+/// submitter_did : DID of request sender
+/// receipt: payment receipt to verify
+///   Note: this param will be used to determine payment_method
+///
+/// # Return
+/// verify_txn_json: Indy request for verification receipt
+/// payment_method: used payment method
+#[no_mangle]
+pub extern fn indy_build_verify_payment_req(command_handle: i32,
+                                            wallet_handle: i32,
+                                            submitter_did: *const c_char,
+                                            receipt: *const c_char,
+                                            cb: Option<extern fn(command_handle_: i32,
+                                                                 err: ErrorCode,
+                                                                 verify_txn_json: *const c_char,
+                                                                 payment_method: *const c_char)>) -> ErrorCode {
+    trace!("indy_build_verify_payment_req: >>> wallet_handle: {:?}, submitter_did: {:?}, receipt: {:?}", wallet_handle, submitter_did, receipt);
+    check_useful_c_str!(submitter_did, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(receipt, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_build_verify_payment_req: entities >>> wallet_handle: {:?}, submitter_did: {:?}, receipt: {:?}", wallet_handle, submitter_did, receipt);
+
+    let result = CommandExecutor::instance().send(Command::Payments(
+        PaymentsCommand::BuildVerifyPaymentReq(wallet_handle, submitter_did, receipt, Box::new(move |result| {
+            let (err, verify_txn_json, payment_method) = result_to_err_code_2!(result, String::new(), String::new());
+            trace!("indy_build_verify_payment_req: verify_txn_json: {:?}, payment_method: {:?}", verify_txn_json, payment_method);
+            let verify_txn_json = CStringUtils::string_to_cstring(verify_txn_json);
+            let payment_method = CStringUtils::string_to_cstring(payment_method);
+            cb(command_handle, err, verify_txn_json.as_ptr(), payment_method.as_ptr());
+        }))
+    ));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_build_verify_payment_req: <<< res: {:?}", res);
+
+    res
+}
+
+/// Parses Indy response with information to verify receipt
+///
+/// # Params
+/// command_handle
+/// payment_method: payment method to use
+/// resp_json: response of the ledger for verify txn
+///
+/// # Return
+/// txn_json: {
+///     sources: [<str>, ]
+///     receipts: [ {
+///         recipient: <str>, // payment address of recipient
+///         receipt: <str>, // receipt that can be used for payment referencing and verification
+///         amount: <int>, // amount
+///         extra: <str>, // optional data from payment transaction
+///     }, ]
+///     extra: <str>, //optional data
+/// }
+#[no_mangle]
+pub extern fn indy_parse_verify_payment_response(command_handle: i32,
+                                                 payment_method: *const c_char,
+                                                 resp_json: *const c_char,
+                                                 cb: Option<extern fn(command_handle_: i32,
+                                                                      err: ErrorCode,
+                                                                      txn_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_parse_verify_payment_response: >>> payment_method: {:?}, resp_json: {:?}", payment_method, resp_json);
+    check_useful_c_str!(payment_method, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(resp_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_parse_verify_payment_response: entities >>> payment_method: {:?}, resp_json: {:?}", payment_method, resp_json);
+
+    let result = CommandExecutor::instance().send(Command::Payments(
+        PaymentsCommand::ParseVerifyPaymentResponse(payment_method, resp_json, Box::new(move |result| {
+            let (err, txn_json) = result_to_err_code_1!(result, String::new());
+            trace!("indy_parse_verify_payment_response: txn_json: {:?}", txn_json);
+            let txn_json = CStringUtils::string_to_cstring(txn_json);
+            cb(command_handle, err, txn_json.as_ptr());
+        }))
+    ));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_parse_verify_payment_response: <<< res: {:?}", res);
+
+    res
+}
+
+/// Prepares inputs and change output for a payment of the given amount.
+/// Collects UTXO of all payment addresses of the payment method stored in the wallet
+/// and selects inputs enough to cover amount and fees.
+///
+/// Note that this function sends GET_UTXO requests to the ledger for each payment address.
+///
+/// # Params
+/// command_handle
+/// wallet_handle: wallet handle
+/// pool_handle: pool handle (created by open_pool_ledger)
+/// submitter_did : DID of request sender
+/// payment_method: payment method to use
+/// amount: amount of tokens to pay (without fees)
+/// fees_json: fees schedule as json object (for example the result of parse_get_txn_fees_response).
+///   Only the fee of the payment txn type is covered in addition to amount:
+///   {
+///     txnType: <int>, // amount of tokens for txn type
+///   }
+///   The fee of the payment txn type must be flat amount: fee rules (per role, per request size)
+///   depend on the payment request, so PaymentOperationNotSupportedError is returned for them.
+/// change_address: payment address to send change to
+/// config_json: (optional) preparation config:
+///   {
+///     strategy: <str>, // "largest_first" (default) or "smallest_sufficient"
+///     txnType: <str>, // (optional) txn type of the payment, its fee is taken from fees_json (0 if absent).
+///                     // Required if fees_json contains several entries.
+///   }
+///
+/// # Return
+/// inputs_json: The list of UTXO inputs as json array:
+///   ["input1", ...]
+/// outputs_json: The change output as json array (empty if there is no change):
+///   [{
+///     paymentAddress: <str>, // change address
+///     amount: <int>, // amount of change tokens
+///   }]
+///   Caller has to add own payment outputs totalling amount.
+#[no_mangle]
+pub extern fn indy_prepare_payment(command_handle: i32,
+                                   wallet_handle: i32,
+                                   pool_handle: i32,
+                                   submitter_did: *const c_char,
+                                   payment_method: *const c_char,
+                                   amount: u64,
+                                   fees_json: *const c_char,
+                                   change_address: *const c_char,
+                                   config_json: *const c_char,
+                                   cb: Option<extern fn(command_handle_: i32,
+                                                        err: ErrorCode,
+                                                        inputs_json: *const c_char,
+                                                        outputs_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prepare_payment: >>> wallet_handle: {:?}, pool_handle: {:?}, submitter_did: {:?}, payment_method: {:?}, amount: {:?}, fees_json: {:?}, change_address: {:?}, config_json: {:?}",
+           wallet_handle, pool_handle, submitter_did, payment_method, amount, fees_json, change_address, config_json);
+    check_useful_c_str!(submitter_did, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(payment_method, ErrorCode::CommonInvalidParam5);
+    check_useful_c_str!(fees_json, ErrorCode::CommonInvalidParam7);
+    check_useful_c_str!(change_address, ErrorCode::CommonInvalidParam8);
+    check_useful_opt_c_str!(config_json, ErrorCode::CommonInvalidParam9);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam10);
+
+    trace!("indy_prepare_payment: entities >>> wallet_handle: {:?}, pool_handle: {:?}, submitter_did: {:?}, payment_method: {:?}, amount: {:?}, fees_json: {:?}, change_address: {:?}, config_json: {:?}",
+           wallet_handle, pool_handle, submitter_did, payment_method, amount, fees_json, change_address, config_json);
+
+    let result = CommandExecutor::instance().send(Command::Payments(
+        PaymentsCommand::PreparePayment(wallet_handle, pool_handle, submitter_did, payment_method, amount, fees_json, change_address, config_json, Box::new(move |result| {
+            let (err, inputs_json, outputs_json) = result_to_err_code_2!(result, String::new(), String::new());
+            trace!("indy_prepare_payment: inputs_json: {:?}, outputs_json: {:?}", inputs_json, outputs_json);
+            let inputs_json = CStringUtils::string_to_cstring(inputs_json);
+            let outputs_json = CStringUtils::string_to_cstring(outputs_json);
+            cb(command_handle, err, inputs_json.as_ptr(), outputs_json.as_ptr());
+        }))
+    ));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_prepare_payment: <<< res: {:?}", res);
+
+    res
+}
+
+/// Signs a message with the key of payment address.
+///
+/// Allows to prove control of the payment address (for example, by signing a challenge)
+/// without moving tokens. Payment method is resolved from the address.
+///
+/// # Params
+/// command_handle
+/// wallet_handle: wallet handle where keys of payment address are stored
+/// address: payment address to sign with
+/// message_raw: a pointer to first byte of message to be signed
+/// message_len: a message length
+///
+/// # Return
+/// signature_raw: a pointer to first byte of signature
+/// signature_len: a signature length
+#[no_mangle]
+pub extern fn indy_sign_with_address(command_handle: i32,
+                                     wallet_handle: i32,
+                                     address: *const c_char,
+                                     message_raw: *const u8,
+                                     message_len: u32,
+                                     cb: Option<extern fn(command_handle_: i32,
+                                                          err: ErrorCode,
+                                                          signature_raw: *const u8,
+                                                          signature_len: u32)>) -> ErrorCode {
+    trace!("indy_sign_with_address: >>> wallet_handle: {:?}, address: {:?}, message_raw: {:?}, message_len: {:?}",
+           wallet_handle, address, message_raw, message_len);
+    check_useful_c_str!(address, ErrorCode::CommonInvalidParam3);
+    check_useful_c_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_sign_with_address: entities >>> wallet_handle: {:?}, address: {:?}, message_raw: {:?}, message_len: {:?}",
+           wallet_handle, address, message_raw, message_len);
+
+    let result = CommandExecutor::instance().send(Command::Payments(
+        PaymentsCommand::SignWithAddress(wallet_handle, address, message_raw, Box::new(move |result| {
+            let (err, signature) = result_to_err_code_1!(result, Vec::new());
+            trace!("indy_sign_with_address: signature: {:?}", signature);
+            let (signature_raw, signature_len) = vec_to_pointer(&signature);
+            cb(command_handle, err, signature_raw, signature_len);
+        }))
+    ));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_sign_with_address: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verifies a message signature made with the key of payment address.
+///
+/// Payment method is resolved from the address.
+///
+/// # Params
+/// command_handle
+/// address: payment address of the signer
+/// message_raw: a pointer to first byte of message that has been signed
+/// message_len: a message length
+/// signature_raw: a pointer to first byte of signature to be verified
+/// signature_len: a signature length
+///
+/// # Return
+/// valid: true - if signature is valid, false - otherwise
+#[no_mangle]
+pub extern fn indy_verify_with_address(command_handle: i32,
+                                       address: *const c_char,
+                                       message_raw: *const u8,
+                                       message_len: u32,
+                                       signature_raw: *const u8,
+                                       signature_len: u32,
+                                       cb: Option<extern fn(command_handle_: i32,
+                                                            err: ErrorCode,
+                                                            valid: bool)>) -> ErrorCode {
+    trace!("indy_verify_with_address: >>> address: {:?}, message_raw: {:?}, message_len: {:?}, signature_raw: {:?}, signature_len: {:?}",
+           address, message_raw, message_len, signature_raw, signature_len);
+    check_useful_c_str!(address, ErrorCode::CommonInvalidParam2);
+    check_useful_c_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam3, ErrorCode::CommonInvalidParam4);
+    check_useful_c_byte_array!(signature_raw, signature_len, ErrorCode::CommonInvalidParam5, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_verify_with_address: entities >>> address: {:?}, message_raw: {:?}, message_len: {:?}, signature_raw: {:?}, signature_len: {:?}",
+           address, message_raw, message_len, signature_raw, signature_len);
+
+    let result = CommandExecutor::instance().send(Command::Payments(
+        PaymentsCommand::VerifyWithAddress(address, message_raw, signature_raw, Box::new(move |result| {
+            let (err, valid) = result_to_err_code_1!(result, false);
+            trace!("indy_verify_with_address: valid: {:?}", valid);
+            cb(command_handle, err, valid);
+        }))
+    ));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_verify_with_address: <<< res: {:?}", res);
+
+    res
+}
@@ -139,6 +139,106 @@ pub extern fn indy_register_wallet_storage(command_handle: i32,
     res
 }
 
+/// Registers optional records expiry handlers for custom wallet storage
+/// previously registered with indy_register_wallet_storage.
+///
+/// Storage that registers these handlers is responsible for excluding expired records
+/// from get and search results.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// type_: Wallet type name.
+/// set_record_expiry: WalletType set record expiry operation handler
+/// purge_expired_records: WalletType purge expired records operation handler
+///
+/// #Returns
+/// Error code
+#[no_mangle]
+pub extern fn indy_register_wallet_storage_expiry(command_handle: i32,
+                                                  type_: *const c_char,
+                                                  set_record_expiry: Option<WalletSetRecordExpiry>,
+                                                  purge_expired_records: Option<WalletPurgeExpiredRecords>,
+                                                  cb: Option<extern fn(xcommand_handle: i32,
+                                                                       err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_wallet_storage_expiry: >>> type_: {:?}", type_);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(set_record_expiry, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(purge_expired_records, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_register_wallet_storage_expiry: entities >>> type_: {:?}", type_);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(
+            WalletCommand::RegisterWalletTypeExpiry(
+                type_,
+                set_record_expiry,
+                purge_expired_records,
+                Box::new(move |result| {
+                    let err = result_to_err_code!(result);
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_register_wallet_storage_expiry: <<< res: {:?}", res);
+
+    res
+}
+
+/// Registers optional records versioning handlers for custom wallet storage
+/// previously registered with indy_register_wallet_storage.
+///
+/// Storage that registers these handlers must increment record version
+/// on every change of record value or tags.
+/// For storage without these handlers libindy derives record version from
+/// record value and tags, and conditional update isn't atomic.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// type_: Wallet type name.
+/// get_record_version: WalletType get record version operation handler
+/// update_record_if_version: WalletType conditional record update operation handler
+///
+/// #Returns
+/// Error code
+#[no_mangle]
+pub extern fn indy_register_wallet_storage_versioning(command_handle: i32,
+                                                      type_: *const c_char,
+                                                      get_record_version: Option<WalletGetRecordVersion>,
+                                                      update_record_if_version: Option<WalletUpdateRecordIfVersion>,
+                                                      cb: Option<extern fn(xcommand_handle: i32,
+                                                                           err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_wallet_storage_versioning: >>> type_: {:?}", type_);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(get_record_version, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(update_record_if_version, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_register_wallet_storage_versioning: entities >>> type_: {:?}", type_);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(
+            WalletCommand::RegisterWalletTypeVersioning(
+                type_,
+                get_record_version,
+                update_record_if_version,
+                Box::new(move |result| {
+                    let err = result_to_err_code!(result);
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_register_wallet_storage_versioning: <<< res: {:?}", res);
+
+    res
+}
+
 /// Creates a new secure wallet with the given unique name.
 ///
 /// #Params
@@ -210,12 +310,23 @@ pub extern fn indy_create_wallet(command_handle: i32,
 /// Opens the wallet with specific name.
 ///
 /// Wallet with corresponded name must be previously created with indy_create_wallet method.
-/// It is impossible to open wallet with the same name more than once.
+/// It is impossible to open wallet with the same name more than once,
+/// unless all handles are opened in read-only mode.
 ///
 /// #Params
 /// name: Name of the wallet.
 /// runtime_config (optional): Runtime wallet configuration json. if NULL, then default runtime_config will be used.
 ///   {
+///       "change_log": Optional<bool> Record append-only encrypted change log of wallet records (false by default).
+///                                    Supported only by default wallet storage.
+///       "concurrent_access": Optional<bool> Allow other processes to access the wallet concurrently (false by default).
+///                                    Default wallet storage switches to WAL journal mode with normal locking.
+///       "busy_timeout": Optional<int> Time in milliseconds to wait for a lock held by another connection
+///                                    (5000 if concurrent_access is set, no waiting otherwise).
+///       "read_only": Optional<bool>  Open wallet in read-only mode (false by default).
+///                                    The same wallet can be opened by several read-only handles at once.
+///                                    Wallet created by previous version of libindy must be opened
+///                                    in read-write mode once to upgrade its storage first.
 ///       "storage": Optional<object>  List of supported keys are defined by wallet type.
 ///   }
 /// credentials_json: Wallet credentials json.
@@ -381,6 +492,170 @@ pub extern fn indy_delete_wallet(command_handle: i32,
     res
 }
 
+/// Returns wallet change log entries recorded after the given sequence number.
+///
+/// Change log must be enabled by "change_log" option of runtime config passed to indy_open_wallet
+/// at least once. It is supported only by default wallet storage. Changes made while the wallet was opened
+/// without "change_log" option aren't recorded: such periods start with "log_disabled" entry
+/// and end with "log_enabled" one.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet.
+/// seq: sequence number of the last known change (0 to get all changes).
+///
+/// #Returns
+/// changes_json: list of changes ordered by sequence number:
+///   [{
+///       "seq": int, // monotonically increasing sequence number
+///       "type": Option<string>, // record type, null for "log_enabled" and "log_disabled" entries
+///       "idHash": Option<string>, // hex encoded SHA-256 hash of UTF-8 record id, null for "log_enabled" and "log_disabled" entries
+///       "operation": string, // one of "add", "update", "add_tags", "update_tags", "delete_tags", "delete",
+///                            // "update_expiry", "expire", "quarantine", "log_enabled", "log_disabled"
+///       "timestamp": int, // time of change as unix timestamp
+///   }]
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_wallet_get_changes_since(command_handle: i32,
+                                            wallet_handle: i32,
+                                            seq: i64,
+                                            cb: Option<extern fn(xcommand_handle: i32,
+                                                                 err: ErrorCode,
+                                                                 changes_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_wallet_get_changes_since: >>> wallet_handle: {:?}, seq: {:?}", wallet_handle, seq);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_wallet_get_changes_since: entities >>> wallet_handle: {:?}, seq: {:?}", wallet_handle, seq);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::GetChangesSince(
+            wallet_handle,
+            seq,
+            Box::new(move |result| {
+                let (err, changes_json) = result_to_err_code_1!(result, String::new());
+                trace!("indy_wallet_get_changes_since: changes_json: {:?}", changes_json);
+                let changes_json = CStringUtils::string_to_cstring(changes_json);
+                cb(command_handle, err, changes_json.as_ptr())
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_wallet_get_changes_since: <<< res: {:?}", res);
+
+    res
+}
+
+/// Deletes all wallet records with expiry time in the past.
+///
+/// Expired records are never returned by get and search calls, but stay in the storage
+/// until they are purged. Default wallet storage also purges them on every opening.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet.
+///
+/// #Returns
+/// purged_count: number of deleted records
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_wallet_purge_expired(command_handle: i32,
+                                        wallet_handle: i32,
+                                        cb: Option<extern fn(xcommand_handle: i32,
+                                                             err: ErrorCode,
+                                                             purged_count: i32)>) -> ErrorCode {
+    trace!("indy_wallet_purge_expired: >>> wallet_handle: {:?}", wallet_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_wallet_purge_expired: entities >>> wallet_handle: {:?}", wallet_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::PurgeExpired(
+            wallet_handle,
+            Box::new(move |result| {
+                let (err, purged_count) = result_to_err_code_1!(result, 0);
+                trace!("indy_wallet_purge_expired: purged_count: {:?}", purged_count);
+                cb(command_handle, err, purged_count)
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_wallet_purge_expired: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verifies integrity of all wallet records.
+///
+/// Every record is decrypted with wallet keys and HMAC of its type, id and tags is checked,
+/// so records damaged in the storage are found before somebody tries to read them.
+/// Optionally broken records can be moved to quarantine. Quarantined records aren't visible
+/// for get and search calls anymore, but are kept in the storage for further investigation.
+/// Quarantine is supported only by default wallet storage.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet.
+/// options_json(optional): verification options json:
+///   {
+///       "quarantine": Optional<bool> // move corrupted records to quarantine, false by default
+///   }
+///
+/// #Returns
+/// report_json: verification report:
+///   {
+///       "checkedCount": int, // number of checked records
+///       "corrupted": [{
+///           "type": Optional<string>, // record type if it can be decrypted
+///           "id": Optional<string>, // record id if it can be decrypted
+///           "idHash": string, // hex encoded hash of record id (the same as in wallet changes)
+///           "errors": [string], // description of found problems
+///       }],
+///       "quarantinedCount": int, // number of records moved to quarantine
+///   }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_verify_wallet(command_handle: i32,
+                                 wallet_handle: i32,
+                                 options_json: *const c_char,
+                                 cb: Option<extern fn(xcommand_handle: i32,
+                                                      err: ErrorCode,
+                                                      report_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verify_wallet: >>> wallet_handle: {:?}, options_json: {:?}", wallet_handle, options_json);
+
+    check_useful_opt_c_str!(options_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_verify_wallet: entities >>> wallet_handle: {:?}, options_json: {:?}", wallet_handle, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::Verify(
+            wallet_handle,
+            options_json,
+            Box::new(move |result| {
+                let (err, report_json) = result_to_err_code_1!(result, String::new());
+                trace!("indy_verify_wallet: report_json: {:?}", report_json);
+                let report_json = CStringUtils::string_to_cstring(report_json);
+                cb(command_handle, err, report_json.as_ptr())
+            })
+        )));
+
+    let res = result_to_err_code!(result);
+
+    trace!("indy_verify_wallet: <<< res: {:?}", res);
+
+    res
+}
+
 /// Create the wallet storage (For example, database creation)
 ///
 /// #Params
@@ -697,3 +972,65 @@ pub type WalletFetchSearchNextRecord = extern fn(storage_handle: i32,
 /// search_handle: wallet search handle (See search_records handler)
 pub type WalletFreeSearch = extern fn(storage_handle: i32,
                                       search_handle: i32) -> ErrorCode;
+
+/// Set or remove the record expiry time
+///
+/// #Params
+/// storage_handle: opened storage handle (See open handler)
+/// type_: record type
+/// id: record id
+/// expires_at: unix timestamp in seconds after that record must be treated as deleted
+///             or -1 to remove record expiry
+///
+/// #Returns
+/// WalletItemNotFound if record doesn't exist or already expired
+pub type WalletSetRecordExpiry = extern fn(storage_handle: i32,
+                                           type_: *const c_char,
+                                           id: *const c_char,
+                                           expires_at: i64) -> ErrorCode;
+
+/// Delete all records with expiry time in the past
+///
+/// #Params
+/// storage_handle: opened storage handle (See open handler)
+/// purged_count_p: pointer to store number of deleted records
+pub type WalletPurgeExpiredRecords = extern fn(storage_handle: i32,
+                                               purged_count_p: *mut usize) -> ErrorCode;
+
+/// Get the current record version
+///
+/// #Params
+/// storage_handle: opened storage handle (See open handler)
+/// type_: record type
+/// id: record id
+/// version_p: pointer to store record version
+///
+/// #Returns
+/// WalletItemNotFound if record doesn't exist
+pub type WalletGetRecordVersion = extern fn(storage_handle: i32,
+                                            type_: *const c_char,
+                                            id: *const c_char,
+                                            version_p: *mut i64) -> ErrorCode;
+
+/// Update record value and/or tags only if record has expected version.
+/// Record version must be incremented after successful update.
+///
+/// #Params
+/// storage_handle: opened storage handle (See open handler)
+/// type_: record type
+/// id: record id
+/// value: new record value or NULL to keep the current one
+/// value_len: new record value length
+/// tags_json: new record tags json (See update_record_tags handler) or NULL to keep the current ones
+/// expected_version: record version the update is based on
+///
+/// #Returns
+/// WalletItemNotFound if record doesn't exist
+/// WalletConflict if record version differs from expected one
+pub type WalletUpdateRecordIfVersion = extern fn(storage_handle: i32,
+                                                 type_: *const c_char,
+                                                 id: *const c_char,
+                                                 value: *const u8,
+                                                 value_len: usize,
+                                                 tags_json: *const c_char,
+                                                 expected_version: i64) -> ErrorCode;
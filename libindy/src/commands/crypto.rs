@@ -1,14 +1,21 @@
 extern crate indy_crypto;
 extern crate serde_json;
+extern crate uuid;
 
-use self::indy_crypto::utils::json::JsonDecodable;
+use self::indy_crypto::utils::json::{JsonDecodable, JsonEncodable};
+use api::crypto::{CryptoCreateKey, CryptoSign, CryptoVerify, CryptoEncryptSealed, CryptoDecryptSealed, CryptoFree,
+                  ExternalSignerSign, ExternalSignerPrecompute, ExternalSignerDecryptSealed};
 use errors::common::CommonError;
 use errors::wallet::WalletError;
 use errors::indy::IndyError;
-use domain::crypto::key::{KeyInfo, Key};
+use domain::crypto::key::{KeyInfo, Key, ExternalKeyInfo, MasterSeed, MasterSeedConfig, KeyDerivationInfo};
 use domain::crypto::combo_box::ComboBox;
+use domain::crypto::pack::{JWE, Protected, Recipient, Header, UnpackMessage};
+use domain::crypto::jws::{JwsHeader, JwsOptions, JWS_ALG_EDDSA};
+use domain::crypto::did::{Did, TheirDid};
 use services::wallet::{WalletService, RecordOptions};
-use services::crypto::CryptoService;
+use services::crypto::{CryptoService, DEFAULT_CRYPTO_TYPE};
+use utils::crypto::chacha20poly1305_ietf::ChaCha20Poly1305IETF;
 
 use std::error::Error;
 use std::rc::Rc;
@@ -17,10 +24,39 @@ use std::str;
 use base64;
 
 pub enum CryptoCommand {
+    RegisterCryptoType(
+        String, // crypto type name
+        CryptoCreateKey, // create key
+        CryptoSign, // sign
+        CryptoVerify, // verify
+        CryptoEncryptSealed, // encrypt sealed
+        CryptoDecryptSealed, // decrypt sealed
+        CryptoFree, // free
+        Box<Fn(Result<(), IndyError>) + Send>),
+    RegisterExternalSigner(
+        String, // signer name
+        ExternalSignerSign, // sign
+        ExternalSignerPrecompute, // precompute
+        ExternalSignerDecryptSealed, // decrypt sealed
+        CryptoFree, // free
+        Box<Fn(Result<(), IndyError>) + Send>),
+    CreateExternalKey(
+        i32, // wallet handle
+        String, // external key info json
+        Box<Fn(Result<String/*verkey*/, IndyError>) + Send>),
     CreateKey(
         i32, // wallet handle
         String, // key info json
         Box<Fn(Result<String/*verkey*/, IndyError>) + Send>),
+    CreateMasterSeed(
+        i32, // wallet handle
+        Option<String>, // master seed id
+        Option<String>, // master seed config json
+        Box<Fn(Result<String/*master seed id*/, IndyError>) + Send>),
+    CreateKeyFromPath(
+        i32, // wallet handle
+        String, // key derivation info json
+        Box<Fn(Result<String/*verkey*/, IndyError>) + Send>),
     SetKeyMetadata(
         i32, // wallet handle
         String, // verkey
@@ -59,7 +95,29 @@ pub enum CryptoCommand {
         i32, // wallet handle
         String, // my vk
         Vec<u8>, // msg
-        Box<Fn(Result<Vec<u8>, IndyError>) + Send>)
+        Box<Fn(Result<Vec<u8>, IndyError>) + Send>),
+    PackMessage(
+        i32, // wallet handle
+        Vec<u8>, // message
+        String, // receiver keys json
+        Option<String>, // sender vk
+        Box<Fn(Result<Vec<u8>, IndyError>) + Send>),
+    UnpackMessage(
+        i32, // wallet handle
+        Vec<u8>, // jwe
+        Box<Fn(Result<Vec<u8>, IndyError>) + Send>),
+    SignJws(
+        i32, // wallet handle
+        String, // signer vk
+        String, // payload json
+        Option<String>, // options json
+        Box<Fn(Result<String, IndyError>) + Send>),
+    VerifyJws(
+        i32, // wallet handle
+        String, // jws
+        Option<String>, // detached payload json
+        Option<String>, // signer vk
+        Box<Fn(Result<bool, IndyError>) + Send>)
 }
 
 pub struct CryptoCommandExecutor {
@@ -79,10 +137,30 @@ impl CryptoCommandExecutor {
 
     pub fn execute(&self, command: CryptoCommand) {
         match command {
+            CryptoCommand::RegisterCryptoType(crypto_type_name, create_key, sign, verify, encrypt_sealed, decrypt_sealed, free, cb) => {
+                info!("RegisterCryptoType command received");
+                cb(self.register_crypto_type(&crypto_type_name, create_key, sign, verify, encrypt_sealed, decrypt_sealed, free));
+            }
+            CryptoCommand::RegisterExternalSigner(signer_name, sign, precompute, decrypt_sealed, free, cb) => {
+                info!("RegisterExternalSigner command received");
+                cb(self.register_external_signer(&signer_name, sign, precompute, decrypt_sealed, free));
+            }
+            CryptoCommand::CreateExternalKey(wallet_handle, key_info_json, cb) => {
+                info!("CreateExternalKey command received");
+                cb(self.create_external_key(wallet_handle, &key_info_json));
+            }
             CryptoCommand::CreateKey(wallet_handle, key_info_json, cb) => {
                 info!("CreateKey command received");
                 cb(self.create_key(wallet_handle, key_info_json));
             }
+            CryptoCommand::CreateMasterSeed(wallet_handle, master_seed_id, config_json, cb) => {
+                info!("CreateMasterSeed command received");
+                cb(self.create_master_seed(wallet_handle, master_seed_id.as_ref().map(String::as_str), config_json.as_ref().map(String::as_str)));
+            }
+            CryptoCommand::CreateKeyFromPath(wallet_handle, key_json, cb) => {
+                info!("CreateKeyFromPath command received");
+                cb(self.create_key_from_path(wallet_handle, &key_json));
+            }
             CryptoCommand::SetKeyMetadata(wallet_handle, verkey, metadata, cb) => {
                 info!("SetKeyMetadata command received");
                 cb(self.set_key_metadata(wallet_handle, verkey, metadata));
@@ -115,9 +193,77 @@ impl CryptoCommandExecutor {
                 info!("AnonymousDecrypt command received");
                 cb(self.anonymous_decrypt(wallet_handle, my_vk, encrypted_msg));
             }
+            CryptoCommand::PackMessage(wallet_handle, message, receiver_keys_json, sender_vk, cb) => {
+                info!("PackMessage command received");
+                cb(self.pack_message(wallet_handle, message, &receiver_keys_json, sender_vk));
+            }
+            CryptoCommand::UnpackMessage(wallet_handle, jwe, cb) => {
+                info!("UnpackMessage command received");
+                cb(self.unpack_message(wallet_handle, jwe));
+            }
+            CryptoCommand::SignJws(wallet_handle, signer_vk, payload_json, options_json, cb) => {
+                info!("SignJws command received");
+                cb(self.sign_jws(wallet_handle, &signer_vk, &payload_json, options_json.as_ref().map(String::as_str)));
+            }
+            CryptoCommand::VerifyJws(wallet_handle, jws, detached_payload_json, signer_vk, cb) => {
+                info!("VerifyJws command received");
+                cb(self.verify_jws(wallet_handle, &jws, detached_payload_json.as_ref().map(String::as_str), signer_vk.as_ref().map(String::as_str)));
+            }
         };
     }
 
+    fn register_crypto_type(&self,
+                            crypto_type_name: &str,
+                            create_key: CryptoCreateKey,
+                            sign: CryptoSign,
+                            verify: CryptoVerify,
+                            encrypt_sealed: CryptoEncryptSealed,
+                            decrypt_sealed: CryptoDecryptSealed,
+                            free: CryptoFree) -> Result<(), IndyError> {
+        debug!("register_crypto_type >>> crypto_type_name: {:?}", crypto_type_name);
+
+        let res = self.crypto_service.register_crypto_type(crypto_type_name, create_key, sign, verify,
+                                                           encrypt_sealed, decrypt_sealed, free)?;
+
+        debug!("register_crypto_type <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn register_external_signer(&self,
+                                signer_name: &str,
+                                sign: ExternalSignerSign,
+                                precompute: ExternalSignerPrecompute,
+                                decrypt_sealed: ExternalSignerDecryptSealed,
+                                free: CryptoFree) -> Result<(), IndyError> {
+        debug!("register_external_signer >>> signer_name: {:?}", signer_name);
+
+        let res = self.crypto_service.register_external_signer(signer_name, sign, precompute, decrypt_sealed, free)?;
+
+        debug!("register_external_signer <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn create_external_key(&self, wallet_handle: i32, key_info_json: &str) -> Result<String, IndyError> {
+        debug!("create_external_key >>> wallet_handle: {:?}, key_info_json: {:?}", wallet_handle, key_info_json);
+
+        let key_info = ExternalKeyInfo::from_json(key_info_json)
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidStructure(
+                    format!("Invalid ExternalKeyInfo json: {}", err.description())))?;
+
+        let key = self.crypto_service.create_external_key(&key_info)?;
+        self.wallet_service.add_indy_object(wallet_handle, &key.verkey, &key, "{}")?;
+
+        let res = key.verkey;
+
+        debug!("create_external_key <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn create_key(&self, wallet_handle: i32, key_info_json: String) -> Result<String, IndyError> {
         debug!("create_key >>> wallet_handle: {:?}, key_info_json: {:?}", wallet_handle, key_info_json);
 
@@ -137,6 +283,65 @@ impl CryptoCommandExecutor {
         Ok(res)
     }
 
+    fn create_master_seed(&self,
+                          wallet_handle: i32,
+                          master_seed_id: Option<&str>,
+                          config_json: Option<&str>) -> Result<String, IndyError> {
+        debug!("create_master_seed >>> wallet_handle: {:?}, master_seed_id: {:?}", wallet_handle, master_seed_id);
+
+        let config = match config_json {
+            Some(config_json) =>
+                MasterSeedConfig::from_json(config_json)
+                    .map_err(map_err_trace!())
+                    .map_err(|err|
+                        CommonError::InvalidStructure(
+                            format!("Invalid MasterSeedConfig json: {}", err.description())))?,
+            None => MasterSeedConfig::default()
+        };
+
+        let master_seed_id = master_seed_id.map(String::from).unwrap_or(uuid::Uuid::new_v4().to_string());
+
+        let master_seed = self.crypto_service.create_master_seed(&config)?;
+
+        self.wallet_service.add_indy_object(wallet_handle, &master_seed_id, &master_seed, "{}")?;
+
+        debug!("create_master_seed <<< master_seed_id: {:?}", master_seed_id);
+
+        Ok(master_seed_id)
+    }
+
+    fn create_key_from_path(&self, wallet_handle: i32, key_json: &str) -> Result<String, IndyError> {
+        debug!("create_key_from_path >>> wallet_handle: {:?}, key_json: {:?}", wallet_handle, key_json);
+
+        let derivation = KeyDerivationInfo::from_json(key_json)
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidStructure(
+                    format!("Invalid KeyDerivationInfo json: {}", err.description())))?;
+
+        let master_seed: MasterSeed =
+            self.wallet_service.get_indy_object(wallet_handle, &derivation.master_seed_id, &RecordOptions::id_value(), &mut String::new())?;
+
+        let key_info = KeyInfo {
+            seed: Some(self.crypto_service.derive_seed(&master_seed, &derivation.path)?),
+            crypto_type: None
+        };
+
+        let key = self.crypto_service.create_key(&key_info)?;
+
+        let metadata = derivation.to_json()
+            .map_err(|err| CommonError::InvalidState(format!("Can't serialize KeyDerivationInfo: {}", err)))?;
+
+        let tags_json = json!({"metadata": metadata}).to_string();
+        self.wallet_service.add_indy_object(wallet_handle, &key.verkey, &key, &tags_json)?;
+
+        let res = key.verkey;
+
+        debug!("create_key_from_path <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn crypto_sign(&self,
                    wallet_handle: i32,
                    my_vk: &str,
@@ -254,6 +459,338 @@ impl CryptoCommandExecutor {
         Ok(res)
     }
 
+    fn pack_message(&self,
+                    wallet_handle: i32,
+                    message: Vec<u8>,
+                    receiver_keys_json: &str,
+                    sender_vk: Option<String>) -> Result<Vec<u8>, IndyError> {
+        debug!("pack_message >>> wallet_handle: {:?}, message: {:?}, receiver_keys_json: {:?}, sender_vk: {:?}",
+               wallet_handle, message, receiver_keys_json, sender_vk);
+
+        let receiver_keys: Vec<String> = serde_json::from_str(receiver_keys_json)
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid receiver keys json: {}", err)))?;
+
+        if receiver_keys.is_empty() {
+            return Err(IndyError::CommonError(CommonError::InvalidStructure("Receiver keys list is empty".to_string())));
+        }
+
+        for receiver_vk in receiver_keys.iter() {
+            self.crypto_service.validate_key(receiver_vk)?;
+        }
+
+        let sender_key: Option<Key> = match sender_vk {
+            Some(ref sender_vk) => {
+                self.crypto_service.validate_key(sender_vk)?;
+                Some(self.wallet_service.get_indy_object(wallet_handle, sender_vk, &RecordOptions::id_value(), &mut String::new())?)
+            }
+            None => None
+        };
+
+        // Content encryption key is shared by all recipients, each of them gets it wrapped with own key
+        let cek = ChaCha20Poly1305IETF::create_key();
+
+        let mut recipients = Vec::new();
+
+        for receiver_vk in receiver_keys {
+            let recipient = match sender_key {
+                Some(ref sender_key) => {
+                    let (encrypted_key, iv) = self.crypto_service.encrypt(sender_key, &receiver_vk, &cek)?;
+                    // Sender verkey is anonymously encrypted, so only recipients know who sent the message
+                    let sender = self.crypto_service.encrypt_sealed(&receiver_vk, sender_key.verkey.as_bytes())?;
+
+                    Recipient {
+                        encrypted_key: base64::encode_config(&encrypted_key, base64::URL_SAFE),
+                        header: Header {
+                            kid: receiver_vk,
+                            iv: Some(base64::encode_config(&iv, base64::URL_SAFE)),
+                            sender: Some(base64::encode_config(&sender, base64::URL_SAFE))
+                        }
+                    }
+                }
+                None => {
+                    let encrypted_key = self.crypto_service.encrypt_sealed(&receiver_vk, &cek)?;
+
+                    Recipient {
+                        encrypted_key: base64::encode_config(&encrypted_key, base64::URL_SAFE),
+                        header: Header {
+                            kid: receiver_vk,
+                            iv: None,
+                            sender: None
+                        }
+                    }
+                }
+            };
+
+            recipients.push(recipient);
+        }
+
+        let protected = Protected {
+            enc: "chacha20poly1305_ietf".to_string(),
+            typ: "JWM/1.0".to_string(),
+            alg: if sender_key.is_some() { "Authcrypt" } else { "Anoncrypt" }.to_string(),
+            recipients
+        };
+
+        let protected = protected.to_json()
+            .map_err(|err| CommonError::InvalidState(format!("Can't serialize protected header: {}", err)))?;
+        let protected = base64::encode_config(protected.as_bytes(), base64::URL_SAFE);
+
+        // Protected header is used as additional authenticated data, so recipients list can't be changed
+        let iv = ChaCha20Poly1305IETF::gen_nonce();
+        let (ciphertext, tag) = ChaCha20Poly1305IETF::encrypt_detached(&message, &cek, &iv, protected.as_bytes());
+
+        let jwe = JWE {
+            protected,
+            iv: base64::encode_config(&iv, base64::URL_SAFE),
+            ciphertext: base64::encode_config(&ciphertext, base64::URL_SAFE),
+            tag: base64::encode_config(&tag, base64::URL_SAFE)
+        };
+
+        let res = jwe.to_json()
+            .map_err(|err| CommonError::InvalidState(format!("Can't serialize JWE: {}", err)))?
+            .into_bytes();
+
+        debug!("pack_message <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn unpack_message(&self,
+                      wallet_handle: i32,
+                      jwe: Vec<u8>) -> Result<Vec<u8>, IndyError> {
+        debug!("unpack_message >>> wallet_handle: {:?}, jwe: {:?}", wallet_handle, jwe);
+
+        let jwe = str::from_utf8(&jwe)
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid JWE: {}", err)))?;
+
+        let jwe = JWE::from_json(jwe)
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid JWE: {}", err)))?;
+
+        let protected = CryptoCommandExecutor::_decode_base64(&jwe.protected, "protected header")?;
+        let protected = str::from_utf8(&protected)
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid protected header: {}", err)))?;
+        let protected = Protected::from_json(protected)
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid protected header: {}", err)))?;
+
+        let (recipient, my_key) = self._find_recipient_key(wallet_handle, protected.recipients)?;
+
+        let encrypted_key = CryptoCommandExecutor::_decode_base64(&recipient.encrypted_key, "encrypted key")?;
+
+        let (cek, sender_vk) = match (recipient.header.sender, recipient.header.iv) {
+            (Some(sender), Some(iv)) => {
+                let sender = CryptoCommandExecutor::_decode_base64(&sender, "sender")?;
+                let iv = CryptoCommandExecutor::_decode_base64(&iv, "iv")?;
+
+                let sender_vk = self.crypto_service.decrypt_sealed(&my_key, &sender)?;
+                let sender_vk = String::from_utf8(sender_vk)
+                    .map_err(|err| CommonError::InvalidStructure(format!("Invalid sender verkey: {}", err)))?;
+
+                self.crypto_service.validate_key(&sender_vk)?;
+
+                let cek = self.crypto_service.decrypt(&my_key, &sender_vk, &encrypted_key, &iv)?;
+                (cek, Some(sender_vk))
+            }
+            (None, None) => (self.crypto_service.decrypt_sealed(&my_key, &encrypted_key)?, None),
+            _ => return Err(IndyError::CommonError(CommonError::InvalidStructure("Recipient header must contain both sender and iv or none of them".to_string())))
+        };
+
+        let iv = CryptoCommandExecutor::_decode_base64(&jwe.iv, "iv")?;
+        let ciphertext = CryptoCommandExecutor::_decode_base64(&jwe.ciphertext, "ciphertext")?;
+        let tag = CryptoCommandExecutor::_decode_base64(&jwe.tag, "tag")?;
+
+        if cek.len() != ChaCha20Poly1305IETF::KEYBYTES {
+            return Err(IndyError::CommonError(CommonError::InvalidStructure("Invalid content encryption key".to_string())));
+        }
+
+        let message = ChaCha20Poly1305IETF::decrypt_detached(&ciphertext, &tag, &cek, &iv, jwe.protected.as_bytes())?;
+        let message = String::from_utf8(message)
+            .map_err(|err| CommonError::InvalidStructure(format!("Message isn't valid UTF8 string: {}", err)))?;
+
+        let unpack_message = UnpackMessage {
+            message,
+            recipient_verkey: my_key.verkey,
+            sender_verkey: sender_vk
+        };
+
+        let res = unpack_message.to_json()
+            .map_err(|err| CommonError::InvalidState(format!("Can't serialize unpacked message: {}", err)))?
+            .into_bytes();
+
+        debug!("unpack_message <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn _find_recipient_key(&self, wallet_handle: i32, recipients: Vec<Recipient>) -> Result<(Recipient, Key), IndyError> {
+        for recipient in recipients {
+            match self.wallet_service.get_indy_object::<Key>(wallet_handle, &recipient.header.kid, &RecordOptions::id_value(), &mut String::new()) {
+                Ok(key) => return Ok((recipient, key)),
+                Err(WalletError::ItemNotFound) => continue,
+                Err(err) => return Err(IndyError::WalletError(err))
+            }
+        }
+
+        Err(IndyError::WalletError(WalletError::ItemNotFound))
+    }
+
+    fn _decode_base64(value: &str, field: &str) -> Result<Vec<u8>, CommonError> {
+        base64::decode_config(value, base64::URL_SAFE)
+            .map_err(|err| CommonError::InvalidStructure(format!("Can't decode {} from base64: {}", field, err)))
+    }
+
+    fn sign_jws(&self,
+                wallet_handle: i32,
+                signer_vk: &str,
+                payload_json: &str,
+                options_json: Option<&str>) -> Result<String, IndyError> {
+        debug!("sign_jws >>> wallet_handle: {:?}, signer_vk: {:?}, payload_json: {:?}, options_json: {:?}", wallet_handle, signer_vk, payload_json, options_json);
+
+        self.crypto_service.validate_key(signer_vk)?;
+
+        let options = match options_json {
+            Some(options_json) =>
+                JwsOptions::from_json(options_json)
+                    .map_err(map_err_trace!())
+                    .map_err(|err|
+                        CommonError::InvalidStructure(
+                            format!("Invalid JwsOptions json: {}", err.description())))?,
+            None => JwsOptions::default()
+        };
+
+        serde_json::from_str::<serde_json::Value>(payload_json)
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid JWS payload json: {}", err.description())))?;
+
+        if !CryptoCommandExecutor::_is_ed25519_key(signer_vk) {
+            return Err(IndyError::CommonError(
+                CommonError::InvalidStructure(format!("JWS signing is supported only for {} keys", DEFAULT_CRYPTO_TYPE))));
+        }
+
+        let key: Key = self.wallet_service.get_indy_object(wallet_handle, signer_vk, &RecordOptions::id_value(), &mut String::new())?;
+
+        let header = JwsHeader {
+            alg: JWS_ALG_EDDSA.to_string(),
+            kid: options.kid.unwrap_or(signer_vk.to_string())
+        };
+
+        let header = header.to_json()
+            .map_err(|err| CommonError::InvalidState(format!("Can't serialize JWS header: {}", err)))?;
+
+        let header = CryptoCommandExecutor::_encode_base64url(header.as_bytes());
+        let payload = CryptoCommandExecutor::_encode_base64url(payload_json.as_bytes());
+
+        let signature = self.crypto_service.sign(&key, format!("{}.{}", header, payload).as_bytes())?;
+        let signature = CryptoCommandExecutor::_encode_base64url(&signature);
+
+        let res = if options.detached.unwrap_or(false) {
+            format!("{}..{}", header, signature)
+        } else {
+            format!("{}.{}.{}", header, payload, signature)
+        };
+
+        debug!("sign_jws <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn verify_jws(&self,
+                  wallet_handle: i32,
+                  jws: &str,
+                  detached_payload_json: Option<&str>,
+                  signer_vk: Option<&str>) -> Result<bool, IndyError> {
+        debug!("verify_jws >>> wallet_handle: {:?}, jws: {:?}, detached_payload_json: {:?}, signer_vk: {:?}", wallet_handle, jws, detached_payload_json, signer_vk);
+
+        let parts: Vec<&str> = jws.split('.').collect();
+
+        if parts.len() != 3 {
+            return Err(IndyError::CommonError(CommonError::InvalidStructure(format!("Invalid JWS: compact serialization expected"))));
+        }
+
+        let (header, payload, signature) = (parts[0], parts[1], parts[2]);
+
+        let payload = match (payload, detached_payload_json) {
+            ("", Some(detached_payload_json)) => CryptoCommandExecutor::_encode_base64url(detached_payload_json.as_bytes()),
+            ("", None) =>
+                return Err(IndyError::CommonError(CommonError::InvalidStructure(format!("Invalid JWS: detached payload isn't provided")))),
+            (payload, None) => payload.to_string(),
+            (_, Some(_)) =>
+                return Err(IndyError::CommonError(CommonError::InvalidStructure(format!("Invalid JWS: payload is both attached and detached"))))
+        };
+
+        let jws_header = CryptoCommandExecutor::_decode_base64url(header, "JWS header")?;
+        let jws_header = str::from_utf8(&jws_header)
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid JWS header: {}", err)))?;
+        let jws_header = JwsHeader::from_json(jws_header)
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid JWS header: {}", err.description())))?;
+
+        if jws_header.alg != JWS_ALG_EDDSA {
+            return Err(IndyError::CommonError(CommonError::InvalidStructure(format!("Unsupported JWS alg: {}", jws_header.alg))));
+        }
+
+        let signature = CryptoCommandExecutor::_decode_base64url(signature, "JWS signature")?;
+
+        let verkey = match signer_vk {
+            Some(signer_vk) => {
+                self.crypto_service.validate_key(signer_vk)?;
+                signer_vk.to_string()
+            }
+            None => self._resolve_jws_kid(wallet_handle, &jws_header.kid)?
+        };
+
+        if !CryptoCommandExecutor::_is_ed25519_key(&verkey) {
+            return Err(IndyError::CommonError(
+                CommonError::InvalidStructure(format!("JWS kid doesn't reference {} key: {}", DEFAULT_CRYPTO_TYPE, jws_header.kid))));
+        }
+
+        let res = self.crypto_service.verify(&verkey, format!("{}.{}", header, payload).as_bytes(), &signature)?;
+
+        debug!("verify_jws <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    // kid is trusted only if it references DID (with optional "#fragment") or key stored in the wallet,
+    // otherwise anybody could sign JWS with own key and put it to the header
+    fn _resolve_jws_kid(&self, wallet_handle: i32, kid: &str) -> Result<String, IndyError> {
+        let did = kid.splitn(2, '#').next().unwrap_or(kid);
+
+        if self.crypto_service.validate_did(did).is_ok() {
+            match self.wallet_service.get_indy_object::<TheirDid>(wallet_handle, did, &RecordOptions::id_value(), &mut String::new()) {
+                Ok(their_did) => return Ok(their_did.verkey),
+                Err(WalletError::ItemNotFound) => {}
+                Err(err) => return Err(IndyError::WalletError(err))
+            }
+
+            match self.wallet_service.get_indy_object::<Did>(wallet_handle, did, &RecordOptions::id_value(), &mut String::new()) {
+                Ok(my_did) => return Ok(my_did.verkey),
+                Err(WalletError::ItemNotFound) if self.crypto_service.validate_key(kid).is_ok() => {}
+                Err(err) => return Err(IndyError::WalletError(err))
+            }
+        }
+
+        self.crypto_service.validate_key(kid)?;
+
+        self.wallet_service.get_indy_record::<Key>(wallet_handle, kid, &RecordOptions::id())?;
+
+        Ok(kid.to_string())
+    }
+
+    fn _is_ed25519_key(verkey: &str) -> bool {
+        match verkey.find(':') {
+            Some(pos) => &verkey[pos + 1..] == DEFAULT_CRYPTO_TYPE,
+            None => true
+        }
+    }
+
+    // JWS uses base64url encoding without padding
+    fn _encode_base64url(value: &[u8]) -> String {
+        base64::encode_config(value, base64::URL_SAFE).trim_right_matches('=').to_string()
+    }
+
+    fn _decode_base64url(value: &str, field: &str) -> Result<Vec<u8>, CommonError> {
+        let padding = (4 - value.len() % 4) % 4;
+        CryptoCommandExecutor::_decode_base64(&format!("{}{}", value, "=".repeat(padding)), field)
+    }
+
     fn set_key_metadata(&self, wallet_handle: i32, verkey: String, metadata: String) -> Result<(), IndyError> {
         debug!("set_key_metadata >>> wallet_handle: {:?}, verkey: {:?}, metadata: {:?}", wallet_handle, verkey, metadata);
 
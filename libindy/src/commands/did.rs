@@ -1,18 +1,21 @@
 extern crate indy_crypto;
 extern crate serde_json;
+extern crate time;
 
 use errors::common::CommonError;
 use errors::did::DidError;
 use errors::wallet::WalletError;
 use errors::indy::IndyError;
-use domain::crypto::key::KeyInfo;
-use domain::crypto::did::{MyDidInfo, Did, TheirDidInfo, TheirDid, TemporaryDid, DidWithMeta};
-use domain::ledger::response::Reply;
+use errors::ledger::LedgerError;
+use domain::crypto::key::{KeyInfo, MasterSeed};
+use domain::crypto::did::{MyDidInfo, Did, TheirDidInfo, TheirDid, TemporaryDid, DidWithMeta, CacheOptions};
+use domain::ledger::response::{Reply, Message};
 use domain::ledger::nym::{GetNymReplyResult, GetNymResultDataV0};
 use domain::ledger::attrib::{GetAttrReplyResult, AttribData, Endpoint};
+use domain::ledger::ddo::{DidDocument, DID_SOV_METHOD};
 use services::pool::PoolService;
 use services::wallet::{WalletService, RecordOptions, SearchOptions};
-use services::crypto::CryptoService;
+use services::crypto::{CryptoService, DEFAULT_CRYPTO_TYPE};
 use services::ledger::LedgerService;
 
 use std::error::Error;
@@ -25,6 +28,7 @@ use commands::{Command, CommandExecutor};
 use std::collections::HashMap;
 use utils::sequence::SequenceUtils;
 use utils::crypto::base58::Base58;
+use utils::qualifier;
 use self::indy_crypto::utils::json::{JsonEncodable, JsonDecodable};
 
 use super::utils::check_wallet_and_pool_handles_consistency;
@@ -58,6 +62,7 @@ pub enum DidCommand {
         i32, // pool handle
         i32, // wallet handle
         String, // did (my or their)
+        Option<String>, // cache options json
         Box<Fn(Result<String/*key*/, IndyError>) + Send>),
     KeyForLocalDid(
         i32, // wallet handle
@@ -73,7 +78,13 @@ pub enum DidCommand {
         i32, // wallet handle
         i32, // pool handle
         String, // did
+        Option<String>, // cache options json
         Box<Fn(Result<(String, Option<String>), IndyError>) + Send>),
+    RefreshTheirDid(
+        i32, // wallet handle
+        i32, // pool handle
+        String, // their did
+        Box<Fn(Result<String, IndyError>) + Send>),
     SetDidMetadata(
         i32, // wallet handle
         String, // did
@@ -87,33 +98,86 @@ pub enum DidCommand {
         String, // did
         String, // verkey
         Box<Fn(Result<String, IndyError>) + Send>),
+    RotateKeyOnLedger(
+        i32, // pool handle
+        i32, // wallet handle
+        String, // my did
+        String, // key info json
+        Box<Fn(Result<String, IndyError>) + Send>),
+    ResolveDidDocument(
+        i32, // pool handle
+        String, // did
+        Box<Fn(Result<String, IndyError>) + Send>),
+    QualifyDid(
+        String, // did
+        String, // method name
+        Box<Fn(Result<String, IndyError>) + Send>),
     // Internal commands
     GetNymAck(
         i32, // wallet_handle
+        String, // did
         Result<String, IndyError>, // GetNym Result
+        bool, // store result in the wallet
         i32, // deferred cmd id
     ),
     // Internal commands
     GetAttribAck(
         i32, // wallet_handle
+        String, // did
+        Result<String, IndyError>, // GetAttrib Result
+        bool, // store result in the wallet
+        i32, // deferred cmd id
+    ),
+    // Internal commands
+    RotateKeyNymAck(
+        i32, // wallet_handle
+        i32, // pool_handle
+        String, // my did
+        Result<String, IndyError>, // Nym Result
+        i32, // deferred cmd id
+    ),
+    // Internal commands
+    RotateKeyGetNymAck(
+        i32, // wallet_handle
+        String, // my did
+        Result<String, IndyError>, // GetNym Result
+        i32, // deferred cmd id
+    ),
+    // Internal commands
+    ResolveDidDocumentGetNymAck(
+        i32, // pool_handle
+        String, // did
+        Result<String, IndyError>, // GetNym Result
+        i32, // deferred cmd id
+    ),
+    // Internal commands
+    ResolveDidDocumentGetAttribAck(
+        String, // did
+        String, // verkey
         Result<String, IndyError>, // GetAttrib Result
         i32, // deferred cmd id
     )
 }
 
 macro_rules! ensure_their_did {
-    ($self_:ident, $wallet_handle:ident, $pool_handle:ident, $their_did:ident, $deferred_cmd:expr, $cb:ident) => (match $self_._wallet_get_their_did($wallet_handle, &$their_did) {
-          Ok(val) => val,
-          Err(WalletError::ItemNotFound) => {
+    ($self_:ident, $wallet_handle:ident, $pool_handle:ident, $their_did:ident, $cache_options:ident, $deferred_cmd:expr, $cb:ident) => ({
+        let cached = match $self_._wallet_get_their_did($wallet_handle, &$their_did) {
+            Ok(val) => if $cache_options.is_fresh(val.resolved_at, time::get_time().sec) { Some(val) } else { None },
+            Err(WalletError::ItemNotFound) => None,
+            Err(err) => return $cb(Err(IndyError::from(err)))
+        };
 
-              check_wallet_and_pool_handles_consistency!($self_.wallet_service, $self_.pool_service,
-                                                         $wallet_handle, $pool_handle, $cb);
+        match cached {
+            Some(val) => val,
+            None => {
+                check_wallet_and_pool_handles_consistency!($self_.wallet_service, $self_.pool_service,
+                                                           $wallet_handle, $pool_handle, $cb);
 
-              // No their their_did present in the wallet. Deffer this command until it is fetched from ledger.
-              return $self_._fetch_their_did_from_ledger($wallet_handle, $pool_handle, &$their_did, $deferred_cmd);
+                // No fresh their_did present in the wallet. Deffer this command until it is fetched from ledger.
+                return $self_._fetch_their_did_from_ledger($wallet_handle, $pool_handle, &$their_did, $cache_options.store(), $deferred_cmd);
             }
-            Err(err) => return $cb(Err(IndyError::from(err)))
-        });
+        }
+    });
 }
 
 pub struct DidCommandExecutor {
@@ -164,9 +228,9 @@ impl DidCommandExecutor {
                 info!("ListMyDidsWithMeta command received");
                 cb(self.list_my_dids_with_meta(wallet_handle));
             }
-            DidCommand::KeyForDid(pool_handle, wallet_handle, did, cb) => {
+            DidCommand::KeyForDid(pool_handle, wallet_handle, did, options_json, cb) => {
                 info!("KeyForDid command received");
-                self.key_for_did(pool_handle, wallet_handle, did, cb);
+                self.key_for_did(pool_handle, wallet_handle, did, options_json, cb);
             }
             DidCommand::KeyForLocalDid(wallet_handle, did, cb) => {
                 info!("KeyForLocalDid command received");
@@ -176,9 +240,13 @@ impl DidCommandExecutor {
                 info!("SetEndpointForDid command received");
                 cb(self.set_endpoint_for_did(wallet_handle, did, address, transport_key));
             }
-            DidCommand::GetEndpointForDid(wallet_handle, pool_handle, did, cb) => {
+            DidCommand::GetEndpointForDid(wallet_handle, pool_handle, did, options_json, cb) => {
                 info!("GetEndpointForDid command received");
-                self.get_endpoint_for_did(wallet_handle, pool_handle, did, cb);
+                self.get_endpoint_for_did(wallet_handle, pool_handle, did, options_json, cb);
+            }
+            DidCommand::RefreshTheirDid(wallet_handle, pool_handle, did, cb) => {
+                info!("RefreshTheirDid command received");
+                self.refresh_their_did(wallet_handle, pool_handle, did, cb);
             }
             DidCommand::SetDidMetadata(wallet_handle, did, metadata, cb) => {
                 info!("SetDidMetadata command received");
@@ -192,13 +260,41 @@ impl DidCommandExecutor {
                 info!("AbbreviateVerkey command received");
                 cb(self.abbreviate_verkey(did, verkey));
             }
-            DidCommand::GetNymAck(wallet_handle, result, deferred_cmd_id) => {
+            DidCommand::RotateKeyOnLedger(pool_handle, wallet_handle, did, key_info_json, cb) => {
+                info!("RotateKeyOnLedger command received");
+                self.rotate_key_on_ledger(pool_handle, wallet_handle, did, key_info_json, cb);
+            }
+            DidCommand::ResolveDidDocument(pool_handle, did, cb) => {
+                info!("ResolveDidDocument command received");
+                self.resolve_did_document(pool_handle, did, cb);
+            }
+            DidCommand::QualifyDid(did, method_name, cb) => {
+                info!("QualifyDid command received");
+                cb(self.qualify_did(did, method_name));
+            }
+            DidCommand::GetNymAck(wallet_handle, did, result, store, deferred_cmd_id) => {
                 info!("GetNymAck command received");
-                self.get_nym_ack(wallet_handle, result, deferred_cmd_id);
+                self.get_nym_ack(wallet_handle, did, result, store, deferred_cmd_id);
             }
-            DidCommand::GetAttribAck(wallet_handle, result, deferred_cmd_id) => {
+            DidCommand::GetAttribAck(wallet_handle, did, result, store, deferred_cmd_id) => {
                 info!("GetAttribAck command received");
-                self.get_attrib_ack(wallet_handle, result, deferred_cmd_id);
+                self.get_attrib_ack(wallet_handle, did, result, store, deferred_cmd_id);
+            }
+            DidCommand::RotateKeyNymAck(wallet_handle, pool_handle, did, result, deferred_cmd_id) => {
+                info!("RotateKeyNymAck command received");
+                self.rotate_key_nym_ack(wallet_handle, pool_handle, did, result, deferred_cmd_id);
+            }
+            DidCommand::RotateKeyGetNymAck(wallet_handle, did, result, deferred_cmd_id) => {
+                info!("RotateKeyGetNymAck command received");
+                self.rotate_key_get_nym_ack(wallet_handle, did, result, deferred_cmd_id);
+            }
+            DidCommand::ResolveDidDocumentGetNymAck(pool_handle, did, result, deferred_cmd_id) => {
+                info!("ResolveDidDocumentGetNymAck command received");
+                self.resolve_did_document_get_nym_ack(pool_handle, did, result, deferred_cmd_id);
+            }
+            DidCommand::ResolveDidDocumentGetAttribAck(did, verkey, result, deferred_cmd_id) => {
+                info!("ResolveDidDocumentGetAttribAck command received");
+                self.resolve_did_document_get_attrib_ack(did, verkey, result, deferred_cmd_id);
             }
         };
     }
@@ -220,10 +316,38 @@ impl DidCommandExecutor {
             };
         }
 
+        let (my_did_info, key_tags_json) = match my_did_info.derivation.clone() {
+            Some(derivation) => {
+                if my_did_info.seed.is_some() || my_did_info.external_key.is_some() {
+                    return Err(IndyError::CommonError(CommonError::InvalidStructure(
+                        format!("Derivation can't be combined with seed or external key"))));
+                }
+
+                if my_did_info.crypto_type.as_ref().map(|crypto_type| crypto_type != DEFAULT_CRYPTO_TYPE).unwrap_or(false) {
+                    return Err(IndyError::CommonError(CommonError::InvalidStructure(
+                        format!("Derivation is supported only for {} crypto type", DEFAULT_CRYPTO_TYPE))));
+                }
+
+                let master_seed: MasterSeed =
+                    self.wallet_service.get_indy_object(wallet_handle, &derivation.master_seed_id, &RecordOptions::id_value(), &mut String::new())?;
+
+                let metadata = derivation.to_json()
+                    .map_err(|err| CommonError::InvalidState(format!("Can't serialize KeyDerivationInfo: {}", err)))?;
+
+                let my_did_info = MyDidInfo {
+                    seed: Some(self.crypto_service.derive_seed(&master_seed, &derivation.path)?),
+                    ..my_did_info
+                };
+
+                (my_did_info, json!({"metadata": metadata}).to_string())
+            }
+            None => (my_did_info, "{}".to_string())
+        };
+
         let (my_did, key) = self.crypto_service.create_my_did(&my_did_info)?;
 
         self.wallet_service.add_indy_object(wallet_handle, &my_did.did, &my_did, "{}")?;
-        self.wallet_service.add_indy_object(wallet_handle, &key.verkey, &key, "{}")?;
+        self.wallet_service.add_indy_object(wallet_handle, &key.verkey, &key, &key_tags_json)?;
 
         let res = (my_did.did, my_did.verkey);
 
@@ -371,11 +495,14 @@ impl DidCommandExecutor {
                    pool_handle: i32,
                    wallet_handle: i32,
                    did: String,
+                   options_json: Option<String>,
                    cb: Box<Fn(Result<String, IndyError>) + Send>) {
-        debug!("key_for_did >>> pool_handle: {:?}, wallet_handle: {:?}, did: {:?}", pool_handle, wallet_handle, did);
+        debug!("key_for_did >>> pool_handle: {:?}, wallet_handle: {:?}, did: {:?}, options_json: {:?}", pool_handle, wallet_handle, did, options_json);
 
         try_cb!(self.crypto_service.validate_did(&did), cb);
 
+        let cache_options = try_cb!(self._parse_cache_options(options_json.as_ref().map(String::as_str)), cb);
+
         // Look to my did
         match self._wallet_get_my_did(wallet_handle, &did) {
             Ok(my_did) => return cb(Ok(my_did.verkey)),
@@ -388,12 +515,14 @@ impl DidCommandExecutor {
                                           wallet_handle,
                                           pool_handle,
                                           did,
+                                          cache_options,
                                           DidCommand::KeyForDid(
                                               pool_handle,
                                               wallet_handle,
                                               did.clone(),
+                                              options_json.clone(),
                                               cb),
-                                           cb);
+                                          cb);
 
         let res = their_did.verkey;
 
@@ -449,33 +578,64 @@ impl DidCommandExecutor {
                             wallet_handle: i32,
                             pool_handle: i32,
                             did: String,
+                            options_json: Option<String>,
                             cb: Box<Fn(Result<(String, Option<String>), IndyError>) + Send>) {
-        debug!("get_endpoint_for_did >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}", wallet_handle, pool_handle, did);
+        debug!("get_endpoint_for_did >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}, options_json: {:?}", wallet_handle, pool_handle, did, options_json);
 
         try_cb!(self.crypto_service.validate_did(&did), cb);
 
+        let cache_options = try_cb!(self._parse_cache_options(options_json.as_ref().map(String::as_str)), cb);
+
         let endpoint =
-            self.wallet_service.get_indy_object::<Endpoint>(wallet_handle, &did, &RecordOptions::id_value(), &mut String::new());
+            match self.wallet_service.get_indy_object::<Endpoint>(wallet_handle, &did, &RecordOptions::id_value(), &mut String::new()) {
+                Ok(endpoint) => if cache_options.is_fresh(endpoint.resolved_at, time::get_time().sec) { Some(endpoint) } else { None },
+                Err(WalletError::ItemNotFound) => None,
+                Err(err) => return cb(Err(IndyError::from(err)))
+            };
 
         match endpoint {
-            Ok(endpoint) => cb(Ok((endpoint.ha, endpoint.verkey))),
-            Err(WalletError::ItemNotFound) => {
+            Some(endpoint) => cb(Ok((endpoint.ha, endpoint.verkey))),
+            None => {
                 check_wallet_and_pool_handles_consistency!(self.wallet_service, self.pool_service,
                                                            wallet_handle, pool_handle, cb);
 
                 return self._fetch_attrib_from_ledger(wallet_handle,
                                                       pool_handle,
                                                       &did,
+                                                      cache_options.store(),
                                                       DidCommand::GetEndpointForDid(
                                                           wallet_handle,
                                                           pool_handle,
                                                           did.clone(),
+                                                          options_json.clone(),
                                                           cb));
             }
-            Err(err) => cb(Err(IndyError::from(err)))
         };
     }
 
+    fn refresh_their_did(&self,
+                         wallet_handle: i32,
+                         pool_handle: i32,
+                         did: String,
+                         cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        debug!("refresh_their_did >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}", wallet_handle, pool_handle, did);
+
+        try_cb!(self.crypto_service.validate_did(&did), cb);
+
+        check_wallet_and_pool_handles_consistency!(self.wallet_service, self.pool_service,
+                                                   wallet_handle, pool_handle, cb);
+
+        self._fetch_their_did_from_ledger(wallet_handle,
+                                          pool_handle,
+                                          &did,
+                                          true,
+                                          DidCommand::RefreshTheirDid(
+                                              wallet_handle,
+                                              pool_handle,
+                                              did.clone(),
+                                              cb));
+    }
+
     fn set_did_metadata(&self,
                         wallet_handle: i32,
                         did: String,
@@ -521,7 +681,7 @@ impl DidCommandExecutor {
         self.crypto_service.validate_did(&did)?;
         self.crypto_service.validate_key(&verkey)?;
 
-        let did = Base58::decode(&did)?;
+        let did = Base58::decode(qualifier::unqualify(&did))?;
         let dverkey = Base58::decode(&verkey)?;
 
         let (first_part, second_part) = dverkey.split_at(16);
@@ -537,19 +697,352 @@ impl DidCommandExecutor {
         Ok(res)
     }
 
+    fn qualify_did(&self,
+                   did: String,
+                   method_name: String) -> Result<String, IndyError> {
+        debug!("qualify_did >>> did: {:?}, method_name: {:?}", did, method_name);
+
+        self.crypto_service.validate_did(&did)?;
+
+        if !qualifier::is_valid_method_name(&method_name) {
+            return Err(IndyError::CommonError(CommonError::InvalidStructure(format!("Invalid DID method name: {}", method_name))));
+        }
+
+        let res = qualifier::qualify(&did, &method_name);
+
+        debug!("qualify_did <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn rotate_key_on_ledger(&self,
+                            pool_handle: i32,
+                            wallet_handle: i32,
+                            my_did: String,
+                            key_info_json: String,
+                            cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        debug!("rotate_key_on_ledger >>> pool_handle: {:?}, wallet_handle: {:?}, my_did: {:?}, key_info_json: {:?}",
+               pool_handle, wallet_handle, my_did, key_info_json);
+
+        try_cb!(self.crypto_service.validate_did(&my_did), cb);
+
+        check_wallet_and_pool_handles_consistency!(self.wallet_service, self.pool_service,
+                                                   wallet_handle, pool_handle, cb);
+
+        // Temporary did left by previous rotation means that its result is unknown.
+        // It must be resolved with indy_replace_keys_apply before the next rotation.
+        if try_cb!(self.wallet_service.record_exists::<TemporaryDid>(wallet_handle, &my_did), cb) {
+            return cb(Err(IndyError::CommonError(CommonError::InvalidState(
+                format!("Previous key rotation for DID {} is not completed", my_did)))));
+        }
+
+        let new_verkey = try_cb!(self.replace_keys_start(wallet_handle, &key_info_json, &my_did), cb);
+
+        let nym_request = match self.ledger_service.build_nym_request(&my_did, &my_did, Some(&new_verkey), None, None) {
+            Ok(nym_request) => nym_request,
+            Err(err) => {
+                try_cb!(self.wallet_service.delete_indy_record::<TemporaryDid>(wallet_handle, &my_did), cb);
+                return cb(Err(IndyError::from(err)));
+            }
+        };
+
+        // Deffer this command until key rotation is confirmed by ledger.
+        let deferred_cmd_id = self._defer_command(DidCommand::RotateKeyOnLedger(pool_handle,
+                                                                                wallet_handle,
+                                                                                my_did.clone(),
+                                                                                key_info_json,
+                                                                                cb));
+
+        // Nym is signed by the current key as temporary key isn't applied yet
+        CommandExecutor::instance()
+            .send(Command::Ledger(LedgerCommand::SignAndSubmitRequest(
+                pool_handle,
+                wallet_handle,
+                my_did.clone(),
+                nym_request,
+                Box::new(move |result| {
+                    CommandExecutor::instance()
+                        .send(Command::Did(DidCommand::RotateKeyNymAck(
+                            wallet_handle,
+                            pool_handle,
+                            my_did.clone(),
+                            result,
+                            deferred_cmd_id
+                        ))).unwrap();
+                })
+            ))).unwrap();
+    }
+
+    fn rotate_key_nym_ack(&self,
+                          wallet_handle: i32,
+                          pool_handle: i32,
+                          my_did: String,
+                          nym_reply_result: Result<String, IndyError>,
+                          deferred_cmd_id: i32) {
+        let get_nym_request = match self._rotate_key_nym_ack(wallet_handle, &my_did, nym_reply_result) {
+            Ok(get_nym_request) => get_nym_request,
+            Err(err) => return self._complete_rotate_key(deferred_cmd_id, Err(err))
+        };
+
+        CommandExecutor::instance()
+            .send(Command::Ledger(LedgerCommand::SubmitRequest(
+                pool_handle,
+                get_nym_request,
+                Box::new(move |result| {
+                    CommandExecutor::instance()
+                        .send(Command::Did(DidCommand::RotateKeyGetNymAck(
+                            wallet_handle,
+                            my_did.clone(),
+                            result,
+                            deferred_cmd_id
+                        ))).unwrap();
+                })
+            ))).unwrap();
+    }
+
+    fn _rotate_key_nym_ack(&self, wallet_handle: i32, my_did: &str, nym_reply_result: Result<String, IndyError>) -> Result<String, IndyError> {
+        trace!("_rotate_key_nym_ack >>> wallet_handle: {:?}, my_did: {:?}, nym_reply_result: {:?}", wallet_handle, my_did, nym_reply_result);
+
+        // Pool errors leave the result unknown, so temporary did is kept for recovery
+        let nym_reply = nym_reply_result?;
+
+        let message: Message<serde_json::Value> = serde_json::from_str(&nym_reply)
+            .map_err(map_err_trace!())
+            .map_err(|err| CommonError::InvalidState(format!("Invalid Nym Response json: {:?}", err)))?;
+
+        match message {
+            Message::Reject(response) | Message::ReqNACK(response) => {
+                // Ledger has definitely not applied the new key
+                self.wallet_service.delete_indy_record::<TemporaryDid>(wallet_handle, my_did)?;
+
+                return Err(IndyError::LedgerError(
+                    LedgerError::InvalidTransaction(format!("Key rotation transaction has been failed: {:?}", response.reason))));
+            }
+            Message::Reply(_) => {}
+        }
+
+        let res = self.ledger_service.build_get_nym_request(my_did, my_did)?;
+
+        trace!("_rotate_key_nym_ack <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn rotate_key_get_nym_ack(&self,
+                              wallet_handle: i32,
+                              my_did: String,
+                              get_nym_reply_result: Result<String, IndyError>,
+                              deferred_cmd_id: i32) {
+        let res = self._rotate_key_get_nym_ack(wallet_handle, &my_did, get_nym_reply_result);
+        self._complete_rotate_key(deferred_cmd_id, res);
+    }
+
+    fn _rotate_key_get_nym_ack(&self, wallet_handle: i32, my_did: &str, get_nym_reply_result: Result<String, IndyError>) -> Result<String, IndyError> {
+        trace!("_rotate_key_get_nym_ack >>> wallet_handle: {:?}, my_did: {:?}, get_nym_reply_result: {:?}", wallet_handle, my_did, get_nym_reply_result);
+
+        let get_nym_reply = get_nym_reply_result?;
+
+        let ledger_did = self._parse_get_nym_reply(&get_nym_reply)?;
+
+        let my_temporary_did: TemporaryDid =
+            self.wallet_service.get_indy_object(wallet_handle, my_did, &RecordOptions::id_value(), &mut String::new())?;
+
+        if ledger_did.verkey != my_temporary_did.verkey {
+            return Err(IndyError::CommonError(CommonError::InvalidState(
+                format!("Key rotation for DID {} is not confirmed by the ledger", my_did))));
+        }
+
+        self.replace_keys_apply(wallet_handle, my_did)?;
+
+        let res = my_temporary_did.verkey;
+
+        trace!("_rotate_key_get_nym_ack <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn _complete_rotate_key(&self, deferred_cmd_id: i32, res: Result<String, IndyError>) {
+        let deferred_cmd = self.deferred_commands.borrow_mut().remove(&deferred_cmd_id);
+
+        match deferred_cmd {
+            Some(DidCommand::RotateKeyOnLedger(_, _, _, _, cb)) => cb(res),
+            Some(_) => error!("Unexpected deferred command for id: {}", deferred_cmd_id),
+            None => error!("No deferred command for id: {}", deferred_cmd_id)
+        }
+    }
+
+    fn resolve_did_document(&self,
+                            pool_handle: i32,
+                            did: String,
+                            cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        debug!("resolve_did_document >>> pool_handle: {:?}, did: {:?}", pool_handle, did);
+
+        try_cb!(self.crypto_service.validate_did(&did), cb);
+
+        if let Some(method_name) = qualifier::method(&did) {
+            if method_name != DID_SOV_METHOD {
+                return cb(Err(IndyError::CommonError(CommonError::InvalidStructure(
+                    format!("Unsupported DID method for resolution: {}", method_name)))));
+            }
+        }
+
+        let did = qualifier::unqualify(&did).to_string();
+
+        // TODO we need passing of my_did as identifier
+        let get_nym_request = try_cb!(self.ledger_service.build_get_nym_request(&did, &did), cb);
+
+        // Deffer this command until DID document data is fetched from ledger.
+        let deferred_cmd_id = self._defer_command(DidCommand::ResolveDidDocument(pool_handle, did.clone(), cb));
+
+        CommandExecutor::instance()
+            .send(Command::Ledger(LedgerCommand::SubmitRequest(
+                pool_handle,
+                get_nym_request,
+                Box::new(move |result| {
+                    CommandExecutor::instance()
+                        .send(Command::Did(DidCommand::ResolveDidDocumentGetNymAck(
+                            pool_handle,
+                            did.clone(),
+                            result,
+                            deferred_cmd_id
+                        ))).unwrap();
+                })
+            ))).unwrap();
+    }
+
+    fn resolve_did_document_get_nym_ack(&self,
+                                        pool_handle: i32,
+                                        did: String,
+                                        get_nym_reply_result: Result<String, IndyError>,
+                                        deferred_cmd_id: i32) {
+        let res = get_nym_reply_result
+            .and_then(|get_nym_reply| self._parse_get_nym_reply(&get_nym_reply))
+            .and_then(|ledger_did|
+                self.ledger_service.build_get_attrib_request(&did, &did, Some("endpoint"), None, None)
+                    .map(|get_attrib_request| (ledger_did.verkey, get_attrib_request))
+                    .map_err(IndyError::from));
+
+        let (verkey, get_attrib_request) = match res {
+            Ok(res) => res,
+            Err(err) => return self._complete_resolve_did_document(deferred_cmd_id, Err(err))
+        };
+
+        CommandExecutor::instance()
+            .send(Command::Ledger(LedgerCommand::SubmitRequest(
+                pool_handle,
+                get_attrib_request,
+                Box::new(move |result| {
+                    CommandExecutor::instance()
+                        .send(Command::Did(DidCommand::ResolveDidDocumentGetAttribAck(
+                            did.clone(),
+                            verkey.clone(),
+                            result,
+                            deferred_cmd_id
+                        ))).unwrap();
+                })
+            ))).unwrap();
+    }
+
+    fn resolve_did_document_get_attrib_ack(&self,
+                                           did: String,
+                                           verkey: String,
+                                           get_attrib_reply_result: Result<String, IndyError>,
+                                           deferred_cmd_id: i32) {
+        let res = self._resolve_did_document_get_attrib_ack(&did, &verkey, get_attrib_reply_result);
+        self._complete_resolve_did_document(deferred_cmd_id, res);
+    }
+
+    fn _resolve_did_document_get_attrib_ack(&self, did: &str, verkey: &str, get_attrib_reply_result: Result<String, IndyError>) -> Result<String, IndyError> {
+        trace!("_resolve_did_document_get_attrib_ack >>> did: {:?}, verkey: {:?}, get_attrib_reply_result: {:?}", did, verkey, get_attrib_reply_result);
+
+        let get_attrib_reply = get_attrib_reply_result?;
+
+        let reply: serde_json::Value = serde_json::from_str(&get_attrib_reply)
+            .map_err(map_err_trace!())
+            .map_err(|err| CommonError::InvalidState(format!("Invalid GetAttrReplyResult json {:?}", err)))?;
+
+        // Endpoint is optional for DID document
+        let endpoint = if reply["result"]["data"].is_null() {
+            None
+        } else {
+            let (_, endpoint) = self._parse_get_attrib_reply(&get_attrib_reply)?;
+            Some((endpoint.ha, endpoint.verkey))
+        };
+
+        // Verkey from ledger may contain crypto type suffix
+        let (verkey, crypto_type) = match verkey.find(':') {
+            Some(pos) => (&verkey[..pos], &verkey[pos + 1..]),
+            None => (verkey, DEFAULT_CRYPTO_TYPE)
+        };
+
+        if crypto_type != DEFAULT_CRYPTO_TYPE {
+            return Err(IndyError::CommonError(CommonError::InvalidState(
+                format!("Unsupported crypto type for DID document: {}", crypto_type))));
+        }
+
+        let res = DidDocument::new(did, verkey, endpoint)
+            .to_json()
+            .map_err(|err|
+                IndyError::CommonError(CommonError::InvalidState(format!("Can't serialize DID document {}", err))))?;
+
+        trace!("_resolve_did_document_get_attrib_ack <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn _complete_resolve_did_document(&self, deferred_cmd_id: i32, res: Result<String, IndyError>) {
+        let deferred_cmd = self.deferred_commands.borrow_mut().remove(&deferred_cmd_id);
+
+        match deferred_cmd {
+            Some(DidCommand::ResolveDidDocument(_, _, cb)) => cb(res),
+            Some(_) => error!("Unexpected deferred command for id: {}", deferred_cmd_id),
+            None => error!("No deferred command for id: {}", deferred_cmd_id)
+        }
+    }
+
     fn get_nym_ack(&self,
                    wallet_handle: i32,
+                   did: String,
                    get_nym_reply_result: Result<String, IndyError>,
+                   store: bool,
                    deferred_cmd_id: i32) {
-        let res = self._get_nym_ack(wallet_handle, get_nym_reply_result);
-        self._execute_deferred_command(deferred_cmd_id, res.err());
+        let res = self._get_nym_ack(wallet_handle, &did, get_nym_reply_result, store);
+
+        // Fetched DID is passed to deferred command directly as it may be not stored in the wallet
+        let deferred_cmd = self.deferred_commands.borrow_mut().remove(&deferred_cmd_id);
+
+        match deferred_cmd {
+            Some(DidCommand::KeyForDid(_, _, _, _, cb)) => cb(res.map(|their_did| their_did.verkey)),
+            Some(DidCommand::RefreshTheirDid(_, _, _, cb)) => {
+                cb(res.and_then(|their_did| their_did.to_json()
+                    .map_err(|err|
+                        IndyError::CommonError(CommonError::InvalidState(format!("Can't serialize TheirDid {}", err))))))
+            }
+            Some(_) => error!("Unexpected deferred command for id: {}", deferred_cmd_id),
+            None => error!("No deferred command for id: {}", deferred_cmd_id)
+        }
     }
 
-    fn _get_nym_ack(&self, wallet_handle: i32, get_nym_reply_result: Result<String, IndyError>) -> Result<(), IndyError> {
-        trace!("_get_nym_ack >>> wallet_handle: {:?}, get_nym_reply_result: {:?}", wallet_handle, get_nym_reply_result);
+    fn _get_nym_ack(&self, wallet_handle: i32, did: &str, get_nym_reply_result: Result<String, IndyError>, store: bool) -> Result<TheirDid, IndyError> {
+        trace!("_get_nym_ack >>> wallet_handle: {:?}, did: {:?}, get_nym_reply_result: {:?}, store: {:?}", wallet_handle, did, get_nym_reply_result, store);
 
         let get_nym_reply = get_nym_reply_result?;
 
+        let mut their_did = self._parse_get_nym_reply(&get_nym_reply)?;
+        // Ledger returns unqualified DID, so requested form is kept
+        their_did.did = did.to_string();
+        their_did.resolved_at = Some(time::get_time().sec);
+
+        if store {
+            self.wallet_service.upsert_indy_object(wallet_handle, &their_did.did, &their_did)?;
+        }
+
+        trace!("_get_nym_ack <<< their_did: {:?}", their_did);
+
+        Ok(their_did)
+    }
+
+    fn _parse_get_nym_reply(&self, get_nym_reply: &str) -> Result<TheirDid, IndyError> {
         let get_nym_response: Reply<GetNymReplyResult> = Reply::from_json(&get_nym_reply)
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidState(format!("Invalid GetNymReplyResult json: {:?}", err)))?;
@@ -565,28 +1058,48 @@ impl DidCommandExecutor {
             GetNymReplyResult::GetNymReplyResultV1(res) => TheirDidInfo::new(res.txn.data.did, res.txn.data.verkey)
         };
 
-        let their_did = self.crypto_service.create_their_did(&their_did_info)?;
-
-        self.wallet_service.add_indy_object(wallet_handle, &their_did.did, &their_did, "{}")?;
-
-        trace!("_get_nym_ack <<<");
+        let res = self.crypto_service.create_their_did(&their_did_info)?;
 
-        Ok(())
+        Ok(res)
     }
 
     fn get_attrib_ack(&self,
                       wallet_handle: i32,
+                      did: String,
                       get_attrib_reply_result: Result<String, IndyError>,
+                      store: bool,
                       deferred_cmd_id: i32) {
-        let res = self._get_attrib_ack(wallet_handle, get_attrib_reply_result);
-        self._execute_deferred_command(deferred_cmd_id, res.err());
+        let res = self._get_attrib_ack(wallet_handle, &did, get_attrib_reply_result, store);
+
+        // Fetched endpoint is passed to deferred command directly as it may be not stored in the wallet
+        let deferred_cmd = self.deferred_commands.borrow_mut().remove(&deferred_cmd_id);
+
+        match deferred_cmd {
+            Some(DidCommand::GetEndpointForDid(_, _, _, _, cb)) => cb(res.map(|endpoint| (endpoint.ha, endpoint.verkey))),
+            Some(_) => error!("Unexpected deferred command for id: {}", deferred_cmd_id),
+            None => error!("No deferred command for id: {}", deferred_cmd_id)
+        }
     }
 
-    fn _get_attrib_ack(&self, wallet_handle: i32, get_attrib_reply_result: Result<String, IndyError>) -> Result<(), IndyError> {
-        trace!("_get_attrib_ack >>> wallet_handle: {:?}, get_attrib_reply_result: {:?}", wallet_handle, get_attrib_reply_result);
+    fn _get_attrib_ack(&self, wallet_handle: i32, did: &str, get_attrib_reply_result: Result<String, IndyError>, store: bool) -> Result<Endpoint, IndyError> {
+        trace!("_get_attrib_ack >>> wallet_handle: {:?}, did: {:?}, get_attrib_reply_result: {:?}, store: {:?}", wallet_handle, did, get_attrib_reply_result, store);
 
         let get_attrib_reply = get_attrib_reply_result?;
 
+        // Ledger returns unqualified DID, so requested form is kept
+        let (_, mut endpoint) = self._parse_get_attrib_reply(&get_attrib_reply)?;
+        endpoint.resolved_at = Some(time::get_time().sec);
+
+        if store {
+            self.wallet_service.upsert_indy_object(wallet_handle, did, &endpoint)?;
+        }
+
+        trace!("_get_attrib_ack <<< endpoint: {:?}", endpoint);
+
+        Ok(endpoint)
+    }
+
+    fn _parse_get_attrib_reply(&self, get_attrib_reply: &str) -> Result<(String, Endpoint), IndyError> {
         let get_attrib_reply: Reply<GetAttrReplyResult> = Reply::from_json(&get_attrib_reply)
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidState(format!("Invalid GetAttrReplyResult json {:?}", err)))?;
@@ -602,11 +1115,7 @@ impl DidCommandExecutor {
 
         let endpoint = Endpoint::new(attrib_data.endpoint.ha, attrib_data.endpoint.verkey);
 
-        self.wallet_service.add_indy_object(wallet_handle, &did, &endpoint, "{}")?;
-
-        trace!("_get_attrib_ack <<<");
-
-        Ok(())
+        Ok((did, endpoint))
     }
 
     fn _defer_command(&self, cmd: DidCommand) -> i32 {
@@ -615,45 +1124,9 @@ impl DidCommandExecutor {
         deferred_cmd_id
     }
 
-    fn _execute_deferred_command(&self, deferred_cmd_id: i32, err: Option<IndyError>) {
-        if let Some(cmd) = self.deferred_commands.borrow_mut().remove(&deferred_cmd_id) {
-            if let Some(err) = err {
-                self._call_error_cb(cmd, err);
-            } else {
-                self.execute(cmd);
-            }
-        } else {
-            error!("No deferred command for id: {}", deferred_cmd_id)
-        }
-    }
-
-    fn _call_error_cb(&self, command: DidCommand, err: IndyError) {
-        match command {
-            DidCommand::CreateAndStoreMyDid(_, _, cb) => {
-                return cb(Err(err));
-            }
-            DidCommand::ReplaceKeysStart(_, _, _, cb) => {
-                return cb(Err(err));
-            }
-            DidCommand::ReplaceKeysApply(_, _, cb) => {
-                return cb(Err(err));
-            }
-            DidCommand::StoreTheirDid(_, _, cb) => {
-                return cb(Err(err));
-            }
-            DidCommand::KeyForDid(_, _, _, cb) => {
-                return cb(Err(err));
-            }
-            DidCommand::GetEndpointForDid(_, _, _, cb) => {
-                return cb(Err(err));
-            }
-            _ => {}
-        }
-    }
-
     fn _fetch_their_did_from_ledger(&self,
                                     wallet_handle: i32, pool_handle: i32,
-                                    did: &str, deferred_cmd: DidCommand) {
+                                    did: &str, store: bool, deferred_cmd: DidCommand) {
         // Deffer this command until their did is fetched from ledger.
         let deferred_cmd_id = self._defer_command(deferred_cmd);
 
@@ -665,6 +1138,8 @@ impl DidCommandExecutor {
                     // TODO: FIXME: Remove this unwrap by sending GetNymAck with the error.
                     format!("Invalid Get Nym Request: {}", err.description()))).unwrap();
 
+        let did = did.to_string();
+
         CommandExecutor::instance()
             .send(Command::Ledger(LedgerCommand::SubmitRequest(
                 pool_handle,
@@ -673,7 +1148,9 @@ impl DidCommandExecutor {
                     CommandExecutor::instance()
                         .send(Command::Did(DidCommand::GetNymAck(
                             wallet_handle,
+                            did.clone(),
                             result,
+                            store,
                             deferred_cmd_id
                         ))).unwrap();
                 })
@@ -682,7 +1159,7 @@ impl DidCommandExecutor {
 
     fn _fetch_attrib_from_ledger(&self,
                                  wallet_handle: i32, pool_handle: i32,
-                                 did: &str, deferred_cmd: DidCommand) {
+                                 did: &str, store: bool, deferred_cmd: DidCommand) {
         // Deffer this command until their did is fetched from ledger.
         let deferred_cmd_id = self._defer_command(deferred_cmd);
 
@@ -694,6 +1171,8 @@ impl DidCommandExecutor {
                     // TODO: FIXME: Remove this unwrap by sending GetAttribAck with the error.
                     format!("Invalid Get Attrib Request: {}", err.description()))).unwrap();
 
+        let did = did.to_string();
+
         CommandExecutor::instance()
             .send(Command::Ledger(LedgerCommand::SubmitRequest(
                 pool_handle,
@@ -702,7 +1181,9 @@ impl DidCommandExecutor {
                     CommandExecutor::instance()
                         .send(Command::Did(DidCommand::GetAttribAck(
                             wallet_handle,
+                            did.clone(),
                             result,
+                            store,
                             deferred_cmd_id
                         ))).unwrap();
                 })
@@ -717,6 +1198,16 @@ impl DidCommandExecutor {
         self.wallet_service.get_indy_object(wallet_handle, &their_did, &RecordOptions::id_value(), &mut String::new())
     }
 
+    fn _parse_cache_options(&self, options_json: Option<&str>) -> Result<CacheOptions, IndyError> {
+        match options_json {
+            Some(options_json) => CacheOptions::from_json(options_json)
+                .map_err(map_err_trace!())
+                .map_err(|err|
+                    IndyError::CommonError(CommonError::InvalidStructure(format!("Invalid cache options json: {}", err.description())))),
+            None => Ok(CacheOptions::default())
+        }
+    }
+
     fn _wallet_get_did_metadata(&self, wallet_handle: i32, did: &str) -> Option<String> {
         self.wallet_service.get_indy_record::<Did>(wallet_handle, &did, &RecordOptions::full()).ok()
             .and_then(|rec| rec.get_tags().map(String::from))
@@ -17,13 +17,18 @@ use services::ledger::LedgerService;
 
 
 use super::utils::check_wallet_and_pool_handles_consistency;
+use commands::{Command, CommandExecutor};
+use commands::payments::PaymentsCommand;
 
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::error::Error;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 use utils::crypto::base58::Base58;
+use utils::qualifier;
+use utils::sequence::SequenceUtils;
 
 use utils::crypto::signature_serializer::serialize_signature;
 
@@ -42,6 +47,34 @@ pub enum LedgerCommand {
         i32, // cmd_id
         Result<String, PoolError>, // result json or error
     ),
+    SignAndSubmitRequestWithFees(
+        i32, // pool handle
+        i32, // wallet handle
+        String, // submitter did
+        String, // request json
+        String, // payment method
+        String, // change address
+        Box<Fn(Result<(String, String), IndyError>) + Send>),
+    SignAndSubmitRequestWithFeesFeesAck(
+        i32, // fee payment id
+        Result<String, IndyError>, // fees json or error
+    ),
+    SignAndSubmitRequestWithFeesFeeAck(
+        i32, // fee payment id
+        Result<u64, IndyError>, // fee of request or error
+    ),
+    SignAndSubmitRequestWithFeesRequestAck(
+        i32, // fee payment id
+        Result<String, IndyError>, // request with fees json or error
+    ),
+    SignAndSubmitRequestWithFeesResultAck(
+        i32, // fee payment id
+        Result<(String, String), IndyError>, // request result json and receipts json or error
+    ),
+    EvictTxnFees(
+        Option<i32>, // pool handle
+        Option<String>, // payment method
+    ),
     SignRequest(
         i32, // wallet handle
         String, // submitter did
@@ -173,6 +206,9 @@ pub enum LedgerCommand {
         Box<Fn(Result<(String, String, u64), IndyError>) + Send>)
 }
 
+// Fee schedules can be changed on the ledger at any moment, so cached ones are refetched periodically
+const TXN_FEES_CACHE_TTL: u64 = 60;
+
 pub struct LedgerCommandExecutor {
     pool_service: Rc<PoolService>,
     crypto_service: Rc<CryptoService>,
@@ -180,6 +216,19 @@ pub struct LedgerCommandExecutor {
     ledger_service: Rc<LedgerService>,
 
     send_callbacks: RefCell<HashMap<i32, Box<Fn(Result<String, IndyError>)>>>,
+    fee_payments: RefCell<HashMap<i32, FeePayment>>,
+    txn_fees_cache: RefCell<HashMap<(i32, String), (Instant, String)>>,
+}
+
+struct FeePayment {
+    pool_handle: i32,
+    wallet_handle: i32,
+    submitter_did: String,
+    request_json: String,
+    txn_type: String,
+    payment_method: String,
+    change_address: String,
+    cb: Box<Fn(Result<(String, String), IndyError>) + Send>,
 }
 
 impl LedgerCommandExecutor {
@@ -193,6 +242,8 @@ impl LedgerCommandExecutor {
             wallet_service,
             ledger_service,
             send_callbacks: RefCell::new(HashMap::new()),
+            fee_payments: RefCell::new(HashMap::new()),
+            txn_fees_cache: RefCell::new(HashMap::new()),
         }
     }
 
@@ -212,6 +263,30 @@ impl LedgerCommandExecutor {
                     .expect("Expect callback to process ack command")
                     (result.map_err(IndyError::from));
             }
+            LedgerCommand::SignAndSubmitRequestWithFees(pool_handle, wallet_handle, submitter_did, request_json, payment_method, change_address, cb) => {
+                info!(target: "ledger_command_executor", "SignAndSubmitRequestWithFees command received");
+                self.sign_and_submit_request_with_fees(pool_handle, wallet_handle, &submitter_did, &request_json, &payment_method, &change_address, cb);
+            }
+            LedgerCommand::SignAndSubmitRequestWithFeesFeesAck(fee_payment_id, result) => {
+                info!(target: "ledger_command_executor", "SignAndSubmitRequestWithFeesFeesAck command received");
+                self.sign_and_submit_request_with_fees_fees_ack(fee_payment_id, result);
+            }
+            LedgerCommand::SignAndSubmitRequestWithFeesFeeAck(fee_payment_id, result) => {
+                info!(target: "ledger_command_executor", "SignAndSubmitRequestWithFeesFeeAck command received");
+                self.sign_and_submit_request_with_fees_fee_ack(fee_payment_id, result);
+            }
+            LedgerCommand::SignAndSubmitRequestWithFeesRequestAck(fee_payment_id, result) => {
+                info!(target: "ledger_command_executor", "SignAndSubmitRequestWithFeesRequestAck command received");
+                self.sign_and_submit_request_with_fees_request_ack(fee_payment_id, result);
+            }
+            LedgerCommand::SignAndSubmitRequestWithFeesResultAck(fee_payment_id, result) => {
+                info!(target: "ledger_command_executor", "SignAndSubmitRequestWithFeesResultAck command received");
+                self._complete_fee_payment(fee_payment_id, result);
+            }
+            LedgerCommand::EvictTxnFees(pool_handle, payment_method) => {
+                info!(target: "ledger_command_executor", "EvictTxnFees command received");
+                self.evict_txn_fees(pool_handle, payment_method.as_ref().map(String::as_str));
+            }
             LedgerCommand::SignRequest(wallet_handle, submitter_did, request_json, cb) => {
                 info!(target: "ledger_command_executor", "SignRequest command received");
                 cb(self.sign_request(wallet_handle, &submitter_did, &request_json));
@@ -352,6 +427,234 @@ impl LedgerCommandExecutor {
         }
     }
 
+    fn sign_and_submit_request_with_fees(&self,
+                                         pool_handle: i32,
+                                         wallet_handle: i32,
+                                         submitter_did: &str,
+                                         request_json: &str,
+                                         payment_method: &str,
+                                         change_address: &str,
+                                         cb: Box<Fn(Result<(String, String), IndyError>) + Send>) {
+        debug!("sign_and_submit_request_with_fees >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, request_json: {:?}, payment_method: {:?}, change_address: {:?}",
+               pool_handle, wallet_handle, submitter_did, request_json, payment_method, change_address);
+
+        check_wallet_and_pool_handles_consistency!(self.wallet_service, self.pool_service,
+                                                   wallet_handle, pool_handle, cb);
+
+        let txn_type = match LedgerCommandExecutor::_get_txn_type(request_json) {
+            Ok(txn_type) => txn_type,
+            Err(err) => return cb(Err(err))
+        };
+
+        let fee_payment_id = SequenceUtils::get_next_id();
+
+        self.fee_payments.borrow_mut().insert(fee_payment_id, FeePayment {
+            pool_handle,
+            wallet_handle,
+            submitter_did: submitter_did.to_string(),
+            request_json: request_json.to_string(),
+            txn_type,
+            payment_method: payment_method.to_string(),
+            change_address: change_address.to_string(),
+            cb,
+        });
+
+        let cached_fees = self.txn_fees_cache.borrow().get(&(pool_handle, payment_method.to_string()))
+            .and_then(|&(ref fetched, ref fees)| if fetched.elapsed() < Duration::from_secs(TXN_FEES_CACHE_TTL) { Some(fees.clone()) } else { None });
+
+        match cached_fees {
+            Some(fees) => self._get_request_fee(fee_payment_id, fees),
+            None => {
+                // Fee schedule is cached per pool handle and payment method until pool is closed, fees are set or ttl expires
+                let payment_method = payment_method.to_string();
+                CommandExecutor::instance().send(Command::Payments(PaymentsCommand::BuildGetTxnFeesReq(
+                    wallet_handle,
+                    submitter_did.to_string(),
+                    payment_method.clone(),
+                    Box::new(move |result| {
+                        let payment_method = payment_method.clone();
+                        match result {
+                            Ok(get_txn_fees_req) => CommandExecutor::instance().send(Command::Ledger(LedgerCommand::SubmitRequest(
+                                pool_handle,
+                                get_txn_fees_req,
+                                Box::new(move |result| {
+                                    match result {
+                                        Ok(response) => CommandExecutor::instance().send(Command::Payments(PaymentsCommand::ParseGetTxnFeesResponse(
+                                            payment_method.clone(),
+                                            response,
+                                            Box::new(move |result| {
+                                                CommandExecutor::instance().send(Command::Ledger(
+                                                    LedgerCommand::SignAndSubmitRequestWithFeesFeesAck(fee_payment_id, result))).unwrap();
+                                            })))).unwrap(),
+                                        Err(err) => CommandExecutor::instance().send(Command::Ledger(
+                                            LedgerCommand::SignAndSubmitRequestWithFeesFeesAck(fee_payment_id, Err(err)))).unwrap()
+                                    }
+                                })))).unwrap(),
+                            Err(err) => CommandExecutor::instance().send(Command::Ledger(
+                                LedgerCommand::SignAndSubmitRequestWithFeesFeesAck(fee_payment_id, Err(err)))).unwrap()
+                        }
+                    })))).unwrap();
+            }
+        }
+    }
+
+    fn sign_and_submit_request_with_fees_fees_ack(&self, fee_payment_id: i32, result: Result<String, IndyError>) {
+        debug!("sign_and_submit_request_with_fees_fees_ack >>> fee_payment_id: {:?}, result: {:?}", fee_payment_id, result);
+
+        let fees = match result {
+            Ok(fees) => fees,
+            Err(err) => return self._complete_fee_payment(fee_payment_id, Err(err))
+        };
+
+        let key = match self.fee_payments.borrow().get(&fee_payment_id) {
+            Some(fee_payment) => (fee_payment.pool_handle, fee_payment.payment_method.clone()),
+            None => return error!("Can't process LedgerCommand::SignAndSubmitRequestWithFeesFeesAck for id {} - appropriate fee payment not found!", fee_payment_id)
+        };
+
+        {
+            let mut txn_fees_cache = self.txn_fees_cache.borrow_mut();
+            txn_fees_cache.retain(|_, &mut (ref fetched, _)| fetched.elapsed() < Duration::from_secs(TXN_FEES_CACHE_TTL));
+            txn_fees_cache.insert(key, (Instant::now(), fees.clone()));
+        }
+
+        self._get_request_fee(fee_payment_id, fees);
+    }
+
+    fn sign_and_submit_request_with_fees_fee_ack(&self, fee_payment_id: i32, result: Result<u64, IndyError>) {
+        debug!("sign_and_submit_request_with_fees_fee_ack >>> fee_payment_id: {:?}, result: {:?}", fee_payment_id, result);
+
+        match result {
+            Ok(fee) => self._add_request_fees(fee_payment_id, fee),
+            Err(err) => self._complete_fee_payment(fee_payment_id, Err(err))
+        }
+    }
+
+    fn evict_txn_fees(&self, pool_handle: Option<i32>, payment_method: Option<&str>) {
+        debug!("evict_txn_fees >>> pool_handle: {:?}, payment_method: {:?}", pool_handle, payment_method);
+
+        self.txn_fees_cache.borrow_mut().retain(|&(ref cached_pool_handle, ref cached_payment_method), _| {
+            pool_handle.map_or(false, |pool_handle| pool_handle != *cached_pool_handle) ||
+                payment_method.map_or(false, |payment_method| payment_method != cached_payment_method.as_str())
+        });
+    }
+
+    fn sign_and_submit_request_with_fees_request_ack(&self, fee_payment_id: i32, result: Result<String, IndyError>) {
+        debug!("sign_and_submit_request_with_fees_request_ack >>> fee_payment_id: {:?}, result: {:?}", fee_payment_id, result);
+
+        let (pool_handle, wallet_handle, submitter_did, payment_method) = match self.fee_payments.borrow().get(&fee_payment_id) {
+            Some(fee_payment) => (fee_payment.pool_handle, fee_payment.wallet_handle, fee_payment.submitter_did.clone(), fee_payment.payment_method.clone()),
+            None => return error!("Can't process LedgerCommand::SignAndSubmitRequestWithFeesRequestAck for id {} - appropriate fee payment not found!", fee_payment_id)
+        };
+
+        let signed_request = result.and_then(|request_json| self._sign_request(wallet_handle, &submitter_did, &request_json, SignatureType::Single));
+
+        match signed_request {
+            Ok(signed_request) => self.submit_request(pool_handle, &signed_request, Box::new(move |result| {
+                match result {
+                    Ok(response) => CommandExecutor::instance().send(Command::Payments(PaymentsCommand::ParseResponseWithFees(
+                        payment_method.clone(),
+                        response.clone(),
+                        Box::new(move |result| {
+                            CommandExecutor::instance().send(Command::Ledger(
+                                LedgerCommand::SignAndSubmitRequestWithFeesResultAck(fee_payment_id, result.map(|receipts_json| (response.clone(), receipts_json))))).unwrap();
+                        })))).unwrap(),
+                    Err(err) => CommandExecutor::instance().send(Command::Ledger(
+                        LedgerCommand::SignAndSubmitRequestWithFeesResultAck(fee_payment_id, Err(err)))).unwrap()
+                }
+            })),
+            Err(err) => self._complete_fee_payment(fee_payment_id, Err(err))
+        }
+    }
+
+    fn _get_request_fee(&self, fee_payment_id: i32, fees: String) {
+        let (wallet_handle, submitter_did, request_json, txn_type, payment_method) = match self.fee_payments.borrow().get(&fee_payment_id) {
+            Some(fee_payment) => (fee_payment.wallet_handle, fee_payment.submitter_did.clone(), fee_payment.request_json.clone(),
+                                  fee_payment.txn_type.clone(), fee_payment.payment_method.clone()),
+            None => return error!("Can't get request fee for id {} - appropriate fee payment not found!", fee_payment_id)
+        };
+
+        // Fee rules may depend on the request, so payment method computes the fee if it can
+        CommandExecutor::instance().send(Command::Payments(PaymentsCommand::GetRequestFee(
+            wallet_handle,
+            submitter_did,
+            payment_method,
+            request_json,
+            txn_type,
+            fees,
+            Box::new(move |result| {
+                CommandExecutor::instance().send(Command::Ledger(
+                    LedgerCommand::SignAndSubmitRequestWithFeesFeeAck(fee_payment_id, result))).unwrap();
+            })))).unwrap();
+    }
+
+    fn _add_request_fees(&self, fee_payment_id: i32, fee: u64) {
+        let (pool_handle, wallet_handle, submitter_did, request_json, txn_type, payment_method, change_address) = match self.fee_payments.borrow().get(&fee_payment_id) {
+            Some(fee_payment) => (fee_payment.pool_handle, fee_payment.wallet_handle, fee_payment.submitter_did.clone(), fee_payment.request_json.clone(),
+                                  fee_payment.txn_type.clone(), fee_payment.payment_method.clone(), fee_payment.change_address.clone()),
+            None => return error!("Can't add fees for id {} - appropriate fee payment not found!", fee_payment_id)
+        };
+
+        if fee == 0 {
+            // Nothing to pay for this txn type, so the request is sent as is
+            return match self._sign_request(wallet_handle, &submitter_did, &request_json, SignatureType::Single) {
+                Ok(signed_request) => self.submit_request(pool_handle, &signed_request, Box::new(move |result| {
+                    CommandExecutor::instance().send(Command::Ledger(
+                        LedgerCommand::SignAndSubmitRequestWithFeesResultAck(fee_payment_id, result.map(|response| (response, "[]".to_string()))))).unwrap();
+                })),
+                Err(err) => self._complete_fee_payment(fee_payment_id, Err(err))
+            };
+        }
+
+        let mut fees_json = serde_json::Map::new();
+        fees_json.insert(txn_type, Value::from(fee));
+        let fees_json = Value::Object(fees_json).to_string();
+
+        CommandExecutor::instance().send(Command::Payments(PaymentsCommand::PreparePayment(
+            wallet_handle,
+            pool_handle,
+            submitter_did.clone(),
+            payment_method,
+            0,
+            fees_json,
+            change_address,
+            None,
+            Box::new(move |result| {
+                match result {
+                    Ok((inputs_json, outputs_json)) => CommandExecutor::instance().send(Command::Payments(PaymentsCommand::AddRequestFees(
+                        wallet_handle,
+                        submitter_did.clone(),
+                        request_json.clone(),
+                        inputs_json,
+                        outputs_json,
+                        Box::new(move |result| {
+                            CommandExecutor::instance().send(Command::Ledger(
+                                LedgerCommand::SignAndSubmitRequestWithFeesRequestAck(fee_payment_id, result.map(|(request_json, _)| request_json)))).unwrap();
+                        })))).unwrap(),
+                    Err(err) => CommandExecutor::instance().send(Command::Ledger(
+                        LedgerCommand::SignAndSubmitRequestWithFeesRequestAck(fee_payment_id, Err(err)))).unwrap()
+                }
+            })))).unwrap();
+    }
+
+    fn _complete_fee_payment(&self, fee_payment_id: i32, result: Result<(String, String), IndyError>) {
+        let fee_payment = self.fee_payments.borrow_mut().remove(&fee_payment_id);
+        match fee_payment {
+            Some(fee_payment) => (fee_payment.cb)(result),
+            None => error!("Can't complete fee payment for id {} - appropriate fee payment not found!", fee_payment_id)
+        }
+    }
+
+    fn _get_txn_type(request_json: &str) -> Result<String, IndyError> {
+        let request: Value = serde_json::from_str(request_json)
+            .map_err(|err| CommonError::InvalidStructure(format!("Request is invalid json: {:?}", err)))?;
+
+        match request["operation"]["type"] {
+            Value::String(ref txn_type) => Ok(txn_type.to_string()),
+            Value::Number(ref txn_type) => Ok(txn_type.to_string()),
+            _ => Err(IndyError::CommonError(CommonError::InvalidStructure("Request doesn't contain operation type".to_string())))
+        }
+    }
+
     fn _sign_request(&self,
                      wallet_handle: i32,
                      submitter_did: &str,
@@ -392,7 +695,7 @@ impl LedgerCommandExecutor {
                         if !request.contains_key("signatures") {
                             request.insert("signatures".to_string(), Value::Object(serde_json::Map::new()));
                         }
-                        request["signatures"].as_object_mut().unwrap().insert(submitter_did.to_string(), Value::String(Base58::encode(&signature)));
+                        request["signatures"].as_object_mut().unwrap().insert(qualifier::unqualify(submitter_did).to_string(), Value::String(Base58::encode(&signature)));
                     });
             }
         }
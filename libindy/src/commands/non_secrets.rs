@@ -23,12 +23,19 @@ pub enum NonSecretsCommand {
                       String, // type
                       String, // id
                       String, // value
+                      Option<i64>, // expected version
                       Box<Fn(Result<(), IndyError>) + Send>),
     UpdateRecordTags(i32, // handle
                      String, // type
                      String, // id
                      String, //tags json
+                     Option<i64>, // expected version
                      Box<Fn(Result<(), IndyError>) + Send>),
+    UpdateRecordExpiry(i32, // handle
+                       String, // type
+                       String, // id
+                       Option<i64>, // expires at
+                       Box<Fn(Result<(), IndyError>) + Send>),
     AddRecordTags(i32, // handle
                   String, // type
                   String, // id
@@ -80,13 +87,17 @@ impl NonSecretsCommandExecutor {
                 info!(target: "non_secrets_command_executor", "AddRecord command received");
                 cb(self.add_record(handle, &type_, &id, &value, tags_json.as_ref().map(String::as_str)));
             }
-            NonSecretsCommand::UpdateRecordValue(handle, type_, id, value, cb) => {
+            NonSecretsCommand::UpdateRecordValue(handle, type_, id, value, expected_version, cb) => {
                 info!(target: "non_secrets_command_executor", "UpdateRecordValue command received");
-                cb(self.update_record_value(handle, &type_, &id, &value));
+                cb(self.update_record_value(handle, &type_, &id, &value, expected_version));
             }
-            NonSecretsCommand::UpdateRecordTags(handle, type_, id, tags_json, cb) => {
+            NonSecretsCommand::UpdateRecordTags(handle, type_, id, tags_json, expected_version, cb) => {
                 info!(target: "non_secrets_command_executor", "UpdateRecordTags command received");
-                cb(self.update_record_tags(handle, &type_, &id, &tags_json));
+                cb(self.update_record_tags(handle, &type_, &id, &tags_json, expected_version));
+            }
+            NonSecretsCommand::UpdateRecordExpiry(handle, type_, id, expires_at, cb) => {
+                info!(target: "non_secrets_command_executor", "UpdateRecordExpiry command received");
+                cb(self.update_record_expiry(handle, &type_, &id, expires_at));
             }
             NonSecretsCommand::AddRecordTags(handle, type_, id, tags_json, cb) => {
                 info!(target: "non_secrets_command_executor", "AddRecordTags command received");
@@ -140,12 +151,17 @@ impl NonSecretsCommandExecutor {
                            wallet_handle: i32,
                            type_: &str,
                            id: &str,
-                           value: &str) -> Result<(), IndyError> {
-        trace!("update_record_value >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, value: {:?}", wallet_handle, type_, id, value);
+                           value: &str,
+                           expected_version: Option<i64>) -> Result<(), IndyError> {
+        trace!("update_record_value >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, value: {:?}, expected_version: {:?}",
+               wallet_handle, type_, id, value, expected_version);
 
         self._check_type(type_)?;
 
-        let res = self.wallet_service.update_record_value(wallet_handle, type_, id, value)?;
+        let res = match expected_version {
+            Some(expected_version) => self.wallet_service.update_record_value_if_version(wallet_handle, type_, id, value, expected_version)?,
+            None => self.wallet_service.update_record_value(wallet_handle, type_, id, value)?
+        };
 
         trace!("update_record_value <<< res: {:?}", res);
 
@@ -156,18 +172,39 @@ impl NonSecretsCommandExecutor {
                           wallet_handle: i32,
                           type_: &str,
                           id: &str,
-                          tags_json: &str) -> Result<(), IndyError> {
-        trace!("update_record_tags >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, tags_json: {:?}", wallet_handle, type_, id, tags_json);
+                          tags_json: &str,
+                          expected_version: Option<i64>) -> Result<(), IndyError> {
+        trace!("update_record_tags >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, tags_json: {:?}, expected_version: {:?}",
+               wallet_handle, type_, id, tags_json, expected_version);
 
         self._check_type(type_)?;
 
-        let res = self.wallet_service.update_record_tags(wallet_handle, type_, id, tags_json)?;
+        let res = match expected_version {
+            Some(expected_version) => self.wallet_service.update_record_tags_if_version(wallet_handle, type_, id, tags_json, expected_version)?,
+            None => self.wallet_service.update_record_tags(wallet_handle, type_, id, tags_json)?
+        };
 
         trace!("update_record_tags <<< res: {:?}", res);
 
         Ok(res)
     }
 
+    fn update_record_expiry(&self,
+                            wallet_handle: i32,
+                            type_: &str,
+                            id: &str,
+                            expires_at: Option<i64>) -> Result<(), IndyError> {
+        trace!("update_record_expiry >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, expires_at: {:?}", wallet_handle, type_, id, expires_at);
+
+        self._check_type(type_)?;
+
+        let res = self.wallet_service.update_record_expiry(wallet_handle, type_, id, expires_at)?;
+
+        trace!("update_record_expiry <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn add_record_tags(&self,
                        wallet_handle: i32,
                        type_: &str,
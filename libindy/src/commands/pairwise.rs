@@ -3,14 +3,18 @@ extern crate indy_crypto;
 
 use errors::common::CommonError;
 use errors::indy::IndyError;
-use services::wallet::{WalletService, RecordOptions};
-use domain::pairwise::{Pairwise, PairwiseInfo};
+use errors::wallet::WalletError;
+use services::wallet::{WalletService, WalletSearch, RecordOptions, SearchOptions};
+use domain::pairwise::{Pairwise, PairwiseInfo, PairwiseSearchResult};
 use domain::crypto::did::{Did, TheirDid};
+use utils::sequence::SequenceUtils;
 
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 use std::str;
 
-use self::indy_crypto::utils::json::JsonEncodable;
+use self::indy_crypto::utils::json::{JsonEncodable, JsonDecodable};
 
 pub enum PairwiseCommand {
     PairwiseExists(
@@ -34,17 +38,42 @@ pub enum PairwiseCommand {
         i32, // wallet handle
         String, // their_did
         Option<String>, // metadata
-        Box<Fn(Result<(), IndyError>) + Send>)
+        Box<Fn(Result<(), IndyError>) + Send>),
+    AddPairwiseMyDid(
+        i32, // wallet handle
+        String, // their_did
+        String, // my_did
+        Box<Fn(Result<(), IndyError>) + Send>),
+    DeletePairwise(
+        i32, // wallet handle
+        String, // their_did
+        Box<Fn(Result<(), IndyError>) + Send>),
+    SearchPairwise(
+        i32, // wallet handle
+        String, // query json
+        Box<Fn(Result<i32, IndyError>) + Send>),
+    FetchPairwiseSearchNextRecords(
+        i32, // search handle
+        usize, // count
+        Box<Fn(Result<String, IndyError>) + Send>),
+    ClosePairwiseSearch(
+        i32, // search handle
+        Box<Fn(Result<(), IndyError>) + Send>),
+    AddMissingPairwiseTags(
+        i32, // wallet handle
+    )
 }
 
 pub struct PairwiseCommandExecutor {
-    wallet_service: Rc<WalletService>
+    wallet_service: Rc<WalletService>,
+    searches: RefCell<HashMap<i32, Box<WalletSearch>>>,
 }
 
 impl PairwiseCommandExecutor {
     pub fn new(wallet_service: Rc<WalletService>) -> PairwiseCommandExecutor {
         PairwiseCommandExecutor {
-            wallet_service
+            wallet_service,
+            searches: RefCell::new(HashMap::new()),
         }
     }
 
@@ -70,6 +99,32 @@ impl PairwiseCommandExecutor {
                 info!(target: "pairwise_command_executor", "SetPairwiseMetadata command received");
                 cb(self.set_pairwise_metadata(wallet_handle, &their_did, metadata.as_ref().map(String::as_str)));
             }
+            PairwiseCommand::AddPairwiseMyDid(wallet_handle, their_did, my_did, cb) => {
+                info!(target: "pairwise_command_executor", "AddPairwiseMyDid command received");
+                cb(self.add_pairwise_my_did(wallet_handle, &their_did, &my_did));
+            }
+            PairwiseCommand::DeletePairwise(wallet_handle, their_did, cb) => {
+                info!(target: "pairwise_command_executor", "DeletePairwise command received");
+                cb(self.delete_pairwise(wallet_handle, &their_did));
+            }
+            PairwiseCommand::SearchPairwise(wallet_handle, query_json, cb) => {
+                info!(target: "pairwise_command_executor", "SearchPairwise command received");
+                cb(self.search_pairwise(wallet_handle, &query_json));
+            }
+            PairwiseCommand::FetchPairwiseSearchNextRecords(search_handle, count, cb) => {
+                info!(target: "pairwise_command_executor", "FetchPairwiseSearchNextRecords command received");
+                cb(self.fetch_pairwise_search_next_records(search_handle, count));
+            }
+            PairwiseCommand::ClosePairwiseSearch(search_handle, cb) => {
+                info!(target: "pairwise_command_executor", "ClosePairwiseSearch command received");
+                cb(self.close_pairwise_search(search_handle));
+            }
+            PairwiseCommand::AddMissingPairwiseTags(wallet_handle) => {
+                info!(target: "pairwise_command_executor", "AddMissingPairwiseTags command received");
+                if let Err(err) = self.add_missing_pairwise_tags(wallet_handle) {
+                    warn!("Can't add missing pairwise tags for wallet {}: {:?}", wallet_handle, err);
+                }
+            }
         };
     }
 
@@ -98,10 +153,11 @@ impl PairwiseCommandExecutor {
         let pairwise = Pairwise {
             my_did: my_did.to_string(),
             their_did: their_did.to_string(),
-            metadata: metadata.map(str::to_string)
+            metadata: metadata.map(str::to_string),
+            my_dids: Vec::new()
         };
 
-        self.wallet_service.add_indy_object(wallet_handle, &their_did, &pairwise, "{}")?;
+        self.wallet_service.add_indy_object(wallet_handle, &their_did, &pairwise, &self._pairwise_tags_json(&pairwise)?)?;
 
         debug!("create_pairwise <<<");
 
@@ -165,9 +221,163 @@ impl PairwiseCommandExecutor {
         pairwise.metadata = metadata.as_ref().map(|m| m.to_string());
 
         self.wallet_service.update_indy_object(wallet_handle, &their_did, &pairwise)?;
+        self.wallet_service.update_indy_record_tags::<Pairwise>(wallet_handle, &their_did, &self._pairwise_tags_json(&pairwise)?)?;
 
         debug!("set_pairwise_metadata <<<");
 
         Ok(())
     }
+
+    fn add_pairwise_my_did(&self,
+                           wallet_handle: i32,
+                           their_did: &str,
+                           my_did: &str) -> Result<(), IndyError> {
+        debug!("add_pairwise_my_did >>> wallet_handle: {:?}, their_did: {:?}, my_did: {:?}", wallet_handle, their_did, my_did);
+
+        self.wallet_service.get_indy_record::<Did>(wallet_handle, &my_did, &RecordOptions::id())?;
+
+        let mut pairwise: Pairwise =
+            self.wallet_service.get_indy_object(wallet_handle, &their_did, &RecordOptions::id_value(), &mut String::new())?;
+
+        let mut my_dids = pairwise.my_dids();
+        my_dids.retain(|did| did != my_did);
+        my_dids.push(my_did.to_string());
+
+        pairwise.my_did = my_did.to_string();
+        pairwise.my_dids = my_dids;
+
+        self.wallet_service.update_indy_object(wallet_handle, &their_did, &pairwise)?;
+        self.wallet_service.update_indy_record_tags::<Pairwise>(wallet_handle, &their_did, &self._pairwise_tags_json(&pairwise)?)?;
+
+        debug!("add_pairwise_my_did <<<");
+
+        Ok(())
+    }
+
+    fn delete_pairwise(&self,
+                       wallet_handle: i32,
+                       their_did: &str) -> Result<(), IndyError> {
+        debug!("delete_pairwise >>> wallet_handle: {:?}, their_did: {:?}", wallet_handle, their_did);
+
+        self.wallet_service.delete_indy_record::<Pairwise>(wallet_handle, &their_did)?;
+
+        debug!("delete_pairwise <<<");
+
+        Ok(())
+    }
+
+    fn search_pairwise(&self,
+                       wallet_handle: i32,
+                       query_json: &str) -> Result<i32, IndyError> {
+        debug!("search_pairwise >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+        let search = self.wallet_service.search_indy_records::<Pairwise>(wallet_handle, query_json, &SearchOptions::id_value())?;
+
+        let search_handle = SequenceUtils::get_next_id();
+
+        self.searches.borrow_mut().insert(search_handle, Box::new(search));
+
+        debug!("search_pairwise <<< search_handle: {:?}", search_handle);
+
+        Ok(search_handle)
+    }
+
+    fn fetch_pairwise_search_next_records(&self,
+                                          search_handle: i32,
+                                          count: usize) -> Result<String, IndyError> {
+        debug!("fetch_pairwise_search_next_records >>> search_handle: {:?}, count: {:?}", search_handle, count);
+
+        let mut searches = self.searches.borrow_mut();
+        let search = searches.get_mut(&search_handle)
+            .ok_or(WalletError::InvalidHandle(format!("Unknown Pairwise search handle: {}", search_handle)))?;
+
+        let mut records: Vec<Pairwise> = Vec::new();
+        for _ in 0..count {
+            match search.fetch_next_record()? {
+                Some(record) => {
+                    let pairwise_value = record.get_value()
+                        .ok_or(CommonError::InvalidStructure(format!("Pairwise not found for id: {}", record.get_id())))?;
+
+                    let mut pairwise = Pairwise::from_json(pairwise_value)
+                        .map_err(|err| CommonError::InvalidState(format!("Can't deserialize Pairwise: {:?}", err)))?;
+                    pairwise.my_dids = pairwise.my_dids();
+
+                    records.push(pairwise)
+                }
+                None => break
+            }
+        }
+
+        let search_result = PairwiseSearchResult {
+            total_count: search.get_total_count()?,
+            records: if records.is_empty() { None } else { Some(records) }
+        };
+
+        let res = search_result.to_json()
+            .map_err(|err| CommonError::InvalidState(format!("Can't serialize PairwiseSearchResult: {:?}", err)))?;
+
+        debug!("fetch_pairwise_search_next_records <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn close_pairwise_search(&self,
+                             search_handle: i32) -> Result<(), IndyError> {
+        debug!("close_pairwise_search >>> search_handle: {:?}", search_handle);
+
+        let res = match self.searches.borrow_mut().remove(&search_handle) {
+            Some(_) => Ok(()),
+            None => Err(WalletError::InvalidHandle(format!("Unknown Pairwise search handle: {}", search_handle)))
+        }?;
+
+        debug!("close_pairwise_search <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    // Pairwise records created by older versions of libindy have no tags. They are tagged on
+    // every read-write opening; read-only opening requires a read-write one before, so
+    // read-only handles have nothing to tag and can't update records anyway.
+    fn add_missing_pairwise_tags(&self, wallet_handle: i32) -> Result<(), IndyError> {
+        debug!("add_missing_pairwise_tags >>> wallet_handle: {:?}", wallet_handle);
+
+        if self.wallet_service.is_read_only(wallet_handle)? {
+            return Ok(());
+        }
+
+        let mut pairwise_search =
+            self.wallet_service.search_indy_records::<Pairwise>(wallet_handle, "{}", &SearchOptions::full())?;
+
+        let mut untagged: Vec<Pairwise> = Vec::new();
+
+        while let Some(record) = pairwise_search.fetch_next_record()? {
+            let tags: HashMap<String, String> = serde_json::from_str(record.get_tags().unwrap_or("{}"))
+                .map_err(|err| CommonError::InvalidState(format!("Can't deserialize Pairwise tags: {:?}", err)))?;
+
+            if tags.contains_key("their_did") {
+                continue;
+            }
+
+            let pairwise_value = record.get_value()
+                .ok_or(CommonError::InvalidStructure(format!("Pairwise not found for id: {}", record.get_id())))?;
+
+            untagged.push(Pairwise::from_json(pairwise_value)
+                .map_err(|err| CommonError::InvalidState(format!("Can't deserialize Pairwise: {:?}", err)))?);
+        }
+
+        for pairwise in untagged.iter() {
+            self.wallet_service.update_indy_record_tags::<Pairwise>(wallet_handle, &pairwise.their_did, &self._pairwise_tags_json(pairwise)?)?;
+        }
+
+        debug!("add_missing_pairwise_tags <<< tagged: {:?}", untagged.len());
+
+        Ok(())
+    }
+
+    fn _pairwise_tags_json(&self, pairwise: &Pairwise) -> Result<String, IndyError> {
+        let res = pairwise.tags().to_json()
+            .map_err(|err| CommonError::InvalidState(format!("Can't serialize PairwiseTags: {:?}", err)))?;
+
+        Ok(res)
+    }
 }
\ No newline at end of file
@@ -2,7 +2,10 @@ extern crate libc;
 
 use errors::indy::IndyError;
 use errors::payments::PaymentsError;
-use services::payments::{PaymentsMethodCBs, PaymentsService};
+use services::payments::{PaymentsMethodCBs, PaymentsService, UTXOInfo, InputSelectionStrategy, PreparePaymentConfig, PaymentHistoryContext};
+use commands::{Command, CommandExecutor};
+use commands::ledger::LedgerCommand;
+use utils::sequence::SequenceUtils;
 
 use serde_json;
 use std::rc::Rc;
@@ -10,6 +13,7 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use services::wallet::{WalletService, RecordOptions};
 use errors::common::CommonError;
+use errors::wallet::WalletError;
 use std::vec::Vec;
 use std::string::String;
 use services::crypto::CryptoService;
@@ -19,6 +23,8 @@ pub enum PaymentsCommand {
         String, //type
         PaymentsMethodCBs, //method callbacks
         Box<Fn(Result<(), IndyError>) + Send>),
+    ListMethods(
+        Box<Fn(Result<String, IndyError>) + Send>),
     CreateAddress(
         i32, //wallet_handle
         String, //type
@@ -72,6 +78,16 @@ pub enum PaymentsCommand {
     BuildPaymentReqAck(
         i32,
         Result<String, PaymentsError>),
+    BuildSplitPaymentReq(
+        i32, //wallet_handle
+        String, //submitter did
+        String, //inputs
+        String, //outputs
+        Box<Fn(Result<String, IndyError>) + Send>),
+    BuildSplitPaymentReqAck(
+        i32, //split id
+        String, //payment_method
+        Result<String, IndyError>),
     ParsePaymentResponse(
         String, //payment_method
         String, //response
@@ -110,7 +126,112 @@ pub enum PaymentsCommand {
         Box<Fn(Result<String, IndyError>) + Send>),
     ParseGetTxnFeesResponseAck(
         i32,
-        Result<String, PaymentsError>)
+        Result<String, PaymentsError>),
+    BuildVerifyPaymentReq(
+        i32, //wallet_handle
+        String, //submitter did
+        String, //receipt
+        Box<Fn(Result<(String, String), IndyError>) + Send>),
+    BuildVerifyPaymentReqAck(
+        i32,
+        Result<String, PaymentsError>),
+    ParseVerifyPaymentResponse(
+        String, //payment_method
+        String, //resp_json
+        Box<Fn(Result<String, IndyError>) + Send>),
+    ParseVerifyPaymentResponseAck(
+        i32,
+        Result<String, PaymentsError>),
+    SignWithAddress(
+        i32, //wallet_handle
+        String, //address
+        Vec<u8>, //message
+        Box<Fn(Result<Vec<u8>, IndyError>) + Send>),
+    SignWithAddressAck(
+        i32, //handle
+        Result<Vec<u8>, PaymentsError>),
+    VerifyWithAddress(
+        String, //address
+        Vec<u8>, //message
+        Vec<u8>, //signature
+        Box<Fn(Result<bool, IndyError>) + Send>),
+    VerifyWithAddressAck(
+        i32, //handle
+        Result<bool, PaymentsError>),
+    GetRequestFee(
+        i32, //wallet_handle
+        String, //submitter did
+        String, //payment_method
+        String, //req
+        String, //txn type
+        String, //fees
+        Box<Fn(Result<u64, IndyError>) + Send>),
+    GetRequestFeeAck(
+        i32, //handle
+        Result<u64, PaymentsError>),
+    PreparePayment(
+        i32, //wallet_handle
+        i32, //pool_handle
+        String, //submitter did
+        String, //payment_method
+        u64, //amount
+        String, //fees
+        String, //change address
+        Option<String>, //config
+        Box<Fn(Result<(String, String), IndyError>) + Send>),
+    PreparePaymentGetUtxoAck(
+        i32, //preparation id
+        Result<String, IndyError>),
+    PreparePaymentUtxoAck(
+        i32, //preparation id
+        Result<String, IndyError>),
+    ParsePaymentResponseWithHistory(
+        i32, //wallet_handle
+        String, //payment_method
+        String, //response
+        String, //history
+        Box<Fn(Result<String, IndyError>) + Send>),
+    ParseResponseWithFeesWithHistory(
+        i32, //wallet_handle
+        String, //payment_method
+        String, //response
+        String, //history
+        Box<Fn(Result<String, IndyError>) + Send>),
+    ParseWithHistoryAck(
+        i32, //recording id
+        Result<String, IndyError>),
+    SearchPaymentHistory(
+        i32, //wallet_handle
+        String, //query
+        Box<Fn(Result<String, IndyError>) + Send>),
+}
+
+const PAYMENT_HISTORY_RECORD_TYPE: &str = "Indy::PaymentHistory";
+
+struct PaymentPreparation {
+    wallet_handle: i32,
+    pool_handle: i32,
+    submitter_did: String,
+    payment_method: String,
+    target: u64,
+    change_address: String,
+    strategy: InputSelectionStrategy,
+    addresses: Vec<String>,
+    utxos: Vec<UTXOInfo>,
+    cb: Box<Fn(Result<(String, String), IndyError>) + Send>,
+}
+
+struct PaymentHistoryRecording {
+    wallet_handle: i32,
+    payment_method: String,
+    response: String,
+    context: PaymentHistoryContext,
+    cb: Box<Fn(Result<String, IndyError>) + Send>,
+}
+
+struct PaymentSplit {
+    requests: Vec<(String, Option<String>)>,
+    cb: Box<Fn(Result<String, IndyError>) + Send>,
 }
 
 pub struct PaymentsCommandExecutor {
@@ -118,6 +239,12 @@ pub struct PaymentsCommandExecutor {
     wallet_service: Rc<WalletService>,
     crypto_service: Rc<CryptoService>,
     pending_callbacks: RefCell<HashMap<i32, Box<Fn(Result<String, IndyError>) + Send>>>,
+    pending_preparations: RefCell<HashMap<i32, PaymentPreparation>>,
+    pending_splits: RefCell<HashMap<i32, PaymentSplit>>,
+    pending_history_recordings: RefCell<HashMap<i32, PaymentHistoryRecording>>,
+    pending_sign_callbacks: RefCell<HashMap<i32, Box<Fn(Result<Vec<u8>, IndyError>) + Send>>>,
+    pending_verify_callbacks: RefCell<HashMap<i32, Box<Fn(Result<bool, IndyError>) + Send>>>,
+    pending_fee_callbacks: RefCell<HashMap<i32, Box<Fn(Result<u64, IndyError>) + Send>>>,
 }
 
 impl PaymentsCommandExecutor {
@@ -127,6 +254,12 @@ impl PaymentsCommandExecutor {
             wallet_service,
             crypto_service,
             pending_callbacks: RefCell::new(HashMap::new()),
+            pending_preparations: RefCell::new(HashMap::new()),
+            pending_splits: RefCell::new(HashMap::new()),
+            pending_history_recordings: RefCell::new(HashMap::new()),
+            pending_sign_callbacks: RefCell::new(HashMap::new()),
+            pending_verify_callbacks: RefCell::new(HashMap::new()),
+            pending_fee_callbacks: RefCell::new(HashMap::new()),
         }
     }
 
@@ -136,6 +269,10 @@ impl PaymentsCommandExecutor {
                 info!(target: "payments_command_executor", "RegisterMethod command received");
                 cb(self.register_method(&type_, method_cbs));
             }
+            PaymentsCommand::ListMethods(cb) => {
+                info!(target: "payments_command_executor", "ListMethods command received");
+                cb(self.list_methods());
+            }
             PaymentsCommand::CreateAddress(wallet_handle, type_, config, cb) => {
                 info!(target: "payments_command_executor", "CreateAddress command received");
                 self.create_address(wallet_handle, &type_, &config, cb);
@@ -188,6 +325,14 @@ impl PaymentsCommandExecutor {
                 info!(target: "payments_command_executor", "BuildPaymentReqAck command received");
                 self.build_payment_req_ack(cmd_handle, result);
             }
+            PaymentsCommand::BuildSplitPaymentReq(wallet_handle, submitter_did, inputs, outputs, cb) => {
+                info!(target: "payments_command_executor", "BuildSplitPaymentReq command received");
+                self.build_split_payment_req(wallet_handle, &submitter_did, &inputs, &outputs, cb);
+            }
+            PaymentsCommand::BuildSplitPaymentReqAck(split_id, payment_method, result) => {
+                info!(target: "payments_command_executor", "BuildSplitPaymentReqAck command received");
+                self.build_split_payment_req_ack(split_id, &payment_method, result);
+            }
             PaymentsCommand::ParsePaymentResponse(payment_method, response, cb) => {
                 info!(target: "payments_command_executor", "ParsePaymentResponse command received");
                 self.parse_payment_response(&payment_method, &response, cb);
@@ -228,6 +373,74 @@ impl PaymentsCommandExecutor {
                 info!(target: "payments_command_executor", "ParseGetTxnFeesResponseAck command received");
                 self.parse_get_txn_fees_response_ack(cmd_handle, result);
             }
+            PaymentsCommand::BuildVerifyPaymentReq(wallet_handle, submitter_did, receipt, cb) => {
+                info!(target: "payments_command_executor", "BuildVerifyPaymentReq command received");
+                self.build_verify_payment_request(wallet_handle, &submitter_did, &receipt, cb);
+            }
+            PaymentsCommand::BuildVerifyPaymentReqAck(command_handle, result) => {
+                info!(target: "payments_command_executor", "BuildVerifyPaymentReqAck command received");
+                self.build_verify_payment_request_ack(command_handle, result);
+            }
+            PaymentsCommand::ParseVerifyPaymentResponse(payment_method, resp_json, cb) => {
+                info!(target: "payments_command_executor", "ParseVerifyPaymentResponse command received");
+                self.parse_verify_payment_response(&payment_method, &resp_json, cb);
+            }
+            PaymentsCommand::ParseVerifyPaymentResponseAck(command_handle, result) => {
+                info!(target: "payments_command_executor", "ParseVerifyPaymentResponseAck command received");
+                self.parse_verify_payment_response_ack(command_handle, result);
+            }
+            PaymentsCommand::SignWithAddress(wallet_handle, address, message, cb) => {
+                info!(target: "payments_command_executor", "SignWithAddress command received");
+                self.sign_with_address(wallet_handle, &address, &message, cb);
+            }
+            PaymentsCommand::SignWithAddressAck(command_handle, result) => {
+                info!(target: "payments_command_executor", "SignWithAddressAck command received");
+                self.sign_with_address_ack(command_handle, result);
+            }
+            PaymentsCommand::VerifyWithAddress(address, message, signature, cb) => {
+                info!(target: "payments_command_executor", "VerifyWithAddress command received");
+                self.verify_with_address(&address, &message, &signature, cb);
+            }
+            PaymentsCommand::VerifyWithAddressAck(command_handle, result) => {
+                info!(target: "payments_command_executor", "VerifyWithAddressAck command received");
+                self.verify_with_address_ack(command_handle, result);
+            }
+            PaymentsCommand::GetRequestFee(wallet_handle, submitter_did, payment_method, req, txn_type, fees, cb) => {
+                info!(target: "payments_command_executor", "GetRequestFee command received");
+                self.get_request_fee(wallet_handle, &submitter_did, &payment_method, &req, &txn_type, &fees, cb);
+            }
+            PaymentsCommand::GetRequestFeeAck(command_handle, result) => {
+                info!(target: "payments_command_executor", "GetRequestFeeAck command received");
+                self.get_request_fee_ack(command_handle, result);
+            }
+            PaymentsCommand::PreparePayment(wallet_handle, pool_handle, submitter_did, payment_method, amount, fees, change_address, config, cb) => {
+                info!(target: "payments_command_executor", "PreparePayment command received");
+                self.prepare_payment(wallet_handle, pool_handle, &submitter_did, &payment_method, amount, &fees, &change_address, config.as_ref().map(String::as_str), cb);
+            }
+            PaymentsCommand::PreparePaymentGetUtxoAck(preparation_id, result) => {
+                info!(target: "payments_command_executor", "PreparePaymentGetUtxoAck command received");
+                self.prepare_payment_get_utxo_ack(preparation_id, result);
+            }
+            PaymentsCommand::PreparePaymentUtxoAck(preparation_id, result) => {
+                info!(target: "payments_command_executor", "PreparePaymentUtxoAck command received");
+                self.prepare_payment_utxo_ack(preparation_id, result);
+            }
+            PaymentsCommand::ParsePaymentResponseWithHistory(wallet_handle, payment_method, response, history, cb) => {
+                info!(target: "payments_command_executor", "ParsePaymentResponseWithHistory command received");
+                self.parse_payment_response_with_history(wallet_handle, &payment_method, &response, &history, cb);
+            }
+            PaymentsCommand::ParseResponseWithFeesWithHistory(wallet_handle, payment_method, response, history, cb) => {
+                info!(target: "payments_command_executor", "ParseResponseWithFeesWithHistory command received");
+                self.parse_response_with_fees_with_history(wallet_handle, &payment_method, &response, &history, cb);
+            }
+            PaymentsCommand::ParseWithHistoryAck(recording_id, result) => {
+                info!(target: "payments_command_executor", "ParseWithHistoryAck command received");
+                self.parse_with_history_ack(recording_id, result);
+            }
+            PaymentsCommand::SearchPaymentHistory(wallet_handle, query, cb) => {
+                info!(target: "payments_command_executor", "SearchPaymentHistory command received");
+                cb(self.search_payment_history(wallet_handle, &query));
+            }
         }
     }
 
@@ -242,6 +455,17 @@ impl PaymentsCommandExecutor {
         res
     }
 
+    fn list_methods(&self) -> Result<String, IndyError> {
+        trace!("list_methods >>>");
+
+        let res = serde_json::to_string(&self.payments_service.list_methods())
+            .map_err(|err| IndyError::CommonError(CommonError::InvalidState(format!("Cannot serialize payment methods: {:?}", err))));
+
+        trace!("list_methods << res: {:?}", res);
+
+        res
+    }
+
     fn create_address(&self, wallet_handle: i32, type_: &str, config: &str, cb: Box<Fn(Result<String, IndyError>) + Send>) {
         trace!("create_address >>> wallet_handle: {:?}, type_: {:?}, config: {:?}", wallet_handle, type_, config);
         match self.wallet_service.check(wallet_handle).map_err(map_err_err!()) {
@@ -275,17 +499,8 @@ impl PaymentsCommandExecutor {
             _ => (),
         };
 
-        match self.wallet_service.search_records(wallet_handle, "Indy::PaymentAddress", "{}", &RecordOptions::id_value()) {
-            Ok(mut search) => {
-                let mut list_addresses: Vec<String> = Vec::new();
-
-                while let Ok(Some(payment_address)) = search.fetch_next_record() {
-                    match payment_address.get_value() {
-                        Some(value) => list_addresses.push(value.to_string()),
-                        None => cb(Err(IndyError::CommonError(CommonError::InvalidState(format!("Record value not found")))))
-                    }
-                }
-
+        match self._list_addresses(wallet_handle) {
+            Ok(list_addresses) => {
                 let json_string =
                     serde_json::to_string(&list_addresses)
                         .map_err(|err|
@@ -293,7 +508,7 @@ impl PaymentsCommandExecutor {
                                 CommonError::InvalidState(format!("Cannot deserialize List of Payment Addresses: {:?}", err))));
                 cb(json_string);
             }
-            Err(err) => cb(Err(IndyError::from(err)))
+            Err(err) => cb(Err(err))
         }
         trace!("list_addresses <<<");
     }
@@ -309,13 +524,10 @@ impl PaymentsCommandExecutor {
             _ => (),
         };
 
-        let method_from_inputs = self.payments_service.parse_method_from_inputs(inputs);
-
         let method = if outputs == "[]" {
-            method_from_inputs
+            self.payments_service.parse_method_from_inputs(inputs)
         } else {
-            let method_from_outputs = self.payments_service.parse_method_from_outputs(outputs);
-            PaymentsCommandExecutor::_merge_parse_result(method_from_inputs, method_from_outputs)
+            self.payments_service.parse_method_from_inputs_and_outputs(inputs, outputs)
         };
 
         match method {
@@ -408,9 +620,7 @@ impl PaymentsCommandExecutor {
             _ => ()
         };
 
-        let method_from_inputs = self.payments_service.parse_method_from_inputs(inputs);
-        let method_from_outputs = self.payments_service.parse_method_from_outputs(outputs);
-        let method = PaymentsCommandExecutor::_merge_parse_result(method_from_inputs, method_from_outputs);
+        let method = self.payments_service.parse_method_from_inputs_and_outputs(inputs, outputs);
 
         match method {
             Ok(type_) => {
@@ -433,6 +643,79 @@ impl PaymentsCommandExecutor {
         trace!("build_payment_req_ack <<<");
     }
 
+    fn build_split_payment_req(&self, wallet_handle: i32, submitter_did: &str, inputs: &str, outputs: &str, cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        trace!("build_split_payment_req >>> wallet_handle: {:?}, submitter_did: {:?}, inputs: {:?}, outputs: {:?}", wallet_handle, submitter_did, inputs, outputs);
+        match self.crypto_service.validate_did(submitter_did).map_err(map_err_err!()) {
+            Err(err) => return cb(Err(IndyError::from(err))),
+            _ => ()
+        }
+
+        match self.wallet_service.check(wallet_handle).map_err(map_err_err!()) {
+            Err(err) => return cb(Err(IndyError::from(err))),
+            _ => ()
+        };
+
+        let parts = match self.payments_service.split_by_method(inputs, outputs) {
+            Ok(parts) => parts,
+            Err(err) => return cb(Err(IndyError::from(err)))
+        };
+
+        let split_id = SequenceUtils::get_next_id();
+        self.pending_splits.borrow_mut().insert(split_id, PaymentSplit {
+            requests: parts.iter().map(|&(ref method, _, _)| (method.clone(), None)).collect(),
+            cb,
+        });
+
+        for (method, inputs, outputs) in parts {
+            let method_copy = method.clone();
+            self._process_method(
+                Box::new(move |result| {
+                    CommandExecutor::instance()
+                        .send(Command::Payments(PaymentsCommand::BuildSplitPaymentReqAck(split_id, method_copy.clone(), result))).unwrap();
+                }),
+                &|i| self.payments_service.build_payment_req(i, &method, wallet_handle, submitter_did, &inputs, &outputs)
+            );
+        }
+        trace!("build_split_payment_req <<<");
+    }
+
+    fn build_split_payment_req_ack(&self, split_id: i32, payment_method: &str, result: Result<String, IndyError>) {
+        trace!("build_split_payment_req_ack >>> split_id: {:?}, payment_method: {:?}, result: {:?}", split_id, payment_method, result);
+        let split = {
+            let mut splits = self.pending_splits.borrow_mut();
+
+            let completed = match splits.get_mut(&split_id) {
+                Some(split) => match result {
+                    Ok(ref request) => {
+                        for entry in split.requests.iter_mut().filter(|entry| entry.0 == payment_method) {
+                            entry.1 = Some(request.clone());
+                        }
+                        split.requests.iter().all(|entry| entry.1.is_some())
+                    }
+                    Err(_) => true
+                },
+                // the split has already failed on the request of another payment method
+                None => return trace!("build_split_payment_req_ack <<< split is already completed")
+            };
+
+            if completed { splits.remove(&split_id) } else { None }
+        };
+
+        if let Some(split) = split {
+            let res = result.and_then(|_| {
+                let requests: Vec<serde_json::Value> = split.requests.into_iter()
+                    .map(|(method, request)| json!({"paymentMethod": method, "request": request}))
+                    .collect();
+
+                serde_json::to_string(&requests)
+                    .map_err(|err| IndyError::CommonError(CommonError::InvalidState(format!("Cannot serialize payment requests: {:?}", err))))
+            });
+
+            (split.cb)(res);
+        }
+        trace!("build_split_payment_req_ack <<<");
+    }
+
     fn parse_payment_response(&self, payment_method: &str, response: &str, cb: Box<Fn(Result<String, IndyError>) + Send>) {
         trace!("parse_payment_response >>> response: {:?}", response);
         self._process_method(cb, &|i| self.payments_service.parse_payment_response(i, payment_method, response));
@@ -488,12 +771,17 @@ impl PaymentsCommandExecutor {
             _ => (),
         };
 
-        match serde_json::from_str::<HashMap<String, i64>>(fees) {
+        // amounts are checked by payment method as it may support fee rules in place of them
+        match serde_json::from_str::<HashMap<String, serde_json::Value>>(fees) {
             Err(err) => {
                 error!("Cannot deserialize Fees: {:?}", err);
                 cb(Err(IndyError::CommonError(CommonError::InvalidStructure(format!("Cannot deserialize Fees: {:?}", err)))))
             },
-            _ => self._process_method(cb, &|i| self.payments_service.build_set_txn_fees_req(i, type_, wallet_handle, submitter_did, fees)),
+            _ => {
+                // Fee schedules cached for automatic fee attachment are going to be outdated
+                CommandExecutor::instance().send(Command::Ledger(LedgerCommand::EvictTxnFees(None, Some(type_.to_string())))).unwrap();
+                self._process_method(cb, &|i| self.payments_service.build_set_txn_fees_req(i, type_, wallet_handle, submitter_did, fees))
+            }
         };
         trace!("build_set_txn_fees_req <<<");
     }
@@ -537,8 +825,441 @@ impl PaymentsCommandExecutor {
         trace!("parse_get_txn_fees_response_ack <<<");
     }
 
+    fn build_verify_payment_request(&self, wallet_handle: i32, submitter_did: &str, receipt: &str, cb: Box<Fn(Result<(String, String), IndyError>) + Send>) {
+        trace!("build_verify_payment_request >>> wallet_handle: {:?}, submitter_did: {:?}, receipt: {:?}", wallet_handle, submitter_did, receipt);
+        match self.crypto_service.validate_did(submitter_did).map_err(map_err_err!()) {
+            Err(err) => return cb(Err(IndyError::from(err))),
+            _ => ()
+        }
+        match self.wallet_service.check(wallet_handle).map_err(map_err_err!()) {
+            Err(err) => return cb(Err(IndyError::from(err))),
+            _ => (),
+        };
+
+        let method = match self.payments_service.parse_method_from_payment_address(receipt) {
+            Ok(method) => method,
+            Err(err) => return cb(Err(IndyError::from(err)))
+        };
+        let method_copy = method.to_string();
+
+        self._process_method(
+            Box::new(move |result| cb(result.map(|s| (s, method.to_string())))),
+            &|i| self.payments_service.build_verify_payment_req(i, &method_copy, wallet_handle, submitter_did, receipt)
+        );
+        trace!("build_verify_payment_request <<<");
+    }
+
+    fn build_verify_payment_request_ack(&self, cmd_handle: i32, result: Result<String, PaymentsError>) {
+        trace!("build_verify_payment_request_ack >>> result: {:?}", result);
+        self._common_ack_payments(cmd_handle, result, "BuildVerifyPaymentReqAck");
+        trace!("build_verify_payment_request_ack <<<");
+    }
+
+    fn parse_verify_payment_response(&self, type_: &str, resp_json: &str, cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        trace!("parse_verify_payment_response >>> response: {:?}", resp_json);
+        self._process_method(cb, &|i| self.payments_service.parse_verify_payment_response(i, type_, resp_json));
+        trace!("parse_verify_payment_response <<<");
+    }
+
+    fn parse_verify_payment_response_ack(&self, cmd_handle: i32, result: Result<String, PaymentsError>) {
+        trace!("parse_verify_payment_response_ack >>> result: {:?}", result);
+        self._common_ack_payments(cmd_handle, result, "ParseVerifyPaymentResponseAck");
+        trace!("parse_verify_payment_response_ack <<<");
+    }
+
+    fn sign_with_address(&self, wallet_handle: i32, address: &str, message: &[u8], cb: Box<Fn(Result<Vec<u8>, IndyError>) + Send>) {
+        trace!("sign_with_address >>> wallet_handle: {:?}, address: {:?}, message: {:?}", wallet_handle, address, message);
+        match self.wallet_service.check(wallet_handle).map_err(map_err_err!()) {
+            Err(err) => return cb(Err(IndyError::from(err))),
+            _ => (),
+        };
+
+        let method = match self.payments_service.parse_method_from_payment_address(address) {
+            Ok(method) => method,
+            Err(err) => return cb(Err(IndyError::from(err)))
+        };
+
+        let cmd_handle = SequenceUtils::get_next_id();
+        match self.payments_service.sign_with_address(cmd_handle, &method, wallet_handle, address, message) {
+            Ok(()) => { self.pending_sign_callbacks.borrow_mut().insert(cmd_handle, cb); }
+            Err(err) => cb(Err(IndyError::from(err)))
+        }
+        trace!("sign_with_address <<<");
+    }
+
+    fn sign_with_address_ack(&self, cmd_handle: i32, result: Result<Vec<u8>, PaymentsError>) {
+        trace!("sign_with_address_ack >>> result: {:?}", result);
+        match self.pending_sign_callbacks.borrow_mut().remove(&cmd_handle) {
+            Some(cb) => cb(result.map_err(IndyError::from)),
+            None => error!("Can't process PaymentsCommand::SignWithAddressAck for handle {} with result {:?} - appropriate callback not found!",
+                           cmd_handle, result),
+        }
+        trace!("sign_with_address_ack <<<");
+    }
+
+    fn verify_with_address(&self, address: &str, message: &[u8], signature: &[u8], cb: Box<Fn(Result<bool, IndyError>) + Send>) {
+        trace!("verify_with_address >>> address: {:?}, message: {:?}, signature: {:?}", address, message, signature);
+        let method = match self.payments_service.parse_method_from_payment_address(address) {
+            Ok(method) => method,
+            Err(err) => return cb(Err(IndyError::from(err)))
+        };
+
+        let cmd_handle = SequenceUtils::get_next_id();
+        match self.payments_service.verify_with_address(cmd_handle, &method, address, message, signature) {
+            Ok(()) => { self.pending_verify_callbacks.borrow_mut().insert(cmd_handle, cb); }
+            Err(err) => cb(Err(IndyError::from(err)))
+        }
+        trace!("verify_with_address <<<");
+    }
+
+    fn verify_with_address_ack(&self, cmd_handle: i32, result: Result<bool, PaymentsError>) {
+        trace!("verify_with_address_ack >>> result: {:?}", result);
+        match self.pending_verify_callbacks.borrow_mut().remove(&cmd_handle) {
+            Some(cb) => cb(result.map_err(IndyError::from)),
+            None => error!("Can't process PaymentsCommand::VerifyWithAddressAck for handle {} with result {:?} - appropriate callback not found!",
+                           cmd_handle, result),
+        }
+        trace!("verify_with_address_ack <<<");
+    }
+
+    fn get_request_fee(&self, wallet_handle: i32, submitter_did: &str, type_: &str, req: &str, txn_type: &str, fees: &str,
+                       cb: Box<Fn(Result<u64, IndyError>) + Send>) {
+        trace!("get_request_fee >>> wallet_handle: {:?}, submitter_did: {:?}, type_: {:?}, req: {:?}, txn_type: {:?}, fees: {:?}",
+               wallet_handle, submitter_did, type_, req, txn_type, fees);
+        match self.payments_service.has_request_fee_handler(type_) {
+            Ok(true) => {
+                let cmd_handle = SequenceUtils::get_next_id();
+                match self.payments_service.get_request_fee(cmd_handle, type_, wallet_handle, submitter_did, req, fees) {
+                    Ok(()) => { self.pending_fee_callbacks.borrow_mut().insert(cmd_handle, cb); }
+                    Err(err) => cb(Err(IndyError::from(err)))
+                }
+            }
+            Ok(false) => cb(self.payments_service.get_flat_fee(fees, txn_type).map_err(IndyError::from)),
+            Err(err) => cb(Err(IndyError::from(err)))
+        }
+        trace!("get_request_fee <<<");
+    }
+
+    fn get_request_fee_ack(&self, cmd_handle: i32, result: Result<u64, PaymentsError>) {
+        trace!("get_request_fee_ack >>> result: {:?}", result);
+        match self.pending_fee_callbacks.borrow_mut().remove(&cmd_handle) {
+            Some(cb) => cb(result.map_err(IndyError::from)),
+            None => error!("Can't process PaymentsCommand::GetRequestFeeAck for handle {} with result {:?} - appropriate callback not found!",
+                           cmd_handle, result),
+        }
+        trace!("get_request_fee_ack <<<");
+    }
+
+    fn prepare_payment(&self, wallet_handle: i32, pool_handle: i32, submitter_did: &str, type_: &str, amount: u64, fees: &str, change_address: &str,
+                       config: Option<&str>, cb: Box<Fn(Result<(String, String), IndyError>) + Send>) {
+        trace!("prepare_payment >>> wallet_handle: {:?}, pool_handle: {:?}, submitter_did: {:?}, type_: {:?}, amount: {:?}, fees: {:?}, change_address: {:?}, config: {:?}",
+               wallet_handle, pool_handle, submitter_did, type_, amount, fees, change_address, config);
+        match self.crypto_service.validate_did(submitter_did).map_err(map_err_err!()) {
+            Err(err) => return cb(Err(IndyError::from(err))),
+            _ => ()
+        }
+        match self.wallet_service.check(wallet_handle).map_err(map_err_err!()) {
+            Err(err) => return cb(Err(IndyError::from(err))),
+            _ => (),
+        };
+
+        let fees = match serde_json::from_str::<HashMap<String, serde_json::Value>>(fees) {
+            Ok(fees) => fees,
+            Err(err) => {
+                error!("Cannot deserialize Fees: {:?}", err);
+                return cb(Err(IndyError::CommonError(CommonError::InvalidStructure(format!("Cannot deserialize Fees: {:?}", err)))));
+            }
+        };
+
+        let config = match config.map(serde_json::from_str::<PreparePaymentConfig>) {
+            Some(Ok(config)) => config,
+            None => PreparePaymentConfig::default(),
+            Some(Err(err)) => {
+                error!("Cannot deserialize config: {:?}", err);
+                return cb(Err(IndyError::CommonError(CommonError::InvalidStructure(format!("Cannot deserialize config: {:?}", err)))));
+            }
+        };
+
+        let fee = match self.payments_service.select_fee(&fees, config.txn_type.as_ref().map(String::as_str)) {
+            Ok(fee) => fee,
+            Err(err) => return cb(Err(IndyError::from(err)))
+        };
+
+        let target = match amount.checked_add(fee) {
+            Some(0) => return cb(Err(IndyError::CommonError(CommonError::InvalidStructure("Nothing to pay: amount and fees are zero".to_string())))),
+            Some(target) => target,
+            None => return cb(Err(IndyError::CommonError(CommonError::InvalidStructure("Total of amount and fees is too big".to_string()))))
+        };
+
+        if !self.payments_service.has_method(type_) {
+            return cb(Err(IndyError::from(PaymentsError::UnknownType(format!("Unknown payment method {}", type_)))));
+        }
+
+        match self.payments_service.parse_method_from_payment_address(change_address) {
+            Ok(ref method) if method == type_ => (),
+            Ok(_) => {
+                error!("Change address belongs to another payment method");
+                return cb(Err(IndyError::from(PaymentsError::IncompatiblePaymentError("Change address belongs to another payment method".to_string()))));
+            }
+            Err(err) => return cb(Err(IndyError::from(err)))
+        }
+
+        let addresses = match self._list_addresses(wallet_handle) {
+            Ok(addresses) => addresses.into_iter()
+                .filter(|address| self.payments_service.parse_method_from_payment_address(address).ok().map_or(false, |method| method == type_))
+                .collect(),
+            Err(err) => return cb(Err(err))
+        };
+
+        let preparation_id = SequenceUtils::get_next_id();
+
+        self.pending_preparations.borrow_mut().insert(preparation_id, PaymentPreparation {
+            wallet_handle,
+            pool_handle,
+            submitter_did: submitter_did.to_string(),
+            payment_method: type_.to_string(),
+            target,
+            change_address: change_address.to_string(),
+            strategy: config.strategy,
+            addresses,
+            utxos: Vec::new(),
+            cb,
+        });
+
+        self._prepare_payment_next_address(preparation_id);
+
+        trace!("prepare_payment <<<");
+    }
+
+    fn prepare_payment_get_utxo_ack(&self, preparation_id: i32, result: Result<String, IndyError>) {
+        trace!("prepare_payment_get_utxo_ack >>> preparation_id: {:?}, result: {:?}", preparation_id, result);
+
+        let type_ = match self.pending_preparations.borrow().get(&preparation_id) {
+            Some(preparation) => preparation.payment_method.clone(),
+            None => return error!("Can't process PaymentsCommand::PreparePaymentGetUtxoAck for id {} - appropriate preparation not found!", preparation_id)
+        };
+
+        match result {
+            Ok(response) => self._process_method(
+                Box::new(move |result| {
+                    CommandExecutor::instance()
+                        .send(Command::Payments(PaymentsCommand::PreparePaymentUtxoAck(preparation_id, result))).unwrap();
+                }),
+                &|i| self.payments_service.parse_get_utxo_response(i, &type_, &response)
+            ),
+            Err(err) => self._complete_prepare_payment(preparation_id, Err(err))
+        }
+
+        trace!("prepare_payment_get_utxo_ack <<<");
+    }
+
+    fn prepare_payment_utxo_ack(&self, preparation_id: i32, result: Result<String, IndyError>) {
+        trace!("prepare_payment_utxo_ack >>> preparation_id: {:?}, result: {:?}", preparation_id, result);
+
+        let utxos = result.and_then(|utxo_json| {
+            serde_json::from_str::<Vec<UTXOInfo>>(&utxo_json)
+                .map_err(|err| IndyError::CommonError(CommonError::InvalidStructure(format!("Cannot deserialize UTXO list: {:?}", err))))
+        });
+
+        match utxos {
+            Ok(utxos) => {
+                match self.pending_preparations.borrow_mut().get_mut(&preparation_id) {
+                    Some(preparation) => preparation.utxos.extend(utxos),
+                    None => return error!("Can't process PaymentsCommand::PreparePaymentUtxoAck for id {} - appropriate preparation not found!", preparation_id)
+                }
+                self._prepare_payment_next_address(preparation_id);
+            }
+            Err(err) => self._complete_prepare_payment(preparation_id, Err(err))
+        }
+
+        trace!("prepare_payment_utxo_ack <<<");
+    }
+
     // HELPERS
 
+    fn parse_payment_response_with_history(&self, wallet_handle: i32, payment_method: &str, response: &str, history: &str, cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        trace!("parse_payment_response_with_history >>> wallet_handle: {:?}, payment_method: {:?}, response: {:?}, history: {:?}", wallet_handle, payment_method, response, history);
+        self._parse_with_history(wallet_handle, payment_method, response, history, cb,
+                                 &|i| self.payments_service.parse_payment_response(i, payment_method, response));
+        trace!("parse_payment_response_with_history <<<");
+    }
+
+    fn parse_response_with_fees_with_history(&self, wallet_handle: i32, payment_method: &str, response: &str, history: &str, cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        trace!("parse_response_with_fees_with_history >>> wallet_handle: {:?}, payment_method: {:?}, response: {:?}, history: {:?}", wallet_handle, payment_method, response, history);
+        self._parse_with_history(wallet_handle, payment_method, response, history, cb,
+                                 &|i| self.payments_service.parse_response_with_fees(i, payment_method, response));
+        trace!("parse_response_with_fees_with_history <<<");
+    }
+
+    fn _parse_with_history(&self, wallet_handle: i32, payment_method: &str, response: &str, history: &str, cb: Box<Fn(Result<String, IndyError>) + Send>,
+                           method: &Fn(i32) -> Result<(), PaymentsError>) {
+        match self.wallet_service.check(wallet_handle).map_err(map_err_err!()) {
+            Err(err) => return cb(Err(IndyError::from(err))),
+            _ => (),
+        };
+
+        let context: PaymentHistoryContext = match serde_json::from_str(history) {
+            Ok(context) => context,
+            Err(err) => return cb(Err(IndyError::CommonError(CommonError::InvalidStructure(format!("Cannot deserialize payment history: {:?}", err)))))
+        };
+
+        let recording_id = SequenceUtils::get_next_id();
+        self.pending_history_recordings.borrow_mut().insert(recording_id, PaymentHistoryRecording {
+            wallet_handle,
+            payment_method: payment_method.to_string(),
+            response: response.to_string(),
+            context,
+            cb,
+        });
+
+        // history is recorded only after the payment method has parsed the response successfully
+        self._process_method(
+            Box::new(move |result| {
+                CommandExecutor::instance()
+                    .send(Command::Payments(PaymentsCommand::ParseWithHistoryAck(recording_id, result))).unwrap();
+            }),
+            method
+        );
+    }
+
+    fn parse_with_history_ack(&self, recording_id: i32, result: Result<String, IndyError>) {
+        trace!("parse_with_history_ack >>> recording_id: {:?}, result: {:?}", recording_id, result);
+        let recording = match self.pending_history_recordings.borrow_mut().remove(&recording_id) {
+            Some(recording) => recording,
+            None => return error!("Can't process PaymentsCommand::ParseWithHistoryAck for id {} with result {:?} - appropriate recording not found!",
+                                  recording_id, result)
+        };
+
+        // the payment has already happened on the ledger, so parsed UTXOs are returned even if history isn't recorded.
+        // Entries have deterministic ids, so parsing the same response again records the missing ones
+        if let Ok(ref utxo_json) = result {
+            if let Err(err) = self._record_payment_history(&recording, utxo_json) {
+                error!("Payment history of {} payment wasn't recorded completely: {:?}", recording.payment_method, err);
+            }
+        }
+
+        (recording.cb)(result);
+        trace!("parse_with_history_ack <<<");
+    }
+
+    fn _record_payment_history(&self, recording: &PaymentHistoryRecording, utxo_json: &str) -> Result<(), IndyError> {
+        let owned_addresses = self._list_addresses(recording.wallet_handle)?;
+
+        let entries = self.payments_service.build_history_entries(&recording.payment_method, &recording.context, &owned_addresses,
+                                                                  &recording.response, utxo_json)?;
+
+        for entry in entries {
+            let value = serde_json::to_string(&entry)
+                .map_err(|err| IndyError::CommonError(CommonError::InvalidState(format!("Cannot serialize payment history entry: {:?}", err))))?;
+            let tags = serde_json::to_string(&entry.tags())
+                .map_err(|err| IndyError::CommonError(CommonError::InvalidState(format!("Cannot serialize payment history tags: {:?}", err))))?;
+
+            match self.wallet_service.add_record(recording.wallet_handle, PAYMENT_HISTORY_RECORD_TYPE, &entry.id(), &value, &tags) {
+                Ok(()) | Err(WalletError::ItemAlreadyExists) => (),
+                Err(err) => return Err(IndyError::from(err))
+            }
+        }
+
+        Ok(())
+    }
+
+    fn search_payment_history(&self, wallet_handle: i32, query: &str) -> Result<String, IndyError> {
+        trace!("search_payment_history >>> wallet_handle: {:?}, query: {:?}", wallet_handle, query);
+
+        let mut search = self.wallet_service.search_records(wallet_handle, PAYMENT_HISTORY_RECORD_TYPE, query, &RecordOptions::id_value())?;
+
+        let mut entries: Vec<serde_json::Value> = Vec::new();
+
+        while let Some(record) = search.fetch_next_record()? {
+            let entry = record.get_value()
+                .ok_or(IndyError::CommonError(CommonError::InvalidState(format!("Record value not found"))))
+                .and_then(|value| serde_json::from_str(value)
+                    .map_err(|err| IndyError::CommonError(CommonError::InvalidState(format!("Cannot deserialize payment history entry: {:?}", err)))))?;
+            entries.push(entry);
+        }
+
+        let res = serde_json::to_string(&entries)
+            .map_err(|err| IndyError::CommonError(CommonError::InvalidState(format!("Cannot serialize payment history: {:?}", err))));
+
+        trace!("search_payment_history <<< res: {:?}", res);
+
+        res
+    }
+
+    fn _list_addresses(&self, wallet_handle: i32) -> Result<Vec<String>, IndyError> {
+        let mut search = self.wallet_service.search_records(wallet_handle, "Indy::PaymentAddress", "{}", &RecordOptions::id_value())?;
+
+        let mut list_addresses: Vec<String> = Vec::new();
+
+        while let Some(payment_address) = search.fetch_next_record()? {
+            match payment_address.get_value() {
+                Some(value) => list_addresses.push(value.to_string()),
+                None => return Err(IndyError::CommonError(CommonError::InvalidState(format!("Record value not found"))))
+            }
+        }
+
+        Ok(list_addresses)
+    }
+
+    fn _prepare_payment_next_address(&self, preparation_id: i32) {
+        let next = match self.pending_preparations.borrow_mut().get_mut(&preparation_id) {
+            Some(preparation) => preparation.addresses.pop().map(|address| {
+                (address, preparation.wallet_handle, preparation.pool_handle, preparation.submitter_did.clone(), preparation.payment_method.clone())
+            }),
+            None => return
+        };
+
+        let (address, wallet_handle, pool_handle, submitter_did, type_) = match next {
+            Some(next) => next,
+            None => return self._complete_prepare_payment(preparation_id, Ok(()))
+        };
+
+        // UTXOs of every address are requested from the ledger one by one and collected in the preparation
+        self._process_method(
+            Box::new(move |result| {
+                match result {
+                    Ok(get_utxo_request) => CommandExecutor::instance()
+                        .send(Command::Ledger(LedgerCommand::SubmitRequest(
+                            pool_handle,
+                            get_utxo_request,
+                            Box::new(move |result| {
+                                CommandExecutor::instance()
+                                    .send(Command::Payments(PaymentsCommand::PreparePaymentGetUtxoAck(preparation_id, result))).unwrap();
+                            })
+                        ))).unwrap(),
+                    Err(err) => CommandExecutor::instance()
+                        .send(Command::Payments(PaymentsCommand::PreparePaymentUtxoAck(preparation_id, Err(err)))).unwrap()
+                }
+            }),
+            &|i| self.payments_service.build_get_utxo_request(i, &type_, wallet_handle, &submitter_did, &address)
+        );
+    }
+
+    fn _complete_prepare_payment(&self, preparation_id: i32, result: Result<(), IndyError>) {
+        let preparation = match self.pending_preparations.borrow_mut().remove(&preparation_id) {
+            Some(preparation) => preparation,
+            None => return error!("Can't complete payment preparation for id {} - appropriate preparation not found!", preparation_id)
+        };
+
+        let res = result
+            .and_then(|_| {
+                self.payments_service.select_inputs(&preparation.utxos, preparation.target, preparation.strategy)
+                    .map_err(IndyError::from)
+            })
+            .and_then(|(inputs, change)| {
+                let outputs = if change > 0 {
+                    vec![json!({"paymentAddress": preparation.change_address, "amount": change})]
+                } else { Vec::new() };
+
+                serde_json::to_string(&inputs)
+                    .and_then(|inputs| serde_json::to_string(&outputs).map(|outputs| (inputs, outputs)))
+                    .map_err(|err| IndyError::CommonError(CommonError::InvalidState(format!("Cannot serialize inputs or outputs: {:?}", err))))
+            });
+
+        (preparation.cb)(res)
+    }
+
+
     fn _process_method(&self, cb: Box<Fn(Result<String, IndyError>) + Send>,
                        method: &Fn(i32) -> Result<(), PaymentsError>) {
         let cmd_handle = ::utils::sequence::SequenceUtils::get_next_id();
@@ -563,15 +1284,4 @@ impl PaymentsCommandExecutor {
                            name, cmd_handle, result),
         }
     }
-
-    fn _merge_parse_result(method_from_inputs: Result<String, PaymentsError>, method_from_outputs: Result<String, PaymentsError>) -> Result<String, PaymentsError> {
-        match (method_from_inputs, method_from_outputs) {
-            (Err(err), _) | (_, Err(err)) => Err(err),
-            (Ok(ref mth1), Ok(ref mth2)) if mth1 != mth2 => {
-                error!("Different payment method in inputs and outputs");
-                Err(PaymentsError::IncompatiblePaymentError("Different payment method in inputs and outputs".to_string()))
-            },
-            (Ok(mth1), Ok(_)) => Ok(mth1)
-        }
-    }
 }
@@ -3,6 +3,8 @@ use errors::indy::IndyError;
 use errors::pool::PoolError;
 
 use services::pool::PoolService;
+use commands::{Command, CommandExecutor};
+use commands::ledger::LedgerCommand;
 
 use std::rc::Rc;
 use std::cell::RefCell;
@@ -185,15 +187,18 @@ impl PoolCommandExecutor {
 
         let result = self.pool_service.close(handle)
             .map_err(From::from)
-            .and_then(|handle| {
+            .and_then(|cmd_id| {
                 match self.close_callbacks.try_borrow_mut() {
-                    Ok(cbs) => Ok((cbs, handle)),
+                    Ok(cbs) => Ok((cbs, cmd_id)),
                     Err(err) => Err(IndyError::PoolError(PoolError::from(CommonError::from(err))))
                 }
             });
         match result {
             Err(err) => { cb(Err(err)); }
-            Ok((mut cbs, handle)) => { cbs.insert(handle, cb); /* TODO check if map contains same key */ }
+            Ok((mut cbs, cmd_id)) => {
+                cbs.insert(cmd_id, cb); /* TODO check if map contains same key */
+                CommandExecutor::instance().send(Command::Ledger(LedgerCommand::EvictTxnFees(Some(handle), None))).unwrap();
+            }
         };
 
         debug!("close <<<");
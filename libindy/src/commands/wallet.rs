@@ -5,10 +5,14 @@ extern crate indy_crypto;
 use errors::indy::IndyError;
 use errors::common::CommonError;
 use errors::wallet::WalletError;
-use services::wallet::WalletService;
+use services::wallet::{WalletService, VerifyOptions};
+use commands::{Command, CommandExecutor};
+use commands::pairwise::PairwiseCommand;
 use api::wallet::*;
 use std::rc::Rc;
 
+use self::indy_crypto::utils::json::{JsonEncodable, JsonDecodable};
+
 pub enum WalletCommand {
     RegisterWalletType(String, // type_
                        WalletCreate, // create
@@ -36,6 +40,14 @@ pub enum WalletCommand {
                        WalletFetchSearchNextRecord, // fetch search next record
                        WalletFreeSearch, // free search
                        Box<Fn(Result<(), IndyError>) + Send>),
+    RegisterWalletTypeExpiry(String, // type_
+                             WalletSetRecordExpiry, // set record expiry
+                             WalletPurgeExpiredRecords, // purge expired records
+                             Box<Fn(Result<(), IndyError>) + Send>),
+    RegisterWalletTypeVersioning(String, // type_
+                                 WalletGetRecordVersion, // get record version
+                                 WalletUpdateRecordIfVersion, // update record if version
+                                 Box<Fn(Result<(), IndyError>) + Send>),
     Create(String, // pool name
            String, // wallet name
            Option<String>, // storage type
@@ -51,7 +63,15 @@ pub enum WalletCommand {
     ListWallets(Box<Fn(Result<String, IndyError>) + Send>),
     Delete(String, // name
            String, // wallet credentials
-           Box<Fn(Result<(), IndyError>) + Send>)
+           Box<Fn(Result<(), IndyError>) + Send>),
+    GetChangesSince(i32, // handle
+                    i64, // seq
+                    Box<Fn(Result<String, IndyError>) + Send>),
+    PurgeExpired(i32, // handle
+                 Box<Fn(Result<i32, IndyError>) + Send>),
+    Verify(i32, // handle
+           Option<String>, // options json
+           Box<Fn(Result<String, IndyError>) + Send>)
 }
 
 pub struct WalletCommandExecutor {
@@ -81,6 +101,14 @@ impl WalletCommandExecutor {
                                       free_storage_metadata, search_records, search_all_records, get_search_total_count,
                                       fetch_search_next_record, free_search));
             }
+            WalletCommand::RegisterWalletTypeExpiry(type_, set_record_expiry, purge_expired_records, cb) => {
+                info!(target: "wallet_command_executor", "RegisterWalletTypeExpiry command received");
+                cb(self.register_type_expiry(&type_, set_record_expiry, purge_expired_records));
+            }
+            WalletCommand::RegisterWalletTypeVersioning(type_, get_record_version, update_record_if_version, cb) => {
+                info!(target: "wallet_command_executor", "RegisterWalletTypeVersioning command received");
+                cb(self.register_type_versioning(&type_, get_record_version, update_record_if_version));
+            }
             WalletCommand::Create(pool_name, name, storage_type, config, credentials, cb) => {
                 info!(target: "wallet_command_executor", "Create command received");
                 cb(self.create(&pool_name, &name, storage_type.as_ref().map(String::as_str),
@@ -102,6 +130,18 @@ impl WalletCommandExecutor {
                 info!(target: "wallet_command_executor", "Delete command received");
                 cb(self.delete(&name, &credentials));
             }
+            WalletCommand::GetChangesSince(handle, seq, cb) => {
+                info!(target: "wallet_command_executor", "GetChangesSince command received");
+                cb(self.get_changes_since(handle, seq));
+            }
+            WalletCommand::PurgeExpired(handle, cb) => {
+                info!(target: "wallet_command_executor", "PurgeExpired command received");
+                cb(self.purge_expired(handle));
+            }
+            WalletCommand::Verify(handle, options_json, cb) => {
+                info!(target: "wallet_command_executor", "Verify command received");
+                cb(self.verify(handle, options_json.as_ref().map(String::as_str)));
+            }
         };
     }
 
@@ -147,6 +187,32 @@ impl WalletCommandExecutor {
         Ok(res)
     }
 
+    fn register_type_expiry(&self,
+                            type_: &str,
+                            set_record_expiry: WalletSetRecordExpiry,
+                            purge_expired_records: WalletPurgeExpiredRecords) -> Result<(), IndyError> {
+        debug!("register_type_expiry >>> type_: {:?}", type_);
+
+        let res = self.wallet_service.register_wallet_storage_expiry(type_, set_record_expiry, purge_expired_records)?;
+
+        debug!("register_type_expiry <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn register_type_versioning(&self,
+                                type_: &str,
+                                get_record_version: WalletGetRecordVersion,
+                                update_record_if_version: WalletUpdateRecordIfVersion) -> Result<(), IndyError> {
+        debug!("register_type_versioning >>> type_: {:?}", type_);
+
+        let res = self.wallet_service.register_wallet_storage_versioning(type_, get_record_version, update_record_if_version)?;
+
+        debug!("register_type_versioning <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn create(&self,
               pool_name: &str,
               name: &str,
@@ -171,6 +237,9 @@ impl WalletCommandExecutor {
 
         let res = self.wallet_service.open_wallet(name, runtime_config, credentials)?;
 
+        // queued before caller gets the handle, so any following pairwise command sees the tags
+        CommandExecutor::instance().send(Command::Pairwise(PairwiseCommand::AddMissingPairwiseTags(res))).unwrap();
+
         debug!("open <<< res: {:?}", res);
 
         Ok(res)
@@ -212,4 +281,52 @@ impl WalletCommandExecutor {
 
         Ok(res)
     }
-}
\ No newline at end of file
+
+    fn get_changes_since(&self,
+                         handle: i32,
+                         seq: i64) -> Result<String, IndyError> {
+        debug!("get_changes_since >>> handle: {:?}, seq: {:?}", handle, seq);
+
+        let res = self.wallet_service.get_changes_since(handle, seq)
+            .and_then(|changes|
+                serde_json::to_string(&changes)
+                    .map_err(|err|
+                        WalletError::CommonError(CommonError::InvalidState(format!("Can't serialize wallet changes {}", err)))))?;
+
+        debug!("get_changes_since <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn purge_expired(&self,
+                     handle: i32) -> Result<i32, IndyError> {
+        debug!("purge_expired >>> handle: {:?}", handle);
+
+        let res = self.wallet_service.purge_expired(handle)? as i32;
+
+        debug!("purge_expired <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn verify(&self,
+              handle: i32,
+              options_json: Option<&str>) -> Result<String, IndyError> {
+        debug!("verify >>> handle: {:?}, options_json: {:?}", handle, options_json);
+
+        let options = match options_json {
+            Some(options_json) => VerifyOptions::from_json(options_json)
+                .map_err(|err| CommonError::InvalidStructure(format!("Invalid verify options json: {}", err)))?,
+            None => VerifyOptions::default()
+        };
+
+        let report = self.wallet_service.verify_wallet(handle, options.quarantine.unwrap_or(false))?;
+
+        let res = report.to_json()
+            .map_err(|err| CommonError::InvalidState(format!("Can't serialize wallet verification report {}", err)))?;
+
+        debug!("verify <<< res: {:?}", res);
+
+        Ok(res)
+    }
+}
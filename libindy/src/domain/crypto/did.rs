@@ -2,6 +2,7 @@ extern crate indy_crypto;
 
 use self::indy_crypto::utils::json::{JsonDecodable, JsonEncodable};
 use named_type::NamedType;
+use super::key::{ExternalKeyInfo, KeyDerivationInfo};
 
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -9,7 +10,10 @@ pub struct MyDidInfo {
     pub did: Option<String>,
     pub seed: Option<String>,
     pub crypto_type: Option<String>,
-    pub cid: Option<bool>
+    pub cid: Option<bool>,
+    pub method_name: Option<String>,
+    pub external_key: Option<ExternalKeyInfo>,
+    pub derivation: Option<KeyDerivationInfo>
 }
 
 impl<'a> JsonDecodable<'a> for MyDidInfo {}
@@ -64,7 +68,11 @@ impl JsonEncodable for DidWithMeta {}
 #[derive(Serialize, Deserialize, Debug, NamedType)]
 pub struct TheirDid {
     pub did: String,
-    pub verkey: String
+    pub verkey: String,
+    // Time (seconds since epoch) when the DID was resolved from the ledger.
+    // Not set for DIDs stored with indy_store_their_did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<i64>
 }
 
 impl JsonEncodable for TheirDid {}
@@ -90,3 +98,30 @@ impl From<TemporaryDid> for Did {
     }
 }
 
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheOptions {
+    pub no_cache: Option<bool>,
+    pub no_store: Option<bool>,
+    pub max_age: Option<i64>
+}
+
+impl CacheOptions {
+    pub fn is_fresh(&self, resolved_at: Option<i64>, now: i64) -> bool {
+        if self.no_cache.unwrap_or(false) {
+            return false;
+        }
+
+        match (self.max_age, resolved_at) {
+            (Some(max_age), Some(resolved_at)) => now - resolved_at <= max_age,
+            _ => true
+        }
+    }
+
+    pub fn store(&self) -> bool {
+        !self.no_store.unwrap_or(false)
+    }
+}
+
+impl<'a> JsonDecodable<'a> for CacheOptions {}
@@ -0,0 +1,23 @@
+extern crate indy_crypto;
+
+use self::indy_crypto::utils::json::{JsonDecodable, JsonEncodable};
+
+pub const JWS_ALG_EDDSA: &'static str = "EdDSA";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JwsHeader {
+    pub alg: String,
+    pub kid: String
+}
+
+impl JsonEncodable for JwsHeader {}
+
+impl<'a> JsonDecodable<'a> for JwsHeader {}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct JwsOptions {
+    pub kid: Option<String>,
+    pub detached: Option<bool>
+}
+
+impl<'a> JsonDecodable<'a> for JwsOptions {}
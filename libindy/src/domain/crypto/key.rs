@@ -6,18 +6,37 @@ use named_type::NamedType;
 #[derive(Serialize, Deserialize, Clone, Debug, NamedType)]
 pub struct Key {
     pub verkey: String,
-    pub signkey: String
+    pub signkey: String,
+    // Set for keys that are held by external signer, signkey is empty in this case
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external: Option<ExternalKeyRef>
 }
 
 impl Key {
     pub fn new(verkey: String, signkey: String) -> Key {
         Key {
             verkey,
-            signkey
+            signkey,
+            external: None
+        }
+    }
+
+    pub fn new_external(verkey: String, signer: String, key_ref: String) -> Key {
+        Key {
+            verkey,
+            signkey: String::new(),
+            external: Some(ExternalKeyRef { signer, key_ref })
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ExternalKeyRef {
+    pub signer: String,
+    pub key_ref: String
+}
+
 impl JsonEncodable for Key {}
 
 impl<'a> JsonDecodable<'a> for Key {}
@@ -30,4 +49,41 @@ pub struct KeyInfo {
 
 impl JsonEncodable for KeyInfo {}
 
-impl<'a> JsonDecodable<'a> for KeyInfo {}
\ No newline at end of file
+impl<'a> JsonDecodable<'a> for KeyInfo {}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ExternalKeyInfo {
+    pub signer: String,
+    pub key_ref: String,
+    pub verkey: String
+}
+
+impl<'a> JsonDecodable<'a> for ExternalKeyInfo {}
+
+#[derive(Serialize, Deserialize, Debug, NamedType)]
+pub struct MasterSeed {
+    pub seed: String // base58 encoded
+}
+
+impl JsonEncodable for MasterSeed {}
+
+impl<'a> JsonDecodable<'a> for MasterSeed {}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct MasterSeedConfig {
+    pub seed: Option<String>,
+    pub mnemonic: Option<String>,
+    pub passphrase: Option<String>
+}
+
+impl<'a> JsonDecodable<'a> for MasterSeedConfig {}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct KeyDerivationInfo {
+    pub master_seed_id: String,
+    pub path: String
+}
+
+impl JsonEncodable for KeyDerivationInfo {}
+
+impl<'a> JsonDecodable<'a> for KeyDerivationInfo {}
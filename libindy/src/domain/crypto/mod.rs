@@ -1,3 +1,5 @@
 pub mod key;
 pub mod did;
 pub mod combo_box;
+pub mod pack;
+pub mod jws;
@@ -0,0 +1,52 @@
+extern crate indy_crypto;
+
+use self::indy_crypto::utils::json::{JsonDecodable, JsonEncodable};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JWE {
+    pub protected: String,
+    pub iv: String,
+    pub ciphertext: String,
+    pub tag: String
+}
+
+impl JsonEncodable for JWE {}
+
+impl<'a> JsonDecodable<'a> for JWE {}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Recipient {
+    pub encrypted_key: String,
+    pub header: Header
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Header {
+    pub kid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iv: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender: Option<String>
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Protected {
+    pub enc: String,
+    pub typ: String,
+    pub alg: String,
+    pub recipients: Vec<Recipient>
+}
+
+impl JsonEncodable for Protected {}
+
+impl<'a> JsonDecodable<'a> for Protected {}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UnpackMessage {
+    pub message: String,
+    pub recipient_verkey: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender_verkey: Option<String>
+}
+
+impl JsonEncodable for UnpackMessage {}
@@ -104,14 +104,19 @@ impl<'a> JsonDecodable<'a> for AttribData {}
 #[derive(Serialize, Deserialize, Clone, Debug, NamedType)]
 pub struct Endpoint {
     pub ha: String,
-    pub verkey: Option<String>
+    pub verkey: Option<String>,
+    // Time (seconds since epoch) when the endpoint was resolved from the ledger.
+    // Not set for endpoints stored with indy_set_endpoint_for_did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<i64>
 }
 
 impl Endpoint {
     pub fn new(ha: String, verkey: Option<String>) -> Endpoint {
         Endpoint {
             ha,
-            verkey
+            verkey,
+            resolved_at: None
         }
     }
 }
@@ -3,9 +3,13 @@ extern crate serde_json;
 extern crate indy_crypto;
 
 use super::constants::GET_DDO;
+use utils::qualifier;
 
 use self::indy_crypto::utils::json::JsonEncodable;
 
+pub const DID_CONTEXT: &'static str = "https://w3id.org/did/v1";
+pub const DID_SOV_METHOD: &'static str = "sov";
+
 #[derive(Serialize, PartialEq, Debug)]
 pub struct GetDdoOperation {
     #[serde(rename = "type")]
@@ -22,4 +26,78 @@ impl GetDdoOperation {
     }
 }
 
-impl JsonEncodable for GetDdoOperation {}
\ No newline at end of file
+impl JsonEncodable for GetDdoOperation {}
+
+#[derive(Serialize, Debug)]
+pub struct DidDocument {
+    #[serde(rename = "@context")]
+    pub context: String,
+    pub id: String,
+    #[serde(rename = "publicKey")]
+    pub public_key: Vec<PublicKey>,
+    pub authentication: Vec<Authentication>,
+    pub service: Vec<Service>
+}
+
+impl DidDocument {
+    pub fn new(did: &str, verkey: &str, endpoint: Option<(String, Option<String>)>) -> DidDocument {
+        let id = qualifier::qualify(did, DID_SOV_METHOD);
+        let key_id = format!("{}#key-1", id);
+
+        let service = endpoint
+            .map(|(ha, endpoint_verkey)|
+                vec![Service {
+                    id: format!("{};indy", id),
+                    type_: "IndyAgent".to_string(),
+                    service_endpoint: ha,
+                    recipient_keys: endpoint_verkey.into_iter().collect()
+                }])
+            .unwrap_or(Vec::new());
+
+        DidDocument {
+            context: DID_CONTEXT.to_string(),
+            public_key: vec![PublicKey {
+                id: key_id.clone(),
+                type_: "Ed25519VerificationKey2018".to_string(),
+                controller: id.clone(),
+                public_key_base58: verkey.to_string()
+            }],
+            authentication: vec![Authentication {
+                type_: "Ed25519SignatureAuthentication2018".to_string(),
+                public_key: key_id
+            }],
+            service,
+            id
+        }
+    }
+}
+
+impl JsonEncodable for DidDocument {}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicKey {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub controller: String,
+    pub public_key_base58: String
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Authentication {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub public_key: String
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Service {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub service_endpoint: String,
+    pub recipient_keys: Vec<String>
+}
@@ -4,6 +4,7 @@ extern crate indy_crypto;
 extern crate time;
 
 use self::indy_crypto::utils::json::JsonEncodable;
+use utils::qualifier;
 
 
 #[derive(Serialize, PartialEq, Debug)]
@@ -21,7 +22,8 @@ impl<T: serde::Serialize> Request<T> {
     fn new(req_id: u64, identifier: &str, operation: T, protocol_version: u64) -> Request<T> {
         Request {
             req_id,
-            identifier: identifier.to_string(),
+            // Ledger wire format uses unqualified DIDs
+            identifier: qualifier::unqualify(identifier).to_string(),
             operation,
             protocol_version,
             signature: None
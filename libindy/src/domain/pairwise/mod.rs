@@ -10,6 +10,26 @@ pub struct Pairwise {
     pub their_did: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub my_dids: Vec<String>, // all my DIDs used in this pairwise including my_did, empty if only my_did was used
+}
+
+impl Pairwise {
+    pub fn my_dids(&self) -> Vec<String> {
+        if self.my_dids.is_empty() {
+            vec![self.my_did.clone()]
+        } else {
+            self.my_dids.clone()
+        }
+    }
+
+    pub fn tags(&self) -> PairwiseTags {
+        PairwiseTags {
+            my_did: self.my_did.clone(),
+            their_did: self.their_did.clone(),
+            metadata: self.metadata.clone()
+        }
+    }
 }
 
 impl JsonEncodable for Pairwise {}
@@ -19,6 +39,8 @@ impl<'a> JsonDecodable<'a> for Pairwise {}
 #[derive(Serialize, Deserialize)]
 pub struct PairwiseInfo {
     pub my_did: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub my_dids: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<String>,
 }
@@ -27,6 +49,7 @@ impl From<Pairwise> for PairwiseInfo {
     fn from(pairwise: Pairwise) -> Self {
         PairwiseInfo {
             my_did: pairwise.my_did,
+            my_dids: pairwise.my_dids,
             metadata: pairwise.metadata
         }
     }
@@ -34,4 +57,23 @@ impl From<Pairwise> for PairwiseInfo {
 
 impl JsonEncodable for PairwiseInfo {}
 
-impl<'a> JsonDecodable<'a> for PairwiseInfo {}
\ No newline at end of file
+impl<'a> JsonDecodable<'a> for PairwiseInfo {}
+
+#[derive(Serialize, Deserialize)]
+pub struct PairwiseTags {
+    pub my_did: String,
+    pub their_did: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<String>,
+}
+
+impl JsonEncodable for PairwiseTags {}
+
+#[derive(Serialize, Deserialize)]
+pub struct PairwiseSearchResult {
+    #[serde(rename = "totalCount")]
+    pub total_count: Option<usize>,
+    pub records: Option<Vec<Pairwise>>,
+}
+
+impl JsonEncodable for PairwiseSearchResult {}
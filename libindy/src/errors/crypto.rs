@@ -12,6 +12,8 @@ use errors::ToErrorCode;
 #[derive(Debug)]
 pub enum CryptoError {
     UnknownCryptoError(String),
+    CryptoTypeAlreadyRegistered(String),
+    ExternalSignerAlreadyRegistered(String),
     CommonError(CommonError)
 }
 
@@ -19,6 +21,8 @@ impl fmt::Display for CryptoError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             CryptoError::UnknownCryptoError(ref description) => write!(f, "Unknown crypto: {}", description),
+            CryptoError::CryptoTypeAlreadyRegistered(ref description) => write!(f, "Crypto type already registered: {}", description),
+            CryptoError::ExternalSignerAlreadyRegistered(ref description) => write!(f, "External signer already registered: {}", description),
             CryptoError::CommonError(ref err) => err.fmt(f)
         }
     }
@@ -28,6 +32,8 @@ impl error::Error for CryptoError {
     fn description(&self) -> &str {
         match *self {
             CryptoError::UnknownCryptoError(ref description) => description,
+            CryptoError::CryptoTypeAlreadyRegistered(ref description) => description,
+            CryptoError::ExternalSignerAlreadyRegistered(ref description) => description,
             CryptoError::CommonError(ref err) => err.description()
         }
     }
@@ -35,6 +41,8 @@ impl error::Error for CryptoError {
     fn cause(&self) -> Option<&error::Error> {
         match *self {
             CryptoError::UnknownCryptoError(_) => None,
+            CryptoError::CryptoTypeAlreadyRegistered(_) => None,
+            CryptoError::ExternalSignerAlreadyRegistered(_) => None,
             CryptoError::CommonError(ref err) => Some(err)
         }
     }
@@ -44,6 +52,8 @@ impl ToErrorCode for CryptoError {
     fn to_error_code(&self) -> ErrorCode {
         match *self {
             CryptoError::UnknownCryptoError(_) => ErrorCode::UnknownCryptoTypeError,
+            CryptoError::CryptoTypeAlreadyRegistered(_) => ErrorCode::CryptoTypeAlreadyRegisteredError,
+            CryptoError::ExternalSignerAlreadyRegistered(_) => ErrorCode::ExternalSignerAlreadyRegisteredError,
             CryptoError::CommonError(ref err) => err.to_error_code()
         }
     }
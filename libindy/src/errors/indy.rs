@@ -82,6 +82,13 @@ impl ToErrorCode for IndyError {
             IndyError::PaymentsError(ref err) => err.to_error_code(),
         }
     }
+
+    fn error_details(&self) -> Option<String> {
+        match *self {
+            IndyError::PaymentsError(ref err) => err.error_details(),
+            _ => None
+        }
+    }
 }
 
 impl From<AnoncredsError> for IndyError {
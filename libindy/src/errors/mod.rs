@@ -10,8 +10,36 @@ pub mod payments;
 
 use api::ErrorCode;
 
+use std::cell::RefCell;
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::ptr;
+
+thread_local! {
+    static CURRENT_ERROR_DETAILS: RefCell<Option<CString>> = RefCell::new(None);
+}
+
 pub trait ToErrorCode {
     fn to_error_code(&self) -> ErrorCode;
+
+    /// Json with structured details of the error for errors that have ones.
+    fn error_details(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Remembers details of the error returned to the caller in the current thread.
+/// Callbacks are called in the thread of command executor, so details are available inside of callback only.
+pub fn set_current_error_details(details: Option<String>) {
+    CURRENT_ERROR_DETAILS.with(|current| {
+        *current.borrow_mut() = details.and_then(|details| CString::new(details).ok());
+    })
+}
+
+pub fn get_current_error_details() -> *const c_char {
+    CURRENT_ERROR_DETAILS.with(|current| {
+        current.borrow().as_ref().map(|details| details.as_ptr()).unwrap_or(ptr::null())
+    })
 }
 
 impl<T> ToErrorCode for Result<(), T> where T: ToErrorCode {
@@ -1,17 +1,31 @@
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
+use serde_json;
+
 use api::ErrorCode;
 use errors::ToErrorCode;
 use errors::common::CommonError;
 
+/// Entries of a request grouped by the payment method they belong to.
+/// Keeps only the groups that prevented the request from being processed.
+#[derive(Debug, Default, Serialize)]
+pub struct MethodConflict {
+    pub inputs: BTreeMap<String, Vec<String>>,
+    pub outputs: BTreeMap<String, Vec<String>>,
+}
+
 #[derive(Debug)]
 pub enum PaymentsError {
     PluggedMethodError(ErrorCode),
     UnknownType(String),
     CommonError(CommonError),
     IncompatiblePaymentError(String),
+    OperationNotSupported(String),
+    InsufficientFundsError(String),
+    MixedMethodsError(MethodConflict),
 }
 
 impl Error for PaymentsError {
@@ -21,6 +35,9 @@ impl Error for PaymentsError {
             PaymentsError::UnknownType(ref msg) => msg.as_str(),
             PaymentsError::PluggedMethodError(_error_code) => "Plugged method error. Consider the error code.",
             PaymentsError::IncompatiblePaymentError(ref msg) => msg.as_str(),
+            PaymentsError::OperationNotSupported(ref msg) => msg.as_str(),
+            PaymentsError::InsufficientFundsError(ref msg) => msg.as_str(),
+            PaymentsError::MixedMethodsError(_) => "Request mixes entries of several payment methods",
         }
     }
 }
@@ -32,6 +49,10 @@ impl Display for PaymentsError {
             PaymentsError::PluggedMethodError(_err_code) => write!(_f, "Plugged method error. Consider the error code."),
             PaymentsError::UnknownType(ref msg) => write!(_f, "Unknown Type Error: {}", msg),
             PaymentsError::IncompatiblePaymentError(ref msg) => write!(_f, "Incompatible Payment Method Error: {}", msg),
+            PaymentsError::OperationNotSupported(ref msg) => write!(_f, "Operation Not Supported Error: {}", msg),
+            PaymentsError::InsufficientFundsError(ref msg) => write!(_f, "Insufficient Funds Error: {}", msg),
+            PaymentsError::MixedMethodsError(ref conflict) =>
+                write!(_f, "Mixed Payment Methods Error: {}", serde_json::to_string(conflict).unwrap_or_default()),
         }
     }
 }
@@ -43,6 +64,16 @@ impl ToErrorCode for PaymentsError {
             PaymentsError::CommonError(ref err) => err.to_error_code(),
             PaymentsError::UnknownType(ref _str) => ErrorCode::PaymentUnknownMethodError,
             PaymentsError::IncompatiblePaymentError(ref _str) => ErrorCode::PaymentIncompatibleMethodsError,
+            PaymentsError::OperationNotSupported(ref _str) => ErrorCode::PaymentOperationNotSupportedError,
+            PaymentsError::InsufficientFundsError(ref _str) => ErrorCode::PaymentInsufficientFundsError,
+            PaymentsError::MixedMethodsError(_) => ErrorCode::PaymentMixedMethodsError,
+        }
+    }
+
+    fn error_details(&self) -> Option<String> {
+        match *self {
+            PaymentsError::MixedMethodsError(ref conflict) => serde_json::to_string(conflict).ok(),
+            _ => None
         }
     }
 }
\ No newline at end of file
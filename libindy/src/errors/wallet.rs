@@ -37,6 +37,7 @@ pub enum WalletError {
     ItemNotFound,
     ItemAlreadyExists,
     QueryError(String),
+    Conflict,
 }
 
 
@@ -59,7 +60,8 @@ impl fmt::Display for WalletError {
             WalletError::EncryptionError(ref description) => write!(f, "Wallet encryption error occurred. Description: {}", description),
             WalletError::ItemNotFound => write!(f, "Item not found"),
             WalletError::ItemAlreadyExists => write!(f, "Item already exists"),
-            WalletError::QueryError(ref description) => write!(f, "{}", description)
+            WalletError::QueryError(ref description) => write!(f, "{}", description),
+            WalletError::Conflict => write!(f, "Item was changed since the expected version")
         }
     }
 }
@@ -84,6 +86,7 @@ impl error::Error for WalletError {
             WalletError::ItemNotFound => "Item not found",
             WalletError::ItemAlreadyExists => "Item already exists",
             WalletError::QueryError(ref description) => description,
+            WalletError::Conflict => "Item was changed since the expected version",
         }
     }
 
@@ -106,6 +109,7 @@ impl error::Error for WalletError {
             WalletError::ItemNotFound => None,
             WalletError::ItemAlreadyExists => None,
             WalletError::QueryError(_) => None,
+            WalletError::Conflict => None,
         }
     }
 }
@@ -130,6 +134,7 @@ impl ToErrorCode for WalletError {
             WalletError::ItemNotFound => ErrorCode::WalletItemNotFound,
             WalletError::ItemAlreadyExists => ErrorCode::WalletItemAlreadyExists,
             WalletError::QueryError(_) => ErrorCode::WalletQueryError,
+            WalletError::Conflict => ErrorCode::WalletConflict,
         }
     }
 }
@@ -161,6 +166,7 @@ impl From<WalletStorageError> for WalletError {
             WalletStorageError::NotFound => WalletError::NotFound(String::from("Storage not found")),
             WalletStorageError::ItemNotFound => WalletError::ItemNotFound,
             WalletStorageError::ItemAlreadyExists => WalletError::ItemAlreadyExists,
+            WalletStorageError::Conflict => WalletError::Conflict,
             WalletStorageError::PluggedStorageError(code) => WalletError::PluggedWalletError(code),
             _ => WalletError::StorageError(err.description().to_string())
         }
@@ -205,6 +211,8 @@ pub enum WalletStorageError {
     ConfigError,
     ItemNotFound,
     ItemAlreadyExists,
+    Busy,
+    Conflict,
     IOError(String),
     PluggedStorageError(ErrorCode),
     CommonError(CommonError),
@@ -216,6 +224,7 @@ impl From<rusqlite::Error> for WalletStorageError {
     fn from(err: rusqlite::Error) -> WalletStorageError {
         match &err {
             &rusqlite::Error::SqliteFailure(libsqlite3_sys::Error{code: libsqlite3_sys::ErrorCode::ConstraintViolation, extended_code: _}, _) => WalletStorageError::ItemAlreadyExists,
+            &rusqlite::Error::SqliteFailure(libsqlite3_sys::Error{code: libsqlite3_sys::ErrorCode::DatabaseBusy, extended_code: _}, _) => WalletStorageError::Busy,
             _ => WalletStorageError::IOError(format!("IO error during storage operation: {}", err.description()))
         }
     }
@@ -263,6 +272,8 @@ impl error::Error for WalletStorageError {
             WalletStorageError::ConfigError => "Storage configuration is invalid",
             WalletStorageError::ItemNotFound => "Item not found",
             WalletStorageError::ItemAlreadyExists => "Item already exists",
+            WalletStorageError::Busy => "Storage is busy",
+            WalletStorageError::Conflict => "Item was changed since the expected version",
             WalletStorageError::PluggedStorageError(_err_code) => "Plugged storage error",
             WalletStorageError::IOError(ref s) => s,
             WalletStorageError::CommonError(ref e) => e.description(),
@@ -280,6 +291,8 @@ impl fmt::Display for WalletStorageError {
             WalletStorageError::ConfigError => write!(f, "Storage configuration is invalid"),
             WalletStorageError::ItemNotFound => write!(f, "Item not found"),
             WalletStorageError::ItemAlreadyExists => write!(f, "Item already exists"),
+            WalletStorageError::Busy => write!(f, "Storage is busy: lock was not acquired before timeout"),
+            WalletStorageError::Conflict => write!(f, "Item was changed since the expected version"),
             WalletStorageError::IOError(ref s) => write!(f, "IO error occurred during storage operation: {}", s),
             WalletStorageError::PluggedStorageError(err_code) => write!(f, "Plugged storage error: {}", err_code as i32),
             WalletStorageError::CommonError(ref e) => write!(f, "Common error: {}", e.description()),
@@ -0,0 +1,97 @@
+use super::plugged::BufferGuard;
+use api::ErrorCode;
+use api::crypto::{ExternalSignerSign, ExternalSignerPrecompute, ExternalSignerDecryptSealed, CryptoFree};
+use errors::common::CommonError;
+
+use std::ffi::CString;
+use std::ptr;
+
+pub struct ExternalSigner {
+    name: String,
+    sign_handler: ExternalSignerSign,
+    precompute_handler: ExternalSignerPrecompute,
+    decrypt_sealed_handler: ExternalSignerDecryptSealed,
+    free_handler: CryptoFree
+}
+
+impl ExternalSigner {
+    pub fn new(name: &str,
+               sign_handler: ExternalSignerSign,
+               precompute_handler: ExternalSignerPrecompute,
+               decrypt_sealed_handler: ExternalSignerDecryptSealed,
+               free_handler: CryptoFree) -> ExternalSigner {
+        ExternalSigner {
+            name: name.to_string(),
+            sign_handler,
+            precompute_handler,
+            decrypt_sealed_handler,
+            free_handler
+        }
+    }
+
+    pub fn sign(&self, key_ref: &str, doc: &[u8]) -> Result<Vec<u8>, CommonError> {
+        let key_ref = ExternalSigner::_key_ref_to_cstring(key_ref)?;
+
+        let mut signature_ptr: *const u8 = ptr::null();
+        let mut signature_len: u32 = 0;
+
+        let err = (self.sign_handler)(key_ref.as_ptr(),
+                                      doc.as_ptr(), doc.len() as u32,
+                                      &mut signature_ptr, &mut signature_len);
+
+        let signature = BufferGuard::new(signature_ptr, signature_len, self.free_handler);
+
+        self._check_err("sign", err)?;
+
+        Ok(signature.to_vec())
+    }
+
+    pub fn precompute(&self, key_ref: &str, their_pk: &[u8]) -> Result<Vec<u8>, CommonError> {
+        let key_ref = ExternalSigner::_key_ref_to_cstring(key_ref)?;
+
+        let mut precomputed_key_ptr: *const u8 = ptr::null();
+        let mut precomputed_key_len: u32 = 0;
+
+        let err = (self.precompute_handler)(key_ref.as_ptr(),
+                                            their_pk.as_ptr(), their_pk.len() as u32,
+                                            &mut precomputed_key_ptr, &mut precomputed_key_len);
+
+        let precomputed_key = BufferGuard::new(precomputed_key_ptr, precomputed_key_len, self.free_handler);
+
+        self._check_err("precompute", err)?;
+
+        Ok(precomputed_key.to_vec())
+    }
+
+    pub fn decrypt_sealed(&self, key_ref: &str, doc: &[u8]) -> Result<Vec<u8>, CommonError> {
+        let key_ref = ExternalSigner::_key_ref_to_cstring(key_ref)?;
+
+        let mut decrypted_ptr: *const u8 = ptr::null();
+        let mut decrypted_len: u32 = 0;
+
+        let err = (self.decrypt_sealed_handler)(key_ref.as_ptr(),
+                                                doc.as_ptr(), doc.len() as u32,
+                                                &mut decrypted_ptr, &mut decrypted_len);
+
+        let decrypted = BufferGuard::new(decrypted_ptr, decrypted_len, self.free_handler);
+
+        self._check_err("decrypt_sealed", err)?;
+
+        Ok(decrypted.to_vec())
+    }
+
+    fn _key_ref_to_cstring(key_ref: &str) -> Result<CString, CommonError> {
+        CString::new(key_ref)
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid external key reference: {:?}", err)))
+    }
+
+    fn _check_err(&self, operation: &str, err: ErrorCode) -> Result<(), CommonError> {
+        match err {
+            ErrorCode::Success => Ok(()),
+            ErrorCode::CommonInvalidStructure =>
+                Err(CommonError::InvalidStructure(format!("External signer {} rejected {} input", self.name, operation))),
+            err =>
+                Err(CommonError::InvalidState(format!("External signer {} failed on {}: {:?}", self.name, operation, err)))
+        }
+    }
+}
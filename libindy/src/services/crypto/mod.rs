@@ -1,23 +1,46 @@
 mod ed25519;
+#[cfg(feature = "ecdsa_secp256k1_openssl")]
+mod secp256k1;
+mod plugged;
+mod external;
 
 use base64;
 
 use self::ed25519::ED25519CryptoType;
+#[cfg(feature = "ecdsa_secp256k1_openssl")]
+use self::secp256k1::Secp256k1CryptoType;
+use self::plugged::PluggedCryptoType;
+use self::external::ExternalSigner;
 
 
 use utils::crypto::base58::Base58;
+use utils::crypto::box_::CryptoBox;
+use utils::crypto::hmacsha512::HMACSHA512;
+use utils::crypto::bip39;
+use utils::crypto::slip10;
 use utils::crypto::verkey_builder::build_full_verkey;
-use domain::crypto::key::{Key, KeyInfo};
+use utils::qualifier;
+use domain::crypto::key::{Key, KeyInfo, ExternalKeyInfo, ExternalKeyRef, MasterSeed, MasterSeedConfig};
 use domain::crypto::did::{Did, MyDidInfo, TheirDidInfo, TheirDid};
 use domain::crypto::combo_box::ComboBox;
 
+use api::crypto::{CryptoCreateKey, CryptoSign, CryptoVerify, CryptoEncryptSealed, CryptoDecryptSealed, CryptoFree,
+                  ExternalSignerSign, ExternalSignerPrecompute, ExternalSignerDecryptSealed};
 use errors::common::CommonError;
 use errors::crypto::CryptoError;
 
+use std::cell::{Ref, RefCell};
 use std::collections::HashMap;
 use std::str;
 
 pub const DEFAULT_CRYPTO_TYPE: &'static str = "ed25519";
+pub const SECP256K1_CRYPTO_TYPE: &'static str = "secp256k1";
+
+const MASTER_SEED_LEN: usize = 32;
+// BIP-0039 mnemonic to seed conversion parameters
+const MNEMONIC_SALT_PREFIX: &'static str = "mnemonic";
+const MNEMONIC_PBKDF2_ROUNDS: usize = 2048;
+const MNEMONIC_SEED_LEN: usize = 64;
 
 trait CryptoType {
     fn encrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CommonError>;
@@ -32,17 +55,126 @@ trait CryptoType {
 }
 
 pub struct CryptoService {
-    crypto_types: HashMap<&'static str, Box<CryptoType>>
+    crypto_types: RefCell<HashMap<String, Box<CryptoType>>>,
+    external_signers: RefCell<HashMap<String, ExternalSigner>>
 }
 
 impl CryptoService {
     pub fn new() -> CryptoService {
-        let mut crypto_types: HashMap<&str, Box<CryptoType>> = HashMap::new();
-        crypto_types.insert(DEFAULT_CRYPTO_TYPE, Box::new(ED25519CryptoType::new()));
+        let mut crypto_types: HashMap<String, Box<CryptoType>> = HashMap::new();
+        crypto_types.insert(DEFAULT_CRYPTO_TYPE.to_string(), Box::new(ED25519CryptoType::new()));
+        #[cfg(feature = "ecdsa_secp256k1_openssl")]
+        crypto_types.insert(SECP256K1_CRYPTO_TYPE.to_string(), Box::new(Secp256k1CryptoType::new()));
 
         CryptoService {
-            crypto_types
+            crypto_types: RefCell::new(crypto_types),
+            external_signers: RefCell::new(HashMap::new())
+        }
+    }
+
+    pub fn register_crypto_type(&self,
+                                crypto_type_name: &str,
+                                create_key: CryptoCreateKey,
+                                sign: CryptoSign,
+                                verify: CryptoVerify,
+                                encrypt_sealed: CryptoEncryptSealed,
+                                decrypt_sealed: CryptoDecryptSealed,
+                                free: CryptoFree) -> Result<(), CryptoError> {
+        trace!("register_crypto_type >>> crypto_type_name: {:?}", crypto_type_name);
+
+        // Crypto type name is stored as verkey suffix, so delimiter can't be used inside of it
+        if crypto_type_name.is_empty() || crypto_type_name.contains(":") {
+            return Err(CryptoError::CommonError(
+                CommonError::InvalidStructure(format!("Invalid crypto type name: {:?}", crypto_type_name))));
         }
+
+        let mut crypto_types = self.crypto_types.borrow_mut();
+
+        if crypto_types.contains_key(crypto_type_name) {
+            return Err(CryptoError::CryptoTypeAlreadyRegistered(crypto_type_name.to_string()));
+        }
+
+        crypto_types.insert(crypto_type_name.to_string(),
+                            Box::new(PluggedCryptoType::new(crypto_type_name, create_key, sign, verify,
+                                                            encrypt_sealed, decrypt_sealed, free)));
+
+        trace!("register_crypto_type <<<");
+
+        Ok(())
+    }
+
+    pub fn register_external_signer(&self,
+                                    signer_name: &str,
+                                    sign: ExternalSignerSign,
+                                    precompute: ExternalSignerPrecompute,
+                                    decrypt_sealed: ExternalSignerDecryptSealed,
+                                    free: CryptoFree) -> Result<(), CryptoError> {
+        trace!("register_external_signer >>> signer_name: {:?}", signer_name);
+
+        if signer_name.is_empty() {
+            return Err(CryptoError::CommonError(
+                CommonError::InvalidStructure(format!("Empty external signer name"))));
+        }
+
+        let mut external_signers = self.external_signers.borrow_mut();
+
+        if external_signers.contains_key(signer_name) {
+            return Err(CryptoError::ExternalSignerAlreadyRegistered(signer_name.to_string()));
+        }
+
+        external_signers.insert(signer_name.to_string(),
+                                ExternalSigner::new(signer_name, sign, precompute, decrypt_sealed, free));
+
+        trace!("register_external_signer <<<");
+
+        Ok(())
+    }
+
+    pub fn create_external_key(&self, key_info: &ExternalKeyInfo) -> Result<Key, CryptoError> {
+        trace!("create_external_key >>> key_info: {:?}", key_info);
+
+        let verkey = self._validate_external_key_info(key_info)?;
+
+        let key = Key::new_external(verkey, key_info.signer.clone(), key_info.key_ref.clone());
+
+        trace!("create_external_key <<< key: {:?}", key);
+
+        Ok(key)
+    }
+
+    pub fn create_master_seed(&self, config: &MasterSeedConfig) -> Result<MasterSeed, CryptoError> {
+        trace!("create_master_seed >>>");
+
+        let seed = match (config.seed.as_ref(), config.mnemonic.as_ref()) {
+            (Some(_), Some(_)) =>
+                return Err(CryptoError::CommonError(
+                    CommonError::InvalidStructure(format!("Seed and mnemonic can't be used together")))),
+            (Some(seed), None) => self.convert_seed(Some(seed))?.unwrap(),
+            (None, Some(mnemonic)) => {
+                bip39::validate_mnemonic(mnemonic)?;
+                let salt = format!("{}{}", MNEMONIC_SALT_PREFIX, config.passphrase.as_ref().map(String::as_str).unwrap_or(""));
+                HMACSHA512::pbkdf2(mnemonic.as_bytes(), salt.as_bytes(), MNEMONIC_PBKDF2_ROUNDS, MNEMONIC_SEED_LEN)?
+            }
+            (None, None) => CryptoBox::gen_random_bytes(MASTER_SEED_LEN)
+        };
+
+        let master_seed = MasterSeed { seed: Base58::encode(&seed) };
+
+        trace!("create_master_seed <<<");
+
+        Ok(master_seed)
+    }
+
+    /// Returns seed of ed25519 key derived from master seed in base64 form accepted by convert_seed.
+    pub fn derive_seed(&self, master_seed: &MasterSeed, path: &str) -> Result<String, CryptoError> {
+        trace!("derive_seed >>> path: {:?}", path);
+
+        let master_seed = Base58::decode(&master_seed.seed)?;
+        let seed = base64::encode(&slip10::derive_ed25519_key(&master_seed, path)?);
+
+        trace!("derive_seed <<<");
+
+        Ok(seed)
     }
 
     pub fn create_key(&self, key_info: &KeyInfo) -> Result<Key, CryptoError> {
@@ -53,13 +185,15 @@ impl CryptoService {
             .map(String::as_str)
             .unwrap_or(DEFAULT_CRYPTO_TYPE);
 
-        if !self.crypto_types.contains_key(crypto_type_name) {
+        let crypto_types = self.crypto_types.borrow();
+
+        if !crypto_types.contains_key(crypto_type_name) {
             return Err(
                 CryptoError::UnknownCryptoError(
                     format!("KeyInfo contains unknown crypto: {}", crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        let crypto_type = crypto_types.get(crypto_type_name).unwrap();
 
         let seed = self.convert_seed(key_info.seed.as_ref().map(String::as_ref))?;
         let (vk, sk) = crypto_type.create_key(seed.as_ref().map(Vec::as_slice))?;
@@ -86,26 +220,56 @@ impl CryptoService {
             .map(String::as_str)
             .unwrap_or(DEFAULT_CRYPTO_TYPE);
 
-        if !self.crypto_types.contains_key(crypto_type_name) {
+        let crypto_types = self.crypto_types.borrow();
+
+        if !crypto_types.contains_key(crypto_type_name) {
             return Err(
                 CryptoError::UnknownCryptoError(
                     format!("MyDidInfo info contains unknown crypto: {}", crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        let crypto_type = crypto_types.get(crypto_type_name).unwrap();
+
+        if let Some(ref method_name) = my_did_info.method_name {
+            if !qualifier::is_valid_method_name(method_name) {
+                return Err(CryptoError::CommonError(
+                    CommonError::InvalidStructure(format!("Invalid DID method name: {}", method_name))));
+            }
+        }
+
+        let (vk, sk) = match my_did_info.external_key {
+            Some(ref external_key) => {
+                if my_did_info.seed.is_some() || !crypto_type_name.eq(DEFAULT_CRYPTO_TYPE) {
+                    return Err(CryptoError::CommonError(
+                        CommonError::InvalidStructure(format!("Seed and crypto type can't be used with external key"))));
+                }
+
+                (Base58::decode(&self._validate_external_key_info(external_key)?)?, Vec::new())
+            }
+            None => {
+                let seed = self.convert_seed(my_did_info.seed.as_ref().map(String::as_ref))?;
+                crypto_type.create_key(seed.as_ref().map(Vec::as_slice))?
+            }
+        };
 
-        let seed = self.convert_seed(my_did_info.seed.as_ref().map(String::as_ref))?;
-        let (vk, sk) = crypto_type.create_key(seed.as_ref().map(Vec::as_slice))?;
         let did = match my_did_info.did {
             Some(ref did) => {
                 self.validate_did(did)?;
-                Base58::decode(did)?
+                Base58::decode(qualifier::unqualify(did))?
             }
             _ if my_did_info.cid == Some(true) => vk.clone(),
             _ => vk[0..16].to_vec()
         };
 
         let did = Base58::encode(&did);
+
+        // Explicit method name has priority, otherwise method of passed DID is kept
+        let did = match my_did_info.method_name.as_ref().map(String::as_str)
+            .or(my_did_info.did.as_ref().and_then(|did| qualifier::method(did))) {
+            Some(method_name) => qualifier::qualify(&did, method_name),
+            None => did
+        };
+
         let mut vk = Base58::encode(&vk);
         let sk = Base58::encode(&sk);
 
@@ -114,7 +278,12 @@ impl CryptoService {
             vk = format!("{}:{}", vk, crypto_type_name);
         }
 
-        let did = (Did::new(did, vk.clone()), Key::new(vk, sk));
+        let key = match my_did_info.external_key {
+            Some(ref external_key) => Key::new_external(vk.clone(), external_key.signer.clone(), external_key.key_ref.clone()),
+            None => Key::new(vk.clone(), sk)
+        };
+
+        let did = (Did::new(did, vk), key);
 
         trace!("create_my_did <<< did: {:?}", did);
 
@@ -125,14 +294,14 @@ impl CryptoService {
         trace!("create_their_did >>> their_did_info: {:?}", their_did_info);
 
         // Check did is correct Base58
-        Base58::decode(&their_did_info.did)?;
+        Base58::decode(qualifier::unqualify(&their_did_info.did))?;
 
         let verkey = build_full_verkey(their_did_info.did.as_str(),
                                        their_did_info.verkey.as_ref().map(String::as_str))?;
 
         self.validate_key(&verkey)?;
 
-        let did = TheirDid { did: their_did_info.did.clone(), verkey };
+        let did = TheirDid { did: their_did_info.did.clone(), verkey, resolved_at: None };
 
         trace!("create_their_did <<< did: {:?}", did);
 
@@ -149,16 +318,23 @@ impl CryptoService {
             DEFAULT_CRYPTO_TYPE
         };
 
-        if !self.crypto_types.contains_key(crypto_type_name) {
+        let crypto_types = self.crypto_types.borrow();
+
+        if !crypto_types.contains_key(crypto_type_name) {
             return Err(
                 CryptoError::UnknownCryptoError(
                     format!("Trying to sign message with unknown crypto: {}", crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        let signature = match my_key.external {
+            Some(ref external) => self._external_signer(external)?.sign(&external.key_ref, doc)?,
+            None => {
+                let crypto_type = crypto_types.get(crypto_type_name).unwrap();
 
-        let my_sk = Base58::decode(my_key.signkey.as_str())?;
-        let signature = crypto_type.sign(&my_sk, doc)?;
+                let my_sk = Base58::decode(my_key.signkey.as_str())?;
+                crypto_type.sign(&my_sk, doc)?
+            }
+        };
 
         trace!("sign <<< signature: {:?}", signature);
 
@@ -175,12 +351,14 @@ impl CryptoService {
             (their_vk, DEFAULT_CRYPTO_TYPE)
         };
 
-        if !self.crypto_types.contains_key(crypto_type_name) {
+        let crypto_types = self.crypto_types.borrow();
+
+        if !crypto_types.contains_key(crypto_type_name) {
             return Err(CryptoError::UnknownCryptoError(
                 format!("Trying to verify message with unknown crypto: {}", crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        let crypto_type = crypto_types.get(crypto_type_name).unwrap();
 
         let their_vk = Base58::decode(&their_vk)?;
 
@@ -224,7 +402,9 @@ impl CryptoService {
             (their_vk, DEFAULT_CRYPTO_TYPE)
         };
 
-        if !self.crypto_types.contains_key(&crypto_type_name) {
+        let crypto_types = self.crypto_types.borrow();
+
+        if !crypto_types.contains_key(crypto_type_name) {
             return Err(CryptoError::UnknownCryptoError(format!("Trying to encrypt message with unknown crypto: {}", crypto_type_name)));
         }
 
@@ -236,13 +416,21 @@ impl CryptoService {
                         their_crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(&crypto_type_name).unwrap();
+        let crypto_type = crypto_types.get(crypto_type_name).unwrap();
 
-        let my_sk = Base58::decode(my_key.signkey.as_str())?;
         let their_vk = Base58::decode(their_vk)?;
         let nonce = crypto_type.gen_nonce();
 
-        let encrypted_doc = crypto_type.encrypt(&my_sk, &their_vk, doc, &nonce)?;
+        let encrypted_doc = match my_key.external {
+            Some(ref external) => {
+                let precomputed_key = self._external_precompute(external, &their_vk)?;
+                CryptoBox::encrypt_precomputed(&precomputed_key, doc, &nonce)?
+            }
+            None => {
+                let my_sk = Base58::decode(my_key.signkey.as_str())?;
+                crypto_type.encrypt(&my_sk, &their_vk, doc, &nonce)?
+            }
+        };
 
         trace!("encrypt <<< encrypted_doc: {:?}, nonce: {:?}", encrypted_doc, nonce);
 
@@ -266,7 +454,9 @@ impl CryptoService {
             (their_vk, DEFAULT_CRYPTO_TYPE)
         };
 
-        if !self.crypto_types.contains_key(&crypto_type_name) {
+        let crypto_types = self.crypto_types.borrow();
+
+        if !crypto_types.contains_key(crypto_type_name) {
             return Err(CryptoError::UnknownCryptoError(
                 format!("Trying to decrypt message with unknown crypto: {}", crypto_type_name)));
         }
@@ -279,12 +469,20 @@ impl CryptoService {
                         their_crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        let crypto_type = crypto_types.get(crypto_type_name).unwrap();
 
-        let my_sk = Base58::decode(&my_key.signkey)?;
         let their_vk = Base58::decode(their_vk)?;
 
-        let decrypted_doc = crypto_type.decrypt(&my_sk, &their_vk, &doc, &nonce)?;
+        let decrypted_doc = match my_key.external {
+            Some(ref external) => {
+                let precomputed_key = self._external_precompute(external, &their_vk)?;
+                CryptoBox::decrypt_precomputed(&precomputed_key, &doc, &nonce)?
+            }
+            None => {
+                let my_sk = Base58::decode(&my_key.signkey)?;
+                crypto_type.decrypt(&my_sk, &their_vk, &doc, &nonce)?
+            }
+        };
 
         trace!("decrypt <<< decrypted_doc: {:?}", decrypted_doc);
 
@@ -301,11 +499,13 @@ impl CryptoService {
             (their_vk, DEFAULT_CRYPTO_TYPE)
         };
 
-        if !self.crypto_types.contains_key(&crypto_type_name) {
+        let crypto_types = self.crypto_types.borrow();
+
+        if !crypto_types.contains_key(crypto_type_name) {
             return Err(CryptoError::UnknownCryptoError(format!("Trying to encrypt sealed message with unknown crypto: {}", crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        let crypto_type = crypto_types.get(crypto_type_name).unwrap();
 
         let their_vk = Base58::decode(their_vk)?;
 
@@ -326,17 +526,24 @@ impl CryptoService {
             (my_key.verkey.as_str(), DEFAULT_CRYPTO_TYPE)
         };
 
-        if !self.crypto_types.contains_key(&crypto_type_name) {
+        let crypto_types = self.crypto_types.borrow();
+
+        if !crypto_types.contains_key(crypto_type_name) {
             return Err(CryptoError::UnknownCryptoError(
                 format!("Trying to decrypt sealed message with unknown crypto: {}", crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        let decrypted_doc = match my_key.external {
+            Some(ref external) => self._external_signer(external)?.decrypt_sealed(&external.key_ref, doc)?,
+            None => {
+                let crypto_type = crypto_types.get(crypto_type_name).unwrap();
 
-        let my_vk = Base58::decode(my_vk)?;
-        let my_sk = Base58::decode(my_key.signkey.as_str())?;
+                let my_vk = Base58::decode(my_vk)?;
+                let my_sk = Base58::decode(my_key.signkey.as_str())?;
 
-        let decrypted_doc = crypto_type.decrypt_sealed(&my_vk, &my_sk, doc)?;
+                crypto_type.decrypt_sealed(&my_vk, &my_sk, doc)?
+            }
+        };
 
         trace!("decrypt_sealed <<< decrypted_doc: {:?}", decrypted_doc);
 
@@ -372,11 +579,13 @@ impl CryptoService {
             (vk, DEFAULT_CRYPTO_TYPE)
         };
 
-        if !self.crypto_types.contains_key(&crypto_type_name) {
+        let crypto_types = self.crypto_types.borrow();
+
+        if !crypto_types.contains_key(crypto_type_name) {
             return Err(CryptoError::UnknownCryptoError(format!("Trying to use key with unknown crypto: {}", crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        let crypto_type = crypto_types.get(crypto_type_name).unwrap();
 
         let vk = if vk.starts_with("~") { &vk[1..] } else { vk };
         let vk = Base58::decode(vk)?;
@@ -391,7 +600,14 @@ impl CryptoService {
     pub fn validate_did(&self, did: &str) -> Result<(), CryptoError> {
         trace!("validate_did >>> did: {:?}", did);
 
-        let did = Base58::decode(did)?;
+        if let Some(method_name) = qualifier::method(did) {
+            if !qualifier::is_valid_method_name(method_name) {
+                return Err(CryptoError::CommonError(
+                    CommonError::InvalidStructure(format!("Invalid DID method name: {}", method_name))));
+            }
+        }
+
+        let did = Base58::decode(qualifier::unqualify(did))?;
 
         if did.len() != 16 && did.len() != 32 {
             return Err(CryptoError::CommonError(
@@ -405,6 +621,41 @@ impl CryptoService {
 
         Ok(res)
     }
+
+    fn _validate_external_key_info(&self, key_info: &ExternalKeyInfo) -> Result<String, CryptoError> {
+        if !self.external_signers.borrow().contains_key(&key_info.signer) {
+            return Err(CryptoError::UnknownCryptoError(format!("Unknown external signer: {}", key_info.signer)));
+        }
+
+        // Only ed25519 keys can be held by external signer
+        let verkey = if key_info.verkey.ends_with(&format!(":{}", DEFAULT_CRYPTO_TYPE)) {
+            &key_info.verkey[..key_info.verkey.len() - DEFAULT_CRYPTO_TYPE.len() - 1]
+        } else {
+            key_info.verkey.as_str()
+        };
+
+        if verkey.contains(":") || Base58::decode(verkey)?.len() != 32 {
+            return Err(CryptoError::CommonError(
+                CommonError::InvalidStructure(format!("External key must be ed25519 verkey: {}", key_info.verkey))));
+        }
+
+        Ok(verkey.to_string())
+    }
+
+    fn _external_signer(&self, external: &ExternalKeyRef) -> Result<Ref<ExternalSigner>, CryptoError> {
+        let external_signers = self.external_signers.borrow();
+
+        if !external_signers.contains_key(&external.signer) {
+            return Err(CryptoError::UnknownCryptoError(format!("Unknown external signer: {}", external.signer)));
+        }
+
+        Ok(Ref::map(external_signers, |external_signers| external_signers.get(&external.signer).unwrap()))
+    }
+
+    fn _external_precompute(&self, external: &ExternalKeyRef, their_vk: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let their_pk = CryptoBox::vk_to_curve25519(their_vk)?;
+        Ok(self._external_signer(external)?.precompute(&external.key_ref, &their_pk)?)
+    }
 }
 
 #[cfg(test)]
@@ -415,7 +666,7 @@ mod tests {
     #[test]
     fn create_my_did_with_works_for_empty_info() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, external_key: None, derivation: None };
         service.create_my_did(&did_info).unwrap();
     }
 
@@ -424,12 +675,63 @@ mod tests {
         let service = CryptoService::new();
 
         let did = "NcYxiDXkpYi6ov5FcYDi1e";
-        let did_info = MyDidInfo { did: Some(did.to_string()), cid: None, seed: None, crypto_type: None };
+        let did_info = MyDidInfo { did: Some(did.to_string()), cid: None, seed: None, crypto_type: None, method_name: None, external_key: None, derivation: None };
 
         let (my_did, _) = service.create_my_did(&did_info).unwrap();
         assert_eq!(did, my_did.did);
     }
 
+    #[test]
+    fn create_my_did_works_for_method_name() {
+        let service = CryptoService::new();
+
+        let did = "NcYxiDXkpYi6ov5FcYDi1e";
+        let did_info = MyDidInfo { did: Some(did.to_string()), cid: None, seed: None, crypto_type: None, method_name: Some("sov".to_string()), external_key: None, derivation: None };
+
+        let (my_did, _) = service.create_my_did(&did_info).unwrap();
+        assert_eq!("did:sov:NcYxiDXkpYi6ov5FcYDi1e", my_did.did);
+    }
+
+    #[test]
+    fn create_my_did_works_for_passed_qualified_did() {
+        let service = CryptoService::new();
+
+        let did = "did:sov:NcYxiDXkpYi6ov5FcYDi1e";
+        let did_info = MyDidInfo { did: Some(did.to_string()), cid: None, seed: None, crypto_type: None, method_name: None, external_key: None, derivation: None };
+
+        let (my_did, _) = service.create_my_did(&did_info).unwrap();
+        assert_eq!(did, my_did.did);
+    }
+
+    #[test]
+    fn create_my_did_not_works_for_invalid_method_name() {
+        let service = CryptoService::new();
+
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: Some("Invalid-Method".to_string()), external_key: None, derivation: None };
+
+        let res = service.create_my_did(&did_info);
+        assert_match!(Err(CryptoError::CommonError(CommonError::InvalidStructure(_))), res);
+    }
+
+    #[test]
+    fn create_their_did_works_for_qualified_did_and_abbreviated_verkey() {
+        let service = CryptoService::new();
+        let did = "did:sov:8wZcEriaNLNKtteJvx7f8i";
+        let their_did_info = TheirDidInfo::new(did.to_string(), Some("~NcYxiDXkpYi6ov5FcYDi1e".to_string()));
+        let their_did = service.create_their_did(&their_did_info).unwrap();
+
+        assert_eq!(did.to_string(), their_did.did);
+        assert_eq!("5L2HBnzbu6Auh2pkDRbFt5f4prvgE2LzknkuYLsKkacp", their_did.verkey);
+    }
+
+    #[test]
+    fn validate_did_works_for_qualified_did() {
+        let service = CryptoService::new();
+        service.validate_did("did:sov:NcYxiDXkpYi6ov5FcYDi1e").unwrap();
+        assert!(service.validate_did("did:Sov:NcYxiDXkpYi6ov5FcYDi1e").is_err());
+        assert!(service.validate_did("did:sov:invalid_base58").is_err());
+    }
+
     #[test]
     fn create_my_did_not_works_for_invalid_crypto_type() {
         let service = CryptoService::new();
@@ -437,7 +739,7 @@ mod tests {
         let did = Some("NcYxiDXkpYi6ov5FcYDi1e".to_string());
         let crypto_type = Some("type".to_string());
 
-        let did_info = MyDidInfo { did: did.clone(), cid: None, seed: None, crypto_type: crypto_type };
+        let did_info = MyDidInfo { did: did.clone(), cid: None, seed: None, crypto_type: crypto_type, method_name: None, external_key: None, derivation: None };
 
         assert!(service.create_my_did(&did_info).is_err());
     }
@@ -449,8 +751,8 @@ mod tests {
         let did = Some("NcYxiDXkpYi6ov5FcYDi1e".to_string());
         let seed = Some("00000000000000000000000000000My1".to_string());
 
-        let did_info_with_seed = MyDidInfo { did: did.clone(), cid: None, seed, crypto_type: None };
-        let did_info_without_seed = MyDidInfo { did: did.clone(), cid: None, seed: None, crypto_type: None };
+        let did_info_with_seed = MyDidInfo { did: did.clone(), cid: None, seed, crypto_type: None, method_name: None, external_key: None, derivation: None };
+        let did_info_without_seed = MyDidInfo { did: did.clone(), cid: None, seed: None, crypto_type: None, method_name: None, external_key: None, derivation: None };
 
         let (did_with_seed, _) = service.create_my_did(&did_info_with_seed).unwrap();
         let (did_without_seed, _) = service.create_my_did(&did_info_without_seed).unwrap();
@@ -497,7 +799,7 @@ mod tests {
     #[test]
     fn sign_works() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, external_key: None, derivation: None };
 
         let message = r#"message"#;
         let (_, my_key) = service.create_my_did(&did_info).unwrap();
@@ -515,7 +817,7 @@ mod tests {
     #[test]
     fn sign_verify_works() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, external_key: None, derivation: None };
         let message = r#"message"#;
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -526,7 +828,7 @@ mod tests {
     #[test]
     fn sign_verify_works_for_verkey_contained_crypto_type() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, external_key: None, derivation: None };
         let message = r#"message"#;
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -535,11 +837,24 @@ mod tests {
         assert!(valid);
     }
 
+    #[test]
+    fn sign_verify_works_for_secp256k1_crypto_type() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: Some(SECP256K1_CRYPTO_TYPE.to_string()), method_name: None, external_key: None, derivation: None };
+        let message = r#"message"#;
+        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+        assert!(my_did.verkey.ends_with(":secp256k1"));
+        service.validate_key(&my_did.verkey).unwrap();
+        let signature = service.sign(&my_key, message.as_bytes()).unwrap();
+        let valid = service.verify(&my_did.verkey, message.as_bytes(), &signature).unwrap();
+        assert!(valid);
+    }
+
 
     #[test]
     fn sign_verify_works_for_verkey_contained_invalid_crypto_type() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, external_key: None, derivation: None };
         let message = r#"message"#;
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -550,7 +865,7 @@ mod tests {
     #[test]
     fn verify_not_works_for_invalid_verkey() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, external_key: None, derivation: None };
         let message = r#"message"#;
         let (_, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -563,7 +878,7 @@ mod tests {
     fn encrypt_works() {
         let service = CryptoService::new();
         let msg = "some message";
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, external_key: None, derivation: None };
         let (_, my_key) = service.create_my_did(&did_info).unwrap();
         let (their_did, _) = service.create_my_did(&did_info.clone()).unwrap();
         let their_did = Did::new(their_did.did, their_did.verkey);
@@ -576,7 +891,7 @@ mod tests {
 
         let msg = "some message";
 
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, external_key: None, derivation: None };
 
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
 
@@ -604,7 +919,7 @@ mod tests {
 
         let msg = "some message";
 
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, external_key: None, derivation: None };
 
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
 
@@ -630,7 +945,7 @@ mod tests {
     fn encrypt_sealed_works() {
         let service = CryptoService::new();
         let msg = "some message";
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, external_key: None, derivation: None };
         let (did, _) = service.create_my_did(&did_info.clone()).unwrap();
         let did = Did::new(did.did, did.verkey);
         service.encrypt_sealed(&did.verkey, msg.as_bytes()).unwrap();
@@ -640,11 +955,46 @@ mod tests {
     fn encrypt_decrypt_sealed_works() {
         let service = CryptoService::new();
         let msg = "some message".as_bytes();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, external_key: None, derivation: None };
         let (did, key) = service.create_my_did(&did_info.clone()).unwrap();
         let encrypt_did = Did::new(did.did.clone(), did.verkey.clone());
         let encrypted_message = service.encrypt_sealed(&encrypt_did.verkey, msg).unwrap();
         let decrypted_message = service.decrypt_sealed(&key, &encrypted_message).unwrap();
         assert_eq!(msg, decrypted_message.as_slice());
     }
+
+    #[test]
+    fn create_master_seed_works_for_mnemonic() {
+        let service = CryptoService::new();
+        let config = MasterSeedConfig {
+            seed: None,
+            mnemonic: Some("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string()),
+            passphrase: Some("TREZOR".to_string())
+        };
+        let master_seed = service.create_master_seed(&config).unwrap();
+        let expected_seed = "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04";
+        let seed: String = Base58::decode(&master_seed.seed).unwrap().iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(expected_seed, seed);
+    }
+
+    #[test]
+    fn create_master_seed_works_for_seed_and_mnemonic() {
+        let service = CryptoService::new();
+        let config = MasterSeedConfig {
+            seed: Some("00000000000000000000000000000My1".to_string()),
+            mnemonic: Some("abandon about".to_string()),
+            passphrase: None
+        };
+        assert_match!(Err(CryptoError::CommonError(CommonError::InvalidStructure(_))), service.create_master_seed(&config));
+    }
+
+    #[test]
+    fn derive_seed_works() {
+        let service = CryptoService::new();
+        let master_seed = service.create_master_seed(&MasterSeedConfig::default()).unwrap();
+        let seed = service.derive_seed(&master_seed, "m/44'/0'").unwrap();
+        assert_eq!(seed, service.derive_seed(&master_seed, "m/44'/0'").unwrap());
+        assert_ne!(seed, service.derive_seed(&master_seed, "m/44'/1'").unwrap());
+        assert_eq!(32, service.convert_seed(Some(&seed)).unwrap().unwrap().len());
+    }
 }
@@ -0,0 +1,174 @@
+use super::CryptoType;
+use api::ErrorCode;
+use api::crypto::{CryptoCreateKey, CryptoSign, CryptoVerify, CryptoEncryptSealed, CryptoDecryptSealed, CryptoFree};
+use errors::common::CommonError;
+
+use std::ptr;
+use std::slice;
+
+pub struct BufferGuard {
+    data: *const u8,
+    len: u32,
+    free_handler: CryptoFree
+}
+
+impl BufferGuard {
+    pub fn new(data: *const u8, len: u32, free_handler: CryptoFree) -> BufferGuard {
+        BufferGuard { data, len, free_handler }
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        if self.data.is_null() {
+            return Vec::new();
+        }
+
+        unsafe { slice::from_raw_parts(self.data, self.len as usize) }.to_vec()
+    }
+}
+
+impl Drop for BufferGuard {
+    fn drop(&mut self) {
+        if !self.data.is_null() {
+            let _err = (self.free_handler)(self.data, self.len);
+        }
+    }
+}
+
+pub struct PluggedCryptoType {
+    name: String,
+    create_key_handler: CryptoCreateKey,
+    sign_handler: CryptoSign,
+    verify_handler: CryptoVerify,
+    encrypt_sealed_handler: CryptoEncryptSealed,
+    decrypt_sealed_handler: CryptoDecryptSealed,
+    free_handler: CryptoFree
+}
+
+impl PluggedCryptoType {
+    pub fn new(name: &str,
+               create_key_handler: CryptoCreateKey,
+               sign_handler: CryptoSign,
+               verify_handler: CryptoVerify,
+               encrypt_sealed_handler: CryptoEncryptSealed,
+               decrypt_sealed_handler: CryptoDecryptSealed,
+               free_handler: CryptoFree) -> PluggedCryptoType {
+        PluggedCryptoType {
+            name: name.to_string(),
+            create_key_handler,
+            sign_handler,
+            verify_handler,
+            encrypt_sealed_handler,
+            decrypt_sealed_handler,
+            free_handler
+        }
+    }
+
+    fn _check_err(&self, operation: &str, err: ErrorCode) -> Result<(), CommonError> {
+        match err {
+            ErrorCode::Success => Ok(()),
+            ErrorCode::CommonInvalidStructure =>
+                Err(CommonError::InvalidStructure(format!("Crypto type {} rejected {} input", self.name, operation))),
+            err =>
+                Err(CommonError::InvalidState(format!("Crypto type {} failed on {}: {:?}", self.name, operation, err)))
+        }
+    }
+}
+
+impl CryptoType for PluggedCryptoType {
+    fn encrypt(&self, _sk: &[u8], _vk: &[u8], _doc: &[u8], _nonce: &[u8]) -> Result<Vec<u8>, CommonError> {
+        Err(CommonError::InvalidState(format!("Authenticated encryption isn't supported for {} keys", self.name)))
+    }
+
+    fn decrypt(&self, _sk: &[u8], _vk: &[u8], _doc: &[u8], _nonce: &[u8]) -> Result<Vec<u8>, CommonError> {
+        Err(CommonError::InvalidState(format!("Authenticated encryption isn't supported for {} keys", self.name)))
+    }
+
+    fn gen_nonce(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn create_key(&self, seed: Option<&[u8]>) -> Result<(Vec<u8>, Vec<u8>), CommonError> {
+        let (seed, seed_len) = match seed {
+            Some(seed) => (seed.as_ptr(), seed.len() as u32),
+            None => (ptr::null(), 0)
+        };
+
+        let mut vk_ptr: *const u8 = ptr::null();
+        let mut vk_len: u32 = 0;
+        let mut sk_ptr: *const u8 = ptr::null();
+        let mut sk_len: u32 = 0;
+
+        let err = (self.create_key_handler)(seed, seed_len, &mut vk_ptr, &mut vk_len, &mut sk_ptr, &mut sk_len);
+
+        let vk = BufferGuard::new(vk_ptr, vk_len, self.free_handler);
+        let sk = BufferGuard::new(sk_ptr, sk_len, self.free_handler);
+
+        self._check_err("create_key", err)?;
+
+        Ok((vk.to_vec(), sk.to_vec()))
+    }
+
+    fn sign(&self, sk: &[u8], doc: &[u8]) -> Result<Vec<u8>, CommonError> {
+        let mut signature_ptr: *const u8 = ptr::null();
+        let mut signature_len: u32 = 0;
+
+        let err = (self.sign_handler)(sk.as_ptr(), sk.len() as u32,
+                                      doc.as_ptr(), doc.len() as u32,
+                                      &mut signature_ptr, &mut signature_len);
+
+        let signature = BufferGuard::new(signature_ptr, signature_len, self.free_handler);
+
+        self._check_err("sign", err)?;
+
+        Ok(signature.to_vec())
+    }
+
+    fn verify(&self, vk: &[u8], doc: &[u8], signature: &[u8]) -> Result<bool, CommonError> {
+        let mut valid = false;
+
+        let err = (self.verify_handler)(vk.as_ptr(), vk.len() as u32,
+                                        doc.as_ptr(), doc.len() as u32,
+                                        signature.as_ptr(), signature.len() as u32,
+                                        &mut valid);
+
+        self._check_err("verify", err)?;
+
+        Ok(valid)
+    }
+
+    fn encrypt_sealed(&self, vk: &[u8], doc: &[u8]) -> Result<Vec<u8>, CommonError> {
+        let mut encrypted_ptr: *const u8 = ptr::null();
+        let mut encrypted_len: u32 = 0;
+
+        let err = (self.encrypt_sealed_handler)(vk.as_ptr(), vk.len() as u32,
+                                                doc.as_ptr(), doc.len() as u32,
+                                                &mut encrypted_ptr, &mut encrypted_len);
+
+        let encrypted = BufferGuard::new(encrypted_ptr, encrypted_len, self.free_handler);
+
+        self._check_err("encrypt_sealed", err)?;
+
+        Ok(encrypted.to_vec())
+    }
+
+    fn decrypt_sealed(&self, vk: &[u8], sk: &[u8], doc: &[u8]) -> Result<Vec<u8>, CommonError> {
+        let mut decrypted_ptr: *const u8 = ptr::null();
+        let mut decrypted_len: u32 = 0;
+
+        let err = (self.decrypt_sealed_handler)(vk.as_ptr(), vk.len() as u32,
+                                                sk.as_ptr(), sk.len() as u32,
+                                                doc.as_ptr(), doc.len() as u32,
+                                                &mut decrypted_ptr, &mut decrypted_len);
+
+        let decrypted = BufferGuard::new(decrypted_ptr, decrypted_len, self.free_handler);
+
+        self._check_err("decrypt_sealed", err)?;
+
+        Ok(decrypted.to_vec())
+    }
+
+    fn validate_key(&self, _vk: &[u8]) -> Result<(), CommonError> {
+        // Key format is opaque for libindy, plugin validates it on use
+        Ok(())
+    }
+}
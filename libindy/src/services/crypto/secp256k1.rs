@@ -0,0 +1,50 @@
+use super::CryptoType;
+use utils::crypto::ecdsa_secp256k1::EcdsaSecp256k1;
+use errors::common::CommonError;
+
+
+pub struct Secp256k1CryptoType {}
+
+impl Secp256k1CryptoType {
+    pub fn new() -> Secp256k1CryptoType {
+        Secp256k1CryptoType {}
+    }
+}
+
+impl CryptoType for Secp256k1CryptoType {
+    fn encrypt(&self, _sk: &[u8], _vk: &[u8], _doc: &[u8], _nonce: &[u8]) -> Result<Vec<u8>, CommonError> {
+        Err(CommonError::InvalidState(format!("Authenticated encryption isn't supported for secp256k1 keys")))
+    }
+
+    fn decrypt(&self, _sk: &[u8], _vk: &[u8], _doc: &[u8], _nonce: &[u8]) -> Result<Vec<u8>, CommonError> {
+        Err(CommonError::InvalidState(format!("Authenticated encryption isn't supported for secp256k1 keys")))
+    }
+
+    fn gen_nonce(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn create_key(&self, seed: Option<&[u8]>) -> Result<(Vec<u8>, Vec<u8>), CommonError> {
+        EcdsaSecp256k1::create_key_pair(seed)
+    }
+
+    fn sign(&self, sk: &[u8], doc: &[u8]) -> Result<Vec<u8>, CommonError> {
+        EcdsaSecp256k1::sign(sk, doc)
+    }
+
+    fn verify(&self, vk: &[u8], doc: &[u8], signature: &[u8]) -> Result<bool, CommonError> {
+        EcdsaSecp256k1::verify(vk, doc, signature)
+    }
+
+    fn encrypt_sealed(&self, _vk: &[u8], _doc: &[u8]) -> Result<Vec<u8>, CommonError> {
+        Err(CommonError::InvalidState(format!("Anonymous encryption isn't supported for secp256k1 keys")))
+    }
+
+    fn decrypt_sealed(&self, _vk: &[u8], _sk: &[u8], _doc: &[u8]) -> Result<Vec<u8>, CommonError> {
+        Err(CommonError::InvalidState(format!("Anonymous encryption isn't supported for secp256k1 keys")))
+    }
+
+    fn validate_key(&self, vk: &[u8]) -> Result<(), CommonError> {
+        EcdsaSecp256k1::validate_public_key(vk)
+    }
+}
@@ -28,6 +28,7 @@ use domain::anoncreds::revocation_registry::RevocationRegistry;
 use domain::anoncreds::revocation_registry_delta::{RevocationRegistryDelta, RevocationRegistryDeltaV1};
 use domain::anoncreds::schema::{Schema, SchemaV1};
 use domain::anoncreds::credential_definition::{CredentialDefinition, CredentialDefinitionV1};
+use utils::qualifier;
 use self::indy_crypto::cl::RevocationRegistryDelta as CryproRevocationRegistryDelta;
 use self::indy_crypto::utils::json::{JsonEncodable, JsonDecodable};
 
@@ -50,7 +51,7 @@ impl LedgerService {
 
         let mut operation: Value = Value::Object(serde_json::map::Map::new());
         operation["type"] = Value::String(NYM.to_string());
-        operation["dest"] = Value::String(dest.to_string());
+        operation["dest"] = Value::String(qualifier::unqualify(dest).to_string());
 
         if let Some(v) = verkey {
             operation["verkey"] = Value::String(v.to_string());
@@ -85,7 +86,7 @@ impl LedgerService {
     pub fn build_get_nym_request(&self, identifier: &str, dest: &str) -> Result<String, CommonError> {
         info!("build_get_nym_request >>> identifier: {:?}, dest: {:?}", identifier, dest);
 
-        let operation = GetNymOperation::new(dest.to_string());
+        let operation = GetNymOperation::new(qualifier::unqualify(dest).to_string());
 
         let request = Request::build_request(identifier, operation)
             .map_err(|err| CommonError::InvalidState(format!("GET_NYM request json is invalid {:?}.", err)))?;
@@ -98,7 +99,7 @@ impl LedgerService {
     pub fn build_get_ddo_request(&self, identifier: &str, dest: &str) -> Result<String, CommonError> {
         info!("build_get_ddo_request >>> identifier: {:?}, dest: {:?}", identifier, dest);
 
-        let operation = GetDdoOperation::new(dest.to_string());
+        let operation = GetDdoOperation::new(qualifier::unqualify(dest).to_string());
 
         let request = Request::build_request(identifier, operation)
             .map_err(|err| CommonError::InvalidState(format!("Invalid get_ddo request json: {:?}", err)))?;
@@ -120,7 +121,7 @@ impl LedgerService {
                 .map_err(|err| CommonError::InvalidStructure(format!("Can not deserialize Raw Attribute: {:?}", err)))?;
         }
 
-        let operation = AttribOperation::new(dest.to_string(),
+        let operation = AttribOperation::new(qualifier::unqualify(dest).to_string(),
                                              hash.as_ref().map(|s| s.to_string()),
                                              raw.as_ref().map(|s| s.to_string()),
                                              enc.as_ref().map(|s| s.to_string()));
@@ -140,7 +141,7 @@ impl LedgerService {
         if raw.is_none() && hash.is_none() && enc.is_none() {
             return Err(CommonError::InvalidStructure(format!("Either raw or hash or enc must be specified")));
         }
-        let operation = GetAttribOperation::new(dest.to_string(), raw, hash, enc);
+        let operation = GetAttribOperation::new(qualifier::unqualify(dest).to_string(), raw, hash, enc);
 
         let request = Request::build_request(identifier, operation)
             .map_err(|err| CommonError::InvalidState(format!("GET_ATTRIB request json is invalid {:?}.", err)))?;
@@ -249,7 +250,7 @@ impl LedgerService {
             return Err(CommonError::InvalidStructure("Invalid data json: Fields node_ip, node_port, client_ip, client_port must be specified together".to_string()));
         }
 
-        let operation = NodeOperation::new(dest.to_string(), data);
+        let operation = NodeOperation::new(qualifier::unqualify(dest).to_string(), data);
 
         let request = Request::build_request(identifier, operation)
             .map_err(|err| CommonError::InvalidState(format!("NODE request json is invalid {:?}.", err)))?;
@@ -577,6 +578,18 @@ impl LedgerService {
 mod tests {
     use super::*;
 
+    #[test]
+    fn build_nym_request_works_for_fully_qualified_dids() {
+        let ledger_service = LedgerService::new();
+        let identifier = "did:sov:NcYxiDXkpYi6ov5FcYDi1e";
+        let dest = "did:sov:VsKV7grR1BUE29mG2Fm2kX";
+
+        let expected_result = r#""identifier":"NcYxiDXkpYi6ov5FcYDi1e","operation":{"dest":"VsKV7grR1BUE29mG2Fm2kX","type":"1"},"protocolVersion":1"#;
+
+        let nym_request = ledger_service.build_nym_request(identifier, dest, None, None, None).unwrap();
+        assert!(nym_request.contains(expected_result));
+    }
+
     #[test]
     fn build_nym_request_works_for_only_required_fields() {
         let ledger_service = LedgerService::new();
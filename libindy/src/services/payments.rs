@@ -1,11 +1,12 @@
 use api::payments::*;
 use api::ErrorCode;
 use errors::common::CommonError;
-use errors::payments::PaymentsError;
+use errors::payments::{MethodConflict, PaymentsError};
 
 use serde_json;
+use serde_json::Value;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
 use std::ffi::{CString, NulError};
 use std::collections::HashSet;
@@ -28,6 +29,11 @@ pub struct PaymentsMethod {
     build_set_txn_fees_req: BuildSetTxnFeesReqCB,
     build_get_txn_fees_req: BuildGetTxnFeesReqCB,
     parse_get_txn_fees_response: ParseGetTxnFeesResponseCB,
+    build_verify_payment_req: Option<BuildVerifyPaymentReqCB>,
+    parse_verify_payment_response: Option<ParseVerifyPaymentResponseCB>,
+    sign_with_address: Option<SignWithAddressCB>,
+    verify_with_address: Option<VerifyWithAddressCB>,
+    get_request_fee: Option<GetRequestFeeCB>,
 }
 
 pub type PaymentsMethodCBs = PaymentsMethod;
@@ -43,7 +49,12 @@ impl PaymentsMethodCBs {
                build_mint_req: BuildMintReqCB,
                build_set_txn_fees_req: BuildSetTxnFeesReqCB,
                build_get_txn_fees_req: BuildGetTxnFeesReqCB,
-               parse_get_txn_fees_response: ParseGetTxnFeesResponseCB) -> Self {
+               parse_get_txn_fees_response: ParseGetTxnFeesResponseCB,
+               build_verify_payment_req: Option<BuildVerifyPaymentReqCB>,
+               parse_verify_payment_response: Option<ParseVerifyPaymentResponseCB>,
+               sign_with_address: Option<SignWithAddressCB>,
+               verify_with_address: Option<VerifyWithAddressCB>,
+               get_request_fee: Option<GetRequestFeeCB>) -> Self {
         PaymentsMethodCBs {
             create_address,
             add_request_fees,
@@ -56,11 +67,39 @@ impl PaymentsMethodCBs {
             build_set_txn_fees_req,
             build_get_txn_fees_req,
             parse_get_txn_fees_response,
+            build_verify_payment_req,
+            parse_verify_payment_response,
+            sign_with_address,
+            verify_with_address,
+            get_request_fee,
         }
     }
 }
 
-impl PaymentsMethod {}
+impl PaymentsMethod {
+    fn capabilities(&self) -> Vec<String> {
+        let mut capabilities: Vec<&str> = vec!["create_payment_address", "add_request_fees", "parse_response_with_fees",
+                                               "build_get_utxo_request", "parse_get_utxo_response", "build_payment_req",
+                                               "parse_payment_response", "build_mint_req", "build_set_txn_fees_req",
+                                               "build_get_txn_fees_req", "parse_get_txn_fees_response"];
+
+        if self.build_verify_payment_req.is_some() && self.parse_verify_payment_response.is_some() {
+            capabilities.push("build_verify_payment_req");
+            capabilities.push("parse_verify_payment_response");
+        }
+
+        if self.sign_with_address.is_some() && self.verify_with_address.is_some() {
+            capabilities.push("sign_with_address");
+            capabilities.push("verify_with_address");
+        }
+
+        if self.get_request_fee.is_some() {
+            capabilities.push("get_request_fee");
+        }
+
+        capabilities.into_iter().map(String::from).collect()
+    }
+}
 
 impl PaymentsService {
     pub fn new() -> Self {
@@ -259,66 +298,421 @@ impl PaymentsService {
         res
     }
 
+    pub fn build_verify_payment_req(&self, cmd_handle: i32, type_: &str, wallet_handle: i32, submitter_did: &str, receipt: &str) -> Result<(), PaymentsError> {
+        trace!("build_verify_payment_req >>> type_: {:?}, wallet_handle: {:?}, submitter_did: {:?}, receipt: {:?}", type_, wallet_handle, submitter_did, receipt);
+        let build_verify_payment_req: BuildVerifyPaymentReqCB = self.methods.borrow().get(type_)
+            .ok_or(PaymentsError::UnknownType(format!("Unknown payment method {}", type_)))?.build_verify_payment_req
+            .ok_or(PaymentsError::OperationNotSupported(format!("Payment method {} doesn't support payment verification", type_)))?;
+
+        let submitter_did = CString::new(submitter_did)?;
+        let receipt = CString::new(receipt)?;
+
+        let err = build_verify_payment_req(cmd_handle, wallet_handle, submitter_did.as_ptr(), receipt.as_ptr(), cbs::build_verify_payment_req(cmd_handle));
+
+        let res = PaymentsService::consume_result(err);
+
+        trace!("build_verify_payment_req <<< result: {:?}", res);
+
+        res
+    }
+
+    pub fn parse_verify_payment_response(&self, cmd_handle: i32, type_: &str, response: &str) -> Result<(), PaymentsError> {
+        trace!("parse_verify_payment_response >>> type_: {:?}, response: {:?}", type_, response);
+        let parse_verify_payment_response: ParseVerifyPaymentResponseCB = self.methods.borrow().get(type_)
+            .ok_or(PaymentsError::UnknownType(format!("Unknown payment method {}", type_)))?.parse_verify_payment_response
+            .ok_or(PaymentsError::OperationNotSupported(format!("Payment method {} doesn't support payment verification", type_)))?;
+
+        let response = CString::new(response)?;
+
+        let err = parse_verify_payment_response(cmd_handle, response.as_ptr(), cbs::parse_verify_payment_response(cmd_handle));
+
+        let res = PaymentsService::consume_result(err);
+
+        trace!("parse_verify_payment_response <<< result: {:?}", res);
+
+        res
+    }
+
+    pub fn sign_with_address(&self, cmd_handle: i32, type_: &str, wallet_handle: i32, address: &str, message: &[u8]) -> Result<(), PaymentsError> {
+        trace!("sign_with_address >>> type_: {:?}, wallet_handle: {:?}, address: {:?}, message: {:?}", type_, wallet_handle, address, message);
+        let sign_with_address: SignWithAddressCB = self.methods.borrow().get(type_)
+            .ok_or(PaymentsError::UnknownType(format!("Unknown payment method {}", type_)))?.sign_with_address
+            .ok_or(PaymentsError::OperationNotSupported(format!("Payment method {} doesn't support signing with address", type_)))?;
+
+        let address = CString::new(address)?;
+
+        let err = sign_with_address(cmd_handle, wallet_handle, address.as_ptr(), message.as_ptr(), message.len() as u32, cbs::sign_with_address(cmd_handle));
+
+        let res = PaymentsService::consume_result(err);
+
+        trace!("sign_with_address <<< result: {:?}", res);
+
+        res
+    }
+
+    pub fn verify_with_address(&self, cmd_handle: i32, type_: &str, address: &str, message: &[u8], signature: &[u8]) -> Result<(), PaymentsError> {
+        trace!("verify_with_address >>> type_: {:?}, address: {:?}, message: {:?}, signature: {:?}", type_, address, message, signature);
+        let verify_with_address: VerifyWithAddressCB = self.methods.borrow().get(type_)
+            .ok_or(PaymentsError::UnknownType(format!("Unknown payment method {}", type_)))?.verify_with_address
+            .ok_or(PaymentsError::OperationNotSupported(format!("Payment method {} doesn't support signing with address", type_)))?;
+
+        let address = CString::new(address)?;
+
+        let err = verify_with_address(cmd_handle, address.as_ptr(), message.as_ptr(), message.len() as u32,
+                                      signature.as_ptr(), signature.len() as u32, cbs::verify_with_address(cmd_handle));
+
+        let res = PaymentsService::consume_result(err);
+
+        trace!("verify_with_address <<< result: {:?}", res);
+
+        res
+    }
+
+    pub fn has_request_fee_handler(&self, type_: &str) -> Result<bool, PaymentsError> {
+        self.methods.borrow().get(type_)
+            .map(|method| method.get_request_fee.is_some())
+            .ok_or(PaymentsError::UnknownType(format!("Unknown payment method {}", type_)))
+    }
+
+    pub fn get_request_fee(&self, cmd_handle: i32, type_: &str, wallet_handle: i32, submitter_did: &str, req: &str, fees: &str) -> Result<(), PaymentsError> {
+        trace!("get_request_fee >>> type_: {:?}, wallet_handle: {:?}, submitter_did: {:?}, req: {:?}, fees: {:?}", type_, wallet_handle, submitter_did, req, fees);
+        let get_request_fee: GetRequestFeeCB = self.methods.borrow().get(type_)
+            .ok_or(PaymentsError::UnknownType(format!("Unknown payment method {}", type_)))?.get_request_fee
+            .ok_or(PaymentsError::OperationNotSupported(format!("Payment method {} doesn't support computing of request fee", type_)))?;
+
+        let submitter_did = CString::new(submitter_did)?;
+        let req = CString::new(req)?;
+        let fees = CString::new(fees)?;
+
+        let err = get_request_fee(cmd_handle, wallet_handle, submitter_did.as_ptr(), req.as_ptr(), fees.as_ptr(), cbs::get_request_fee(cmd_handle));
+
+        let res = PaymentsService::consume_result(err);
+
+        trace!("get_request_fee <<< result: {:?}", res);
+
+        res
+    }
+
+    /// Takes the fee of txn type from fees schedule for payment methods that can't compute fee of request.
+    /// Txn types absent in the schedule are free. Only flat amounts can be applied this way.
+    pub fn get_flat_fee(&self, fees: &str, txn_type: &str) -> Result<u64, PaymentsError> {
+        trace!("get_flat_fee >>> fees: {:?}, txn_type: {:?}", fees, txn_type);
+
+        let fees: HashMap<String, Value> = serde_json::from_str(fees)
+            .map_err(|err| PaymentsError::CommonError(CommonError::InvalidStructure(format!("Cannot deserialize fees: {:?}", err))))?;
+
+        let res = match fees.get(txn_type) {
+            None => Ok(0),
+            Some(fee) => fee.as_u64()
+                .ok_or(PaymentsError::OperationNotSupported(format!("Fee of txn type {} isn't flat amount, payment method must compute fee of request", txn_type)))
+        };
+
+        trace!("get_flat_fee <<< result: {:?}", res);
+
+        res
+    }
+
     pub fn parse_method_from_inputs(&self, inputs: &str) -> Result<String, PaymentsError> {
         trace!("parse_method_from_inputs >>> inputs: {:?}", inputs);
+        let input_groups = self.group_inputs_by_method(inputs)?;
+
+        if input_groups.len() != 1 {
+            let err = PaymentsError::MixedMethodsError(MethodConflict { inputs: input_groups, outputs: BTreeMap::new() });
+            error!("Unable to identify payment method from inputs -- {}", err);
+            return Err(err);
+        }
+
+        let res = Ok(input_groups.into_iter().next().unwrap().0);
+
+        trace!("parse_method_from_inputs <<< result: {:?}", res);
+
+        res
+    }
+
+    pub fn parse_method_from_outputs(&self, outputs: &str) -> Result<String, PaymentsError> {
+        trace!("parse_method_from_outputs >>> outputs: {:?}", outputs);
+        let output_groups = self.group_outputs_by_method(outputs)?;
+
+        if output_groups.len() != 1 {
+            let err = PaymentsError::MixedMethodsError(MethodConflict { inputs: BTreeMap::new(), outputs: PaymentsService::_output_addresses(&output_groups) });
+            error!("Unable to identify payment method from outputs -- {}", err);
+            return Err(err);
+        }
+
+        let res = Ok(output_groups.into_iter().next().unwrap().0);
+
+        trace!("parse_method_from_outputs <<< result: {:?}", res);
+
+        res
+    }
+
+    pub fn parse_method_from_inputs_and_outputs(&self, inputs: &str, outputs: &str) -> Result<String, PaymentsError> {
+        trace!("parse_method_from_inputs_and_outputs >>> inputs: {:?}, outputs: {:?}", inputs, outputs);
+        let input_groups = self.group_inputs_by_method(inputs)?;
+        let output_groups = self.group_outputs_by_method(outputs)?;
+
+        let methods: HashSet<&String> = input_groups.keys().chain(output_groups.keys()).collect();
+        if methods.len() != 1 {
+            let err = PaymentsError::MixedMethodsError(MethodConflict { inputs: input_groups.clone(), outputs: PaymentsService::_output_addresses(&output_groups) });
+            error!("Different payment method in inputs and outputs -- {}", err);
+            return Err(err);
+        }
+
+        let res = Ok(methods.into_iter().next().unwrap().to_string());
+
+        trace!("parse_method_from_inputs_and_outputs <<< result: {:?}", res);
+
+        res
+    }
+
+    /// Splits inputs and outputs of payment into parts of each payment method.
+    /// Returns payment method, inputs json and outputs json for each part ordered by payment method.
+    pub fn split_by_method(&self, inputs: &str, outputs: &str) -> Result<Vec<(String, String, String)>, PaymentsError> {
+        trace!("split_by_method >>> inputs: {:?}, outputs: {:?}", inputs, outputs);
+        let mut input_groups = self.group_inputs_by_method(inputs)?;
+        let mut output_groups = self.group_outputs_by_method(outputs)?;
+
+        let unmatched_inputs: BTreeMap<String, Vec<String>> = input_groups.iter()
+            .filter(|&(method, _)| !output_groups.contains_key(method))
+            .map(|(method, inputs)| (method.clone(), inputs.clone()))
+            .collect();
+        let unmatched_outputs: BTreeMap<String, Vec<Value>> = output_groups.iter()
+            .filter(|&(method, _)| !input_groups.contains_key(method))
+            .map(|(method, outputs)| (method.clone(), outputs.clone()))
+            .collect();
+
+        if !unmatched_inputs.is_empty() || !unmatched_outputs.is_empty() {
+            let err = PaymentsError::MixedMethodsError(MethodConflict { inputs: unmatched_inputs, outputs: PaymentsService::_output_addresses(&unmatched_outputs) });
+            error!("Some payment methods have inputs without outputs or outputs without inputs -- {}", err);
+            return Err(err);
+        }
+
+        let mut parts = Vec::new();
+        for method in input_groups.keys().cloned().collect::<Vec<String>>() {
+            let inputs = input_groups.remove(&method).unwrap();
+            let outputs = output_groups.remove(&method).unwrap();
+
+            let inputs = serde_json::to_string(&inputs)
+                .map_err(|err| PaymentsError::CommonError(CommonError::InvalidState(format!("Cannot serialize inputs: {:?}", err))))?;
+            let outputs = serde_json::to_string(&outputs)
+                .map_err(|err| PaymentsError::CommonError(CommonError::InvalidState(format!("Cannot serialize outputs: {:?}", err))))?;
+
+            parts.push((method, inputs, outputs));
+        }
+
+        let res = Ok(parts);
+
+        trace!("split_by_method <<< result: {:?}", res);
+
+        res
+    }
+
+    pub fn group_inputs_by_method(&self, inputs: &str) -> Result<BTreeMap<String, Vec<String>>, PaymentsError> {
         let inputs: Vec<&str> = serde_json::from_str(inputs).map_err(|_| PaymentsError::CommonError(CommonError::InvalidStructure("Unable to parse inputs".to_string())))?;
-        let inputs_len = inputs.len();
-        if inputs_len == 0 {
+        if inputs.is_empty() {
             error!("No inputs for transaction");
             return Err(PaymentsError::CommonError(CommonError::InvalidStructure("No inputs for transaction".to_string())));
         }
-        let input_set: HashSet<&str> = inputs.into_iter().collect();
-        if inputs_len != input_set.len() {
+
+        let input_set: HashSet<&str> = inputs.iter().cloned().collect();
+        if inputs.len() != input_set.len() {
             error!("Several equal inputs");
             return Err(PaymentsError::CommonError(CommonError::InvalidStructure("Several equal inputs".to_string())));
         }
-        let input_methods: Vec<Option<String>> = input_set.into_iter().map(|s| self._parse_method_from_payment_address(s)).collect();
-        if input_methods.contains(&None) {
-            error!("Some payment addresses are incorrectly formed");
-            return Err(PaymentsError::CommonError(CommonError::InvalidStructure("Some payment addresses are incorrectly formed".to_string())));
-        }
-        let input_methods_set: HashSet<String> = input_methods.into_iter().map(|s| s.unwrap()).collect();
-        if input_methods_set.len() != 1 {
-            error!("Unable to identify payment method from inputs");
-            return Err(PaymentsError::IncompatiblePaymentError("Unable to identify payment method from inputs".to_string()));
+
+        let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut malformed: Vec<&str> = Vec::new();
+        for input in inputs {
+            match self._parse_method_from_payment_address(input) {
+                Some(method) => groups.entry(method).or_insert_with(Vec::new).push(input.to_string()),
+                None => malformed.push(input)
+            }
         }
-        let res = Ok(input_methods_set.into_iter().next().unwrap());
 
-        trace!("parse_method_from_inputs <<< result: {:?}", res);
+        if !malformed.is_empty() {
+            error!("Some payment addresses are incorrectly formed: {:?}", malformed);
+            return Err(PaymentsError::CommonError(CommonError::InvalidStructure(format!("Some payment addresses are incorrectly formed: {:?}", malformed))));
+        }
 
-        res
+        Ok(groups)
     }
 
-    pub fn parse_method_from_outputs(&self, outputs: &str) -> Result<String, PaymentsError> {
-        trace!("parse_method_from_outputs >>> outputs: {:?}", outputs);
-        let outputs: Vec<Output> = serde_json::from_str(outputs).map_err(|_| PaymentsError::CommonError(CommonError::InvalidStructure("Unable to parse outputs".to_string())))?;
-        let outputs_len = outputs.len();
-        if outputs_len == 0 {
+    pub fn group_outputs_by_method(&self, outputs: &str) -> Result<BTreeMap<String, Vec<Value>>, PaymentsError> {
+        let outputs: Vec<Value> = serde_json::from_str(outputs).map_err(|_| PaymentsError::CommonError(CommonError::InvalidStructure("Unable to parse outputs".to_string())))?;
+        if outputs.is_empty() {
             error!("No outputs for transaction");
             return Err(PaymentsError::CommonError(CommonError::InvalidStructure("No outputs for transaction".to_string())));
         }
 
-        let payment_address_set: HashSet<String> = outputs.into_iter().map(|s| s.payment_address).collect();
-        if payment_address_set.len() != outputs_len {
+        let mut payment_addresses: Vec<String> = Vec::new();
+        for output in outputs.iter() {
+            let output: Output = serde_json::from_value(output.clone()).map_err(|_| PaymentsError::CommonError(CommonError::InvalidStructure("Unable to parse outputs".to_string())))?;
+            payment_addresses.push(output.payment_address);
+        }
+
+        let payment_address_set: HashSet<&String> = payment_addresses.iter().collect();
+        if payment_address_set.len() != payment_addresses.len() {
             error!("Several equal payment addresses");
             return Err(PaymentsError::CommonError(CommonError::InvalidStructure("Several equal payment addresses".to_string())));
         }
 
-        let payment_methods: Vec<Option<String>> = payment_address_set.into_iter().map(|s| self._parse_method_from_payment_address(s.as_str())).collect();
-        if payment_methods.contains(&None) {
-            error!("Some payment addresses are incorrectly formed");
-            return Err(PaymentsError::CommonError(CommonError::InvalidStructure("Some payment addresses are incorrectly formed".to_string())));
+        let mut groups: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+        let mut malformed: Vec<&str> = Vec::new();
+        for (payment_address, output) in payment_addresses.iter().zip(outputs.into_iter()) {
+            match self._parse_method_from_payment_address(payment_address) {
+                Some(method) => groups.entry(method).or_insert_with(Vec::new).push(output),
+                None => malformed.push(payment_address)
+            }
         }
 
-        let payment_method_set: HashSet<String> = payment_methods.into_iter().map(|s| s.unwrap()).collect();
-        if payment_method_set.len() != 1 {
-            error!("Unable to identify payment method from outputs");
-            return Err(PaymentsError::IncompatiblePaymentError("Unable to identify payment method from outputs".to_string()));
+        if !malformed.is_empty() {
+            error!("Some payment addresses are incorrectly formed: {:?}", malformed);
+            return Err(PaymentsError::CommonError(CommonError::InvalidStructure(format!("Some payment addresses are incorrectly formed: {:?}", malformed))));
         }
 
-        let res = Ok(payment_method_set.into_iter().next().unwrap());
+        Ok(groups)
+    }
+
+    fn _output_addresses(output_groups: &BTreeMap<String, Vec<Value>>) -> BTreeMap<String, Vec<String>> {
+        output_groups.iter()
+            .map(|(method, outputs)| {
+                let addresses = outputs.iter()
+                    .filter_map(|output| output["paymentAddress"].as_str().map(String::from))
+                    .collect();
+                (method.clone(), addresses)
+            })
+            .collect()
+    }
 
-        trace!("parse_method_from_outputs <<< result: {:?}", res);
+    /// Builds payment history entries from UTXOs the payment method parsed from the response.
+    /// UTXOs of addresses from owned_addresses are considered as received by the wallet, others as sent.
+    /// UTXOs without payment address can't be attributed and are skipped.
+    pub fn build_history_entries(&self, payment_method: &str, context: &PaymentHistoryContext, owned_addresses: &Vec<String>,
+                                 response: &str, utxo_json: &str) -> Result<Vec<PaymentHistoryEntry>, PaymentsError> {
+        trace!("build_history_entries >>> payment_method: {:?}, context: {:?}, owned_addresses: {:?}, response: {:?}, utxo_json: {:?}",
+               payment_method, context, owned_addresses, response, utxo_json);
+
+        let utxos: Vec<UTXOInfo> = serde_json::from_str(utxo_json)
+            .map_err(|err| PaymentsError::CommonError(CommonError::InvalidStructure(format!("Cannot deserialize parsed UTXOs: {:?}", err))))?;
+
+        let seq_no = PaymentsService::_parse_seq_no_from_response(response);
+
+        let mut entries = Vec::new();
+
+        for utxo in utxos {
+            let payment_address = match utxo.payment_address {
+                Some(payment_address) => payment_address,
+                None => {
+                    warn!("Payment method {} doesn't report payment address of UTXO {}, it isn't recorded in history", payment_method, utxo.input);
+                    continue;
+                }
+            };
+
+            let direction = if owned_addresses.contains(&payment_address) { PaymentDirection::Received } else { PaymentDirection::Sent };
+
+            entries.push(PaymentHistoryEntry {
+                payment_method: payment_method.to_string(),
+                direction,
+                input: utxo.input,
+                payment_address,
+                amount: utxo.amount,
+                txn_type: context.txn_type.clone(),
+                seq_no,
+                submitter_did: context.submitter_did.clone(),
+                extra: utxo.extra,
+            });
+        }
+
+        let res = Ok(entries);
+
+        trace!("build_history_entries <<< result: {:?}", res);
+
+        res
+    }
+
+    fn _parse_seq_no_from_response(response: &str) -> Option<u64> {
+        let response: Value = serde_json::from_str(response).ok()?;
+
+        response["result"]["seqNo"].as_u64()
+            .or_else(|| response["result"]["txnMetadata"]["seqNo"].as_u64())
+    }
+
+    pub fn list_methods(&self) -> Vec<PaymentMethodInfo> {
+        let mut methods: Vec<PaymentMethodInfo> = self.methods.borrow().iter()
+            .map(|(name, method)| PaymentMethodInfo {
+                method: name.clone(),
+                capabilities: method.capabilities(),
+            })
+            .collect();
+
+        methods.sort_by(|a, b| a.method.cmp(&b.method));
+        methods
+    }
+
+    pub fn has_method(&self, method_type: &str) -> bool {
+        self.methods.borrow().contains_key(method_type)
+    }
+
+    /// Picks the fee of the payment txn type from the fees schedule.
+    /// Txn types absent in the schedule are free. Without explicit txn type the schedule
+    /// must contain at most one entry, otherwise it's unknown which fee applies.
+    /// Fee rules can't be applied as they depend on the payment request that isn't built yet.
+    pub fn select_fee(&self, fees: &HashMap<String, Value>, txn_type: Option<&str>) -> Result<u64, PaymentsError> {
+        trace!("select_fee >>> fees: {:?}, txn_type: {:?}", fees, txn_type);
+
+        let fee = match txn_type {
+            Some(txn_type) => fees.get(txn_type).map(|fee| (txn_type, fee)),
+            None if fees.len() <= 1 => fees.iter().next().map(|(txn_type, fee)| (txn_type.as_str(), fee)),
+            None => return Err(PaymentsError::CommonError(CommonError::InvalidStructure(
+                "Fees contain several txn types, txnType must be set in config".to_string())))
+        };
+
+        let res = match fee {
+            None => Ok(0),
+            Some((txn_type, fee)) => fee.as_u64()
+                .ok_or(PaymentsError::OperationNotSupported(format!("Fee of txn type {} isn't flat amount, fee rules can't be applied before request is built", txn_type)))
+        };
+
+        trace!("select_fee <<< result: {:?}", res);
+
+        res
+    }
+
+    /// Chooses inputs that cover the target amount according to the strategy.
+    /// Returns chosen inputs and the change that should be returned to the payer.
+    pub fn select_inputs(&self, utxos: &Vec<UTXOInfo>, target: u64, strategy: InputSelectionStrategy) -> Result<(Vec<String>, u64), PaymentsError> {
+        trace!("select_inputs >>> utxos: {:?}, target: {:?}, strategy: {:?}", utxos, target, strategy);
+
+        let mut sorted: Vec<&UTXOInfo> = utxos.iter().collect();
+        sorted.sort_by(|a, b| b.amount.cmp(&a.amount).then(a.input.cmp(&b.input)));
+
+        if strategy == InputSelectionStrategy::SmallestSufficient {
+            if let Some(utxo) = sorted.iter().rev().find(|utxo| utxo.amount >= target) {
+                let res = Ok((vec![utxo.input.clone()], utxo.amount - target));
+                trace!("select_inputs <<< result: {:?}", res);
+                return res;
+            }
+        }
+
+        let mut inputs = Vec::new();
+        let mut total: u64 = 0;
+
+        for utxo in sorted {
+            if total >= target {
+                break;
+            }
+            inputs.push(utxo.input.clone());
+            total = total.saturating_add(utxo.amount);
+        }
+
+        if total < target {
+            error!("Insufficient funds on inputs");
+            return Err(PaymentsError::InsufficientFundsError(format!("Insufficient funds on inputs: {} available, {} required", total, target)));
+        }
+
+        let res = Ok((inputs, total - target));
+
+        trace!("select_inputs <<< result: {:?}", res);
 
         res
     }
@@ -356,9 +750,111 @@ impl PaymentsService {
 pub struct Output {
     #[serde(rename = "paymentAddress")]
     payment_address: String,
-    amount: i32,
+    amount: u64,
     extra: Option<String>
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UTXOInfo {
+    pub input: String,
+    pub amount: u64,
+    pub extra: Option<String>,
+    #[serde(rename = "paymentAddress")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_address: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum InputSelectionStrategy {
+    LargestFirst,
+    SmallestSufficient,
+}
+
+impl Default for InputSelectionStrategy {
+    fn default() -> Self {
+        InputSelectionStrategy::LargestFirst
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentHistoryContext {
+    pub submitter_did: String,
+    pub txn_type: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentDirection {
+    Sent,
+    Received,
+}
+
+impl PaymentDirection {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            PaymentDirection::Sent => "sent",
+            PaymentDirection::Received => "received",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentHistoryEntry {
+    pub payment_method: String,
+    pub direction: PaymentDirection,
+    pub input: String,
+    pub payment_address: String,
+    pub amount: u64,
+    pub txn_type: Option<String>,
+    pub seq_no: Option<u64>,
+    pub submitter_did: String,
+    pub extra: Option<String>,
+}
+
+impl PaymentHistoryEntry {
+    /// UTXO is unique on the ledger of payment method, so parsing the same response again
+    /// produces the same ids and the payment isn't recorded twice
+    pub fn id(&self) -> String {
+        format!("{}:{}", self.payment_method, self.input)
+    }
+
+    /// Tags of wallet record, the same names as entry fields are used so they can be queried
+    pub fn tags(&self) -> HashMap<&'static str, String> {
+        let mut tags = HashMap::new();
+        tags.insert("paymentMethod", self.payment_method.clone());
+        tags.insert("direction", self.direction.as_str().to_string());
+        tags.insert("input", self.input.clone());
+        tags.insert("paymentAddress", self.payment_address.clone());
+        tags.insert("amount", self.amount.to_string());
+        tags.insert("submitterDid", self.submitter_did.clone());
+
+        if let Some(ref txn_type) = self.txn_type {
+            tags.insert("txnType", txn_type.clone());
+        }
+        if let Some(seq_no) = self.seq_no {
+            tags.insert("seqNo", seq_no.to_string());
+        }
+
+        tags
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct PaymentMethodInfo {
+    pub method: String,
+    pub capabilities: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct PreparePaymentConfig {
+    #[serde(default)]
+    pub strategy: InputSelectionStrategy,
+    #[serde(rename = "txnType")]
+    pub txn_type: Option<String>,
+}
 //
 //impl PartialEq for Output {
 //    fn eq(&self, other: &Rhs) -> bool {
@@ -380,6 +876,7 @@ mod cbs {
 
     use std::sync::Mutex;
     use std::ffi::CStr;
+    use std::slice;
 
     use self::libc::c_char;
 
@@ -453,6 +950,61 @@ mod cbs {
         send_ack(cmd_handle, Box::new(move |cmd_handle, result| PaymentsCommand::ParseGetTxnFeesResponseAck(cmd_handle, result)))
     }
 
+    pub fn build_verify_payment_req(cmd_handle: i32) -> Option<extern fn(command_handle: i32,
+                                                                         err: ErrorCode,
+                                                                         c_str: *const c_char) -> ErrorCode> {
+        send_ack(cmd_handle, Box::new(move |cmd_handle, result| PaymentsCommand::BuildVerifyPaymentReqAck(cmd_handle, result)))
+    }
+
+    pub fn parse_verify_payment_response(cmd_handle: i32) -> Option<extern fn(command_handle: i32,
+                                                                              err: ErrorCode,
+                                                                              c_str: *const c_char) -> ErrorCode> {
+        send_ack(cmd_handle, Box::new(move |cmd_handle, result| PaymentsCommand::ParseVerifyPaymentResponseAck(cmd_handle, result)))
+    }
+
+    pub fn sign_with_address(cmd_handle: i32) -> Option<extern fn(command_handle: i32,
+                                                                  err: ErrorCode,
+                                                                  signature_raw: *const u8,
+                                                                  signature_len: u32) -> ErrorCode> {
+        cbs::_closure_to_cb_byte_array(cmd_handle, Box::new(move |err, signature| -> ErrorCode {
+            let result = if err == ErrorCode::Success {
+                Ok(signature)
+            } else {
+                Err(PaymentsError::PluggedMethodError(err))
+            };
+            CommandExecutor::instance().send(Command::Payments(
+                PaymentsCommand::SignWithAddressAck(cmd_handle, result))).to_error_code()
+        }))
+    }
+
+    pub fn verify_with_address(cmd_handle: i32) -> Option<extern fn(command_handle: i32,
+                                                                    err: ErrorCode,
+                                                                    valid: bool) -> ErrorCode> {
+        cbs::_closure_to_cb_bool(cmd_handle, Box::new(move |err, valid| -> ErrorCode {
+            let result = if err == ErrorCode::Success {
+                Ok(valid)
+            } else {
+                Err(PaymentsError::PluggedMethodError(err))
+            };
+            CommandExecutor::instance().send(Command::Payments(
+                PaymentsCommand::VerifyWithAddressAck(cmd_handle, result))).to_error_code()
+        }))
+    }
+
+    pub fn get_request_fee(cmd_handle: i32) -> Option<extern fn(command_handle: i32,
+                                                                err: ErrorCode,
+                                                                fee: u64) -> ErrorCode> {
+        cbs::_closure_to_cb_u64(cmd_handle, Box::new(move |err, fee| -> ErrorCode {
+            let result = if err == ErrorCode::Success {
+                Ok(fee)
+            } else {
+                Err(PaymentsError::PluggedMethodError(err))
+            };
+            CommandExecutor::instance().send(Command::Payments(
+                PaymentsCommand::GetRequestFeeAck(cmd_handle, result))).to_error_code()
+        }))
+    }
+
     fn send_ack(cmd_handle: i32, builder: Box<Fn(i32, Result<String, PaymentsError>) -> PaymentsCommand + Send>) -> Option<extern fn(command_handle: i32,
                                                                                                                                      err: ErrorCode,
                                                                                                                                      c_str: *const c_char) -> ErrorCode> {
@@ -487,4 +1039,318 @@ mod cbs {
 
         Some(_callback)
     }
-}
\ No newline at end of file
+
+    pub fn _closure_to_cb_byte_array(command_handle: i32, closure: Box<FnMut(ErrorCode, Vec<u8>) -> ErrorCode + Send>)
+                                     -> Option<extern fn(command_handle: i32,
+                                                         err: ErrorCode,
+                                                         raw: *const u8,
+                                                         len: u32) -> ErrorCode> {
+        lazy_static! {
+            static ref CALLBACKS: Mutex < HashMap < i32, Box < FnMut(ErrorCode, Vec<u8>) -> ErrorCode + Send > >> = Default::default();
+        }
+
+        extern "C" fn _callback(command_handle: i32, err: ErrorCode, raw: *const u8, len: u32) -> ErrorCode {
+            let mut cb = CALLBACKS.lock().unwrap().remove(&command_handle).unwrap();
+            let data = if raw.is_null() { Vec::new() } else { unsafe { slice::from_raw_parts(raw, len as usize).to_vec() } };
+            cb(err, data)
+        }
+
+        let mut callbacks = CALLBACKS.lock().unwrap();
+        callbacks.insert(command_handle, closure);
+
+        Some(_callback)
+    }
+
+    pub fn _closure_to_cb_bool(command_handle: i32, closure: Box<FnMut(ErrorCode, bool) -> ErrorCode + Send>)
+                               -> Option<extern fn(command_handle: i32,
+                                                   err: ErrorCode,
+                                                   valid: bool) -> ErrorCode> {
+        lazy_static! {
+            static ref CALLBACKS: Mutex < HashMap < i32, Box < FnMut(ErrorCode, bool) -> ErrorCode + Send > >> = Default::default();
+        }
+
+        extern "C" fn _callback(command_handle: i32, err: ErrorCode, valid: bool) -> ErrorCode {
+            let mut cb = CALLBACKS.lock().unwrap().remove(&command_handle).unwrap();
+            cb(err, valid)
+        }
+
+        let mut callbacks = CALLBACKS.lock().unwrap();
+        callbacks.insert(command_handle, closure);
+
+        Some(_callback)
+    }
+
+    pub fn _closure_to_cb_u64(command_handle: i32, closure: Box<FnMut(ErrorCode, u64) -> ErrorCode + Send>)
+                              -> Option<extern fn(command_handle: i32,
+                                                  err: ErrorCode,
+                                                  value: u64) -> ErrorCode> {
+        lazy_static! {
+            static ref CALLBACKS: Mutex < HashMap < i32, Box < FnMut(ErrorCode, u64) -> ErrorCode + Send > >> = Default::default();
+        }
+
+        extern "C" fn _callback(command_handle: i32, err: ErrorCode, value: u64) -> ErrorCode {
+            let mut cb = CALLBACKS.lock().unwrap().remove(&command_handle).unwrap();
+            cb(err, value)
+        }
+
+        let mut callbacks = CALLBACKS.lock().unwrap();
+        callbacks.insert(command_handle, closure);
+
+        Some(_callback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _utxos() -> Vec<UTXOInfo> {
+        vec![("pay:null:1_a", 5), ("pay:null:2_a", 20), ("pay:null:3_b", 10), ("pay:null:4_b", 1)]
+            .into_iter()
+            .map(|(input, amount)| UTXOInfo { input: input.to_string(), amount, extra: None, payment_address: None })
+            .collect()
+    }
+
+    #[test]
+    fn select_inputs_works_for_largest_first() {
+        let payments_service = PaymentsService::new();
+
+        let (inputs, change) = payments_service.select_inputs(&_utxos(), 25, InputSelectionStrategy::LargestFirst).unwrap();
+
+        assert_eq!(inputs, vec!["pay:null:2_a", "pay:null:3_b"]);
+        assert_eq!(change, 5);
+    }
+
+    #[test]
+    fn select_inputs_works_for_smallest_sufficient() {
+        let payments_service = PaymentsService::new();
+
+        let (inputs, change) = payments_service.select_inputs(&_utxos(), 4, InputSelectionStrategy::SmallestSufficient).unwrap();
+
+        assert_eq!(inputs, vec!["pay:null:1_a"]);
+        assert_eq!(change, 1);
+    }
+
+    #[test]
+    fn select_inputs_works_for_smallest_sufficient_without_single_sufficient_input() {
+        let payments_service = PaymentsService::new();
+
+        let (inputs, change) = payments_service.select_inputs(&_utxos(), 33, InputSelectionStrategy::SmallestSufficient).unwrap();
+
+        assert_eq!(inputs, vec!["pay:null:2_a", "pay:null:3_b", "pay:null:1_a"]);
+        assert_eq!(change, 2);
+    }
+
+    #[test]
+    fn select_inputs_works_for_exact_amount() {
+        let payments_service = PaymentsService::new();
+
+        let (inputs, change) = payments_service.select_inputs(&_utxos(), 20, InputSelectionStrategy::LargestFirst).unwrap();
+
+        assert_eq!(inputs, vec!["pay:null:2_a"]);
+        assert_eq!(change, 0);
+    }
+
+    #[test]
+    fn select_inputs_works_for_insufficient_funds() {
+        let payments_service = PaymentsService::new();
+
+        let res = payments_service.select_inputs(&_utxos(), 37, InputSelectionStrategy::LargestFirst);
+
+        assert_match!(Err(PaymentsError::InsufficientFundsError(_)), res);
+    }
+
+    fn _fees() -> HashMap<String, Value> {
+        serde_json::from_str(r#"{"1": 3, "10001": 2}"#).unwrap()
+    }
+
+    #[test]
+    fn select_fee_works_for_txn_type() {
+        let payments_service = PaymentsService::new();
+
+        assert_eq!(payments_service.select_fee(&_fees(), Some("10001")).unwrap(), 2);
+    }
+
+    #[test]
+    fn select_fee_works_for_txn_type_without_fee() {
+        let payments_service = PaymentsService::new();
+
+        assert_eq!(payments_service.select_fee(&_fees(), Some("101")).unwrap(), 0);
+    }
+
+    #[test]
+    fn select_fee_works_for_single_entry_without_txn_type() {
+        let payments_service = PaymentsService::new();
+
+        let fees: HashMap<String, Value> = serde_json::from_str(r#"{"10001": 2}"#).unwrap();
+
+        assert_eq!(payments_service.select_fee(&fees, None).unwrap(), 2);
+        assert_eq!(payments_service.select_fee(&HashMap::new(), None).unwrap(), 0);
+    }
+
+    #[test]
+    fn select_fee_works_for_several_entries_without_txn_type() {
+        let payments_service = PaymentsService::new();
+
+        let res = payments_service.select_fee(&_fees(), None);
+
+        assert_match!(Err(PaymentsError::CommonError(CommonError::InvalidStructure(_))), res);
+    }
+
+    #[test]
+    fn select_fee_works_for_fee_rules() {
+        let payments_service = PaymentsService::new();
+
+        let fees: HashMap<String, Value> = serde_json::from_str(r#"{"1": 3, "10001": {"amount": 2, "roles": {"TRUSTEE": 0}, "perByte": 1}}"#).unwrap();
+
+        assert_eq!(payments_service.select_fee(&fees, Some("1")).unwrap(), 3);
+        assert_match!(Err(PaymentsError::OperationNotSupported(_)), payments_service.select_fee(&fees, Some("10001")));
+    }
+
+    #[test]
+    fn get_flat_fee_works() {
+        let payments_service = PaymentsService::new();
+
+        assert_eq!(payments_service.get_flat_fee(r#"{"1": 3, "10001": 2}"#, "10001").unwrap(), 2);
+        assert_eq!(payments_service.get_flat_fee(r#"{"1": 3, "10001": 2}"#, "101").unwrap(), 0);
+    }
+
+    #[test]
+    fn get_flat_fee_works_for_fee_rules() {
+        let payments_service = PaymentsService::new();
+
+        let res = payments_service.get_flat_fee(r#"{"1": {"amount": 3, "roles": {"TRUSTEE": 0}}}"#, "1");
+
+        assert_match!(Err(PaymentsError::OperationNotSupported(_)), res);
+    }
+
+    #[test]
+    fn parse_method_from_outputs_works_for_amount_greater_than_i32() {
+        let payments_service = PaymentsService::new();
+
+        let method = payments_service.parse_method_from_outputs(r#"[{"paymentAddress": "pay:null:a", "amount": 5000000000},
+                                                                    {"paymentAddress": "pay:null:b", "amount": 18446744073709551615}]"#).unwrap();
+
+        assert_eq!(method, "null");
+    }
+
+    #[test]
+    fn parse_method_from_outputs_works_for_negative_amount() {
+        let payments_service = PaymentsService::new();
+
+        let res = payments_service.parse_method_from_outputs(r#"[{"paymentAddress": "pay:null:a", "amount": -1}]"#);
+
+        assert_match!(Err(PaymentsError::CommonError(CommonError::InvalidStructure(_))), res);
+    }
+
+    #[test]
+    fn parse_method_from_inputs_and_outputs_works_for_mixed_methods() {
+        let payments_service = PaymentsService::new();
+
+        let res = payments_service.parse_method_from_inputs_and_outputs(r#"["pay:null:1_a", "pay:other:2_b"]"#,
+                                                                        r#"[{"paymentAddress": "pay:null:c", "amount": 1}]"#);
+
+        match res {
+            Err(PaymentsError::MixedMethodsError(conflict)) => {
+                assert_eq!(conflict.inputs.get("null").unwrap(), &vec!["pay:null:1_a".to_string()]);
+                assert_eq!(conflict.inputs.get("other").unwrap(), &vec!["pay:other:2_b".to_string()]);
+                assert_eq!(conflict.outputs.get("null").unwrap(), &vec!["pay:null:c".to_string()]);
+            }
+            res => panic!("Unexpected result: {:?}", res)
+        }
+    }
+
+    #[test]
+    fn split_by_method_works() {
+        let payments_service = PaymentsService::new();
+
+        let parts = payments_service.split_by_method(r#"["pay:other:2_b", "pay:null:1_a"]"#,
+                                                     r#"[{"paymentAddress": "pay:null:c", "amount": 1, "extra": "x"}, {"paymentAddress": "pay:other:d", "amount": 2}]"#).unwrap();
+
+        assert_eq!(parts, vec![
+            ("null".to_string(), r#"["pay:null:1_a"]"#.to_string(), r#"[{"amount":1,"extra":"x","paymentAddress":"pay:null:c"}]"#.to_string()),
+            ("other".to_string(), r#"["pay:other:2_b"]"#.to_string(), r#"[{"amount":2,"paymentAddress":"pay:other:d"}]"#.to_string()),
+        ]);
+    }
+
+    #[test]
+    fn split_by_method_works_for_method_without_outputs() {
+        let payments_service = PaymentsService::new();
+
+        let res = payments_service.split_by_method(r#"["pay:other:2_b", "pay:null:1_a"]"#,
+                                                   r#"[{"paymentAddress": "pay:null:c", "amount": 1}]"#);
+
+        match res {
+            Err(PaymentsError::MixedMethodsError(conflict)) => {
+                assert_eq!(conflict.inputs.len(), 1);
+                assert_eq!(conflict.inputs.get("other").unwrap(), &vec!["pay:other:2_b".to_string()]);
+                assert!(conflict.outputs.is_empty());
+            }
+            res => panic!("Unexpected result: {:?}", res)
+        }
+    }
+
+    fn _history_context() -> PaymentHistoryContext {
+        serde_json::from_str(r#"{"submitterDid": "Th7MpTaRZVRYnPiabds81Y", "txnType": "1"}"#).unwrap()
+    }
+
+    const HISTORY_UTXOS: &str = r#"[
+        {"input": "pay:null:7_a", "amount": 3, "paymentAddress": "pay:null:a"},
+        {"input": "pay:null:7_b", "amount": 5, "extra": "x", "paymentAddress": "pay:null:b"},
+        {"input": "pay:null:7_c", "amount": 2}
+    ]"#;
+
+    #[test]
+    fn build_history_entries_works() {
+        let payments_service = PaymentsService::new();
+
+        let entries = payments_service.build_history_entries("null", &_history_context(), &vec!["pay:null:a".to_string()],
+                                                             r#"{"result":{"seqNo":7}}"#, HISTORY_UTXOS).unwrap();
+
+        let summary: Vec<(PaymentDirection, &str, &str, u64)> = entries.iter()
+            .map(|e| (e.direction, e.input.as_str(), e.payment_address.as_str(), e.amount))
+            .collect();
+
+        assert_eq!(summary, vec![
+            (PaymentDirection::Received, "pay:null:7_a", "pay:null:a", 3),
+            (PaymentDirection::Sent, "pay:null:7_b", "pay:null:b", 5),
+        ]);
+        assert!(entries.iter().all(|e| e.seq_no == Some(7) && e.txn_type == Some("1".to_string())));
+        assert_eq!(entries[1].extra, Some("x".to_string()));
+        assert_eq!(entries[0].id(), "null:pay:null:7_a");
+    }
+
+    #[test]
+    fn build_history_entries_works_for_response_without_seq_no() {
+        let payments_service = PaymentsService::new();
+
+        let entries = payments_service.build_history_entries("null", &_history_context(), &vec![], "{}", HISTORY_UTXOS).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|e| e.seq_no.is_none()));
+    }
+
+    #[test]
+    fn build_history_entries_works_for_invalid_utxos() {
+        let payments_service = PaymentsService::new();
+
+        let res = payments_service.build_history_entries("null", &_history_context(), &vec![], "{}", r#"[{"input": "pay:null:7_a", "amount": -3}]"#);
+
+        assert_match!(Err(PaymentsError::CommonError(CommonError::InvalidStructure(_))), res);
+    }
+
+    #[test]
+    fn payment_history_entry_tags_works() {
+        let payments_service = PaymentsService::new();
+
+        let entries = payments_service.build_history_entries("null", &_history_context(), &vec![], r#"{"result":{"txnMetadata":{"seqNo":8}}}"#, HISTORY_UTXOS).unwrap();
+        let tags = entries[1].tags();
+
+        assert_eq!(tags.get("direction").unwrap(), "sent");
+        assert_eq!(tags.get("input").unwrap(), "pay:null:7_b");
+        assert_eq!(tags.get("paymentAddress").unwrap(), "pay:null:b");
+        assert_eq!(tags.get("amount").unwrap(), "5");
+        assert_eq!(tags.get("seqNo").unwrap(), "8");
+        assert_eq!(tags.get("submitterDid").unwrap(), "Th7MpTaRZVRYnPiabds81Y");
+    }
+}
@@ -55,6 +55,18 @@ pub(super) fn encrypt_tags(tags: &HashMap<String, String>, tag_name_key: &[u8],
     etags
 }
 
+pub(super) fn decrypt_searchable(enc_text: &[u8], key: &[u8], hmac_key: &[u8]) -> Result<Vec<u8>, WalletError> {
+    let data = ChaCha20Poly1305IETF::decrypt(enc_text, key)?;
+
+    // Searchable encryption uses HMAC of plain text as a nonce, so the same cypher text
+    // must be produced again if HMAC matches
+    if ChaCha20Poly1305IETF::encrypt_as_searchable(&data, key, hmac_key) != enc_text {
+        return Err(WalletError::EncryptionError("HMAC doesn't match decrypted data".to_string()));
+    }
+
+    Ok(data)
+}
+
 pub(super) fn decrypt_tags(etags: &Option<Vec<Tag>>, tag_name_key: &[u8], tag_value_key: &[u8]) -> Result<Option<HashMap<String, String>>, WalletError> {
     match etags {
         &None => Ok(None),
@@ -111,6 +123,27 @@ mod tests {
         assert_eq!(tags, u);
     }
 
+    #[test]
+    fn test_decrypt_searchable_works() {
+        let key = ChaCha20Poly1305IETF::create_key();
+        let hmac_key = ChaCha20Poly1305IETF::create_key();
+
+        let c = ChaCha20Poly1305IETF::encrypt_as_searchable("value".as_bytes(), &key, &hmac_key);
+        let u = decrypt_searchable(&c, &key, &hmac_key).unwrap();
+        assert_eq!("value".as_bytes().to_vec(), u);
+    }
+
+    #[test]
+    fn test_decrypt_searchable_works_for_hmac_mismatch() {
+        let key = ChaCha20Poly1305IETF::create_key();
+        let hmac_key = ChaCha20Poly1305IETF::create_key();
+        let other_hmac_key = ChaCha20Poly1305IETF::create_key();
+
+        let c = ChaCha20Poly1305IETF::encrypt_as_searchable("value".as_bytes(), &key, &other_hmac_key);
+        let res = decrypt_searchable(&c, &key, &hmac_key);
+        assert_match!(Err(WalletError::EncryptionError(_)), res);
+    }
+
     #[test]
     fn test_decrypt_tags_works_for_none() {
         let tag_name_key = ChaCha20Poly1305IETF::create_key();
@@ -41,7 +41,7 @@ impl WalletIterator {
                 Some(tags) => Some(serde_json::to_string(&tags)?)
             };
 
-            Ok(Some(WalletRecord::new(name, None, value, tags)))
+            Ok(Some(WalletRecord::new(name, None, value, tags, None)))
         } else { Ok(None) }
     }
 
@@ -28,7 +28,7 @@ use utils::crypto::chacha20poly1305_ietf::ChaCha20Poly1305IETF;
 use self::storage::WalletStorageType;
 use self::storage::default::SQLiteStorageType;
 use self::storage::plugged::PluggedStorageType;
-use self::wallet::{Wallet, Keys, Tags};
+use self::wallet::{Wallet, WalletRuntimeConfig, Keys, Tags};
 use self::indy_crypto::utils::json::{JsonDecodable, JsonEncodable};
 use utils::crypto::pwhash_argon2i13::PwhashArgon2i13;
 
@@ -168,6 +168,34 @@ impl WalletService {
         Ok(())
     }
 
+    pub fn register_wallet_storage_expiry(&self,
+                                          type_: &str,
+                                          set_record_expiry: WalletSetRecordExpiry,
+                                          purge_expired_records: WalletPurgeExpiredRecords) -> Result<(), WalletError> {
+        let mut storage_types = self.storage_types.borrow_mut();
+
+        match storage_types.get_mut(type_) {
+            Some(storage_type) => storage_type.register_expiry_handlers(set_record_expiry, purge_expired_records)?,
+            None => return Err(WalletError::UnknownType(type_.to_string()))
+        };
+
+        Ok(())
+    }
+
+    pub fn register_wallet_storage_versioning(&self,
+                                              type_: &str,
+                                              get_record_version: WalletGetRecordVersion,
+                                              update_record_if_version: WalletUpdateRecordIfVersion) -> Result<(), WalletError> {
+        let mut storage_types = self.storage_types.borrow_mut();
+
+        match storage_types.get_mut(type_) {
+            Some(storage_type) => storage_type.register_versioning_handlers(get_record_version, update_record_if_version)?,
+            None => return Err(WalletError::UnknownType(type_.to_string()))
+        };
+
+        Ok(())
+    }
+
     pub fn create_wallet(&self,
                          pool_name: &str,
                          name: &str,
@@ -275,8 +303,16 @@ impl WalletService {
             Some(storage_type) => storage_type,
         };
 
+        let read_only = match runtime_config {
+            Some(runtime_config) => WalletRuntimeConfig::parse_from_json(runtime_config)
+                .map_err(|err| CommonError::InvalidStructure(format!("Cannot deserialize runtime config: {:?}", err)))?
+                .read_only,
+            None => false
+        };
+
+        // The same wallet can be opened several times only if all handles are read-only
         let mut wallets = self.wallets.borrow_mut();
-        if wallets.values().any(|ref wallet| wallet.get_name() == name) {
+        if wallets.values().any(|ref wallet| wallet.get_name() == name && !(read_only && wallet.is_read_only())) {
             return Err(WalletError::AlreadyOpened(name.to_string()));
         }
 
@@ -285,9 +321,10 @@ impl WalletService {
             .map_err(|err| CommonError::InvalidState(format!("Cannot deserialize Storage Config")))?;
 
         let credentials = WalletCredentials::from_json(credentials, &config.salt)?;
-        let storage = storage_type.open_storage(name,
-                                                Some(&config_json),
-                                                &credentials.storage_credentials)?;
+        let mut storage = storage_type.open_storage(name,
+                                                    Some(&config_json),
+                                                    runtime_config,
+                                                    &credentials.storage_credentials)?;
 
         let key_decryption_result = ChaCha20Poly1305IETF::decrypt(
             &storage.get_storage_metadata()?,
@@ -299,8 +336,14 @@ impl WalletService {
         };
 
         let keys = Keys::new(keys_vector);
+        storage.set_change_log_key(&keys.change_log_key()?)?;
 
-        let wallet = Wallet::new(name, &descriptor.pool_name, storage, keys);
+        // purging is logged, so it can't be done before change log key is set
+        if !read_only {
+            storage.purge_expired()?;
+        }
+
+        let wallet = Wallet::new(name, &descriptor.pool_name, storage, keys, read_only);
         if let Some(ref rekey) = credentials.rekey {
             wallet.rotate_key(&rekey[..])?;
         }
@@ -382,6 +425,20 @@ impl WalletService {
         }
     }
 
+    pub fn update_record_value_if_version(&self, wallet_handle: i32, type_: &str, name: &str, value: &str, expected_version: i64) -> Result<(), WalletError> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.update_if_version(type_, name, Some(value), None, expected_version),
+            None => Err(WalletError::InvalidHandle(wallet_handle.to_string()))
+        }
+    }
+
+    pub fn update_record_expiry(&self, wallet_handle: i32, type_: &str, name: &str, expires_at: Option<i64>) -> Result<(), WalletError> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.update_expiry(type_, name, expires_at),
+            None => Err(WalletError::InvalidHandle(wallet_handle.to_string()))
+        }
+    }
+
     pub fn update_indy_object<T>(&self, wallet_handle: i32, name: &str, object: &T) -> Result<String, WalletError> where T: JsonEncodable, T: NamedType {
         let type_ = T::short_type_name();
         match self.wallets.borrow().get(&wallet_handle) {
@@ -420,6 +477,16 @@ impl WalletService {
         }
     }
 
+    pub fn update_record_tags_if_version(&self, wallet_handle: i32, type_: &str, name: &str, tags_json: &str, expected_version: i64) -> Result<(), WalletError> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => {
+                let tags: Tags = serde_json::from_str(tags_json)?;
+                wallet.update_if_version(type_, name, None, Some(&tags), expected_version)
+            }
+            None => Err(WalletError::InvalidHandle(wallet_handle.to_string()))
+        }
+    }
+
     pub fn update_indy_record_tags<T>(&self, wallet_handle: i32, name: &str, tags_json: &str) -> Result<(), WalletError> where T: NamedType {
         self.update_record_tags(wallet_handle, &self.add_prefix(T::short_type_name()), name, tags_json)
     }
@@ -507,6 +574,13 @@ impl WalletService {
         }
     }
 
+    pub fn is_read_only(&self, wallet_handle: i32) -> Result<bool, WalletError> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => Ok(wallet.is_read_only()),
+            None => Err(WalletError::InvalidHandle(wallet_handle.to_string()))
+        }
+    }
+
     pub fn upsert_indy_object<'a, T>(&self, wallet_handle: i32, name: &str, object: &T) -> Result<(), WalletError>
         where T: JsonEncodable, T: JsonDecodable<'a>, T: NamedType {
         if self.record_exists::<T>(wallet_handle, name)? {
@@ -529,6 +603,27 @@ impl WalletService {
         }
     }
 
+    pub fn get_changes_since(&self, wallet_handle: i32, seq: i64) -> Result<Vec<WalletChange>, WalletError> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.get_changes_since(seq),
+            None => Err(WalletError::InvalidHandle(wallet_handle.to_string()))
+        }
+    }
+
+    pub fn purge_expired(&self, wallet_handle: i32) -> Result<usize, WalletError> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.purge_expired(),
+            None => Err(WalletError::InvalidHandle(wallet_handle.to_string()))
+        }
+    }
+
+    pub fn verify_wallet(&self, wallet_handle: i32, quarantine: bool) -> Result<WalletVerificationReport, WalletError> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.verify(quarantine),
+            None => Err(WalletError::InvalidHandle(wallet_handle.to_string()))
+        }
+    }
+
     pub fn check(&self, handle: i32) -> Result<(), WalletError> {
         match self.wallets.borrow().get(&handle) {
             Some(_) => Ok(()),
@@ -550,7 +645,9 @@ pub struct WalletRecord {
     #[serde(rename = "type")]
     type_: Option<String>,
     value: Option<String>,
-    tags: Option<String>
+    tags: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    version: Option<i64>
 }
 
 impl JsonEncodable for WalletRecord {}
@@ -558,12 +655,13 @@ impl JsonEncodable for WalletRecord {}
 impl<'a> JsonDecodable<'a> for WalletRecord {}
 
 impl WalletRecord {
-    pub fn new(name: String, type_: Option<String>, value: Option<String>, tags: Option<String>) -> WalletRecord {
+    pub fn new(name: String, type_: Option<String>, value: Option<String>, tags: Option<String>, version: Option<i64>) -> WalletRecord {
         WalletRecord {
             name: name,
             type_: type_,
             value: value,
             tags: tags,
+            version: version,
         }
     }
     pub fn get_id(&self) -> &str {
@@ -587,14 +685,109 @@ impl WalletRecord {
     pub fn get_tags(&self) -> Option<&str> {
         self.tags.as_ref().map(String::as_str)
     }
+
+    pub fn get_version(&self) -> Option<i64> {
+        self.version
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletChange {
+    seq: i64,
+    #[serde(rename = "type")]
+    type_: Option<String>,
+    id_hash: Option<String>,
+    operation: String,
+    timestamp: i64
 }
 
+impl JsonEncodable for WalletChange {}
+
+impl WalletChange {
+    pub fn new(seq: i64, type_: Option<String>, id_hash: Option<String>, operation: String, timestamp: i64) -> WalletChange {
+        WalletChange {
+            seq,
+            type_,
+            id_hash,
+            operation,
+            timestamp
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletVerificationReport {
+    checked_count: usize,
+    corrupted: Vec<CorruptedRecord>,
+    quarantined_count: usize
+}
+
+impl JsonEncodable for WalletVerificationReport {}
+
+impl WalletVerificationReport {
+    pub fn new(checked_count: usize, corrupted: Vec<CorruptedRecord>, quarantined_count: usize) -> WalletVerificationReport {
+        WalletVerificationReport {
+            checked_count,
+            corrupted,
+            quarantined_count
+        }
+    }
+
+    pub fn get_checked_count(&self) -> usize {
+        self.checked_count
+    }
+
+    pub fn get_corrupted(&self) -> &[CorruptedRecord] {
+        &self.corrupted
+    }
+
+    pub fn get_quarantined_count(&self) -> usize {
+        self.quarantined_count
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorruptedRecord {
+    #[serde(rename = "type")]
+    type_: Option<String>,
+    id: Option<String>,
+    id_hash: String,
+    errors: Vec<String>
+}
+
+impl CorruptedRecord {
+    pub fn new(type_: Option<String>, id: Option<String>, id_hash: String, errors: Vec<String>) -> CorruptedRecord {
+        CorruptedRecord {
+            type_,
+            id,
+            id_hash,
+            errors
+        }
+    }
+
+    pub fn get_errors(&self) -> &[String] {
+        &self.errors
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct VerifyOptions {
+    pub quarantine: Option<bool>
+}
+
+impl<'a> JsonDecodable<'a> for VerifyOptions {}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RecordOptions {
     pub retrieve_type: Option<bool>,
     pub retrieve_value: Option<bool>,
-    pub retrieve_tags: Option<bool>
+    pub retrieve_tags: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub retrieve_version: Option<bool>
 }
 
 impl JsonEncodable for RecordOptions {}
@@ -606,7 +799,8 @@ impl RecordOptions {
         let options = RecordOptions {
             retrieve_type: Some(false),
             retrieve_value: Some(false),
-            retrieve_tags: Some(false)
+            retrieve_tags: Some(false),
+            retrieve_version: None
         };
 
         options.to_json().unwrap()
@@ -616,7 +810,8 @@ impl RecordOptions {
         let options = RecordOptions {
             retrieve_type: Some(false),
             retrieve_value: Some(true),
-            retrieve_tags: Some(false)
+            retrieve_tags: Some(false),
+            retrieve_version: None
         };
 
         options.to_json().unwrap()
@@ -626,7 +821,8 @@ impl RecordOptions {
         let options = RecordOptions {
             retrieve_type: Some(true),
             retrieve_value: Some(true),
-            retrieve_tags: Some(true)
+            retrieve_tags: Some(true),
+            retrieve_version: None
         };
 
         options.to_json().unwrap()
@@ -920,6 +1116,39 @@ mod tests {
         let res = wallet_service.open_wallet("test_wallet", None, "{}");
         assert_match!(Err(WalletError::InputError(_)), res);
     }
+
+    #[test]
+    fn wallet_service_open_wallet_works_for_read_only_twice() {
+        _cleanup();
+
+        let wallet_service = WalletService::new();
+        wallet_service.create_wallet("pool1", "test_wallet", None, None, &_credentials()).unwrap();
+        wallet_service.open_wallet("test_wallet", Some(r#"{"read_only": true}"#), &_credentials()).unwrap();
+        wallet_service.open_wallet("test_wallet", Some(r#"{"read_only": true}"#), &_credentials()).unwrap();
+    }
+
+    #[test]
+    fn wallet_service_open_wallet_returns_error_for_read_only_and_read_write() {
+        _cleanup();
+
+        let wallet_service = WalletService::new();
+        wallet_service.create_wallet("pool1", "test_wallet", None, None, &_credentials()).unwrap();
+        wallet_service.open_wallet("test_wallet", None, &_credentials()).unwrap();
+        let res = wallet_service.open_wallet("test_wallet", Some(r#"{"read_only": true}"#), &_credentials());
+        assert_match!(Err(WalletError::AlreadyOpened(_)), res);
+    }
+
+    #[test]
+    fn wallet_service_add_record_returns_error_for_read_only() {
+        _cleanup();
+
+        let wallet_service = WalletService::new();
+        wallet_service.create_wallet("pool1", "test_wallet", None, None, &_credentials()).unwrap();
+        let wallet_handle = wallet_service.open_wallet("test_wallet", Some(r#"{"read_only": true}"#), &_credentials()).unwrap();
+
+        let res = wallet_service.add_record(wallet_handle, "type", "key1", "value1", "{}");
+        assert_match!(Err(WalletError::AccessFailed(_)), res);
+    }
     //
     //    //    #[test]
     //    //    fn wallet_service_open_works_for_plugged() {
@@ -1,22 +1,28 @@
 extern crate owning_ref;
+extern crate time;
 
 mod query;
 mod transaction;
 
 use std;
 
+use libsqlite3_sys;
 use rusqlite;
 use serde_json;
 
 use self::owning_ref::OwningHandle;
 use std::rc::Rc;
+use std::time::Duration;
 
+use api::wallet::{WalletSetRecordExpiry, WalletPurgeExpiredRecords, WalletGetRecordVersion, WalletUpdateRecordIfVersion};
 use utils::environment::EnvironmentUtils;
 use errors::wallet::WalletStorageError;
 use errors::common::CommonError;
 use services::wallet::language;
+use services::wallet::wallet::WalletRuntimeConfig;
+use utils::crypto::chacha20poly1305_ietf::ChaCha20Poly1305IETF;
 
-use super::{StorageIterator, WalletStorageType, WalletStorage, StorageEntity, EncryptedValue, Tag, TagName, FetchOptions};
+use super::{StorageIterator, WalletStorageType, WalletStorage, StorageEntity, StorageChange, EncryptedValue, Tag, TagName, FetchOptions};
 use super::super::SearchOptions;
 
 const _SQLITE_DB: &str = "sqlite.db";
@@ -44,6 +50,7 @@ const _CREATE_SCHEMA: &str = "
         name NOT NULL,
         value NOT NULL,
         key NOT NULL,
+        version INTEGER NOT NULL DEFAULT 1,
         PRIMARY KEY(id)
     );
 
@@ -85,6 +92,58 @@ const _CREATE_SCHEMA: &str = "
 
     END TRANSACTION;
 ";
+const _DEFAULT_BUSY_TIMEOUT_MS: u64 = 5000;
+const _CREATE_EXPIRY_SCHEMA: &str = "
+    /*** Item Expiry Table ***/
+
+    CREATE TABLE IF NOT EXISTS item_expiry(
+        item_id INTEGER NOT NULL,
+        expires_at INTEGER NOT NULL,
+        PRIMARY KEY(item_id)
+    );
+
+    CREATE INDEX IF NOT EXISTS ix_item_expiry_expires_at ON item_expiry(expires_at);
+";
+const _CREATE_QUARANTINE_SCHEMA: &str = "
+    /*** Quarantine Tables ***/
+
+    CREATE TABLE IF NOT EXISTS quarantined_items(
+        id INTEGER NOT NULL,
+        type NOT NULL,
+        name NOT NULL,
+        value NOT NULL,
+        key NOT NULL,
+        quarantined_at INTEGER NOT NULL,
+        PRIMARY KEY(id)
+    );
+
+    CREATE TABLE IF NOT EXISTS quarantined_tags(
+        item_id INTEGER NOT NULL,
+        name NOT NULL,
+        value NOT NULL,
+        plaintext INTEGER NOT NULL
+    );
+
+    CREATE INDEX IF NOT EXISTS ix_quarantined_tags_item_id ON quarantined_tags(item_id);
+";
+const _CREATE_CHANGE_LOG_SCHEMA: &str = "
+    /*** Change Log Tables ***/
+
+    CREATE TABLE IF NOT EXISTS change_log(
+        seq INTEGER PRIMARY KEY AUTOINCREMENT,
+        type,
+        name,
+        data NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS change_log_state(
+        id INTEGER NOT NULL,
+        enabled INTEGER NOT NULL,
+        PRIMARY KEY(id)
+    );
+";
+const _CHANGE_LOG_ENABLED: &str = "log_enabled";
+const _CHANGE_LOG_DISABLED: &str = "log_disabled";
 
 
 #[derive(Debug)]
@@ -215,6 +274,9 @@ impl StorageIterator for SQLiteStorageIterator {
 #[derive(Debug)]
 struct SQLiteStorage {
     conn: Rc<rusqlite::Connection>,
+    change_log: bool,
+    change_log_key: Option<Vec<u8>>,
+    read_only: bool,
 }
 
 pub struct SQLiteStorageType {}
@@ -230,6 +292,43 @@ impl SQLiteStorageType {
         path.push(_SQLITE_DB );
         path
     }
+
+    fn upgrade_schema(conn: &rusqlite::Connection) -> Result<(), WalletStorageError> {
+        // Wallets created before records expiry was introduced don't have expiry table yet
+        conn.execute_batch(_CREATE_EXPIRY_SCHEMA)?;
+
+        // Wallets created before integrity verification was introduced don't have quarantine tables yet
+        conn.execute_batch(_CREATE_QUARANTINE_SCHEMA)?;
+
+        // Wallets created before records versioning was introduced don't have version column yet
+        if !SQLiteStorageType::has_version_column(conn)? {
+            conn.execute_batch("ALTER TABLE items ADD COLUMN version INTEGER NOT NULL DEFAULT 1;")?;
+        }
+
+        Ok(())
+    }
+
+    fn is_schema_upgraded(conn: &rusqlite::Connection) -> Result<bool, WalletStorageError> {
+        let tables: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name IN ('item_expiry', 'quarantined_items', 'quarantined_tags')",
+            &[],
+            |row| row.get(0))?;
+
+        Ok(tables == 3 && SQLiteStorageType::has_version_column(conn)?)
+    }
+
+    fn has_version_column(conn: &rusqlite::Connection) -> Result<bool, WalletStorageError> {
+        let mut stmt = conn.prepare("PRAGMA table_info(items)")?;
+        let mut rows = stmt.query(&[])?;
+
+        let mut has_version = false;
+        while let Some(row) = rows.next() {
+            let row = row?;
+            let column: String = row.get(1);
+            has_version = has_version || column == "version";
+        }
+        Ok(has_version)
+    }
 }
 
 
@@ -264,50 +363,15 @@ impl WalletStorage for SQLiteStorage {
     ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
     ///
     fn get(&self, type_: &Vec<u8>, name: &Vec<u8>, options: &str) -> Result<StorageEntity, WalletStorageError> {
-        let options: FetchOptions = if options == "{}" {
-            FetchOptions::default()
-        } else {
-            serde_json::from_str(options)?
-        };
-        let res: Result<(i64, Vec<u8>, Vec<u8>), rusqlite::Error> = self.conn.query_row(
-            "SELECT id, value, key FROM items where type = ?1 AND name = ?2",
-            &[type_, name],
-            |row| {
-                (row.get(0), row.get(1), row.get(2))
-            }
-        );
-        let item = match res {
-            Ok(entity) => entity,
-            Err(rusqlite::Error::QueryReturnedNoRows) => return Err(WalletStorageError::ItemNotFound),
-            Err(err) => return Err(WalletStorageError::from(err))
-        };
-        let value = if options.retrieve_value
-            { Some(EncryptedValue::new(item.1, item.2)) } else { None };
-        let type_ = if options.retrieve_type { Some(type_.clone()) } else { None };
-        let tags = if options.retrieve_tags {
-            let mut tags = Vec::new();
-
-            // get all encrypted.
-            let mut stmt = self.conn.prepare_cached("SELECT name, value FROM tags_encrypted WHERE item_id = ?1")?;
-            let mut rows = stmt.query(&[&item.0])?;
-
-            while let Some(row) = rows.next() {
-                let row = row?;
-                tags.push(Tag::Encrypted(row.get(0), row.get(1)));
-            }
-
-            // get all plain
-            let mut stmt = self.conn.prepare_cached("SELECT name, value FROM tags_plaintext WHERE item_id = ?1")?;
-            let mut rows = stmt.query(&[&item.0])?;
-
-            while let Some(row) = rows.next() {
-                let row = row?;
-                tags.push(Tag::PlainText(row.get(0), row.get(1)));
-            }
-            Some(tags)
-        } else { None };
+        self._get(type_, name, options).map(|(entity, _)| entity)
+    }
 
-         Ok(StorageEntity::new(name.clone(), value, type_, tags))
+    ///
+    /// The same as `get`, but also returns the version of the item
+    /// read by the same statement as the value.
+    ///
+    fn get_versioned(&self, type_: &Vec<u8>, name: &Vec<u8>, options: &str) -> Result<(StorageEntity, i64), WalletStorageError> {
+        self._get(type_, name, options)
     }
 
     ///
@@ -341,11 +405,17 @@ impl WalletStorage for SQLiteStorage {
     ///
     fn add(&self, type_: &Vec<u8>, name: &Vec<u8>, value: &EncryptedValue, tags: &[Tag]) -> Result<(), WalletStorageError> {
         let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+
+        // expired item that isn't purged yet shouldn't prevent adding new one with the same name
+        self._purge_expired(&tx, " AND i.type = ?1 AND i.name = ?2", &[type_, name])?;
+
         let res = tx.prepare_cached("INSERT INTO items (type, name, value, key) VALUES (?1, ?2, ?3, ?4)")?
                             .insert(&[type_, name, &value.data, &value.key]);
 
         let id = match res {
             Ok(entity) => entity,
+            Err(rusqlite::Error::SqliteFailure(libsqlite3_sys::Error { code: libsqlite3_sys::ErrorCode::DatabaseBusy, .. }, _)) => return Err(WalletStorageError::Busy),
+            Err(rusqlite::Error::SqliteFailure(libsqlite3_sys::Error { code: libsqlite3_sys::ErrorCode::ReadOnly, .. }, _)) => return Err(WalletStorageError::IOError("Wallet storage is opened in read-only mode".to_string())),
             Err(rusqlite::Error::SqliteFailure(_, _)) => return Err(WalletStorageError::ItemAlreadyExists),
             Err(err) => return Err(WalletStorageError::from(err))
         };
@@ -362,21 +432,31 @@ impl WalletStorage for SQLiteStorage {
             }
         }
 
+        self._log_change(&tx, type_, name, "add")?;
+
         tx.commit()?;
 
         Ok(())
     }
     
     fn update(&self, type_: &Vec<u8>, name: &Vec<u8>, value: &EncryptedValue) -> Result<(), WalletStorageError> {
-        let res = self.conn.prepare_cached("UPDATE items SET value = ?1, key = ?2 WHERE type = ?3 AND name = ?4")?
+        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+
+        let res = tx.prepare_cached("UPDATE items SET value = ?1, key = ?2, version = version + 1 WHERE type = ?3 AND name = ?4")?
             .execute(&[&value.data, &value.key, type_, name]);
 
         match res {
-            Ok(1) => Ok(()),
-            Ok(0) => Err(WalletStorageError::ItemNotFound),
-            Ok(count) => Err(WalletStorageError::CommonError(CommonError::InvalidState(format!("SQLite returned update row count: {}", count)))),
-            Err(err) => Err(WalletStorageError::from(err)),
-        }
+            Ok(1) => {}
+            Ok(0) => return Err(WalletStorageError::ItemNotFound),
+            Ok(count) => return Err(WalletStorageError::CommonError(CommonError::InvalidState(format!("SQLite returned update row count: {}", count)))),
+            Err(err) => return Err(WalletStorageError::from(err)),
+        };
+
+        self._log_change(&tx, type_, name, "update")?;
+
+        tx.commit()?;
+
+        Ok(())
     }
 
     fn add_tags(&self, type_: &Vec<u8>, name: &Vec<u8>, tags: &[Tag]) -> Result<(), WalletStorageError> {
@@ -402,6 +482,11 @@ impl WalletStorage for SQLiteStorage {
                 };
             }
         }
+
+        tx.execute("UPDATE items SET version = version + 1 WHERE id = ?1", &[&item_id])?;
+
+        self._log_change(&tx, type_, name, "add_tags")?;
+
         tx.commit()?;
 
         Ok(())
@@ -419,20 +504,12 @@ impl WalletStorage for SQLiteStorage {
             Ok(id) => id
         };
 
-        tx.execute("DELETE FROM tags_encrypted WHERE item_id = ?1", &[&item_id])?;
-        tx.execute("DELETE FROM tags_plaintext WHERE item_id = ?1", &[&item_id])?;
+        self._replace_tags(&tx, item_id, tags)?;
 
-        {
-            let mut enc_tag_insert_stmt = tx.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-            let mut plain_tag_insert_stmt = tx.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+        tx.execute("UPDATE items SET version = version + 1 WHERE id = ?1", &[&item_id])?;
+
+        self._log_change(&tx, type_, name, "update_tags")?;
 
-            for tag in tags {
-                match tag {
-                    &Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(&[&item_id, tag_name, tag_data])?,
-                    &Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(&[&item_id, tag_name, tag_data])?
-                };
-            }
-        }
         tx.commit()?;
 
         Ok(())
@@ -460,6 +537,11 @@ impl WalletStorage for SQLiteStorage {
                 };
             }
         }
+
+        tx.execute("UPDATE items SET version = version + 1 WHERE id = ?1", &[&item_id])?;
+
+        self._log_change(&tx, type_, name, "delete_tags")?;
+
         tx.commit()?;
 
         Ok(())
@@ -492,15 +574,26 @@ impl WalletStorage for SQLiteStorage {
     ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
     ///
     fn delete(&self, type_: &Vec<u8>, name: &Vec<u8>) -> Result<(), WalletStorageError> {
-        let row_count = self.conn.execute(
+        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+
+        tx.execute(
+            "DELETE FROM item_expiry WHERE item_id IN (SELECT id FROM items WHERE type = ?1 AND name = ?2)",
+            &[type_, name]
+        )?;
+
+        let row_count = tx.execute(
             "DELETE FROM items where type = ?1 AND name = ?2",
             &[type_, name]
         )?;
-        if row_count == 1 {
-            Ok(())
-        } else {
-            Err(WalletStorageError::ItemNotFound)
+        if row_count != 1 {
+            return Err(WalletStorageError::ItemNotFound);
         }
+
+        self._log_change(&tx, type_, name, "delete")?;
+
+        tx.commit()?;
+
+        Ok(())
     }
 
     fn get_storage_metadata(&self) -> Result<Vec<u8>, WalletStorageError> {
@@ -580,12 +673,361 @@ impl WalletStorage for SQLiteStorage {
         }
     }
 
+    ///
+    /// Returns change log entries with sequence number greater than `seq`
+    /// ordered by sequence number. Entries are returned even if the storage is opened
+    /// without change log: periods without logging are delimited by "log_disabled"
+    /// and "log_enabled" marker entries.
+    ///
+    /// # Errors
+    ///
+    ///  * `CommonError::InvalidState` - change log has never been enabled for the wallet
+    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///
+    fn get_changes_since(&self, seq: i64) -> Result<Vec<StorageChange>, WalletStorageError> {
+        if self._change_log_state()?.is_none() {
+            return Err(WalletStorageError::CommonError(
+                CommonError::InvalidState("Change log has never been enabled for the wallet".to_string())));
+        }
+
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT seq, type, name, data FROM change_log WHERE seq > ?1 ORDER BY seq")?;
+        let mut rows = stmt.query(&[&seq])?;
+
+        let mut changes = Vec::new();
+        while let Some(row) = rows.next() {
+            let row = row?;
+            changes.push(StorageChange {
+                seq: row.get(0),
+                type_: row.get(1),
+                name: row.get(2),
+                data: row.get(3),
+            });
+        }
+
+        Ok(changes)
+    }
+
+    ///
+    /// Sets the key change log entries are encrypted with. Records "log_enabled" or
+    /// "log_disabled" marker if logging state differs from the one of the previous opening,
+    /// so consumers of the log can detect periods when changes weren't recorded.
+    ///
+    fn set_change_log_key(&mut self, key: &[u8]) -> Result<(), WalletStorageError> {
+        self.change_log_key = Some(key.to_vec());
+
+        if self.read_only {
+            return Ok(());
+        }
+
+        let marker = match (self.change_log, self._change_log_state()?) {
+            (true, Some(true)) | (false, Some(false)) | (false, None) => return Ok(()),
+            (true, _) => _CHANGE_LOG_ENABLED,
+            (false, Some(true)) => _CHANGE_LOG_DISABLED,
+        };
+
+        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+        self._append_change(&tx, None, None, marker)?;
+        tx.execute("INSERT OR REPLACE INTO change_log_state (id, enabled) VALUES (1, ?1)", &[&self.change_log])?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Sets or removes (if `expires_at` is None) the expiry time of the item.
+    /// `expires_at` is a unix timestamp in seconds.
+    ///
+    /// # Errors
+    ///
+    ///  * `WalletStorageError::ItemNotFound` - Item is not found in database or already expired
+    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///
+    fn set_expiry(&self, type_: &Vec<u8>, name: &Vec<u8>, expires_at: Option<i64>) -> Result<(), WalletStorageError> {
+        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+
+        let res: Result<i64, rusqlite::Error> = tx.query_row(
+            &format!("SELECT id FROM items as i WHERE type = ?1 AND name = ?2 AND {}", query::NOT_EXPIRED_CLAUSE),
+            &[type_, name],
+            |row| row.get(0)
+        );
+
+        let item_id = match res {
+            Ok(item_id) => item_id,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Err(WalletStorageError::ItemNotFound),
+            Err(err) => return Err(WalletStorageError::from(err))
+        };
+
+        match expires_at {
+            Some(expires_at) => tx.execute("INSERT OR REPLACE INTO item_expiry (item_id, expires_at) VALUES (?1, ?2)", &[&item_id, &expires_at])?,
+            None => tx.execute("DELETE FROM item_expiry WHERE item_id = ?1", &[&item_id])?
+        };
+
+        self._log_change(&tx, type_, name, "update_expiry")?;
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Deletes all items with expiry time in the past.
+    /// Returns the number of deleted items.
+    ///
+    fn purge_expired(&self) -> Result<usize, WalletStorageError> {
+        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+        let count = self._purge_expired(&tx, "", &[])?;
+        tx.commit()?;
+        Ok(count)
+    }
+
+    ///
+    /// Returns the current version of the item.
+    /// Version starts from 1 and is incremented by every change of item value or tags.
+    ///
+    /// # Errors
+    ///
+    ///  * `WalletStorageError::ItemNotFound` - Item is not found in database or expired
+    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///
+    fn get_version(&self, type_: &Vec<u8>, name: &Vec<u8>) -> Result<i64, WalletStorageError> {
+        let res: Result<i64, rusqlite::Error> = self.conn.query_row(
+            &format!("SELECT version FROM items as i WHERE type = ?1 AND name = ?2 AND {}", query::NOT_EXPIRED_CLAUSE),
+            &[type_, name],
+            |row| row.get(0)
+        );
+
+        match res {
+            Ok(version) => Ok(version),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Err(WalletStorageError::ItemNotFound),
+            Err(err) => Err(WalletStorageError::from(err))
+        }
+    }
+
+    ///
+    /// Replaces value and/or tags of the item only if its current version
+    /// is equal to `expected_version`.
+    ///
+    /// # Errors
+    ///
+    ///  * `WalletStorageError::ItemNotFound` - Item is not found in database or expired
+    ///  * `WalletStorageError::Conflict` - Item version differs from expected one
+    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///
+    fn update_if_version(&self, type_: &Vec<u8>, name: &Vec<u8>, value: Option<&EncryptedValue>, tags: Option<&[Tag]>, expected_version: i64) -> Result<(), WalletStorageError> {
+        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Immediate)?;
+
+        let res: Result<(i64, i64), rusqlite::Error> = tx.query_row(
+            &format!("SELECT id, version FROM items as i WHERE type = ?1 AND name = ?2 AND {}", query::NOT_EXPIRED_CLAUSE),
+            &[type_, name],
+            |row| (row.get(0), row.get(1))
+        );
+
+        let (item_id, version) = match res {
+            Ok(item) => item,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Err(WalletStorageError::ItemNotFound),
+            Err(err) => return Err(WalletStorageError::from(err))
+        };
+
+        if version != expected_version {
+            return Err(WalletStorageError::Conflict);
+        }
+
+        if let Some(value) = value {
+            tx.execute("UPDATE items SET value = ?1, key = ?2 WHERE id = ?3", &[&value.data, &value.key, &item_id])?;
+            self._log_change(&tx, type_, name, "update")?;
+        }
+
+        if let Some(tags) = tags {
+            self._replace_tags(&tx, item_id, tags)?;
+            self._log_change(&tx, type_, name, "update_tags")?;
+        }
+
+        tx.execute("UPDATE items SET version = version + 1 WHERE id = ?1", &[&item_id])?;
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Moves the item with all its tags to quarantine tables, so it isn't visible
+    /// for get and search anymore but still can be inspected or restored manually.
+    ///
+    /// # Errors
+    ///
+    ///  * `WalletStorageError::ItemNotFound` - Item is not found in database
+    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///
+    fn quarantine(&self, type_: &Vec<u8>, name: &Vec<u8>) -> Result<(), WalletStorageError> {
+        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+
+        let res: Result<(i64, Vec<u8>, Vec<u8>), rusqlite::Error> = tx.query_row(
+            "SELECT id, value, key FROM items WHERE type = ?1 AND name = ?2",
+            &[type_, name],
+            |row| (row.get(0), row.get(1), row.get(2))
+        );
+
+        let (item_id, value, key) = match res {
+            Ok(item) => item,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Err(WalletStorageError::ItemNotFound),
+            Err(err) => return Err(WalletStorageError::from(err))
+        };
+
+        tx.execute("INSERT INTO quarantined_items (type, name, value, key, quarantined_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                   &[type_, name, &value, &key, &time::get_time().sec])?;
+        let quarantined_id = tx.last_insert_rowid();
+
+        tx.execute("INSERT INTO quarantined_tags (item_id, name, value, plaintext) SELECT ?1, name, value, 0 FROM tags_encrypted WHERE item_id = ?2",
+                   &[&quarantined_id, &item_id])?;
+        tx.execute("INSERT INTO quarantined_tags (item_id, name, value, plaintext) SELECT ?1, name, value, 1 FROM tags_plaintext WHERE item_id = ?2",
+                   &[&quarantined_id, &item_id])?;
+
+        tx.execute("DELETE FROM tags_encrypted WHERE item_id = ?1", &[&item_id])?;
+        tx.execute("DELETE FROM tags_plaintext WHERE item_id = ?1", &[&item_id])?;
+        tx.execute("DELETE FROM item_expiry WHERE item_id = ?1", &[&item_id])?;
+        tx.execute("DELETE FROM items WHERE id = ?1", &[&item_id])?;
+
+        self._log_change(&tx, type_, name, "quarantine")?;
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
     fn close(&mut self) -> Result<(), WalletStorageError> {
         Ok(())
     }
-}
+}
+
+impl SQLiteStorage {
+    fn _get(&self, type_: &Vec<u8>, name: &Vec<u8>, options: &str) -> Result<(StorageEntity, i64), WalletStorageError> {
+        let options: FetchOptions = if options == "{}" {
+            FetchOptions::default()
+        } else {
+            serde_json::from_str(options)?
+        };
+        let res: Result<(i64, Vec<u8>, Vec<u8>, i64), rusqlite::Error> = self.conn.query_row(
+            &format!("SELECT id, value, key, version FROM items as i where type = ?1 AND name = ?2 AND {}", query::NOT_EXPIRED_CLAUSE),
+            &[type_, name],
+            |row| {
+                (row.get(0), row.get(1), row.get(2), row.get(3))
+            }
+        );
+        let item = match res {
+            Ok(entity) => entity,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Err(WalletStorageError::ItemNotFound),
+            Err(err) => return Err(WalletStorageError::from(err))
+        };
+        let value = if options.retrieve_value
+            { Some(EncryptedValue::new(item.1, item.2)) } else { None };
+        let type_ = if options.retrieve_type { Some(type_.clone()) } else { None };
+        let tags = if options.retrieve_tags {
+            let mut tags = Vec::new();
+
+            // get all encrypted.
+            let mut stmt = self.conn.prepare_cached("SELECT name, value FROM tags_encrypted WHERE item_id = ?1")?;
+            let mut rows = stmt.query(&[&item.0])?;
+
+            while let Some(row) = rows.next() {
+                let row = row?;
+                tags.push(Tag::Encrypted(row.get(0), row.get(1)));
+            }
+
+            // get all plain
+            let mut stmt = self.conn.prepare_cached("SELECT name, value FROM tags_plaintext WHERE item_id = ?1")?;
+            let mut rows = stmt.query(&[&item.0])?;
+
+            while let Some(row) = rows.next() {
+                let row = row?;
+                tags.push(Tag::PlainText(row.get(0), row.get(1)));
+            }
+            Some(tags)
+        } else { None };
+
+        Ok((StorageEntity::new(name.clone(), value, type_, tags), item.3))
+    }
+
+    fn _replace_tags(&self, conn: &rusqlite::Connection, item_id: i64, tags: &[Tag]) -> Result<(), WalletStorageError> {
+        conn.execute("DELETE FROM tags_encrypted WHERE item_id = ?1", &[&item_id])?;
+        conn.execute("DELETE FROM tags_plaintext WHERE item_id = ?1", &[&item_id])?;
+
+        let mut enc_tag_insert_stmt = conn.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+        let mut plain_tag_insert_stmt = conn.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+
+        for tag in tags {
+            match tag {
+                &Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(&[&item_id, tag_name, tag_data])?,
+                &Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(&[&item_id, tag_name, tag_data])?
+            };
+        }
+
+        Ok(())
+    }
+
+    fn _purge_expired(&self, conn: &rusqlite::Connection, filter: &str, args: &[&rusqlite::types::ToSql]) -> Result<usize, WalletStorageError> {
+        let expired: Vec<(i64, Vec<u8>, Vec<u8>)> = {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT i.id, i.type, i.name FROM items as i WHERE NOT ({}){}", query::NOT_EXPIRED_CLAUSE, filter))?;
+            let mut rows = stmt.query(args)?;
+
+            let mut expired = Vec::new();
+            while let Some(row) = rows.next() {
+                let row = row?;
+                expired.push((row.get(0), row.get(1), row.get(2)));
+            }
+            expired
+        };
+
+        for &(ref item_id, ref type_, ref name) in expired.iter() {
+            conn.execute("DELETE FROM tags_encrypted WHERE item_id = ?1", &[item_id])?;
+            conn.execute("DELETE FROM tags_plaintext WHERE item_id = ?1", &[item_id])?;
+            conn.execute("DELETE FROM items WHERE id = ?1", &[item_id])?;
+            conn.execute("DELETE FROM item_expiry WHERE item_id = ?1", &[item_id])?;
+            self._log_change(conn, type_, name, "expire")?;
+        }
+
+        Ok(expired.len())
+    }
+
+    fn _log_change(&self, conn: &rusqlite::Connection, type_: &Vec<u8>, name: &Vec<u8>, operation: &str) -> Result<(), WalletStorageError> {
+        if !self.change_log {
+            return Ok(());
+        }
+
+        self._append_change(conn, Some(type_.clone()), Some(name.clone()), operation)
+    }
+
+    fn _append_change(&self, conn: &rusqlite::Connection, type_: Option<Vec<u8>>, name: Option<Vec<u8>>, operation: &str) -> Result<(), WalletStorageError> {
+        let key = self.change_log_key.as_ref()
+            .ok_or(WalletStorageError::CommonError(CommonError::InvalidState("Change log key isn't set".to_string())))?;
+
+        // operation and time of change are encrypted, record type and name are already encrypted by wallet
+        let data = json!({"operation": operation, "timestamp": time::get_time().sec}).to_string();
+        let data = ChaCha20Poly1305IETF::encrypt_as_not_searchable(data.as_bytes(), key);
+
+        conn.prepare_cached("INSERT INTO change_log (type, name, data) VALUES (?1, ?2, ?3)")?
+            .execute(&[&type_, &name, &data])?;
+
+        Ok(())
+    }
+
+    /// Whether change log was enabled at the last writable opening, None if it has never been enabled.
+    fn _change_log_state(&self) -> Result<Option<bool>, WalletStorageError> {
+        // read-only opening doesn't create change log tables for wallets which have never had them
+        let tables: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'change_log_state'", &[], |row| row.get(0))?;
+        if tables == 0 {
+            return Ok(None);
+        }
+
+        match self.conn.query_row("SELECT enabled FROM change_log_state WHERE id = 1", &[], |row| row.get(0)) {
+            Ok(enabled) => Ok(Some(enabled)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(err) => Err(WalletStorageError::from(err))
+        }
+    }
 
-impl SQLiteStorage {
     fn _prepare_statement(&self, sql: &str) -> Result<
         OwningHandle<Rc<rusqlite::Connection>, Box<rusqlite::Statement<'static>>>,
         WalletStorageError> {
@@ -668,7 +1110,9 @@ impl WalletStorageType for SQLiteStorageType {
 
         let conn = rusqlite::Connection::open(db_file_path.as_path())?;
 
-        match conn.execute_batch(_CREATE_SCHEMA) {
+        match conn.execute_batch(_CREATE_SCHEMA)
+            .and_then(|_| conn.execute_batch(_CREATE_EXPIRY_SCHEMA))
+            .and_then(|_| conn.execute_batch(_CREATE_QUARANTINE_SCHEMA)) {
             Ok(_) => match conn.execute("INSERT OR REPLACE INTO metadata(value) VALUES(?1)", &[metadata]) {
                 Ok(_) => Ok(()),
                 Err(error) => {
@@ -694,7 +1138,7 @@ impl WalletStorageType for SQLiteStorageType {
     ///
     ///  * `name` - name of the SQLite DB file
     ///  * `config` - config containing the location of SQLite DB files
-    ///  * `runtime_config` - #TODO
+    ///  * `runtime_config` - wallet runtime config. `change_log` option enables change log
     ///  * `credentials` - DB credentials
     ///
     /// # Returns
@@ -711,16 +1155,76 @@ impl WalletStorageType for SQLiteStorageType {
     ///  * `WalletStorageError::NotFound` - File with the provided name not found
     ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
     ///
-    fn open_storage(&self, name: &str, config: Option<&str>, credentials: &str) -> Result<Box<WalletStorage>, WalletStorageError> {
+    fn open_storage(&self, name: &str, config: Option<&str>, runtime_config: Option<&str>, credentials: &str) -> Result<Box<WalletStorage>, WalletStorageError> {
         let db_file_path = SQLiteStorageType::create_path(name);
 
         if !db_file_path.exists() {
             return Err(WalletStorageError::NotFound);
         }
 
-        let conn = rusqlite::Connection::open(db_file_path.as_path())?;
+        let runtime_config = match runtime_config {
+            Some(runtime_config) => WalletRuntimeConfig::parse_from_json(runtime_config)
+                .map_err(|_| WalletStorageError::ConfigError)?,
+            None => WalletRuntimeConfig::default()
+        };
+
+        let conn = if runtime_config.read_only {
+            rusqlite::Connection::open_with_flags(db_file_path.as_path(), rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?
+        } else {
+            rusqlite::Connection::open(db_file_path.as_path())?
+        };
+
+        if runtime_config.concurrent_access {
+            // WAL journal lets readers from other processes proceed while one writer is active.
+            // Read-only connection can't switch journal mode, so it relies on the mode set by writers.
+            conn.execute_batch("PRAGMA locking_mode=NORMAL;")?;
+            if !runtime_config.read_only {
+                conn.execute_batch("PRAGMA journal_mode=WAL;")?;
+            }
+        }
+
+        let busy_timeout = match runtime_config.busy_timeout {
+            Some(busy_timeout) => Some(busy_timeout),
+            None if runtime_config.concurrent_access => Some(_DEFAULT_BUSY_TIMEOUT_MS),
+            None => None
+        };
+
+        if let Some(busy_timeout) = busy_timeout {
+            conn.busy_timeout(Duration::from_millis(busy_timeout))?;
+        }
 
-        Ok(Box::new(SQLiteStorage { conn: Rc::new(conn) }))
+        // Change log tables are created even if logging is off to record the gap in the log
+        if !runtime_config.read_only {
+            conn.execute_batch(_CREATE_CHANGE_LOG_SCHEMA)?;
+        }
+
+        let storage = SQLiteStorage {
+            conn: Rc::new(conn),
+            change_log: runtime_config.change_log,
+            change_log_key: None,
+            read_only: runtime_config.read_only,
+        };
+
+        // expired records are purged by wallet after the change log key is set
+        if !runtime_config.read_only {
+            SQLiteStorageType::upgrade_schema(&storage.conn)?;
+        } else if !SQLiteStorageType::is_schema_upgraded(&storage.conn)? {
+            // read-only connection can't add tables and columns the queries rely on
+            return Err(WalletStorageError::CommonError(CommonError::InvalidState(
+                "Wallet created by previous version of libindy must be opened in read-write mode once to upgrade it".to_string())));
+        }
+
+        Ok(Box::new(storage))
+    }
+
+    fn register_expiry_handlers(&mut self, _set_record_expiry: WalletSetRecordExpiry, _purge_expired_records: WalletPurgeExpiredRecords) -> Result<(), WalletStorageError> {
+        // default storage handles records expiry by itself
+        Err(WalletStorageError::ConfigError)
+    }
+
+    fn register_versioning_handlers(&mut self, _get_record_version: WalletGetRecordVersion, _update_record_if_version: WalletUpdateRecordIfVersion) -> Result<(), WalletStorageError> {
+        // default storage handles records versions by itself
+        Err(WalletStorageError::ConfigError)
     }
 }
 
@@ -739,10 +1243,52 @@ mod tests {
         let test_keys = _get_test_keys();
 
         storage_type.create_storage("test_wallet", None, "", &test_keys).unwrap();
-        let storage = storage_type.open_storage("test_wallet", None, "").unwrap();
+        let storage = storage_type.open_storage("test_wallet", None, None, "").unwrap();
+        storage
+    }
+
+
+    const _CHANGE_LOG_KEY: [u8; 32] = [3; 32];
+
+    // Schema of wallets created before records expiry, integrity verification and versioning were introduced
+    const _BASELINE_SCHEMA: &str = "
+        CREATE TABLE metadata (id INTEGER NOT NULL, value NOT NULL, PRIMARY KEY(id));
+        CREATE TABLE items(id INTEGER NOT NULL, type NOT NULL, name NOT NULL, value NOT NULL, key NOT NULL, PRIMARY KEY(id));
+        CREATE UNIQUE INDEX ux_items_type_name ON items(type, name);
+        CREATE TABLE tags_encrypted(name NOT NULL, value NOT NULL, item_id INTEGER NOT NULL, PRIMARY KEY(name, item_id),
+            FOREIGN KEY(item_id) REFERENCES items(id) ON DELETE CASCADE ON UPDATE CASCADE);
+        CREATE TABLE tags_plaintext(name NOT NULL, value NOT NULL, item_id INTEGER NOT NULL, PRIMARY KEY(name, item_id),
+            FOREIGN KEY(item_id) REFERENCES items(id) ON DELETE CASCADE ON UPDATE CASCADE);
+    ";
+
+    fn _create_baseline_storage(name: &str, metadata: &Vec<u8>) {
+        let path = SQLiteStorageType::create_path(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        let conn = rusqlite::Connection::open(path.as_path()).unwrap();
+        conn.execute_batch(_BASELINE_SCHEMA).unwrap();
+        conn.execute("INSERT INTO metadata(value) VALUES(?1)", &[metadata]).unwrap();
+    }
+
+    fn _open_test_storage_with_change_log(change_log: bool) -> Box<WalletStorage> {
+        _prepare_path();
+        let storage_type = SQLiteStorageType::new();
+        let test_keys = _get_test_keys();
+
+        storage_type.create_storage("test_wallet", None, "", &test_keys).unwrap();
+        let mut storage = storage_type.open_storage("test_wallet", None, Some(&format!(r#"{{"change_log": {}}}"#, change_log)), "").unwrap();
+        storage.set_change_log_key(&_CHANGE_LOG_KEY).unwrap();
         storage
     }
 
+    fn _change_operations(changes: &[StorageChange]) -> Vec<String> {
+        changes.iter()
+            .map(|change| {
+                let data = ChaCha20Poly1305IETF::decrypt(&change.data, &_CHANGE_LOG_KEY).unwrap();
+                serde_json::from_slice::<serde_json::Value>(&data).unwrap()["operation"].as_str().unwrap().to_string()
+            })
+            .collect()
+    }
 
     fn _remove_test_file(file: &str) {
         if std::path::Path::new(&file).exists() {
@@ -848,7 +1394,7 @@ mod tests {
 
         storage_type.create_storage("test_wallet", None, &"", &test_metadata).unwrap();
 
-        let storage = storage_type.open_storage("test_wallet", None, &"").unwrap();
+        let storage = storage_type.open_storage("test_wallet", None, None, &"").unwrap();
         let storage_metadata = storage.get_storage_metadata().unwrap();
 
         assert_eq!(test_metadata, storage_metadata);
@@ -881,7 +1427,7 @@ mod tests {
         let test_keys = _get_test_keys();
 
         storage_type.create_storage("test_wallet", None, "", &test_keys).unwrap();
-        storage_type.open_storage("test_wallet", None, "").unwrap();
+        storage_type.open_storage("test_wallet", None, None, "").unwrap();
     }
 
     /** negative tests */
@@ -891,7 +1437,7 @@ mod tests {
         _prepare_path();
         let storage_type = SQLiteStorageType::new();
 
-        let res = storage_type.open_storage("test_wallet", None, "");
+        let res = storage_type.open_storage("test_wallet", None, None, "");
 
         assert_match!(Err(WalletStorageError::NotFound), res);
     }
@@ -909,7 +1455,7 @@ mod tests {
         let keys = test_keys.clone(); // TODO: fix this
 
         storage_type.create_storage("test_wallet", None, "", &test_keys).unwrap();
-        let mut storage = storage_type.open_storage("test_wallet", None, "").unwrap();
+        let mut storage = storage_type.open_storage("test_wallet", None, None, "").unwrap();
         assert_eq!(keys, test_keys);
 
         let type_: Vec<u8> = vec![1, 2, 3];
@@ -937,7 +1483,7 @@ mod tests {
         let keys = test_keys.clone(); // TODO: fix this
 
         storage_type.create_storage("test_wallet", None, "", &test_keys).unwrap();
-        let mut storage = storage_type.open_storage("test_wallet", None, "").unwrap();
+        let mut storage = storage_type.open_storage("test_wallet", None, None, "").unwrap();
         assert_eq!(keys, test_keys);
 
         let type_: Vec<u8> = vec![1, 2, 3];
@@ -973,11 +1519,11 @@ mod tests {
         tags.push(Tag::PlainText(vec![1, 5, 8, 1], "Plain value".to_string()));
 
         {
-            let mut storage = storage_type.open_storage("test_wallet", None, "").unwrap();
+            let mut storage = storage_type.open_storage("test_wallet", None, None, "").unwrap();
             storage.add(&type_, &name, &value, &tags).unwrap();
         }
 
-        let storage = storage_type.open_storage("test_wallet", None, "").unwrap();
+        let storage = storage_type.open_storage("test_wallet", None, None, "").unwrap();
         let entity = storage.get(&type_, &name, r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
         let entity_value = entity.value.unwrap();
 
@@ -992,7 +1538,7 @@ mod tests {
         let storage_type = SQLiteStorageType::new();
         let test_keys = _get_test_keys();
         storage_type.create_storage("test_wallet", None, "", &test_keys).unwrap();
-        let mut storage = storage_type.open_storage("test_wallet", None, "").unwrap();
+        let mut storage = storage_type.open_storage("test_wallet", None, None, "").unwrap();
         let type_: Vec<u8> = vec![1, 2, 3];
         let name: Vec<u8> = vec![4, 5, 6];
         let value = EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]};
@@ -1014,7 +1560,7 @@ mod tests {
         let test_keys = _get_test_keys();
 
         storage_type.create_storage("test_wallet", None, "", &test_keys).unwrap();
-        let mut storage = storage_type.open_storage("test_wallet", None, "").unwrap();
+        let mut storage = storage_type.open_storage("test_wallet", None, None, "").unwrap();
 
         let type_: Vec<u8> = vec![1, 2, 3];
         let name: Vec<u8> = vec![4, 5, 6];
@@ -1045,7 +1591,7 @@ mod tests {
         let test_keys = _get_test_keys();
 
         storage_type.create_storage("test_wallet", None, "", &test_keys).unwrap();
-        let mut storage = storage_type.open_storage("test_wallet", None, "").unwrap();
+        let mut storage = storage_type.open_storage("test_wallet", None, None, "").unwrap();
 
         let type_: Vec<u8> = vec![1, 2, 3];
         let name: Vec<u8> = vec![4, 5, 6];
@@ -1076,7 +1622,7 @@ mod tests {
         let test_keys = _get_test_keys();
 
         storage_type.create_storage("test_wallet", None, "", &test_keys).unwrap();
-        let mut storage = storage_type.open_storage("test_wallet", None, "").unwrap();
+        let mut storage = storage_type.open_storage("test_wallet", None, None, "").unwrap();
 
         let type_: Vec<u8> = vec![1, 2, 3];
         let name: Vec<u8> = vec![4, 5, 6];
@@ -1104,7 +1650,7 @@ mod tests {
         let test_keys = _get_test_keys();
 
         storage_type.create_storage("test_wallet", None, "", &test_keys).unwrap();
-        let mut storage = storage_type.open_storage("test_wallet", None, "").unwrap();
+        let mut storage = storage_type.open_storage("test_wallet", None, None, "").unwrap();
 
         let type_: Vec<u8> = vec![1, 2, 3];
         let name1: Vec<u8> = vec![4, 5, 6];
@@ -1145,7 +1691,7 @@ mod tests {
         let test_keys = _get_test_keys();
 
         storage_type.create_storage("test_wallet", None, "", &test_keys).unwrap();
-        let storage = storage_type.open_storage("test_wallet", None, "").unwrap();
+        let storage = storage_type.open_storage("test_wallet", None, None, "").unwrap();
         let mut storage_iterator = storage.get_all().unwrap();
         let res = storage_iterator.next().unwrap();
 
@@ -1581,4 +2127,372 @@ mod tests {
 
         assert_match!(Err(WalletStorageError::NotFound), res);
     }
+
+    /**
+     * Change log tests
+     */
+
+    #[test]
+    fn sqlite_storage_change_log_works() {
+        let storage = _open_test_storage_with_change_log(true);
+
+        let type_: Vec<u8> = vec![1, 2, 3];
+        let name: Vec<u8> = vec![4, 5, 6];
+        let value = EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]};
+        let new_value = EncryptedValue{data: vec![1, 1, 1], key: vec![2, 2, 2]};
+
+        storage.add(&type_, &name, &value, &[]).unwrap();
+        storage.update(&type_, &name, &new_value).unwrap();
+        storage.delete(&type_, &name).unwrap();
+
+        let changes = storage.get_changes_since(0).unwrap();
+        assert_eq!(vec![_CHANGE_LOG_ENABLED, "add", "update", "delete"], _change_operations(&changes));
+        assert!(changes[0].type_.is_none() && changes[0].name.is_none());
+        assert!(changes[1..].iter().all(|change| change.type_ == Some(type_.clone()) && change.name == Some(name.clone())));
+        assert!(changes[1].seq < changes[2].seq && changes[2].seq < changes[3].seq);
+
+        let changes = storage.get_changes_since(changes[2].seq).unwrap();
+        assert_eq!(vec!["delete"], _change_operations(&changes));
+    }
+
+    #[test]
+    fn sqlite_storage_change_log_encrypts_operations() {
+        let storage = _open_test_storage_with_change_log(true);
+
+        storage.add(&vec![1, 2, 3], &vec![4, 5, 6], &EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]}, &[]).unwrap();
+
+        let changes = storage.get_changes_since(0).unwrap();
+        assert!(changes.iter().all(|change| !String::from_utf8_lossy(&change.data).contains("add") &&
+            !String::from_utf8_lossy(&change.data).contains("timestamp")));
+    }
+
+    #[test]
+    fn sqlite_storage_change_log_skips_failed_operations() {
+        let storage = _open_test_storage_with_change_log(true);
+
+        let res = storage.delete(&vec![1, 2, 3], &vec![4, 5, 6]);
+        assert_match!(Err(WalletStorageError::ItemNotFound), res);
+
+        assert_eq!(vec![_CHANGE_LOG_ENABLED], _change_operations(&storage.get_changes_since(0).unwrap()));
+    }
+
+    #[test]
+    fn sqlite_storage_change_log_records_gaps() {
+        _prepare_path();
+        let storage_type = SQLiteStorageType::new();
+        let test_keys = _get_test_keys();
+        let value = EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]};
+
+        storage_type.create_storage("test_wallet", None, "", &test_keys).unwrap();
+
+        {
+            let mut storage = storage_type.open_storage("test_wallet", None, Some(r#"{"change_log": true}"#), "").unwrap();
+            storage.set_change_log_key(&_CHANGE_LOG_KEY).unwrap();
+            storage.add(&vec![1, 2, 3], &vec![4, 5, 6], &value, &[]).unwrap();
+        }
+
+        {
+            let mut storage = storage_type.open_storage("test_wallet", None, None, "").unwrap();
+            storage.set_change_log_key(&_CHANGE_LOG_KEY).unwrap();
+            storage.add(&vec![1, 2, 3], &vec![4, 5, 7], &value, &[]).unwrap();
+
+            // log is available even if changes aren't logged now
+            assert_eq!(vec![_CHANGE_LOG_ENABLED, "add", _CHANGE_LOG_DISABLED], _change_operations(&storage.get_changes_since(0).unwrap()));
+        }
+
+        let mut storage = storage_type.open_storage("test_wallet", None, Some(r#"{"change_log": true}"#), "").unwrap();
+        storage.set_change_log_key(&_CHANGE_LOG_KEY).unwrap();
+
+        assert_eq!(vec![_CHANGE_LOG_ENABLED, "add", _CHANGE_LOG_DISABLED, _CHANGE_LOG_ENABLED], _change_operations(&storage.get_changes_since(0).unwrap()));
+    }
+
+    #[test]
+    fn sqlite_storage_change_log_returns_error_if_never_enabled() {
+        let storage = _open_test_storage_with_change_log(false);
+
+        let res = storage.get_changes_since(0);
+        assert_match!(Err(WalletStorageError::CommonError(CommonError::InvalidState(_))), res);
+    }
+
+    #[test]
+    fn sqlite_storage_concurrent_access_works() {
+        _prepare_path();
+        let storage_type = SQLiteStorageType::new();
+        let test_keys = _get_test_keys();
+
+        storage_type.create_storage("test_wallet", None, "", &test_keys).unwrap();
+        let writer = storage_type.open_storage("test_wallet", None, Some(r#"{"concurrent_access": true}"#), "").unwrap();
+        let reader = storage_type.open_storage("test_wallet", None, Some(r#"{"concurrent_access": true, "read_only": true}"#), "").unwrap();
+
+        let type_: Vec<u8> = vec![1, 2, 3];
+        let name: Vec<u8> = vec![4, 5, 6];
+        let value = EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]};
+
+        writer.add(&type_, &name, &value, &[]).unwrap();
+
+        let entity = reader.get(&type_, &name, r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##).unwrap();
+        assert_eq!(value, entity.value.unwrap());
+    }
+
+    #[test]
+    fn sqlite_storage_read_only_rejects_writes() {
+        _prepare_path();
+        let storage_type = SQLiteStorageType::new();
+        let test_keys = _get_test_keys();
+
+        storage_type.create_storage("test_wallet", None, "", &test_keys).unwrap();
+        let storage = storage_type.open_storage("test_wallet", None, Some(r#"{"read_only": true}"#), "").unwrap();
+
+        let value = EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]};
+        let res = storage.add(&vec![1, 2, 3], &vec![4, 5, 6], &value, &[]);
+        assert_match!(Err(WalletStorageError::IOError(_)), res);
+    }
+
+    #[test]
+    fn sqlite_storage_read_only_open_works_for_not_upgraded_wallet() {
+        _prepare_path();
+        let storage_type = SQLiteStorageType::new();
+        let test_keys = _get_test_keys();
+
+        _create_baseline_storage("test_wallet", &test_keys);
+
+        let res = storage_type.open_storage("test_wallet", None, Some(r#"{"read_only": true}"#), "");
+        assert_match!(Err(WalletStorageError::CommonError(CommonError::InvalidState(_))), res);
+
+        // read-write opening upgrades the schema
+        {
+            let storage = storage_type.open_storage("test_wallet", None, None, "").unwrap();
+            storage.add(&vec![1, 2, 3], &vec![4, 5, 6], &EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]}, &[]).unwrap();
+        }
+
+        let storage = storage_type.open_storage("test_wallet", None, Some(r#"{"read_only": true}"#), "").unwrap();
+        assert_eq!(1, storage.get_version(&vec![1, 2, 3], &vec![4, 5, 6]).unwrap());
+        storage.get(&vec![1, 2, 3], &vec![4, 5, 6], r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##).unwrap();
+    }
+
+    #[test]
+    fn sqlite_storage_get_skips_expired() {
+        let storage = _create_and_open_test_storage();
+
+        let type_: Vec<u8> = vec![1, 2, 3];
+        let name: Vec<u8> = vec![4, 5, 6];
+        let value = EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]};
+
+        storage.add(&type_, &name, &value, &[]).unwrap();
+        storage.set_expiry(&type_, &name, Some(1)).unwrap();
+
+        let res = storage.get(&type_, &name, r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##);
+        assert_match!(Err(WalletStorageError::ItemNotFound), res);
+    }
+
+    #[test]
+    fn sqlite_storage_get_works_for_not_expired_yet() {
+        let storage = _create_and_open_test_storage();
+
+        let type_: Vec<u8> = vec![1, 2, 3];
+        let name: Vec<u8> = vec![4, 5, 6];
+        let value = EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]};
+
+        storage.add(&type_, &name, &value, &[]).unwrap();
+        storage.set_expiry(&type_, &name, Some(time::get_time().sec + 3600)).unwrap();
+
+        let entity = storage.get(&type_, &name, r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##).unwrap();
+        assert_eq!(value, entity.value.unwrap());
+    }
+
+    #[test]
+    fn sqlite_storage_set_expiry_works_for_removing_expiry() {
+        let storage = _create_and_open_test_storage();
+
+        let type_: Vec<u8> = vec![1, 2, 3];
+        let name: Vec<u8> = vec![4, 5, 6];
+        let value = EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]};
+
+        storage.add(&type_, &name, &value, &[]).unwrap();
+        storage.set_expiry(&type_, &name, Some(time::get_time().sec + 3600)).unwrap();
+        storage.set_expiry(&type_, &name, None).unwrap();
+
+        assert_eq!(0, storage.purge_expired().unwrap());
+        storage.get(&type_, &name, r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##).unwrap();
+    }
+
+    #[test]
+    fn sqlite_storage_set_expiry_returns_error_for_unknown_item() {
+        let storage = _create_and_open_test_storage();
+
+        let res = storage.set_expiry(&vec![1, 2, 3], &vec![4, 5, 6], Some(1));
+        assert_match!(Err(WalletStorageError::ItemNotFound), res);
+    }
+
+    #[test]
+    fn sqlite_storage_purge_expired_works() {
+        let storage = _create_and_open_test_storage();
+
+        let type_: Vec<u8> = vec![1, 2, 3];
+        let name_1: Vec<u8> = vec![4, 5, 6];
+        let name_2: Vec<u8> = vec![4, 5, 7];
+        let value = EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]};
+        let tags = vec![Tag::PlainText(vec![0, 0, 0], "tag_value".to_string())];
+
+        storage.add(&type_, &name_1, &value, &tags).unwrap();
+        storage.add(&type_, &name_2, &value, &tags).unwrap();
+        storage.set_expiry(&type_, &name_1, Some(1)).unwrap();
+
+        assert_eq!(1, storage.purge_expired().unwrap());
+        assert_eq!(0, storage.purge_expired().unwrap());
+
+        storage.get(&type_, &name_2, r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+    }
+
+    #[test]
+    fn sqlite_storage_purge_expired_works_after_reopen() {
+        _prepare_path();
+        let storage_type = SQLiteStorageType::new();
+        let test_keys = _get_test_keys();
+
+        storage_type.create_storage("test_wallet", None, "", &test_keys).unwrap();
+
+        let type_: Vec<u8> = vec![1, 2, 3];
+        let name: Vec<u8> = vec![4, 5, 6];
+        let value = EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]};
+
+        {
+            let storage = storage_type.open_storage("test_wallet", None, None, "").unwrap();
+            storage.add(&type_, &name, &value, &[]).unwrap();
+            storage.set_expiry(&type_, &name, Some(1)).unwrap();
+        }
+
+        // opening doesn't purge expired records as change log key isn't set yet
+        let storage = storage_type.open_storage("test_wallet", None, None, "").unwrap();
+        assert_eq!(1, storage.purge_expired().unwrap());
+        assert_eq!(0, storage.purge_expired().unwrap());
+    }
+
+    #[test]
+    fn sqlite_storage_add_works_for_expired_item_with_same_name() {
+        let storage = _create_and_open_test_storage();
+
+        let type_: Vec<u8> = vec![1, 2, 3];
+        let name: Vec<u8> = vec![4, 5, 6];
+        let value = EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]};
+        let new_value = EncryptedValue{data: vec![1, 1, 1], key: vec![2, 2, 2]};
+
+        storage.add(&type_, &name, &value, &[]).unwrap();
+        storage.set_expiry(&type_, &name, Some(1)).unwrap();
+        storage.add(&type_, &name, &new_value, &[]).unwrap();
+
+        let entity = storage.get(&type_, &name, r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##).unwrap();
+        assert_eq!(new_value, entity.value.unwrap());
+    }
+
+    #[test]
+    fn sqlite_storage_get_version_works() {
+        let storage = _create_and_open_test_storage();
+
+        let type_: Vec<u8> = vec![1, 2, 3];
+        let name: Vec<u8> = vec![4, 5, 6];
+        let value = EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]};
+        let new_value = EncryptedValue{data: vec![1, 1, 1], key: vec![2, 2, 2]};
+        let tags = vec![Tag::PlainText(vec![0, 0, 0], "tag_value".to_string())];
+
+        storage.add(&type_, &name, &value, &[]).unwrap();
+        assert_eq!(1, storage.get_version(&type_, &name).unwrap());
+
+        storage.update(&type_, &name, &new_value).unwrap();
+        assert_eq!(2, storage.get_version(&type_, &name).unwrap());
+
+        storage.add_tags(&type_, &name, &tags).unwrap();
+        assert_eq!(3, storage.get_version(&type_, &name).unwrap());
+
+        storage.update_tags(&type_, &name, &tags).unwrap();
+        assert_eq!(4, storage.get_version(&type_, &name).unwrap());
+
+        storage.delete_tags(&type_, &name, &[TagName::OfPlain(vec![0, 0, 0])]).unwrap();
+        assert_eq!(5, storage.get_version(&type_, &name).unwrap());
+    }
+
+    #[test]
+    fn sqlite_storage_get_version_returns_error_for_unknown_item() {
+        let storage = _create_and_open_test_storage();
+
+        let res = storage.get_version(&vec![1, 2, 3], &vec![4, 5, 6]);
+        assert_match!(Err(WalletStorageError::ItemNotFound), res);
+    }
+
+    #[test]
+    fn sqlite_storage_update_if_version_works() {
+        let storage = _create_and_open_test_storage();
+
+        let type_: Vec<u8> = vec![1, 2, 3];
+        let name: Vec<u8> = vec![4, 5, 6];
+        let value = EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]};
+        let new_value = EncryptedValue{data: vec![1, 1, 1], key: vec![2, 2, 2]};
+        let tags = vec![Tag::PlainText(vec![0, 0, 0], "tag_value".to_string())];
+
+        storage.add(&type_, &name, &value, &[]).unwrap();
+        storage.update_if_version(&type_, &name, Some(&new_value), Some(&tags), 1).unwrap();
+
+        let entity = storage.get(&type_, &name, r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+        assert_eq!(new_value, entity.value.unwrap());
+        assert_eq!(tags, entity.tags.unwrap());
+        assert_eq!(2, storage.get_version(&type_, &name).unwrap());
+    }
+
+    #[test]
+    fn sqlite_storage_update_if_version_returns_conflict_for_outdated_version() {
+        let storage = _create_and_open_test_storage();
+
+        let type_: Vec<u8> = vec![1, 2, 3];
+        let name: Vec<u8> = vec![4, 5, 6];
+        let value = EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]};
+        let new_value = EncryptedValue{data: vec![1, 1, 1], key: vec![2, 2, 2]};
+
+        storage.add(&type_, &name, &value, &[]).unwrap();
+        storage.update(&type_, &name, &new_value).unwrap();
+
+        let res = storage.update_if_version(&type_, &name, Some(&value), None, 1);
+        assert_match!(Err(WalletStorageError::Conflict), res);
+
+        let entity = storage.get(&type_, &name, r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##).unwrap();
+        assert_eq!(new_value, entity.value.unwrap());
+    }
+
+    #[test]
+    fn sqlite_storage_update_if_version_returns_error_for_unknown_item() {
+        let storage = _create_and_open_test_storage();
+
+        let value = EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]};
+
+        let res = storage.update_if_version(&vec![1, 2, 3], &vec![4, 5, 6], Some(&value), None, 1);
+        assert_match!(Err(WalletStorageError::ItemNotFound), res);
+    }
+
+    #[test]
+    fn sqlite_storage_quarantine_works() {
+        let storage = _create_and_open_test_storage();
+
+        let type_: Vec<u8> = vec![1, 2, 3];
+        let name: Vec<u8> = vec![4, 5, 6];
+        let value = EncryptedValue{data: vec![7, 8, 9], key: vec![10, 11, 12]};
+        let tags = vec![Tag::PlainText(vec![0, 0, 0], "tag_value".to_string()),
+                        Tag::Encrypted(vec![1, 1, 1], vec![2, 2, 2])];
+
+        storage.add(&type_, &name, &value, &tags).unwrap();
+        storage.quarantine(&type_, &name).unwrap();
+
+        let res = storage.get(&type_, &name, r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##);
+        assert_match!(Err(WalletStorageError::ItemNotFound), res);
+
+        storage.add(&type_, &name, &value, &[]).unwrap();
+        let entity = storage.get(&type_, &name, r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+        assert!(entity.tags.unwrap().is_empty());
+    }
+
+    #[test]
+    fn sqlite_storage_quarantine_returns_error_for_unknown_item() {
+        let storage = _create_and_open_test_storage();
+
+        let res = storage.quarantine(&vec![1, 2, 3], &vec![4, 5, 6]);
+        assert_match!(Err(WalletStorageError::ItemNotFound), res);
+    }
 }
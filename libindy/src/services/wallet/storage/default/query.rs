@@ -4,6 +4,10 @@ use errors::wallet::WalletQueryError;
 use services::wallet::language::{Operator,TagName,TargetValue};
 
 
+// Filters out items with expiry time in the past. Expects items table to be aliased as `i`.
+pub const NOT_EXPIRED_CLAUSE: &str = "NOT EXISTS (SELECT 1 FROM item_expiry as e WHERE e.item_id = i.id AND e.expires_at <= CAST(strftime('%s', 'now') AS INTEGER))";
+
+
 // Translates Wallet Query Language to SQL
 // WQL input is provided as a reference to a top level Operator
 // Result is a tuple of query string and query arguments
@@ -11,7 +15,7 @@ pub fn wql_to_sql<'a>(class: &'a Vec<u8>, op: &'a Operator, options: Option<&str
     let mut arguments: Vec<&ToSql> = Vec::new();
     arguments.push(class);
     let clause_string = operator_to_sql(op, &mut arguments)?;
-    let mut query_string = "SELECT i.id, i.name, i.value, i.key, i.type FROM items as i WHERE i.type = ?".to_string();
+    let mut query_string = format!("SELECT i.id, i.name, i.value, i.key, i.type FROM items as i WHERE i.type = ? AND {}", NOT_EXPIRED_CLAUSE);
     if !clause_string.is_empty() {
         query_string.push_str(" AND ");
         query_string.push_str(&clause_string);
@@ -24,7 +28,7 @@ pub fn wql_to_sql_count<'a>(class: &'a Vec<u8>, op: &'a Operator) -> Result<(Str
     let mut arguments: Vec<&ToSql> = Vec::new();
     arguments.push(class);
     let clause_string = operator_to_sql(op, &mut arguments)?;
-    let mut query_string = "SELECT count(*) FROM items as i WHERE i.type = ?".to_string();
+    let mut query_string = format!("SELECT count(*) FROM items as i WHERE i.type = ? AND {}", NOT_EXPIRED_CLAUSE);
     if !clause_string.is_empty() {
         query_string.push_str(" AND ");
         query_string.push_str(&clause_string);
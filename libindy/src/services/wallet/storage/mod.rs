@@ -1,6 +1,7 @@
 pub mod default;
 pub mod plugged;
 
+use api::wallet::{WalletSetRecordExpiry, WalletPurgeExpiredRecords, WalletGetRecordVersion, WalletUpdateRecordIfVersion};
 use errors::wallet::WalletStorageError;
 use services::wallet::language;
 use services::wallet::wallet::EncryptedValue;
@@ -80,6 +81,15 @@ impl StorageEntity {
 }
 
 
+#[derive(Clone, Debug)]
+pub struct StorageChange {
+    pub seq: i64,
+    pub type_: Option<Vec<u8>>,
+    pub name: Option<Vec<u8>>,
+    pub data: Vec<u8>,
+}
+
+
 pub trait StorageIterator {
     fn next(&mut self) -> Result<Option<StorageEntity>, WalletStorageError>;
     fn get_total_count(&self) -> Result<Option<usize>, WalletStorageError>;
@@ -88,6 +98,7 @@ pub trait StorageIterator {
 
 pub trait WalletStorage {
     fn get(&self, type_: &Vec<u8>, name: &Vec<u8>, options: &str) -> Result<StorageEntity, WalletStorageError>;
+    fn get_versioned(&self, type_: &Vec<u8>, name: &Vec<u8>, options: &str) -> Result<(StorageEntity, i64), WalletStorageError>;
     fn add(&self, type_: &Vec<u8>, name: &Vec<u8>, value: &EncryptedValue, tags: &[Tag]) -> Result<(), WalletStorageError>;
     fn update(&self, type_: &Vec<u8>, name: &Vec<u8>, value: &EncryptedValue) -> Result<(), WalletStorageError>;
     fn add_tags(&self, type_: &Vec<u8>, name: &Vec<u8>, tags: &[Tag]) -> Result<(), WalletStorageError>;
@@ -98,12 +109,21 @@ pub trait WalletStorage {
     fn set_storage_metadata(&self, metadata: &Vec<u8>) -> Result<(), WalletStorageError>;
     fn get_all(&self) -> Result<Box<StorageIterator>, WalletStorageError>;
     fn search(&self, type_: &Vec<u8>, query: &language::Operator, options: Option<&str>) -> Result<Box<StorageIterator>, WalletStorageError>;
+    fn get_changes_since(&self, seq: i64) -> Result<Vec<StorageChange>, WalletStorageError>;
+    fn set_change_log_key(&mut self, key: &[u8]) -> Result<(), WalletStorageError>;
+    fn set_expiry(&self, type_: &Vec<u8>, name: &Vec<u8>, expires_at: Option<i64>) -> Result<(), WalletStorageError>;
+    fn purge_expired(&self) -> Result<usize, WalletStorageError>;
+    fn get_version(&self, type_: &Vec<u8>, name: &Vec<u8>) -> Result<i64, WalletStorageError>;
+    fn update_if_version(&self, type_: &Vec<u8>, name: &Vec<u8>, value: Option<&EncryptedValue>, tags: Option<&[Tag]>, expected_version: i64) -> Result<(), WalletStorageError>;
+    fn quarantine(&self, type_: &Vec<u8>, name: &Vec<u8>) -> Result<(), WalletStorageError>;
     fn close(&mut self) -> Result<(), WalletStorageError>;
 }
 
 
 pub trait WalletStorageType {
     fn create_storage(&self, name: &str, config: Option<&str>, credentials: &str, keys: &Vec<u8>) -> Result<(), WalletStorageError>;
-    fn open_storage(&self, name: &str, config: Option<&str>, credentials: &str) -> Result<Box<WalletStorage>, WalletStorageError>;
+    fn open_storage(&self, name: &str, config: Option<&str>, runtime_config: Option<&str>, credentials: &str) -> Result<Box<WalletStorage>, WalletStorageError>;
     fn delete_storage(&self, name: &str, config: Option<&str>, credentials: &str) -> Result<(), WalletStorageError>;
+    fn register_expiry_handlers(&mut self, set_record_expiry: WalletSetRecordExpiry, purge_expired_records: WalletPurgeExpiredRecords) -> Result<(), WalletStorageError>;
+    fn register_versioning_handlers(&mut self, get_record_version: WalletGetRecordVersion, update_record_if_version: WalletUpdateRecordIfVersion) -> Result<(), WalletStorageError>;
 }
\ No newline at end of file
@@ -1,6 +1,8 @@
+extern crate digest;
 extern crate libc;
 extern crate indy_crypto;
 extern crate serde_json;
+extern crate sha2;
 
 use std::ffi::{CString, CStr};
 use std::ptr;
@@ -15,9 +17,10 @@ use errors::wallet::WalletStorageError;
 use services::wallet::language;
 
 
-use super::{StorageIterator, WalletStorageType, WalletStorage, StorageEntity, EncryptedValue, Tag, TagName, FetchOptions};
+use super::{StorageIterator, WalletStorageType, WalletStorage, StorageEntity, StorageChange, EncryptedValue, Tag, TagName, FetchOptions};
 use super::super::SearchOptions;
 
+use self::digest::{FixedOutput, Input};
 use self::libc::c_char;
 use self::indy_crypto::utils::json::JsonDecodable;
 
@@ -232,7 +235,11 @@ struct PluggedStorage {
     get_search_total_count_handler: WalletGetSearchTotalCount,
     fetch_search_next_record_handler: WalletFetchSearchNextRecord,
     free_search_handler: WalletFreeSearch,
-    close_handler: WalletClose
+    close_handler: WalletClose,
+    set_record_expiry_handler: Option<WalletSetRecordExpiry>,
+    purge_expired_records_handler: Option<WalletPurgeExpiredRecords>,
+    get_record_version_handler: Option<WalletGetRecordVersion>,
+    update_record_if_version_handler: Option<WalletUpdateRecordIfVersion>
 }
 
 impl PluggedStorage {
@@ -281,6 +288,10 @@ impl PluggedStorage {
             fetch_search_next_record_handler,
             free_search_handler,
             close_handler,
+            set_record_expiry_handler: None,
+            purge_expired_records_handler: None,
+            get_record_version_handler: None,
+            update_record_if_version_handler: None,
         }
     }
 }
@@ -337,6 +348,43 @@ fn _tags_names_to_json(tag_names: &[TagName]) -> Result<String, WalletStorageErr
     serde_json::to_string(&tags).map_err(|err| WalletStorageError::IOError(err.to_string()))
 }
 
+const _FINGERPRINT_FETCH_OPTIONS: &str = r#"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"#;
+
+fn _len_bytes(len: usize) -> Vec<u8> {
+    (0..8).rev().map(|i| ((len as u64) >> (i * 8)) as u8).collect()
+}
+
+// Storages that don't register versioning handlers get a version derived from
+// the stored value and tags, so any change of the record changes its version.
+fn _record_fingerprint(entity: &StorageEntity) -> i64 {
+    let mut tags: Vec<Vec<u8>> = entity.tags.as_ref().map(|tags| tags.iter().map(|tag| {
+        let (kind, name, value) = match *tag {
+            Tag::Encrypted(ref name, ref value) => (b'e', name.clone(), value.clone()),
+            Tag::PlainText(ref name, ref value) => (b'p', name.clone(), value.as_bytes().to_vec()),
+        };
+        let mut bytes = vec![kind];
+        bytes.extend_from_slice(&_len_bytes(name.len()));
+        bytes.extend(name);
+        bytes.extend(value);
+        bytes
+    }).collect()).unwrap_or(Vec::new());
+    tags.sort();
+
+    let mut hasher = sha2::Sha256::default();
+    hasher.process(&entity.value.as_ref().map(EncryptedValue::to_bytes).unwrap_or(Vec::new()));
+    for tag in tags {
+        hasher.process(&_len_bytes(tag.len()));
+        hasher.process(&tag);
+    }
+    let hash = hasher.fixed_result();
+
+    let mut version: i64 = 0;
+    for byte in hash.iter().take(8) {
+        version = (version << 8) | (*byte as i64);
+    }
+    version & ::std::i64::MAX
+}
+
 impl WalletStorage for PluggedStorage {
     fn get(&self, type_: &Vec<u8>, name: &Vec<u8>, options: &str) -> Result<StorageEntity, WalletStorageError> {
         let type_param = type_;
@@ -609,6 +657,156 @@ impl WalletStorage for PluggedStorage {
         ))
     }
 
+    fn get_changes_since(&self, _seq: i64) -> Result<Vec<StorageChange>, WalletStorageError> {
+        // change log is supported only by default storage
+        Err(WalletStorageError::ConfigError)
+    }
+
+    fn set_change_log_key(&mut self, _key: &[u8]) -> Result<(), WalletStorageError> {
+        // plugged storage doesn't keep change log
+        Ok(())
+    }
+
+    fn set_expiry(&self, type_: &Vec<u8>, id: &Vec<u8>, expires_at: Option<i64>) -> Result<(), WalletStorageError> {
+        // records expiry is supported only if storage registered expiry handlers
+        let set_record_expiry_handler = self.set_record_expiry_handler.ok_or(WalletStorageError::ConfigError)?;
+
+        let type_ = CString::new(base64::encode(type_))?;
+        let id = CString::new(base64::encode(id))?;
+
+        let err = (set_record_expiry_handler)(self.handle,
+                                              type_.as_ptr(),
+                                              id.as_ptr(),
+                                              expires_at.unwrap_or(-1));
+
+        if err == ErrorCode::WalletItemNotFound {
+            return Err(WalletStorageError::ItemNotFound);
+        }
+        else if err != ErrorCode::Success {
+            return Err(WalletStorageError::PluggedStorageError(err));
+        }
+
+        Ok(())
+    }
+
+    fn purge_expired(&self) -> Result<usize, WalletStorageError> {
+        let purge_expired_records_handler = match self.purge_expired_records_handler {
+            Some(handler) => handler,
+            None => return Ok(0) // storage without expiry support can't contain expired records
+        };
+
+        let mut purged_count: usize = 0;
+
+        let err = (purge_expired_records_handler)(self.handle, &mut purged_count);
+
+        if err != ErrorCode::Success {
+            return Err(WalletStorageError::PluggedStorageError(err));
+        }
+
+        Ok(purged_count)
+    }
+
+    fn get_versioned(&self, type_: &Vec<u8>, name: &Vec<u8>, options: &str) -> Result<(StorageEntity, i64), WalletStorageError> {
+        if self.get_record_version_handler.is_some() {
+            // Plugged storage API has no call returning both, so version is read first:
+            // a concurrent update can only make it older than the data and cause a conflict later
+            let version = self.get_version(type_, name)?;
+            return Ok((self.get(type_, name, options)?, version));
+        }
+
+        let options: FetchOptions = serde_json::from_str(options)
+            .map_err(|err|
+                WalletStorageError::CommonError(
+                    CommonError::InvalidStructure(format!("Cannot deserialize RecordRetrieveOptions: {:?}", err))))?;
+
+        let mut entity = self.get(type_, name, _FINGERPRINT_FETCH_OPTIONS)?;
+        let version = _record_fingerprint(&entity);
+
+        if options.retrieve_type { entity.type_ = Some(type_.clone()); }
+        if !options.retrieve_value { entity.value = None; }
+        if !options.retrieve_tags { entity.tags = None; }
+
+        Ok((entity, version))
+    }
+
+    fn get_version(&self, type_: &Vec<u8>, id: &Vec<u8>) -> Result<i64, WalletStorageError> {
+        let get_record_version_handler = match self.get_record_version_handler {
+            Some(handler) => handler,
+            None => {
+                let entity = self.get(type_, id, _FINGERPRINT_FETCH_OPTIONS)?;
+                return Ok(_record_fingerprint(&entity));
+            }
+        };
+
+        let type_ = CString::new(base64::encode(type_))?;
+        let id = CString::new(base64::encode(id))?;
+
+        let mut version: i64 = 0;
+
+        let err = (get_record_version_handler)(self.handle,
+                                               type_.as_ptr(),
+                                               id.as_ptr(),
+                                               &mut version);
+
+        if err == ErrorCode::WalletItemNotFound {
+            return Err(WalletStorageError::ItemNotFound);
+        }
+        else if err != ErrorCode::Success {
+            return Err(WalletStorageError::PluggedStorageError(err));
+        }
+
+        Ok(version)
+    }
+
+    fn update_if_version(&self, type_: &Vec<u8>, id: &Vec<u8>, value: Option<&EncryptedValue>, tags: Option<&[Tag]>, expected_version: i64) -> Result<(), WalletStorageError> {
+        let update_record_if_version_handler = match self.update_record_if_version_handler {
+            Some(handler) => handler,
+            None => {
+                // Without storage support the check and the update are separate calls,
+                // so concurrent writers to the same record can't be fully excluded
+                if self.get_version(type_, id)? != expected_version {
+                    return Err(WalletStorageError::Conflict);
+                }
+                if let Some(value) = value {
+                    self.update(type_, id, value)?;
+                }
+                if let Some(tags) = tags {
+                    self.update_tags(type_, id, tags)?;
+                }
+                return Ok(());
+            }
+        };
+
+        let type_ = CString::new(base64::encode(type_))?;
+        let id = CString::new(base64::encode(id))?;
+        let joined_value = value.map(|value| value.to_bytes());
+
+        let tags_json = match tags {
+            Some(tags) => Some(CString::new(_tags_to_json(tags)?)?),
+            None => None
+        };
+
+        let err = (update_record_if_version_handler)(self.handle,
+                                                     type_.as_ptr(),
+                                                     id.as_ptr(),
+                                                     joined_value.as_ref().map_or(ptr::null(), |x| x.as_ptr()),
+                                                     joined_value.as_ref().map_or(0, |x| x.len()),
+                                                     tags_json.as_ref().map_or(ptr::null(), |x| x.as_ptr()),
+                                                     expected_version);
+
+        match err {
+            ErrorCode::Success => Ok(()),
+            ErrorCode::WalletItemNotFound => Err(WalletStorageError::ItemNotFound),
+            ErrorCode::WalletConflict => Err(WalletStorageError::Conflict),
+            err => Err(WalletStorageError::PluggedStorageError(err))
+        }
+    }
+
+    fn quarantine(&self, _type_: &Vec<u8>, _id: &Vec<u8>) -> Result<(), WalletStorageError> {
+        // records quarantine is supported only by default storage
+        Err(WalletStorageError::ConfigError)
+    }
+
     fn close(&mut self) -> Result<(), WalletStorageError> {
         let err = (self.close_handler)(self.handle);
 
@@ -657,7 +855,11 @@ pub struct PluggedStorageType {
     search_all_records_handler: WalletSearchAllRecords,
     get_search_total_count_handler: WalletGetSearchTotalCount,
     fetch_search_next_record_handler: WalletFetchSearchNextRecord,
-    free_search_handler: WalletFreeSearch
+    free_search_handler: WalletFreeSearch,
+    set_record_expiry_handler: Option<WalletSetRecordExpiry>,
+    purge_expired_records_handler: Option<WalletPurgeExpiredRecords>,
+    get_record_version_handler: Option<WalletGetRecordVersion>,
+    update_record_if_version_handler: Option<WalletUpdateRecordIfVersion>
 }
 
 
@@ -711,6 +913,10 @@ impl PluggedStorageType {
             get_search_total_count_handler,
             fetch_search_next_record_handler,
             free_search_handler,
+            set_record_expiry_handler: None,
+            purge_expired_records_handler: None,
+            get_record_version_handler: None,
+            update_record_if_version_handler: None,
         }
     }
 }
@@ -739,7 +945,7 @@ impl WalletStorageType for PluggedStorageType {
         Ok(())
     }
 
-    fn open_storage(&self, name: &str, config: Option<&str>, credentials: &str) -> Result<Box<WalletStorage>, WalletStorageError> {
+    fn open_storage(&self, name: &str, config: Option<&str>, runtime_config: Option<&str>, credentials: &str) -> Result<Box<WalletStorage>, WalletStorageError> {
         let mut handle: i32 = -1;
         let cname = CString::new(name)?;
 
@@ -748,11 +954,16 @@ impl WalletStorageType for PluggedStorageType {
             None => None
         };
 
+        let runtime_config = match runtime_config {
+            Some(runtime_config) => Some(CString::new(runtime_config)?),
+            None => None
+        };
+
         let credentials = CString::new(credentials)?;
 
         let err = (self.open_handler)(cname.as_ptr(),
                                       config.as_ref().map_or(ptr::null(), |x| x.as_ptr()),
-                                      "".as_ptr() as *const i8, // TODO!!!
+                                      runtime_config.as_ref().map_or(ptr::null(), |x| x.as_ptr()),
                                       credentials.as_ptr(),
                                       &mut handle);
 
@@ -760,30 +971,36 @@ impl WalletStorageType for PluggedStorageType {
             return Err(WalletStorageError::PluggedStorageError(err));
         }
 
-        Ok(Box::new(
-            PluggedStorage::new(
-                handle,
-                self.add_record_handler,
-                self.update_record_value_handler,
-                self.update_record_tags_handler,
-                self.add_record_tags_handler,
-                self.delete_record_tags_handler,
-                self.delete_record_handler,
-                self.get_record_handler,
-                self.get_record_id_handler,
-                self.get_record_type_handler,
-                self.get_record_value_handler,
-                self.get_record_tags_handler,
-                self.free_record_handler,
-                self.get_storage_metadata_handler,
-                self.set_storage_metadata_handler,
-                self.free_storage_metadata_handler,
-                self.search_records_handler,
-                self.search_all_records_handler,
-                self.get_search_total_count_handler,
-                self.fetch_search_next_record_handler,
-                self.free_search_handler,
-                self.close_handler)))
+        let mut storage = PluggedStorage::new(
+            handle,
+            self.add_record_handler,
+            self.update_record_value_handler,
+            self.update_record_tags_handler,
+            self.add_record_tags_handler,
+            self.delete_record_tags_handler,
+            self.delete_record_handler,
+            self.get_record_handler,
+            self.get_record_id_handler,
+            self.get_record_type_handler,
+            self.get_record_value_handler,
+            self.get_record_tags_handler,
+            self.free_record_handler,
+            self.get_storage_metadata_handler,
+            self.set_storage_metadata_handler,
+            self.free_storage_metadata_handler,
+            self.search_records_handler,
+            self.search_all_records_handler,
+            self.get_search_total_count_handler,
+            self.fetch_search_next_record_handler,
+            self.free_search_handler,
+            self.close_handler);
+
+        storage.set_record_expiry_handler = self.set_record_expiry_handler;
+        storage.purge_expired_records_handler = self.purge_expired_records_handler;
+        storage.get_record_version_handler = self.get_record_version_handler;
+        storage.update_record_if_version_handler = self.update_record_if_version_handler;
+
+        Ok(Box::new(storage))
     }
 
     fn delete_storage(&self, name: &str, config: Option<&str>, credentials: &str) -> Result<(), WalletStorageError> {
@@ -806,6 +1023,18 @@ impl WalletStorageType for PluggedStorageType {
 
         Ok(())
     }
+
+    fn register_expiry_handlers(&mut self, set_record_expiry: WalletSetRecordExpiry, purge_expired_records: WalletPurgeExpiredRecords) -> Result<(), WalletStorageError> {
+        self.set_record_expiry_handler = Some(set_record_expiry);
+        self.purge_expired_records_handler = Some(purge_expired_records);
+        Ok(())
+    }
+
+    fn register_versioning_handlers(&mut self, get_record_version: WalletGetRecordVersion, update_record_if_version: WalletUpdateRecordIfVersion) -> Result<(), WalletStorageError> {
+        self.get_record_version_handler = Some(get_record_version);
+        self.update_record_if_version_handler = Some(update_record_if_version);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -1409,7 +1638,7 @@ mod tests {
         let storage_name = "wallet1";
         let credentials = "credentials";
 
-        let mut storage = storage_type.open_storage(storage_name, None, &credentials).unwrap();
+        let mut storage = storage_type.open_storage(storage_name, None, None, &credentials).unwrap();
         storage.close().unwrap();
 
         let expected_open_call = Call::OpenHandler(
@@ -1459,7 +1688,7 @@ mod tests {
         let storage_name = "wallet1";
         let credentials = "credentials";
 
-        let storage = storage_type.open_storage(storage_name, None, &credentials).unwrap();
+        let storage = storage_type.open_storage(storage_name, None, None, &credentials).unwrap();
 
         let expected_call = Call::OpenHandler(
             Some(storage_name.to_owned()),
@@ -1619,6 +1848,34 @@ mod tests {
 
     }
 
+    #[test]
+    fn plugged_storage_get_versioned_works_without_versioning_handlers() {
+        DEBUG_VEC.write().unwrap().clear();
+
+        let storage = _open_storage();
+
+        DEBUG_VEC.write().unwrap().clear();
+
+        let type_ = _random_vector(32);
+        let id = _random_vector(32);
+        let options = _fetch_options(false, false, true);
+
+        let (storage_entity, version) = storage.get_versioned(&type_, &id, &options).unwrap();
+
+        let expected_storage_entity = StorageEntity {
+            type_: Some(type_.clone()),
+            name: id.clone(),
+            value: None,
+            tags: None,
+        };
+
+        assert_eq!(expected_storage_entity, storage_entity);
+        assert_eq!(version, storage.get_version(&type_, &id).unwrap());
+
+        let res = storage.update_if_version(&type_, &id, Some(&RETURN_VALUE.read().unwrap().1), None, version - 1);
+        assert_match!(Err(WalletStorageError::Conflict), res);
+    }
+
     #[test]
     fn plugged_storage_get_record_type_value_tags_works() {
         DEBUG_VEC.write().unwrap().clear();
@@ -1,9 +1,16 @@
+extern crate hex;
+extern crate digest;
+extern crate sha2;
+
 use std::collections::HashMap;
 use std::io::{Write,Read};
 use std::rc::Rc;
 
 use serde_json;
+use self::hex::ToHex;
+use self::digest::{FixedOutput, Input};
 use utils::crypto::chacha20poly1305_ietf::ChaCha20Poly1305IETF;
+use utils::crypto::hmacsha512::HMACSHA512;
 
 use errors::wallet::WalletError;
 use errors::common::CommonError;
@@ -13,11 +20,13 @@ use super::iterator::WalletIterator;
 use super::encryption::*;
 use super::query_encryption::encrypt_query;
 use super::language;
-use super::WalletRecord;
+use super::{WalletRecord, WalletChange, RecordOptions, WalletVerificationReport, CorruptedRecord};
 
 
 pub(super) type Tags = HashMap<String, String>;
 
+const _CHANGE_LOG_KEY_INFO: &[u8] = b"indy_wallet_change_log_key";
+
 
 #[derive(Debug, Default, Clone)]
 pub(super) struct Keys {
@@ -79,11 +88,29 @@ impl Keys {
 
         return ChaCha20Poly1305IETF::encrypt_as_not_searchable(&keys, &master_key);
     }
+
+    /// Key of change log entries. It is derived from the type key as wallet metadata
+    /// of existing wallets has no room for one more key.
+    pub fn change_log_key(&self) -> Result<[u8; 32], CommonError> {
+        let hmac = HMACSHA512::hmac(&self.type_key, _CHANGE_LOG_KEY_INFO)?;
+
+        let mut key = [0u8; 32];
+        key.clone_from_slice(&hmac[0..32]);
+        Ok(key)
+    }
 }
 
 
 #[derive(Deserialize,Debug)]
-pub struct WalletRuntimeConfig {}
+pub struct WalletRuntimeConfig {
+    #[serde(default)]
+    pub change_log: bool,
+    #[serde(default)]
+    pub concurrent_access: bool,
+    pub busy_timeout: Option<u64>,
+    #[serde(default)]
+    pub read_only: bool
+}
 
 impl WalletRuntimeConfig {
     pub fn parse_from_json(json_str: &str) -> Result<WalletRuntimeConfig, WalletError> {
@@ -94,16 +121,29 @@ impl WalletRuntimeConfig {
 
 impl Default for WalletRuntimeConfig {
     fn default() -> WalletRuntimeConfig {
-        WalletRuntimeConfig {}
+        WalletRuntimeConfig {
+            change_log: false,
+            concurrent_access: false,
+            busy_timeout: None,
+            read_only: false
+        }
     }
 }
 
 
+#[derive(Deserialize)]
+struct ChangeData {
+    operation: String,
+    timestamp: i64
+}
+
+
 pub(super) struct Wallet {
     name: String,
     pool_name: String,
     storage: Box<storage::WalletStorage>,
     keys: Rc<Keys>,
+    read_only: bool,
 }
 
 
@@ -159,16 +199,26 @@ impl EncryptedValue {
 }
 
 impl Wallet {
-    pub fn new(name: &str, pool_name: &str, storage: Box<storage::WalletStorage>, keys: Keys) -> Wallet {
+    pub fn new(name: &str, pool_name: &str, storage: Box<storage::WalletStorage>, keys: Keys, read_only: bool) -> Wallet {
         Wallet {
             name: name.to_string(),
             pool_name: pool_name.to_string(),
             storage: storage,
             keys: Rc::new(keys),
+            read_only: read_only,
         }
     }
 
+    fn check_writable(&self) -> Result<(), WalletError> {
+        if self.read_only {
+            return Err(WalletError::AccessFailed("Wallet is opened in read-only mode".to_string()));
+        }
+        Ok(())
+    }
+
     pub fn add(&self, type_: &str, name: &str, value: &str, tags: &HashMap<String, String>) -> Result<(), WalletError> {
+        self.check_writable()?;
+
         let etype = ChaCha20Poly1305IETF::encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let ename = ChaCha20Poly1305IETF::encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
         let evalue = EncryptedValue::encrypt(value, &self.keys.value_key);
@@ -180,6 +230,8 @@ impl Wallet {
     }
     
     pub fn add_tags(&self, type_: &str, name: &str, tags: &HashMap<String, String>) -> Result<(), WalletError> {
+        self.check_writable()?;
+
         let encrypted_type = ChaCha20Poly1305IETF::encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let encrypted_name = ChaCha20Poly1305IETF::encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
         let encrypted_tags = encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key);
@@ -188,6 +240,8 @@ impl Wallet {
     }
 
     pub fn update_tags(&self, type_: &str, name: &str, tags: &HashMap<String, String>) -> Result<(), WalletError> {
+        self.check_writable()?;
+
         let encrypted_type = ChaCha20Poly1305IETF::encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let encrypted_name = ChaCha20Poly1305IETF::encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
         let encrypted_tags = encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key);
@@ -196,6 +250,8 @@ impl Wallet {
     }
 
     pub fn delete_tags(&self, type_: &str, name: &str, tag_names: &[String]) -> Result<(), WalletError> {
+        self.check_writable()?;
+
         let encrypted_type = ChaCha20Poly1305IETF::encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let encrypted_name = ChaCha20Poly1305IETF::encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
         let encrypted_tag_names = encrypt_tag_names(tag_names, &self.keys.tag_name_key, &self.keys.tags_hmac_key);
@@ -204,6 +260,8 @@ impl Wallet {
     }
 
     pub fn update(&self, type_: &str, name: &str, new_value: &str) -> Result<(), WalletError> {
+        self.check_writable()?;
+
         let encrypted_type = ChaCha20Poly1305IETF::encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let encrypted_name = ChaCha20Poly1305IETF::encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
         let encrypted_value = EncryptedValue::encrypt(new_value, &self.keys.value_key);
@@ -211,11 +269,38 @@ impl Wallet {
         Ok(())
     }
 
+    pub fn update_expiry(&self, type_: &str, name: &str, expires_at: Option<i64>) -> Result<(), WalletError> {
+        self.check_writable()?;
+
+        let encrypted_type = ChaCha20Poly1305IETF::encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
+        let encrypted_name = ChaCha20Poly1305IETF::encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
+        self.storage.set_expiry(&encrypted_type, &encrypted_name, expires_at)?;
+        Ok(())
+    }
+
+    pub fn update_if_version(&self, type_: &str, name: &str, new_value: Option<&str>, tags: Option<&HashMap<String, String>>, expected_version: i64) -> Result<(), WalletError> {
+        self.check_writable()?;
+
+        let encrypted_type = ChaCha20Poly1305IETF::encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
+        let encrypted_name = ChaCha20Poly1305IETF::encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
+        let encrypted_value = new_value.map(|value| EncryptedValue::encrypt(value, &self.keys.value_key));
+        let encrypted_tags = tags.map(|tags| encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key));
+        self.storage.update_if_version(&encrypted_type, &encrypted_name, encrypted_value.as_ref(), encrypted_tags.as_ref().map(Vec::as_slice), expected_version)?;
+        Ok(())
+    }
+
     pub fn get(&self, type_: &str, name: &str, options: &str) -> Result<WalletRecord, WalletError> {
         let etype = ChaCha20Poly1305IETF::encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let ename = ChaCha20Poly1305IETF::encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
 
-        let result = self.storage.get(&etype, &ename, options)?;
+        let record_options: RecordOptions = serde_json::from_str(options)?;
+
+        let (result, version) = if record_options.retrieve_version.unwrap_or(false) {
+            let (result, version) = self.storage.get_versioned(&etype, &ename, options)?;
+            (result, Some(version))
+        } else {
+            (self.storage.get(&etype, &ename, options)?, None)
+        };
 
         let value = match result.value {
             None => None,
@@ -227,10 +312,12 @@ impl Wallet {
             Some(tags) => Some(serde_json::to_string(&tags)?)
         };
 
-        Ok(WalletRecord::new(String::from(name), result.type_.map(|_| type_.to_string()), value, tags))
+        Ok(WalletRecord::new(String::from(name), result.type_.map(|_| type_.to_string()), value, tags, version))
     }
 
     pub fn delete(&self, type_: &str, name: &str) -> Result<(), WalletError> {
+        self.check_writable()?;
+
         let etype = ChaCha20Poly1305IETF::encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let ename = ChaCha20Poly1305IETF::encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
 
@@ -247,12 +334,126 @@ impl Wallet {
         Ok(wallet_iterator)
     }
 
+    pub fn get_changes_since(&self, seq: i64) -> Result<Vec<WalletChange>, WalletError> {
+        let change_log_key = self.keys.change_log_key()?;
+        let mut changes = Vec::new();
+
+        for change in self.storage.get_changes_since(seq)? {
+            let type_ = match change.type_ {
+                Some(ref type_) => Some(String::from_utf8(ChaCha20Poly1305IETF::decrypt(type_, &self.keys.type_key)?)?),
+                None => None
+            };
+            // id hash is SHA-256 of plain record id, so callers can match it with their records
+            let id_hash = match change.name {
+                Some(ref name) => {
+                    let mut hasher = sha2::Sha256::default();
+                    hasher.process(&ChaCha20Poly1305IETF::decrypt(name, &self.keys.name_key)?);
+                    Some(hasher.fixed_result().to_vec().to_hex())
+                }
+                None => None
+            };
+            let data: ChangeData = serde_json::from_slice(&ChaCha20Poly1305IETF::decrypt(&change.data, &change_log_key)?)?;
+            changes.push(WalletChange::new(change.seq, type_, id_hash, data.operation, data.timestamp));
+        }
+
+        Ok(changes)
+    }
+
+    pub fn purge_expired(&self) -> Result<usize, WalletError> {
+        self.check_writable()?;
+
+        let purged_count = self.storage.purge_expired()?;
+        Ok(purged_count)
+    }
+
+    pub fn verify(&self, quarantine: bool) -> Result<WalletVerificationReport, WalletError> {
+        if quarantine {
+            self.check_writable()?;
+        }
+
+        let mut checked_count = 0;
+        let mut corrupted = Vec::new();
+        let mut corrupted_items = Vec::new();
+
+        {
+            let mut storage_iterator = self.storage.get_all()?;
+
+            while let Some(entity) = storage_iterator.next()? {
+                checked_count += 1;
+
+                let etype = entity.type_.unwrap_or_default();
+                let mut errors = Vec::new();
+
+                let type_ = self._verify_searchable(&etype, &self.keys.type_key, &self.keys.item_hmac_key, "type", &mut errors);
+                let name = self._verify_searchable(&entity.name, &self.keys.name_key, &self.keys.item_hmac_key, "id", &mut errors);
+
+                if let Some(evalue) = entity.value {
+                    if let Err(err) = evalue.decrypt(&self.keys.value_key) {
+                        errors.push(format!("Invalid value: {}", err));
+                    }
+                }
+
+                for etag in entity.tags.unwrap_or_default() {
+                    match etag {
+                        storage::Tag::PlainText(ref ename, _) => {
+                            self._verify_searchable(ename, &self.keys.tag_name_key, &self.keys.tags_hmac_key, "tag name", &mut errors);
+                        }
+                        storage::Tag::Encrypted(ref ename, ref evalue) => {
+                            self._verify_searchable(ename, &self.keys.tag_name_key, &self.keys.tags_hmac_key, "tag name", &mut errors);
+                            self._verify_searchable(evalue, &self.keys.tag_value_key, &self.keys.tags_hmac_key, "tag value", &mut errors);
+                        }
+                    }
+                }
+
+                if !errors.is_empty() {
+                    // The same id hash is used by change log, so broken record can be matched with its history
+                    let mut hasher = sha2::Sha256::default();
+                    hasher.process(&entity.name);
+                    let id_hash = hasher.fixed_result().to_vec();
+
+                    corrupted.push(CorruptedRecord::new(type_, name, id_hash.to_hex(), errors));
+                    corrupted_items.push((etype, entity.name));
+                }
+            }
+        }
+
+        let mut quarantined_count = 0;
+
+        if quarantine {
+            // Items are moved only after iteration is finished as storage iterator can't survive deletion
+            for (etype, ename) in corrupted_items {
+                self.storage.quarantine(&etype, &ename)?;
+                quarantined_count += 1;
+            }
+        }
+
+        Ok(WalletVerificationReport::new(checked_count, corrupted, quarantined_count))
+    }
+
+    fn _verify_searchable(&self, enc_text: &[u8], key: &[u8], hmac_key: &[u8], field: &str, errors: &mut Vec<String>) -> Option<String> {
+        match decrypt_searchable(enc_text, key, hmac_key) {
+            Ok(data) => match String::from_utf8(data) {
+                Ok(data) => Some(data),
+                Err(_) => {
+                    errors.push(format!("Invalid {}: Invalid UTF8 string", field));
+                    None
+                }
+            },
+            Err(err) => {
+                errors.push(format!("Invalid {}: {}", field, err));
+                None
+            }
+        }
+    }
+
     pub fn close(&mut self) -> Result<(), WalletError> {
         self.storage.close()?;
         Ok(())
     }
 
     pub(super) fn rotate_key(&self, new_master_key: &[u8]) -> Result<(), WalletError> {
+        self.check_writable()?;
+
         let new_metadata = self.keys.encrypt(new_master_key);
         self.storage.set_storage_metadata(&new_metadata)?;
         Ok(())
@@ -266,6 +467,10 @@ impl Wallet {
         self.name.clone()
     }
 
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
     fn export(&self, writer: Box<Write>, key: [u8; 32]) -> Result<(), WalletError> {
         unimplemented!()
     }
@@ -320,7 +525,7 @@ mod tests {
         let master_key = _get_test_master_key();
         storage_type.create_storage("test_wallet", None, "", &Keys::gen_keys(master_key)).unwrap();
         let credentials = _credentials();
-        let storage = storage_type.open_storage("test_wallet", None, &credentials[..]).unwrap();
+        let storage = storage_type.open_storage("test_wallet", None, None, &credentials[..]).unwrap();
 
         let keys = Keys::new(
             ChaCha20Poly1305IETF::decrypt(
@@ -329,7 +534,7 @@ mod tests {
             ).unwrap()
         );
 
-        Wallet::new(name, pool_name, storage, keys)
+        Wallet::new(name, pool_name, storage, keys, false)
     }
 
     fn _get_test_master_key() -> [u8; 32] {
@@ -479,14 +684,14 @@ mod tests {
 
         let storage_type = SQLiteStorageType::new();
         let credentials = _credentials();
-        let storage = storage_type.open_storage("test_wallet", None, &credentials[..]).unwrap();
+        let storage = storage_type.open_storage("test_wallet", None, None, &credentials[..]).unwrap();
         let keys = Keys::new(
             ChaCha20Poly1305IETF::decrypt( // DARKO
                 &storage.get_storage_metadata().unwrap(),
                 &_get_test_master_key()
             ).unwrap()
         );
-        let wallet = Wallet::new("test_wallet", "test_pool", storage, keys);
+        let wallet = Wallet::new("test_wallet", "test_pool", storage, keys, false);
 
         let entity = wallet.get(type_, name, &_fetch_options(false, true, true)).unwrap();
 
@@ -1909,4 +2114,87 @@ mod tests {
         assert_eq!(res.name, "foo".to_string());
         assert_eq!(res.value.unwrap(), "bar".to_string())
     }
+
+    /**
+     * Verify tests
+    */
+    fn _add_corrupted_value(wallet: &Wallet, type_: &str, name: &str) {
+        let etype = ChaCha20Poly1305IETF::encrypt_as_searchable(type_.as_bytes(), &wallet.keys.type_key, &wallet.keys.item_hmac_key);
+        let ename = ChaCha20Poly1305IETF::encrypt_as_searchable(name.as_bytes(), &wallet.keys.name_key, &wallet.keys.item_hmac_key);
+        let evalue = EncryptedValue::encrypt("value", &ChaCha20Poly1305IETF::create_key());
+        wallet.storage.add(&etype, &ename, &evalue, &[]).unwrap();
+    }
+
+    #[test]
+    fn wallet_verify_works() {
+        _cleanup();
+        let wallet = _create_wallet();
+        let mut tags = HashMap::new();
+        tags.insert("tag1".to_string(), "tag_value_1".to_string());
+        tags.insert("~tag2".to_string(), "tag_value_2".to_string());
+
+        wallet.add("test", "name1", "value1", &tags).unwrap();
+        wallet.add("test", "name2", "value2", &HashMap::new()).unwrap();
+
+        let report = wallet.verify(false).unwrap();
+
+        assert_eq!(2, report.get_checked_count());
+        assert!(report.get_corrupted().is_empty());
+        assert_eq!(0, report.get_quarantined_count());
+    }
+
+    #[test]
+    fn wallet_verify_works_for_corrupted_value() {
+        _cleanup();
+        let wallet = _create_wallet();
+
+        wallet.add("test", "name1", "value1", &HashMap::new()).unwrap();
+        _add_corrupted_value(&wallet, "test", "name2");
+
+        let report = wallet.verify(false).unwrap();
+
+        assert_eq!(2, report.get_checked_count());
+        assert_eq!(1, report.get_corrupted().len());
+        assert_eq!(1, report.get_corrupted()[0].get_errors().len());
+        assert_eq!(0, report.get_quarantined_count());
+    }
+
+    #[test]
+    fn wallet_verify_works_for_tag_hmac_mismatch() {
+        _cleanup();
+        let wallet = _create_wallet();
+
+        let etype = ChaCha20Poly1305IETF::encrypt_as_searchable("test".as_bytes(), &wallet.keys.type_key, &wallet.keys.item_hmac_key);
+        let ename = ChaCha20Poly1305IETF::encrypt_as_searchable("name1".as_bytes(), &wallet.keys.name_key, &wallet.keys.item_hmac_key);
+        let evalue = EncryptedValue::encrypt("value1", &wallet.keys.value_key);
+        let etags = vec![storage::Tag::PlainText(
+            ChaCha20Poly1305IETF::encrypt_as_searchable("tag1".as_bytes(), &wallet.keys.tag_name_key, &ChaCha20Poly1305IETF::create_key()),
+            "tag_value_1".to_string()
+        )];
+        wallet.storage.add(&etype, &ename, &evalue, &etags).unwrap();
+
+        let report = wallet.verify(false).unwrap();
+
+        assert_eq!(1, report.get_corrupted().len());
+    }
+
+    #[test]
+    fn wallet_verify_works_for_quarantine() {
+        _cleanup();
+        let wallet = _create_wallet();
+
+        wallet.add("test", "name1", "value1", &HashMap::new()).unwrap();
+        _add_corrupted_value(&wallet, "test", "name2");
+
+        let report = wallet.verify(true).unwrap();
+        assert_eq!(1, report.get_corrupted().len());
+        assert_eq!(1, report.get_quarantined_count());
+
+        let res = wallet.get("test", "name2", &_fetch_options(false, true, false));
+        assert_match!(Err(WalletError::ItemNotFound), res);
+
+        let report = wallet.verify(false).unwrap();
+        assert_eq!(1, report.get_checked_count());
+        assert!(report.get_corrupted().is_empty());
+    }
 }
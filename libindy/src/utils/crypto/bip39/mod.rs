@@ -0,0 +1,88 @@
+use errors::common::CommonError;
+use utils::crypto::hash::Hash;
+
+// BIP-0039 mnemonic sentence validation against English word list.
+// Each word encodes 11 bits, last ENT / 32 bits of the sentence are checksum of entropy.
+
+const WORD_BITS: usize = 11;
+const WORDS: &'static str = include_str!("english.txt");
+
+/// Checks that all words of mnemonic are in the word list and checksum matches entropy.
+pub fn validate_mnemonic(mnemonic: &str) -> Result<(), CommonError> {
+    let words: Vec<&str> = mnemonic.split_whitespace().collect();
+
+    if words.len() < 12 || words.len() > 24 || words.len() % 3 != 0 {
+        return Err(CommonError::InvalidStructure(format!("Invalid number of mnemonic words: {}", words.len())));
+    }
+
+    let word_list: Vec<&str> = WORDS.lines().collect();
+
+    let mut bits: Vec<bool> = Vec::with_capacity(words.len() * WORD_BITS);
+
+    for word in words.iter() {
+        let index = word_list.binary_search(word)
+            .map_err(|_| CommonError::InvalidStructure(format!("Unknown mnemonic word: {}", word)))?;
+
+        for i in (0..WORD_BITS).rev() {
+            bits.push((index >> i) & 1 == 1);
+        }
+    }
+
+    let checksum_len = bits.len() / 33;
+    let entropy_len = bits.len() - checksum_len;
+
+    let entropy: Vec<u8> = bits[..entropy_len]
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0u8, |acc, bit| (acc << 1) | (*bit as u8)))
+        .collect();
+
+    let mut ctx = Hash::new_context()?;
+    ctx.update(&entropy)?;
+    let hash = ctx.finish2()?;
+
+    let checksum_matches = (0..checksum_len)
+        .all(|i| bits[entropy_len + i] == ((hash[i / 8] >> (7 - i % 8)) & 1 == 1));
+
+    if !checksum_matches {
+        return Err(CommonError::InvalidStructure(format!("Invalid mnemonic checksum")));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_list_works() {
+        let word_list: Vec<&str> = WORDS.lines().collect();
+        assert_eq!(1 << WORD_BITS, word_list.len());
+        assert_eq!("abandon", word_list[0]);
+        assert_eq!("zoo", word_list[2047]);
+    }
+
+    #[test]
+    fn validate_mnemonic_works() {
+        validate_mnemonic("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about").unwrap();
+        validate_mnemonic("legal winner thank year wave sausage worth useful legal winner thank yellow").unwrap();
+        validate_mnemonic("zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote").unwrap();
+    }
+
+    #[test]
+    fn validate_mnemonic_not_works_for_invalid_checksum() {
+        let res = validate_mnemonic("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon");
+        assert_match!(Err(CommonError::InvalidStructure(_)), res);
+    }
+
+    #[test]
+    fn validate_mnemonic_not_works_for_unknown_word() {
+        let res = validate_mnemonic("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abut");
+        assert_match!(Err(CommonError::InvalidStructure(_)), res);
+    }
+
+    #[test]
+    fn validate_mnemonic_not_works_for_invalid_words_count() {
+        assert_match!(Err(CommonError::InvalidStructure(_)), validate_mnemonic("abandon about"));
+    }
+}
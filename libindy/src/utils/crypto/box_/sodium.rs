@@ -51,10 +51,61 @@ impl CryptoBox {
             .map_err(|err| CommonError::InvalidStructure(format!("Unable to decrypt data: {:?}", err)))
     }
 
+    // Production code gets precomputed keys from external signer,
+    // local precomputation is only needed to check encrypt/decrypt_precomputed
+    #[cfg(test)]
+    pub fn precompute(private_key: &[u8], public_key: &[u8]) -> Result<Vec<u8>, CommonError> {
+        if private_key.len() != 32 || public_key.len() != 32 {
+            return Err(CommonError::InvalidStructure(format!("Invalid key")))
+        }
+
+        Ok(box_::precompute(
+            &box_::PublicKey(_clone_into_array(public_key)),
+            &box_::SecretKey(_clone_into_array(private_key))
+        ).0.to_vec())
+    }
+
+    pub fn encrypt_precomputed(precomputed_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CommonError> {
+        if nonce.len() != 24 {
+            return Err(CommonError::InvalidStructure(format!("Invalid nonce")))
+        }
+
+        if precomputed_key.len() != 32 {
+            return Err(CommonError::InvalidStructure(format!("Invalid precomputed key")))
+        }
+
+        Ok(box_::seal_precomputed(
+            doc,
+            &box_::Nonce(_clone_into_array(nonce)),
+            &box_::PrecomputedKey(_clone_into_array(precomputed_key))
+        ))
+    }
+
+    pub fn decrypt_precomputed(precomputed_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CommonError> {
+        if nonce.len() != 24 {
+            return Err(CommonError::InvalidStructure(format!("Invalid nonce")))
+        }
+
+        if precomputed_key.len() != 32 {
+            return Err(CommonError::InvalidStructure(format!("Invalid precomputed key")))
+        }
+
+        box_::open_precomputed(
+            doc,
+            &box_::Nonce(_clone_into_array(nonce)),
+            &box_::PrecomputedKey(_clone_into_array(precomputed_key))
+        )
+            .map_err(|err| CommonError::InvalidStructure(format!("Unable to decrypt data: {:?}", err)))
+    }
+
     pub fn gen_nonce() -> Vec<u8> {
         box_::gen_nonce()[..].to_vec()
     }
 
+    pub fn gen_random_bytes(len: usize) -> Vec<u8> {
+        randombytes::randombytes(len)
+    }
+
     pub fn create_key_pair_for_signature(seed: Option<&[u8]>) -> Result<(Vec<u8>, Vec<u8>), CommonError> {
         if seed.is_some() && seed.unwrap().len() != 32 {
             return Err(CommonError::InvalidStructure(format!("Invalid seed")));
@@ -164,6 +215,30 @@ mod tests {
         assert_eq!(text, alice_decrypted_text.unwrap());
     }
 
+    #[test]
+    fn encrypt_decrypt_precomputed_works() {
+        let text = randombytes::randombytes(16);
+        let nonce = CryptoBox::gen_nonce();
+
+        let (alice_ver_key, alice_sign_key) = CryptoBox::create_key_pair_for_signature(None).unwrap();
+        let alice_pk = CryptoBox::vk_to_curve25519(&alice_ver_key).unwrap();
+        let alice_sk = CryptoBox::sk_to_curve25519(&alice_sign_key).unwrap();
+
+        let (bob_ver_key, bob_sign_key) = CryptoBox::create_key_pair_for_signature(None).unwrap();
+        let bob_pk = CryptoBox::vk_to_curve25519(&bob_ver_key).unwrap();
+        let bob_sk = CryptoBox::sk_to_curve25519(&bob_sign_key).unwrap();
+
+        let alice_precomputed_key = CryptoBox::precompute(&alice_sk, &bob_pk).unwrap();
+        let encrypted_text = CryptoBox::encrypt_precomputed(&alice_precomputed_key, &text, &nonce).unwrap();
+
+        let decrypted_text = CryptoBox::decrypt(&bob_sk, &alice_pk, &encrypted_text, &nonce).unwrap();
+        assert_eq!(text, decrypted_text);
+
+        let bob_precomputed_key = CryptoBox::precompute(&bob_sk, &alice_pk).unwrap();
+        let decrypted_text = CryptoBox::decrypt_precomputed(&bob_precomputed_key, &encrypted_text, &nonce).unwrap();
+        assert_eq!(text, decrypted_text);
+    }
+
     #[test]
     fn signin_verify_works() {
         let seed = randombytes::randombytes(32);
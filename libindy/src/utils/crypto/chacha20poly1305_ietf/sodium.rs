@@ -18,7 +18,6 @@ impl ChaCha20Poly1305IETF {
         chacha20poly1305_ietf::gen_key()[..].to_vec()
     }
 
-    #[allow(dead_code)]
     pub fn gen_nonce() -> Vec<u8> {
         chacha20poly1305_ietf::gen_nonce()[..].to_vec()
     }
@@ -57,6 +56,35 @@ impl ChaCha20Poly1305IETF {
         result
     }
 
+    pub fn encrypt_detached(data: &[u8], key: &[u8], nonce: &[u8], aad: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let mut ct = chacha20poly1305_ietf::seal(
+            data,
+            Some(aad),
+            &chacha20poly1305_ietf::Nonce(_clone_into_array(nonce)),
+            &chacha20poly1305_ietf::Key(_clone_into_array(key))
+        );
+
+        let tag = ct.split_off(data.len());
+        (ct, tag)
+    }
+
+    pub fn decrypt_detached(enc_text: &[u8], tag: &[u8], key: &[u8], nonce: &[u8], aad: &[u8]) -> Result<Vec<u8>, CommonError> {
+        if tag.len() != chacha20poly1305_ietf::TAGBYTES || nonce.len() != chacha20poly1305_ietf::NONCEBYTES {
+            return Err(CommonError::InvalidStructure(format!("Unable to decrypt data: Invalid tag or nonce length")));
+        }
+
+        let mut ct = enc_text.to_vec();
+        ct.extend_from_slice(tag);
+
+        chacha20poly1305_ietf::open(
+            &ct,
+            Some(aad),
+            &chacha20poly1305_ietf::Nonce(_clone_into_array(nonce)),
+            &chacha20poly1305_ietf::Key(_clone_into_array(key))
+        )
+            .map_err(|err| CommonError::InvalidStructure(format!("Unable to decrypt data: {:?}", err)))
+    }
+
     pub fn decrypt(enc_text: &[u8], key: &[u8]) -> Result<Vec<u8>, CommonError> {
         if enc_text.len() <= chacha20poly1305_ietf::NONCEBYTES {
             return Err(CommonError::InvalidStructure(format!("Unable to decrypt data: Cyphertext too short")));
@@ -99,4 +127,27 @@ mod tests {
         let u = ChaCha20Poly1305IETF::decrypt(&c, &key).unwrap();
         assert_eq!(data, u);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn encrypt_detached_decrypt_detached_works() {
+        let data = randombytes::randombytes(100);
+        let key = ChaCha20Poly1305IETF::create_key();
+        let nonce = ChaCha20Poly1305IETF::gen_nonce();
+        let aad = b"some protected header";
+
+        let (c, tag) = ChaCha20Poly1305IETF::encrypt_detached(&data, &key, &nonce, aad);
+        let u = ChaCha20Poly1305IETF::decrypt_detached(&c, &tag, &key, &nonce, aad).unwrap();
+        assert_eq!(data, u);
+    }
+
+    #[test]
+    fn decrypt_detached_works_for_other_aad() {
+        let data = randombytes::randombytes(100);
+        let key = ChaCha20Poly1305IETF::create_key();
+        let nonce = ChaCha20Poly1305IETF::gen_nonce();
+
+        let (c, tag) = ChaCha20Poly1305IETF::encrypt_detached(&data, &key, &nonce, b"aad");
+        let res = ChaCha20Poly1305IETF::decrypt_detached(&c, &tag, &key, &nonce, b"other aad");
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,161 @@
+extern crate openssl;
+
+use errors::common::CommonError;
+
+use self::openssl::bn::{BigNum, BigNumContext};
+use self::openssl::ec::{EcGroup, EcKey, EcPoint, POINT_CONVERSION_COMPRESSED};
+use self::openssl::hash::MessageDigest;
+use self::openssl::nid;
+use self::openssl::pkey::PKey;
+use self::openssl::sign::{Signer, Verifier};
+
+pub const SEED_LEN: usize = 32;
+pub const PRIVATE_KEY_LEN: usize = 32;
+pub const PUBLIC_KEY_LEN: usize = 33;
+
+pub struct EcdsaSecp256k1 {}
+
+impl EcdsaSecp256k1 {
+    /// Returns (compressed public key, private scalar) pair.
+    /// Seed is used as private scalar, so it must be in range [1, n-1] of curve order.
+    pub fn create_key_pair(seed: Option<&[u8]>) -> Result<(Vec<u8>, Vec<u8>), CommonError> {
+        let group = EcdsaSecp256k1::_group()?;
+        let mut ctx = BigNumContext::new()?;
+
+        let key = match seed {
+            Some(seed) => {
+                if seed.len() != SEED_LEN {
+                    return Err(CommonError::InvalidStructure(format!("Invalid seed")));
+                }
+                EcdsaSecp256k1::_key_from_private(&group, seed)?
+            }
+            None => EcKey::generate(&group)?
+        };
+
+        let public_key = key.public_key()
+            .ok_or(CommonError::InvalidState(format!("Generated secp256k1 key has no public part")))?
+            .to_bytes(&group, POINT_CONVERSION_COMPRESSED, &mut ctx)?;
+        let private_key = key.private_key()
+            .ok_or(CommonError::InvalidState(format!("Generated secp256k1 key has no private part")))?
+            .to_vec();
+        let private_key = EcdsaSecp256k1::_to_fixed_len(private_key, PRIVATE_KEY_LEN);
+
+        Ok((public_key, private_key))
+    }
+
+    /// Produces DER encoded ECDSA signature of SHA-256 digest of doc.
+    pub fn sign(private_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, CommonError> {
+        if private_key.len() != PRIVATE_KEY_LEN {
+            return Err(CommonError::InvalidStructure(format!("Invalid sign key")));
+        }
+
+        let group = EcdsaSecp256k1::_group()?;
+        let key = PKey::from_ec_key(EcdsaSecp256k1::_key_from_private(&group, private_key)?)?;
+
+        let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+        signer.update(doc)?;
+
+        Ok(signer.sign_to_vec()?)
+    }
+
+    pub fn verify(public_key: &[u8], doc: &[u8], signature: &[u8]) -> Result<bool, CommonError> {
+        let group = EcdsaSecp256k1::_group()?;
+        let key = PKey::from_ec_key(EcdsaSecp256k1::_key_from_public(&group, public_key)?)?;
+
+        let mut verifier = Verifier::new(MessageDigest::sha256(), &key)?;
+        verifier.update(doc)?;
+
+        Ok(verifier.verify(signature)?)
+    }
+
+    pub fn validate_public_key(public_key: &[u8]) -> Result<(), CommonError> {
+        let group = EcdsaSecp256k1::_group()?;
+        EcdsaSecp256k1::_key_from_public(&group, public_key)?;
+        Ok(())
+    }
+
+    fn _group() -> Result<EcGroup, CommonError> {
+        Ok(EcGroup::from_curve_name(nid::SECP256K1)?)
+    }
+
+    fn _key_from_private(group: &EcGroup, private_key: &[u8]) -> Result<EcKey, CommonError> {
+        let mut ctx = BigNumContext::new()?;
+
+        let mut order = BigNum::new()?;
+        group.order(&mut order, &mut ctx)?;
+
+        let private_number = BigNum::from_slice(private_key)?;
+
+        if private_number.num_bits() == 0 || private_number >= order {
+            return Err(CommonError::InvalidStructure(format!("Private key is out of secp256k1 curve order")));
+        }
+
+        let mut public_point = EcPoint::new(group)?;
+        public_point.mul_generator(group, &private_number, &ctx)?;
+
+        let mut key = EcKey::from_public_key(group, &public_point)?;
+        key.set_private_key(&private_number)?;
+
+        Ok(key)
+    }
+
+    fn _key_from_public(group: &EcGroup, public_key: &[u8]) -> Result<EcKey, CommonError> {
+        if public_key.len() != PUBLIC_KEY_LEN {
+            return Err(CommonError::InvalidStructure(format!("Invalid verkey")));
+        }
+
+        let mut ctx = BigNumContext::new()?;
+        let public_point = EcPoint::from_bytes(group, public_key, &mut ctx)?;
+
+        Ok(EcKey::from_public_key(group, &public_point)?)
+    }
+
+    fn _to_fixed_len(mut bytes: Vec<u8>, len: usize) -> Vec<u8> {
+        // BigNum drops leading zeroes
+        while bytes.len() < len {
+            bytes.insert(0, 0);
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_key_pair_works() {
+        let (public_key, private_key) = EcdsaSecp256k1::create_key_pair(None).unwrap();
+        assert_eq!(PUBLIC_KEY_LEN, public_key.len());
+        assert_eq!(PRIVATE_KEY_LEN, private_key.len());
+    }
+
+    #[test]
+    fn create_key_pair_works_for_seed() {
+        let seed = [1u8; SEED_LEN];
+
+        let (public_key1, private_key1) = EcdsaSecp256k1::create_key_pair(Some(&seed)).unwrap();
+        let (public_key2, private_key2) = EcdsaSecp256k1::create_key_pair(Some(&seed)).unwrap();
+
+        assert_eq!(public_key1, public_key2);
+        assert_eq!(private_key1, private_key2);
+        assert_eq!(seed.to_vec(), private_key1);
+    }
+
+    #[test]
+    fn create_key_pair_works_for_out_of_order_seed() {
+        let seed = [0xFFu8; SEED_LEN];
+        let res = EcdsaSecp256k1::create_key_pair(Some(&seed));
+        assert_match!(Err(CommonError::InvalidStructure(_)), res);
+    }
+
+    #[test]
+    fn sign_verify_works() {
+        let doc = b"Test message";
+        let (public_key, private_key) = EcdsaSecp256k1::create_key_pair(None).unwrap();
+
+        let signature = EcdsaSecp256k1::sign(&private_key, doc).unwrap();
+        assert!(EcdsaSecp256k1::verify(&public_key, doc, &signature).unwrap());
+        assert!(!EcdsaSecp256k1::verify(&public_key, b"Other message", &signature).unwrap());
+    }
+}
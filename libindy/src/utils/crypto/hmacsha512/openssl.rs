@@ -0,0 +1,47 @@
+extern crate openssl;
+
+use errors::common::CommonError;
+
+use self::openssl::hash::MessageDigest;
+use self::openssl::pkcs5::pbkdf2_hmac;
+use self::openssl::pkey::PKey;
+use self::openssl::sign::Signer;
+
+pub const HMAC_OUTPUT_LEN: usize = 64;
+
+pub struct HMACSHA512 {}
+
+impl HMACSHA512 {
+    pub fn hmac(key: &[u8], data: &[u8]) -> Result<Vec<u8>, CommonError> {
+        let key = PKey::hmac(key)?;
+
+        let mut signer = Signer::new(MessageDigest::sha512(), &key)?;
+        signer.update(data)?;
+
+        Ok(signer.sign_to_vec()?)
+    }
+
+    pub fn pbkdf2(password: &[u8], salt: &[u8], iterations: usize, key_len: usize) -> Result<Vec<u8>, CommonError> {
+        let mut key = vec![0u8; key_len];
+        pbkdf2_hmac(password, salt, iterations, MessageDigest::sha512(), &mut key)?;
+        Ok(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_works() {
+        let mac = HMACSHA512::hmac(b"key", b"The quick brown fox jumps over the lazy dog").unwrap();
+        assert_eq!(HMAC_OUTPUT_LEN, mac.len());
+        assert_eq!(vec![0xb4, 0x2a, 0xf0, 0x90], mac[0..4].to_vec());
+    }
+
+    #[test]
+    fn pbkdf2_works() {
+        let key = HMACSHA512::pbkdf2(b"password", b"salt", 1, 64).unwrap();
+        assert_eq!(vec![0x86, 0x7f, 0x70, 0xcf], key[0..4].to_vec());
+    }
+}
@@ -15,12 +15,26 @@ pub mod xsalsa20;
 #[path = "chacha20poly1305_ietf/sodium.rs"]
 pub mod chacha20poly1305_ietf;
 
+#[cfg(feature = "ecdsa_secp256k1_openssl")]
+#[path = "ecdsa_secp256k1/openssl.rs"]
+pub mod ecdsa_secp256k1;
+
 #[cfg(feature = "hash_openssl")]
 #[path = "hash/openssl.rs"]
 pub mod hash;
 
+#[cfg(feature = "hmacsha512_openssl")]
+#[path = "hmacsha512/openssl.rs"]
+pub mod hmacsha512;
+
+#[cfg(feature = "hash_openssl")]
+pub mod bip39;
+
 pub mod signature_serializer;
 
+#[cfg(feature = "hmacsha512_openssl")]
+pub mod slip10;
+
 pub mod verkey_builder;
 
 
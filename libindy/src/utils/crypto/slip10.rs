@@ -0,0 +1,104 @@
+use errors::common::CommonError;
+use utils::crypto::hmacsha512::HMACSHA512;
+
+// SLIP-0010 hierarchical key derivation for ed25519 curve.
+// Only hardened derivation is defined for ed25519, so every path index is hardened.
+
+const ED25519_CURVE_KEY: &'static [u8] = b"ed25519 seed";
+const HARDENED_OFFSET: u32 = 0x80000000;
+
+pub const KEY_LEN: usize = 32;
+
+/// Derives 32 bytes ed25519 private key (seed) for path like "m/44'/0'/1'".
+pub fn derive_ed25519_key(master_seed: &[u8], path: &str) -> Result<Vec<u8>, CommonError> {
+    let indexes = parse_path(path)?;
+
+    let (mut key, mut chain_code) = _split(HMACSHA512::hmac(ED25519_CURVE_KEY, master_seed)?);
+
+    for index in indexes {
+        let mut data = Vec::with_capacity(1 + KEY_LEN + 4);
+        data.push(0x00);
+        data.extend_from_slice(&key);
+        data.extend_from_slice(&_ser32(index | HARDENED_OFFSET));
+
+        let (child_key, child_chain_code) = _split(HMACSHA512::hmac(&chain_code, &data)?);
+        key = child_key;
+        chain_code = child_chain_code;
+    }
+
+    Ok(key)
+}
+
+/// Parses derivation path to the list of not offset indexes.
+pub fn parse_path(path: &str) -> Result<Vec<u32>, CommonError> {
+    let mut parts = path.split('/');
+
+    if parts.next() != Some("m") {
+        return Err(CommonError::InvalidStructure(format!("Derivation path must start with \"m\": {}", path)));
+    }
+
+    parts
+        .map(|part| {
+            let index = if part.ends_with("'") || part.ends_with("H") {
+                &part[..part.len() - 1]
+            } else {
+                return Err(CommonError::InvalidStructure(
+                    format!("Only hardened derivation is supported for ed25519 keys: {}", path)));
+            };
+
+            match index.parse::<u32>() {
+                Ok(index) if index < HARDENED_OFFSET => Ok(index),
+                _ => Err(CommonError::InvalidStructure(format!("Invalid derivation path index: {}", part)))
+            }
+        })
+        .collect()
+}
+
+fn _split(mut bytes: Vec<u8>) -> (Vec<u8>, Vec<u8>) {
+    let chain_code = bytes.split_off(KEY_LEN);
+    (bytes, chain_code)
+}
+
+fn _ser32(index: u32) -> [u8; 4] {
+    [(index >> 24) as u8, (index >> 16) as u8, (index >> 8) as u8, index as u8]
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate hex;
+
+    use super::*;
+    use self::hex::FromHex;
+
+    // SLIP-0010 test vector 1 for ed25519
+    const SEED: &'static str = "000102030405060708090a0b0c0d0e0f";
+
+    #[test]
+    fn derive_ed25519_key_works_for_master() {
+        let key = derive_ed25519_key(&Vec::<u8>::from_hex(SEED).unwrap(), "m").unwrap();
+        assert_eq!(Vec::<u8>::from_hex("2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7").unwrap(), key);
+    }
+
+    #[test]
+    fn derive_ed25519_key_works_for_path() {
+        let key = derive_ed25519_key(&Vec::<u8>::from_hex(SEED).unwrap(), "m/0'").unwrap();
+        assert_eq!(Vec::<u8>::from_hex("68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3").unwrap(), key);
+
+        let key = derive_ed25519_key(&Vec::<u8>::from_hex(SEED).unwrap(), "m/0H/1H").unwrap();
+        assert_eq!(Vec::<u8>::from_hex("b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2").unwrap(), key);
+    }
+
+    #[test]
+    fn parse_path_works() {
+        assert_eq!(Vec::<u32>::new(), parse_path("m").unwrap());
+        assert_eq!(vec![44, 0, 1], parse_path("m/44'/0'/1H").unwrap());
+    }
+
+    #[test]
+    fn parse_path_not_works_for_invalid_path() {
+        assert_match!(Err(CommonError::InvalidStructure(_)), parse_path("44'/0'"));
+        assert_match!(Err(CommonError::InvalidStructure(_)), parse_path("m/44'/0"));
+        assert_match!(Err(CommonError::InvalidStructure(_)), parse_path("m/abc'"));
+        assert_match!(Err(CommonError::InvalidStructure(_)), parse_path("m/2147483648'"));
+    }
+}
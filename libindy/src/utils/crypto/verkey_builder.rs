@@ -1,5 +1,6 @@
 use errors::common::CommonError;
 use utils::crypto::base58::Base58;
+use utils::qualifier;
 
 
 pub fn build_full_verkey(dest: &str, verkey: Option<&str>) -> Result<String, CommonError> {
@@ -12,7 +13,7 @@ pub fn build_full_verkey(dest: &str, verkey: Option<&str>) -> Result<String, Com
         };
 
         let verkey = if verkey.starts_with("~") {
-            let mut result = Base58::decode(dest)?;
+            let mut result = Base58::decode(qualifier::unqualify(dest))?;
             let mut end = Base58::decode(&verkey[1..])?;
             result.append(&mut end);
             Base58::encode(&result)
@@ -29,6 +30,6 @@ pub fn build_full_verkey(dest: &str, verkey: Option<&str>) -> Result<String, Com
         Ok(verkey)
     } else {
         // Cryptonym
-        Ok(dest.to_owned())
+        Ok(qualifier::unqualify(dest).to_owned())
     }
 }
@@ -22,6 +22,8 @@ pub mod result;
 
 pub mod sequence;
 
+pub mod qualifier;
+
 #[cfg(test)]
 #[macro_use]
 pub mod test;
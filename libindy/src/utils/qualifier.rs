@@ -0,0 +1,66 @@
+// Fully qualified DID has form did:<method_name>:<method_specific_id>
+const PREFIX: &'static str = "did";
+const DELIMITER: char = ':';
+
+pub fn qualify(did: &str, method: &str) -> String {
+    format!("{}{}{}{}{}", PREFIX, DELIMITER, method, DELIMITER, unqualify(did))
+}
+
+pub fn unqualify(did: &str) -> &str {
+    split(did).1
+}
+
+pub fn method(did: &str) -> Option<&str> {
+    split(did).0
+}
+
+pub fn is_valid_method_name(method: &str) -> bool {
+    !method.is_empty() && method.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+}
+
+fn split(did: &str) -> (Option<&str>, &str) {
+    let parts: Vec<&str> = did.splitn(3, DELIMITER).collect();
+
+    if parts.len() == 3 && parts[0] == PREFIX {
+        (Some(parts[1]), parts[2])
+    } else {
+        (None, did)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn qualify_works() {
+        assert_eq!("did:sov:NcYxiDXkpYi6ov5FcYDi1e", qualify("NcYxiDXkpYi6ov5FcYDi1e", "sov"));
+    }
+
+    #[test]
+    fn qualify_works_for_qualified_did() {
+        assert_eq!("did:peer:NcYxiDXkpYi6ov5FcYDi1e", qualify("did:sov:NcYxiDXkpYi6ov5FcYDi1e", "peer"));
+    }
+
+    #[test]
+    fn unqualify_works() {
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e", unqualify("did:sov:NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e", unqualify("NcYxiDXkpYi6ov5FcYDi1e"));
+    }
+
+    #[test]
+    fn method_works() {
+        assert_eq!(Some("sov"), method("did:sov:NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!(None, method("NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!(None, method("sov:NcYxiDXkpYi6ov5FcYDi1e"));
+    }
+
+    #[test]
+    fn is_valid_method_name_works() {
+        assert!(is_valid_method_name("sov"));
+        assert!(is_valid_method_name("v2ledger"));
+        assert!(!is_valid_method_name(""));
+        assert!(!is_valid_method_name("Sov"));
+        assert!(!is_valid_method_name("my-ledger"));
+    }
+}
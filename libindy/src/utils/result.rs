@@ -1,8 +1,14 @@
 macro_rules! result_to_err_code {
     ($result:ident) => {
         match $result {
-            Ok(_) => ErrorCode::Success,
-            Err(err) => err.to_error_code()
+            Ok(_) => {
+                ::errors::set_current_error_details(None);
+                ErrorCode::Success
+            }
+            Err(err) => {
+                ::errors::set_current_error_details(err.error_details());
+                err.to_error_code()
+            }
         };
     }
 }
@@ -10,8 +16,14 @@ macro_rules! result_to_err_code {
 macro_rules! result_to_err_code_1 {
     ($result:ident, $default_value:expr) => {
         match $result {
-            Ok(res) => (ErrorCode::Success, res),
-            Err(err) => (err.to_error_code(), $default_value)
+            Ok(res) => {
+                ::errors::set_current_error_details(None);
+                (ErrorCode::Success, res)
+            }
+            Err(err) => {
+                ::errors::set_current_error_details(err.error_details());
+                (err.to_error_code(), $default_value)
+            }
         };
     }
 }
@@ -19,8 +31,14 @@ macro_rules! result_to_err_code_1 {
 macro_rules! result_to_err_code_2 {
     ($result:ident, $default_value1:expr, $default_value2:expr) => {
         match $result {
-            Ok((res1, res2)) => (ErrorCode::Success, res1, res2),
-            Err(err) => (err.to_error_code(), $default_value1, $default_value2)
+            Ok((res1, res2)) => {
+                ::errors::set_current_error_details(None);
+                (ErrorCode::Success, res1, res2)
+            }
+            Err(err) => {
+                ::errors::set_current_error_details(err.error_details());
+                (err.to_error_code(), $default_value1, $default_value2)
+            }
         };
     }
 }
@@ -28,8 +46,14 @@ macro_rules! result_to_err_code_2 {
 macro_rules! result_to_err_code_3 {
     ($result:ident, $default_value1:expr, $default_value2:expr, $default_value3:expr) => {
         match $result {
-            Ok((res1, res2, res3)) => (ErrorCode::Success, res1, res2, res3),
-            Err(err) => (err.to_error_code(), $default_value1, $default_value2, $default_value3)
+            Ok((res1, res2, res3)) => {
+                ::errors::set_current_error_details(None);
+                (ErrorCode::Success, res1, res2, res3)
+            }
+            Err(err) => {
+                ::errors::set_current_error_details(err.error_details());
+                (err.to_error_code(), $default_value1, $default_value2, $default_value3)
+            }
         };
     }
 }
@@ -37,8 +61,14 @@ macro_rules! result_to_err_code_3 {
 macro_rules! result_to_err_code_4 {
     ($result:ident, $default_value1:expr, $default_value2:expr, $default_value3:expr, $default_value4:expr) => {
         match $result {
-            Ok((res1, res2, res3, res4)) => (ErrorCode::Success, res1, res2, res3, res4),
-            Err(err) => (err.to_error_code(), $default_value1, $default_value2, $default_value3, $default_value4)
+            Ok((res1, res2, res3, res4)) => {
+                ::errors::set_current_error_details(None);
+                (ErrorCode::Success, res1, res2, res3, res4)
+            }
+            Err(err) => {
+                ::errors::set_current_error_details(err.error_details());
+                (err.to_error_code(), $default_value1, $default_value2, $default_value3, $default_value4)
+            }
         };
     }
 }
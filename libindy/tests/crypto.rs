@@ -25,6 +25,9 @@ use utils::crypto::CryptoUtils;
 use utils::did::DidUtils;
 use utils::test::TestUtils;
 use utils::constants::*;
+use utils::xor_crypto_type::XOR_CRYPTO_TYPE;
+use utils::external_signer::{SoftwareSigner, SOFTWARE_SIGNER};
+use utils::ledger::LedgerUtils;
 
 use indy::api::ErrorCode;
 
@@ -66,14 +69,451 @@ mod high_cases {
             TestUtils::cleanup_storage();
         }
 
+        #[test]
+        fn indy_create_key_works_for_secp256k1() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let verkey = CryptoUtils::create_key_with_crypto_type(wallet_handle, Some(MY1_SEED), "secp256k1").unwrap();
+            assert!(verkey.ends_with(":secp256k1"));
+
+            let (verkey, _) = verkey.split_at(verkey.len() - ":secp256k1".len());
+            assert_eq!(verkey.from_base58().unwrap().len(), 33);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_key_works_for_unknown_crypto_type() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = CryptoUtils::create_key_with_crypto_type(wallet_handle, None, "unknown_crypto_type");
+            assert_eq!(ErrorCode::UnknownCryptoTypeError, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
         #[test]
         fn indy_create_key_works_for_invalid_wallet_handle() {
             TestUtils::cleanup_storage();
 
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let res = CryptoUtils::create_key(wallet_handle + 1, None);
-            assert_eq!(ErrorCode::WalletInvalidHandle, res.unwrap_err());
+            let res = CryptoUtils::create_key(wallet_handle + 1, None);
+            assert_eq!(ErrorCode::WalletInvalidHandle, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod register_crypto_type {
+        use super::*;
+
+        #[test]
+        fn indy_register_crypto_type_works_for_sign_verify() {
+            TestUtils::cleanup_storage();
+            CryptoUtils::register_xor_crypto_type();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let my_vk = CryptoUtils::create_key_with_crypto_type(wallet_handle, Some(MY1_SEED), XOR_CRYPTO_TYPE).unwrap();
+            assert!(my_vk.ends_with(":xor"));
+
+            let signature = CryptoUtils::sign(wallet_handle, &my_vk, MESSAGE.as_bytes()).unwrap();
+
+            assert!(CryptoUtils::verify(&my_vk, MESSAGE.as_bytes(), &signature).unwrap());
+            assert!(!CryptoUtils::verify(&my_vk, "Other message".as_bytes(), &signature).unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_register_crypto_type_works_for_anon_crypt() {
+            TestUtils::cleanup_storage();
+            CryptoUtils::register_xor_crypto_type();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let my_vk = CryptoUtils::create_key_with_crypto_type(wallet_handle, None, XOR_CRYPTO_TYPE).unwrap();
+
+            let encrypted_msg = CryptoUtils::anon_crypt(&my_vk, MESSAGE.as_bytes()).unwrap();
+            let decrypted_msg = CryptoUtils::anon_decrypt(wallet_handle, &my_vk, &encrypted_msg).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), decrypted_msg);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_register_crypto_type_works_for_create_my_did() {
+            TestUtils::cleanup_storage();
+            CryptoUtils::register_xor_crypto_type();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (did, verkey) = DidUtils::create_my_did(wallet_handle, &format!(r#"{{"crypto_type":"{}"}}"#, XOR_CRYPTO_TYPE)).unwrap();
+            assert!(verkey.ends_with(":xor"));
+
+            let signature = CryptoUtils::sign(wallet_handle, &verkey, MESSAGE.as_bytes()).unwrap();
+            assert!(CryptoUtils::verify(&verkey, MESSAGE.as_bytes(), &signature).unwrap());
+
+            assert_eq!(verkey, DidUtils::key_for_local_did(wallet_handle, &did).unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_register_crypto_type_works_for_auth_crypt_not_supported() {
+            TestUtils::cleanup_storage();
+            CryptoUtils::register_xor_crypto_type();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let my_vk = CryptoUtils::create_key_with_crypto_type(wallet_handle, None, XOR_CRYPTO_TYPE).unwrap();
+
+            let res = CryptoUtils::auth_crypt(wallet_handle, &my_vk, &my_vk, MESSAGE.as_bytes());
+            assert_eq!(ErrorCode::CommonInvalidState, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_register_crypto_type_works_for_already_registered_type() {
+            let res = CryptoUtils::register_crypto_type("ed25519");
+            assert_eq!(ErrorCode::CryptoTypeAlreadyRegisteredError, res.unwrap_err());
+
+            let res = CryptoUtils::register_crypto_type("secp256k1");
+            assert_eq!(ErrorCode::CryptoTypeAlreadyRegisteredError, res.unwrap_err());
+        }
+
+        #[test]
+        fn indy_register_crypto_type_works_for_invalid_name() {
+            let res = CryptoUtils::register_crypto_type("invalid:name");
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+        }
+    }
+
+    mod external_signer {
+        use super::*;
+
+        fn _external_key_json(key_ref: &str, verkey: &str) -> String {
+            json!({"signer": SOFTWARE_SIGNER, "key_ref": key_ref, "verkey": verkey}).to_string()
+        }
+
+        #[test]
+        fn indy_external_signer_works_for_sign_verify() {
+            TestUtils::cleanup_storage();
+            CryptoUtils::register_software_signer();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let verkey = SoftwareSigner::generate_key("sign_verify_key");
+            let my_vk = CryptoUtils::create_external_key(wallet_handle, &_external_key_json("sign_verify_key", &verkey)).unwrap();
+            assert_eq!(verkey, my_vk);
+
+            let signature = CryptoUtils::sign(wallet_handle, &my_vk, MESSAGE.as_bytes()).unwrap();
+            assert!(CryptoUtils::verify(&my_vk, MESSAGE.as_bytes(), &signature).unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_external_signer_works_for_auth_crypt_decrypt() {
+            TestUtils::cleanup_storage();
+            CryptoUtils::register_software_signer();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let verkey = SoftwareSigner::generate_key("auth_crypt_key");
+            let external_vk = CryptoUtils::create_external_key(wallet_handle, &_external_key_json("auth_crypt_key", &verkey)).unwrap();
+            let software_vk = CryptoUtils::create_key(wallet_handle, None).unwrap();
+
+            let encrypted_msg = CryptoUtils::auth_crypt(wallet_handle, &external_vk, &software_vk, MESSAGE.as_bytes()).unwrap();
+            let (sender_vk, decrypted_msg) = CryptoUtils::auth_decrypt(wallet_handle, &software_vk, &encrypted_msg).unwrap();
+            assert_eq!(external_vk, sender_vk);
+            assert_eq!(MESSAGE.as_bytes().to_vec(), decrypted_msg);
+
+            let encrypted_msg = CryptoUtils::auth_crypt(wallet_handle, &software_vk, &external_vk, MESSAGE.as_bytes()).unwrap();
+            let (sender_vk, decrypted_msg) = CryptoUtils::auth_decrypt(wallet_handle, &external_vk, &encrypted_msg).unwrap();
+            assert_eq!(software_vk, sender_vk);
+            assert_eq!(MESSAGE.as_bytes().to_vec(), decrypted_msg);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_external_signer_works_for_anon_decrypt() {
+            TestUtils::cleanup_storage();
+            CryptoUtils::register_software_signer();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let verkey = SoftwareSigner::generate_key("anon_decrypt_key");
+            let my_vk = CryptoUtils::create_external_key(wallet_handle, &_external_key_json("anon_decrypt_key", &verkey)).unwrap();
+
+            let encrypted_msg = CryptoUtils::anon_crypt(&my_vk, MESSAGE.as_bytes()).unwrap();
+            let decrypted_msg = CryptoUtils::anon_decrypt(wallet_handle, &my_vk, &encrypted_msg).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), decrypted_msg);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_external_signer_works_for_sign_request() {
+            TestUtils::cleanup_storage();
+            CryptoUtils::register_software_signer();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let verkey = SoftwareSigner::generate_key("sign_request_key");
+            let my_did_json = json!({"external_key": {"signer": SOFTWARE_SIGNER, "key_ref": "sign_request_key", "verkey": verkey}}).to_string();
+            let (my_did, my_vk) = DidUtils::create_my_did(wallet_handle, &my_did_json).unwrap();
+            assert_eq!(verkey, my_vk);
+
+            let signed_request = LedgerUtils::sign_request(wallet_handle, &my_did, REQUEST).unwrap();
+            let signed_request: serde_json::Value = serde_json::from_str(&signed_request).unwrap();
+            assert!(signed_request["signature"].is_string());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_external_signer_works_for_seed_and_external_key() {
+            TestUtils::cleanup_storage();
+            CryptoUtils::register_software_signer();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let verkey = SoftwareSigner::generate_key("seed_key");
+            let my_did_json = json!({"seed": MY1_SEED, "external_key": {"signer": SOFTWARE_SIGNER, "key_ref": "seed_key", "verkey": verkey}}).to_string();
+            let res = DidUtils::create_my_did(wallet_handle, &my_did_json);
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_external_key_works_for_unknown_signer() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let key_json = json!({"signer": "unknown_signer", "key_ref": "key", "verkey": VERKEY_MY1}).to_string();
+            let res = CryptoUtils::create_external_key(wallet_handle, &key_json);
+            assert_eq!(ErrorCode::UnknownCryptoTypeError, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_register_external_signer_works_for_already_registered() {
+            CryptoUtils::register_software_signer();
+
+            let res = CryptoUtils::register_external_signer(SOFTWARE_SIGNER);
+            assert_eq!(ErrorCode::ExternalSignerAlreadyRegisteredError, res.unwrap_err());
+        }
+    }
+
+    mod key_derivation {
+        use super::*;
+
+        const MNEMONIC: &'static str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        #[test]
+        fn indy_create_key_from_path_works_for_same_path() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let master_seed_id = CryptoUtils::create_master_seed(wallet_handle, None, None).unwrap();
+
+            let verkey = CryptoUtils::create_key_from_path(wallet_handle, &master_seed_id, "m/44'/0'/1'").unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let master_seed_id = CryptoUtils::create_master_seed(wallet_handle, None, Some(&json!({"seed": MY1_SEED}).to_string())).unwrap();
+            let verkey_1 = CryptoUtils::create_key_from_path(wallet_handle, &master_seed_id, "m/44'/0'/1'").unwrap();
+            assert_ne!(verkey, verkey_1);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let master_seed_id = CryptoUtils::create_master_seed(wallet_handle, None, Some(&json!({"seed": MY1_SEED}).to_string())).unwrap();
+            let verkey_2 = CryptoUtils::create_key_from_path(wallet_handle, &master_seed_id, "m/44H/0H/1H").unwrap();
+            assert_eq!(verkey_1, verkey_2);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_key_from_path_works_for_different_paths() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let master_seed_id = CryptoUtils::create_master_seed(wallet_handle, Some("master_seed"), None).unwrap();
+            assert_eq!("master_seed", master_seed_id);
+
+            let verkey_1 = CryptoUtils::create_key_from_path(wallet_handle, &master_seed_id, "m/0'").unwrap();
+            let verkey_2 = CryptoUtils::create_key_from_path(wallet_handle, &master_seed_id, "m/1'").unwrap();
+            assert_ne!(verkey_1, verkey_2);
+
+            let signature = CryptoUtils::sign(wallet_handle, &verkey_1, MESSAGE.as_bytes()).unwrap();
+            assert!(CryptoUtils::verify(&verkey_1, MESSAGE.as_bytes(), &signature).unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_key_from_path_works_for_metadata() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let master_seed_id = CryptoUtils::create_master_seed(wallet_handle, None, None).unwrap();
+            let verkey = CryptoUtils::create_key_from_path(wallet_handle, &master_seed_id, "m/44'/0'/1'").unwrap();
+
+            let metadata = CryptoUtils::get_key_metadata(wallet_handle, &verkey).unwrap();
+            let metadata: serde_json::Value = serde_json::from_str(&metadata).unwrap();
+            assert_eq!(json!({"master_seed_id": master_seed_id, "path": "m/44'/0'/1'"}), metadata);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_master_seed_works_for_mnemonic_restore() {
+            TestUtils::cleanup_storage();
+
+            let config = json!({"mnemonic": MNEMONIC, "passphrase": "TREZOR"}).to_string();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let master_seed_id = CryptoUtils::create_master_seed(wallet_handle, None, Some(&config)).unwrap();
+            let verkey = CryptoUtils::create_key_from_path(wallet_handle, &master_seed_id, "m/44'/0'").unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let master_seed_id = CryptoUtils::create_master_seed(wallet_handle, None, Some(&config)).unwrap();
+            let restored_verkey = CryptoUtils::create_key_from_path(wallet_handle, &master_seed_id, "m/44'/0'").unwrap();
+            assert_eq!(verkey, restored_verkey);
+
+            let master_seed_id = CryptoUtils::create_master_seed(wallet_handle, None, Some(&json!({"mnemonic": MNEMONIC}).to_string())).unwrap();
+            let other_verkey = CryptoUtils::create_key_from_path(wallet_handle, &master_seed_id, "m/44'/1'").unwrap();
+            assert_ne!(verkey, other_verkey);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_master_seed_works_for_invalid_mnemonic_checksum() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+            let res = CryptoUtils::create_master_seed(wallet_handle, None, Some(&json!({"mnemonic": mnemonic}).to_string()));
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_master_seed_works_for_seed_and_mnemonic() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = CryptoUtils::create_master_seed(wallet_handle, None, Some(&json!({"seed": MY1_SEED, "mnemonic": MNEMONIC}).to_string()));
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_master_seed_works_for_duplicate_id() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            CryptoUtils::create_master_seed(wallet_handle, Some("master_seed"), None).unwrap();
+
+            let res = CryptoUtils::create_master_seed(wallet_handle, Some("master_seed"), None);
+            assert_eq!(ErrorCode::WalletItemAlreadyExists, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_key_from_path_works_for_non_hardened_path() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let master_seed_id = CryptoUtils::create_master_seed(wallet_handle, None, None).unwrap();
+
+            let res = CryptoUtils::create_key_from_path(wallet_handle, &master_seed_id, "m/44'/0");
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_key_from_path_works_for_unknown_master_seed() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = CryptoUtils::create_key_from_path(wallet_handle, "unknown_master_seed", "m/44'/0'");
+            assert_eq!(ErrorCode::WalletItemNotFound, res.unwrap_err());
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
@@ -261,6 +701,24 @@ mod high_cases {
             TestUtils::cleanup_storage();
         }
 
+        #[test]
+        fn indy_crypto_sign_verify_works_for_secp256k1() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let my_vk = CryptoUtils::create_key_with_crypto_type(wallet_handle, None, "secp256k1").unwrap();
+
+            let signature = CryptoUtils::sign(wallet_handle, &my_vk, MESSAGE.as_bytes()).unwrap();
+
+            assert!(CryptoUtils::verify(&my_vk, MESSAGE.as_bytes(), &signature).unwrap());
+            assert!(!CryptoUtils::verify(&my_vk, "Other message".as_bytes(), &signature).unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
         #[test]
         fn indy_crypto_sign_works_for_unknow_signer() {
             TestUtils::cleanup_storage();
@@ -330,6 +788,174 @@ mod high_cases {
         }
     }
 
+    mod jws {
+        use super::*;
+
+        const PAYLOAD: &'static str = r#"{"iss":"agent","nonce":"1496822211362017764"}"#;
+
+        fn _decode_part(part: &str) -> serde_json::Value {
+            let padding = (4 - part.len() % 4) % 4;
+            let part = base64::decode_config(&format!("{}{}", part, "=".repeat(padding)), base64::URL_SAFE).unwrap();
+            serde_json::from_slice(&part).unwrap()
+        }
+
+        #[test]
+        fn indy_crypto_sign_jws_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let verkey = CryptoUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let jws = CryptoUtils::sign_jws(wallet_handle, &verkey, PAYLOAD, None).unwrap();
+
+            let parts: Vec<&str> = jws.split('.').collect();
+            assert_eq!(3, parts.len());
+            assert!(!parts.iter().any(|part| part.contains('=')));
+            assert_eq!(json!({"alg": "EdDSA", "kid": verkey}), _decode_part(parts[0]));
+            assert_eq!(serde_json::from_str::<serde_json::Value>(PAYLOAD).unwrap(), _decode_part(parts[1]));
+
+            assert!(CryptoUtils::verify_jws(wallet_handle, &jws, None, None).unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_crypto_verify_jws_works_for_their_did_kid() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let verkey = CryptoUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+            DidUtils::store_their_did_from_parts(wallet_handle, DID_MY1, VERKEY_MY1).unwrap();
+
+            let options = json!({"kid": format!("{}#key-1", DID_MY1)}).to_string();
+            let jws = CryptoUtils::sign_jws(wallet_handle, &verkey, PAYLOAD, Some(&options)).unwrap();
+
+            assert!(CryptoUtils::verify_jws(wallet_handle, &jws, None, None).unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_crypto_verify_jws_works_for_unknown_did_kid() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let verkey = CryptoUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let options = json!({"kid": DID_MY1}).to_string();
+            let jws = CryptoUtils::sign_jws(wallet_handle, &verkey, PAYLOAD, Some(&options)).unwrap();
+
+            assert_eq!(ErrorCode::WalletItemNotFound, CryptoUtils::verify_jws(wallet_handle, &jws, None, None).unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_crypto_verify_jws_works_for_detached_payload() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let verkey = CryptoUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let jws = CryptoUtils::sign_jws(wallet_handle, &verkey, PAYLOAD, Some(r#"{"detached":true}"#)).unwrap();
+            assert!(jws.contains(".."));
+
+            assert!(CryptoUtils::verify_jws(wallet_handle, &jws, Some(PAYLOAD), None).unwrap());
+            assert!(!CryptoUtils::verify_jws(wallet_handle, &jws, Some(r#"{"iss":"other"}"#), None).unwrap());
+            assert_eq!(ErrorCode::CommonInvalidStructure, CryptoUtils::verify_jws(wallet_handle, &jws, None, None).unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_crypto_verify_jws_works_for_other_signer() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let verkey = CryptoUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+            DidUtils::store_their_did_from_parts(wallet_handle, DID_MY2, VERKEY_MY2).unwrap();
+
+            let options = json!({"kid": DID_MY2}).to_string();
+            let jws = CryptoUtils::sign_jws(wallet_handle, &verkey, PAYLOAD, Some(&options)).unwrap();
+
+            assert!(!CryptoUtils::verify_jws(wallet_handle, &jws, None, None).unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_crypto_verify_jws_works_for_unknown_verkey_kid() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let verkey = CryptoUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+            let jws = CryptoUtils::sign_jws(wallet_handle, &verkey, PAYLOAD, None).unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+
+            // self-signed JWS must not be trusted just because its header references the signing key
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            assert_eq!(ErrorCode::WalletItemNotFound, CryptoUtils::verify_jws(wallet_handle, &jws, None, None).unwrap_err());
+            assert!(CryptoUtils::verify_jws(wallet_handle, &jws, None, Some(&verkey)).unwrap());
+            assert!(!CryptoUtils::verify_jws(wallet_handle, &jws, None, Some(VERKEY_MY2)).unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_crypto_sign_jws_works_for_invalid_payload() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let verkey = CryptoUtils::create_key(wallet_handle, None).unwrap();
+
+            let res = CryptoUtils::sign_jws(wallet_handle, &verkey, "not a json", None);
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_crypto_sign_jws_works_for_secp256k1_key() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let verkey = CryptoUtils::create_key_with_crypto_type(wallet_handle, None, "secp256k1").unwrap();
+
+            let res = CryptoUtils::sign_jws(wallet_handle, &verkey, PAYLOAD, None);
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
     mod auth_crypt {
         use super::*;
 
@@ -610,6 +1236,156 @@ mod high_cases {
             TestUtils::cleanup_storage();
         }
     }
+
+    mod pack_message {
+        use super::*;
+
+        fn _unpack(wallet_handle: i32, jwe: &[u8]) -> serde_json::Value {
+            let res = CryptoUtils::unpack_message(wallet_handle, jwe).unwrap();
+            serde_json::from_slice(&res).unwrap()
+        }
+
+        #[test]
+        fn indy_pack_message_works_for_anoncrypt() {
+            TestUtils::cleanup_storage();
+
+            let sender_wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let recipient_wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let verkey = CryptoUtils::create_key(recipient_wallet_handle, Some(MY2_SEED)).unwrap();
+            let receiver_keys = json!([verkey]).to_string();
+
+            let jwe = CryptoUtils::pack_message(sender_wallet_handle, MESSAGE.as_bytes(), &receiver_keys, None).unwrap();
+
+            let unpacked = _unpack(recipient_wallet_handle, &jwe);
+            assert_eq!(MESSAGE, unpacked["message"].as_str().unwrap());
+            assert_eq!(verkey, unpacked["recipient_verkey"].as_str().unwrap());
+            assert!(unpacked["sender_verkey"].is_null());
+
+            WalletUtils::close_wallet(sender_wallet_handle).unwrap();
+            WalletUtils::close_wallet(recipient_wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_pack_message_works_for_authcrypt() {
+            TestUtils::cleanup_storage();
+
+            let sender_wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let recipient_wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let sender_vk = CryptoUtils::create_key(sender_wallet_handle, Some(MY1_SEED)).unwrap();
+            let verkey = CryptoUtils::create_key(recipient_wallet_handle, Some(MY2_SEED)).unwrap();
+            let receiver_keys = json!([verkey]).to_string();
+
+            let jwe = CryptoUtils::pack_message(sender_wallet_handle, MESSAGE.as_bytes(), &receiver_keys, Some(&sender_vk)).unwrap();
+
+            let unpacked = _unpack(recipient_wallet_handle, &jwe);
+            assert_eq!(MESSAGE, unpacked["message"].as_str().unwrap());
+            assert_eq!(verkey, unpacked["recipient_verkey"].as_str().unwrap());
+            assert_eq!(sender_vk, unpacked["sender_verkey"].as_str().unwrap());
+
+            WalletUtils::close_wallet(sender_wallet_handle).unwrap();
+            WalletUtils::close_wallet(recipient_wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_pack_message_works_for_multiple_recipients() {
+            TestUtils::cleanup_storage();
+
+            let sender_wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let recipient_wallet_handle_1 = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let recipient_wallet_handle_2 = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let sender_vk = CryptoUtils::create_key(sender_wallet_handle, Some(MY1_SEED)).unwrap();
+            let verkey_1 = CryptoUtils::create_key(recipient_wallet_handle_1, Some(MY2_SEED)).unwrap();
+            let verkey_2 = CryptoUtils::create_key(recipient_wallet_handle_2, None).unwrap();
+            let receiver_keys = json!([verkey_1, verkey_2]).to_string();
+
+            let jwe = CryptoUtils::pack_message(sender_wallet_handle, MESSAGE.as_bytes(), &receiver_keys, Some(&sender_vk)).unwrap();
+
+            let unpacked = _unpack(recipient_wallet_handle_1, &jwe);
+            assert_eq!(MESSAGE, unpacked["message"].as_str().unwrap());
+            assert_eq!(verkey_1, unpacked["recipient_verkey"].as_str().unwrap());
+            assert_eq!(sender_vk, unpacked["sender_verkey"].as_str().unwrap());
+
+            let unpacked = _unpack(recipient_wallet_handle_2, &jwe);
+            assert_eq!(MESSAGE, unpacked["message"].as_str().unwrap());
+            assert_eq!(verkey_2, unpacked["recipient_verkey"].as_str().unwrap());
+            assert_eq!(sender_vk, unpacked["sender_verkey"].as_str().unwrap());
+
+            WalletUtils::close_wallet(sender_wallet_handle).unwrap();
+            WalletUtils::close_wallet(recipient_wallet_handle_1).unwrap();
+            WalletUtils::close_wallet(recipient_wallet_handle_2).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_pack_message_works_for_invalid_receiver_keys() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = CryptoUtils::pack_message(wallet_handle, MESSAGE.as_bytes(), VERKEY_MY2, None);
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_pack_message_works_for_unknown_sender_verkey() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let receiver_keys = json!([VERKEY_MY2]).to_string();
+
+            let res = CryptoUtils::pack_message(wallet_handle, MESSAGE.as_bytes(), &receiver_keys, Some(VERKEY_TRUSTEE));
+            assert_eq!(ErrorCode::WalletItemNotFound, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_unpack_message_works_for_unknown_recipient() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let receiver_keys = json!([VERKEY_TRUSTEE]).to_string();
+
+            let jwe = CryptoUtils::pack_message(wallet_handle, MESSAGE.as_bytes(), &receiver_keys, None).unwrap();
+
+            let res = CryptoUtils::unpack_message(wallet_handle, &jwe);
+            assert_eq!(ErrorCode::WalletItemNotFound, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_unpack_message_works_for_invalid_jwe() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = CryptoUtils::unpack_message(wallet_handle, "not a jwe".as_bytes());
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
 }
 
 mod load {
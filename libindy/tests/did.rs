@@ -20,6 +20,7 @@ mod utils;
 
 use utils::wallet::WalletUtils;
 use utils::did::DidUtils;
+use utils::crypto::CryptoUtils;
 use utils::test::TestUtils;
 use utils::pool::PoolUtils;
 use utils::ledger::LedgerUtils;
@@ -164,6 +165,203 @@ mod high_cases {
         }
     }
 
+    mod key_for_did_with_options {
+        use super::*;
+
+        fn _publish_and_rotate_key(pool_handle: i32, their_wallet_handle: i32) -> (String, String, String) {
+            let (did, verkey) = DidUtils::create_store_and_publish_my_did_from_trustee(their_wallet_handle, pool_handle).unwrap();
+
+            let new_verkey = DidUtils::replace_keys_start(their_wallet_handle, &did, "{}").unwrap();
+
+            let nym_request = LedgerUtils::build_nym_request(&did, &did, Some(&new_verkey), None, None).unwrap();
+            let nym_resp = LedgerUtils::sign_and_submit_request(pool_handle, their_wallet_handle, &did, &nym_request).unwrap();
+
+            DidUtils::replace_keys_apply(their_wallet_handle, &did).unwrap();
+
+            let get_nym_request = LedgerUtils::build_get_nym_request(&did, &did).unwrap();
+            LedgerUtils::submit_request_with_retries(pool_handle, &get_nym_request, &nym_resp).unwrap();
+
+            (did, verkey, new_verkey)
+        }
+
+        #[test]
+        fn indy_key_for_did_with_options_works_for_no_cache() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let their_wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (did, verkey) = DidUtils::create_store_and_publish_my_did_from_trustee(their_wallet_handle, pool_handle).unwrap();
+
+            thread::sleep(std::time::Duration::from_millis(1000));
+
+            let received_verkey = DidUtils::key_for_did(pool_handle, wallet_handle, &did).unwrap();
+            assert_eq!(verkey, received_verkey);
+
+            let new_verkey = DidUtils::replace_keys_start(their_wallet_handle, &did, "{}").unwrap();
+            let nym_request = LedgerUtils::build_nym_request(&did, &did, Some(&new_verkey), None, None).unwrap();
+            let nym_resp = LedgerUtils::sign_and_submit_request(pool_handle, their_wallet_handle, &did, &nym_request).unwrap();
+            DidUtils::replace_keys_apply(their_wallet_handle, &did).unwrap();
+
+            let get_nym_request = LedgerUtils::build_get_nym_request(&did, &did).unwrap();
+            LedgerUtils::submit_request_with_retries(pool_handle, &get_nym_request, &nym_resp).unwrap();
+
+            let received_verkey = DidUtils::key_for_did(pool_handle, wallet_handle, &did).unwrap();
+            assert_eq!(verkey, received_verkey);
+
+            let received_verkey = DidUtils::key_for_did_with_options(pool_handle, wallet_handle, &did, Some(r#"{"noCache": true}"#)).unwrap();
+            assert_eq!(new_verkey, received_verkey);
+
+            let received_verkey = DidUtils::key_for_local_did(wallet_handle, &did).unwrap();
+            assert_eq!(new_verkey, received_verkey);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            WalletUtils::close_wallet(their_wallet_handle).unwrap();
+            PoolUtils::close(pool_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_key_for_did_with_options_works_for_no_store() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let their_wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (did, _, new_verkey) = _publish_and_rotate_key(pool_handle, their_wallet_handle);
+
+            let received_verkey = DidUtils::key_for_did_with_options(pool_handle, wallet_handle, &did, Some(r#"{"noStore": true}"#)).unwrap();
+            assert_eq!(new_verkey, received_verkey);
+
+            let res = DidUtils::key_for_local_did(wallet_handle, &did);
+            assert_eq!(ErrorCode::WalletItemNotFound, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            WalletUtils::close_wallet(their_wallet_handle).unwrap();
+            PoolUtils::close(pool_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_key_for_did_with_options_works_for_max_age() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let their_wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (did, _, new_verkey) = _publish_and_rotate_key(pool_handle, their_wallet_handle);
+
+            let received_verkey = DidUtils::key_for_did(pool_handle, wallet_handle, &did).unwrap();
+            assert_eq!(new_verkey, received_verkey);
+
+            thread::sleep(std::time::Duration::from_millis(2000));
+
+            // Invalid pool handle proves that fresh enough value is taken from the wallet
+            let received_verkey = DidUtils::key_for_did_with_options(pool_handle + 1, wallet_handle, &did, Some(r#"{"maxAge": 3600}"#)).unwrap();
+            assert_eq!(new_verkey, received_verkey);
+
+            let res = DidUtils::key_for_did_with_options(pool_handle + 1, wallet_handle, &did, Some(r#"{"maxAge": 1}"#));
+            assert_eq!(ErrorCode::PoolLedgerInvalidPoolHandle, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            WalletUtils::close_wallet(their_wallet_handle).unwrap();
+            PoolUtils::close(pool_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_key_for_did_with_options_works_for_stored_their_did() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            DidUtils::store_their_did_from_parts(wallet_handle, DID, VERKEY).unwrap();
+
+            let received_verkey = DidUtils::key_for_did_with_options(-1, wallet_handle, DID, Some(r#"{"maxAge": 0}"#)).unwrap();
+            assert_eq!(VERKEY, received_verkey);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_key_for_did_with_options_works_for_invalid_options() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = DidUtils::key_for_did_with_options(-1, wallet_handle, DID, Some(r#"{"maxAge": "1"}"#));
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod refresh_their_did {
+        use super::*;
+
+        #[test]
+        fn indy_refresh_their_did_works() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let their_wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (did, verkey) = DidUtils::create_store_and_publish_my_did_from_trustee(their_wallet_handle, pool_handle).unwrap();
+
+            DidUtils::store_their_did_from_parts(wallet_handle, &did, &verkey).unwrap();
+
+            let new_verkey = DidUtils::replace_keys_start(their_wallet_handle, &did, "{}").unwrap();
+            let nym_request = LedgerUtils::build_nym_request(&did, &did, Some(&new_verkey), None, None).unwrap();
+            let nym_resp = LedgerUtils::sign_and_submit_request(pool_handle, their_wallet_handle, &did, &nym_request).unwrap();
+            DidUtils::replace_keys_apply(their_wallet_handle, &did).unwrap();
+
+            let get_nym_request = LedgerUtils::build_get_nym_request(&did, &did).unwrap();
+            LedgerUtils::submit_request_with_retries(pool_handle, &get_nym_request, &nym_resp).unwrap();
+
+            let their_did_json = DidUtils::refresh_their_did(wallet_handle, pool_handle, &did).unwrap();
+            let their_did: serde_json::Value = serde_json::from_str(&their_did_json).unwrap();
+            assert_eq!(did, their_did["did"].as_str().unwrap());
+            assert_eq!(new_verkey, their_did["verkey"].as_str().unwrap());
+            assert!(their_did["resolved_at"].is_i64());
+
+            let received_verkey = DidUtils::key_for_local_did(wallet_handle, &did).unwrap();
+            assert_eq!(new_verkey, received_verkey);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            WalletUtils::close_wallet(their_wallet_handle).unwrap();
+            PoolUtils::close(pool_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_refresh_their_did_works_for_incompatible_wallet_and_pool() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet("other pool", None).unwrap();
+
+            let res = DidUtils::refresh_their_did(wallet_handle, pool_handle, DID_TRUSTEE);
+            assert_eq!(ErrorCode::WalletIncompatiblePoolError, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            PoolUtils::close(pool_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
     mod key_for_local_did {
         use super::*;
 
@@ -766,6 +964,77 @@ mod high_cases {
             TestUtils::cleanup_storage();
         }
 
+        #[test]
+        fn indy_create_my_did_works_with_method_name() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, my_verkey) = DidUtils::create_my_did(wallet_handle, &format!(r#"{{"seed":"{}", "method_name":"sov"}}"#, MY1_SEED)).unwrap();
+            assert_eq!(format!("did:sov:{}", DID_MY1), my_did);
+            assert_eq!(VERKEY_MY1, my_verkey);
+
+            let verkey = DidUtils::key_for_local_did(wallet_handle, &my_did).unwrap();
+            assert_eq!(my_verkey, verkey);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_invalid_method_name() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = DidUtils::create_my_did(wallet_handle, r#"{"method_name":"Invalid:Method"}"#);
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_my_did_works_with_derivation() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let master_seed_id = CryptoUtils::create_master_seed(wallet_handle, None, None).unwrap();
+            let derivation = json!({"master_seed_id": master_seed_id, "path": "m/44'/0'/0'"});
+
+            let (my_did, my_verkey) = DidUtils::create_my_did(wallet_handle, &json!({"derivation": derivation}).to_string()).unwrap();
+
+            let metadata = CryptoUtils::get_key_metadata(wallet_handle, &my_verkey).unwrap();
+            assert_eq!(derivation, serde_json::from_str::<serde_json::Value>(&metadata).unwrap());
+
+            let verkey = DidUtils::key_for_local_did(wallet_handle, &my_did).unwrap();
+            assert_eq!(my_verkey, verkey);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_derivation_with_seed() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let master_seed_id = CryptoUtils::create_master_seed(wallet_handle, None, None).unwrap();
+            let my_did_json = json!({"seed": MY1_SEED, "derivation": {"master_seed_id": master_seed_id, "path": "m/0'"}}).to_string();
+
+            let res = DidUtils::create_my_did(wallet_handle, &my_did_json);
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
         #[test]
         fn indy_create_my_did_works_for_exists_crypto_type() {
             TestUtils::cleanup_storage();
@@ -779,6 +1048,23 @@ mod high_cases {
             TestUtils::cleanup_storage();
         }
 
+        #[test]
+        fn indy_create_my_did_works_for_secp256k1_crypto_type() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, my_verkey) = DidUtils::create_my_did(wallet_handle, &format!(r#"{{"seed":"{}", "crypto_type":"secp256k1"}}"#, MY1_SEED)).unwrap();
+            assert!(my_verkey.ends_with(":secp256k1"));
+
+            let verkey = DidUtils::key_for_local_did(wallet_handle, &my_did).unwrap();
+            assert_eq!(my_verkey, verkey);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
         #[test]
         fn indy_create_my_did_works_for_invalid_wallet_handle() {
             TestUtils::cleanup_storage();
@@ -1024,6 +1310,24 @@ mod high_cases {
             TestUtils::cleanup_storage();
         }
 
+        #[test]
+        fn indy_store_their_did_works_for_fully_qualified_did() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let did = format!("did:sov:{}", DID_TRUSTEE);
+            let identity_json = json!({"did": did, "verkey": VERKEY_TRUSTEE}).to_string();
+            DidUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+
+            let verkey = DidUtils::key_for_local_did(wallet_handle, &did).unwrap();
+            assert_eq!(VERKEY_TRUSTEE, verkey);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
         #[test]
         fn indy_create_my_did_works_for_invalid_json() {
             TestUtils::cleanup_storage();
@@ -1172,6 +1476,256 @@ mod high_cases {
         }
     }
 
+    mod rotate_did_key_on_ledger {
+        use super::*;
+
+        #[test]
+        fn indy_rotate_did_key_on_ledger_works() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, my_verkey) = DidUtils::create_store_and_publish_my_did_from_trustee(wallet_handle, pool_handle).unwrap();
+
+            let new_verkey = DidUtils::rotate_did_key_on_ledger(pool_handle, wallet_handle, &my_did, "{}").unwrap();
+            assert_ne!(my_verkey, new_verkey);
+
+            let verkey = DidUtils::key_for_local_did(wallet_handle, &my_did).unwrap();
+            assert_eq!(new_verkey, verkey);
+
+            let schema_request = LedgerUtils::build_schema_request(&my_did, SCHEMA_DATA).unwrap();
+            let response = LedgerUtils::sign_and_submit_request(pool_handle, wallet_handle, &my_did, &schema_request).unwrap();
+            PoolUtils::check_response_type(&response, ResponseType::REPLY);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            PoolUtils::close(pool_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_rotate_did_key_on_ledger_works_for_seed() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _) = DidUtils::create_store_and_publish_my_did_from_trustee(wallet_handle, pool_handle).unwrap();
+
+            let key_json = json!({"seed": MY1_SEED}).to_string();
+            let new_verkey = DidUtils::rotate_did_key_on_ledger(pool_handle, wallet_handle, &my_did, &key_json).unwrap();
+            assert_eq!(VERKEY_MY1, new_verkey);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            PoolUtils::close(pool_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_rotate_did_key_on_ledger_works_for_not_written_did() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, my_verkey) = DidUtils::create_my_did(wallet_handle, "{}").unwrap();
+
+            let res = DidUtils::rotate_did_key_on_ledger(pool_handle, wallet_handle, &my_did, "{}");
+            assert_eq!(ErrorCode::LedgerInvalidTransaction, res.unwrap_err());
+
+            let verkey = DidUtils::key_for_local_did(wallet_handle, &my_did).unwrap();
+            assert_eq!(my_verkey, verkey);
+
+            // Temporary key is discarded
+            let res = DidUtils::replace_keys_apply(wallet_handle, &my_did);
+            assert_eq!(ErrorCode::WalletItemNotFound, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            PoolUtils::close(pool_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_rotate_did_key_on_ledger_works_for_not_completed_rotation() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _) = DidUtils::create_store_and_publish_my_did_from_trustee(wallet_handle, pool_handle).unwrap();
+
+            DidUtils::replace_keys_start(wallet_handle, &my_did, "{}").unwrap();
+
+            let res = DidUtils::rotate_did_key_on_ledger(pool_handle, wallet_handle, &my_did, "{}");
+            assert_eq!(ErrorCode::CommonInvalidState, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            PoolUtils::close(pool_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_rotate_did_key_on_ledger_works_for_incompatible_wallet_and_pool() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet("other pool", None).unwrap();
+
+            let (my_did, _) = DidUtils::create_my_did(wallet_handle, "{}").unwrap();
+
+            let res = DidUtils::rotate_did_key_on_ledger(pool_handle, wallet_handle, &my_did, "{}");
+            assert_eq!(ErrorCode::WalletIncompatiblePoolError, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            PoolUtils::close(pool_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod resolve_did_document {
+        use super::*;
+
+        #[test]
+        fn indy_resolve_did_document_works() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (did, verkey) = DidUtils::create_store_and_publish_my_did_from_trustee(wallet_handle, pool_handle).unwrap();
+
+            let attrib_data = json!({"endpoint": {"ha": ENDPOINT, "verkey": VERKEY_TRUSTEE}}).to_string();
+            let attrib_request = LedgerUtils::build_attrib_request(&did, &did, None, Some(&attrib_data), None).unwrap();
+            let attrib_req_resp = LedgerUtils::sign_and_submit_request(pool_handle, wallet_handle, &did, &attrib_request).unwrap();
+
+            let get_attrib_req = LedgerUtils::build_get_attrib_request(&did, &did, Some("endpoint"), None, None).unwrap();
+            LedgerUtils::submit_request_with_retries(pool_handle, &get_attrib_req, &attrib_req_resp).unwrap();
+
+            let did_doc_json = DidUtils::resolve_did_document(pool_handle, &did).unwrap();
+            let did_doc: serde_json::Value = serde_json::from_str(&did_doc_json).unwrap();
+
+            let id = format!("did:sov:{}", did);
+            let key_id = format!("{}#key-1", id);
+
+            assert_eq!(json!("https://w3id.org/did/v1"), did_doc["@context"]);
+            assert_eq!(json!(id), did_doc["id"]);
+            assert_eq!(json!([{
+                "id": key_id,
+                "type": "Ed25519VerificationKey2018",
+                "controller": id,
+                "publicKeyBase58": verkey
+            }]), did_doc["publicKey"]);
+            assert_eq!(json!([{"type": "Ed25519SignatureAuthentication2018", "publicKey": key_id}]), did_doc["authentication"]);
+            assert_eq!(json!([{
+                "id": format!("{};indy", id),
+                "type": "IndyAgent",
+                "serviceEndpoint": ENDPOINT,
+                "recipientKeys": [VERKEY_TRUSTEE]
+            }]), did_doc["service"]);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            PoolUtils::close(pool_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_resolve_did_document_works_for_abbreviated_verkey_and_no_endpoint() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (trustee_did, _) = DidUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let (did, verkey) = DidUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+            let abbr_verkey = DidUtils::abbreviate_verkey(&did, &verkey).unwrap();
+
+            let nym_request = LedgerUtils::build_nym_request(&trustee_did, &did, Some(&abbr_verkey), None, None).unwrap();
+            let nym_resp = LedgerUtils::sign_and_submit_request(pool_handle, wallet_handle, &trustee_did, &nym_request).unwrap();
+
+            let get_nym_request = LedgerUtils::build_get_nym_request(&did, &did).unwrap();
+            LedgerUtils::submit_request_with_retries(pool_handle, &get_nym_request, &nym_resp).unwrap();
+
+            let did_doc_json = DidUtils::resolve_did_document(pool_handle, &format!("did:sov:{}", did)).unwrap();
+            let did_doc: serde_json::Value = serde_json::from_str(&did_doc_json).unwrap();
+
+            assert_eq!(json!(verkey), did_doc["publicKey"][0]["publicKeyBase58"]);
+            assert_eq!(json!([]), did_doc["service"]);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            PoolUtils::close(pool_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_resolve_did_document_works_for_unknown_did() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+
+            let res = DidUtils::resolve_did_document(pool_handle, DID);
+            assert_eq!(ErrorCode::CommonInvalidState, res.unwrap_err());
+
+            PoolUtils::close(pool_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_resolve_did_document_works_for_invalid_did() {
+            TestUtils::cleanup_storage();
+
+            let res = DidUtils::resolve_did_document(-1, INVALID_BASE58_DID);
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_resolve_did_document_works_for_invalid_pool_handle() {
+            TestUtils::cleanup_storage();
+
+            let res = DidUtils::resolve_did_document(-1, DID);
+            assert_eq!(ErrorCode::PoolLedgerInvalidPoolHandle, res.unwrap_err());
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod qualify_did {
+        use super::*;
+
+        #[test]
+        fn indy_qualify_did_works() {
+            let full_qualified_did = DidUtils::qualify_did(DID_TRUSTEE, "sov").unwrap();
+            assert_eq!(format!("did:sov:{}", DID_TRUSTEE), full_qualified_did);
+        }
+
+        #[test]
+        fn indy_qualify_did_works_for_already_qualified_did() {
+            let full_qualified_did = DidUtils::qualify_did(&format!("did:peer:{}", DID_TRUSTEE), "sov").unwrap();
+            assert_eq!(format!("did:sov:{}", DID_TRUSTEE), full_qualified_did);
+        }
+
+        #[test]
+        fn indy_qualify_did_works_for_invalid_method_name() {
+            let res = DidUtils::qualify_did(DID_TRUSTEE, "Sov:1");
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+        }
+
+        #[test]
+        fn indy_qualify_did_works_for_invalid_did() {
+            let res = DidUtils::qualify_did(INVALID_BASE58_DID, "sov");
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+        }
+    }
+
     mod abbreviate_verkey {
         use super::*;
 
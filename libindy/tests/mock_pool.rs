@@ -0,0 +1,287 @@
+extern crate indy;
+extern crate mockpool;
+
+// Workaround to share some utils code based on indy sdk types between tests and indy sdk
+use indy::api as api;
+
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+#[macro_use]
+extern crate lazy_static;
+extern crate log;
+extern crate named_type;
+#[macro_use]
+extern crate named_type_derive;
+
+#[macro_use]
+mod utils;
+
+use mockpool::{MockPool, NodeBehaviour};
+
+use utils::anoncreds::AnoncredsUtils;
+use utils::constants::*;
+use utils::did::DidUtils;
+use utils::ledger::LedgerUtils;
+use utils::pool::PoolUtils;
+use utils::test::TestUtils;
+use utils::types::*;
+use utils::wallet::WalletUtils;
+
+// Tests of the pool and ledger API against in-process mock nodes, they don't need running Indy pool.
+
+fn _start_pool() -> MockPool {
+    let mock_pool = MockPool::start(4);
+    mock_pool.add_nym(DID_TRUSTEE, Some(VERKEY_TRUSTEE), Some("0"));
+    mock_pool
+}
+
+fn _send_nym(pool_handle: i32, wallet_handle: i32, submitter_did: &str) -> (String, String) {
+    let (my_did, my_verkey) = DidUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+    let nym_request = LedgerUtils::build_nym_request(submitter_did, &my_did, Some(&my_verkey), None, None).unwrap();
+    let nym_response = LedgerUtils::sign_and_submit_request(pool_handle, wallet_handle, submitter_did, &nym_request).unwrap();
+    PoolUtils::check_response_type(&nym_response, ResponseType::REPLY);
+
+    (my_did, my_verkey)
+}
+
+fn _get_nym_data(pool_handle: i32, did: &str) -> Option<GetNymResultData> {
+    let get_nym_request = LedgerUtils::build_get_nym_request(DID_MY1, did).unwrap();
+    let get_nym_response = LedgerUtils::submit_request(pool_handle, &get_nym_request).unwrap();
+
+    let get_nym_response: Reply<GetNymReplyResult> = serde_json::from_str(&get_nym_response).unwrap();
+    get_nym_response.result.data.map(|data| serde_json::from_str(&data).unwrap())
+}
+
+mod high_cases {
+    use super::*;
+
+    #[test]
+    fn open_pool_ledger_works_for_mock_pool() {
+        TestUtils::cleanup_storage();
+
+        let mock_pool = MockPool::start(4);
+
+        let pool_handle = PoolUtils::create_and_open_pool_ledger_for_genesis_txns("mock_pool_open", mock_pool.genesis_txns()).unwrap();
+
+        PoolUtils::close(pool_handle).unwrap();
+
+        TestUtils::cleanup_storage();
+    }
+
+    #[test]
+    fn open_pool_ledger_works_for_catchup_from_mock_pool() {
+        TestUtils::cleanup_storage();
+
+        let mock_pool = MockPool::start(4);
+
+        let pool_handle = PoolUtils::create_and_open_pool_ledger_for_genesis_txns("mock_pool_catchup", &mock_pool.genesis_txns()[0..3]).unwrap();
+
+        PoolUtils::refresh(pool_handle).unwrap();
+        PoolUtils::close(pool_handle).unwrap();
+
+        TestUtils::cleanup_storage();
+    }
+
+    #[test]
+    #[cfg(not(feature = "local_nodes_pool"))]
+    fn open_pool_ledger_works_for_mock_pool_from_test_genesis() {
+        TestUtils::cleanup_storage();
+
+        let txn_file_path = PoolUtils::create_genesis_txn_file_for_test_pool("mock_pool_test_genesis", None, None);
+        let _mock_pool = MockPool::start_from_genesis(txn_file_path.as_path());
+
+        let pool_config = PoolUtils::pool_config_json(txn_file_path.as_path());
+        PoolUtils::create_pool_ledger_config("mock_pool_test_genesis", Some(pool_config.as_str())).unwrap();
+        let pool_handle = PoolUtils::open_pool_ledger("mock_pool_test_genesis", None).unwrap();
+
+        PoolUtils::close(pool_handle).unwrap();
+
+        TestUtils::cleanup_storage();
+    }
+
+    #[test]
+    fn nym_requests_work_for_mock_pool() {
+        TestUtils::cleanup_storage();
+
+        let mock_pool = _start_pool();
+
+        let pool_handle = PoolUtils::create_and_open_pool_ledger_for_genesis_txns("mock_pool_nym", mock_pool.genesis_txns()).unwrap();
+        let wallet_handle = WalletUtils::create_and_open_wallet("mock_pool_nym", None).unwrap();
+
+        let (trustee_did, _) = DidUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+        let (my_did, my_verkey) = _send_nym(pool_handle, wallet_handle, &trustee_did);
+
+        let nym_data = _get_nym_data(pool_handle, &my_did).unwrap();
+        assert_eq!(my_did, nym_data.dest);
+        assert_eq!(trustee_did, nym_data.identifier);
+        assert_eq!(Some(my_verkey), nym_data.verkey);
+
+        PoolUtils::close(pool_handle).unwrap();
+        WalletUtils::close_wallet(wallet_handle).unwrap();
+
+        TestUtils::cleanup_storage();
+    }
+
+    #[test]
+    fn get_nym_request_works_for_unknown_did_for_mock_pool() {
+        TestUtils::cleanup_storage();
+
+        let mock_pool = _start_pool();
+
+        let pool_handle = PoolUtils::create_and_open_pool_ledger_for_genesis_txns("mock_pool_unknown_nym", mock_pool.genesis_txns()).unwrap();
+
+        assert!(_get_nym_data(pool_handle, DID_MY2).is_none());
+
+        PoolUtils::close(pool_handle).unwrap();
+
+        TestUtils::cleanup_storage();
+    }
+
+    #[test]
+    fn schema_requests_work_for_mock_pool() {
+        TestUtils::cleanup_storage();
+
+        let mock_pool = _start_pool();
+
+        let pool_handle = PoolUtils::create_and_open_pool_ledger_for_genesis_txns("mock_pool_schema", mock_pool.genesis_txns()).unwrap();
+        let wallet_handle = WalletUtils::create_and_open_wallet("mock_pool_schema", None).unwrap();
+
+        let (trustee_did, _) = DidUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+        let schema_request = LedgerUtils::build_schema_request(&trustee_did, SCHEMA_DATA).unwrap();
+        let schema_response = LedgerUtils::sign_and_submit_request(pool_handle, wallet_handle, &trustee_did, &schema_request).unwrap();
+        PoolUtils::check_response_type(&schema_response, ResponseType::REPLY);
+
+        let schema_id = AnoncredsUtils::build_id(&trustee_did, "2", GVT_SCHEMA_NAME, SCHEMA_VERSION);
+        let get_schema_request = LedgerUtils::build_get_schema_request(DID_MY1, &schema_id).unwrap();
+        let get_schema_response = LedgerUtils::submit_request(pool_handle, &get_schema_request).unwrap();
+
+        let (parsed_schema_id, schema_json) = LedgerUtils::parse_get_schema_response(&get_schema_response).unwrap();
+        assert_eq!(schema_id, parsed_schema_id);
+
+        let schema: serde_json::Value = serde_json::from_str(&schema_json).unwrap();
+        assert_eq!(json!(["name"]), schema["attrNames"]);
+
+        PoolUtils::close(pool_handle).unwrap();
+        WalletUtils::close_wallet(wallet_handle).unwrap();
+
+        TestUtils::cleanup_storage();
+    }
+}
+
+mod medium_cases {
+    use super::*;
+
+    #[test]
+    fn nym_request_works_for_unknown_submitter_for_mock_pool() {
+        TestUtils::cleanup_storage();
+
+        let mock_pool = _start_pool();
+
+        let pool_handle = PoolUtils::create_and_open_pool_ledger_for_genesis_txns("mock_pool_unknown_submitter", mock_pool.genesis_txns()).unwrap();
+        let wallet_handle = WalletUtils::create_and_open_wallet("mock_pool_unknown_submitter", None).unwrap();
+
+        let (my_did, _) = DidUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+        let nym_request = LedgerUtils::build_nym_request(&my_did, DID_MY2, None, None, None).unwrap();
+        let nym_response = LedgerUtils::sign_and_submit_request(pool_handle, wallet_handle, &my_did, &nym_request).unwrap();
+        PoolUtils::check_response_type(&nym_response, ResponseType::REJECT);
+
+        PoolUtils::close(pool_handle).unwrap();
+        WalletUtils::close_wallet(wallet_handle).unwrap();
+
+        TestUtils::cleanup_storage();
+    }
+
+    #[test]
+    fn nym_request_works_for_silent_node_for_mock_pool() {
+        TestUtils::cleanup_storage();
+
+        let mock_pool = _start_pool();
+        mock_pool.set_behaviour(3, NodeBehaviour::Silent);
+
+        let pool_handle = PoolUtils::create_and_open_pool_ledger_for_genesis_txns("mock_pool_silent", mock_pool.genesis_txns()).unwrap();
+        let wallet_handle = WalletUtils::create_and_open_wallet("mock_pool_silent", None).unwrap();
+
+        let (trustee_did, _) = DidUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+        let (my_did, _) = _send_nym(pool_handle, wallet_handle, &trustee_did);
+
+        assert!(_get_nym_data(pool_handle, &my_did).is_some());
+
+        PoolUtils::close(pool_handle).unwrap();
+        WalletUtils::close_wallet(wallet_handle).unwrap();
+
+        TestUtils::cleanup_storage();
+    }
+
+    #[test]
+    fn nym_request_works_for_nack_from_f_plus_one_nodes_for_mock_pool() {
+        TestUtils::cleanup_storage();
+
+        let mock_pool = _start_pool();
+
+        let pool_handle = PoolUtils::create_and_open_pool_ledger_for_genesis_txns("mock_pool_nack", mock_pool.genesis_txns()).unwrap();
+        let wallet_handle = WalletUtils::create_and_open_wallet("mock_pool_nack", None).unwrap();
+
+        let (trustee_did, _) = DidUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+        mock_pool.set_behaviour(0, NodeBehaviour::Nack);
+        mock_pool.set_behaviour(1, NodeBehaviour::Nack);
+
+        let nym_request = LedgerUtils::build_nym_request(&trustee_did, DID_MY2, None, None, None).unwrap();
+        let nym_response = LedgerUtils::sign_and_submit_request(pool_handle, wallet_handle, &trustee_did, &nym_request).unwrap();
+        PoolUtils::check_response_type(&nym_response, ResponseType::REQNACK);
+
+        PoolUtils::close(pool_handle).unwrap();
+        WalletUtils::close_wallet(wallet_handle).unwrap();
+
+        TestUtils::cleanup_storage();
+    }
+
+    #[test]
+    fn get_nym_request_works_for_stale_node_for_mock_pool() {
+        TestUtils::cleanup_storage();
+
+        let mock_pool = _start_pool();
+        mock_pool.set_behaviour(0, NodeBehaviour::Stale(mock_pool.domain_ledger_size()));
+
+        let pool_handle = PoolUtils::create_and_open_pool_ledger_for_genesis_txns("mock_pool_stale", mock_pool.genesis_txns()).unwrap();
+        let wallet_handle = WalletUtils::create_and_open_wallet("mock_pool_stale", None).unwrap();
+
+        let (trustee_did, _) = DidUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+        let (my_did, my_verkey) = _send_nym(pool_handle, wallet_handle, &trustee_did);
+
+        let nym_data = _get_nym_data(pool_handle, &my_did).unwrap();
+        assert_eq!(Some(my_verkey), nym_data.verkey);
+
+        PoolUtils::close(pool_handle).unwrap();
+        WalletUtils::close_wallet(wallet_handle).unwrap();
+
+        TestUtils::cleanup_storage();
+    }
+
+    #[test]
+    fn get_nym_request_works_for_bad_state_proof_for_mock_pool() {
+        TestUtils::cleanup_storage();
+
+        let mock_pool = _start_pool();
+        mock_pool.set_behaviour(0, NodeBehaviour::BadStateProof);
+
+        let pool_handle = PoolUtils::create_and_open_pool_ledger_for_genesis_txns("mock_pool_bad_state_proof", mock_pool.genesis_txns()).unwrap();
+        let wallet_handle = WalletUtils::create_and_open_wallet("mock_pool_bad_state_proof", None).unwrap();
+
+        let (trustee_did, _) = DidUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+        let (my_did, my_verkey) = _send_nym(pool_handle, wallet_handle, &trustee_did);
+
+        let nym_data = _get_nym_data(pool_handle, &my_did).unwrap();
+        assert_eq!(Some(my_verkey), nym_data.verkey);
+
+        PoolUtils::close(pool_handle).unwrap();
+        WalletUtils::close_wallet(wallet_handle).unwrap();
+
+        TestUtils::cleanup_storage();
+    }
+}
@@ -352,6 +352,247 @@ mod high_cases {
         }
     }
 
+    mod update_record_expiry {
+        use super::*;
+
+        #[test]
+        fn indy_update_record_expiry_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            NonSecretsUtils::add_wallet_record(wallet_handle, TYPE, ID, VALUE, None).unwrap();
+            NonSecretsUtils::update_wallet_record_expiry(wallet_handle, TYPE, ID, 1).unwrap();
+
+            let res = NonSecretsUtils::get_wallet_record(wallet_handle, TYPE, ID, OPTIONS_EMPTY);
+            assert_eq!(ErrorCode::WalletItemNotFound, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_update_record_expiry_works_for_future_time() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            NonSecretsUtils::add_wallet_record(wallet_handle, TYPE, ID, VALUE, None).unwrap();
+            NonSecretsUtils::update_wallet_record_expiry(wallet_handle, TYPE, ID, i32::max_value() as i64).unwrap();
+
+            check_record_field(wallet_handle, TYPE, ID, "value", VALUE);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_update_record_expiry_works_for_search() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            NonSecretsUtils::add_wallet_record(wallet_handle, TYPE, ID, VALUE, None).unwrap();
+            NonSecretsUtils::add_wallet_record(wallet_handle, TYPE, ID_2, VALUE_2, None).unwrap();
+            NonSecretsUtils::update_wallet_record_expiry(wallet_handle, TYPE, ID, 1).unwrap();
+
+            let options = json!({
+                "retrieveRecords": true,
+                "retrieveTotalCount": true,
+                "retrieveType": false,
+                "retrieveValue": true,
+                "retrieveTags": false
+            }).to_string();
+
+            let search_handle = NonSecretsUtils::open_wallet_search(wallet_handle, TYPE, QUERY_EMPTY, &options).unwrap();
+
+            let records = NonSecretsUtils::fetch_wallet_search_next_records(wallet_handle, search_handle, 5).unwrap();
+
+            check_search_records(&records, vec![
+                WalletRecord { id: ID_2.to_string(), type_: None, value: Some(VALUE_2.to_string()), tags: None }]);
+
+            let search_records: SearchRecords = serde_json::from_str(&records).unwrap();
+            assert_eq!(Some(1), search_records.total_count);
+
+            NonSecretsUtils::close_wallet_search(search_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_update_record_expiry_works_for_add_after_expiry() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            NonSecretsUtils::add_wallet_record(wallet_handle, TYPE, ID, VALUE, None).unwrap();
+            NonSecretsUtils::update_wallet_record_expiry(wallet_handle, TYPE, ID, 1).unwrap();
+            NonSecretsUtils::add_wallet_record(wallet_handle, TYPE, ID, VALUE_2, None).unwrap();
+
+            check_record_field(wallet_handle, TYPE, ID, "value", VALUE_2);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_update_record_expiry_works_for_not_found_record() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = NonSecretsUtils::update_wallet_record_expiry(wallet_handle, TYPE, ID, 1);
+            assert_eq!(ErrorCode::WalletItemNotFound, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_update_record_expiry_works_for_invalid_expires_at() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            NonSecretsUtils::add_wallet_record(wallet_handle, TYPE, ID, VALUE, None).unwrap();
+
+            let res = NonSecretsUtils::update_wallet_record_expiry(wallet_handle, TYPE, ID, -2);
+            assert_eq!(ErrorCode::CommonInvalidParam5, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod update_record_with_version {
+        use super::*;
+
+        #[test]
+        fn indy_get_record_works_for_retrieve_version() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            NonSecretsUtils::add_wallet_record(wallet_handle, TYPE, ID, VALUE, None).unwrap();
+            assert_eq!(1, NonSecretsUtils::get_wallet_record_version(wallet_handle, TYPE, ID).unwrap());
+
+            NonSecretsUtils::update_wallet_record_value(wallet_handle, TYPE, ID, VALUE_2).unwrap();
+            assert_eq!(2, NonSecretsUtils::get_wallet_record_version(wallet_handle, TYPE, ID).unwrap());
+
+            NonSecretsUtils::update_wallet_record_tags(wallet_handle, TYPE, ID, TAGS).unwrap();
+            assert_eq!(3, NonSecretsUtils::get_wallet_record_version(wallet_handle, TYPE, ID).unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_update_record_value_with_version_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            NonSecretsUtils::add_wallet_record(wallet_handle, TYPE, ID, VALUE, None).unwrap();
+            NonSecretsUtils::update_wallet_record_value_with_version(wallet_handle, TYPE, ID, VALUE_2, 1).unwrap();
+
+            check_record_field(wallet_handle, TYPE, ID, "value", VALUE_2);
+            assert_eq!(2, NonSecretsUtils::get_wallet_record_version(wallet_handle, TYPE, ID).unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_update_record_value_with_version_works_for_conflict() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            NonSecretsUtils::add_wallet_record(wallet_handle, TYPE, ID, VALUE, None).unwrap();
+            NonSecretsUtils::update_wallet_record_value_with_version(wallet_handle, TYPE, ID, VALUE_2, 1).unwrap();
+
+            let res = NonSecretsUtils::update_wallet_record_value_with_version(wallet_handle, TYPE, ID, VALUE_3, 1);
+            assert_eq!(ErrorCode::WalletConflict, res.unwrap_err());
+
+            check_record_field(wallet_handle, TYPE, ID, "value", VALUE_2);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_update_record_tags_with_version_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            NonSecretsUtils::add_wallet_record(wallet_handle, TYPE, ID, VALUE, Some(TAGS)).unwrap();
+            NonSecretsUtils::update_wallet_record_tags_with_version(wallet_handle, TYPE, ID, TAGS_2, 1).unwrap();
+
+            check_record_field(wallet_handle, TYPE, ID, "tags", TAGS_2);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_update_record_tags_with_version_works_for_conflict() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            NonSecretsUtils::add_wallet_record(wallet_handle, TYPE, ID, VALUE, Some(TAGS)).unwrap();
+            NonSecretsUtils::add_wallet_record_tags(wallet_handle, TYPE, ID, TAGS_2).unwrap();
+
+            let res = NonSecretsUtils::update_wallet_record_tags_with_version(wallet_handle, TYPE, ID, TAGS_3, 1);
+            assert_eq!(ErrorCode::WalletConflict, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_update_record_value_with_version_works_for_not_found_record() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = NonSecretsUtils::update_wallet_record_value_with_version(wallet_handle, TYPE, ID, VALUE, 1);
+            assert_eq!(ErrorCode::WalletItemNotFound, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_update_record_value_with_version_works_for_invalid_version() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            NonSecretsUtils::add_wallet_record(wallet_handle, TYPE, ID, VALUE, None).unwrap();
+
+            let res = NonSecretsUtils::update_wallet_record_value_with_version(wallet_handle, TYPE, ID, VALUE_2, 0);
+            assert_eq!(ErrorCode::CommonInvalidParam6, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
     mod add_record_tags {
         use super::*;
 
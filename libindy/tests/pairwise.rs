@@ -397,5 +397,232 @@ mod high_cases {
             TestUtils::cleanup_storage();
         }
     }
-}
 
+    mod add_pairwise_my_did {
+        use super::*;
+
+        #[test]
+        fn indy_add_pairwise_my_did_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _) = DidUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
+            let (my_did_2, _) = DidUtils::create_and_store_my_did(wallet_handle, Some(MY2_SEED)).unwrap();
+
+            DidUtils::store_their_did_from_parts(wallet_handle, DID_TRUSTEE, VERKEY_TRUSTEE).unwrap();
+
+            PairwiseUtils::create_pairwise(wallet_handle, DID_TRUSTEE, &my_did, Some(METADATA)).unwrap();
+            PairwiseUtils::add_pairwise_my_did(wallet_handle, DID_TRUSTEE, &my_did_2).unwrap();
+
+            let pairwise_info_json = PairwiseUtils::get_pairwise(wallet_handle, DID_TRUSTEE).unwrap();
+            assert_eq!(format!(r#"{{"my_did":"{}","my_dids":["{}","{}"],"metadata":"{}"}}"#, my_did_2, my_did, my_did_2, METADATA), pairwise_info_json);
+
+            PairwiseUtils::add_pairwise_my_did(wallet_handle, DID_TRUSTEE, &my_did).unwrap();
+
+            let pairwise_info_json = PairwiseUtils::get_pairwise(wallet_handle, DID_TRUSTEE).unwrap();
+            assert_eq!(format!(r#"{{"my_did":"{}","my_dids":["{}","{}"],"metadata":"{}"}}"#, my_did, my_did_2, my_did, METADATA), pairwise_info_json);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_add_pairwise_my_did_works_for_not_found_my_did() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _) = DidUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
+
+            DidUtils::store_their_did_from_parts(wallet_handle, DID_TRUSTEE, VERKEY_TRUSTEE).unwrap();
+
+            PairwiseUtils::create_pairwise(wallet_handle, DID_TRUSTEE, &my_did, None).unwrap();
+
+            assert_eq!(ErrorCode::WalletItemNotFound, PairwiseUtils::add_pairwise_my_did(wallet_handle, DID_TRUSTEE, DID_MY2).unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod delete_pairwise {
+        use super::*;
+
+        #[test]
+        fn indy_delete_pairwise_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _) = DidUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
+
+            DidUtils::store_their_did_from_parts(wallet_handle, DID_TRUSTEE, VERKEY_TRUSTEE).unwrap();
+
+            PairwiseUtils::create_pairwise(wallet_handle, DID_TRUSTEE, &my_did, None).unwrap();
+            assert!(PairwiseUtils::pairwise_exists(wallet_handle, DID_TRUSTEE).unwrap());
+
+            PairwiseUtils::delete_pairwise(wallet_handle, DID_TRUSTEE).unwrap();
+            assert!(!PairwiseUtils::pairwise_exists(wallet_handle, DID_TRUSTEE).unwrap());
+
+            PairwiseUtils::create_pairwise(wallet_handle, DID_TRUSTEE, &my_did, None).unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_delete_pairwise_works_for_not_created_pairwise() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            assert_eq!(ErrorCode::WalletItemNotFound, PairwiseUtils::delete_pairwise(wallet_handle, DID_TRUSTEE).unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod search_pairwise {
+        use super::*;
+
+        fn _create_pairwise_list(wallet_handle: i32) -> String {
+            let (my_did, _) = DidUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
+
+            DidUtils::store_their_did_from_parts(wallet_handle, DID_TRUSTEE, VERKEY_TRUSTEE).unwrap();
+            DidUtils::store_their_did_from_parts(wallet_handle, DID_MY2, VERKEY_MY2).unwrap();
+
+            PairwiseUtils::create_pairwise(wallet_handle, DID_TRUSTEE, &my_did, Some(METADATA)).unwrap();
+            PairwiseUtils::create_pairwise(wallet_handle, DID_MY2, &my_did, None).unwrap();
+
+            my_did
+        }
+
+        fn _fetch(search_handle: i32, count: usize) -> serde_json::Value {
+            let pairwise_json = PairwiseUtils::fetch_pairwise_search_next_records(search_handle, count).unwrap();
+            serde_json::from_str(&pairwise_json).unwrap()
+        }
+
+        #[test]
+        fn indy_search_pairwise_works_for_metadata() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let my_did = _create_pairwise_list(wallet_handle);
+
+            let search_handle = PairwiseUtils::search_pairwise(wallet_handle, &json!({"metadata": METADATA}).to_string()).unwrap();
+
+            let result = _fetch(search_handle, 10);
+            assert_eq!(json!({
+                "totalCount": 1,
+                "records": [{"my_did": my_did, "their_did": DID_TRUSTEE, "metadata": METADATA, "my_dids": [my_did]}]
+            }), result);
+
+            PairwiseUtils::close_pairwise_search(search_handle).unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_search_pairwise_works_for_paging() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let my_did = _create_pairwise_list(wallet_handle);
+
+            let search_handle = PairwiseUtils::search_pairwise(wallet_handle, &json!({"my_did": my_did}).to_string()).unwrap();
+
+            let result = _fetch(search_handle, 1);
+            assert_eq!(2, result["totalCount"].as_u64().unwrap());
+            assert_eq!(1, result["records"].as_array().unwrap().len());
+
+            let result = _fetch(search_handle, 1);
+            assert_eq!(1, result["records"].as_array().unwrap().len());
+
+            let result = _fetch(search_handle, 1);
+            assert!(result["records"].is_null());
+
+            PairwiseUtils::close_pairwise_search(search_handle).unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_search_pairwise_works_after_set_metadata() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            _create_pairwise_list(wallet_handle);
+
+            PairwiseUtils::set_pairwise_metadata(wallet_handle, DID_MY2, Some(METADATA)).unwrap();
+            PairwiseUtils::set_pairwise_metadata(wallet_handle, DID_TRUSTEE, None).unwrap();
+
+            let search_handle = PairwiseUtils::search_pairwise(wallet_handle, &json!({"metadata": METADATA}).to_string()).unwrap();
+
+            let result = _fetch(search_handle, 10);
+            assert_eq!(1, result["totalCount"].as_u64().unwrap());
+            assert_eq!(DID_MY2, result["records"][0]["their_did"].as_str().unwrap());
+
+            PairwiseUtils::close_pairwise_search(search_handle).unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_search_pairwise_works_for_read_only_wallet() {
+            TestUtils::cleanup_storage();
+
+            let wallet_name = "indy_search_pairwise_works_for_read_only_wallet";
+            WalletUtils::create_wallet(POOL, wallet_name, None, None, None).unwrap();
+            let wallet_handle = WalletUtils::open_wallet(wallet_name, None, None).unwrap();
+            _create_pairwise_list(wallet_handle);
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            let wallet_handle = WalletUtils::open_wallet(wallet_name, Some(r#"{"read_only":true}"#), None).unwrap();
+
+            let search_handle = PairwiseUtils::search_pairwise(wallet_handle, &json!({"metadata": METADATA}).to_string()).unwrap();
+
+            let result = _fetch(search_handle, 10);
+            assert_eq!(1, result["totalCount"].as_u64().unwrap());
+            assert_eq!(DID_TRUSTEE, result["records"][0]["their_did"].as_str().unwrap());
+
+            PairwiseUtils::close_pairwise_search(search_handle).unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_close_pairwise_search_works_for_invalid_handle() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let search_handle = PairwiseUtils::search_pairwise(wallet_handle, "{}").unwrap();
+
+            PairwiseUtils::close_pairwise_search(search_handle).unwrap();
+
+            assert_eq!(ErrorCode::WalletInvalidHandle, PairwiseUtils::close_pairwise_search(search_handle).unwrap_err());
+            assert_eq!(ErrorCode::WalletInvalidHandle, PairwiseUtils::fetch_pairwise_search_next_records(search_handle, 1).unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+}
@@ -41,6 +41,14 @@ static PAYMENT_RESPONSE: &str = r#"{"reqId":1, "utxos":[{"input": "pay:null:1",
 static GET_TXN_FEES_RESPONSE: &str = r#"{"reqId":1, fees:{"txnType1":1, "txnType2":2}}"#;
 static TEST_RES_STRING: &str = "test";
 static CORRECT_PAYMENT_ADDRESS: &str = "pay:null:test";
+static CORRECT_RECEIPT: &str = "pay:null:1_test";
+static SIGNATURE: &[u8] = &[1, 2, 3, 4];
+static SPLIT_INPUTS: &str = r#"["pay:null:1", "pay:split_second:1", "pay:null:2"]"#;
+static SPLIT_OUTPUTS: &str = r#"[{"paymentAddress": "pay:split_second:2", "amount":1}, {"paymentAddress": "pay:null:3", "amount":2, "extra":"2"}]"#;
+static HISTORY_PAYMENT_ADDRESS: &str = "pay:null:history";
+static PAYMENT_HISTORY: &str = r#"{"submitterDid": "Th7MpTaRZVRYnPiabds81Y", "txnType": "10001"}"#;
+static HISTORY_RESPONSE: &str = r#"{"result": {"seqNo": 5}}"#;
+static HISTORY_UTXOS: &str = r#"[{"input": "pay:null:5_2", "amount": 2, "extra": "2", "paymentAddress": "pay:null:2"}, {"input": "pay:null:5_history", "amount": 1, "paymentAddress": "pay:null:history"}]"#;
 
 mod high_cases {
     use super::*;
@@ -64,12 +72,40 @@ mod high_cases {
                                                          Some(payments::mock_method::build_set_txn_fees_req::handle),
                                                          Some(payments::mock_method::build_get_txn_fees_req::handle),
                                                          Some(payments::mock_method::parse_get_txn_fees_response::handle),
+                                                         Some(payments::mock_method::build_verify_payment_req::handle),
+                                                         Some(payments::mock_method::parse_verify_payment_response::handle),
+                                                         Some(payments::mock_method::sign_with_address::handle),
+                                                         Some(payments::mock_method::verify_with_address::handle),
+                                                         Some(payments::mock_method::get_request_fee::handle),
             ).unwrap();
 
             TestUtils::cleanup_storage();
         }
     }
 
+    mod list_payment_methods {
+        use super::*;
+
+        #[test]
+        fn list_payment_methods_works() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+
+            let methods = payments::list_payment_methods().unwrap();
+            let methods: Vec<serde_json::Value> = serde_json::from_str(&methods).unwrap();
+
+            let method = methods.iter().find(|method| method["method"] == PAYMENT_METHOD_NAME).unwrap();
+            let capabilities: Vec<&str> = method["capabilities"].as_array().unwrap().iter().map(|c| c.as_str().unwrap()).collect();
+
+            assert!(capabilities.contains(&"build_payment_req"));
+            assert!(capabilities.contains(&"build_verify_payment_req"));
+            assert!(capabilities.contains(&"sign_with_address"));
+            assert!(capabilities.contains(&"get_request_fee"));
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
     mod create_payment_address {
         use super::*;
 
@@ -249,6 +285,54 @@ mod high_cases {
         }
     }
 
+    mod split_payment_request {
+        use super::*;
+
+        #[test]
+        fn build_split_payment_req_works() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            payments::register_payment_method("split_second",
+                                              Some(payments::mock_method::create_payment_address::handle),
+                                              Some(payments::mock_method::add_request_fees::handle),
+                                              Some(payments::mock_method::parse_response_with_fees::handle),
+                                              Some(payments::mock_method::build_get_utxo_request::handle),
+                                              Some(payments::mock_method::parse_get_utxo_response::handle),
+                                              Some(payments::mock_method::build_payment_req::handle),
+                                              Some(payments::mock_method::parse_payment_response::handle),
+                                              Some(payments::mock_method::build_mint_req::handle),
+                                              Some(payments::mock_method::build_set_txn_fees_req::handle),
+                                              Some(payments::mock_method::build_get_txn_fees_req::handle),
+                                              Some(payments::mock_method::parse_get_txn_fees_response::handle),
+                                              None,
+                                              None,
+                                              None,
+                                              None,
+                                              None,
+            ).unwrap();
+
+            payments::mock_method::build_payment_req::inject_mock(ErrorCode::Success, TEST_RES_STRING);
+            payments::mock_method::build_payment_req::inject_mock(ErrorCode::Success, TEST_RES_STRING);
+
+            let reqs = payments::build_split_payment_req(wallet_handle,
+                                                         IDENTIFIER,
+                                                         SPLIT_INPUTS,
+                                                         SPLIT_OUTPUTS,
+            ).unwrap();
+            let reqs: Vec<serde_json::Value> = serde_json::from_str(&reqs).unwrap();
+
+            assert_eq!(reqs, vec![
+                json!({"paymentMethod": PAYMENT_METHOD_NAME, "request": TEST_RES_STRING}),
+                json!({"paymentMethod": "split_second", "request": TEST_RES_STRING}),
+            ]);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+    }
+
     mod parse_payment_response {
         use super::*;
 
@@ -359,6 +443,179 @@ mod high_cases {
             TestUtils::cleanup_storage();
         }
     }
+
+    mod build_verify_payment_req {
+        use super::*;
+
+        #[test]
+        fn build_verify_payment_req_works() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            payments::mock_method::build_verify_payment_req::inject_mock(ErrorCode::Success, TEST_RES_STRING);
+
+            let (req, payment_method) = payments::build_verify_payment_req(wallet_handle, IDENTIFIER, CORRECT_RECEIPT).unwrap();
+
+            assert_eq!(req, TEST_RES_STRING.to_string());
+            assert_eq!(PAYMENT_METHOD_NAME, payment_method);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod parse_verify_payment_response {
+        use super::*;
+
+        #[test]
+        fn parse_verify_payment_response_works() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+
+            payments::mock_method::parse_verify_payment_response::inject_mock(ErrorCode::Success, TEST_RES_STRING);
+
+            let res_plugin = payments::parse_verify_payment_response(PAYMENT_METHOD_NAME, EMPTY_OBJECT).unwrap();
+
+            assert_eq!(res_plugin, TEST_RES_STRING);
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod sign_with_address {
+        use super::*;
+
+        #[test]
+        fn sign_with_address_works() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            payments::mock_method::sign_with_address::inject_mock(ErrorCode::Success, SIGNATURE);
+
+            let signature = payments::sign_with_address(wallet_handle, CORRECT_PAYMENT_ADDRESS, MESSAGE.as_bytes()).unwrap();
+            assert_eq!(signature, SIGNATURE.to_vec());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod verify_with_address {
+        use super::*;
+
+        #[test]
+        fn verify_with_address_works() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+
+            payments::mock_method::verify_with_address::inject_mock(ErrorCode::Success, true);
+
+            let valid = payments::verify_with_address(CORRECT_PAYMENT_ADDRESS, MESSAGE.as_bytes(), SIGNATURE).unwrap();
+            assert!(valid);
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod payment_history {
+        use super::*;
+
+        #[test]
+        fn parse_payment_response_with_history_works() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            payments::mock_method::create_payment_address::inject_mock(ErrorCode::Success, HISTORY_PAYMENT_ADDRESS);
+            payments::create_payment_address(wallet_handle, EMPTY_OBJECT, PAYMENT_METHOD_NAME).unwrap();
+
+            payments::mock_method::parse_payment_response::inject_mock(ErrorCode::Success, HISTORY_UTXOS);
+
+            let res_plugin = payments::parse_payment_response_with_history(wallet_handle, PAYMENT_METHOD_NAME, HISTORY_RESPONSE, PAYMENT_HISTORY).unwrap();
+            assert_eq!(res_plugin, HISTORY_UTXOS);
+
+            let history = payments::search_payment_history(wallet_handle, EMPTY_OBJECT).unwrap();
+            let history: Vec<serde_json::Value> = serde_json::from_str(&history).unwrap();
+            assert_eq!(history.len(), 2);
+
+            let sent = payments::search_payment_history(wallet_handle, r#"{"direction": "sent"}"#).unwrap();
+            let sent: Vec<serde_json::Value> = serde_json::from_str(&sent).unwrap();
+            assert_eq!(sent, vec![json!({
+                "paymentMethod": PAYMENT_METHOD_NAME,
+                "direction": "sent",
+                "input": "pay:null:5_2",
+                "paymentAddress": "pay:null:2",
+                "amount": 2,
+                "txnType": "10001",
+                "seqNo": 5,
+                "submitterDid": IDENTIFIER,
+                "extra": "2"
+            })]);
+
+            let received = payments::search_payment_history(wallet_handle, r#"{"direction": "received"}"#).unwrap();
+            let received: Vec<serde_json::Value> = serde_json::from_str(&received).unwrap();
+            assert_eq!(received.len(), 1);
+            assert_eq!(received[0]["paymentAddress"], HISTORY_PAYMENT_ADDRESS);
+            assert_eq!(received[0]["amount"], 1);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn parse_payment_response_with_history_works_for_same_response_parsed_twice() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            payments::mock_method::parse_payment_response::inject_mock(ErrorCode::Success, HISTORY_UTXOS);
+            payments::parse_payment_response_with_history(wallet_handle, PAYMENT_METHOD_NAME, HISTORY_RESPONSE, PAYMENT_HISTORY).unwrap();
+
+            payments::mock_method::parse_payment_response::inject_mock(ErrorCode::Success, HISTORY_UTXOS);
+            let res_plugin = payments::parse_payment_response_with_history(wallet_handle, PAYMENT_METHOD_NAME, HISTORY_RESPONSE, PAYMENT_HISTORY).unwrap();
+            assert_eq!(res_plugin, HISTORY_UTXOS);
+
+            let history = payments::search_payment_history(wallet_handle, EMPTY_OBJECT).unwrap();
+            let history: Vec<serde_json::Value> = serde_json::from_str(&history).unwrap();
+            assert_eq!(history.len(), 2);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn parse_response_with_fees_with_history_works() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            payments::mock_method::parse_response_with_fees::inject_mock(ErrorCode::Success, HISTORY_UTXOS);
+
+            let res_plugin = payments::parse_response_with_fees_with_history(wallet_handle, PAYMENT_METHOD_NAME, HISTORY_RESPONSE, PAYMENT_HISTORY).unwrap();
+            assert_eq!(res_plugin, HISTORY_UTXOS);
+
+            let sent = payments::search_payment_history(wallet_handle, r#"{"direction": "sent", "txnType": "10001"}"#).unwrap();
+            let sent: Vec<serde_json::Value> = serde_json::from_str(&sent).unwrap();
+            assert_eq!(sent.len(), 2);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn search_payment_history_works_for_empty_history() {
+            TestUtils::cleanup_storage();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let history = payments::search_payment_history(wallet_handle, EMPTY_OBJECT).unwrap();
+            assert_eq!(history, EMPTY_ARRAY);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+    }
 }
 
 mod medium_cases {
@@ -383,12 +640,73 @@ mod medium_cases {
                                                         None,
                                                         None,
                                                         None,
+                                                        None,
+                                                        None,
+                                                        None,
+                                                        None,
+                                                        None,
             ).unwrap_err();
 
             assert_eq!(err, ErrorCode::CommonInvalidParam3);
 
             TestUtils::cleanup_storage();
         }
+
+        #[test]
+        fn register_payment_method_works_for_only_one_verification_method() {
+            TestUtils::cleanup_storage();
+
+            let err = payments::register_payment_method("register_payment_method_works_for_only_one_verification_method",
+                                                        Some(payments::mock_method::create_payment_address::handle),
+                                                        Some(payments::mock_method::add_request_fees::handle),
+                                                        Some(payments::mock_method::parse_response_with_fees::handle),
+                                                        Some(payments::mock_method::build_get_utxo_request::handle),
+                                                        Some(payments::mock_method::parse_get_utxo_response::handle),
+                                                        Some(payments::mock_method::build_payment_req::handle),
+                                                        Some(payments::mock_method::parse_payment_response::handle),
+                                                        Some(payments::mock_method::build_mint_req::handle),
+                                                        Some(payments::mock_method::build_set_txn_fees_req::handle),
+                                                        Some(payments::mock_method::build_get_txn_fees_req::handle),
+                                                        Some(payments::mock_method::parse_get_txn_fees_response::handle),
+                                                        Some(payments::mock_method::build_verify_payment_req::handle),
+                                                        None,
+                                                        None,
+                                                        None,
+                                                        None,
+            ).unwrap_err();
+
+            assert_eq!(err, ErrorCode::CommonInvalidParam15);
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn register_payment_method_works_for_only_one_address_signing_method() {
+            TestUtils::cleanup_storage();
+
+            let err = payments::register_payment_method("register_payment_method_works_for_only_one_address_signing_method",
+                                                        Some(payments::mock_method::create_payment_address::handle),
+                                                        Some(payments::mock_method::add_request_fees::handle),
+                                                        Some(payments::mock_method::parse_response_with_fees::handle),
+                                                        Some(payments::mock_method::build_get_utxo_request::handle),
+                                                        Some(payments::mock_method::parse_get_utxo_response::handle),
+                                                        Some(payments::mock_method::build_payment_req::handle),
+                                                        Some(payments::mock_method::parse_payment_response::handle),
+                                                        Some(payments::mock_method::build_mint_req::handle),
+                                                        Some(payments::mock_method::build_set_txn_fees_req::handle),
+                                                        Some(payments::mock_method::build_get_txn_fees_req::handle),
+                                                        Some(payments::mock_method::parse_get_txn_fees_response::handle),
+                                                        None,
+                                                        None,
+                                                        Some(payments::mock_method::sign_with_address::handle),
+                                                        None,
+                                                        None,
+            ).unwrap_err();
+
+            assert_eq!(err, ErrorCode::CommonInvalidParam17);
+
+            TestUtils::cleanup_storage();
+        }
     }
 
     mod create_payment_address {
@@ -528,7 +846,7 @@ mod medium_cases {
                                                  INCOMPATIBLE_OUTPUTS,
             ).unwrap_err();
 
-            assert_eq!(err, ErrorCode::PaymentIncompatibleMethodsError);
+            assert_eq!(err, ErrorCode::PaymentMixedMethodsError);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
             TestUtils::cleanup_storage();
@@ -546,7 +864,7 @@ mod medium_cases {
                                                  EMPTY_ARRAY,
             ).unwrap_err();
 
-            assert_eq!(err, ErrorCode::PaymentIncompatibleMethodsError);
+            assert_eq!(err, ErrorCode::PaymentMixedMethodsError);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
             TestUtils::cleanup_storage();
@@ -565,7 +883,7 @@ mod medium_cases {
                                                  r#"[{"paymentAddress": "pay:null2:1", "amount":1, "extra":"1"}]"#,
             ).unwrap_err();
 
-            assert_eq!(err, ErrorCode::PaymentIncompatibleMethodsError);
+            assert_eq!(err, ErrorCode::PaymentMixedMethodsError);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
             TestUtils::cleanup_storage();
@@ -960,7 +1278,7 @@ mod medium_cases {
                                                   IDENTIFIER,
                                                   INCOMPATIBLE_INPUTS,
                                                   CORRECT_OUTPUTS);
-            assert_eq!(ErrorCode::PaymentIncompatibleMethodsError, res.unwrap_err());
+            assert_eq!(ErrorCode::PaymentMixedMethodsError, res.unwrap_err());
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
             TestUtils::cleanup_storage();
@@ -976,7 +1294,32 @@ mod medium_cases {
                                                   IDENTIFIER,
                                                   CORRECT_INPUTS,
                                                   INCOMPATIBLE_OUTPUTS);
-            assert_eq!(ErrorCode::PaymentIncompatibleMethodsError, res.unwrap_err());
+            assert_eq!(ErrorCode::PaymentMixedMethodsError, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn build_payment_request_works_for_mixed_methods_error_details() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(WALLET, None).unwrap();
+            let inputs = r#"["pay:PAYMENT_METHOD_1:1", "pay:PAYMENT_METHOD_2:2"]"#;
+            let outputs = r#"[{"paymentAddress": "pay:PAYMENT_METHOD_2:3", "amount": 1}]"#;
+
+            let (err, details) = payments::build_payment_req_error_details(wallet_handle,
+                                                                           IDENTIFIER,
+                                                                           inputs,
+                                                                           outputs);
+
+            assert_eq!(ErrorCode::PaymentMixedMethodsError, err);
+
+            let details: serde_json::Value = serde_json::from_str(&details.unwrap()).unwrap();
+            assert_eq!(json!({
+                "inputs": {"PAYMENT_METHOD_1": ["pay:PAYMENT_METHOD_1:1"], "PAYMENT_METHOD_2": ["pay:PAYMENT_METHOD_2:2"]},
+                "outputs": {"PAYMENT_METHOD_2": ["pay:PAYMENT_METHOD_2:3"]}
+            }), details);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
             TestUtils::cleanup_storage();
@@ -995,7 +1338,7 @@ mod medium_cases {
                                                   inputs,
                                                   outputs);
 
-            assert_eq!(ErrorCode::PaymentIncompatibleMethodsError, res.unwrap_err());
+            assert_eq!(ErrorCode::PaymentMixedMethodsError, res.unwrap_err());
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
             TestUtils::cleanup_storage();
@@ -1092,113 +1435,263 @@ mod medium_cases {
         }
     }
 
-    mod parse_payment_response {
+    mod split_payment_request {
         use super::*;
 
         #[test]
-        fn parse_payment_response_works_for_unknown_payment_method() {
+        fn build_split_payment_req_works_for_method_without_outputs() {
             TestUtils::cleanup_storage();
             payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(WALLET, None).unwrap();
 
-            let res = payments::parse_payment_response(WRONG_PAYMENT_METHOD_NAME,
-                                                       PAYMENT_RESPONSE);
+            let err = payments::build_split_payment_req(wallet_handle,
+                                                        IDENTIFIER,
+                                                        SPLIT_INPUTS,
+                                                        CORRECT_OUTPUTS,
+            ).unwrap_err();
 
-            assert_eq!(ErrorCode::PaymentUnknownMethodError, res.unwrap_err());
+            assert_eq!(ErrorCode::PaymentMixedMethodsError, err);
 
+            WalletUtils::close_wallet(wallet_handle).unwrap();
             TestUtils::cleanup_storage();
         }
 
         #[test]
-        fn parse_payment_response_works_for_generic_error() {
+        fn build_split_payment_req_works_for_unknown_method() {
             TestUtils::cleanup_storage();
             payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(WALLET, None).unwrap();
 
-            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
-
-            payments::mock_method::parse_payment_response::inject_mock(ErrorCode::WalletAccessFailed, "");
-
-            let err = payments::parse_payment_response(PAYMENT_METHOD_NAME, EMPTY_OBJECT).unwrap_err();
+            let err = payments::build_split_payment_req(wallet_handle,
+                                                        IDENTIFIER,
+                                                        INPUTS_UNKNOWN_METHOD,
+                                                        OUTPUTS_UNKNOWN_METHOD,
+            ).unwrap_err();
 
-            assert_eq!(err, ErrorCode::WalletAccessFailed);
+            assert_eq!(ErrorCode::PaymentUnknownMethodError, err);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
             TestUtils::cleanup_storage();
         }
-    }
-
-    mod mint_request {
-        use super::*;
 
         #[test]
-        fn build_mint_request_works_for_empty_outputs() {
+        fn build_split_payment_req_works_for_invalid_wallet_handle() {
             TestUtils::cleanup_storage();
             payments::mock_method::init();
             let wallet_handle = WalletUtils::create_and_open_wallet(WALLET, None).unwrap();
 
-            let res = payments::build_mint_req(wallet_handle,
-                                               IDENTIFIER,
-                                               EMPTY_ARRAY);
+            let err = payments::build_split_payment_req(wallet_handle + 1,
+                                                        IDENTIFIER,
+                                                        CORRECT_INPUTS,
+                                                        CORRECT_OUTPUTS,
+            ).unwrap_err();
 
-            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+            assert_eq!(ErrorCode::WalletInvalidHandle, err);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
             TestUtils::cleanup_storage();
         }
 
         #[test]
-        fn build_mint_request_works_for_unknown_payment_method() {
+        fn build_split_payment_req_works_for_plugin_error() {
             TestUtils::cleanup_storage();
             payments::mock_method::init();
             let wallet_handle = WalletUtils::create_and_open_wallet(WALLET, None).unwrap();
 
-            let res = payments::build_mint_req(wallet_handle,
-                                               IDENTIFIER,
-                                               OUTPUTS_UNKNOWN_METHOD);
+            payments::mock_method::build_payment_req::inject_mock(ErrorCode::WalletAccessFailed, "");
 
-            assert_eq!(ErrorCode::PaymentUnknownMethodError, res.unwrap_err());
+            let err = payments::build_split_payment_req(wallet_handle,
+                                                        IDENTIFIER,
+                                                        CORRECT_INPUTS,
+                                                        CORRECT_OUTPUTS,
+            ).unwrap_err();
+
+            assert_eq!(ErrorCode::WalletAccessFailed, err);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
             TestUtils::cleanup_storage();
         }
+    }
+
+    mod list_payment_methods {
+        use super::*;
 
         #[test]
-        fn build_mint_request_works_for_invalid_wallet_handle() {
-            TestUtils::cleanup_storage();
-            payments::mock_method::init();
-            let wallet_handle = WalletUtils::create_and_open_wallet(WALLET, None).unwrap();
+        fn list_payment_methods_works_for_method_without_optional_operations() {
+            TestUtils::cleanup_storage();
+
+            payments::register_payment_method("no_optional_operations",
+                                              Some(payments::mock_method::create_payment_address::handle),
+                                              Some(payments::mock_method::add_request_fees::handle),
+                                              Some(payments::mock_method::parse_response_with_fees::handle),
+                                              Some(payments::mock_method::build_get_utxo_request::handle),
+                                              Some(payments::mock_method::parse_get_utxo_response::handle),
+                                              Some(payments::mock_method::build_payment_req::handle),
+                                              Some(payments::mock_method::parse_payment_response::handle),
+                                              Some(payments::mock_method::build_mint_req::handle),
+                                              Some(payments::mock_method::build_set_txn_fees_req::handle),
+                                              Some(payments::mock_method::build_get_txn_fees_req::handle),
+                                              Some(payments::mock_method::parse_get_txn_fees_response::handle),
+                                              None,
+                                              None,
+                                              None,
+                                              None,
+                                              None,
+            ).unwrap();
 
-            let invalid_wallet_handle = wallet_handle + 1;
-            let res = payments::build_mint_req(invalid_wallet_handle,
-                                               IDENTIFIER,
-                                               CORRECT_OUTPUTS);
+            let methods = payments::list_payment_methods().unwrap();
+            let methods: Vec<serde_json::Value> = serde_json::from_str(&methods).unwrap();
 
-            assert_eq!(ErrorCode::WalletInvalidHandle, res.unwrap_err());
+            let method = methods.iter().find(|method| method["method"] == "no_optional_operations").unwrap();
+            let capabilities: Vec<&str> = method["capabilities"].as_array().unwrap().iter().map(|c| c.as_str().unwrap()).collect();
+
+            assert!(capabilities.contains(&"build_payment_req"));
+            assert!(!capabilities.contains(&"build_verify_payment_req"));
+            assert!(!capabilities.contains(&"sign_with_address"));
+            assert!(!capabilities.contains(&"get_request_fee"));
 
-            WalletUtils::close_wallet(wallet_handle).unwrap();
             TestUtils::cleanup_storage();
         }
 
         #[test]
-        fn build_mint_request_works_for_invalid_submitter_did() {
+        fn list_payment_methods_works_for_method_registered_with_base_api() {
             TestUtils::cleanup_storage();
-            payments::mock_method::init();
-            let wallet_handle = WalletUtils::create_and_open_wallet(WALLET, None).unwrap();
 
-            let res = payments::build_mint_req(wallet_handle,
-                                               INVALID_IDENTIFIER,
-                                               CORRECT_OUTPUTS);
+            payments::register_base_payment_method("base_api_method",
+                                                   Some(payments::mock_method::create_payment_address::handle),
+                                                   Some(payments::mock_method::add_request_fees::handle),
+                                                   Some(payments::mock_method::parse_response_with_fees::handle),
+                                                   Some(payments::mock_method::build_get_utxo_request::handle),
+                                                   Some(payments::mock_method::parse_get_utxo_response::handle),
+                                                   Some(payments::mock_method::build_payment_req::handle),
+                                                   Some(payments::mock_method::parse_payment_response::handle),
+                                                   Some(payments::mock_method::build_mint_req::handle),
+                                                   Some(payments::mock_method::build_set_txn_fees_req::handle),
+                                                   Some(payments::mock_method::build_get_txn_fees_req::handle),
+                                                   Some(payments::mock_method::parse_get_txn_fees_response::handle),
+            ).unwrap();
 
-            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+            let methods = payments::list_payment_methods().unwrap();
+            let methods: Vec<serde_json::Value> = serde_json::from_str(&methods).unwrap();
+
+            let method = methods.iter().find(|method| method["method"] == "base_api_method").unwrap();
+            let capabilities: Vec<&str> = method["capabilities"].as_array().unwrap().iter().map(|c| c.as_str().unwrap()).collect();
+
+            assert!(capabilities.contains(&"build_payment_req"));
+            assert!(!capabilities.contains(&"build_verify_payment_req"));
+            assert!(!capabilities.contains(&"verify_with_address"));
 
-            WalletUtils::close_wallet(wallet_handle).unwrap();
             TestUtils::cleanup_storage();
         }
+    }
+
+    mod parse_payment_response {
+        use super::*;
 
         #[test]
-        fn build_mint_request_works_for_invalid_outputs_format() {
+        fn parse_payment_response_works_for_unknown_payment_method() {
             TestUtils::cleanup_storage();
             payments::mock_method::init();
-            let wallet_handle = WalletUtils::create_and_open_wallet(WALLET, None).unwrap();
+
+            let res = payments::parse_payment_response(WRONG_PAYMENT_METHOD_NAME,
+                                                       PAYMENT_RESPONSE);
+
+            assert_eq!(ErrorCode::PaymentUnknownMethodError, res.unwrap_err());
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn parse_payment_response_works_for_generic_error() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            payments::mock_method::parse_payment_response::inject_mock(ErrorCode::WalletAccessFailed, "");
+
+            let err = payments::parse_payment_response(PAYMENT_METHOD_NAME, EMPTY_OBJECT).unwrap_err();
+
+            assert_eq!(err, ErrorCode::WalletAccessFailed);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod mint_request {
+        use super::*;
+
+        #[test]
+        fn build_mint_request_works_for_empty_outputs() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(WALLET, None).unwrap();
+
+            let res = payments::build_mint_req(wallet_handle,
+                                               IDENTIFIER,
+                                               EMPTY_ARRAY);
+
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn build_mint_request_works_for_unknown_payment_method() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(WALLET, None).unwrap();
+
+            let res = payments::build_mint_req(wallet_handle,
+                                               IDENTIFIER,
+                                               OUTPUTS_UNKNOWN_METHOD);
+
+            assert_eq!(ErrorCode::PaymentUnknownMethodError, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn build_mint_request_works_for_invalid_wallet_handle() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(WALLET, None).unwrap();
+
+            let invalid_wallet_handle = wallet_handle + 1;
+            let res = payments::build_mint_req(invalid_wallet_handle,
+                                               IDENTIFIER,
+                                               CORRECT_OUTPUTS);
+
+            assert_eq!(ErrorCode::WalletInvalidHandle, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn build_mint_request_works_for_invalid_submitter_did() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(WALLET, None).unwrap();
+
+            let res = payments::build_mint_req(wallet_handle,
+                                               INVALID_IDENTIFIER,
+                                               CORRECT_OUTPUTS);
+
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn build_mint_request_works_for_invalid_outputs_format() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(WALLET, None).unwrap();
 
             let res = payments::build_mint_req(wallet_handle,
                                                IDENTIFIER,
@@ -1251,7 +1744,7 @@ mod medium_cases {
                                                IDENTIFIER,
                                                INCOMPATIBLE_OUTPUTS);
 
-            assert_eq!(ErrorCode::PaymentIncompatibleMethodsError, res.unwrap_err());
+            assert_eq!(ErrorCode::PaymentMixedMethodsError, res.unwrap_err());
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
             TestUtils::cleanup_storage();
@@ -1474,4 +1967,519 @@ mod medium_cases {
             TestUtils::cleanup_storage();
         }
     }
+
+    mod build_verify_payment_req {
+        use super::*;
+
+        #[test]
+        fn build_verify_payment_req_works_for_nonexistant_plugin() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::build_verify_payment_req(wallet_handle, IDENTIFIER, "pay:null1:1_test").unwrap_err();
+
+            assert_eq!(err, ErrorCode::PaymentUnknownMethodError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn build_verify_payment_req_works_for_malformed_receipt() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::build_verify_payment_req(wallet_handle, IDENTIFIER, "pay:null").unwrap_err();
+
+            assert_eq!(err, ErrorCode::PaymentIncompatibleMethodsError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn build_verify_payment_req_works_for_method_without_verification() {
+            TestUtils::cleanup_storage();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            payments::register_payment_method("no_verification",
+                                              Some(payments::mock_method::create_payment_address::handle),
+                                              Some(payments::mock_method::add_request_fees::handle),
+                                              Some(payments::mock_method::parse_response_with_fees::handle),
+                                              Some(payments::mock_method::build_get_utxo_request::handle),
+                                              Some(payments::mock_method::parse_get_utxo_response::handle),
+                                              Some(payments::mock_method::build_payment_req::handle),
+                                              Some(payments::mock_method::parse_payment_response::handle),
+                                              Some(payments::mock_method::build_mint_req::handle),
+                                              Some(payments::mock_method::build_set_txn_fees_req::handle),
+                                              Some(payments::mock_method::build_get_txn_fees_req::handle),
+                                              Some(payments::mock_method::parse_get_txn_fees_response::handle),
+                                              None,
+                                              None,
+                                              None,
+                                              None,
+                                              None,
+            ).unwrap();
+
+            let err = payments::build_verify_payment_req(wallet_handle, IDENTIFIER, "pay:no_verification:1_test").unwrap_err();
+            assert_eq!(err, ErrorCode::PaymentOperationNotSupportedError);
+
+            let err = payments::parse_verify_payment_response("no_verification", EMPTY_OBJECT).unwrap_err();
+            assert_eq!(err, ErrorCode::PaymentOperationNotSupportedError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn build_verify_payment_req_works_for_invalid_wallet_handle() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::build_verify_payment_req(wallet_handle + 1, IDENTIFIER, CORRECT_RECEIPT).unwrap_err();
+
+            assert_eq!(err, ErrorCode::WalletInvalidHandle);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn build_verify_payment_req_works_for_invalid_submitter_did() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::build_verify_payment_req(wallet_handle, INVALID_IDENTIFIER, CORRECT_RECEIPT).unwrap_err();
+
+            assert_eq!(err, ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn build_verify_payment_req_works_for_generic_error() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            payments::mock_method::build_verify_payment_req::inject_mock(ErrorCode::WalletAccessFailed, "");
+
+            let err = payments::build_verify_payment_req(wallet_handle, IDENTIFIER, CORRECT_RECEIPT).unwrap_err();
+
+            assert_eq!(err, ErrorCode::WalletAccessFailed);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod parse_verify_payment_response {
+        use super::*;
+
+        #[test]
+        fn parse_verify_payment_response_works_for_nonexistant_plugin() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+
+            let err = payments::parse_verify_payment_response(WRONG_PAYMENT_METHOD_NAME, EMPTY_OBJECT).unwrap_err();
+
+            assert_eq!(err, ErrorCode::PaymentUnknownMethodError);
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn parse_verify_payment_response_works_for_generic_error() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+
+            payments::mock_method::parse_verify_payment_response::inject_mock(ErrorCode::PaymentSourceDoesNotExistError, "");
+
+            let err = payments::parse_verify_payment_response(PAYMENT_METHOD_NAME, EMPTY_OBJECT).unwrap_err();
+
+            assert_eq!(err, ErrorCode::PaymentSourceDoesNotExistError);
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod prepare_payment {
+        use super::*;
+
+        const POOL_HANDLE: i32 = 1;
+
+        #[test]
+        fn prepare_payment_works_for_no_payment_addresses() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::prepare_payment(wallet_handle, POOL_HANDLE, IDENTIFIER, PAYMENT_METHOD_NAME, 10, CORRECT_FEES, CORRECT_PAYMENT_ADDRESS,
+                                                Some(r#"{"txnType": "txnType1"}"#)).unwrap_err();
+
+            assert_eq!(err, ErrorCode::PaymentInsufficientFundsError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn prepare_payment_works_for_unknown_payment_method() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::prepare_payment(wallet_handle, POOL_HANDLE, IDENTIFIER, WRONG_PAYMENT_METHOD_NAME, 10, EMPTY_OBJECT, "pay:null_payment_handler:test", None).unwrap_err();
+
+            assert_eq!(err, ErrorCode::PaymentUnknownMethodError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn prepare_payment_works_for_incompatible_change_address() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::prepare_payment(wallet_handle, POOL_HANDLE, IDENTIFIER, PAYMENT_METHOD_NAME, 10, EMPTY_OBJECT, "pay:PAYMENT_METHOD_1:test", None).unwrap_err();
+
+            assert_eq!(err, ErrorCode::PaymentIncompatibleMethodsError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn prepare_payment_works_for_invalid_fees_format() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::prepare_payment(wallet_handle, POOL_HANDLE, IDENTIFIER, PAYMENT_METHOD_NAME, 10, EMPTY_ARRAY, CORRECT_PAYMENT_ADDRESS, None).unwrap_err();
+
+            assert_eq!(err, ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn prepare_payment_works_for_invalid_config() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::prepare_payment(wallet_handle, POOL_HANDLE, IDENTIFIER, PAYMENT_METHOD_NAME, 10, EMPTY_OBJECT, CORRECT_PAYMENT_ADDRESS,
+                                                Some(r#"{"strategy": "random"}"#)).unwrap_err();
+
+            assert_eq!(err, ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn prepare_payment_works_for_several_fees_without_txn_type() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::prepare_payment(wallet_handle, POOL_HANDLE, IDENTIFIER, PAYMENT_METHOD_NAME, 10, CORRECT_FEES, CORRECT_PAYMENT_ADDRESS, None).unwrap_err();
+
+            assert_eq!(err, ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn prepare_payment_works_for_fee_rules() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let fees = r#"{"txnType1": {"amount": 1, "roles": {"TRUSTEE": 0}, "perByte": 1, "freeBytes": 100}}"#;
+            let err = payments::prepare_payment(wallet_handle, POOL_HANDLE, IDENTIFIER, PAYMENT_METHOD_NAME, 10, fees, CORRECT_PAYMENT_ADDRESS,
+                                                Some(r#"{"txnType": "txnType1"}"#)).unwrap_err();
+
+            assert_eq!(err, ErrorCode::PaymentOperationNotSupportedError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn prepare_payment_works_for_nothing_to_pay() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::prepare_payment(wallet_handle, POOL_HANDLE, IDENTIFIER, PAYMENT_METHOD_NAME, 0, EMPTY_OBJECT, CORRECT_PAYMENT_ADDRESS, None).unwrap_err();
+
+            assert_eq!(err, ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn prepare_payment_works_for_invalid_wallet_handle() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::prepare_payment(wallet_handle + 1, POOL_HANDLE, IDENTIFIER, PAYMENT_METHOD_NAME, 10, CORRECT_FEES, CORRECT_PAYMENT_ADDRESS, None).unwrap_err();
+
+            assert_eq!(err, ErrorCode::WalletInvalidHandle);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn prepare_payment_works_for_invalid_submitter_did() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::prepare_payment(wallet_handle, POOL_HANDLE, INVALID_IDENTIFIER, PAYMENT_METHOD_NAME, 10, CORRECT_FEES, CORRECT_PAYMENT_ADDRESS, None).unwrap_err();
+
+            assert_eq!(err, ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod sign_with_address {
+        use super::*;
+
+        #[test]
+        fn sign_with_address_works_for_unknown_payment_method() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::sign_with_address(wallet_handle, "pay:null_payment_handler:test", MESSAGE.as_bytes()).unwrap_err();
+            assert_eq!(err, ErrorCode::PaymentUnknownMethodError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn sign_with_address_works_for_malformed_address() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::sign_with_address(wallet_handle, "pay:null", MESSAGE.as_bytes()).unwrap_err();
+            assert_eq!(err, ErrorCode::PaymentIncompatibleMethodsError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn sign_with_address_works_for_method_without_address_signing() {
+            TestUtils::cleanup_storage();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            payments::register_payment_method("no_address_signing",
+                                              Some(payments::mock_method::create_payment_address::handle),
+                                              Some(payments::mock_method::add_request_fees::handle),
+                                              Some(payments::mock_method::parse_response_with_fees::handle),
+                                              Some(payments::mock_method::build_get_utxo_request::handle),
+                                              Some(payments::mock_method::parse_get_utxo_response::handle),
+                                              Some(payments::mock_method::build_payment_req::handle),
+                                              Some(payments::mock_method::parse_payment_response::handle),
+                                              Some(payments::mock_method::build_mint_req::handle),
+                                              Some(payments::mock_method::build_set_txn_fees_req::handle),
+                                              Some(payments::mock_method::build_get_txn_fees_req::handle),
+                                              Some(payments::mock_method::parse_get_txn_fees_response::handle),
+                                              None,
+                                              None,
+                                              None,
+                                              None,
+                                              None,
+            ).unwrap();
+
+            let err = payments::sign_with_address(wallet_handle, "pay:no_address_signing:test", MESSAGE.as_bytes()).unwrap_err();
+            assert_eq!(err, ErrorCode::PaymentOperationNotSupportedError);
+
+            let err = payments::verify_with_address("pay:no_address_signing:test", MESSAGE.as_bytes(), SIGNATURE).unwrap_err();
+            assert_eq!(err, ErrorCode::PaymentOperationNotSupportedError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn sign_with_address_works_for_invalid_wallet_handle() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::sign_with_address(wallet_handle + 1, CORRECT_PAYMENT_ADDRESS, MESSAGE.as_bytes()).unwrap_err();
+            assert_eq!(err, ErrorCode::WalletInvalidHandle);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn sign_with_address_works_for_generic_error() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            payments::mock_method::sign_with_address::inject_mock(ErrorCode::WalletItemNotFound, &[]);
+
+            let err = payments::sign_with_address(wallet_handle, CORRECT_PAYMENT_ADDRESS, MESSAGE.as_bytes()).unwrap_err();
+            assert_eq!(err, ErrorCode::WalletItemNotFound);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod verify_with_address {
+        use super::*;
+
+        #[test]
+        fn verify_with_address_works_for_unknown_payment_method() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+
+            let err = payments::verify_with_address("pay:null_payment_handler:test", MESSAGE.as_bytes(), SIGNATURE).unwrap_err();
+            assert_eq!(err, ErrorCode::PaymentUnknownMethodError);
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn verify_with_address_works_for_generic_error() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+
+            payments::mock_method::verify_with_address::inject_mock(ErrorCode::CommonInvalidStructure, false);
+
+            let err = payments::verify_with_address(CORRECT_PAYMENT_ADDRESS, MESSAGE.as_bytes(), SIGNATURE).unwrap_err();
+            assert_eq!(err, ErrorCode::CommonInvalidStructure);
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod payment_history {
+        use super::*;
+
+        #[test]
+        fn parse_payment_response_with_history_works_for_invalid_history() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::parse_payment_response_with_history(wallet_handle, PAYMENT_METHOD_NAME, EMPTY_OBJECT, r#"{"outputs": []}"#).unwrap_err();
+            assert_eq!(err, ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn parse_payment_response_with_history_works_for_invalid_parsed_utxos() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let utxos = r#"[{"input": "pay:null:5_2", "amount": -2, "paymentAddress": "pay:null:2"}]"#;
+            payments::mock_method::parse_payment_response::inject_mock(ErrorCode::Success, utxos);
+
+            // the payment is on the ledger already, so parsed response is returned even if history can't be recorded
+            let res_plugin = payments::parse_payment_response_with_history(wallet_handle, PAYMENT_METHOD_NAME, HISTORY_RESPONSE, PAYMENT_HISTORY).unwrap();
+            assert_eq!(res_plugin, utxos);
+
+            let history = payments::search_payment_history(wallet_handle, EMPTY_OBJECT).unwrap();
+            assert_eq!(history, EMPTY_ARRAY);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn parse_payment_response_with_history_works_for_unknown_payment_method() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::parse_payment_response_with_history(wallet_handle, WRONG_PAYMENT_METHOD_NAME, EMPTY_OBJECT, PAYMENT_HISTORY).unwrap_err();
+            assert_eq!(err, ErrorCode::PaymentUnknownMethodError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn parse_payment_response_with_history_works_for_invalid_wallet_handle() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::parse_payment_response_with_history(wallet_handle + 1, PAYMENT_METHOD_NAME, EMPTY_OBJECT, PAYMENT_HISTORY).unwrap_err();
+            assert_eq!(err, ErrorCode::WalletInvalidHandle);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn parse_payment_response_with_history_works_for_generic_error() {
+            TestUtils::cleanup_storage();
+            payments::mock_method::init();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            payments::mock_method::parse_payment_response::inject_mock(ErrorCode::WalletAccessFailed, "");
+
+            let err = payments::parse_payment_response_with_history(wallet_handle, PAYMENT_METHOD_NAME, EMPTY_OBJECT, PAYMENT_HISTORY).unwrap_err();
+            assert_eq!(err, ErrorCode::WalletAccessFailed);
+
+            let history = payments::search_payment_history(wallet_handle, EMPTY_OBJECT).unwrap();
+            assert_eq!(history, EMPTY_ARRAY);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn search_payment_history_works_for_invalid_query() {
+            TestUtils::cleanup_storage();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::search_payment_history(wallet_handle, "not a query").unwrap_err();
+            assert_eq!(err, ErrorCode::WalletQueryError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn search_payment_history_works_for_invalid_wallet_handle() {
+            TestUtils::cleanup_storage();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let err = payments::search_payment_history(wallet_handle + 1, EMPTY_OBJECT).unwrap_err();
+            assert_eq!(err, ErrorCode::WalletInvalidHandle);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+    }
 }
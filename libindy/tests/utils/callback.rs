@@ -1,6 +1,6 @@
 extern crate libc;
 
-use indy::api::ErrorCode;
+use indy::api::{ErrorCode, indy_get_current_error_details};
 
 use self::libc::c_char;
 use std::ffi::CStr;
@@ -157,6 +157,44 @@ impl CallbackUtils {
         (receiver, command_handle, Some(_callback))
     }
 
+    pub fn _closure_to_cb_ec_error_details_string_string() -> (Receiver<(ErrorCode, Option<String>)>, i32,
+                                                                Option<extern fn(command_handle: i32,
+                                                                                 err: ErrorCode,
+                                                                                 str1: *const c_char,
+                                                                                 str2: *const c_char)>) {
+        let (sender, receiver) = channel();
+
+        lazy_static! {
+            static ref CALLBACKS: Mutex < HashMap < i32, Box < FnMut(ErrorCode, Option<String>) + Send > >> = Default::default();
+    }
+
+        let closure = Box::new(move |err, details| {
+            sender.send((err, details)).unwrap();
+        });
+
+        extern "C" fn _callback(command_handle: i32, err: ErrorCode, _str1: *const c_char, _str2: *const c_char) {
+            let mut callbacks = CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+
+            // details are available only in the thread libindy calls callback in
+            let mut details_ptr: *const c_char = ::std::ptr::null();
+            indy_get_current_error_details(&mut details_ptr);
+            let details = if details_ptr.is_null() {
+                None
+            } else {
+                Some(unsafe { CStr::from_ptr(details_ptr).to_str().unwrap().to_string() })
+            };
+
+            cb(err, details)
+        }
+
+        let mut callbacks = CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (receiver, command_handle, Some(_callback))
+    }
+
     pub fn _closure_to_cb_ec_string_string_string() -> (Receiver<(ErrorCode, String, String, String)>, i32,
                                                         Option<extern fn(command_handle: i32,
                                                                          err: ErrorCode,
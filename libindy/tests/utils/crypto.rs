@@ -1,11 +1,16 @@
 extern crate libc;
 
 use std::ffi::CString;
+use std::ptr::null;
 
 use indy::api::crypto::*;
 use indy::api::ErrorCode;
 
 use utils::callback::CallbackUtils;
+use utils::xor_crypto_type::{XorCryptoType, XOR_CRYPTO_TYPE};
+use utils::external_signer::{SoftwareSigner, SOFTWARE_SIGNER};
+
+use std::sync::{Once, ONCE_INIT};
 
 pub struct CryptoUtils {}
 
@@ -24,6 +29,110 @@ impl CryptoUtils {
         super::results::result_to_string(err, receiver)
     }
 
+    pub fn create_key_with_crypto_type(wallet_handle: i32, seed: Option<&str>, crypto_type: &str) -> Result<String, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+        let key_json = match seed {
+            Some(seed) => format!(r#"{{"seed":"{}", "crypto_type":"{}"}}"#, seed, crypto_type),
+            None => format!(r#"{{"crypto_type":"{}"}}"#, crypto_type)
+        };
+        let key_json = CString::new(key_json).unwrap();
+
+        let err = indy_create_key(command_handle, wallet_handle, key_json.as_ptr(), cb);
+
+        super::results::result_to_string(err, receiver)
+    }
+
+    pub fn register_crypto_type(crypto_type_name: &str) -> Result<(), ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec();
+
+        let crypto_type_name = CString::new(crypto_type_name).unwrap();
+
+        let err = indy_register_crypto_type(command_handle,
+                                            crypto_type_name.as_ptr(),
+                                            Some(XorCryptoType::create_key),
+                                            Some(XorCryptoType::sign),
+                                            Some(XorCryptoType::verify),
+                                            Some(XorCryptoType::encrypt_sealed),
+                                            Some(XorCryptoType::decrypt_sealed),
+                                            Some(XorCryptoType::free),
+                                            cb);
+
+        super::results::result_to_empty(err, receiver)
+    }
+
+    pub fn register_xor_crypto_type() {
+        lazy_static! {
+            static ref REGISTER_XOR_CRYPTO_TYPE_INIT: Once = ONCE_INIT;
+        }
+
+        REGISTER_XOR_CRYPTO_TYPE_INIT.call_once(|| {
+            CryptoUtils::register_crypto_type(XOR_CRYPTO_TYPE).unwrap();
+        });
+    }
+
+    pub fn register_external_signer(signer_name: &str) -> Result<(), ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec();
+
+        let signer_name = CString::new(signer_name).unwrap();
+
+        let err = indy_register_external_signer(command_handle,
+                                                signer_name.as_ptr(),
+                                                Some(SoftwareSigner::sign),
+                                                Some(SoftwareSigner::precompute),
+                                                Some(SoftwareSigner::decrypt_sealed),
+                                                Some(SoftwareSigner::free),
+                                                cb);
+
+        super::results::result_to_empty(err, receiver)
+    }
+
+    pub fn register_software_signer() {
+        lazy_static! {
+            static ref REGISTER_SOFTWARE_SIGNER_INIT: Once = ONCE_INIT;
+        }
+
+        REGISTER_SOFTWARE_SIGNER_INIT.call_once(|| {
+            CryptoUtils::register_external_signer(SOFTWARE_SIGNER).unwrap();
+        });
+    }
+
+    pub fn create_external_key(wallet_handle: i32, key_json: &str) -> Result<String, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+        let key_json = CString::new(key_json).unwrap();
+
+        let err = indy_create_external_key(command_handle, wallet_handle, key_json.as_ptr(), cb);
+
+        super::results::result_to_string(err, receiver)
+    }
+
+    pub fn create_master_seed(wallet_handle: i32, master_seed_id: Option<&str>, config_json: Option<&str>) -> Result<String, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+        let master_seed_id_str = master_seed_id.map(|s| CString::new(s).unwrap());
+        let config_json_str = config_json.map(|s| CString::new(s).unwrap());
+
+        let err = indy_create_master_seed(command_handle,
+                                          wallet_handle,
+                                          master_seed_id_str.as_ref().map(|s| s.as_ptr()).unwrap_or(null()),
+                                          config_json_str.as_ref().map(|s| s.as_ptr()).unwrap_or(null()),
+                                          cb);
+
+        super::results::result_to_string(err, receiver)
+    }
+
+    pub fn create_key_from_path(wallet_handle: i32, master_seed_id: &str, path: &str) -> Result<String, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+        let key_json = json!({"master_seed_id": master_seed_id, "path": path}).to_string();
+        let key_json = CString::new(key_json).unwrap();
+
+        let err = indy_create_key_from_path(command_handle, wallet_handle, key_json.as_ptr(), cb);
+
+        super::results::result_to_string(err, receiver)
+    }
+
     pub fn set_key_metadata(wallet_handle: i32, verkey: &str, metadata: &str) -> Result<(), ErrorCode> {
         let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec();
 
@@ -80,6 +189,40 @@ impl CryptoUtils {
         super::results::result_to_bool(err, receiver)
     }
 
+    pub fn sign_jws(wallet_handle: i32, signer_vk: &str, payload_json: &str, options_json: Option<&str>) -> Result<String, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+        let signer_vk = CString::new(signer_vk).unwrap();
+        let payload_json = CString::new(payload_json).unwrap();
+        let options_json_str = options_json.map(|s| CString::new(s).unwrap());
+
+        let err = indy_crypto_sign_jws(command_handle,
+                                       wallet_handle,
+                                       signer_vk.as_ptr(),
+                                       payload_json.as_ptr(),
+                                       options_json_str.as_ref().map(|s| s.as_ptr()).unwrap_or(null()),
+                                       cb);
+
+        super::results::result_to_string(err, receiver)
+    }
+
+    pub fn verify_jws(wallet_handle: i32, jws: &str, detached_payload_json: Option<&str>, signer_vk: Option<&str>) -> Result<bool, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_bool();
+
+        let jws = CString::new(jws).unwrap();
+        let detached_payload_json_str = detached_payload_json.map(|s| CString::new(s).unwrap());
+        let signer_vk_str = signer_vk.map(|s| CString::new(s).unwrap());
+
+        let err = indy_crypto_verify_jws(command_handle,
+                                         wallet_handle,
+                                         jws.as_ptr(),
+                                         detached_payload_json_str.as_ref().map(|s| s.as_ptr()).unwrap_or(null()),
+                                         signer_vk_str.as_ref().map(|s| s.as_ptr()).unwrap_or(null()),
+                                         cb);
+
+        super::results::result_to_bool(err, receiver)
+    }
+
     pub fn auth_crypt(wallet_handle: i32, my_vk: &str, their_vk: &str, msg: &[u8]) -> Result<Vec<u8>, ErrorCode> {
         let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_vec_u8();
 
@@ -143,4 +286,35 @@ impl CryptoUtils {
 
         super::results::result_to_vec_u8(err, receiver)
     }
-}
\ No newline at end of file
+
+    pub fn pack_message(wallet_handle: i32, msg: &[u8], receiver_keys: &str, sender: Option<&str>) -> Result<Vec<u8>, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_vec_u8();
+
+        let receiver_keys = CString::new(receiver_keys).unwrap();
+        let sender_str = sender.map(|s| CString::new(s).unwrap());
+
+        let err =
+            indy_pack_message(command_handle,
+                              wallet_handle,
+                              msg.as_ptr() as *const u8,
+                              msg.len() as u32,
+                              receiver_keys.as_ptr(),
+                              sender_str.as_ref().map(|s| s.as_ptr()).unwrap_or(null()),
+                              cb);
+
+        super::results::result_to_vec_u8(err, receiver)
+    }
+
+    pub fn unpack_message(wallet_handle: i32, jwe: &[u8]) -> Result<Vec<u8>, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_vec_u8();
+
+        let err =
+            indy_unpack_message(command_handle,
+                                wallet_handle,
+                                jwe.as_ptr() as *const u8,
+                                jwe.len() as u32,
+                                cb);
+
+        super::results::result_to_vec_u8(err, receiver)
+    }
+}
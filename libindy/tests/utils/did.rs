@@ -1,6 +1,7 @@
 extern crate libc;
 
 use std::ffi::CString;
+use std::ptr::null;
 
 use indy::api::did::*;
 use indy::api::ErrorCode;
@@ -93,6 +94,17 @@ impl DidUtils {
         Ok(verkey)
     }
 
+    pub fn rotate_did_key_on_ledger(pool_handle: i32, wallet_handle: i32, did: &str, key_json: &str) -> Result<String, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+        let did = CString::new(did).unwrap();
+        let key_json = CString::new(key_json).unwrap();
+
+        let err = indy_rotate_did_key_on_ledger(command_handle, pool_handle, wallet_handle, did.as_ptr(), key_json.as_ptr(), cb);
+
+        super::results::result_to_string(err, receiver)
+    }
+
     pub fn key_for_did(pool_handle: i32, wallet_handle: i32, did: &str) -> Result<String, ErrorCode> {
         let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
 
@@ -103,6 +115,28 @@ impl DidUtils {
         super::results::result_to_string(err, receiver)
     }
 
+    pub fn key_for_did_with_options(pool_handle: i32, wallet_handle: i32, did: &str, options_json: Option<&str>) -> Result<String, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+        let did = CString::new(did).unwrap();
+        let options_json_str = options_json.map(|s| CString::new(s).unwrap());
+
+        let err = indy_key_for_did_with_options(command_handle, pool_handle, wallet_handle, did.as_ptr(),
+                                                options_json_str.as_ref().map(|s| s.as_ptr()).unwrap_or(null()), cb);
+
+        super::results::result_to_string(err, receiver)
+    }
+
+    pub fn refresh_their_did(wallet_handle: i32, pool_handle: i32, did: &str) -> Result<String, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+        let did = CString::new(did).unwrap();
+
+        let err = indy_refresh_their_did(command_handle, wallet_handle, pool_handle, did.as_ptr(), cb);
+
+        super::results::result_to_string(err, receiver)
+    }
+
     pub fn key_for_local_did(wallet_handle: i32, did: &str) -> Result<String, ErrorCode> {
         let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
 
@@ -140,6 +174,18 @@ impl DidUtils {
         super::results::result_to_string_opt_string(err, receiver)
     }
 
+    pub fn get_endpoint_for_did_with_options(wallet_handle: i32, pool_handle: i32, did: &str, options_json: Option<&str>) -> Result<(String, Option<String>), ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_string_opt_string();
+
+        let did = CString::new(did).unwrap();
+        let options_json_str = options_json.map(|s| CString::new(s).unwrap());
+
+        let err = indy_get_endpoint_for_did_with_options(command_handle, wallet_handle, pool_handle, did.as_ptr(),
+                                                         options_json_str.as_ref().map(|s| s.as_ptr()).unwrap_or(null()), cb);
+
+        super::results::result_to_string_opt_string(err, receiver)
+    }
+
     pub fn set_did_metadata(wallet_handle: i32, did: &str, metadata: &str) -> Result<(), ErrorCode> {
         let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec();
 
@@ -181,4 +227,25 @@ impl DidUtils {
 
         super::results::result_to_string(err, receiver)
     }
-}
\ No newline at end of file
+
+    pub fn resolve_did_document(pool_handle: i32, did: &str) -> Result<String, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+        let did = CString::new(did).unwrap();
+
+        let err = indy_resolve_did_document(command_handle, pool_handle, did.as_ptr(), cb);
+
+        super::results::result_to_string(err, receiver)
+    }
+
+    pub fn qualify_did(did: &str, method_name: &str) -> Result<String, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+        let did = CString::new(did).unwrap();
+        let method_name = CString::new(method_name).unwrap();
+
+        let err = indy_qualify_did(command_handle, did.as_ptr(), method_name.as_ptr(), cb);
+
+        super::results::result_to_string(err, receiver)
+    }
+}
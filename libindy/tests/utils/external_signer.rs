@@ -0,0 +1,138 @@
+extern crate libc;
+extern crate sodiumoxide;
+extern crate rust_base58;
+
+use indy::api::ErrorCode;
+
+use self::libc::{c_char, c_int};
+use self::rust_base58::ToBase58;
+use self::sodiumoxide::crypto::{box_, sealedbox, sign};
+
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::ptr;
+use std::slice;
+use std::sync::Mutex;
+
+pub const SOFTWARE_SIGNER: &'static str = "software_signer";
+
+extern {
+    fn crypto_sign_ed25519_pk_to_curve25519(curve25519_pk: *mut [u8; 32],
+                                            ed25519_pk: *const [u8; 32]) -> c_int;
+    fn crypto_sign_ed25519_sk_to_curve25519(curve25519_sk: *mut [u8; 32],
+                                            ed25519_sk: *const [u8; 64]) -> c_int;
+}
+
+lazy_static! {
+    static ref SOFTWARE_SIGNER_KEYS: Mutex<HashMap<String, (sign::PublicKey, sign::SecretKey)>> = Default::default();
+}
+
+/// Software stand-in for HSM backed external signer.
+/// Keys never leave this module, libindy gets only key references.
+pub struct SoftwareSigner {}
+
+impl SoftwareSigner {
+    /// Generates key inside of the signer and returns its verkey
+    pub fn generate_key(key_ref: &str) -> String {
+        let (pk, sk) = sign::gen_keypair();
+        let verkey = pk.0.to_base58();
+
+        SOFTWARE_SIGNER_KEYS.lock().unwrap().insert(key_ref.to_string(), (pk, sk));
+
+        verkey
+    }
+
+    pub extern fn sign(key_ref: *const c_char,
+                       message: *const u8,
+                       message_len: u32,
+                       signature_p: *mut *const u8,
+                       signature_len_p: *mut u32) -> ErrorCode {
+        let (_, sk) = match SoftwareSigner::_get_key(key_ref) {
+            Some(key) => key,
+            None => return ErrorCode::WalletItemNotFound
+        };
+
+        let message = unsafe { slice::from_raw_parts(message, message_len as usize) };
+        let signature = sign::sign_detached(message, &sk);
+
+        SoftwareSigner::_return_buffer(signature.0.to_vec(), signature_p, signature_len_p);
+
+        ErrorCode::Success
+    }
+
+    pub extern fn precompute(key_ref: *const c_char,
+                             their_pk: *const u8,
+                             their_pk_len: u32,
+                             precomputed_key_p: *mut *const u8,
+                             precomputed_key_len_p: *mut u32) -> ErrorCode {
+        let (_, sk) = match SoftwareSigner::_get_key(key_ref) {
+            Some(key) => key,
+            None => return ErrorCode::WalletItemNotFound
+        };
+
+        let their_pk = match box_::PublicKey::from_slice(unsafe { slice::from_raw_parts(their_pk, their_pk_len as usize) }) {
+            Some(their_pk) => their_pk,
+            None => return ErrorCode::CommonInvalidStructure
+        };
+
+        let precomputed_key = box_::precompute(&their_pk, &SoftwareSigner::_sk_to_curve25519(&sk));
+
+        SoftwareSigner::_return_buffer(precomputed_key.0.to_vec(), precomputed_key_p, precomputed_key_len_p);
+
+        ErrorCode::Success
+    }
+
+    pub extern fn decrypt_sealed(key_ref: *const c_char,
+                                 encrypted: *const u8,
+                                 encrypted_len: u32,
+                                 decrypted_p: *mut *const u8,
+                                 decrypted_len_p: *mut u32) -> ErrorCode {
+        let (pk, sk) = match SoftwareSigner::_get_key(key_ref) {
+            Some(key) => key,
+            None => return ErrorCode::WalletItemNotFound
+        };
+
+        let encrypted = unsafe { slice::from_raw_parts(encrypted, encrypted_len as usize) };
+
+        let decrypted = match sealedbox::open(encrypted,
+                                              &SoftwareSigner::_pk_to_curve25519(&pk),
+                                              &SoftwareSigner::_sk_to_curve25519(&sk)) {
+            Ok(decrypted) => decrypted,
+            Err(_) => return ErrorCode::CommonInvalidStructure
+        };
+
+        SoftwareSigner::_return_buffer(decrypted, decrypted_p, decrypted_len_p);
+
+        ErrorCode::Success
+    }
+
+    pub extern fn free(data: *const u8, data_len: u32) -> ErrorCode {
+        unsafe { Box::from_raw(slice::from_raw_parts_mut(data as *mut u8, data_len as usize) as *mut [u8]) };
+        ErrorCode::Success
+    }
+
+    fn _get_key(key_ref: *const c_char) -> Option<(sign::PublicKey, sign::SecretKey)> {
+        let key_ref = unsafe { CStr::from_ptr(key_ref) }.to_str().unwrap();
+        SOFTWARE_SIGNER_KEYS.lock().unwrap().get(key_ref).cloned()
+    }
+
+    fn _pk_to_curve25519(pk: &sign::PublicKey) -> box_::PublicKey {
+        let mut to: [u8; 32] = [0; 32];
+        unsafe { crypto_sign_ed25519_pk_to_curve25519(&mut to, &pk.0); }
+        box_::PublicKey(to)
+    }
+
+    fn _sk_to_curve25519(sk: &sign::SecretKey) -> box_::SecretKey {
+        let mut to: [u8; 32] = [0; 32];
+        unsafe { crypto_sign_ed25519_sk_to_curve25519(&mut to, &sk.0); }
+        box_::SecretKey(to)
+    }
+
+    fn _return_buffer(data: Vec<u8>, data_p: *mut *const u8, data_len_p: *mut u32) {
+        let data = data.into_boxed_slice();
+        unsafe {
+            *data_len_p = data.len() as u32;
+            *data_p = if data.is_empty() { ptr::null() } else { Box::into_raw(data) as *const u8 };
+        }
+    }
+}
@@ -19,6 +19,8 @@ pub mod non_secrets;
 pub mod results;
 pub mod payments;
 pub mod rand_utils;
+pub mod xor_crypto_type;
+pub mod external_signer;
 
 #[macro_use]
 #[allow(unused_macros)]
@@ -1,3 +1,5 @@
+extern crate serde_json;
+
 use indy::api::ErrorCode;
 use indy::api::non_secrets::*;
 
@@ -94,6 +96,67 @@ impl NonSecretsUtils {
         super::results::result_to_empty(err, receiver)
     }
 
+    pub fn update_wallet_record_expiry(wallet_handle: i32, type_: &str, id: &str, expires_at: i64) -> Result<(), ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec();
+
+        let type_ = CString::new(type_).unwrap();
+        let id = CString::new(id).unwrap();
+
+        let err =
+            indy_update_wallet_record_expiry(command_handle,
+                                             wallet_handle,
+                                             type_.as_ptr(),
+                                             id.as_ptr(),
+                                             expires_at,
+                                             cb);
+
+        super::results::result_to_empty(err, receiver)
+    }
+
+    pub fn update_wallet_record_value_with_version(wallet_handle: i32, type_: &str, id: &str, value: &str, expected_version: i64) -> Result<(), ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec();
+
+        let type_ = CString::new(type_).unwrap();
+        let id = CString::new(id).unwrap();
+        let value = CString::new(value).unwrap();
+
+        let err =
+            indy_update_wallet_record_value_with_version(command_handle,
+                                                         wallet_handle,
+                                                         type_.as_ptr(),
+                                                         id.as_ptr(),
+                                                         value.as_ptr(),
+                                                         expected_version,
+                                                         cb);
+
+        super::results::result_to_empty(err, receiver)
+    }
+
+    pub fn update_wallet_record_tags_with_version(wallet_handle: i32, type_: &str, id: &str, tags_json: &str, expected_version: i64) -> Result<(), ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec();
+
+        let type_ = CString::new(type_).unwrap();
+        let id = CString::new(id).unwrap();
+        let tags_json = CString::new(tags_json).unwrap();
+
+        let err =
+            indy_update_wallet_record_tags_with_version(command_handle,
+                                                        wallet_handle,
+                                                        type_.as_ptr(),
+                                                        id.as_ptr(),
+                                                        tags_json.as_ptr(),
+                                                        expected_version,
+                                                        cb);
+
+        super::results::result_to_empty(err, receiver)
+    }
+
+    pub fn get_wallet_record_version(wallet_handle: i32, type_: &str, id: &str) -> Result<i64, ErrorCode> {
+        let record = NonSecretsUtils::get_wallet_record(wallet_handle, type_, id, r#"{"retrieveVersion": true}"#)?;
+        let record: serde_json::Value = serde_json::from_str(&record).unwrap();
+        Ok(record["version"].as_i64().unwrap())
+    }
+
     pub fn add_wallet_record_tags(wallet_handle: i32, type_: &str, id: &str, tags_json: &str) -> Result<(), ErrorCode> {
         let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec();
 
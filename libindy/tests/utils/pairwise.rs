@@ -72,4 +72,51 @@ impl PairwiseUtils {
 
         super::results::result_to_empty(err, receiver)
     }
-}
\ No newline at end of file
+
+    pub fn add_pairwise_my_did(wallet_handle: i32, their_did: &str, my_did: &str) -> Result<(), ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec();
+
+        let their_did = CString::new(their_did).unwrap();
+        let my_did = CString::new(my_did).unwrap();
+
+        let err = indy_add_pairwise_my_did(command_handle, wallet_handle, their_did.as_ptr(), my_did.as_ptr(), cb);
+
+        super::results::result_to_empty(err, receiver)
+    }
+
+    pub fn delete_pairwise(wallet_handle: i32, their_did: &str) -> Result<(), ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec();
+
+        let their_did = CString::new(their_did).unwrap();
+
+        let err = indy_delete_pairwise(command_handle, wallet_handle, their_did.as_ptr(), cb);
+
+        super::results::result_to_empty(err, receiver)
+    }
+
+    pub fn search_pairwise(wallet_handle: i32, query_json: &str) -> Result<i32, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_i32();
+
+        let query_json = CString::new(query_json).unwrap();
+
+        let err = indy_search_pairwise(command_handle, wallet_handle, query_json.as_ptr(), cb);
+
+        super::results::result_to_int(err, receiver)
+    }
+
+    pub fn fetch_pairwise_search_next_records(search_handle: i32, count: usize) -> Result<String, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+        let err = indy_fetch_pairwise_search_next_records(command_handle, search_handle, count, cb);
+
+        super::results::result_to_string(err, receiver)
+    }
+
+    pub fn close_pairwise_search(search_handle: i32) -> Result<(), ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec();
+
+        let err = indy_close_pairwise_search(command_handle, search_handle, cb);
+
+        super::results::result_to_empty(err, receiver)
+    }
+}
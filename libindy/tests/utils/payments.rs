@@ -5,6 +5,7 @@ use utils::callback::CallbackUtils;
 use std::collections::VecDeque;
 use std::ffi::CString;
 use std::os::raw::c_char;
+use std::ptr::null;
 use std::sync::{Once, ONCE_INIT, Mutex};
 
 pub struct PaymentsUtils {}
@@ -72,20 +73,25 @@ pub mod mock_method {
         CREATE_PAYMENT_METHOD_INIT.call_once(|| {
             let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec();
             let payment_method_name = CString::new("null").unwrap();
-            indy_register_payment_method(cmd_handle,
-                                         payment_method_name.as_ptr(),
-                                         Some(create_payment_address::handle),
-                                         Some(add_request_fees::handle),
-                                         Some(parse_response_with_fees::handle),
-                                         Some(build_get_utxo_request::handle),
-                                         Some(parse_get_utxo_response::handle),
-                                         Some(build_payment_req::handle),
-                                         Some(parse_payment_response::handle),
-                                         Some(build_mint_req::handle),
-                                         Some(build_set_txn_fees_req::handle),
-                                         Some(build_get_txn_fees_req::handle),
-                                         Some(parse_get_txn_fees_response::handle),
-                                         cb,
+            indy_register_payment_method_ex(cmd_handle,
+                                            payment_method_name.as_ptr(),
+                                            Some(create_payment_address::handle),
+                                            Some(add_request_fees::handle),
+                                            Some(parse_response_with_fees::handle),
+                                            Some(build_get_utxo_request::handle),
+                                            Some(parse_get_utxo_response::handle),
+                                            Some(build_payment_req::handle),
+                                            Some(parse_payment_response::handle),
+                                            Some(build_mint_req::handle),
+                                            Some(build_set_txn_fees_req::handle),
+                                            Some(build_get_txn_fees_req::handle),
+                                            Some(parse_get_txn_fees_response::handle),
+                                            Some(build_verify_payment_req::handle),
+                                            Some(parse_verify_payment_response::handle),
+                                            Some(sign_with_address::handle),
+                                            Some(verify_with_address::handle),
+                                            Some(get_request_fee::handle),
+                                            cb,
             );
 
             receiver.recv().unwrap();
@@ -135,6 +141,100 @@ pub mod mock_method {
     pub mod parse_get_txn_fees_response {
         mocked_handler!(_resp_json: *const c_char);
     }
+
+    pub mod build_verify_payment_req {
+        mocked_handler!(_wallet_handle: i32, _submitter_did: *const c_char, _receipt: *const c_char);
+    }
+
+    pub mod parse_verify_payment_response {
+        mocked_handler!(_resp_json: *const c_char);
+    }
+
+    pub mod sign_with_address {
+        use super::*;
+
+        lazy_static! {
+          static ref INJECTIONS: Mutex<VecDeque<(ErrorCode, Vec<u8>)>> = Default::default();
+        }
+
+        pub extern fn handle(cmd_handle: i32,
+                             _wallet_handle: i32,
+                             _address: *const c_char,
+                             _message_raw: *const u8,
+                             _message_len: u32,
+                             cb: Option<extern fn(command_handle_: i32,
+                                                  err: ErrorCode,
+                                                  signature_raw: *const u8,
+                                                  signature_len: u32) -> ErrorCode>) -> ErrorCode {
+            let cb = cb.unwrap_or_else(|| {
+                panic!("Null passed as callback!")
+            });
+
+            let (err, res) = INJECTIONS.lock().unwrap().pop_front().expect("No injections left!");
+            (cb)(cmd_handle, err, res.as_ptr(), res.len() as u32)
+        }
+
+        pub fn inject_mock(err: ErrorCode, res: &[u8]) {
+            INJECTIONS.lock().unwrap().push_back((err, res.to_vec()))
+        }
+    }
+
+    pub mod verify_with_address {
+        use super::*;
+
+        lazy_static! {
+          static ref INJECTIONS: Mutex<VecDeque<(ErrorCode, bool)>> = Default::default();
+        }
+
+        pub extern fn handle(cmd_handle: i32,
+                             _address: *const c_char,
+                             _message_raw: *const u8,
+                             _message_len: u32,
+                             _signature_raw: *const u8,
+                             _signature_len: u32,
+                             cb: Option<extern fn(command_handle_: i32,
+                                                  err: ErrorCode,
+                                                  valid: bool) -> ErrorCode>) -> ErrorCode {
+            let cb = cb.unwrap_or_else(|| {
+                panic!("Null passed as callback!")
+            });
+
+            let (err, res) = INJECTIONS.lock().unwrap().pop_front().expect("No injections left!");
+            (cb)(cmd_handle, err, res)
+        }
+
+        pub fn inject_mock(err: ErrorCode, res: bool) {
+            INJECTIONS.lock().unwrap().push_back((err, res))
+        }
+    }
+
+    pub mod get_request_fee {
+        use super::*;
+
+        lazy_static! {
+          static ref INJECTIONS: Mutex<VecDeque<(ErrorCode, u64)>> = Default::default();
+        }
+
+        pub extern fn handle(cmd_handle: i32,
+                             _wallet_handle: i32,
+                             _submitter_did: *const c_char,
+                             _req_json: *const c_char,
+                             _fees_json: *const c_char,
+                             cb: Option<extern fn(command_handle_: i32,
+                                                  err: ErrorCode,
+                                                  fee: u64) -> ErrorCode>) -> ErrorCode {
+            let cb = cb.unwrap_or_else(|| {
+                panic!("Null passed as callback!")
+            });
+
+            let (err, res) = INJECTIONS.lock().unwrap().pop_front().expect("No injections left!");
+            (cb)(cmd_handle, err, res)
+        }
+
+        pub fn inject_mock(err: ErrorCode, res: u64) {
+            INJECTIONS.lock().unwrap().push_back((err, res))
+        }
+    }
 }
 
 pub fn register_payment_method(payment_method_name: &str,
@@ -149,6 +249,52 @@ pub fn register_payment_method(payment_method_name: &str,
                                build_set_txn_fees_req: Option<BuildSetTxnFeesReqCB>,
                                build_get_txn_fees_req: Option<BuildGetTxnFeesReqCB>,
                                parse_get_txn_fees_response: Option<ParseGetTxnFeesResponseCB>,
+                               build_verify_payment_req: Option<BuildVerifyPaymentReqCB>,
+                               parse_verify_payment_response: Option<ParseVerifyPaymentResponseCB>,
+                               sign_with_address: Option<SignWithAddressCB>,
+                               verify_with_address: Option<VerifyWithAddressCB>,
+                               get_request_fee: Option<GetRequestFeeCB>,
+) -> Result<(), ErrorCode> {
+    let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec();
+
+    let payment_method_name = CString::new(payment_method_name).unwrap();
+
+    let err = indy_register_payment_method_ex(cmd_handle,
+                                              payment_method_name.as_ptr(),
+                                              create_payment_address,
+                                              add_request_fees,
+                                              parse_response_with_fees,
+                                              build_get_utxo_request,
+                                              parse_get_utxo_response,
+                                              build_payment_req,
+                                              parse_payment_response,
+                                              build_mint_req,
+                                              build_set_txn_fees_req,
+                                              build_get_txn_fees_req,
+                                              parse_get_txn_fees_response,
+                                              build_verify_payment_req,
+                                              parse_verify_payment_response,
+                                              sign_with_address,
+                                              verify_with_address,
+                                              get_request_fee,
+                                              cb,
+    );
+
+    super::results::result_to_empty(err, receiver)
+}
+
+pub fn register_base_payment_method(payment_method_name: &str,
+                                    create_payment_address: Option<CreatePaymentAddressCB>,
+                                    add_request_fees: Option<AddRequestFeesCB>,
+                                    parse_response_with_fees: Option<ParseResponseWithFeesCB>,
+                                    build_get_utxo_request: Option<BuildGetUTXORequestCB>,
+                                    parse_get_utxo_response: Option<ParseGetUTXOResponseCB>,
+                                    build_payment_req: Option<BuildPaymentReqCB>,
+                                    parse_payment_response: Option<ParsePaymentResponseCB>,
+                                    build_mint_req: Option<BuildMintReqCB>,
+                                    build_set_txn_fees_req: Option<BuildSetTxnFeesReqCB>,
+                                    build_get_txn_fees_req: Option<BuildGetTxnFeesReqCB>,
+                                    parse_get_txn_fees_response: Option<ParseGetTxnFeesResponseCB>,
 ) -> Result<(), ErrorCode> {
     let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec();
 
@@ -202,6 +348,17 @@ pub fn list_payment_addresses(wallet_handle: i32) -> Result<String, ErrorCode> {
     super::results::result_to_string(err, receiver)
 }
 
+pub fn list_payment_methods() -> Result<String, ErrorCode> {
+    let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+    let err = indy_list_payment_methods(
+        cmd_handle,
+        cb,
+    );
+
+    super::results::result_to_string(err, receiver)
+}
+
 pub fn add_request_fees(wallet_handle: i32, submitter_did: &str, req_json: &str, inputs_json: &str, outputs_json: &str) -> Result<(String, String), ErrorCode> {
     let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec_string_string();
 
@@ -257,6 +414,43 @@ pub fn build_payment_req(wallet_handle: i32, submitter_did: &str, inputs_json: &
     super::results::result_to_string_string(err, receiver)
 }
 
+pub fn build_payment_req_error_details(wallet_handle: i32, submitter_did: &str, inputs_json: &str, outputs_json: &str) -> (ErrorCode, Option<String>) {
+    let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec_error_details_string_string();
+
+    let inputs_json = CString::new(inputs_json).unwrap();
+    let outputs_json = CString::new(outputs_json).unwrap();
+    let submitter_did = CString::new(submitter_did).unwrap();
+
+    let err = indy_build_payment_req(cmd_handle,
+                                     wallet_handle,
+                                     submitter_did.as_ptr(),
+                                     inputs_json.as_ptr(),
+                                     outputs_json.as_ptr(),
+                                     cb,
+    );
+    assert_eq!(ErrorCode::Success, err);
+
+    receiver.recv().unwrap()
+}
+
+pub fn build_split_payment_req(wallet_handle: i32, submitter_did: &str, inputs_json: &str, outputs_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+    let inputs_json = CString::new(inputs_json).unwrap();
+    let outputs_json = CString::new(outputs_json).unwrap();
+    let submitter_did = CString::new(submitter_did).unwrap();
+
+    let err = indy_build_split_payment_req(cmd_handle,
+                                           wallet_handle,
+                                           submitter_did.as_ptr(),
+                                           inputs_json.as_ptr(),
+                                           outputs_json.as_ptr(),
+                                           cb,
+    );
+
+    super::results::result_to_string(err, receiver)
+}
+
 pub fn parse_response_with_fees(payment_method: &str, resp_json: &str) -> Result<String, ErrorCode> {
     let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
 
@@ -300,6 +494,53 @@ pub fn parse_payment_response(payment_method: &str, resp_json: &str) -> Result<S
     super::results::result_to_string(err, receiver)
 }
 
+pub fn parse_payment_response_with_history(wallet_handle: i32, payment_method: &str, resp_json: &str, history_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+    let payment_method = CString::new(payment_method).unwrap();
+    let resp_json = CString::new(resp_json).unwrap();
+    let history_json = CString::new(history_json).unwrap();
+
+    let err = indy_parse_payment_response_with_history(cmd_handle,
+                                                       wallet_handle,
+                                                       payment_method.as_ptr(),
+                                                       resp_json.as_ptr(),
+                                                       history_json.as_ptr(),
+                                                       cb);
+
+    super::results::result_to_string(err, receiver)
+}
+
+pub fn parse_response_with_fees_with_history(wallet_handle: i32, payment_method: &str, resp_json: &str, history_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+    let payment_method = CString::new(payment_method).unwrap();
+    let resp_json = CString::new(resp_json).unwrap();
+    let history_json = CString::new(history_json).unwrap();
+
+    let err = indy_parse_response_with_fees_with_history(cmd_handle,
+                                                         wallet_handle,
+                                                         payment_method.as_ptr(),
+                                                         resp_json.as_ptr(),
+                                                         history_json.as_ptr(),
+                                                         cb);
+
+    super::results::result_to_string(err, receiver)
+}
+
+pub fn search_payment_history(wallet_handle: i32, query_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+    let query_json = CString::new(query_json).unwrap();
+
+    let err = indy_search_payment_history(cmd_handle,
+                                          wallet_handle,
+                                          query_json.as_ptr(),
+                                          cb);
+
+    super::results::result_to_string(err, receiver)
+}
+
 pub fn build_mint_req(wallet_handle: i32, submitter_did: &str, outputs_json: &str) -> Result<(String, String), ErrorCode> {
     let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec_string_string();
 
@@ -364,3 +605,92 @@ pub fn parse_get_txn_fees_response(payment_method: &str, resp_json: &str) -> Res
 
     super::results::result_to_string(err, receiver)
 }
+
+pub fn build_verify_payment_req(wallet_handle: i32, submitter_did: &str, receipt: &str) -> Result<(String, String), ErrorCode> {
+    let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec_string_string();
+
+    let receipt = CString::new(receipt).unwrap();
+    let submitter_did = CString::new(submitter_did).unwrap();
+
+    let err = indy_build_verify_payment_req(cmd_handle,
+                                            wallet_handle,
+                                            submitter_did.as_ptr(),
+                                            receipt.as_ptr(),
+                                            cb,
+    );
+
+    super::results::result_to_string_string(err, receiver)
+}
+
+pub fn parse_verify_payment_response(payment_method: &str, resp_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+    let payment_method = CString::new(payment_method).unwrap();
+    let resp_json = CString::new(resp_json).unwrap();
+
+    let err = indy_parse_verify_payment_response(cmd_handle,
+                                                 payment_method.as_ptr(),
+                                                 resp_json.as_ptr(),
+                                                 cb,
+    );
+
+    super::results::result_to_string(err, receiver)
+}
+
+pub fn prepare_payment(wallet_handle: i32, pool_handle: i32, submitter_did: &str, payment_method: &str, amount: u64, fees_json: &str,
+                       change_address: &str, config_json: Option<&str>) -> Result<(String, String), ErrorCode> {
+    let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec_string_string();
+
+    let submitter_did = CString::new(submitter_did).unwrap();
+    let payment_method = CString::new(payment_method).unwrap();
+    let fees_json = CString::new(fees_json).unwrap();
+    let change_address = CString::new(change_address).unwrap();
+    let config_json_str = config_json.map(|s| CString::new(s).unwrap()).unwrap_or(CString::new("").unwrap());
+
+    let err = indy_prepare_payment(cmd_handle,
+                                   wallet_handle,
+                                   pool_handle,
+                                   submitter_did.as_ptr(),
+                                   payment_method.as_ptr(),
+                                   amount,
+                                   fees_json.as_ptr(),
+                                   change_address.as_ptr(),
+                                   if config_json.is_some() { config_json_str.as_ptr() } else { null() },
+                                   cb,
+    );
+
+    super::results::result_to_string_string(err, receiver)
+}
+
+pub fn sign_with_address(wallet_handle: i32, address: &str, message: &[u8]) -> Result<Vec<u8>, ErrorCode> {
+    let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec_vec_u8();
+
+    let address = CString::new(address).unwrap();
+
+    let err = indy_sign_with_address(cmd_handle,
+                                     wallet_handle,
+                                     address.as_ptr(),
+                                     message.as_ptr() as *const u8,
+                                     message.len() as u32,
+                                     cb,
+    );
+
+    super::results::result_to_vec_u8(err, receiver)
+}
+
+pub fn verify_with_address(address: &str, message: &[u8], signature: &[u8]) -> Result<bool, ErrorCode> {
+    let (receiver, cmd_handle, cb) = CallbackUtils::_closure_to_cb_ec_bool();
+
+    let address = CString::new(address).unwrap();
+
+    let err = indy_verify_with_address(cmd_handle,
+                                       address.as_ptr(),
+                                       message.as_ptr() as *const u8,
+                                       message.len() as u32,
+                                       signature.as_ptr() as *const u8,
+                                       signature.len() as u32,
+                                       cb,
+    );
+
+    super::results::result_to_bool(err, receiver)
+}
@@ -12,7 +12,6 @@ use self::indy_crypto::utils::json::JsonEncodable;
 use std::fs;
 use std::ffi::CString;
 use std::io::Write;
-#[cfg(feature = "local_nodes_pool")]
 use std::ptr::null;
 use std::path::{Path, PathBuf};
 use utils::types::{Response, ResponseType};
@@ -133,7 +132,6 @@ impl PoolUtils {
         super::results::result_to_empty(err, receiver)
     }
 
-    #[cfg(feature = "local_nodes_pool")]
     pub fn open_pool_ledger(pool_name: &str, config: Option<&str>) -> Result<i32, ErrorCode> {
         let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_i32();
 
@@ -155,6 +153,13 @@ impl PoolUtils {
         PoolUtils::open_pool_ledger(pool_name, None)
     }
 
+    pub fn create_and_open_pool_ledger_for_genesis_txns(pool_name: &str, genesis_txns: &[String]) -> Result<i32, ErrorCode> {
+        let txn_file_path = PoolUtils::create_genesis_txn_file(pool_name, genesis_txns.join("\n").as_str(), None);
+        let pool_config = PoolUtils::pool_config_json(txn_file_path.as_path());
+        PoolUtils::create_pool_ledger_config(pool_name, Some(pool_config.as_str()))?;
+        PoolUtils::open_pool_ledger(pool_name, None)
+    }
+
     pub fn refresh(pool_handle: i32) -> Result<(), ErrorCode> {
         let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec();
 
@@ -114,4 +114,33 @@ impl WalletUtils {
 
         super::results::result_to_empty(err, receiver)
     }
-}
\ No newline at end of file
+
+    pub fn get_changes_since(wallet_handle: i32, seq: i64) -> Result<String, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+        let err = indy_wallet_get_changes_since(command_handle, wallet_handle, seq, cb);
+
+        super::results::result_to_string(err, receiver)
+    }
+
+    pub fn purge_expired(wallet_handle: i32) -> Result<i32, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_i32();
+
+        let err = indy_wallet_purge_expired(command_handle, wallet_handle, cb);
+
+        super::results::result_to_int(err, receiver)
+    }
+
+    pub fn verify_wallet(wallet_handle: i32, options_json: Option<&str>) -> Result<String, ErrorCode> {
+        let (receiver, command_handle, cb) = CallbackUtils::_closure_to_cb_ec_string();
+
+        let options_json_str = options_json.map(|s| CString::new(s).unwrap());
+
+        let err = indy_verify_wallet(command_handle,
+                                     wallet_handle,
+                                     options_json_str.as_ref().map(|s| s.as_ptr()).unwrap_or(null()),
+                                     cb);
+
+        super::results::result_to_string(err, receiver)
+    }
+}
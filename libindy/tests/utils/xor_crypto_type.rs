@@ -0,0 +1,105 @@
+extern crate libc;
+
+use indy::api::ErrorCode;
+
+use std::ptr;
+use std::slice;
+
+pub const XOR_CRYPTO_TYPE: &'static str = "xor";
+const KEY_LEN: usize = 32;
+
+/// Toy crypto type that is used to test registration of custom crypto types.
+/// Verkey and signkey are the same bytes, signature and encryption are just xor with the key.
+pub struct XorCryptoType {}
+
+impl XorCryptoType {
+    pub extern fn create_key(seed: *const u8,
+                             seed_len: u32,
+                             verkey_p: *mut *const u8,
+                             verkey_len_p: *mut u32,
+                             signkey_p: *mut *const u8,
+                             signkey_len_p: *mut u32) -> ErrorCode {
+        let key = if seed.is_null() {
+            (0..KEY_LEN).map(|i| i as u8).collect::<Vec<u8>>()
+        } else {
+            let seed = unsafe { slice::from_raw_parts(seed, seed_len as usize) };
+            if seed.len() != KEY_LEN {
+                return ErrorCode::CommonInvalidStructure;
+            }
+            seed.to_vec()
+        };
+
+        XorCryptoType::_return_buffer(key.clone(), verkey_p, verkey_len_p);
+        XorCryptoType::_return_buffer(key, signkey_p, signkey_len_p);
+
+        ErrorCode::Success
+    }
+
+    pub extern fn sign(signkey: *const u8,
+                       signkey_len: u32,
+                       message: *const u8,
+                       message_len: u32,
+                       signature_p: *mut *const u8,
+                       signature_len_p: *mut u32) -> ErrorCode {
+        let signature = XorCryptoType::_xor(signkey, signkey_len, message, message_len);
+        XorCryptoType::_return_buffer(signature, signature_p, signature_len_p);
+        ErrorCode::Success
+    }
+
+    pub extern fn verify(verkey: *const u8,
+                         verkey_len: u32,
+                         message: *const u8,
+                         message_len: u32,
+                         signature: *const u8,
+                         signature_len: u32,
+                         valid_p: *mut bool) -> ErrorCode {
+        let expected = XorCryptoType::_xor(verkey, verkey_len, message, message_len);
+        let signature = unsafe { slice::from_raw_parts(signature, signature_len as usize) };
+        unsafe { *valid_p = expected.as_slice() == signature; }
+        ErrorCode::Success
+    }
+
+    pub extern fn encrypt_sealed(verkey: *const u8,
+                                 verkey_len: u32,
+                                 message: *const u8,
+                                 message_len: u32,
+                                 encrypted_p: *mut *const u8,
+                                 encrypted_len_p: *mut u32) -> ErrorCode {
+        let encrypted = XorCryptoType::_xor(verkey, verkey_len, message, message_len);
+        XorCryptoType::_return_buffer(encrypted, encrypted_p, encrypted_len_p);
+        ErrorCode::Success
+    }
+
+    pub extern fn decrypt_sealed(_verkey: *const u8,
+                                 _verkey_len: u32,
+                                 signkey: *const u8,
+                                 signkey_len: u32,
+                                 encrypted: *const u8,
+                                 encrypted_len: u32,
+                                 decrypted_p: *mut *const u8,
+                                 decrypted_len_p: *mut u32) -> ErrorCode {
+        let decrypted = XorCryptoType::_xor(signkey, signkey_len, encrypted, encrypted_len);
+        XorCryptoType::_return_buffer(decrypted, decrypted_p, decrypted_len_p);
+        ErrorCode::Success
+    }
+
+    pub extern fn free(data: *const u8, data_len: u32) -> ErrorCode {
+        unsafe { Box::from_raw(slice::from_raw_parts_mut(data as *mut u8, data_len as usize) as *mut [u8]) };
+        ErrorCode::Success
+    }
+
+    fn _xor(key: *const u8, key_len: u32, data: *const u8, data_len: u32) -> Vec<u8> {
+        let key = unsafe { slice::from_raw_parts(key, key_len as usize) };
+        let data = unsafe { slice::from_raw_parts(data, data_len as usize) };
+
+        data.iter().zip(key.iter().cycle()).map(|(d, k)| d ^ k).collect()
+    }
+
+    fn _return_buffer(data: Vec<u8>, data_p: *mut *const u8, data_len_p: *mut u32) {
+        let data = data.into_boxed_slice();
+        unsafe {
+            *data_len_p = data.len() as u32;
+            *data_p = if data.is_empty() { ptr::null() } else { Box::into_raw(data) as *const u8 };
+        }
+    }
+}
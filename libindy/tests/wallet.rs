@@ -1,4 +1,6 @@
 extern crate indy;
+extern crate openssl;
+extern crate hex;
 
 // Workaround to share some utils code based on indy sdk types between tests and indy sdk
 use indy::api as api;
@@ -18,12 +20,18 @@ mod utils;
 
 use utils::inmem_wallet::InmemWallet;
 use utils::wallet::WalletUtils;
+use utils::non_secrets::{NonSecretsUtils, TYPE as RECORD_TYPE, ID, ID_2, VALUE, VALUE_2};
 use utils::test::TestUtils;
 use utils::constants::*;
 
 use indy::api::ErrorCode;
 
+use self::openssl::hash::{MessageDigest, Hasher};
+use self::hex::ToHex;
+
 pub const CONFIG: &'static str = r#"{"freshness_time":1000}"#;
+pub const CHANGE_LOG_CONFIG: &'static str = r#"{"change_log":true}"#;
+pub const READ_ONLY_CONFIG: &'static str = r#"{"read_only":true,"concurrent_access":true}"#;
 
 mod high_cases {
     use super::*;
@@ -195,6 +203,21 @@ mod high_cases {
 
             TestUtils::cleanup_storage();
         }
+
+        #[test]
+        fn indy_open_wallet_works_for_read_only_twice() {
+            TestUtils::cleanup_storage();
+
+            let wallet_name = "indy_open_wallet_works_for_read_only_twice";
+            WalletUtils::create_wallet(POOL, wallet_name, None, None, None).unwrap();
+            let wallet_handle_1 = WalletUtils::open_wallet(wallet_name, Some(READ_ONLY_CONFIG), None).unwrap();
+            let wallet_handle_2 = WalletUtils::open_wallet(wallet_name, Some(READ_ONLY_CONFIG), None).unwrap();
+
+            WalletUtils::close_wallet(wallet_handle_1).unwrap();
+            WalletUtils::close_wallet(wallet_handle_2).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
     }
 
     mod close_wallet {
@@ -230,6 +253,229 @@ mod high_cases {
 //            InmemWallet::cleanup();
 //        }
     }
+
+    mod get_changes_since {
+        use super::*;
+
+        #[test]
+        fn indy_wallet_get_changes_since_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_name = "indy_wallet_get_changes_since_works";
+            WalletUtils::create_wallet(POOL, wallet_name, None, None, None).unwrap();
+            let wallet_handle = WalletUtils::open_wallet(wallet_name, Some(CHANGE_LOG_CONFIG), None).unwrap();
+
+            NonSecretsUtils::add_wallet_record(wallet_handle, RECORD_TYPE, ID, VALUE, None).unwrap();
+            NonSecretsUtils::update_wallet_record_value(wallet_handle, RECORD_TYPE, ID, VALUE_2).unwrap();
+            NonSecretsUtils::delete_wallet_record(wallet_handle, RECORD_TYPE, ID).unwrap();
+
+            let changes_json = WalletUtils::get_changes_since(wallet_handle, 0).unwrap();
+            let changes: Vec<serde_json::Value> = serde_json::from_str(&changes_json).unwrap();
+
+            assert_eq!(4, changes.len());
+            assert_eq!("log_enabled", changes[0]["operation"].as_str().unwrap());
+            assert_eq!("add", changes[1]["operation"].as_str().unwrap());
+            assert_eq!("update", changes[2]["operation"].as_str().unwrap());
+            assert_eq!("delete", changes[3]["operation"].as_str().unwrap());
+            assert!(changes[1..].iter().all(|change| change["type"].as_str().unwrap() == RECORD_TYPE));
+            assert!(changes[1..].iter().all(|change| change["idHash"] == changes[1]["idHash"]));
+
+            let seq = changes[2]["seq"].as_i64().unwrap();
+            let changes_json = WalletUtils::get_changes_since(wallet_handle, seq).unwrap();
+            let changes: Vec<serde_json::Value> = serde_json::from_str(&changes_json).unwrap();
+            assert_eq!(1, changes.len());
+            assert_eq!("delete", changes[0]["operation"].as_str().unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_wallet_get_changes_since_works_for_reopen() {
+            TestUtils::cleanup_storage();
+
+            let wallet_name = "indy_wallet_get_changes_since_works_for_reopen";
+            WalletUtils::create_wallet(POOL, wallet_name, None, None, None).unwrap();
+            let wallet_handle = WalletUtils::open_wallet(wallet_name, Some(CHANGE_LOG_CONFIG), None).unwrap();
+            NonSecretsUtils::add_wallet_record(wallet_handle, RECORD_TYPE, ID, VALUE, None).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            let wallet_handle = WalletUtils::open_wallet(wallet_name, Some(CHANGE_LOG_CONFIG), None).unwrap();
+
+            let changes_json = WalletUtils::get_changes_since(wallet_handle, 0).unwrap();
+            let changes: Vec<serde_json::Value> = serde_json::from_str(&changes_json).unwrap();
+            assert_eq!(2, changes.len());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_wallet_get_changes_since_works_for_gap_in_change_log() {
+            TestUtils::cleanup_storage();
+
+            let wallet_name = "indy_wallet_get_changes_since_works_for_gap_in_change_log";
+            WalletUtils::create_wallet(POOL, wallet_name, None, None, None).unwrap();
+            let wallet_handle = WalletUtils::open_wallet(wallet_name, Some(CHANGE_LOG_CONFIG), None).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            let wallet_handle = WalletUtils::open_wallet(wallet_name, None, None).unwrap();
+            NonSecretsUtils::add_wallet_record(wallet_handle, RECORD_TYPE, ID, VALUE, None).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            let wallet_handle = WalletUtils::open_wallet(wallet_name, Some(CHANGE_LOG_CONFIG), None).unwrap();
+
+            let changes_json = WalletUtils::get_changes_since(wallet_handle, 0).unwrap();
+            let changes: Vec<serde_json::Value> = serde_json::from_str(&changes_json).unwrap();
+            let operations: Vec<&str> = changes.iter().map(|change| change["operation"].as_str().unwrap()).collect();
+            assert_eq!(vec!["log_enabled", "log_disabled", "log_enabled"], operations);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_wallet_get_changes_since_works_for_expired_record() {
+            TestUtils::cleanup_storage();
+
+            let wallet_name = "indy_wallet_get_changes_since_works_for_expired_record";
+            WalletUtils::create_wallet(POOL, wallet_name, None, None, None).unwrap();
+            let wallet_handle = WalletUtils::open_wallet(wallet_name, Some(CHANGE_LOG_CONFIG), None).unwrap();
+            NonSecretsUtils::add_wallet_record(wallet_handle, RECORD_TYPE, ID, VALUE, None).unwrap();
+            NonSecretsUtils::update_wallet_record_expiry(wallet_handle, RECORD_TYPE, ID, 1).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            // expired record is purged on opening
+            let wallet_handle = WalletUtils::open_wallet(wallet_name, Some(CHANGE_LOG_CONFIG), None).unwrap();
+
+            let changes_json = WalletUtils::get_changes_since(wallet_handle, 0).unwrap();
+            let changes: Vec<serde_json::Value> = serde_json::from_str(&changes_json).unwrap();
+            let operations: Vec<&str> = changes.iter().map(|change| change["operation"].as_str().unwrap()).collect();
+            assert_eq!(vec!["log_enabled", "add", "update_expiry", "expire"], operations);
+
+            let mut hasher = Hasher::new(MessageDigest::sha256()).unwrap();
+            hasher.update(ID.as_bytes()).unwrap();
+            let id_hash = hasher.finish2().unwrap().as_ref().to_hex();
+            assert!(changes[1..].iter().all(|change| change["idHash"].as_str().unwrap() == id_hash));
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod purge_expired {
+        use super::*;
+
+        #[test]
+        fn indy_wallet_purge_expired_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            NonSecretsUtils::add_wallet_record(wallet_handle, RECORD_TYPE, ID, VALUE, None).unwrap();
+            NonSecretsUtils::add_wallet_record(wallet_handle, RECORD_TYPE, ID_2, VALUE_2, None).unwrap();
+            NonSecretsUtils::update_wallet_record_expiry(wallet_handle, RECORD_TYPE, ID, 1).unwrap();
+
+            assert_eq!(1, WalletUtils::purge_expired(wallet_handle).unwrap());
+            assert_eq!(0, WalletUtils::purge_expired(wallet_handle).unwrap());
+
+            NonSecretsUtils::get_wallet_record(wallet_handle, RECORD_TYPE, ID_2, "{}").unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_wallet_purge_expired_works_for_invalid_handle() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = WalletUtils::purge_expired(wallet_handle + 1);
+            assert_eq!(ErrorCode::WalletInvalidHandle, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod verify_wallet {
+        use super::*;
+
+        #[test]
+        fn indy_verify_wallet_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            NonSecretsUtils::add_wallet_record(wallet_handle, RECORD_TYPE, ID, VALUE, None).unwrap();
+            NonSecretsUtils::add_wallet_record(wallet_handle, RECORD_TYPE, ID_2, VALUE_2, None).unwrap();
+
+            let report = WalletUtils::verify_wallet(wallet_handle, None).unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(2, report["checkedCount"].as_u64().unwrap());
+            assert!(report["corrupted"].as_array().unwrap().is_empty());
+            assert_eq!(0, report["quarantinedCount"].as_u64().unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_verify_wallet_works_for_quarantine() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            NonSecretsUtils::add_wallet_record(wallet_handle, RECORD_TYPE, ID, VALUE, None).unwrap();
+
+            let report = WalletUtils::verify_wallet(wallet_handle, Some(r#"{"quarantine":true}"#)).unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(1, report["checkedCount"].as_u64().unwrap());
+            assert_eq!(0, report["quarantinedCount"].as_u64().unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_verify_wallet_works_for_invalid_options() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = WalletUtils::verify_wallet(wallet_handle, Some(r#"{"quarantine":"yes"}"#));
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_verify_wallet_works_for_invalid_handle() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = WalletUtils::verify_wallet(wallet_handle + 1, None);
+            assert_eq!(ErrorCode::WalletInvalidHandle, res.unwrap_err());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
 }
 
 mod medium_cases {
@@ -432,6 +678,36 @@ mod medium_cases {
             TestUtils::cleanup_storage();
         }
 
+        #[test]
+        fn indy_open_wallet_works_for_read_only_after_read_write() {
+            TestUtils::cleanup_storage();
+
+            WalletUtils::create_wallet(POOL, WALLET, None, None, None).unwrap();
+
+            let wallet_handle = WalletUtils::open_wallet(WALLET, None, None).unwrap();
+            let res = WalletUtils::open_wallet(WALLET, Some(READ_ONLY_CONFIG), None);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletAlreadyOpenedError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_open_wallet_works_for_write_to_read_only() {
+            TestUtils::cleanup_storage();
+
+            WalletUtils::create_wallet(POOL, WALLET, None, None, None).unwrap();
+
+            let wallet_handle = WalletUtils::open_wallet(WALLET, Some(READ_ONLY_CONFIG), None).unwrap();
+            let res = NonSecretsUtils::add_wallet_record(wallet_handle, RECORD_TYPE, ID, VALUE, None);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletAccessFailed);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
         #[test]
         fn indy_open_wallet_works_for_two_wallets() {
             TestUtils::cleanup_storage();
@@ -521,4 +797,36 @@ mod medium_cases {
             TestUtils::cleanup_storage();
         }
     }
+
+    mod get_changes_since {
+        use super::*;
+
+        #[test]
+        fn indy_wallet_get_changes_since_works_for_disabled_change_log() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = WalletUtils::get_changes_since(wallet_handle, 0);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletStorageError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_wallet_get_changes_since_works_for_invalid_handle() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = WalletUtils::get_changes_since(wallet_handle + 1, 0);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletInvalidHandle);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
 }
@@ -1,15 +1,38 @@
 #ifndef __nullpay__plugin__included__
 #define __nullpay__plugin__included__
 
+#include <indy_mod.h>
+
 #ifdef __cplusplus
 extern "C" {
 #endif
 
-    /// TODO: FIXME: Probide list of interfaces
+    /// Registers "null" payment method in libindy.
+    /// Ledger is chosen by NULLPAY_LEDGER environment variable ("default" if not set),
+    /// and its state is kept in SQLite file set by NULLPAY_STORAGE_PATH or in memory otherwise.
+
+    extern indy_error_t nullpay_init();
+
+    /// Registers "null" payment method in libindy with explicit configuration.
+    ///
+    /// config_json: {
+    ///     "ledger": optional<string> - name of the ledger to work with, "default" by default.
+    ///                                  Ledgers with different names are fully isolated.
+    ///     "storage_path": optional<string> - path to SQLite file to keep ledger state in.
+    ///                                        State is kept in process memory if not set.
+    ///                                        Processes that use the same file and ledger name share payment state.
+    /// }
+
+    extern indy_error_t nullpay_init_with_config(const char * config_json);
+
+    /// Removes all transactions, UTXOs and fees of the ledger.
+    ///
+    /// ledger_name: optional name of the ledger to reset, the configured one is used if null.
+
+    extern indy_error_t nullpay_reset(const char * ledger_name);
 
 #ifdef __cplusplus
 }
 #endif
 
 #endif
-
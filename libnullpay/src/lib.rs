@@ -1,5 +1,4 @@
 extern crate libc;
-extern crate rand;
 
 #[macro_use]
 extern crate lazy_static;
@@ -9,6 +8,7 @@ extern crate log;
 extern crate serde_derive;
 extern crate serde_json;
 extern crate serde;
+extern crate rusqlite;
 
 mod libindy;
 #[macro_use]
@@ -17,14 +17,100 @@ mod utils;
 mod payment_method;
 mod services;
 
+use services::config_ledger;
+use services::ledger_storage::{self, NullpayConfig};
+use utils::cstring::CStringUtils;
+
+use libc::c_char;
+use serde_json::from_str;
 use std::ffi::CString;
+use std::sync::Mutex;
+
+lazy_static! {
+    static ref REGISTERED: Mutex<bool> = Default::default();
+}
 
+/// Registers "null" payment method in libindy.
+/// Ledger is chosen by NULLPAY_LEDGER environment variable ("default" if not set),
+/// and its state is kept in SQLite file set by NULLPAY_STORAGE_PATH or in memory otherwise.
 #[no_mangle]
 pub extern fn nullpay_init() -> ErrorCode {
+    _init(NullpayConfig::from_env())
+}
+
+/// Registers "null" payment method in libindy with explicit configuration.
+/// Can be called again to switch the configuration, the method is registered only once.
+///
+/// config_json: {
+///     "ledger": optional<string> - name of the ledger to work with, "default" by default.
+///                                  Ledgers with different names are fully isolated.
+///     "storage_path": optional<string> - path to SQLite file to keep ledger state in.
+///                                        State is kept in process memory if not set.
+///                                        Processes that use the same file and ledger name share payment state.
+/// }
+#[no_mangle]
+pub extern fn nullpay_init_with_config(config_json: *const c_char) -> ErrorCode {
+    check_useful_c_str!(config_json, ErrorCode::CommonInvalidParam1);
+
+    let config = match from_str::<NullpayConfig>(config_json.as_str()) {
+        Ok(config) => config,
+        Err(_) => return ErrorCode::CommonInvalidStructure
+    };
+
+    _init(config)
+}
+
+/// Removes all transactions, UTXOs and fees of the ledger.
+///
+/// ledger_name: optional name of the ledger to reset, the configured one is used if null.
+#[no_mangle]
+pub extern fn nullpay_reset(ledger_name: *const c_char) -> ErrorCode {
+    let ledger_name = match CStringUtils::c_str_to_string(ledger_name) {
+        Ok(ledger_name) => ledger_name,
+        Err(_) => return ErrorCode::CommonInvalidParam1
+    };
+
+    match ledger_storage::reset(ledger_name.as_ref().map(String::as_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(ec) => ec
+    }
+}
+
+/// Assigns ledger role to the DID. Fees of requests the DID submits
+/// are charged according to the role rules of the fee schedule.
+///
+/// did: DID of requests submitter.
+/// role: optional role name (e.g. "TRUSTEE", "STEWARD"), the role of DID is removed if null.
+#[no_mangle]
+pub extern fn nullpay_set_did_role(did: *const c_char, role: *const c_char) -> ErrorCode {
+    check_useful_c_str!(did, ErrorCode::CommonInvalidParam1);
+    let role = match CStringUtils::c_str_to_string(role) {
+        Ok(role) => role,
+        Err(_) => return ErrorCode::CommonInvalidParam2
+    };
+
+    match ledger_storage::with_state(|state| config_ledger::set_role(state, did, role)) {
+        Ok(()) => ErrorCode::Success,
+        Err(ec) => ec
+    }
+}
+
+fn _init(config: NullpayConfig) -> ErrorCode {
     utils::logger::init();
+
+    if let Err(ec) = ledger_storage::configure(config) {
+        return ec;
+    }
+
+    // repeated init only switches configuration, payment method stays registered in libindy
+    let mut registered = REGISTERED.lock().unwrap_or_else(|err| err.into_inner());
+    if *registered {
+        return ErrorCode::Success;
+    }
+
     let payment_method_name = CString::new(payment_method::PAYMENT_METHOD_NAME).unwrap();
 
-    libindy::payments::register_payment_method(
+    let ec = libindy::payments::register_payment_method(
         payment_method_name.as_ptr(),
         payment_method::create_payment_address::handle,
         payment_method::add_request_fees::handle,
@@ -36,8 +122,16 @@ pub extern fn nullpay_init() -> ErrorCode {
         payment_method::build_mint_req::handle,
         payment_method::build_set_txn_fees_req::handle,
         payment_method::build_get_txn_fees_req::handle,
-        payment_method::parse_get_txn_fees_response::handle
-    )
+        payment_method::parse_get_txn_fees_response::handle,
+        payment_method::build_verify_payment_req::handle,
+        payment_method::parse_verify_payment_response::handle,
+        payment_method::sign_with_address::handle,
+        payment_method::verify_with_address::handle,
+        payment_method::get_request_fee::handle
+    );
+
+    *registered = ec == ErrorCode::Success;
+    ec
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -100,6 +194,21 @@ pub enum ErrorCode
     // Caller passed invalid value as param 14 (null, invalid json and etc..)
     CommonInvalidParam14 = 116,
 
+    // Caller passed invalid value as param 15 (null, invalid json and etc..)
+    CommonInvalidParam15 = 117,
+
+    // Caller passed invalid value as param 16 (null, invalid json and etc..)
+    CommonInvalidParam16 = 118,
+
+    // Caller passed invalid value as param 17 (null, invalid json and etc..)
+    CommonInvalidParam17 = 119,
+
+    // Caller passed invalid value as param 18 (null, invalid json and etc..)
+    CommonInvalidParam18 = 120,
+
+    // Caller passed invalid value as param 19 (null, invalid json and etc..)
+    CommonInvalidParam19 = 121,
+
     // Wallet errors
     // Caller passed invalid wallet handle
     WalletInvalidHandle = 200,
@@ -146,6 +255,9 @@ pub enum ErrorCode
     // Returned if provided wallet query is invalid
     WalletQueryError = 214,
 
+    // Returned if wallet record was changed since the expected version
+    WalletConflict = 215,
+
     // Ledger errors
     // Trying to open pool ledger that wasn't created before
     PoolLedgerNotCreatedError = 300,
@@ -201,4 +313,13 @@ pub enum ErrorCode
 
     // Insufficient funds on inputs
     PaymentInsufficientFundsError = 702,
+
+    // Unknown payment source (receipt or input) was given
+    PaymentSourceDoesNotExistError = 703,
+
+    // Payment method doesn't support requested operation
+    PaymentOperationNotSupportedError = 704,
+
+    // Inputs and outputs of request belong to several payment methods
+    PaymentMixedMethodsError = 705,
 }
\ No newline at end of file
@@ -0,0 +1,95 @@
+use ErrorCode;
+
+use libc::c_char;
+use std::ffi::CString;
+use utils::callbacks;
+
+pub fn create_key(
+    wallet_handle: i32,
+    key_json: &str,
+    cb: Box<FnMut(ErrorCode, String) + Send>,
+) -> ErrorCode {
+    let (command_handle, cb) = callbacks::closure_to_cb_ec_string(cb);
+    let key_json = CString::new(key_json).unwrap();
+
+    unsafe {
+        indy_create_key(
+            command_handle,
+            wallet_handle,
+            key_json.as_ptr(),
+            cb,
+        )
+    }
+}
+
+pub fn sign(
+    wallet_handle: i32,
+    signer_vk: &str,
+    message: &[u8],
+    cb: Box<FnMut(ErrorCode, Vec<u8>) + Send>,
+) -> ErrorCode {
+    let (command_handle, cb) = callbacks::closure_to_cb_ec_vec_u8(cb);
+    let signer_vk = CString::new(signer_vk).unwrap();
+
+    unsafe {
+        indy_crypto_sign(
+            command_handle,
+            wallet_handle,
+            signer_vk.as_ptr(),
+            message.as_ptr(),
+            message.len() as u32,
+            cb,
+        )
+    }
+}
+
+pub fn verify(
+    signer_vk: &str,
+    message: &[u8],
+    signature: &[u8],
+    cb: Box<FnMut(ErrorCode, bool) + Send>,
+) -> ErrorCode {
+    let (command_handle, cb) = callbacks::closure_to_cb_ec_bool(cb);
+    let signer_vk = CString::new(signer_vk).unwrap();
+
+    unsafe {
+        indy_crypto_verify(
+            command_handle,
+            signer_vk.as_ptr(),
+            message.as_ptr(),
+            message.len() as u32,
+            signature.as_ptr(),
+            signature.len() as u32,
+            cb,
+        )
+    }
+}
+
+extern {
+    #[no_mangle]
+    pub fn indy_create_key(command_handle: i32,
+                           wallet_handle: i32,
+                           key_json: *const c_char,
+                           cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                verkey: *const c_char)>) -> ErrorCode;
+
+    #[no_mangle]
+    pub fn indy_crypto_sign(command_handle: i32,
+                            wallet_handle: i32,
+                            signer_vk: *const c_char,
+                            message_raw: *const u8,
+                            message_len: u32,
+                            cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                 signature_raw: *const u8,
+                                                 signature_len: u32)>) -> ErrorCode;
+
+    #[no_mangle]
+    pub fn indy_crypto_verify(command_handle: i32,
+                              signer_vk: *const c_char,
+                              message_raw: *const u8,
+                              message_len: u32,
+                              signature_raw: *const u8,
+                              signature_len: u32,
+                              cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                   valid: bool)>) -> ErrorCode;
+}
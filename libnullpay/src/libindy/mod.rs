@@ -1,2 +1,3 @@
+pub mod crypto;
 pub mod ledger;
-pub mod payments;
\ No newline at end of file
+pub mod payments;
@@ -66,6 +66,51 @@ pub type ParseGetTxnFeesResponseCB = extern fn(command_handle: i32,
                                                resp_json: *const c_char,
                                                cb: Option<IndyPaymentCallback>) -> ErrorCode;
 
+pub type BuildVerifyPaymentReqCB = extern fn(command_handle: i32,
+                                             wallet_handle: i32,
+                                             submitter_did: *const c_char,
+                                             receipt: *const c_char,
+                                             cb: Option<IndyPaymentCallback>) -> ErrorCode;
+
+pub type ParseVerifyPaymentResponseCB = extern fn(command_handle: i32,
+                                                  resp_json: *const c_char,
+                                                  cb: Option<IndyPaymentCallback>) -> ErrorCode;
+
+pub type IndySignWithAddressCallback = extern fn(command_handle_: i32,
+                                                err: ErrorCode,
+                                                signature_raw: *const u8,
+                                                signature_len: u32) -> ErrorCode;
+
+pub type SignWithAddressCB = extern fn(command_handle: i32,
+                                       wallet_handle: i32,
+                                       address: *const c_char,
+                                       message_raw: *const u8,
+                                       message_len: u32,
+                                       cb: Option<IndySignWithAddressCallback>) -> ErrorCode;
+
+pub type IndyVerifyWithAddressCallback = extern fn(command_handle_: i32,
+                                                  err: ErrorCode,
+                                                  valid: bool) -> ErrorCode;
+
+pub type VerifyWithAddressCB = extern fn(command_handle: i32,
+                                         address: *const c_char,
+                                         message_raw: *const u8,
+                                         message_len: u32,
+                                         signature_raw: *const u8,
+                                         signature_len: u32,
+                                         cb: Option<IndyVerifyWithAddressCallback>) -> ErrorCode;
+
+pub type IndyGetRequestFeeCallback = extern fn(command_handle_: i32,
+                                               err: ErrorCode,
+                                               fee: u64) -> ErrorCode;
+
+pub type GetRequestFeeCB = extern fn(command_handle: i32,
+                                     wallet_handle: i32,
+                                     submitter_did: *const c_char,
+                                     req_json: *const c_char,
+                                     fees_json: *const c_char,
+                                     cb: Option<IndyGetRequestFeeCallback>) -> ErrorCode;
+
 pub fn register_payment_method(
     payment_method: *const c_char,
     create_payment_address: CreatePaymentAddressCB,
@@ -79,6 +124,11 @@ pub fn register_payment_method(
     build_set_txn_fees_req: BuildSetTxnFeesReqCB,
     build_get_txn_fees_req: BuildGetTxnFeesReqCB,
     parse_get_txn_fees_response: ParseGetTxnFeesResponseCB,
+    build_verify_payment_req: BuildVerifyPaymentReqCB,
+    parse_verify_payment_response: ParseVerifyPaymentResponseCB,
+    sign_with_address: SignWithAddressCB,
+    verify_with_address: VerifyWithAddressCB,
+    get_request_fee: GetRequestFeeCB,
 ) -> ErrorCode {
     let (sender, receiver) = channel();
 
@@ -89,7 +139,7 @@ pub fn register_payment_method(
     let (cmd_handle, cb) = callbacks::closure_to_cb_ec(closure);
 
     unsafe {
-        indy_register_payment_method(
+        indy_register_payment_method_ex(
             cmd_handle,
             payment_method,
             Some(create_payment_address),
@@ -103,6 +153,11 @@ pub fn register_payment_method(
             Some(build_set_txn_fees_req),
             Some(build_get_txn_fees_req),
             Some(parse_get_txn_fees_response),
+            Some(build_verify_payment_req),
+            Some(parse_verify_payment_response),
+            Some(sign_with_address),
+            Some(verify_with_address),
+            Some(get_request_fee),
             cb,
         );
     }
@@ -112,7 +167,7 @@ pub fn register_payment_method(
 
 extern {
     #[no_mangle]
-    pub fn indy_register_payment_method(
+    pub fn indy_register_payment_method_ex(
         command_handle: i32,
         payment_method: *const c_char,
         create_payment_address: Option<CreatePaymentAddressCB>,
@@ -126,6 +181,11 @@ extern {
         build_set_txn_fees_req: Option<BuildSetTxnFeesReqCB>,
         build_get_txn_fees_req: Option<BuildGetTxnFeesReqCB>,
         parse_get_txn_fees_response: Option<ParseGetTxnFeesResponseCB>,
+        build_verify_payment_req: Option<BuildVerifyPaymentReqCB>,
+        parse_verify_payment_response: Option<ParseVerifyPaymentResponseCB>,
+        sign_with_address: Option<SignWithAddressCB>,
+        verify_with_address: Option<VerifyWithAddressCB>,
+        get_request_fee: Option<GetRequestFeeCB>,
         cb: Option<extern fn(command_handle_: i32, err: ErrorCode)>) -> ErrorCode;
 }
 
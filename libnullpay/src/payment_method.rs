@@ -1,36 +1,57 @@
 use ErrorCode;
+use libindy::crypto;
 use libindy::ledger;
-use libindy::payments::IndyPaymentCallback;
+use libindy::payments::{IndyPaymentCallback, IndySignWithAddressCallback, IndyVerifyWithAddressCallback, IndyGetRequestFeeCallback};
 use services::*;
+use services::ledger_storage::LedgerState;
 use services::response_storage::*;
 use utils::types::*;
-use utils::rand;
 use utils::json_helper::{parse_operation_from_request, serialize_infos};
 use utils::cstring::CStringUtils;
+use utils::utxo::{from_utxo, to_utxo, verkey_from_address};
 
 use serde_json::{from_str, to_string};
 use std::collections::HashMap;
 use std::ffi::CString;
 use std::os::raw::c_char;
+use std::slice;
 
 pub static PAYMENT_METHOD_NAME: &str = "null";
 
 pub mod create_payment_address {
     use super::*;
 
-    pub extern fn handle(cmd_handle: i32, _wallet_handle: i32, _config: *const c_char, cb: Option<IndyPaymentCallback>) -> ErrorCode {
-        trace!("libnullpay::create_payment_address::handle << ");
-        let res = format!("pay:null:{}", rand::get_rand_string(15));
-        let err = ErrorCode::Success;
-        trace!("libnullpay::create_payment_address::handle >> ");
-        _process_callback(cmd_handle, err, res, cb)
+    pub extern fn handle(cmd_handle: i32, wallet_handle: i32, config: *const c_char, cb: Option<IndyPaymentCallback>) -> ErrorCode {
+        check_useful_c_str!(config, ErrorCode::CommonInvalidState);
+        trace!("libnullpay::create_payment_address::handle << config: {}", config);
+
+        parse_json!(config, PaymentAddressConfig, ErrorCode::CommonInvalidStructure);
+
+        //address keys are always ed25519 ones so their verkeys can be used as address parts
+        let key_json = match to_string(&PaymentAddressConfig { seed: config.seed }) {
+            Ok(key_json) => key_json,
+            Err(_) => return ErrorCode::CommonInvalidState
+        };
+
+        crypto::create_key(
+            wallet_handle,
+            key_json.as_str(),
+            Box::new(move |ec, verkey| {
+                let res = if ec == ErrorCode::Success {
+                    format!("pay:{}:{}", PAYMENT_METHOD_NAME, verkey)
+                } else { String::new() };
+
+                trace!("libnullpay::create_payment_address::handle >> ");
+                _process_callback(cmd_handle, ec, res, cb);
+            }),
+        )
     }
 }
 
 pub mod add_request_fees {
     use super::*;
 
-    pub extern fn handle(cmd_handle: i32, _wallet_handle: i32, submitter_did: *const c_char, req_json: *const c_char, inputs_json: *const c_char, outputs_json: *const c_char, cb: Option<IndyPaymentCallback>) -> ErrorCode {
+    pub extern fn handle(cmd_handle: i32, wallet_handle: i32, submitter_did: *const c_char, req_json: *const c_char, inputs_json: *const c_char, outputs_json: *const c_char, cb: Option<IndyPaymentCallback>) -> ErrorCode {
         check_useful_c_str!(req_json, ErrorCode::CommonInvalidState);
         check_useful_c_str!(inputs_json, ErrorCode::CommonInvalidState);
         check_useful_c_str!(outputs_json, ErrorCode::CommonInvalidState);
@@ -53,49 +74,77 @@ pub mod add_request_fees {
 
         trace!("TXN: {}", txn_type);
 
-        let fee = match config_ledger::get_fee(txn_type) {
-            Some(fee) => fee,
-            None => {
+        //fee rules take into account the role of submitter and the size of request without fees
+        let request_size = req_json.len() as u64;
+        let fee = match ledger_storage::with_state(|state| config_ledger::get_fee(state, txn_type, &submitter_did, request_size)) {
+            Ok(Some(fee)) => fee,
+            Ok(None) => {
                 trace!("No fees found for request");
                 0
             }
+            Err(ec) => return ec
         };
 
         trace!("FEE: {}", fee);
 
-        let total_amount = _count_total_inputs(&inputs_json);
-        let total_payments = _count_total_payments(&outputs_json);
-
-        let err = if total_amount >= total_payments + fee {
-            match parse_req_id_from_request(&req_json) {
-                Err(ec) => return ec,
-                _ => ()
-            };
-            //we have enough money for this txn, give it back
-            let seq_no = payment_ledger::add_txn(inputs_json.clone(), outputs_json.clone());
-
-            _process_inputs(&inputs_json);
-            let infos: Vec<UTXOInfo> = _process_outputs(&outputs_json, seq_no);
-
-            _save_response(&infos, &req_json)
-        } else {
-            //we don't have enough money, send GET_TXN transaction to callback and in response PaymentsInsufficientFundsError will be returned
-            ledger::build_get_txn_request(
-                submitter_did.as_str(),
-                1,
-                Box::new(move |ec, res| {
-                    let ec = if ec == ErrorCode::Success {
-                        _add_response(&res, "INSUFFICIENT_FUNDS")
-                    } else { ec };
-                    trace!("libnullpay::add_request_fees::handle >>");
-                    _process_callback(cmd_handle, ec, res, cb);
-                }),
-            );
-            return ErrorCode::Success;
+        let req_id = match parse_req_id_from_request(&req_json) {
+            Ok(req_id) => req_id,
+            Err(ec) => return ec
         };
 
-        trace!("libnullpay::add_request_fees::handle >>");
-        _process_callback(cmd_handle, err, req_json, cb)
+        _sign_and_check_inputs(
+            wallet_handle,
+            inputs_json.clone(),
+            outputs_json.clone(),
+            req_id.to_string(),
+            Box::new(move |res| {
+                let (signed_inputs, valid) = match res {
+                    Ok(res) => res,
+                    Err(ec) => {
+                        _process_callback(cmd_handle, ec, String::new(), cb);
+                        return;
+                    }
+                };
+
+                let applied = if valid {
+                    _apply_txn(signed_inputs, &inputs_json, &outputs_json, fee)
+                } else {
+                    Ok(None)
+                };
+
+                let err = match applied {
+                    //we have enough money for this txn, give it back
+                    Ok(Some(infos)) => _save_response(&infos, &req_json),
+                    //some inputs are not signed by their owners, ledger rejects the fees and in response LedgerSecurityError will be returned
+                    Ok(None) if !valid => _add_response(&req_json, INVALID_SIGNATURE_RESPONSE),
+                    Ok(None) => {
+                        //we don't have enough money, send GET_TXN transaction to callback and in response PaymentsInsufficientFundsError will be returned
+                        let ec = ledger::build_get_txn_request(
+                            submitter_did.as_str(),
+                            1,
+                            Box::new(move |ec, res| {
+                                let ec = if ec == ErrorCode::Success {
+                                    _add_response(&res, INSUFFICIENT_FUNDS_RESPONSE)
+                                } else { ec };
+                                trace!("libnullpay::add_request_fees::handle >>");
+                                _process_callback(cmd_handle, ec, res, cb);
+                            }),
+                        );
+
+                        if ec != ErrorCode::Success {
+                            _process_callback(cmd_handle, ec, String::new(), cb);
+                        }
+                        return;
+                    }
+                    Err(ec) => ec
+                };
+
+                trace!("libnullpay::add_request_fees::handle >>");
+                _process_callback(cmd_handle, err, req_json.clone(), cb);
+            }),
+        );
+
+        ErrorCode::Success
     }
 }
 
@@ -121,9 +170,15 @@ pub mod build_get_utxo_request {
             1,
             Box::new(move |ec, res| {
                 let ec = if ec == ErrorCode::Success {
-                    let utxos = utxo_cache::get_utxos_by_payment_address(&payment_address);
-                    let infos: Vec<UTXOInfo> = utxos.into_iter().filter_map(payment_ledger::get_utxo_info).collect();
-                    _save_response(&infos, &res)
+                    let infos = ledger_storage::with_state(|state| {
+                        let utxos = utxo_cache::get_utxos_by_payment_address(state, &payment_address);
+                        utxos.into_iter().filter_map(|utxo| payment_ledger::get_utxo_info(state, utxo)).collect::<Vec<UTXOInfo>>()
+                    });
+
+                    match infos {
+                        Ok(infos) => _save_response(&infos, &res),
+                        Err(ec) => ec
+                    }
                 } else { ec };
 
                 trace!("libnullpay::build_get_utxo_request::handle >>");
@@ -145,7 +200,7 @@ pub mod parse_get_utxo_response {
 pub mod build_payment_req {
     use super::*;
 
-    pub extern fn handle(cmd_handle: i32, _wallet_handle: i32, submitter_did: *const c_char, inputs_json: *const c_char, outputs_json: *const c_char, cb: Option<IndyPaymentCallback>) -> ErrorCode {
+    pub extern fn handle(cmd_handle: i32, wallet_handle: i32, submitter_did: *const c_char, inputs_json: *const c_char, outputs_json: *const c_char, cb: Option<IndyPaymentCallback>) -> ErrorCode {
         check_useful_c_str!(submitter_did, ErrorCode::CommonInvalidState);
         check_useful_c_str!(inputs_json, ErrorCode::CommonInvalidState);
         check_useful_c_str!(outputs_json, ErrorCode::CommonInvalidState);
@@ -158,27 +213,37 @@ pub mod build_payment_req {
             submitter_did.as_str(),
             1,
             Box::new(move |ec, res| {
-                let total_balance = _count_total_inputs(&inputs_json);
-                let total_payments = _count_total_payments(&outputs_json);
-
-                let ec = if ec == ErrorCode::Success {
-                    if total_balance >= total_payments {
-                        let seq_no = payment_ledger::add_txn(inputs_json.clone(), outputs_json.clone());
-
-                        _process_inputs(&inputs_json);
-                        let infos = _process_outputs(&outputs_json, seq_no);
-
-                        _save_response(&infos, &res)
-                    } else {
-                        _add_response(&res, "INSUFFICIENT_FUNDS");
-                        ErrorCode::Success
-                    }
-                } else {
-                    ec
-                };
-
-                trace!("libnullpay::build_payment_req::handle >>");
-                _process_callback(cmd_handle, ec, res, cb);
+                if ec != ErrorCode::Success {
+                    trace!("libnullpay::build_payment_req::handle >>");
+                    _process_callback(cmd_handle, ec, res, cb);
+                    return;
+                }
+
+                let inputs_json = inputs_json.clone();
+                let outputs_json = outputs_json.clone();
+
+                _sign_and_check_inputs(
+                    wallet_handle,
+                    inputs_json.clone(),
+                    outputs_json.clone(),
+                    String::new(),
+                    Box::new(move |signed| {
+                        let ec = match signed {
+                            Ok((_, false)) => _add_response(&res, INVALID_SIGNATURE_RESPONSE),
+                            Ok((signed_inputs, true)) => {
+                                match _apply_txn(signed_inputs, &inputs_json, &outputs_json, 0) {
+                                    Ok(Some(infos)) => _save_response(&infos, &res),
+                                    Ok(None) => _add_response(&res, INSUFFICIENT_FUNDS_RESPONSE),
+                                    Err(ec) => ec
+                                }
+                            }
+                            Err(ec) => ec
+                        };
+
+                        trace!("libnullpay::build_payment_req::handle >>");
+                        _process_callback(cmd_handle, ec, res.clone(), cb);
+                    }),
+                );
             }),
         )
     }
@@ -206,13 +271,17 @@ pub mod build_mint_req {
         ledger::build_get_txn_request(submitter_did.as_str(),
                                       1,
                                       Box::new(move |ec, res| {
-                                          if ec == ErrorCode::Success {
-                                              let seq_no = payment_ledger::add_txn(vec![], outputs_json.clone());
+                                          let ec = if ec == ErrorCode::Success {
+                                              let minted = ledger_storage::with_state(|state| {
+                                                  let seq_no = payment_ledger::add_txn(state, vec![], outputs_json.clone());
 
-                                              outputs_json.clone().into_iter().for_each(|output| {
-                                                  utxo_cache::add_utxo(&output.payment_address, seq_no, output.amount);
+                                                  outputs_json.clone().into_iter().for_each(|output| {
+                                                      utxo_cache::add_utxo(state, &output.payment_address, seq_no, output.amount);
+                                                  });
                                               });
-                                          }
+
+                                              minted.err().unwrap_or(ec)
+                                          } else { ec };
 
                                           trace!("libnullpay::build_mint_req::handle >>");
                                           _process_callback(cmd_handle, ec, res, cb);
@@ -229,14 +298,18 @@ pub mod build_set_txn_fees_req {
         check_useful_c_str!(fees_json, ErrorCode::CommonInvalidState);
         trace!("libnullpay::build_set_txn_fees_req::handle << fees_json: {}, submitter_did: {}", fees_json, submitter_did);
 
-        parse_json!(fees_json, HashMap<String, i32>, ErrorCode::CommonInvalidStructure);
+        parse_json!(fees_json, HashMap<String, TxnFee>, ErrorCode::CommonInvalidStructure);
 
         ledger::build_get_txn_request(submitter_did.as_str(),
                                       1,
                                       Box::new(move |ec, res| {
-                                          if ec == ErrorCode::Success {
-                                              fees_json.clone().into_iter().for_each(|(key, value)| config_ledger::set_fees(key, value));
-                                          }
+                                          let ec = if ec == ErrorCode::Success {
+                                              let set = ledger_storage::with_state(|state| {
+                                                  fees_json.clone().into_iter().for_each(|(key, value)| config_ledger::set_fees(state, key, value));
+                                              });
+
+                                              set.err().unwrap_or(ec)
+                                          } else { ec };
 
                                           trace!("libnullpay::build_set_txn_fees_req::handle >>");
                                           _process_callback(cmd_handle, ec, res, cb);
@@ -256,9 +329,9 @@ pub mod build_get_txn_fees_req {
                                       1,
                                       Box::new(move |ec, res| {
                                           let ec = if ec == ErrorCode::Success {
-                                              let info = config_ledger::get_all_fees();
+                                              let info = ledger_storage::with_state(|state| config_ledger::get_all_fees(state));
 
-                                              match to_string(&info).map_err(|_| ErrorCode::CommonInvalidState) {
+                                              match info.and_then(|info| to_string(&info).map_err(|_| ErrorCode::CommonInvalidState)) {
                                                   Ok(str) => _add_response(&res, &str),
                                                   Err(ec) => ec
                                               }
@@ -280,6 +353,163 @@ pub mod parse_get_txn_fees_response {
     }
 }
 
+pub mod build_verify_payment_req {
+    use super::*;
+
+    pub extern fn handle(cmd_handle: i32, _wallet_handle: i32, submitter_did: *const c_char, receipt: *const c_char, cb: Option<IndyPaymentCallback>) -> ErrorCode {
+        check_useful_c_str!(submitter_did, ErrorCode::CommonInvalidState);
+        check_useful_c_str!(receipt, ErrorCode::CommonInvalidState);
+        trace!("libnullpay::build_verify_payment_req::handle << receipt: {}, submitter_did: {}", receipt, submitter_did);
+
+        let (seq_no, recipient) = match from_utxo(&receipt) {
+            Some(res) => res,
+            None => return ErrorCode::CommonInvalidStructure
+        };
+
+        ledger::build_get_txn_request(submitter_did.as_str(),
+                                      1,
+                                      Box::new(move |ec, res| {
+                                          let ec = if ec == ErrorCode::Success {
+                                              let info = ledger_storage::with_state(|state| {
+                                                  payment_ledger::get_txn(state, seq_no)
+                                                      .map(|(inputs, outputs)| _payment_info(seq_no, inputs, outputs))
+                                                      .and_then(|info| if info.receipts.iter().any(|r| r.recipient == recipient) { Some(info) } else { None })
+                                              });
+
+                                              match info {
+                                                  Ok(Some(info)) => match to_string(&info) {
+                                                      Ok(str) => _add_response(&res, &str),
+                                                      Err(_) => ErrorCode::CommonInvalidState
+                                                  },
+                                                  //the ledger knows nothing about the receipt, in response PaymentSourceDoesNotExistError will be returned
+                                                  Ok(None) => _add_response(&res, SOURCE_DOES_NOT_EXIST_RESPONSE),
+                                                  Err(ec) => ec
+                                              }
+                                          } else { ec };
+
+                                          trace!("libnullpay::build_verify_payment_req::handle >>");
+                                          _process_callback(cmd_handle, ec, res, cb);
+                                      }),
+        )
+    }
+}
+
+pub mod parse_verify_payment_response {
+    use super::*;
+
+    pub extern fn handle(cmd_handle: i32, resp_json: *const c_char, cb: Option<IndyPaymentCallback>) -> ErrorCode {
+        trace!("libnullpay::parse_verify_payment_response::handle <<");
+        _process_parse_response(cmd_handle, resp_json, cb)
+    }
+}
+
+pub mod sign_with_address {
+    use super::*;
+
+    pub extern fn handle(cmd_handle: i32, wallet_handle: i32, address: *const c_char, message_raw: *const u8, message_len: u32, cb: Option<IndySignWithAddressCallback>) -> ErrorCode {
+        check_useful_c_str!(address, ErrorCode::CommonInvalidState);
+        let message = match _bytes_from_raw(message_raw, message_len) {
+            Some(message) => message,
+            None => return ErrorCode::CommonInvalidState
+        };
+        trace!("libnullpay::sign_with_address::handle << address: {}, message: {:?}", address, message);
+
+        //address part of payment address is the verkey of wallet key created for it
+        let verkey = match verkey_from_address(address.as_str()) {
+            Some(verkey) => verkey,
+            None => return ErrorCode::CommonInvalidStructure
+        };
+
+        crypto::sign(
+            wallet_handle,
+            verkey.as_str(),
+            signatures::address_message(&message).as_slice(),
+            Box::new(move |ec, signature| {
+                trace!("libnullpay::sign_with_address::handle >>");
+                if let Some(cb) = cb {
+                    cb(cmd_handle, ec, signature.as_ptr(), signature.len() as u32);
+                }
+            }),
+        )
+    }
+}
+
+pub mod verify_with_address {
+    use super::*;
+
+    pub extern fn handle(cmd_handle: i32, address: *const c_char, message_raw: *const u8, message_len: u32, signature_raw: *const u8, signature_len: u32, cb: Option<IndyVerifyWithAddressCallback>) -> ErrorCode {
+        check_useful_c_str!(address, ErrorCode::CommonInvalidState);
+        let message = match _bytes_from_raw(message_raw, message_len) {
+            Some(message) => message,
+            None => return ErrorCode::CommonInvalidState
+        };
+        let signature = match _bytes_from_raw(signature_raw, signature_len) {
+            Some(signature) => signature,
+            None => return ErrorCode::CommonInvalidState
+        };
+        trace!("libnullpay::verify_with_address::handle << address: {}, message: {:?}, signature: {:?}", address, message, signature);
+
+        let verkey = match verkey_from_address(address.as_str()) {
+            Some(verkey) => verkey,
+            None => return ErrorCode::CommonInvalidStructure
+        };
+
+        crypto::verify(
+            verkey.as_str(),
+            signatures::address_message(&message).as_slice(),
+            signature.as_slice(),
+            Box::new(move |ec, valid| {
+                trace!("libnullpay::verify_with_address::handle >> valid: {}", valid);
+                if let Some(cb) = cb {
+                    cb(cmd_handle, ec, valid);
+                }
+            }),
+        )
+    }
+}
+
+pub mod get_request_fee {
+    use super::*;
+
+    pub extern fn handle(cmd_handle: i32, _wallet_handle: i32, submitter_did: *const c_char, req_json: *const c_char, fees_json: *const c_char, cb: Option<IndyGetRequestFeeCallback>) -> ErrorCode {
+        check_useful_c_str!(submitter_did, ErrorCode::CommonInvalidState);
+        check_useful_c_str!(req_json, ErrorCode::CommonInvalidState);
+        check_useful_c_str!(fees_json, ErrorCode::CommonInvalidState);
+        trace!("libnullpay::get_request_fee::handle << submitter_did: {}, req_json: {}, fees_json: {}", submitter_did, req_json, fees_json);
+
+        parse_json!(fees_json, HashMap<String, TxnFee>, ErrorCode::CommonInvalidStructure);
+
+        let txn_type = match parse_operation_from_request(req_json.as_str()) {
+            Ok(res) => res,
+            Err(ec) => {
+                error!("Can't parse operation from request");
+                return ec;
+            }
+        };
+
+        //the same rules as in add_request_fees, but applied to the fees schedule known to the caller
+        let request_size = req_json.len() as u64;
+        let (ec, fee) = match ledger_storage::with_state(|state| config_ledger::get_role(state, &submitter_did)) {
+            Ok(role) => (ErrorCode::Success, fees_json.get(&txn_type).map(|fee| fee.amount(role.as_ref().map(String::as_str), request_size)).unwrap_or(0)),
+            Err(ec) => (ec, 0)
+        };
+
+        trace!("libnullpay::get_request_fee::handle >> fee: {}", fee);
+        match cb {
+            Some(cb) => cb(cmd_handle, ec, fee),
+            None => ec
+        }
+    }
+}
+
+fn _bytes_from_raw(raw: *const u8, len: u32) -> Option<Vec<u8>> {
+    if raw.is_null() || len == 0 {
+        return None;
+    }
+
+    Some(unsafe { slice::from_raw_parts(raw, len as usize) }.to_vec())
+}
+
 fn _process_parse_response(cmd_handle: i32, response: *const c_char, cb: Option<IndyPaymentCallback>) -> ErrorCode {
     check_useful_c_str!(response, ErrorCode::CommonInvalidState);
     trace!("resp_json: {}", response);
@@ -299,19 +529,54 @@ fn _process_callback(cmd_handle: i32, err: ErrorCode, response: String, cb: Opti
     }
 }
 
-fn _process_outputs(outputs: &Vec<UTXOOutput>, seq_no: i32) -> Vec<UTXOInfo> {
+fn _apply_txn(signed_inputs: Vec<SignedInput>, inputs: &Vec<String>, outputs: &Vec<UTXOOutput>, fee: u64) -> Result<Option<Vec<UTXOInfo>>, ErrorCode> {
+    //balance check and ledger changes are done at once, so concurrent spends of the same inputs are not possible
+    ledger_storage::with_state(|state| {
+        let required = match _count_total_payments(outputs).and_then(|total| total.checked_add(fee)) {
+            Some(required) => required,
+            //nobody can own more than u64::MAX tokens
+            None => return None
+        };
+
+        if _count_total_inputs(state, inputs) < required {
+            return None;
+        }
+
+        let seq_no = payment_ledger::add_txn(state, signed_inputs, outputs.clone());
+
+        _process_inputs(state, inputs);
+        Some(_process_outputs(state, outputs, seq_no))
+    })
+}
+
+fn _payment_info(seq_no: i32, inputs: Vec<SignedInput>, outputs: Vec<UTXOOutput>) -> PaymentInfo {
+    PaymentInfo {
+        sources: inputs.into_iter().map(|input| input.input).collect(),
+        receipts: outputs.into_iter().filter_map(|out| {
+            to_utxo(&out.payment_address, seq_no).map(|receipt| ReceiptInfo {
+                receipt,
+                recipient: out.payment_address,
+                amount: out.amount,
+                extra: out.extra,
+            })
+        }).collect(),
+        extra: None,
+    }
+}
+
+fn _process_outputs(state: &mut LedgerState, outputs: &Vec<UTXOOutput>, seq_no: i32) -> Vec<UTXOInfo> {
     outputs.into_iter().map(|out| {
-        match utxo_cache::add_utxo(&out.payment_address, seq_no, out.amount)
-            .map(|utxo| payment_ledger::get_utxo_info(utxo)) {
+        match utxo_cache::add_utxo(state, &out.payment_address, seq_no, out.amount)
+            .map(|utxo| payment_ledger::get_utxo_info(state, utxo)) {
             Some(Some(utxo_info)) => utxo_info,
             _ => panic!("Some UTXO was not processed!")
         }
     }).collect()
 }
 
-fn _process_inputs(inputs: &Vec<String>) {
+fn _process_inputs(state: &mut LedgerState, inputs: &Vec<String>) {
     inputs.into_iter().for_each(|s| {
-        utxo_cache::remove_utxo(s);
+        utxo_cache::remove_utxo(state, s);
     });
 }
 
@@ -329,10 +594,43 @@ fn _add_response(request: &str, response: &str) -> ErrorCode {
     }
 }
 
-fn _count_total_inputs(inputs: &Vec<String>) -> i32 {
-    inputs.into_iter().filter_map(utxo_cache::get_balanse_of_utxo).fold(0, |acc, next| acc + next)
+fn _count_total_inputs(state: &LedgerState, inputs: &Vec<String>) -> u64 {
+    inputs.into_iter().filter_map(|utxo| utxo_cache::get_balanse_of_utxo(state, utxo)).fold(0, |acc, next| acc.saturating_add(next))
 }
 
-fn _count_total_payments(outputs: &Vec<UTXOOutput>) -> i32 {
-    outputs.into_iter().fold(0, |acc, next| acc + next.amount)
-}
\ No newline at end of file
+fn _count_total_payments(outputs: &Vec<UTXOOutput>) -> Option<u64> {
+    outputs.into_iter().fold(Some(0), |acc, next| acc.and_then(|acc| acc.checked_add(next.amount)))
+}
+fn _sign_and_check_inputs(wallet_handle: i32, inputs: Vec<String>, outputs: Vec<UTXOOutput>, extra: String,
+                          cb: Box<FnMut(Result<(Vec<SignedInput>, bool), ErrorCode>) + Send>) {
+    let mut cb = Some(cb);
+
+    signatures::sign_inputs(
+        wallet_handle,
+        inputs,
+        &outputs.clone(),
+        extra.clone().as_str(),
+        Box::new(move |res| {
+            let mut cb = match cb.take() {
+                Some(cb) => cb,
+                None => return
+            };
+
+            let signed_inputs = match res {
+                Ok(signed_inputs) => signed_inputs,
+                Err(ec) => return cb(Err(ec))
+            };
+
+            //the same check the ledger does before it accepts transaction inputs
+            let checked_inputs = signed_inputs.clone();
+            signatures::verify_inputs(
+                signed_inputs,
+                &outputs,
+                extra.as_str(),
+                Box::new(move |valid| {
+                    cb(valid.map(|valid| (checked_inputs.clone(), valid)))
+                }),
+            );
+        }),
+    )
+}
@@ -1,26 +1,28 @@
+use services::ledger_storage::LedgerState;
+use utils::types::TxnFee;
+
 use std::collections::HashMap;
-use std::sync::Mutex;
 
-lazy_static! {
-    static ref FEES: Mutex<HashMap<String, i32>> = Default::default();
+pub fn set_fees(state: &mut LedgerState, txn_name: String, txn_fee: TxnFee) {
+    state.fees.insert(txn_name, txn_fee);
 }
 
-pub fn set_fees(txn_name: String, txn_fee: i32) {
-    let mut fees = FEES.lock().unwrap();
-    fees.insert(txn_name, txn_fee);
+pub fn get_fee(state: &LedgerState, txn_name: String, submitter_did: &str, request_size: u64) -> Option<u64> {
+    let role = get_role(state, submitter_did);
+    state.fees.get(&txn_name).map(|fee| fee.amount(role.as_ref().map(String::as_str), request_size))
 }
 
-pub fn get_fee(txn_name: String) -> Option<i32> {
-    let fees = FEES.lock().unwrap();
-    fees.get(&txn_name).map(|res| res.clone())
+pub fn get_all_fees(state: &LedgerState) -> HashMap<String, TxnFee> {
+    state.fees.clone()
 }
 
-pub fn get_all_fees() -> HashMap<String, i32> {
-    let fees = FEES.lock().unwrap();
-    fees.clone()
+pub fn set_role(state: &mut LedgerState, did: String, role: Option<String>) {
+    match role {
+        Some(role) => state.roles.insert(did, role),
+        None => state.roles.remove(&did)
+    };
 }
 
-pub fn clear_fees() {
-    let mut fees = FEES.lock().unwrap();
-    fees.clear();
-}
\ No newline at end of file
+pub fn get_role(state: &LedgerState, did: &str) -> Option<String> {
+    state.roles.get(did).map(|res| res.clone())
+}
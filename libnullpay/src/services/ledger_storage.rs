@@ -0,0 +1,261 @@
+use ErrorCode;
+use utils::types::{SignedInput, TxnFee, UTXOOutput};
+
+use rusqlite::{self, Connection};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{from_str, to_string};
+use std::collections::HashMap;
+use std::env;
+use std::sync::Mutex;
+
+pub static DEFAULT_LEDGER_NAME: &str = "default";
+
+static LEDGER_NAME_ENV: &str = "NULLPAY_LEDGER";
+static STORAGE_PATH_ENV: &str = "NULLPAY_STORAGE_PATH";
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct NullpayConfig {
+    pub ledger: Option<String>,
+    pub storage_path: Option<String>,
+}
+
+impl NullpayConfig {
+    pub fn from_env() -> NullpayConfig {
+        NullpayConfig {
+            ledger: env::var(LEDGER_NAME_ENV).ok(),
+            storage_path: env::var(STORAGE_PATH_ENV).ok(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct LedgerState {
+    pub txns: HashMap<i32, (Vec<SignedInput>, Vec<UTXOOutput>)>,
+    pub last_seq_no: i32,
+    pub utxos: HashMap<String, Vec<String>>,
+    pub balances: HashMap<String, u64>,
+    pub fees: HashMap<String, TxnFee>,
+    #[serde(default)]
+    pub roles: HashMap<String, String>,
+}
+
+lazy_static! {
+    static ref CONFIG: Mutex<NullpayConfig> = Default::default();
+    static ref MEMORY_LEDGERS: Mutex<HashMap<String, LedgerState>> = Default::default();
+}
+
+pub fn configure(config: NullpayConfig) -> Result<(), ErrorCode> {
+    if let Some(ref path) = config.storage_path {
+        // fail on init rather than on the first payment operation
+        _open(path)?;
+    }
+
+    let mut current = CONFIG.lock().unwrap();
+    *current = config;
+    Ok(())
+}
+
+/// Runs the closure against the state of the configured ledger.
+/// For SQLite storage the whole call is one immediate transaction, so other processes
+/// working with the same file see either all changes made by the closure or none of them.
+/// Each transaction, UTXO list, balance, fee and role is a separate row and only changed rows are written.
+pub fn with_state<F, T>(f: F) -> Result<T, ErrorCode> where F: FnOnce(&mut LedgerState) -> T {
+    let config = CONFIG.lock().unwrap().clone();
+    let ledger = _ledger_name(&config, None);
+
+    match config.storage_path {
+        Some(path) => _with_sqlite_state(&path, &ledger, f),
+        None => {
+            let mut ledgers = MEMORY_LEDGERS.lock().unwrap();
+            Ok(f(ledgers.entry(ledger).or_insert_with(Default::default)))
+        }
+    }
+}
+
+pub fn reset(ledger: Option<&str>) -> Result<(), ErrorCode> {
+    let config = CONFIG.lock().unwrap().clone();
+    let ledger = _ledger_name(&config, ledger);
+
+    match config.storage_path {
+        Some(path) => {
+            let conn = _open(&path)?;
+            conn.execute("DELETE FROM ledgers WHERE name = ?1", &[&ledger]).map_err(_map_storage_err)?;
+            conn.execute("DELETE FROM ledger_entries WHERE ledger = ?1", &[&ledger])
+                .map(|_| ())
+                .map_err(_map_storage_err)
+        }
+        None => {
+            MEMORY_LEDGERS.lock().unwrap().remove(&ledger);
+            Ok(())
+        }
+    }
+}
+
+fn _ledger_name(config: &NullpayConfig, ledger: Option<&str>) -> String {
+    ledger.map(str::to_string)
+        .or(config.ledger.clone())
+        .unwrap_or(DEFAULT_LEDGER_NAME.to_string())
+}
+
+fn _open(path: &str) -> Result<Connection, ErrorCode> {
+    let conn = Connection::open(path).map_err(_map_storage_err)?;
+
+    // "ledgers" keeps whole states written by previous versions, they are moved to "ledger_entries" on first use
+    conn.execute_batch("PRAGMA busy_timeout = 10000;
+                        CREATE TABLE IF NOT EXISTS ledgers (name TEXT NOT NULL PRIMARY KEY, state TEXT NOT NULL);
+                        CREATE TABLE IF NOT EXISTS ledger_entries (
+                            ledger TEXT NOT NULL,
+                            key TEXT NOT NULL,
+                            value TEXT NOT NULL,
+                            PRIMARY KEY (ledger, key));")
+        .map_err(_map_storage_err)?;
+
+    Ok(conn)
+}
+
+fn _with_sqlite_state<F, T>(path: &str, ledger: &str, f: F) -> Result<T, ErrorCode> where F: FnOnce(&mut LedgerState) -> T {
+    let conn = _open(path)?;
+
+    conn.execute_batch("BEGIN IMMEDIATE").map_err(_map_storage_err)?;
+
+    let res = _load_entries(&conn, ledger)
+        .and_then(|stored| {
+            let mut state = _state_from_entries(&stored)?;
+            let res = f(&mut state);
+            _save_changed_entries(&conn, ledger, &stored, &_state_to_entries(&state)?).map(|_| res)
+        });
+
+    conn.execute_batch(if res.is_ok() { "COMMIT" } else { "ROLLBACK" })
+        .map_err(_map_storage_err)?;
+
+    res
+}
+
+const TXN_PREFIX: &str = "txn:";
+const UTXOS_PREFIX: &str = "utxos:";
+const BALANCE_PREFIX: &str = "balance:";
+const FEE_PREFIX: &str = "fee:";
+const ROLE_PREFIX: &str = "role:";
+const LAST_SEQ_NO_KEY: &str = "last_seq_no";
+
+fn _load_entries(conn: &Connection, ledger: &str) -> Result<HashMap<String, String>, ErrorCode> {
+    let legacy: Result<String, rusqlite::Error> = conn.query_row(
+        "SELECT state FROM ledgers WHERE name = ?1",
+        &[&ledger],
+        |row| row.get(0));
+
+    match legacy {
+        Ok(state) => {
+            let state: LedgerState = from_str(&state).map_err(|err| {
+                error!("Can't deserialize ledger state: {:?}", err);
+                ErrorCode::CommonInvalidState
+            })?;
+            _save_changed_entries(conn, ledger, &HashMap::new(), &_state_to_entries(&state)?)?;
+            conn.execute("DELETE FROM ledgers WHERE name = ?1", &[&ledger]).map_err(_map_storage_err)?;
+        }
+        Err(rusqlite::Error::QueryReturnedNoRows) => {}
+        Err(err) => return Err(_map_storage_err(err))
+    }
+
+    let mut stmt = conn.prepare("SELECT key, value FROM ledger_entries WHERE ledger = ?1").map_err(_map_storage_err)?;
+    let rows = stmt.query_map(&[&ledger], |row| (row.get(0), row.get(1))).map_err(_map_storage_err)?;
+
+    let mut entries = HashMap::new();
+    for row in rows {
+        let (key, value): (String, String) = row.map_err(_map_storage_err)?;
+        entries.insert(key, value);
+    }
+
+    Ok(entries)
+}
+
+fn _save_changed_entries(conn: &Connection,
+                         ledger: &str,
+                         stored: &HashMap<String, String>,
+                         entries: &HashMap<String, String>) -> Result<(), ErrorCode> {
+    for (key, value) in entries {
+        if stored.get(key) != Some(value) {
+            conn.execute("INSERT OR REPLACE INTO ledger_entries (ledger, key, value) VALUES (?1, ?2, ?3)", &[&ledger, key, value])
+                .map_err(_map_storage_err)?;
+        }
+    }
+
+    for key in stored.keys().filter(|key| !entries.contains_key(*key)) {
+        conn.execute("DELETE FROM ledger_entries WHERE ledger = ?1 AND key = ?2", &[&ledger, key])
+            .map_err(_map_storage_err)?;
+    }
+
+    Ok(())
+}
+
+fn _state_to_entries(state: &LedgerState) -> Result<HashMap<String, String>, ErrorCode> {
+    let mut entries = HashMap::new();
+
+    for (seq_no, txn) in &state.txns {
+        entries.insert(format!("{}{}", TXN_PREFIX, seq_no), _to_json(txn)?);
+    }
+    for (address, utxos) in &state.utxos {
+        entries.insert(format!("{}{}", UTXOS_PREFIX, address), _to_json(utxos)?);
+    }
+    for (utxo, balance) in &state.balances {
+        entries.insert(format!("{}{}", BALANCE_PREFIX, utxo), _to_json(balance)?);
+    }
+    for (txn_type, fee) in &state.fees {
+        entries.insert(format!("{}{}", FEE_PREFIX, txn_type), _to_json(fee)?);
+    }
+    for (did, role) in &state.roles {
+        entries.insert(format!("{}{}", ROLE_PREFIX, did), _to_json(role)?);
+    }
+    entries.insert(LAST_SEQ_NO_KEY.to_string(), _to_json(&state.last_seq_no)?);
+
+    Ok(entries)
+}
+
+fn _state_from_entries(entries: &HashMap<String, String>) -> Result<LedgerState, ErrorCode> {
+    let mut state = LedgerState::default();
+
+    for (key, value) in entries {
+        if key.starts_with(TXN_PREFIX) {
+            let seq_no = key[TXN_PREFIX.len()..].parse::<i32>().map_err(|err| {
+                error!("Invalid ledger entry key {}: {:?}", key, err);
+                ErrorCode::CommonInvalidState
+            })?;
+            state.txns.insert(seq_no, _from_json(value)?);
+        } else if key.starts_with(UTXOS_PREFIX) {
+            state.utxos.insert(key[UTXOS_PREFIX.len()..].to_string(), _from_json(value)?);
+        } else if key.starts_with(BALANCE_PREFIX) {
+            state.balances.insert(key[BALANCE_PREFIX.len()..].to_string(), _from_json(value)?);
+        } else if key.starts_with(FEE_PREFIX) {
+            state.fees.insert(key[FEE_PREFIX.len()..].to_string(), _from_json(value)?);
+        } else if key.starts_with(ROLE_PREFIX) {
+            state.roles.insert(key[ROLE_PREFIX.len()..].to_string(), _from_json(value)?);
+        } else if key == LAST_SEQ_NO_KEY {
+            state.last_seq_no = _from_json(value)?;
+        } else {
+            error!("Unknown ledger entry key: {}", key);
+            return Err(ErrorCode::CommonInvalidState);
+        }
+    }
+
+    Ok(state)
+}
+
+fn _to_json<T: Serialize>(value: &T) -> Result<String, ErrorCode> {
+    to_string(value).map_err(|err| {
+        error!("Can't serialize ledger entry: {:?}", err);
+        ErrorCode::CommonInvalidState
+    })
+}
+
+fn _from_json<T: DeserializeOwned>(value: &str) -> Result<T, ErrorCode> {
+    from_str(value).map_err(|err| {
+        error!("Can't deserialize ledger entry: {:?}", err);
+        ErrorCode::CommonInvalidState
+    })
+}
+
+fn _map_storage_err(err: rusqlite::Error) -> ErrorCode {
+    error!("Ledger storage error: {:?}", err);
+    ErrorCode::CommonIOError
+}
@@ -1,4 +1,6 @@
 pub mod config_ledger;
+pub mod ledger_storage;
 pub mod payment_ledger;
 pub mod response_storage;
+pub mod signatures;
 pub mod utxo_cache;
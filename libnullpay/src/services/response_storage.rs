@@ -3,6 +3,10 @@ use std::collections::HashMap;
 use std::sync::Mutex;
 use ErrorCode;
 
+pub static INSUFFICIENT_FUNDS_RESPONSE: &str = "INSUFFICIENT_FUNDS";
+pub static INVALID_SIGNATURE_RESPONSE: &str = "INVALID_SIGNATURE";
+pub static SOURCE_DOES_NOT_EXIST_RESPONSE: &str = "SOURCE_DOES_NOT_EXIST";
+
 lazy_static! {
     static ref RESPONSES: Mutex<HashMap<String, String>> = Default::default();
 }
@@ -31,7 +35,9 @@ pub fn get_response(response: &str) -> Result<String, ErrorCode> {
 
     let mut responses = RESPONSES.lock().unwrap();
     match responses.remove(req_id.to_string().as_str()) {
-        Some(ref resp) if resp == "INSUFFICIENT_FUNDS" => Err(ErrorCode::PaymentInsufficientFundsError),
+        Some(ref resp) if resp == INSUFFICIENT_FUNDS_RESPONSE => Err(ErrorCode::PaymentInsufficientFundsError),
+        Some(ref resp) if resp == INVALID_SIGNATURE_RESPONSE => Err(ErrorCode::LedgerSecurityError),
+        Some(ref resp) if resp == SOURCE_DOES_NOT_EXIST_RESPONSE => Err(ErrorCode::PaymentSourceDoesNotExistError),
         Some(resp) => Ok(resp),
         None => Err(ErrorCode::CommonInvalidState)
     }
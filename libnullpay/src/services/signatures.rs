@@ -0,0 +1,155 @@
+use ErrorCode;
+use libindy::crypto;
+use utils::types::{SignedInput, UTXOOutput};
+use utils::utxo::verkey_from_utxo;
+
+use serde_json::to_string;
+use std::sync::{Arc, Mutex};
+
+type ResultsClosure<T> = Box<FnMut(Result<Vec<T>, ErrorCode>) + Send>;
+
+struct Collector<T> {
+    results: Vec<Option<T>>,
+    remaining: usize,
+    cb: Option<ResultsClosure<T>>,
+}
+
+impl<T> Collector<T> {
+    fn new(count: usize, cb: ResultsClosure<T>) -> Collector<T> {
+        Collector {
+            results: (0..count).map(|_| None).collect(),
+            remaining: count,
+            cb: Some(cb),
+        }
+    }
+
+    fn set(&mut self, idx: usize, result: Result<T, ErrorCode>) -> Option<(ResultsClosure<T>, Result<Vec<T>, ErrorCode>)> {
+        if self.cb.is_none() {
+            return None;
+        }
+
+        match result {
+            Ok(res) => {
+                self.results[idx] = Some(res);
+                self.remaining -= 1;
+
+                if self.remaining == 0 {
+                    let results = self.results.drain(..).map(|res| res.unwrap()).collect();
+                    self.cb.take().map(|cb| (cb, Ok(results)))
+                } else {
+                    None
+                }
+            }
+            Err(ec) => self.cb.take().map(|cb| (cb, Err(ec)))
+        }
+    }
+}
+
+fn _set_result<T>(collector: &Arc<Mutex<Collector<T>>>, idx: usize, result: Result<T, ErrorCode>) {
+    // the lock is released before the caller closure is invoked so it can start new libindy calls
+    let ready = collector.lock().unwrap().set(idx, result);
+
+    if let Some((mut cb, results)) = ready {
+        cb(results)
+    }
+}
+
+const ADDRESS_MESSAGE_CONTEXT: &[u8] = b"nullpay:address-message:";
+
+/// Prefixes a message signed with payment address key by fixed context tag.
+/// Signatures made by sign_with_address can't be replayed as input signatures this way.
+pub fn address_message(message: &[u8]) -> Vec<u8> {
+    let mut res = ADDRESS_MESSAGE_CONTEXT.to_vec();
+    res.extend_from_slice(message);
+    res
+}
+
+pub fn input_message(input: &str, outputs: &Vec<UTXOOutput>, extra: &str) -> Result<Vec<u8>, ErrorCode> {
+    to_string(&(input, outputs, extra))
+        .map(|message| message.into_bytes())
+        .map_err(|_| ErrorCode::CommonInvalidState)
+}
+
+/// Signs every input with the key of its payment address held in the wallet.
+/// Inputs of addresses the wallet has no key for are left unsigned and will be rejected by the ledger.
+pub fn sign_inputs(wallet_handle: i32, inputs: Vec<String>, outputs: &Vec<UTXOOutput>, extra: &str, mut cb: ResultsClosure<SignedInput>) {
+    let mut requests = Vec::new();
+
+    for input in inputs {
+        let verkey = match verkey_from_utxo(&input) {
+            Some(verkey) => verkey,
+            None => return cb(Err(ErrorCode::CommonInvalidStructure))
+        };
+
+        let message = match input_message(&input, outputs, extra) {
+            Ok(message) => message,
+            Err(ec) => return cb(Err(ec))
+        };
+
+        requests.push((input, verkey, message));
+    }
+
+    if requests.is_empty() {
+        return cb(Ok(Vec::new()));
+    }
+
+    let collector = Arc::new(Mutex::new(Collector::new(requests.len(), cb)));
+
+    for (idx, (input, verkey, message)) in requests.into_iter().enumerate() {
+        let sign_collector = collector.clone();
+
+        let ec = crypto::sign(wallet_handle, &verkey, &message, Box::new(move |ec, signature| {
+            let result = match ec {
+                ErrorCode::Success => Ok(Some(signature)),
+                ErrorCode::WalletItemNotFound => Ok(None),
+                ec => Err(ec)
+            };
+
+            _set_result(&sign_collector, idx, result.map(|signature| SignedInput { input: input.clone(), signature }));
+        }));
+
+        if ec != ErrorCode::Success {
+            _set_result(&collector, idx, Err(ec));
+        }
+    }
+}
+
+/// Checks that every input is signed by the key of its payment address.
+pub fn verify_inputs(inputs: Vec<SignedInput>, outputs: &Vec<UTXOOutput>, extra: &str, mut cb: Box<FnMut(Result<bool, ErrorCode>) + Send>) {
+    let mut requests = Vec::new();
+
+    for input in inputs {
+        let (verkey, signature) = match (verkey_from_utxo(&input.input), input.signature) {
+            (Some(verkey), Some(signature)) => (verkey, signature),
+            _ => return cb(Ok(false))
+        };
+
+        let message = match input_message(&input.input, outputs, extra) {
+            Ok(message) => message,
+            Err(ec) => return cb(Err(ec))
+        };
+
+        requests.push((verkey, message, signature));
+    }
+
+    if requests.is_empty() {
+        return cb(Ok(true));
+    }
+
+    let collector: Arc<Mutex<Collector<bool>>> = Arc::new(Mutex::new(Collector::new(requests.len(), Box::new(move |results: Result<Vec<bool>, ErrorCode>| {
+        cb(results.map(|results| results.into_iter().all(|valid| valid)))
+    }))));
+
+    for (idx, (verkey, message, signature)) in requests.into_iter().enumerate() {
+        let verify_collector = collector.clone();
+
+        let ec = crypto::verify(&verkey, &message, &signature, Box::new(move |ec, valid| {
+            // a signature libindy can't even parse is just an invalid one for the ledger
+            _set_result(&verify_collector, idx, Ok(ec == ErrorCode::Success && valid));
+        }));
+
+        if ec != ErrorCode::Success {
+            _set_result(&collector, idx, Ok(false));
+        }
+    }
+}
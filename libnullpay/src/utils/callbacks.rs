@@ -4,6 +4,7 @@ use utils::sequence;
 use libc::c_char;
 use std::collections::HashMap;
 use std::ffi::CStr;
+use std::slice;
 use std::sync::Mutex;
 
 type EcClosure = Box<FnMut(ErrorCode) + Send>;
@@ -17,8 +18,7 @@ pub fn closure_to_cb_ec(closure: EcClosure) -> (i32, EcCallback) {
     }
 
     extern "C" fn _callback(command_handle: i32, err: ErrorCode) {
-        let mut callbacks = CALLBACKS.lock().unwrap();
-        let mut cb = callbacks.remove(&command_handle).unwrap();
+        let mut cb = CALLBACKS.lock().unwrap().remove(&command_handle).unwrap();
         cb(err)
     }
 
@@ -35,8 +35,7 @@ pub fn closure_to_cb_ec_string(closure: EcStringClosure) -> (i32, EcStringCallba
     }
 
     extern "C" fn _callback(command_handle: i32, err: ErrorCode, c_str: *const c_char) {
-        let mut callbacks = CALLBACKS.lock().unwrap();
-        let mut cb = callbacks.remove(&command_handle).unwrap();
+        let mut cb = CALLBACKS.lock().unwrap().remove(&command_handle).unwrap();
         let metadata = unsafe { CStr::from_ptr(c_str).to_str().unwrap().to_string() };
         cb(err, metadata)
     }
@@ -46,4 +45,43 @@ pub fn closure_to_cb_ec_string(closure: EcStringClosure) -> (i32, EcStringCallba
     callbacks.insert(command_handle, closure);
 
     (command_handle, Some(_callback))
-}
\ No newline at end of file
+}
+type EcVecU8Closure = Box<FnMut(ErrorCode, Vec<u8>) + Send>;
+type EcVecU8Callback = Option<extern fn(command_handle: i32, err: ErrorCode, raw: *const u8, len: u32)>;
+type EcBoolClosure = Box<FnMut(ErrorCode, bool) + Send>;
+type EcBoolCallback = Option<extern fn(command_handle: i32, err: ErrorCode, valid: bool)>;
+
+pub fn closure_to_cb_ec_vec_u8(closure: EcVecU8Closure) -> (i32, EcVecU8Callback) {
+    lazy_static! {
+       static ref CALLBACKS: Mutex<HashMap<i32, EcVecU8Closure>> = Default::default();
+    }
+
+    extern "C" fn _callback(command_handle: i32, err: ErrorCode, raw: *const u8, len: u32) {
+        let mut cb = CALLBACKS.lock().unwrap().remove(&command_handle).unwrap();
+        let vec = if raw.is_null() { Vec::new() } else { unsafe { slice::from_raw_parts(raw, len as usize).to_vec() } };
+        cb(err, vec)
+    }
+
+    let mut callbacks = CALLBACKS.lock().unwrap();
+    let command_handle = sequence::get_next_id();
+    callbacks.insert(command_handle, closure);
+
+    (command_handle, Some(_callback))
+}
+
+pub fn closure_to_cb_ec_bool(closure: EcBoolClosure) -> (i32, EcBoolCallback) {
+    lazy_static! {
+       static ref CALLBACKS: Mutex<HashMap<i32, EcBoolClosure>> = Default::default();
+    }
+
+    extern "C" fn _callback(command_handle: i32, err: ErrorCode, valid: bool) {
+        let mut cb = CALLBACKS.lock().unwrap().remove(&command_handle).unwrap();
+        cb(err, valid)
+    }
+
+    let mut callbacks = CALLBACKS.lock().unwrap();
+    let command_handle = sequence::get_next_id();
+    callbacks.insert(command_handle, closure);
+
+    (command_handle, Some(_callback))
+}
@@ -5,6 +5,5 @@ pub mod cstring;
 pub mod json_helper;
 pub mod logger;
 pub mod sequence;
-pub mod rand;
 pub mod types;
 pub mod utxo;
\ No newline at end of file
@@ -1,8 +1,10 @@
+use std::collections::HashMap;
+
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct UTXOOutput {
     #[serde(rename = "paymentAddress")]
     pub payment_address: String,
-    pub amount: i32,
+    pub amount: u64,
     pub extra: Option<String>
 }
 
@@ -19,6 +21,73 @@ impl Clone for UTXOOutput {
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct UTXOInfo {
     pub input: String,
-    pub amount: i32,
+    pub amount: u64,
+    pub extra: Option<String>,
+    #[serde(rename = "paymentAddress")]
+    pub payment_address: String,
+}
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct SignedInput {
+    pub input: String,
+    pub signature: Option<Vec<u8>>
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct PaymentAddressConfig {
+    pub seed: Option<String>
+}
+
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ReceiptInfo {
+    pub receipt: String,
+    pub recipient: String,
+    pub amount: u64,
     pub extra: Option<String>
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct PaymentInfo {
+    pub sources: Vec<String>,
+    pub receipts: Vec<ReceiptInfo>,
+    pub extra: Option<String>
+}
+
+/// Fee of transaction type: either flat amount or set of rules.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum TxnFee {
+    Flat(u64),
+    Rules(FeeRules)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Eq, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FeeRules {
+    #[serde(default)]
+    pub amount: u64,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub roles: HashMap<String, u64>,
+    #[serde(default)]
+    pub per_byte: u64,
+    #[serde(default)]
+    pub free_bytes: u64
+}
+
+impl TxnFee {
+    /// Fee for the request of given size sent by submitter with given role.
+    /// Role fee replaces the base amount, size surcharge is added to both.
+    pub fn amount(&self, role: Option<&str>, request_size: u64) -> u64 {
+        match *self {
+            TxnFee::Flat(amount) => amount,
+            TxnFee::Rules(ref rules) => {
+                let base = role.and_then(|role| rules.roles.get(role))
+                    .map(|amount| amount.clone())
+                    .unwrap_or(rules.amount);
+
+                let surcharge = request_size.saturating_sub(rules.free_bytes).saturating_mul(rules.per_byte);
+
+                base.saturating_add(surcharge)
+            }
+        }
+    }
+}
@@ -39,3 +39,17 @@ pub fn from_utxo(utxo: &str) -> Option<(i32, String)> {
 
     Some((seq_no, address_parts.join(":")))
 }
+
+pub fn verkey_from_utxo(utxo: &str) -> Option<String> {
+    from_utxo(utxo).and_then(|(_, payment_address)| verkey_from_address(&payment_address))
+}
+
+pub fn verkey_from_address(payment_address: &str) -> Option<String> {
+    let addr_parts: Vec<&str> = payment_address.split(":").collect();
+
+    if addr_parts.len() != 3 {
+        return None
+    }
+
+    addr_parts.get(2).map(|verkey| verkey.to_string())
+}
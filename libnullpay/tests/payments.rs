@@ -20,6 +20,7 @@ use utils::types::*;
 use utils::ledger;
 use utils::pool;
 use utils::did;
+use utils::crypto;
 use nullpay::ErrorCode;
 
 use std::collections::HashMap;
@@ -30,6 +31,8 @@ static POOL_NAME: &str = "pool_1";
 static SUBMITTER_DID: &str = "Th7MpTaRZVRYnPiabds81Y";
 static TRUSTEE_SEED: &str = "000000000000000000000000Trustee1";
 static FEES: &str = r#"{"1":1, "101":2}"#;
+static MESSAGE: &str = "I control this payment address";
+static FEE_RULES: &str = r#"{"20001": {"amount": 10, "roles": {"TRUSTEE": 0}, "perByte": 1, "freeBytes": 1024}}"#;
 
 mod high_cases {
     use super::*;
@@ -49,6 +52,23 @@ mod high_cases {
             wallet::close_wallet(wallet_handle).unwrap();
             test_utils::cleanup_storage();
         }
+
+        #[test]
+        pub fn create_payment_address_works_for_seed() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let other_wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+
+            let payment_address = payments::create_payment_address(wallet_handle, PAYMENT_METHOD_NAME, &format!(r#"{{"seed":"{}"}}"#, TRUSTEE_SEED)).unwrap();
+            let (_, trustee_vk) = did::create_and_store_my_did(other_wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+            assert_eq!(payment_address, format!("pay:null:{}", trustee_vk));
+
+            wallet::close_wallet(other_wallet_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
     }
 
     mod list_payments_addresses {
@@ -85,7 +105,7 @@ mod high_cases {
             let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
 
             let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
-            let mint: Vec<(String, i32, Option<&str>)> = addresses.clone().into_iter().enumerate().map(|(i, addr)| (addr, (i+1) as i32, None)).collect();
+            let mint: Vec<(String, u64, Option<&str>)> = addresses.clone().into_iter().enumerate().map(|(i, addr)| (addr, (i+1) as u64, None)).collect();
 
             payments_utils::mint_tokens(mint, wallet_handle, pool_handle, SUBMITTER_DID);
 
@@ -134,6 +154,26 @@ mod high_cases {
     mod mint {
         use super::*;
 
+        #[test]
+        pub fn mint_works_for_amount_greater_than_i32() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            let mint = vec![(addresses.get(0).unwrap().to_string(), 5_000_000_000, None)];
+            payments_utils::mint_tokens(mint, wallet_handle, pool_handle, SUBMITTER_DID);
+
+            let utxos = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+            let utxo_info: &UTXOInfo = utxos.get(addresses.get(0).unwrap()).unwrap().get(0).unwrap();
+            assert_eq!(utxo_info.amount, 5_000_000_000);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
         #[test]
         pub fn mint_works() {
             test_utils::cleanup_storage();
@@ -144,7 +184,7 @@ mod high_cases {
             let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
             let mint: Vec<UTXOOutput> = addresses.clone().into_iter().enumerate().map(|(i, payment_address)| UTXOOutput {
                 payment_address,
-                amount: ((i+1)*10) as i32,
+                amount: ((i+1)*10) as u64,
                 extra: None
             }).collect();
 
@@ -191,7 +231,7 @@ mod high_cases {
             let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
 
             //3. Mint tokens
-            let mint: Vec<(String, i32, Option<&str>)> = addresses.clone().into_iter().enumerate().map(|(i, addr)| (addr, ((i+2)*10) as i32, None)).collect();
+            let mint: Vec<(String, u64, Option<&str>)> = addresses.clone().into_iter().enumerate().map(|(i, addr)| (addr, ((i+2)*10) as u64, None)).collect();
             payments_utils::mint_tokens(mint, wallet_handle, pool_handle, my_did.as_str());
 
             //4. Get created UTXOs
@@ -230,7 +270,7 @@ mod high_cases {
             assert!(utxo_1.is_empty());
             let utxo_2 = utxo_map.get(addresses.get(1).unwrap()).unwrap();
             assert_eq!(utxo_2.len(), 2);
-            let amounts: Vec<i32> = utxo_2.into_iter().map(|info| info.amount).collect();
+            let amounts: Vec<u64> = utxo_2.into_iter().map(|info| info.amount).collect();
             assert!(amounts.contains(&30));
             assert!(amounts.contains(&19));
 
@@ -252,7 +292,7 @@ mod high_cases {
 
             let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
 
-            let mint: Vec<(String, i32, Option<&str>)> = addresses.clone().into_iter().enumerate().map(|(i, addr)| (addr, i as i32, None)).collect();
+            let mint: Vec<(String, u64, Option<&str>)> = addresses.clone().into_iter().enumerate().map(|(i, addr)| (addr, i as u64, None)).collect();
             payments_utils::mint_tokens(mint, wallet_handle, pool_handle, my_did.as_str());
 
             payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, r#"{"1": 10, "101": 10}"#);
@@ -300,7 +340,7 @@ mod high_cases {
             let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
 
             //2. Mint tokens and get created utxos
-            let mint: Vec<(String, i32, Option<&str>)> = addresses.clone().into_iter().enumerate().map(|(i, addr)| (addr, ((i+2)*10) as i32, None)).collect();
+            let mint: Vec<(String, u64, Option<&str>)> = addresses.clone().into_iter().enumerate().map(|(i, addr)| (addr, ((i+2)*10) as u64, None)).collect();
             payments_utils::mint_tokens(mint, wallet_handle, pool_handle, SUBMITTER_DID);
 
             let utxos = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
@@ -330,7 +370,7 @@ mod high_cases {
             let utxos: Vec<UTXOInfo> = serde_json::from_str(payment_resp_parsed.as_str()).unwrap();
             assert_eq!(utxos.len(), 2);
 
-            let utxos: HashMap<i32, String> = utxos.into_iter().map(|info| (info.amount, info.input)).collect();
+            let utxos: HashMap<u64, String> = utxos.into_iter().map(|info| (info.amount, info.input)).collect();
             let payment_utxo = utxos.get(&19).unwrap();
             let change_utxo = utxos.get(&1).unwrap();
 
@@ -344,12 +384,12 @@ mod high_cases {
             let utxo_map = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
             let utxo_1 = utxo_map.get(addresses.get(0).unwrap()).unwrap();
             assert_eq!(utxo_1.len(), 1);
-            let amounts: Vec<i32> = utxo_1.into_iter().map(|info| info.amount).collect();
+            let amounts: Vec<u64> = utxo_1.into_iter().map(|info| info.amount).collect();
             assert!(amounts.contains(&1));
 
             let utxo_2 = utxo_map.get(addresses.get(1).unwrap()).unwrap();
             assert_eq!(utxo_2.len(), 2);
-            let amounts: Vec<i32> = utxo_2.into_iter().map(|info| info.amount).collect();
+            let amounts: Vec<u64> = utxo_2.into_iter().map(|info| info.amount).collect();
             assert!(amounts.contains(&19));
             assert!(amounts.contains(&30));
 
@@ -367,7 +407,7 @@ mod high_cases {
 
             let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
 
-            let mint: Vec<(String, i32, Option<&str>)> = addresses.clone().into_iter().enumerate().map(|(i, addr)| (addr, ((i+2)*10) as i32, None)).collect();
+            let mint: Vec<(String, u64, Option<&str>)> = addresses.clone().into_iter().enumerate().map(|(i, addr)| (addr, ((i+2)*10) as u64, None)).collect();
             payments_utils::mint_tokens(mint, wallet_handle, pool_handle, SUBMITTER_DID);
 
             let utxos = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
@@ -399,27 +439,96 @@ mod high_cases {
             wallet::close_wallet(wallet_handle).unwrap();
             test_utils::cleanup_storage();
         }
+
+        #[test]
+        pub fn payments_work_for_not_owned_address() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let other_wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
+
+            let mint: Vec<(String, u64, Option<&str>)> = addresses.clone().into_iter().enumerate().map(|(i, addr)| (addr, ((i+2)*10) as u64, None)).collect();
+            payments_utils::mint_tokens(mint, wallet_handle, pool_handle, SUBMITTER_DID);
+
+            let utxos = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+
+            let addr_1 = addresses.get(0).unwrap();
+            let utxos_1: Vec<String> = utxos.get(addr_1.as_str()).unwrap().into_iter().map(|info| info.input.clone()).collect();
+            let inputs = serde_json::to_string(&utxos_1).unwrap();
+
+            let other_addresses = payments_utils::create_addresses(vec!["{}"], other_wallet_handle, PAYMENT_METHOD_NAME);
+
+            let outputs = vec![UTXOOutput{
+                payment_address: other_addresses.get(0).unwrap().to_string(),
+                amount: 20,
+                extra: None
+            }];
+            let outputs = serde_json::to_string(&outputs).unwrap();
+
+            //the other wallet has no key of the first address, so the ledger rejects its inputs
+            let (payment_req, payment_method) = payments::build_payment_req(other_wallet_handle, SUBMITTER_DID, inputs.as_str(), outputs.as_str()).unwrap();
+            let payment_resp = ledger::submit_request(pool_handle, payment_req.as_str()).unwrap();
+            let payment_err = payments::parse_payment_response(payment_method.as_str(), payment_resp.as_str()).unwrap_err();
+            assert_eq!(payment_err, ErrorCode::LedgerSecurityError);
+
+            let utxos_after = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+            assert_eq!(utxos, utxos_after);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(other_wallet_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
     }
 
-    mod fees {
+    mod verify_payment {
         use super::*;
 
         #[test]
-        pub fn set_request_fees_works() {
+        pub fn verify_payment_works() {
             test_utils::cleanup_storage();
             plugin::init_plugin();
             let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
             let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
 
-            let fees_req = payments::build_set_txn_fees_req(wallet_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, FEES).unwrap();
-            ledger::submit_request(pool_handle, fees_req.as_str()).unwrap();
+            let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            payments_utils::mint_tokens(vec![(addresses[0].clone(), 10, None)], wallet_handle, pool_handle, SUBMITTER_DID);
 
-            let fees_stored = payments_utils::get_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME);
+            let utxos = payments_utils::get_utxos_with_balance(vec![addresses[0].clone()], wallet_handle, pool_handle, SUBMITTER_DID);
+            let input = utxos.get(&addresses[0]).unwrap()[0].input.clone();
+            let inputs = serde_json::to_string(&vec![input.clone()]).unwrap();
 
-            let fee_1 = fees_stored.get("1").unwrap();
-            assert_eq!(fee_1, &1);
-            let fee_2 = fees_stored.get("101").unwrap();
-            assert_eq!(fee_2, &2);
+            let outputs = vec![UTXOOutput {
+                payment_address: addresses[1].clone(),
+                amount: 7,
+                extra: Some("order 1".to_string())
+            }, UTXOOutput {
+                payment_address: addresses[0].clone(),
+                amount: 3,
+                extra: None
+            }];
+            let outputs = serde_json::to_string(&outputs).unwrap();
+
+            let (payment_req, payment_method) = payments::build_payment_req(wallet_handle, SUBMITTER_DID, inputs.as_str(), outputs.as_str()).unwrap();
+            let payment_resp = ledger::submit_request(pool_handle, payment_req.as_str()).unwrap();
+            let payment_resp = payments::parse_payment_response(payment_method.as_str(), payment_resp.as_str()).unwrap();
+            let payment_utxos: Vec<UTXOInfo> = serde_json::from_str(payment_resp.as_str()).unwrap();
+            let receipt = payment_utxos.into_iter().find(|info| info.amount == 7).unwrap().input;
+
+            let info = payments_utils::verify_payment(wallet_handle, pool_handle, SUBMITTER_DID, &receipt).unwrap();
+
+            assert_eq!(info.sources, vec![input]);
+            assert_eq!(info.receipts.len(), 2);
+            assert!(info.receipts.contains(&ReceiptInfo {
+                receipt,
+                recipient: addresses[1].clone(),
+                amount: 7,
+                extra: Some("order 1".to_string())
+            }));
+            assert!(info.receipts.iter().any(|receipt| receipt.recipient == addresses[0] && receipt.amount == 3));
 
             pool::close(pool_handle).unwrap();
             wallet::close_wallet(wallet_handle).unwrap();
@@ -427,73 +536,92 @@ mod high_cases {
         }
 
         #[test]
-        pub fn get_request_fees_works() {
+        pub fn verify_payment_works_for_minted_receipt() {
             test_utils::cleanup_storage();
             plugin::init_plugin();
             let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
             let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
 
-            payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, FEES);
+            let addresses = payments_utils::create_addresses(vec!["{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            payments_utils::mint_tokens(vec![(addresses[0].clone(), 10, None)], wallet_handle, pool_handle, SUBMITTER_DID);
 
-            let req = payments::build_get_txn_fees_req(wallet_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME).unwrap();
-            let resp = ledger::submit_request(pool_handle, req.as_str()).unwrap();
-            let resp = payments::parse_get_utxo_response(PAYMENT_METHOD_NAME, resp.as_str()).unwrap();
-            let map = serde_json::from_str::<HashMap<String, i32>>(resp.as_str()).unwrap();
+            let utxos = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+            let receipt = utxos.get(&addresses[0]).unwrap()[0].input.clone();
 
-            let fee_1 = map.get("1").unwrap();
-            assert_eq!(fee_1, &1);
-            let fee_2 = map.get("101").unwrap();
-            assert_eq!(fee_2, &2);
+            let info = payments_utils::verify_payment(wallet_handle, pool_handle, SUBMITTER_DID, &receipt).unwrap();
+
+            assert!(info.sources.is_empty());
+            assert_eq!(info.receipts, vec![ReceiptInfo {
+                receipt,
+                recipient: addresses[0].clone(),
+                amount: 10,
+                extra: None
+            }]);
 
             pool::close(pool_handle).unwrap();
             wallet::close_wallet(wallet_handle).unwrap();
             test_utils::cleanup_storage();
         }
     }
-}
 
-mod medium_cases {
-    use super::*;
-    
-    mod add_request_fees {
+    mod sign_and_submit_request_with_fees {
         use super::*;
-        
+
         #[test]
-        pub fn add_request_fees_works_for_request_without_operation() {
+        pub fn sign_and_submit_request_with_fees_works() {
             test_utils::cleanup_storage();
             plugin::init_plugin();
             let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
             let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
 
-            let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
-            let mint: Vec<(String, i32, Option<&str>)> = addresses.clone().into_iter().enumerate().map(|(i, addr)| (addr, i as i32, None)).collect();
-            payments_utils::mint_tokens(mint, wallet_handle, pool_handle, SUBMITTER_DID);
+            let (trustee_did, _) = did::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let (my_did, my_vk) = did::create_and_store_my_did(wallet_handle, None).unwrap();
+            let nym_req = ledger::build_nym_request(&trustee_did, &my_did, &my_vk, "aaa", "TRUSTEE").unwrap();
 
+            let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            payments_utils::mint_tokens(vec![(addresses[0].clone(), 10, None)], wallet_handle, pool_handle, SUBMITTER_DID);
             payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, FEES);
 
+            let (nym_resp, receipts) = ledger::sign_and_submit_request_with_fees(pool_handle, wallet_handle, &trustee_did, &nym_req,
+                                                                                 PAYMENT_METHOD_NAME, &addresses[1]).unwrap();
+
+            let nym_resp: serde_json::Value = serde_json::from_str(&nym_resp).unwrap();
+            assert_eq!(nym_resp["op"].as_str().unwrap(), "REPLY");
+
+            let receipts: Vec<UTXOInfo> = serde_json::from_str(&receipts).unwrap();
+            assert_eq!(receipts.len(), 1);
+            assert_eq!(receipts[0].amount, 9);
+
             let utxos = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+            assert!(utxos.get(&addresses[0]).unwrap().is_empty());
+            assert_eq!(utxos.get(&addresses[1]).unwrap(), &receipts);
 
-            let addr_1 = addresses.get(0).unwrap();
-            let utxos_1: Vec<String> = utxos.get(addr_1.as_str()).unwrap().into_iter().map(|info| info.input.clone()).collect();
-            let inputs = serde_json::to_string(&utxos_1).unwrap();
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
 
-            let outputs = vec![UTXOOutput{
-                payment_address: addresses.get(1).unwrap().to_string(),
-                amount: 19,
-                extra: None
-            }, UTXOOutput {
-                payment_address: addresses.get(0).unwrap().to_string(),
-                amount: 1,
-                extra: None
-            }];
-            let outputs = serde_json::to_string(&outputs).unwrap();
+        #[test]
+        pub fn sign_and_submit_request_with_fees_works_for_txn_type_without_fees() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
 
-            let nym_req_err = payments::add_request_fees(wallet_handle, SUBMITTER_DID, EMPTY_OBJECT, inputs.as_str(), outputs.as_str()).unwrap_err();
-            assert_eq!(nym_req_err, ErrorCode::CommonInvalidStructure);
+            let (trustee_did, _) = did::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let (my_did, my_vk) = did::create_and_store_my_did(wallet_handle, None).unwrap();
+            let nym_req = ledger::build_nym_request(&trustee_did, &my_did, &my_vk, "aaa", "TRUSTEE").unwrap();
 
-            //IMPORTANT: check that utxo cache stays the same
-            let utxos_after = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
-            assert_eq!(utxos, utxos_after);
+            let addresses = payments_utils::create_addresses(vec!["{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            payments_utils::mint_tokens(vec![(addresses[0].clone(), 10, None)], wallet_handle, pool_handle, SUBMITTER_DID);
+            payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, r#"{"101":2}"#);
+
+            let (_, receipts) = ledger::sign_and_submit_request_with_fees(pool_handle, wallet_handle, &trustee_did, &nym_req,
+                                                                          PAYMENT_METHOD_NAME, &addresses[0]).unwrap();
+            assert_eq!(receipts, "[]");
+
+            let utxos = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+            assert_eq!(utxos.get(&addresses[0]).unwrap()[0].amount, 10);
 
             pool::close(pool_handle).unwrap();
             wallet::close_wallet(wallet_handle).unwrap();
@@ -501,64 +629,779 @@ mod medium_cases {
         }
 
         #[test]
-        pub fn add_request_fees_works_for_request_without_req_id() {
+        pub fn sign_and_submit_request_with_fees_works_for_fee_rules() {
             test_utils::cleanup_storage();
             plugin::init_plugin();
             let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
             let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
 
+            let (trustee_did, _) = did::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            plugin::set_did_role(trustee_did.as_str(), Some("TRUSTEE")).unwrap();
+            let (my_did, my_vk) = did::create_and_store_my_did(wallet_handle, None).unwrap();
+            let nym_req = ledger::build_nym_request(&trustee_did, &my_did, &my_vk, "aaa", "TRUSTEE").unwrap();
+
             let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
-            let mint: Vec<(String, i32, Option<&str>)> = addresses.clone().into_iter().enumerate().map(|(i, addr)| (addr, i as i32, None)).collect();
-            payments_utils::mint_tokens(mint, wallet_handle, pool_handle, SUBMITTER_DID);
+            payments_utils::mint_tokens(vec![(addresses[0].clone(), 10, None)], wallet_handle, pool_handle, SUBMITTER_DID);
+            payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, r#"{"1": {"amount": 5, "roles": {"TRUSTEE": 2}}}"#);
 
-            payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, FEES);
+            let (_, receipts) = ledger::sign_and_submit_request_with_fees(pool_handle, wallet_handle, &trustee_did, &nym_req,
+                                                                          PAYMENT_METHOD_NAME, &addresses[1]).unwrap();
 
-            let utxos = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+            let receipts: Vec<UTXOInfo> = serde_json::from_str(&receipts).unwrap();
+            assert_eq!(receipts.len(), 1);
+            assert_eq!(receipts[0].amount, 8);
 
-            let addr_1 = addresses.get(0).unwrap();
-            let utxos_1: Vec<String> = utxos.get(addr_1.as_str()).unwrap().into_iter().map(|info| info.input.clone()).collect();
-            let inputs = serde_json::to_string(&utxos_1).unwrap();
+            plugin::set_did_role(trustee_did.as_str(), None).unwrap();
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
 
-            let outputs = vec![UTXOOutput{
-                payment_address: addresses.get(1).unwrap().to_string(),
-                amount: 19,
+        #[test]
+        pub fn sign_and_submit_request_with_fees_works_for_changed_fees() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let (trustee_did, _) = did::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            payments_utils::mint_tokens(vec![(addresses[0].clone(), 10, None)], wallet_handle, pool_handle, SUBMITTER_DID);
+            payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, r#"{"1": 1}"#);
+
+            let (my_did, my_vk) = did::create_and_store_my_did(wallet_handle, None).unwrap();
+            let nym_req = ledger::build_nym_request(&trustee_did, &my_did, &my_vk, "aaa", "TRUSTEE").unwrap();
+            let (_, receipts) = ledger::sign_and_submit_request_with_fees(pool_handle, wallet_handle, &trustee_did, &nym_req,
+                                                                          PAYMENT_METHOD_NAME, &addresses[1]).unwrap();
+            let receipts: Vec<UTXOInfo> = serde_json::from_str(&receipts).unwrap();
+            assert_eq!(receipts[0].amount, 9);
+
+            //cached schedule is dropped once new fees are set
+            payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, r#"{"1": 3}"#);
+
+            let (my_did, my_vk) = did::create_and_store_my_did(wallet_handle, None).unwrap();
+            let nym_req = ledger::build_nym_request(&trustee_did, &my_did, &my_vk, "aaa", "TRUSTEE").unwrap();
+            let (_, receipts) = ledger::sign_and_submit_request_with_fees(pool_handle, wallet_handle, &trustee_did, &nym_req,
+                                                                          PAYMENT_METHOD_NAME, &addresses[0]).unwrap();
+            let receipts: Vec<UTXOInfo> = serde_json::from_str(&receipts).unwrap();
+            assert_eq!(receipts[0].amount, 6);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+    }
+
+    mod prepare_payment {
+        use super::*;
+
+        #[test]
+        pub fn prepare_payment_works() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}", "{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            payments_utils::mint_tokens(vec![(addresses[0].clone(), 10, None), (addresses[1].clone(), 5, None)], wallet_handle, pool_handle, SUBMITTER_DID);
+
+            let (inputs, change) = payments::prepare_payment(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, 11, r#"{"10001": 1}"#, &addresses[0], None).unwrap();
+            let inputs: Vec<String> = serde_json::from_str(&inputs).unwrap();
+            let mut outputs: Vec<UTXOOutput> = serde_json::from_str(&change).unwrap();
+
+            assert_eq!(inputs.len(), 2);
+            assert_eq!(outputs, vec![UTXOOutput {
+                payment_address: addresses[0].clone(),
+                amount: 3,
                 extra: None
-            }, UTXOOutput {
-                payment_address: addresses.get(0).unwrap().to_string(),
-                amount: 1,
+            }]);
+
+            outputs.push(UTXOOutput {
+                payment_address: addresses[2].clone(),
+                amount: 12,
                 extra: None
-            }];
+            });
+
+            let inputs = serde_json::to_string(&inputs).unwrap();
             let outputs = serde_json::to_string(&outputs).unwrap();
 
-            let nym_req_err = payments::add_request_fees(wallet_handle, SUBMITTER_DID, r#"{"reqId": 111}"#, inputs.as_str(), outputs.as_str()).unwrap_err();
-            assert_eq!(nym_req_err, ErrorCode::CommonInvalidStructure);
+            let (payment_req, _) = payments::build_payment_req(wallet_handle, SUBMITTER_DID, inputs.as_str(), outputs.as_str()).unwrap();
+            ledger::submit_request(pool_handle, payment_req.as_str()).unwrap();
 
-            //IMPORTANT: check that utxo cache stays the same
-            let utxos_after = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
-            assert_eq!(utxos, utxos_after);
+            let utxos = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+
+            assert_eq!(utxos.get(&addresses[0]).unwrap()[0].amount, 3);
+            assert!(utxos.get(&addresses[1]).unwrap().is_empty());
+            assert_eq!(utxos.get(&addresses[2]).unwrap()[0].amount, 12);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn prepare_payment_works_for_smallest_sufficient_strategy() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            payments_utils::mint_tokens(vec![(addresses[0].clone(), 10, None), (addresses[1].clone(), 5, None)], wallet_handle, pool_handle, SUBMITTER_DID);
+
+            let utxos = payments_utils::get_utxos_with_balance(vec![addresses[1].clone()], wallet_handle, pool_handle, SUBMITTER_DID);
+
+            let (inputs, change) = payments::prepare_payment(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, 5, "{}", &addresses[0],
+                                                             Some(r#"{"strategy": "smallest_sufficient"}"#)).unwrap();
+
+            assert_eq!(serde_json::from_str::<Vec<String>>(&inputs).unwrap(), vec![utxos.get(&addresses[1]).unwrap()[0].input.clone()]);
+            assert_eq!(change, "[]");
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn prepare_payment_works_for_fees_schedule() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            payments_utils::mint_tokens(vec![(addresses[0].clone(), 10, None)], wallet_handle, pool_handle, SUBMITTER_DID);
+
+            let (_, change) = payments::prepare_payment(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, 5,
+                                                        r#"{"1": 3, "10001": 1}"#, &addresses[0],
+                                                        Some(r#"{"txnType": "10001"}"#)).unwrap();
+            let outputs: Vec<UTXOOutput> = serde_json::from_str(&change).unwrap();
+
+            assert_eq!(outputs, vec![UTXOOutput {
+                payment_address: addresses[0].clone(),
+                amount: 4,
+                extra: None
+            }]);
 
             pool::close(pool_handle).unwrap();
             wallet::close_wallet(wallet_handle).unwrap();
             test_utils::cleanup_storage();
         }
     }
-    
+
     mod fees {
         use super::*;
-        
+
         #[test]
-        pub fn build_set_txn_fees_works_for_invalid_json() {
+        pub fn set_request_fees_works() {
             test_utils::cleanup_storage();
             plugin::init_plugin();
             let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
             let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
 
-            let err = payments::build_set_txn_fees_req(wallet_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, "{1}").unwrap_err();
-            assert_eq!(err, ErrorCode::CommonInvalidStructure);
+            let fees_req = payments::build_set_txn_fees_req(wallet_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, FEES).unwrap();
+            ledger::submit_request(pool_handle, fees_req.as_str()).unwrap();
+
+            let fees_stored = payments_utils::get_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME);
+
+            let fee_1 = fees_stored.get("1").unwrap();
+            assert_eq!(fee_1.as_u64(), Some(1));
+            let fee_2 = fees_stored.get("101").unwrap();
+            assert_eq!(fee_2.as_u64(), Some(2));
 
             pool::close(pool_handle).unwrap();
             wallet::close_wallet(wallet_handle).unwrap();
             test_utils::cleanup_storage();
         }
-    }
-}
\ No newline at end of file
+
+        #[test]
+        pub fn get_request_fees_works() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, FEES);
+
+            let req = payments::build_get_txn_fees_req(wallet_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME).unwrap();
+            let resp = ledger::submit_request(pool_handle, req.as_str()).unwrap();
+            let resp = payments::parse_get_utxo_response(PAYMENT_METHOD_NAME, resp.as_str()).unwrap();
+            let map = serde_json::from_str::<HashMap<String, serde_json::Value>>(resp.as_str()).unwrap();
+
+            let fee_1 = map.get("1").unwrap();
+            assert_eq!(fee_1.as_u64(), Some(1));
+            let fee_2 = map.get("101").unwrap();
+            assert_eq!(fee_2.as_u64(), Some(2));
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn get_request_fees_works_for_fee_rules() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, FEE_RULES);
+
+            let fees_stored = payments_utils::get_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME);
+
+            let expected: HashMap<String, serde_json::Value> = serde_json::from_str(FEE_RULES).unwrap();
+            assert_eq!(fees_stored.get("20001"), expected.get("20001"));
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn add_request_fees_works_for_role_fee() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let (trustee_did, _) = did::create_and_store_my_did(wallet_handle, None).unwrap();
+            plugin::set_did_role(trustee_did.as_str(), Some("TRUSTEE")).unwrap();
+            let (my_did, _) = did::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, r#"{"20002": {"amount": 10, "roles": {"TRUSTEE": 0}}}"#);
+
+            let addresses = payments_utils::create_addresses(vec!["{}", "{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            let mint: Vec<(String, u64, Option<&str>)> = addresses.clone().into_iter().take(2).map(|addr| (addr, 10, None)).collect();
+            payments_utils::mint_tokens(mint, wallet_handle, pool_handle, SUBMITTER_DID);
+
+            let utxos = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+
+            let outputs = vec![UTXOOutput {
+                payment_address: addresses.get(2).unwrap().to_string(),
+                amount: 10,
+                extra: None
+            }];
+            let outputs = serde_json::to_string(&outputs).unwrap();
+
+            //1. trustee pays no fee, so all tokens can be transferred
+            let inputs: Vec<String> = utxos.get(addresses.get(0).unwrap()).unwrap().into_iter().map(|info| info.input.clone()).collect();
+            let inputs = serde_json::to_string(&inputs).unwrap();
+            let req = r#"{"reqId": 20002001, "operation": {"type": "20002"}}"#;
+
+            payments::add_request_fees(wallet_handle, trustee_did.as_str(), req, inputs.as_str(), outputs.as_str()).unwrap();
+
+            //2. submitter without role pays the base fee, so the same transfer is rejected
+            let inputs: Vec<String> = utxos.get(addresses.get(1).unwrap()).unwrap().into_iter().map(|info| info.input.clone()).collect();
+            let inputs = serde_json::to_string(&inputs).unwrap();
+            let req = r#"{"reqId": 20002002, "operation": {"type": "20002"}}"#;
+
+            payments::add_request_fees(wallet_handle, my_did.as_str(), req, inputs.as_str(), outputs.as_str()).unwrap();
+
+            let utxos_after = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+            assert!(utxos_after.get(addresses.get(0).unwrap()).unwrap().is_empty());
+            assert_eq!(utxos_after.get(addresses.get(1).unwrap()), utxos.get(addresses.get(1).unwrap()));
+            assert_eq!(utxos_after.get(addresses.get(2).unwrap()).unwrap().len(), 1);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn add_request_fees_works_for_size_surcharge() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            //surcharge is 1 token per byte over free bytes, so the request costs 5 tokens
+            let req = r#"{"reqId": 20003001, "operation": {"type": "20003"}}"#;
+            let fees = format!(r#"{{"20003": {{"perByte": 1, "freeBytes": {}}}}}"#, req.len() - 5);
+            payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, fees.as_str());
+
+            let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            let mint = vec![(addresses.get(0).unwrap().to_string(), 10, None)];
+            payments_utils::mint_tokens(mint, wallet_handle, pool_handle, SUBMITTER_DID);
+
+            let utxos = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+            let inputs: Vec<String> = utxos.get(addresses.get(0).unwrap()).unwrap().into_iter().map(|info| info.input.clone()).collect();
+            let inputs = serde_json::to_string(&inputs).unwrap();
+
+            let outputs = vec![UTXOOutput {
+                payment_address: addresses.get(1).unwrap().to_string(),
+                amount: 5,
+                extra: None
+            }];
+            let outputs = serde_json::to_string(&outputs).unwrap();
+
+            payments::add_request_fees(wallet_handle, SUBMITTER_DID, req, inputs.as_str(), outputs.as_str()).unwrap();
+
+            let utxos_after = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+            assert!(utxos_after.get(addresses.get(0).unwrap()).unwrap().is_empty());
+            let amounts: Vec<u64> = utxos_after.get(addresses.get(1).unwrap()).unwrap().into_iter().map(|info| info.amount).collect();
+            assert_eq!(amounts, vec![5]);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+    }
+
+    mod sign_with_address {
+        use super::*;
+
+        #[test]
+        pub fn sign_with_address_works() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}"], wallet_handle, PAYMENT_METHOD_NAME);
+
+            let signature = payments::sign_with_address(wallet_handle, &addresses[0], MESSAGE.as_bytes()).unwrap();
+
+            let valid = payments::verify_with_address(&addresses[0], MESSAGE.as_bytes(), &signature).unwrap();
+            assert!(valid);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn verify_with_address_works_for_other_message() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}"], wallet_handle, PAYMENT_METHOD_NAME);
+
+            let signature = payments::sign_with_address(wallet_handle, &addresses[0], MESSAGE.as_bytes()).unwrap();
+
+            let valid = payments::verify_with_address(&addresses[0], "Other message".as_bytes(), &signature).unwrap();
+            assert!(!valid);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn verify_with_address_works_for_other_address() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
+
+            let signature = payments::sign_with_address(wallet_handle, &addresses[0], MESSAGE.as_bytes()).unwrap();
+
+            let valid = payments::verify_with_address(&addresses[1], MESSAGE.as_bytes(), &signature).unwrap();
+            assert!(!valid);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn sign_with_address_works_for_signature_not_valid_for_plain_message() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            let verkey = addresses[0].split(":").last().unwrap();
+
+            //inputs are authorized with plain signature of the message, so address signature can't be used to spend them
+            let message = format!(r#"["pay:null:1_{}",[{{"paymentAddress":"{}","amount":10,"extra":null}}],"1"]"#, verkey, addresses[0]);
+
+            let signature = payments::sign_with_address(wallet_handle, &addresses[0], message.as_bytes()).unwrap();
+
+            let valid = payments::verify_with_address(&addresses[0], message.as_bytes(), &signature).unwrap();
+            assert!(valid);
+
+            let valid = crypto::verify(verkey, message.as_bytes(), &signature).unwrap();
+            assert!(!valid);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+    }
+}
+
+mod medium_cases {
+    use super::*;
+    
+    mod add_request_fees {
+        use super::*;
+        
+        #[test]
+        pub fn add_request_fees_works_for_request_without_operation() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            let mint: Vec<(String, u64, Option<&str>)> = addresses.clone().into_iter().enumerate().map(|(i, addr)| (addr, i as u64, None)).collect();
+            payments_utils::mint_tokens(mint, wallet_handle, pool_handle, SUBMITTER_DID);
+
+            payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, FEES);
+
+            let utxos = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+
+            let addr_1 = addresses.get(0).unwrap();
+            let utxos_1: Vec<String> = utxos.get(addr_1.as_str()).unwrap().into_iter().map(|info| info.input.clone()).collect();
+            let inputs = serde_json::to_string(&utxos_1).unwrap();
+
+            let outputs = vec![UTXOOutput{
+                payment_address: addresses.get(1).unwrap().to_string(),
+                amount: 19,
+                extra: None
+            }, UTXOOutput {
+                payment_address: addresses.get(0).unwrap().to_string(),
+                amount: 1,
+                extra: None
+            }];
+            let outputs = serde_json::to_string(&outputs).unwrap();
+
+            let nym_req_err = payments::add_request_fees(wallet_handle, SUBMITTER_DID, EMPTY_OBJECT, inputs.as_str(), outputs.as_str()).unwrap_err();
+            assert_eq!(nym_req_err, ErrorCode::CommonInvalidStructure);
+
+            //IMPORTANT: check that utxo cache stays the same
+            let utxos_after = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+            assert_eq!(utxos, utxos_after);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn add_request_fees_works_for_request_without_req_id() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            let mint: Vec<(String, u64, Option<&str>)> = addresses.clone().into_iter().enumerate().map(|(i, addr)| (addr, i as u64, None)).collect();
+            payments_utils::mint_tokens(mint, wallet_handle, pool_handle, SUBMITTER_DID);
+
+            payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, FEES);
+
+            let utxos = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+
+            let addr_1 = addresses.get(0).unwrap();
+            let utxos_1: Vec<String> = utxos.get(addr_1.as_str()).unwrap().into_iter().map(|info| info.input.clone()).collect();
+            let inputs = serde_json::to_string(&utxos_1).unwrap();
+
+            let outputs = vec![UTXOOutput{
+                payment_address: addresses.get(1).unwrap().to_string(),
+                amount: 19,
+                extra: None
+            }, UTXOOutput {
+                payment_address: addresses.get(0).unwrap().to_string(),
+                amount: 1,
+                extra: None
+            }];
+            let outputs = serde_json::to_string(&outputs).unwrap();
+
+            let nym_req_err = payments::add_request_fees(wallet_handle, SUBMITTER_DID, r#"{"reqId": 111}"#, inputs.as_str(), outputs.as_str()).unwrap_err();
+            assert_eq!(nym_req_err, ErrorCode::CommonInvalidStructure);
+
+            //IMPORTANT: check that utxo cache stays the same
+            let utxos_after = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+            assert_eq!(utxos, utxos_after);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn add_request_fees_works_for_not_owned_address() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let other_wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let (trustee_did, _) = did::create_and_store_my_did(other_wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let (my_did, my_vk) = did::create_and_store_my_did(other_wallet_handle, None).unwrap();
+            let nym_req = ledger::build_nym_request(&trustee_did, &my_did, &my_vk, "aaa", "TRUSTEE").unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            let mint: Vec<(String, u64, Option<&str>)> = addresses.clone().into_iter().enumerate().map(|(i, addr)| (addr, ((i+2)*10) as u64, None)).collect();
+            payments_utils::mint_tokens(mint, wallet_handle, pool_handle, SUBMITTER_DID);
+
+            payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, FEES);
+
+            let utxos = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+
+            let addr_1 = addresses.get(0).unwrap();
+            let utxos_1: Vec<String> = utxos.get(addr_1.as_str()).unwrap().into_iter().map(|info| info.input.clone()).collect();
+            let inputs = serde_json::to_string(&utxos_1).unwrap();
+
+            let outputs = vec![UTXOOutput{
+                payment_address: addresses.get(1).unwrap().to_string(),
+                amount: 19,
+                extra: None
+            }];
+            let outputs = serde_json::to_string(&outputs).unwrap();
+
+            let (nym_req_with_fees, payment_method) = payments::add_request_fees(other_wallet_handle, SUBMITTER_DID, nym_req.as_str(), inputs.as_str(), outputs.as_str()).unwrap();
+            let nym_resp = ledger::sign_and_submit_request(pool_handle, other_wallet_handle, trustee_did.as_str(), nym_req_with_fees.as_str()).unwrap();
+            let nym_resp_parsed_err = payments::parse_response_with_fees(payment_method.as_str(), nym_resp.as_str()).unwrap_err();
+            assert_eq!(nym_resp_parsed_err, ErrorCode::LedgerSecurityError);
+
+            //IMPORTANT: check that utxo cache stays the same
+            let utxos_after = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+            assert_eq!(utxos, utxos_after);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(other_wallet_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+    }
+    
+    mod fees {
+        use super::*;
+        
+        #[test]
+        pub fn build_set_txn_fees_works_for_invalid_json() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let err = payments::build_set_txn_fees_req(wallet_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, "{1}").unwrap_err();
+            assert_eq!(err, ErrorCode::CommonInvalidStructure);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn build_set_txn_fees_works_for_negative_fee() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let err = payments::build_set_txn_fees_req(wallet_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, r#"{"1": -1}"#).unwrap_err();
+            assert_eq!(err, ErrorCode::CommonInvalidStructure);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn build_set_txn_fees_works_for_unknown_rule() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let err = payments::build_set_txn_fees_req(wallet_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, r#"{"1": {"perKb": 1}}"#).unwrap_err();
+            assert_eq!(err, ErrorCode::CommonInvalidStructure);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn set_did_role_works_for_empty_did() {
+            plugin::init_plugin();
+
+            let err = plugin::set_did_role("", Some("TRUSTEE")).unwrap_err();
+            assert_eq!(err, ErrorCode::CommonInvalidParam1);
+        }
+    }
+
+    mod sign_and_submit_request_with_fees {
+        use super::*;
+
+        #[test]
+        pub fn sign_and_submit_request_with_fees_works_for_insufficient_funds() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let (trustee_did, _) = did::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let (my_did, my_vk) = did::create_and_store_my_did(wallet_handle, None).unwrap();
+            let nym_req = ledger::build_nym_request(&trustee_did, &my_did, &my_vk, "aaa", "TRUSTEE").unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, FEES);
+
+            let err = ledger::sign_and_submit_request_with_fees(pool_handle, wallet_handle, &trustee_did, &nym_req,
+                                                                PAYMENT_METHOD_NAME, &addresses[0]).unwrap_err();
+            assert_eq!(err, ErrorCode::PaymentInsufficientFundsError);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn sign_and_submit_request_with_fees_works_for_request_without_operation() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let (trustee_did, _) = did::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let addresses = payments_utils::create_addresses(vec!["{}"], wallet_handle, PAYMENT_METHOD_NAME);
+
+            let err = ledger::sign_and_submit_request_with_fees(pool_handle, wallet_handle, &trustee_did, r#"{"reqId": 111}"#,
+                                                                PAYMENT_METHOD_NAME, &addresses[0]).unwrap_err();
+            assert_eq!(err, ErrorCode::CommonInvalidStructure);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+    }
+
+    mod payment {
+        use super::*;
+
+        #[test]
+        pub fn payments_work_for_amount_overflow() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            let mint = vec![(addresses.get(0).unwrap().to_string(), 10, None)];
+            payments_utils::mint_tokens(mint, wallet_handle, pool_handle, SUBMITTER_DID);
+
+            let utxos = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+            let inputs: Vec<String> = utxos.get(addresses.get(0).unwrap()).unwrap().into_iter().map(|info| info.input.clone()).collect();
+            let inputs = serde_json::to_string(&inputs).unwrap();
+
+            //total of outputs overflows u64, so it can't be covered by any inputs
+            let outputs = vec![UTXOOutput {
+                payment_address: addresses.get(1).unwrap().to_string(),
+                amount: u64::max_value(),
+                extra: None
+            }, UTXOOutput {
+                payment_address: addresses.get(0).unwrap().to_string(),
+                amount: 2,
+                extra: None
+            }];
+            let outputs = serde_json::to_string(&outputs).unwrap();
+
+            let (payment_req, payment_method) = payments::build_payment_req(wallet_handle, SUBMITTER_DID, inputs.as_str(), outputs.as_str()).unwrap();
+            let payment_resp = ledger::submit_request(pool_handle, payment_req.as_str()).unwrap();
+            let payment_err = payments::parse_payment_response(payment_method.as_str(), payment_resp.as_str()).unwrap_err();
+            assert_eq!(payment_err, ErrorCode::PaymentInsufficientFundsError);
+
+            let utxos_after = payments_utils::get_utxos_with_balance(addresses.clone(), wallet_handle, pool_handle, SUBMITTER_DID);
+            assert_eq!(utxos, utxos_after);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+    }
+
+    mod prepare_payment {
+        use super::*;
+
+        #[test]
+        pub fn prepare_payment_works_for_insufficient_funds() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}", "{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            payments_utils::mint_tokens(vec![(addresses[0].clone(), 10, None), (addresses[1].clone(), 5, None)], wallet_handle, pool_handle, SUBMITTER_DID);
+
+            let err = payments::prepare_payment(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, 15, r#"{"10001": 1}"#, &addresses[0], None).unwrap_err();
+            assert_eq!(err, ErrorCode::PaymentInsufficientFundsError);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+    }
+
+    mod verify_payment {
+        use super::*;
+
+        #[test]
+        pub fn verify_payment_works_for_unknown_receipt() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}"], wallet_handle, PAYMENT_METHOD_NAME);
+            let receipt = addresses[0].replacen(&format!("pay:{}:", PAYMENT_METHOD_NAME), &format!("pay:{}:100500_", PAYMENT_METHOD_NAME), 1);
+
+            let err = payments_utils::verify_payment(wallet_handle, pool_handle, SUBMITTER_DID, &receipt).unwrap_err();
+            assert_eq!(err, ErrorCode::PaymentSourceDoesNotExistError);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn verify_payment_works_for_malformed_receipt() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+
+            let err = payments::build_verify_payment_req(wallet_handle, SUBMITTER_DID, "pay:null:no_seq_no_here").unwrap_err();
+            assert_eq!(err, ErrorCode::CommonInvalidStructure);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+    }
+
+    mod sign_with_address {
+        use super::*;
+
+        #[test]
+        pub fn sign_with_address_works_for_address_from_other_wallet() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let other_wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}"], other_wallet_handle, PAYMENT_METHOD_NAME);
+
+            let err = payments::sign_with_address(wallet_handle, &addresses[0], MESSAGE.as_bytes()).unwrap_err();
+            assert_eq!(err, ErrorCode::WalletItemNotFound);
+
+            wallet::close_wallet(other_wallet_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn verify_with_address_works_for_malformed_signature() {
+            test_utils::cleanup_storage();
+            plugin::init_plugin();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+
+            let addresses = payments_utils::create_addresses(vec!["{}"], wallet_handle, PAYMENT_METHOD_NAME);
+
+            let err = payments::verify_with_address(&addresses[0], MESSAGE.as_bytes(), &[1, 2, 3]).unwrap_err();
+            assert_eq!(err, ErrorCode::CommonInvalidStructure);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+    }
+}
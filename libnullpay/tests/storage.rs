@@ -0,0 +1,172 @@
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+extern crate serde;
+extern crate nullpay;
+
+#[macro_use]
+extern crate lazy_static;
+extern crate log;
+
+#[macro_use]
+mod utils;
+
+use utils::plugin;
+use utils::payments_utils;
+use utils::wallet;
+use utils::test_utils;
+use utils::pool;
+use nullpay::ErrorCode;
+
+use std::sync::Mutex;
+
+static PAYMENT_METHOD_NAME: &str = "null";
+static POOL_NAME: &str = "pool_1";
+static SUBMITTER_DID: &str = "Th7MpTaRZVRYnPiabds81Y";
+static FEES: &str = r#"{"1":1, "101":2}"#;
+
+lazy_static! {
+    // plugin configuration is process wide, so tests that change it can't run in parallel
+    static ref CONFIG_LOCK: Mutex<()> = Default::default();
+}
+
+fn mint_to_new_address(wallet_handle: i32, pool_handle: i32, amount: u64) -> String {
+    let addresses = payments_utils::create_addresses(vec!["{}"], wallet_handle, PAYMENT_METHOD_NAME);
+    let mint: Vec<(String, u64, Option<&str>)> = addresses.clone().into_iter().map(|addr| (addr, amount, None)).collect();
+    payments_utils::mint_tokens(mint, wallet_handle, pool_handle, SUBMITTER_DID);
+    addresses[0].clone()
+}
+
+fn utxos_count(address: &str, wallet_handle: i32, pool_handle: i32) -> usize {
+    let utxos = payments_utils::get_utxos_with_balance(vec![address.to_string()], wallet_handle, pool_handle, SUBMITTER_DID);
+    utxos.get(address).unwrap().len()
+}
+
+mod high_cases {
+    use super::*;
+
+    mod init_with_config {
+        use super::*;
+
+        #[test]
+        pub fn init_with_config_works_for_named_ledgers() {
+            let _lock = CONFIG_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+            test_utils::cleanup_storage();
+            plugin::init_plugin_with_config(r#"{"ledger":"named_ledgers_1"}"#).unwrap();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let address = mint_to_new_address(wallet_handle, pool_handle, 10);
+            assert_eq!(utxos_count(&address, wallet_handle, pool_handle), 1);
+
+            plugin::init_plugin_with_config(r#"{"ledger":"named_ledgers_2"}"#).unwrap();
+            assert_eq!(utxos_count(&address, wallet_handle, pool_handle), 0);
+
+            plugin::init_plugin_with_config(r#"{"ledger":"named_ledgers_1"}"#).unwrap();
+            assert_eq!(utxos_count(&address, wallet_handle, pool_handle), 1);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn init_with_config_works_for_storage_path() {
+            let _lock = CONFIG_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+            test_utils::cleanup_storage();
+            let storage_path = plugin::storage_path("nullpay_storage_path.db");
+            let config = format!(r#"{{"ledger":"storage_path","storage_path":"{}"}}"#, storage_path);
+            plugin::init_plugin_with_config(&config).unwrap();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let address = mint_to_new_address(wallet_handle, pool_handle, 10);
+            payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, FEES);
+
+            //the same ledger kept in memory knows nothing about the stored state
+            plugin::init_plugin_with_config(r#"{"ledger":"storage_path"}"#).unwrap();
+            assert_eq!(utxos_count(&address, wallet_handle, pool_handle), 0);
+
+            plugin::init_plugin_with_config(&config).unwrap();
+            assert_eq!(utxos_count(&address, wallet_handle, pool_handle), 1);
+
+            let fees = payments_utils::get_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME);
+            assert_eq!(fees.get("1"), Some(&1));
+            assert_eq!(fees.get("101"), Some(&2));
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+    }
+
+    mod reset {
+        use super::*;
+
+        #[test]
+        pub fn reset_works() {
+            let _lock = CONFIG_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+            test_utils::cleanup_storage();
+            plugin::init_plugin_with_config(r#"{"ledger":"reset"}"#).unwrap();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let address = mint_to_new_address(wallet_handle, pool_handle, 10);
+            payments_utils::set_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME, FEES);
+
+            plugin::reset(None).unwrap();
+
+            assert_eq!(utxos_count(&address, wallet_handle, pool_handle), 0);
+            let fees = payments_utils::get_request_fees(wallet_handle, pool_handle, SUBMITTER_DID, PAYMENT_METHOD_NAME);
+            assert!(fees.is_empty());
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+
+        #[test]
+        pub fn reset_works_for_other_ledger() {
+            let _lock = CONFIG_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+            test_utils::cleanup_storage();
+            let storage_path = plugin::storage_path("nullpay_reset.db");
+            plugin::init_plugin_with_config(&format!(r#"{{"ledger":"reset_1","storage_path":"{}"}}"#, storage_path)).unwrap();
+            let wallet_handle = wallet::create_and_open_wallet(POOL_NAME, None).unwrap();
+            let pool_handle = pool::create_and_open_pool_ledger(POOL_NAME).unwrap();
+
+            let address = mint_to_new_address(wallet_handle, pool_handle, 10);
+
+            plugin::reset(Some("reset_2")).unwrap();
+            assert_eq!(utxos_count(&address, wallet_handle, pool_handle), 1);
+
+            plugin::reset(Some("reset_1")).unwrap();
+            assert_eq!(utxos_count(&address, wallet_handle, pool_handle), 0);
+
+            pool::close(pool_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            test_utils::cleanup_storage();
+        }
+    }
+}
+
+mod medium_cases {
+    use super::*;
+
+    mod init_with_config {
+        use super::*;
+
+        #[test]
+        pub fn init_with_config_works_for_invalid_json() {
+            let _lock = CONFIG_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+            let err = plugin::init_plugin_with_config("{1}").unwrap_err();
+            assert_eq!(err, ErrorCode::CommonInvalidStructure);
+        }
+
+        #[test]
+        pub fn init_with_config_works_for_unreachable_storage_path() {
+            let _lock = CONFIG_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+            let err = plugin::init_plugin_with_config(r#"{"storage_path":"/nonexistent_dir/nullpay.db"}"#).unwrap_err();
+            assert_eq!(err, ErrorCode::CommonIOError);
+        }
+    }
+}
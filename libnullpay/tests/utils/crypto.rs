@@ -0,0 +1,34 @@
+use nullpay::ErrorCode;
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+pub fn verify(their_vk: &str, message: &[u8], signature: &[u8]) -> Result<bool, ErrorCode> {
+    let (receiver, command_handle, cb) = super::callbacks::_closure_to_cb_ec_bool();
+
+    let their_vk = CString::new(their_vk).unwrap();
+
+    let err = unsafe {
+        indy_crypto_verify(command_handle,
+                           their_vk.as_ptr(),
+                           message.as_ptr() as *const u8,
+                           message.len() as u32,
+                           signature.as_ptr() as *const u8,
+                           signature.len() as u32,
+                           cb)
+    };
+
+    super::results::result_to_bool(err, receiver)
+}
+
+extern {
+    #[no_mangle]
+    fn indy_crypto_verify(command_handle: i32,
+                          their_vk: *const c_char,
+                          message_raw: *const u8,
+                          message_len: u32,
+                          signature_raw: *const u8,
+                          signature_len: u32,
+                          cb: Option<extern fn(command_handle_: i32,
+                                               err: ErrorCode,
+                                               valid: bool)>) -> ErrorCode;
+}
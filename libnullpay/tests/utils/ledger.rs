@@ -30,6 +30,29 @@ pub fn sign_and_submit_request(pool_handle: i32, wallet_handle: i32, submitter_d
     super::results::result_to_string(err, receiver)
 }
 
+pub fn sign_and_submit_request_with_fees(pool_handle: i32, wallet_handle: i32, submitter_did: &str, request_json: &str,
+                                         payment_method: &str, change_address: &str) -> Result<(String, String), ErrorCode> {
+    let (receiver, command_handle, cb) = super::callbacks::_closure_to_cb_ec_string_string();
+
+    let submitter_did = CString::new(submitter_did).unwrap();
+    let request_json = CString::new(request_json).unwrap();
+    let payment_method = CString::new(payment_method).unwrap();
+    let change_address = CString::new(change_address).unwrap();
+
+    let err = unsafe {
+        indy_sign_and_submit_request_with_fees(command_handle,
+                                               pool_handle,
+                                               wallet_handle,
+                                               submitter_did.as_ptr(),
+                                               request_json.as_ptr(),
+                                               payment_method.as_ptr(),
+                                               change_address.as_ptr(),
+                                               cb)
+    };
+
+    super::results::result_to_string_string(err, receiver)
+}
+
 pub fn build_nym_request(submitter_did: &str, target_did: &str, verkey: &str, alias: &str, role: &str) -> Result<String, ErrorCode> {
     let (receiver, command_handle, cb) = super::callbacks::_closure_to_cb_ec_string();
 
@@ -81,4 +104,17 @@ extern {
                                                                     err: ErrorCode,
                                                                     request_result_json: *const c_char)>) -> ErrorCode;
 
+    #[no_mangle]
+    fn indy_sign_and_submit_request_with_fees(command_handle: i32,
+                                              pool_handle: i32,
+                                              wallet_handle: i32,
+                                              submitter_did: *const c_char,
+                                              request_json: *const c_char,
+                                              payment_method: *const c_char,
+                                              change_address: *const c_char,
+                                              cb: Option<extern fn(xcommand_handle: i32,
+                                                                   err: ErrorCode,
+                                                                   request_result_json: *const c_char,
+                                                                   receipts_json: *const c_char)>) -> ErrorCode;
+
 }
\ No newline at end of file
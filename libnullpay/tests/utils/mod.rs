@@ -1,6 +1,7 @@
 extern crate libc;
 extern crate nullpay;
 
+pub mod crypto;
 pub mod did;
 pub mod payments;
 pub mod plugin;
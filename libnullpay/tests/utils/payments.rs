@@ -2,6 +2,7 @@ use nullpay::ErrorCode;
 
 use std::ffi::CString;
 use std::os::raw::c_char;
+use std::ptr::null;
 
 pub fn create_payment_address(wallet_handle: i32, payment_method: &str, config: &str) -> Result<String, ErrorCode> {
     let (receiver, command_handle, cb) = super::callbacks::_closure_to_cb_ec_string();
@@ -216,6 +217,103 @@ pub fn parse_get_txn_fees_response(payment_method: &str, resp_json: &str) -> Res
     super::results::result_to_string(err, receiver)
 }
 
+pub fn build_verify_payment_req(wallet_handle: i32, submitter_did: &str, receipt: &str) -> Result<(String, String), ErrorCode> {
+    let (receiver, command_handle, cb) =
+        super::callbacks::_closure_to_cb_ec_string_string();
+
+    let submitter_did = CString::new(submitter_did).unwrap();
+    let receipt = CString::new(receipt).unwrap();
+
+    let err = unsafe {
+        indy_build_verify_payment_req(command_handle,
+                                      wallet_handle,
+                                      submitter_did.as_ptr(),
+                                      receipt.as_ptr(),
+                                      cb)
+    };
+
+    super::results::result_to_string_string(err, receiver)
+}
+
+pub fn parse_verify_payment_response(payment_method: &str, resp_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) =
+        super::callbacks::_closure_to_cb_ec_string();
+
+    let payment_method = CString::new(payment_method).unwrap();
+    let resp_json = CString::new(resp_json).unwrap();
+
+    let err = unsafe {
+        indy_parse_verify_payment_response(command_handle,
+                                           payment_method.as_ptr(),
+                                           resp_json.as_ptr(),
+                                           cb)
+    };
+
+    super::results::result_to_string(err, receiver)
+}
+
+pub fn prepare_payment(wallet_handle: i32, pool_handle: i32, submitter_did: &str, payment_method: &str, amount: u64, fees_json: &str,
+                       change_address: &str, config_json: Option<&str>) -> Result<(String, String), ErrorCode> {
+    let (receiver, command_handle, cb) =
+        super::callbacks::_closure_to_cb_ec_string_string();
+
+    let submitter_did = CString::new(submitter_did).unwrap();
+    let payment_method = CString::new(payment_method).unwrap();
+    let fees_json = CString::new(fees_json).unwrap();
+    let change_address = CString::new(change_address).unwrap();
+    let config_json_str = config_json.map(|s| CString::new(s).unwrap()).unwrap_or(CString::new("").unwrap());
+
+    let err = unsafe {
+        indy_prepare_payment(command_handle,
+                             wallet_handle,
+                             pool_handle,
+                             submitter_did.as_ptr(),
+                             payment_method.as_ptr(),
+                             amount,
+                             fees_json.as_ptr(),
+                             change_address.as_ptr(),
+                             if config_json.is_some() { config_json_str.as_ptr() } else { null() },
+                             cb)
+    };
+
+    super::results::result_to_string_string(err, receiver)
+}
+
+pub fn sign_with_address(wallet_handle: i32, address: &str, message: &[u8]) -> Result<Vec<u8>, ErrorCode> {
+    let (receiver, command_handle, cb) = super::callbacks::_closure_to_cb_ec_vec_u8();
+
+    let address = CString::new(address).unwrap();
+
+    let err = unsafe {
+        indy_sign_with_address(command_handle,
+                               wallet_handle,
+                               address.as_ptr(),
+                               message.as_ptr() as *const u8,
+                               message.len() as u32,
+                               cb)
+    };
+
+    super::results::result_to_vec_u8(err, receiver)
+}
+
+pub fn verify_with_address(address: &str, message: &[u8], signature: &[u8]) -> Result<bool, ErrorCode> {
+    let (receiver, command_handle, cb) = super::callbacks::_closure_to_cb_ec_bool();
+
+    let address = CString::new(address).unwrap();
+
+    let err = unsafe {
+        indy_verify_with_address(command_handle,
+                                 address.as_ptr(),
+                                 message.as_ptr() as *const u8,
+                                 message.len() as u32,
+                                 signature.as_ptr() as *const u8,
+                                 signature.len() as u32,
+                                 cb)
+    };
+
+    super::results::result_to_bool(err, receiver)
+}
+
 extern {
     #[no_mangle]
     fn indy_create_payment_address(command_handle: i32,
@@ -326,4 +424,59 @@ extern {
                                         cb: Option<extern fn(command_handle_: i32,
                                                              err: ErrorCode,
                                                              fees_json: *const c_char)>) -> ErrorCode;
+
+    #[no_mangle]
+    fn indy_build_verify_payment_req(command_handle: i32,
+                                     wallet_handle: i32,
+                                     submitter_did: *const c_char,
+                                     receipt: *const c_char,
+                                     cb: Option<extern fn(command_handle_: i32,
+                                                          err: ErrorCode,
+                                                          verify_txn_json: *const c_char,
+                                                          payment_method: *const c_char)>) -> ErrorCode;
+
+    #[no_mangle]
+    fn indy_parse_verify_payment_response(command_handle: i32,
+                                          payment_method: *const c_char,
+                                          resp_json: *const c_char,
+                                          cb: Option<extern fn(command_handle_: i32,
+                                                               err: ErrorCode,
+                                                               txn_json: *const c_char)>) -> ErrorCode;
+
+    #[no_mangle]
+    fn indy_prepare_payment(command_handle: i32,
+                            wallet_handle: i32,
+                            pool_handle: i32,
+                            submitter_did: *const c_char,
+                            payment_method: *const c_char,
+                            amount: u64,
+                            fees_json: *const c_char,
+                            change_address: *const c_char,
+                            config_json: *const c_char,
+                            cb: Option<extern fn(command_handle_: i32,
+                                                 err: ErrorCode,
+                                                 inputs_json: *const c_char,
+                                                 outputs_json: *const c_char)>) -> ErrorCode;
+
+    #[no_mangle]
+    fn indy_sign_with_address(command_handle: i32,
+                              wallet_handle: i32,
+                              address: *const c_char,
+                              message_raw: *const u8,
+                              message_len: u32,
+                              cb: Option<extern fn(command_handle_: i32,
+                                                   err: ErrorCode,
+                                                   signature_raw: *const u8,
+                                                   signature_len: u32)>) -> ErrorCode;
+
+    #[no_mangle]
+    fn indy_verify_with_address(command_handle: i32,
+                                address: *const c_char,
+                                message_raw: *const u8,
+                                message_len: u32,
+                                signature_raw: *const u8,
+                                signature_len: u32,
+                                cb: Option<extern fn(command_handle_: i32,
+                                                     err: ErrorCode,
+                                                     valid: bool)>) -> ErrorCode;
 }
@@ -1,4 +1,5 @@
-use utils::types::{UTXOInfo, UTXOOutput};
+use utils::types::{PaymentInfo, UTXOInfo, UTXOOutput};
+use nullpay::ErrorCode;
 use utils::payments;
 
 use serde_json;
@@ -11,7 +12,7 @@ pub fn create_addresses(cfgs: Vec<&str>, wallet_handle: i32, payment_method: &st
     }).collect()
 }
 
-pub fn mint_tokens(addresses: Vec<(String, i32, Option<&str>)>, wallet_handle: i32, pool_handle: i32, submitter_did: &str){
+pub fn mint_tokens(addresses: Vec<(String, u64, Option<&str>)>, wallet_handle: i32, pool_handle: i32, submitter_did: &str){
     let mint: Vec<UTXOOutput> = addresses.into_iter().map(|(payment_address, amount, extra)| {
         UTXOOutput {
             payment_address,
@@ -41,9 +42,16 @@ pub fn set_request_fees(wallet_handle: i32, pool_handle: i32, submitter_did: &st
     ledger::submit_request(pool_handle, req.as_str()).unwrap();
 }
 
-pub fn get_request_fees(wallet_handle: i32, pool_handle: i32, submitter_did: &str, payment_method: &str) -> HashMap<String, i32> {
+pub fn get_request_fees(wallet_handle: i32, pool_handle: i32, submitter_did: &str, payment_method: &str) -> HashMap<String, serde_json::Value> {
     let req = payments::build_get_txn_fees_req(wallet_handle, submitter_did, payment_method).unwrap();
     let resp = ledger::submit_request(pool_handle, req.as_str()).unwrap();
     let resp = payments::parse_get_utxo_response(payment_method, resp.as_str()).unwrap();
-    serde_json::from_str::<HashMap<String, i32>>(resp.as_str()).unwrap()
-}
\ No newline at end of file
+    serde_json::from_str::<HashMap<String, serde_json::Value>>(resp.as_str()).unwrap()
+}
+
+pub fn verify_payment(wallet_handle: i32, pool_handle: i32, submitter_did: &str, receipt: &str) -> Result<PaymentInfo, ErrorCode> {
+    let (req, payment_method) = payments::build_verify_payment_req(wallet_handle, submitter_did, receipt)?;
+    let resp = ledger::submit_request(pool_handle, req.as_str()).unwrap();
+    let resp = payments::parse_verify_payment_response(payment_method.as_str(), resp.as_str())?;
+    Ok(serde_json::from_str::<PaymentInfo>(resp.as_str()).unwrap())
+}
@@ -1,5 +1,9 @@
 use nullpay;
+use nullpay::ErrorCode;
 
+use std::ffi::CString;
+use std::fs;
+use std::ptr::null;
 use std::sync::{Once, ONCE_INIT};
 
 lazy_static! {
@@ -10,4 +14,37 @@ pub fn init_plugin() {
     CREATE_PAYMENT_METHOD_INIT.call_once(|| {
         nullpay::nullpay_init();
     });
-}
\ No newline at end of file
+}
+
+pub fn init_plugin_with_config(config: &str) -> Result<(), ErrorCode> {
+    let config = CString::new(config).unwrap();
+
+    match nullpay::nullpay_init_with_config(config.as_ptr()) {
+        ErrorCode::Success => Ok(()),
+        err => Err(err)
+    }
+}
+
+pub fn reset(ledger_name: Option<&str>) -> Result<(), ErrorCode> {
+    let ledger_name = ledger_name.map(|s| CString::new(s).unwrap());
+
+    match nullpay::nullpay_reset(ledger_name.as_ref().map(|s| s.as_ptr()).unwrap_or(null())) {
+        ErrorCode::Success => Ok(()),
+        err => Err(err)
+    }
+}
+
+pub fn set_did_role(did: &str, role: Option<&str>) -> Result<(), ErrorCode> {
+    let did = CString::new(did).unwrap();
+    let role = role.map(|s| CString::new(s).unwrap());
+
+    match nullpay::nullpay_set_did_role(did.as_ptr(), role.as_ref().map(|s| s.as_ptr()).unwrap_or(null())) {
+        ErrorCode::Success => Ok(()),
+        err => Err(err)
+    }
+}
+
+pub fn storage_path(file_name: &str) -> String {
+    fs::create_dir_all(super::environment::tmp_path()).unwrap();
+    super::environment::tmp_file_path(file_name).to_str().unwrap().to_string()
+}
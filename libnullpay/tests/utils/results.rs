@@ -56,4 +56,32 @@ pub fn result_to_string_string(err: ErrorCode, receiver: Receiver<(ErrorCode, St
     }
 
     Ok((val, val2))
+}
+
+pub fn result_to_vec_u8(err: ErrorCode, receiver: Receiver<(ErrorCode, Vec<u8>)>) -> Result<Vec<u8>, ErrorCode> {
+    if err != ErrorCode::Success {
+        return Err(err);
+    }
+
+    let (err, val) = receiver.recv().unwrap();
+
+    if err != ErrorCode::Success {
+        return Err(err);
+    }
+
+    Ok(val)
+}
+
+pub fn result_to_bool(err: ErrorCode, receiver: Receiver<(ErrorCode, bool)>) -> Result<bool, ErrorCode> {
+    if err != ErrorCode::Success {
+        return Err(err);
+    }
+
+    let (err, val) = receiver.recv().unwrap();
+
+    if err != ErrorCode::Success {
+        return Err(err);
+    }
+
+    Ok(val)
 }
\ No newline at end of file
@@ -2,7 +2,7 @@
 pub struct UTXOOutput {
     #[serde(rename = "paymentAddress")]
     pub payment_address: String,
-    pub amount: i32,
+    pub amount: u64,
     pub extra: Option<String>
 }
 
@@ -19,6 +19,21 @@ impl Clone for UTXOOutput {
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct UTXOInfo {
     pub input: String,
-    pub amount: i32,
+    pub amount: u64,
     pub extra: Option<String>
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ReceiptInfo {
+    pub receipt: String,
+    pub recipient: String,
+    pub amount: u64,
+    pub extra: Option<String>
+}
+
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct PaymentInfo {
+    pub sources: Vec<String>,
+    pub receipts: Vec<ReceiptInfo>,
+    pub extra: Option<String>
+}
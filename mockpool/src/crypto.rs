@@ -0,0 +1,52 @@
+use libc::c_int;
+use rust_base58::ToBase58;
+use sodiumoxide::crypto::sign;
+use zmq;
+
+extern {
+    // TODO: fix hack:
+    // this functions isn't included to sodiumoxide rust wrappers,
+    // temporary local binding is used to call libsodium-sys function
+    fn crypto_sign_ed25519_pk_to_curve25519(
+        curve25519_pk: *mut [u8; 32],
+        ed25519_pk: *const [u8; 32]) -> c_int;
+    fn crypto_sign_ed25519_sk_to_curve25519(
+        curve25519_sk: *mut [u8; 32],
+        ed25519_sk: *const [u8; 64]) -> c_int;
+}
+
+/// Keys of mock node. They are derived from the seed made of node alias padded with zeros
+/// on the left ("000000000000000000000000000Node1"), the same way keys of the nodes
+/// from test genesis transactions of Indy SDK were generated.
+pub struct NodeKeys {
+    /// Base58 ed25519 verkey of the node, `dest` of its NODE transaction.
+    pub verkey: String,
+    /// DID built from the verkey, the node uses it as submitter of its NODE transaction.
+    pub did: String,
+    /// Z85 curve25519 keys of the node for CurveZMQ.
+    pub public_key: String,
+    pub secret_key: String,
+}
+
+impl NodeKeys {
+    pub fn from_alias(alias: &str) -> NodeKeys {
+        let seed = format!("{:0>32}", alias);
+        let seed = sign::Seed::from_slice(seed.as_bytes())
+            .expect(&format!("Node alias {:?} is too long to build seed of node keys from", alias));
+        let (vk, sk) = sign::keypair_from_seed(&seed);
+
+        let mut public_key: [u8; 32] = [0; 32];
+        let mut secret_key: [u8; 32] = [0; 32];
+        unsafe {
+            crypto_sign_ed25519_pk_to_curve25519(&mut public_key, &vk.0);
+            crypto_sign_ed25519_sk_to_curve25519(&mut secret_key, &sk.0);
+        }
+
+        NodeKeys {
+            verkey: (&vk.0 as &[u8]).to_base58(),
+            did: (&vk.0[0..16]).to_base58(),
+            public_key: zmq::z85_encode(&public_key).expect("Can't encode node public key as z85"),
+            secret_key: zmq::z85_encode(&secret_key).expect("Can't encode node secret key as z85"),
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use serde_json::Value;
+
+use std::cmp;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const NYM: &'static str = "1";
+pub const ATTRIB: &'static str = "100";
+pub const SCHEMA: &'static str = "101";
+pub const CRED_DEF: &'static str = "102";
+pub const GET_ATTR: &'static str = "104";
+pub const GET_NYM: &'static str = "105";
+pub const GET_SCHEMA: &'static str = "107";
+pub const GET_CRED_DEF: &'static str = "108";
+
+pub const WRITE_TXN_TYPES: [&'static str; 4] = [NYM, ATTRIB, SCHEMA, CRED_DEF];
+pub const READ_TXN_TYPES: [&'static str; 4] = [GET_NYM, GET_ATTR, GET_SCHEMA, GET_CRED_DEF];
+
+struct DomainTxn {
+    seq_no: usize,
+    txn_time: u64,
+    identifier: String,
+    operation: Value,
+}
+
+impl DomainTxn {
+    fn txn_type(&self) -> &str {
+        self.operation["type"].as_str().unwrap_or("")
+    }
+}
+
+/// In-memory domain ledger shared by all mock nodes. It stands for the state the pool
+/// reached consensus on, so every write is ordered once no matter how many nodes received it.
+pub struct DomainLedger {
+    txns: Vec<DomainTxn>,
+    results: HashMap<(String, u64), Result<usize, String>>,
+}
+
+impl DomainLedger {
+    pub fn new() -> DomainLedger {
+        DomainLedger {
+            txns: Vec::new(),
+            results: HashMap::new(),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.txns.len()
+    }
+
+    /// Adds NYM the same way domain genesis transactions do, without any submitter.
+    pub fn add_nym(&mut self, did: &str, verkey: Option<&str>, role: Option<&str>) {
+        let operation = json!({"type": NYM, "dest": did, "verkey": verkey, "role": role});
+        self.append(did, operation);
+    }
+
+    /// Orders write request and returns `result` field of its REPLY or reason of REJECT.
+    /// Repeated requests with the same identifier and reqId get the result of the first one.
+    pub fn write(&mut self, request: &Value) -> Result<Value, String> {
+        let identifier = request["identifier"].as_str().unwrap_or("").to_string();
+        let req_id = request["reqId"].as_u64().unwrap_or(0);
+
+        let key = (identifier.clone(), req_id);
+        if !self.results.contains_key(&key) {
+            let res = self.validate(&identifier, &request["operation"])
+                .map(|_| self.append(&identifier, request["operation"].clone()));
+            self.results.insert(key.clone(), res);
+        }
+
+        let seq_no = self.results[&key].clone()?;
+        let txn = &self.txns[seq_no - 1];
+
+        let mut result = txn.operation.clone();
+        result["identifier"] = json!(identifier);
+        result["reqId"] = json!(req_id);
+        result["signature"] = request["signature"].clone();
+        result["seqNo"] = json!(txn.seq_no);
+        result["txnTime"] = json!(txn.txn_time);
+        Ok(result)
+    }
+
+    /// Builds `result` field of REPLY for read request as if the ledger had only its first `size` transactions.
+    pub fn read(&self, request: &Value, size: usize) -> Value {
+        let operation = &request["operation"];
+        let txns = &self.txns[..cmp::min(size, self.txns.len())];
+
+        let mut result = json!({
+            "type": operation["type"],
+            "identifier": request["identifier"],
+            "reqId": request["reqId"],
+            "data": null,
+            "seqNo": null,
+            "txnTime": null
+        });
+
+        match operation["type"].as_str().unwrap_or("") {
+            GET_NYM => {
+                let dest = operation["dest"].as_str().unwrap_or("");
+                result["dest"] = json!(dest);
+
+                if let Some(mut data) = DomainLedger::nym(txns, dest) {
+                    result["seqNo"] = data["seqNo"].clone();
+                    result["txnTime"] = data["txnTime"].clone();
+                    data["dest"] = json!(dest);
+                    result["data"] = json!(data.to_string());
+                }
+            }
+            GET_ATTR => {
+                let dest = operation["dest"].as_str().unwrap_or("");
+                result["dest"] = json!(dest);
+
+                for field in ["raw", "hash", "enc"].iter() {
+                    if let Some(name) = operation[*field].as_str() {
+                        result[*field] = json!(name);
+
+                        let found = txns.iter().rev()
+                            .find(|txn| txn.txn_type() == ATTRIB && txn.operation["dest"] == dest &&
+                                DomainLedger::attrib_name(&txn.operation).map(|(f, n)| f == *field && n == name).unwrap_or(false));
+                        if let Some(txn) = found {
+                            result["data"] = txn.operation[*field].clone();
+                            result["seqNo"] = json!(txn.seq_no);
+                            result["txnTime"] = json!(txn.txn_time);
+                        }
+                    }
+                }
+            }
+            GET_SCHEMA => {
+                let dest = operation["dest"].as_str().unwrap_or("");
+                let (name, version) = (&operation["data"]["name"], &operation["data"]["version"]);
+                result["dest"] = json!(dest);
+                result["data"] = json!({"name": name, "version": version});
+
+                let found = txns.iter()
+                    .find(|txn| txn.txn_type() == SCHEMA && txn.identifier == dest &&
+                        txn.operation["data"]["name"] == *name && txn.operation["data"]["version"] == *version);
+                if let Some(txn) = found {
+                    result["data"] = txn.operation["data"].clone();
+                    result["seqNo"] = json!(txn.seq_no);
+                    result["txnTime"] = json!(txn.txn_time);
+                }
+            }
+            GET_CRED_DEF => {
+                result["ref"] = operation["ref"].clone();
+                result["signature_type"] = operation["signature_type"].clone();
+                result["origin"] = operation["origin"].clone();
+
+                let found = txns.iter()
+                    .find(|txn| txn.txn_type() == CRED_DEF && json!(txn.identifier) == operation["origin"] &&
+                        txn.operation["ref"] == operation["ref"] && txn.operation["signature_type"] == operation["signature_type"]);
+                if let Some(txn) = found {
+                    result["data"] = txn.operation["data"].clone();
+                    result["seqNo"] = json!(txn.seq_no);
+                    result["txnTime"] = json!(txn.txn_time);
+                }
+            }
+            _ => {}
+        }
+
+        result
+    }
+
+    fn validate(&self, identifier: &str, operation: &Value) -> Result<(), String> {
+        if DomainLedger::nym(&self.txns, identifier).is_none() {
+            return Err(format!("client request invalid: could not authenticate, verkey for {} cannot be found", identifier));
+        }
+
+        match operation["type"].as_str().unwrap_or("") {
+            NYM => {
+                if !operation["dest"].is_string() {
+                    return Err("client request invalid: missed dest".to_string());
+                }
+            }
+            ATTRIB => {
+                let dest = operation["dest"].as_str().unwrap_or("");
+                if DomainLedger::nym(&self.txns, dest).is_none() {
+                    return Err(format!("client request invalid: dest {} is not found on the ledger", dest));
+                }
+                if DomainLedger::attrib_name(operation).is_none() {
+                    return Err("client request invalid: attribute must contain exactly one of raw, hash, enc; raw must be an object with exactly one attribute".to_string());
+                }
+            }
+            SCHEMA => {
+                let data = &operation["data"];
+                let exists = self.txns.iter()
+                    .any(|txn| txn.txn_type() == SCHEMA && txn.identifier == identifier &&
+                        txn.operation["data"]["name"] == data["name"] && txn.operation["data"]["version"] == data["version"]);
+                if exists {
+                    return Err(format!("client request invalid: schema {} {} already exists", data["name"], data["version"]));
+                }
+            }
+            CRED_DEF => {
+                let schema_exists = operation["ref"].as_u64()
+                    .and_then(|seq_no| self.txns.get((seq_no as usize).wrapping_sub(1)))
+                    .map(|txn| txn.txn_type() == SCHEMA)
+                    .unwrap_or(false);
+                if !schema_exists {
+                    return Err(format!("client request invalid: schema with seqNo {} is not found", operation["ref"]));
+                }
+
+                let exists = self.txns.iter()
+                    .any(|txn| txn.txn_type() == CRED_DEF && txn.identifier == identifier &&
+                        txn.operation["ref"] == operation["ref"] && txn.operation["signature_type"] == operation["signature_type"]);
+                if exists {
+                    return Err(format!("client request invalid: claim definition for schema {} already exists", operation["ref"]));
+                }
+            }
+            xtype => return Err(format!("client request invalid: unsupported transaction type {}", xtype))
+        }
+        Ok(())
+    }
+
+    fn append(&mut self, identifier: &str, operation: Value) -> usize {
+        let seq_no = self.txns.len() + 1;
+        self.txns.push(DomainTxn {
+            seq_no,
+            txn_time: SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or(0),
+            identifier: identifier.to_string(),
+            operation,
+        });
+        seq_no
+    }
+
+    /// Current state of NYM as a result of all NYM transactions for `dest`.
+    fn nym(txns: &[DomainTxn], dest: &str) -> Option<Value> {
+        txns.iter()
+            .filter(|txn| txn.txn_type() == NYM && txn.operation["dest"] == dest)
+            .fold(None, |nym, txn| {
+                let mut nym = nym.unwrap_or(json!({"identifier": txn.identifier, "role": null, "verkey": null}));
+                if !txn.operation["verkey"].is_null() {
+                    nym["verkey"] = txn.operation["verkey"].clone();
+                }
+                if let Some(role) = txn.operation.get("role") {
+                    nym["role"] = if role == "" { Value::Null } else { role.clone() };
+                }
+                nym["seqNo"] = json!(txn.seq_no);
+                nym["txnTime"] = json!(txn.txn_time);
+                Some(nym)
+            })
+    }
+
+    /// Field of ATTRIB operation with the attribute and the name attribute is looked up by.
+    fn attrib_name(operation: &Value) -> Option<(&'static str, String)> {
+        match (operation["raw"].as_str(), operation["hash"].as_str(), operation["enc"].as_str()) {
+            (Some(raw), None, None) => {
+                ::serde_json::from_str::<Value>(raw).ok()
+                    .and_then(|raw| raw.as_object()
+                        .and_then(|raw| if raw.len() == 1 { raw.keys().next().cloned() } else { None }))
+                    .map(|name| ("raw", name))
+            }
+            (None, Some(hash), None) => Some(("hash", hash.to_string())),
+            (None, None, Some(enc)) => Some(("enc", enc.to_string())),
+            _ => None
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! In-process mock of Indy validator pool for hermetic tests of Indy SDK.
+//!
+//! Mock nodes are CurveZMQ servers speaking the client part of the node protocol:
+//! they answer pings, LEDGER_STATUS and CATCHUP_REQ messages for pool ledger built from genesis
+//! transactions and REQACK/REPLY/REJECT/REQNACK to NYM, ATTRIB, SCHEMA, CRED_DEF requests and their GET_*
+//! counterparts served from in-memory domain ledger. Signatures of requests aren't verified, but submitters
+//! must have NYM on the ledger (see `MockPool::add_nym`). Nodes have no BLS keys, so their replies
+//! carry no state proofs and the client relies on f + 1 equal replies.
+//!
+//! Every node can be switched to misbehave on demand with `MockPool::set_behaviour`.
+
+extern crate base64;
+extern crate digest;
+extern crate libc;
+#[macro_use]
+extern crate log;
+extern crate rmp_serde;
+extern crate rust_base58;
+#[macro_use]
+extern crate serde_json;
+extern crate sha2;
+extern crate sodiumoxide;
+extern crate zmq;
+
+mod crypto;
+mod domain_ledger;
+mod merkle_tree;
+mod node;
+mod pool_ledger;
+
+pub use node::NodeBehaviour;
+
+use serde_json::Value;
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use crypto::NodeKeys;
+use domain_ledger::DomainLedger;
+use node::Node;
+use pool_ledger::PoolLedger;
+
+/// Running mock pool. Nodes are stopped when it is dropped.
+pub struct MockPool {
+    genesis_txns: Vec<String>,
+    behaviours: Vec<Arc<Mutex<NodeBehaviour>>>,
+    domain_ledger: Arc<Mutex<DomainLedger>>,
+    stop: Arc<AtomicBool>,
+    nodes: Vec<thread::JoinHandle<()>>,
+    _ctx: zmq::Context,
+}
+
+impl MockPool {
+    /// Starts `nodes_count` nodes named Node1, Node2, ... on free ports of 127.0.0.1.
+    /// Genesis transactions for the pool are returned by `genesis_txns`.
+    pub fn start(nodes_count: usize) -> MockPool {
+        let ctx = zmq::Context::new();
+        let mut genesis_txns = Vec::new();
+        let mut sockets = Vec::new();
+
+        for i in 1..nodes_count + 1 {
+            let alias = format!("Node{}", i);
+            let keys = NodeKeys::from_alias(&alias);
+            let (socket, port) = Node::bind(&ctx, &keys, "127.0.0.1", None);
+
+            genesis_txns.push(json!({
+                "data": {
+                    "alias": alias,
+                    "client_ip": "127.0.0.1",
+                    "client_port": port,
+                    "services": ["VALIDATOR"]
+                },
+                "dest": keys.verkey,
+                "identifier": keys.did,
+                "type": "0"
+            }).to_string());
+            sockets.push((alias, socket));
+        }
+
+        MockPool::_start(ctx, genesis_txns, sockets)
+    }
+
+    /// Starts validator nodes of genesis transactions file on their client addresses.
+    /// Keys of every node must be derived from its alias as keys of nodes
+    /// of Indy SDK test pool are ("000000000000000000000000000Node1" seed for Node1).
+    pub fn start_from_genesis(genesis_txn_file: &Path) -> MockPool {
+        let file = File::open(genesis_txn_file)
+            .expect(&format!("Can't open genesis transactions file {:?}", genesis_txn_file));
+
+        let genesis_txns: Vec<String> = BufReader::new(file).lines()
+            .map(|line| line.expect("Can't read genesis transactions file").trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        // NODE transactions can update the node added by previous ones
+        let mut nodes: Vec<(String, Value)> = Vec::new();
+        for genesis_txn in &genesis_txns {
+            let txn: Value = ::serde_json::from_str(genesis_txn).expect("Invalid genesis transaction");
+            let txn = if txn["txn"].is_object() {
+                json!({"type": txn["txn"]["type"], "dest": txn["txn"]["data"]["dest"], "data": txn["txn"]["data"]["data"]})
+            } else {
+                txn
+            };
+
+            if txn["type"] != "0" {
+                continue;
+            }
+
+            let dest = txn["dest"].as_str().expect("Genesis transaction without dest").to_string();
+            let idx = match nodes.iter().position(|&(ref node_dest, _)| *node_dest == dest) {
+                Some(idx) => idx,
+                None => {
+                    nodes.push((dest, json!({})));
+                    nodes.len() - 1
+                }
+            };
+            if let Some(data) = txn["data"].as_object() {
+                for (key, value) in data {
+                    nodes[idx].1[key] = value.clone();
+                }
+            }
+        }
+
+        let ctx = zmq::Context::new();
+        let mut sockets = Vec::new();
+
+        for (dest, data) in nodes {
+            let is_validator = data["services"].as_array()
+                .map(|services| services.iter().any(|service| service == "VALIDATOR"))
+                .unwrap_or(false);
+            if !is_validator {
+                continue;
+            }
+
+            let alias = data["alias"].as_str().expect("Node transaction without alias").to_string();
+            let keys = NodeKeys::from_alias(&alias);
+            assert_eq!(dest, keys.verkey, "Keys of node {} aren't derived from its alias", alias);
+
+            let (socket, _) = Node::bind(&ctx, &keys,
+                                         data["client_ip"].as_str().expect("Node transaction without client_ip"),
+                                         Some(data["client_port"].as_u64().expect("Node transaction without client_port")));
+            sockets.push((alias, socket));
+        }
+
+        MockPool::_start(ctx, genesis_txns, sockets)
+    }
+
+    fn _start(ctx: zmq::Context, genesis_txns: Vec<String>, sockets: Vec<(String, zmq::Socket)>) -> MockPool {
+        let pool_ledger = Arc::new(PoolLedger::new(
+            genesis_txns.iter()
+                .map(|txn| ::serde_json::from_str(txn).expect("Invalid genesis transaction"))
+                .collect()));
+        let domain_ledger = Arc::new(Mutex::new(DomainLedger::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let mut behaviours = Vec::new();
+        let mut nodes = Vec::new();
+
+        for (alias, socket) in sockets {
+            let behaviour = Arc::new(Mutex::new(NodeBehaviour::Honest));
+            let node = Node::new(alias.clone(), socket, behaviour.clone(), pool_ledger.clone(), domain_ledger.clone(), stop.clone());
+
+            nodes.push(thread::Builder::new()
+                .name(alias)
+                .spawn(move || node.run())
+                .expect("Can't start node thread"));
+            behaviours.push(behaviour);
+        }
+
+        MockPool {
+            genesis_txns,
+            behaviours,
+            domain_ledger,
+            stop,
+            nodes,
+            _ctx: ctx,
+        }
+    }
+
+    /// Genesis transactions of the pool, one JSON per item.
+    pub fn genesis_txns(&self) -> &[String] {
+        &self.genesis_txns
+    }
+
+    pub fn nodes_count(&self) -> usize {
+        self.behaviours.len()
+    }
+
+    /// Changes the way node with index `node_idx` (in genesis transactions order) answers to the client.
+    pub fn set_behaviour(&self, node_idx: usize, behaviour: NodeBehaviour) {
+        *self.behaviours[node_idx].lock().unwrap() = behaviour;
+    }
+
+    /// Adds NYM to the domain ledger as domain genesis transactions do, e.g. for trustee
+    /// submitting the following requests.
+    pub fn add_nym(&self, did: &str, verkey: Option<&str>, role: Option<&str>) {
+        self.domain_ledger.lock().unwrap().add_nym(did, verkey, role);
+    }
+
+    /// Count of transactions in the domain ledger, can be used to make node `NodeBehaviour::Stale`
+    /// at the current state of the ledger.
+    pub fn domain_ledger_size(&self) -> usize {
+        self.domain_ledger.lock().unwrap().size()
+    }
+}
+
+impl Drop for MockPool {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        for node in self.nodes.drain(..) {
+            if node.join().is_err() {
+                warn!("Mock node thread panicked");
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use digest::{FixedOutput, Input};
+use sha2::Sha256;
+
+/// Append-only Merkle tree with the same hashing as libindy uses for pool ledger
+/// (RFC 6962: leaves are prefixed with 0x00 and inner nodes with 0x01 before hashing).
+pub struct MerkleTree {
+    leaves: Vec<Vec<u8>>
+}
+
+impl MerkleTree {
+    pub fn new() -> MerkleTree {
+        MerkleTree {
+            leaves: Vec::new()
+        }
+    }
+
+    pub fn append(&mut self, data: &[u8]) {
+        self.leaves.push(MerkleTree::hash_leaf(data));
+    }
+
+    /// Root hash of the tree built from the first `size` leaves.
+    pub fn root_hash(&self, size: usize) -> Vec<u8> {
+        MerkleTree::subtree_hash(&self.leaves[..size])
+    }
+
+    /// Hashes proving that the tree of `old_size` leaves is a prefix of the tree of `new_size` leaves,
+    /// in the order expected by `MerkleTree::consistency_proof` of libindy.
+    pub fn consistency_proof(&self, old_size: usize, new_size: usize) -> Vec<Vec<u8>> {
+        let mut proof = Vec::new();
+        if old_size > 0 && old_size < new_size {
+            MerkleTree::subproof(old_size, &self.leaves[..new_size], true, &mut proof);
+        }
+        proof
+    }
+
+    fn subproof(old_size: usize, leaves: &[Vec<u8>], complete_subtree: bool, proof: &mut Vec<Vec<u8>>) {
+        if old_size == leaves.len() {
+            if !complete_subtree {
+                proof.push(MerkleTree::subtree_hash(leaves));
+            }
+            return;
+        }
+
+        let split = MerkleTree::split(leaves.len());
+        if old_size <= split {
+            MerkleTree::subproof(old_size, &leaves[..split], complete_subtree, proof);
+            proof.push(MerkleTree::subtree_hash(&leaves[split..]));
+        } else {
+            MerkleTree::subproof(old_size - split, &leaves[split..], false, proof);
+            proof.push(MerkleTree::subtree_hash(&leaves[..split]));
+        }
+    }
+
+    fn subtree_hash(leaves: &[Vec<u8>]) -> Vec<u8> {
+        match leaves.len() {
+            0 => Sha256::default().fixed_result().to_vec(),
+            1 => leaves[0].clone(),
+            count => {
+                let split = MerkleTree::split(count);
+                MerkleTree::hash_nodes(&MerkleTree::subtree_hash(&leaves[..split]),
+                                       &MerkleTree::subtree_hash(&leaves[split..]))
+            }
+        }
+    }
+
+    /// The largest power of two smaller than `count`.
+    fn split(count: usize) -> usize {
+        let mut split = 1;
+        while split << 1 < count {
+            split <<= 1;
+        }
+        split
+    }
+
+    fn hash_leaf(data: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha256::default();
+        hasher.process(&[0x00]);
+        hasher.process(data);
+        hasher.fixed_result().to_vec()
+    }
+
+    fn hash_nodes(left: &[u8], right: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha256::default();
+        hasher.process(&[0x01]);
+        hasher.process(left);
+        hasher.process(right);
+        hasher.fixed_result().to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALUES: [&'static str; 4] = [
+        "{\"data\":{\"alias\":\"Node1\",\"client_ip\":\"192.168.1.35\",\"client_port\":9702,\"node_ip\":\"192.168.1.35\",\"node_port\":9701,\"services\":[\"VALIDATOR\"]},\"dest\":\"Gw6pDLhcBcoQesN72qfotTgFa7cbuqZpkX3Xo6pLhPhv\",\"identifier\":\"FYmoFw55GeQH7SRFa37dkx1d2dZ3zUF8ckg7wmL7ofN4\",\"txnId\":\"fea82e10e894419fe2bea7d96296a6d46f50f93f9eeda954ec461b2ed2950b62\",\"type\":\"0\"}",
+        "{\"data\":{\"alias\":\"Node2\",\"client_ip\":\"192.168.1.35\",\"client_port\":9704,\"node_ip\":\"192.168.1.35\",\"node_port\":9703,\"services\":[\"VALIDATOR\"]},\"dest\":\"8ECVSk179mjsjKRLWiQtssMLgp6EPhWXtaYyStWPSGAb\",\"identifier\":\"8QhFxKxyaFsJy4CyxeYX34dFH8oWqyBv1P4HLQCsoeLy\",\"txnId\":\"1ac8aece2a18ced660fef8694b61aac3af08ba875ce3026a160acbc3a3af35fc\",\"type\":\"0\"}",
+        "{\"data\":{\"alias\":\"Node3\",\"client_ip\":\"192.168.1.35\",\"client_port\":9706,\"node_ip\":\"192.168.1.35\",\"node_port\":9705,\"services\":[\"VALIDATOR\"]},\"dest\":\"DKVxG2fXXTU8yT5N7hGEbXB3dfdAnYv1JczDUHpmDxya\",\"identifier\":\"2yAeV5ftuasWNgQwVYzeHeTuM7LwwNtPR3Zg9N4JiDgF\",\"txnId\":\"7e9f355dffa78ed24668f0e0e369fd8c224076571c51e2ea8be5f26479edebe4\",\"type\":\"0\"}",
+        "{\"data\":{\"alias\":\"Node4\",\"client_ip\":\"192.168.1.35\",\"client_port\":9708,\"node_ip\":\"192.168.1.35\",\"node_port\":9707,\"services\":[\"VALIDATOR\"]},\"dest\":\"4PS3EDQ3dW1tci1Bp6543CfuuebjFrg36kLAUcskGfaA\",\"identifier\":\"FTE95CVthRtrBnK2PYCBbC9LghTcGwi9Zfi1Gz2dnyNx\",\"txnId\":\"aa5e817d7cc626170eca175822029339a444eb0ee8f0bd20d3b0b76e566fb008\",\"type\":\"0\"}"];
+
+    fn _tree(count: usize) -> MerkleTree {
+        let mut tree = MerkleTree::new();
+        for i in 0..count {
+            tree.append(format!("{}", i).as_bytes());
+        }
+        tree
+    }
+
+    // Port of the verification performed by libindy on CONSISTENCY_PROOF and CATCHUP_REP messages
+    fn _verify(old_root: &[u8], old_size: usize, new_root: &[u8], new_size: usize, proof: &[Vec<u8>]) -> bool {
+        let node = MerkleTree::hash_nodes;
+
+        let mut old_node = old_size - 1;
+        let mut new_node = new_size - 1;
+        while old_node % 2 != 0 {
+            old_node /= 2;
+            new_node /= 2;
+        }
+
+        let mut proof = proof.iter();
+        let (mut old_hash, mut new_hash) = if old_node != 0 {
+            let hash = proof.next().unwrap().clone();
+            (hash.clone(), hash)
+        } else {
+            (old_root.to_vec(), old_root.to_vec())
+        };
+
+        while old_node != 0 {
+            if old_node % 2 != 0 {
+                let next = proof.next().unwrap();
+                old_hash = node(next, &old_hash);
+                new_hash = node(next, &new_hash);
+            } else if old_node < new_node {
+                new_hash = node(&new_hash, proof.next().unwrap());
+            }
+            old_node /= 2;
+            new_node /= 2;
+        }
+
+        while new_node != 0 {
+            new_hash = node(&new_hash, proof.next().unwrap());
+            new_node /= 2;
+        }
+
+        proof.next().is_none() && old_hash == old_root && new_hash == new_root
+    }
+
+    #[test]
+    fn root_hash_works() {
+        let mut tree = MerkleTree::new();
+        for value in VALUES.iter() {
+            tree.append(value.as_bytes());
+        }
+
+        let root_hash = tree.root_hash(4).iter().map(|b| format!("{:02x}", b)).collect::<String>();
+        assert_eq!("1285070cf01debc1155cef8dfd5ba54c05abb919a4c08c8632b079fb1e1e5e7c", root_hash);
+    }
+
+    #[test]
+    fn consistency_proof_works() {
+        let tree = _tree(11);
+
+        for new_size in 1..12 {
+            for old_size in 1..new_size {
+                let proof = tree.consistency_proof(old_size, new_size);
+                assert!(_verify(&tree.root_hash(old_size), old_size, &tree.root_hash(new_size), new_size, &proof),
+                        "old_size: {}, new_size: {}", old_size, new_size);
+            }
+        }
+    }
+
+    #[test]
+    fn consistency_proof_works_for_same_size() {
+        let tree = _tree(5);
+        assert!(tree.consistency_proof(5, 5).is_empty());
+    }
+}
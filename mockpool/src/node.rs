@@ -0,0 +1,257 @@
+use base64;
+use rust_base58::ToBase58;
+use serde_json;
+use serde_json::Value;
+use zmq;
+
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crypto::NodeKeys;
+use domain_ledger::{DomainLedger, READ_TXN_TYPES, WRITE_TXN_TYPES};
+use pool_ledger::{PoolLedger, POOL_LEDGER_ID};
+
+const POLL_TIMEOUT: i64 = 100; /* in ms */
+
+/// How mock node answers to the client.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NodeBehaviour {
+    /// Follows the protocol.
+    Honest,
+    /// Drops all incoming messages including pings, so the client runs into its timeouts.
+    Silent,
+    /// Answers REQNACK to all ledger requests.
+    Nack,
+    /// Answers read requests as if the domain ledger contained only its first `n` transactions.
+    Stale(usize),
+    /// Answers read requests with empty result backed by forged state proof.
+    BadStateProof,
+}
+
+pub struct Node {
+    alias: String,
+    socket: zmq::Socket,
+    behaviour: Arc<Mutex<NodeBehaviour>>,
+    pool_ledger: Arc<PoolLedger>,
+    domain_ledger: Arc<Mutex<DomainLedger>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Node {
+    /// Binds CurveZMQ server socket for clients of the node and returns it with the port it is bound to.
+    pub fn bind(ctx: &zmq::Context, keys: &NodeKeys, ip: &str, port: Option<u64>) -> (zmq::Socket, u64) {
+        let socket = ctx.socket(zmq::SocketType::ROUTER).expect("Can't create node socket");
+        socket.set_curve_publickey(&keys.public_key).expect("Can't set node public key");
+        socket.set_curve_secretkey(&keys.secret_key).expect("Can't set node secret key");
+        socket.set_curve_server(true).expect("Can't set node socket as curve server");
+        socket.set_linger(0).expect("Can't set linger for node socket");
+
+        let addr = format!("tcp://{}:{}", ip, port.map(|port| port.to_string()).unwrap_or("*".to_string()));
+        socket.bind(&addr).expect(&format!("Can't bind node socket to {}", addr));
+
+        let endpoint = socket.get_last_endpoint()
+            .expect("Can't get node socket endpoint")
+            .expect("Node socket endpoint isn't utf-8 string");
+        let port = endpoint.rsplit(':').next()
+            .and_then(|port| port.parse::<u64>().ok())
+            .expect(&format!("Can't get port from node socket endpoint {}", endpoint));
+
+        (socket, port)
+    }
+
+    pub fn new(alias: String,
+               socket: zmq::Socket,
+               behaviour: Arc<Mutex<NodeBehaviour>>,
+               pool_ledger: Arc<PoolLedger>,
+               domain_ledger: Arc<Mutex<DomainLedger>>,
+               stop: Arc<AtomicBool>) -> Node {
+        Node {
+            alias,
+            socket,
+            behaviour,
+            pool_ledger,
+            domain_ledger,
+            stop,
+        }
+    }
+
+    pub fn run(self) {
+        trace!("Node {} started", self.alias);
+
+        while !self.stop.load(Ordering::SeqCst) {
+            match self.socket.poll(zmq::POLLIN, POLL_TIMEOUT) {
+                Ok(1) => {}
+                Ok(_) => continue,
+                Err(err) => {
+                    warn!("Node {} poll failed: {:?}", self.alias, err);
+                    break;
+                }
+            }
+
+            let msg = match self.socket.recv_multipart(zmq::DONTWAIT) {
+                Ok(msg) => msg,
+                Err(err) => {
+                    warn!("Node {} recv failed: {:?}", self.alias, err);
+                    continue;
+                }
+            };
+            if msg.len() != 2 {
+                warn!("Node {} received message of unexpected format {:?}", self.alias, msg);
+                continue;
+            }
+
+            let behaviour = *self.behaviour.lock().unwrap();
+            let raw_msg = String::from_utf8_lossy(&msg[1]).to_string();
+            trace!("Node {} ({:?}) received {}", self.alias, behaviour, raw_msg);
+
+            if behaviour == NodeBehaviour::Silent {
+                continue;
+            }
+
+            for resp in self.process_msg(&raw_msg, behaviour) {
+                trace!("Node {} sends {}", self.alias, resp);
+                if let Err(err) = self.socket.send_multipart(&[msg[0].as_slice(), resp.as_bytes()], zmq::DONTWAIT) {
+                    warn!("Node {} can't send message: {:?}", self.alias, err);
+                }
+            }
+        }
+
+        trace!("Node {} stopped", self.alias);
+    }
+
+    fn process_msg(&self, raw_msg: &str, behaviour: NodeBehaviour) -> Vec<String> {
+        if raw_msg == "pi" {
+            return vec!["po".to_string()];
+        }
+
+        let msg: Value = match serde_json::from_str(raw_msg) {
+            Ok(msg) => msg,
+            Err(err) => {
+                warn!("Node {} received invalid message: {:?}", self.alias, err);
+                return Vec::new();
+            }
+        };
+
+        match msg["op"].as_str() {
+            Some("LEDGER_STATUS") => self.process_ledger_status(&msg),
+            Some("CATCHUP_REQ") => self.process_catchup_req(&msg),
+            None if msg["operation"].is_object() => self.process_request(&msg, behaviour),
+            _ => {
+                warn!("Node {} received unsupported message {}", self.alias, raw_msg);
+                Vec::new()
+            }
+        }
+    }
+
+    fn process_ledger_status(&self, ledger_status: &Value) -> Vec<String> {
+        if ledger_status["ledgerId"].as_u64() != Some(POOL_LEDGER_ID) {
+            warn!("Node {} has no ledger {}", self.alias, ledger_status["ledgerId"]);
+            return Vec::new();
+        }
+
+        let size = self.pool_ledger.size();
+        let resp = match ledger_status["txnSeqNo"].as_u64().map(|seq_no| seq_no as usize) {
+            Some(client_size) if client_size < size => json!({
+                "op": "CONSISTENCY_PROOF",
+                "ledgerId": POOL_LEDGER_ID,
+                "seqNoStart": client_size,
+                "seqNoEnd": size,
+                "ppSeqNo": null,
+                "viewNo": null,
+                "oldMerkleRoot": self.pool_ledger.root_hash(client_size),
+                "newMerkleRoot": self.pool_ledger.root_hash(size),
+                "hashes": self.pool_ledger.consistency_proof(client_size, size)
+            }),
+            _ => json!({
+                "op": "LEDGER_STATUS",
+                "ledgerId": POOL_LEDGER_ID,
+                "txnSeqNo": size,
+                "merkleRoot": self.pool_ledger.root_hash(size),
+                "ppSeqNo": null,
+                "viewNo": null
+            })
+        };
+        vec![resp.to_string()]
+    }
+
+    fn process_catchup_req(&self, catchup_req: &Value) -> Vec<String> {
+        let (seq_no_start, seq_no_end, catchup_till) =
+            match (catchup_req["seqNoStart"].as_u64(), catchup_req["seqNoEnd"].as_u64(), catchup_req["catchupTill"].as_u64()) {
+                (Some(start), Some(end), Some(till))
+                if 0 < start && start <= end && end <= till && till as usize <= self.pool_ledger.size() => (start as usize, end as usize, till as usize),
+                _ => {
+                    warn!("Node {} received invalid catchup request {}", self.alias, catchup_req);
+                    return Vec::new();
+                }
+            };
+
+        let resp = json!({
+            "op": "CATCHUP_REP",
+            "ledgerId": POOL_LEDGER_ID,
+            "txns": self.pool_ledger.txns(seq_no_start, seq_no_end),
+            "consProof": self.pool_ledger.consistency_proof(seq_no_end, catchup_till)
+        });
+        vec![resp.to_string()]
+    }
+
+    fn process_request(&self, request: &Value, behaviour: NodeBehaviour) -> Vec<String> {
+        let (req_id, identifier) = match (request["reqId"].as_u64(), request["identifier"].as_str()) {
+            (Some(req_id), Some(identifier)) => (req_id, identifier),
+            _ => {
+                warn!("Node {} received request without reqId or identifier {}", self.alias, request);
+                return Vec::new();
+            }
+        };
+        let txn_type = request["operation"]["type"].as_str().unwrap_or("");
+
+        let nack = |reason: String| json!({"op": "REQNACK", "reqId": req_id, "identifier": identifier, "reason": reason}).to_string();
+
+        if behaviour == NodeBehaviour::Nack {
+            return vec![nack(format!("node {} nacks all requests", self.alias))];
+        }
+
+        let result = if WRITE_TXN_TYPES.contains(&txn_type) {
+            self.domain_ledger.lock().unwrap().write(request)
+        } else if READ_TXN_TYPES.contains(&txn_type) {
+            let domain_ledger = self.domain_ledger.lock().unwrap();
+            match behaviour {
+                NodeBehaviour::Stale(size) => Ok(domain_ledger.read(request, size)),
+                NodeBehaviour::BadStateProof => {
+                    let mut result = domain_ledger.read(request, 0);
+                    result["state_proof"] = self.forged_state_proof();
+                    Ok(result)
+                }
+                _ => Ok(domain_ledger.read(request, domain_ledger.size()))
+            }
+        } else {
+            return vec![nack(format!("client request invalid: mock node doesn't support transaction type {:?}", txn_type))];
+        };
+
+        let ack = json!({"op": "REQACK", "reqId": req_id, "identifier": identifier}).to_string();
+        let resp = match result {
+            Ok(result) => json!({"op": "REPLY", "result": result}),
+            Err(reason) => json!({"op": "REJECT", "reqId": req_id, "identifier": identifier, "reason": reason})
+        };
+        vec![ack, resp.to_string()]
+    }
+
+    /// State proof which is well-formed, but proves nothing: root hash doesn't match any proof node.
+    fn forged_state_proof(&self) -> Value {
+        let root_hash = [0x11 as u8; 32].to_vec().to_base58();
+        json!({
+            "root_hash": root_hash,
+            "proof_nodes": base64::encode(&[0xc0]), // RLP of empty list
+            "multi_signature": {
+                "signature": [0x22 as u8; 32].to_vec().to_base58(),
+                "participants": [self.alias],
+                "value": {
+                    "ledger_id": 1,
+                    "pool_state_root_hash": root_hash,
+                    "state_root_hash": root_hash,
+                    "txn_root_hash": root_hash,
+                    "timestamp": 0
+                }
+            }
+        })
+    }
+}
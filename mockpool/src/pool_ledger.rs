@@ -0,0 +1,55 @@
+use rmp_serde;
+use rust_base58::ToBase58;
+use serde_json::Value;
+use serde_json::map::Map;
+
+use merkle_tree::MerkleTree;
+
+pub const POOL_LEDGER_ID: u64 = 0;
+
+/// Pool ledger of mock nodes. It contains genesis transactions only and never grows,
+/// but clients knowing just a prefix of it are caught up to its full size.
+pub struct PoolLedger {
+    txns: Vec<Value>,
+    merkle_tree: MerkleTree,
+}
+
+impl PoolLedger {
+    pub fn new(txns: Vec<Value>) -> PoolLedger {
+        let mut merkle_tree = MerkleTree::new();
+        for txn in &txns {
+            // libindy appends pool transactions to its tree in MessagePack form
+            merkle_tree.append(&rmp_serde::to_vec_named(txn).expect("Can't serialize pool transaction"));
+        }
+
+        PoolLedger {
+            txns,
+            merkle_tree,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.txns.len()
+    }
+
+    pub fn root_hash(&self, size: usize) -> String {
+        self.merkle_tree.root_hash(size).to_base58()
+    }
+
+    pub fn consistency_proof(&self, old_size: usize, new_size: usize) -> Vec<String> {
+        self.merkle_tree.consistency_proof(old_size, new_size)
+            .iter()
+            .map(|hash| hash.to_base58())
+            .collect()
+    }
+
+    /// Transactions with sequence numbers from `seq_no_start` to `seq_no_end` inclusive
+    /// in the form of `txns` field of CATCHUP_REP message.
+    pub fn txns(&self, seq_no_start: usize, seq_no_end: usize) -> Map<String, Value> {
+        let mut txns = Map::new();
+        for seq_no in seq_no_start..seq_no_end + 1 {
+            txns.insert(seq_no.to_string(), self.txns[seq_no - 1].clone());
+        }
+        txns
+    }
+}
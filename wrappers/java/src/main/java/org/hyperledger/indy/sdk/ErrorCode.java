@@ -100,6 +100,31 @@ public enum ErrorCode {
 	 */
 	CommonInvalidParam14(116),
 
+	/**
+	 * Caller passed invalid value as param 15 (null, invalid json and etc..)
+	 */
+	CommonInvalidParam15(117),
+
+	/**
+	 * Caller passed invalid value as param 16 (null, invalid json and etc..)
+	 */
+	CommonInvalidParam16(118),
+
+	/**
+	 * Caller passed invalid value as param 17 (null, invalid json and etc..)
+	 */
+	CommonInvalidParam17(119),
+
+	/**
+	 * Caller passed invalid value as param 18 (null, invalid json and etc..)
+	 */
+	CommonInvalidParam18(120),
+
+	/**
+	 * Caller passed invalid value as param 19 (null, invalid json and etc..)
+	 */
+	CommonInvalidParam19(121),
+
 	// Wallet errors
 	 
 	/**
@@ -177,6 +202,11 @@ public enum ErrorCode {
 	 */
 	WalletQueryError(214),
 
+	/**
+	 * Returned if wallet record was changed since the expected version
+	 */
+	WalletConflict(215),
+
 	// Ledger errors
 	
 	/**
@@ -258,6 +288,16 @@ public enum ErrorCode {
 	 */
 	UnknownCryptoTypeError(500),
 
+	/**
+	 * Attempt to register crypto type with name used for another registered crypto type
+	 */
+	CryptoTypeAlreadyRegisteredError(501),
+
+	/**
+	 * Attempt to register external signer with name used for another registered external signer
+	 */
+	ExternalSignerAlreadyRegisteredError(502),
+
 	/**
 	 * Attempt to create duplicate did.
 	 */
@@ -277,6 +317,21 @@ public enum ErrorCode {
 	 * Insufficient funds on inputs
 	 */
 	InsufficientFundsError(702),
+
+	/**
+	 * Unknown payment source (receipt or input) was given
+	 */
+	PaymentSourceDoesNotExistError(703),
+
+	/**
+	 * Payment method doesn't support requested operation
+	 */
+	PaymentOperationNotSupportedError(704),
+
+	/**
+	 * Inputs and outputs of request belong to several payment methods
+	 */
+	PaymentMixedMethodsError(705),
 	;
 
 	private int value;
@@ -11,6 +11,8 @@ import org.hyperledger.indy.sdk.pool.InvalidPoolException;
 import org.hyperledger.indy.sdk.pool.PoolConfigNotCreatedException;
 import org.hyperledger.indy.sdk.pool.PoolLedgerConfigExistsException;
 import org.hyperledger.indy.sdk.pool.PoolLedgerTerminatedException;
+import org.hyperledger.indy.sdk.crypto.CryptoTypeAlreadyRegisteredException;
+import org.hyperledger.indy.sdk.crypto.ExternalSignerAlreadyRegisteredException;
 import org.hyperledger.indy.sdk.crypto.UnknownCryptoException;
 import org.hyperledger.indy.sdk.wallet.*;
 
@@ -65,6 +67,11 @@ public class IndyException extends Exception {
 			case CommonInvalidParam12:
 			case CommonInvalidParam13:
 			case CommonInvalidParam14:
+			case CommonInvalidParam15:
+			case CommonInvalidParam16:
+			case CommonInvalidParam17:
+			case CommonInvalidParam18:
+			case CommonInvalidParam19:
 				return new InvalidParameterException(sdkErrorCode);
 			case CommonInvalidState:
 				return new InvalidStateException();
@@ -96,6 +103,8 @@ public class IndyException extends Exception {
 				return new WalletItemAlreadyExistsException();
 			case WalletQueryError:
 				return new WalletInvalidQueryException();
+			case WalletConflict:
+				return new WalletConflictException();
 			case WalletIncompatiblePoolError:
 				return new WrongWalletForPoolException();
 			case WalletAlreadyOpenedError:
@@ -132,6 +141,10 @@ public class IndyException extends Exception {
 				return new CredDefAlreadyExistsException();
 			case UnknownCryptoTypeError:
 				return new UnknownCryptoException();
+			case CryptoTypeAlreadyRegisteredError:
+				return new CryptoTypeAlreadyRegisteredException();
+			case ExternalSignerAlreadyRegisteredError:
+				return new ExternalSignerAlreadyRegisteredException();
 			case DidAlreadyExistsError:
 				return new DidAlreadyExistsException();
 			case UnknownPaymentMethod:
@@ -140,6 +153,12 @@ public class IndyException extends Exception {
 				return new IncompatiblePaymentException();
 			case InsufficientFundsError:
 				return new InsufficientFundsException();
+			case PaymentSourceDoesNotExistError:
+				return new PaymentSourceDoesNotExistException();
+			case PaymentOperationNotSupportedError:
+				return new PaymentOperationNotSupportedException();
+			case PaymentMixedMethodsError:
+				return new PaymentMixedMethodsException(LibIndy.getCurrentErrorDetails());
 			default:
 				String message = String.format("An unmapped error with the code '%s' was returned by the SDK.", sdkErrorCode);
 				return new IndyException(message, sdkErrorCode);
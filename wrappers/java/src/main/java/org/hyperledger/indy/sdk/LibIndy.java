@@ -6,6 +6,8 @@ import com.sun.jna.Callback;
 import com.sun.jna.Library;
 import com.sun.jna.Native;
 import com.sun.jna.NativeLibrary;
+import com.sun.jna.Pointer;
+import com.sun.jna.ptr.PointerByReference;
 
 public abstract class LibIndy {
 
@@ -156,6 +158,9 @@ public abstract class LibIndy {
 		int indy_build_get_txn_fees_req(int command_handle, int wallet_handle, String submitter_did, String payment_method, Callback cb);
 		int indy_parse_get_txn_fees_response(int command_handle, String payment_method, String resp_json, Callback cb);
 
+		// mod.rs
+		void indy_get_current_error_details(PointerByReference error_details_json_p);
+
 	}
 
 	/*
@@ -215,4 +220,21 @@ public abstract class LibIndy {
 
 		return api != null;
 	}
+
+	/**
+	 * Gets structured details of the last error returned by the SDK in the current thread.
+	 * The SDK calls callbacks in its own thread, so details are available inside of callback only.
+	 *
+	 * @return Details json or null if the last error has no details.
+	 */
+	public static String getCurrentErrorDetails() {
+
+		if (! isInitialized()) return null;
+
+		PointerByReference detailsRef = new PointerByReference();
+		api.indy_get_current_error_details(detailsRef);
+
+		Pointer details = detailsRef.getValue();
+		return details == null ? null : details.getString(0);
+	}
 }
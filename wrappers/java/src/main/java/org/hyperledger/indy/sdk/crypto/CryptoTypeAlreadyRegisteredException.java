@@ -0,0 +1,21 @@
+package org.hyperledger.indy.sdk.crypto;
+
+import org.hyperledger.indy.sdk.ErrorCode;
+import org.hyperledger.indy.sdk.IndyException;
+
+/**
+ * Exception thrown when attempting to register a crypto type with the name of already registered one.
+ */
+public class CryptoTypeAlreadyRegisteredException extends IndyException
+{
+	private static final long serialVersionUID = 6275711661964891560L;
+	private final static String message = "A crypto type with the specified name has already been registered.";
+
+	/**
+	 * Initializes a new CryptoTypeAlreadyRegisteredException.
+	 */
+	public CryptoTypeAlreadyRegisteredException() 
+    {
+    	super(message, ErrorCode.CryptoTypeAlreadyRegisteredError.value());
+    }
+}
@@ -0,0 +1,21 @@
+package org.hyperledger.indy.sdk.crypto;
+
+import org.hyperledger.indy.sdk.ErrorCode;
+import org.hyperledger.indy.sdk.IndyException;
+
+/**
+ * Exception thrown when attempting to register an external signer with the name of already registered one.
+ */
+public class ExternalSignerAlreadyRegisteredException extends IndyException
+{
+	private static final long serialVersionUID = 3108492316604739845L;
+	private final static String message = "An external signer with the specified name has already been registered.";
+
+	/**
+	 * Initializes a new ExternalSignerAlreadyRegisteredException.
+	 */
+	public ExternalSignerAlreadyRegisteredException() 
+    {
+    	super(message, ErrorCode.ExternalSignerAlreadyRegisteredError.value());
+    }
+}
@@ -0,0 +1,29 @@
+package org.hyperledger.indy.sdk.payments;
+
+import org.hyperledger.indy.sdk.ErrorCode;
+import org.hyperledger.indy.sdk.IndyException;
+
+public class PaymentMixedMethodsException extends IndyException {
+    private static final long serialVersionUID = 6397499268992083528L;
+    private static final String message = "Inputs and outputs of request belong to several payment methods";
+    private final String details;
+
+    /**
+     * Initializes a new {@link PaymentMixedMethodsException} with the specified details.
+     *
+     * @param details Json with inputs and outputs grouped by payment method or null if SDK didn't provide them.
+     */
+    public PaymentMixedMethodsException(String details) {
+        super(message, ErrorCode.PaymentMixedMethodsError.value());
+        this.details = details;
+    }
+
+    /**
+     * Gets inputs and outputs of the request grouped by payment method.
+     *
+     * @return Details json or null.
+     */
+    public String getDetails() {
+        return details;
+    }
+}
@@ -0,0 +1,16 @@
+package org.hyperledger.indy.sdk.payments;
+
+import org.hyperledger.indy.sdk.ErrorCode;
+import org.hyperledger.indy.sdk.IndyException;
+
+public class PaymentOperationNotSupportedException extends IndyException {
+    private static final long serialVersionUID = 4871306125487345609L;
+    private static final String message = "Payment method doesn't support requested operation";
+
+    /**
+     * Initializes a new {@link PaymentOperationNotSupportedException} with the specified message.
+     */
+    public PaymentOperationNotSupportedException() {
+        super(message, ErrorCode.PaymentOperationNotSupportedError.value());
+    }
+}
@@ -0,0 +1,16 @@
+package org.hyperledger.indy.sdk.payments;
+
+import org.hyperledger.indy.sdk.ErrorCode;
+import org.hyperledger.indy.sdk.IndyException;
+
+public class PaymentSourceDoesNotExistException extends IndyException {
+    private static final long serialVersionUID = -2370145860423196823L;
+    private static final String message = "Unknown payment source (receipt or input) was given";
+
+    /**
+     * Initializes a new {@link PaymentSourceDoesNotExistException} with the specified message.
+     */
+    public PaymentSourceDoesNotExistException() {
+        super(message, ErrorCode.PaymentSourceDoesNotExistError.value());
+    }
+}
@@ -0,0 +1,21 @@
+package org.hyperledger.indy.sdk.wallet;
+
+import org.hyperledger.indy.sdk.ErrorCode;
+import org.hyperledger.indy.sdk.IndyException;
+
+/**
+ * Exception thrown when wallet record was changed since the expected version.
+ */
+public class WalletConflictException extends IndyException
+{
+	private static final long serialVersionUID = 3294831240096535507L;
+	private final static String message = "Wallet record was changed since the expected version.";
+
+	/**
+	 * Initializes a new WalletConflictException.
+	 */
+	public WalletConflictException()
+    {
+    	super(message, ErrorCode.WalletConflict.value());
+    }
+}
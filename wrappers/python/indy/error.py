@@ -51,6 +51,27 @@ class ErrorCode(IntEnum):
     # IO Error
     CommonIOError = 114,
 
+    # Caller passed invalid value as param 13 (null, invalid json and etc..)
+    CommonInvalidParam13 = 115,
+
+    # Caller passed invalid value as param 14 (null, invalid json and etc..)
+    CommonInvalidParam14 = 116,
+
+    # Caller passed invalid value as param 15 (null, invalid json and etc..)
+    CommonInvalidParam15 = 117,
+
+    # Caller passed invalid value as param 16 (null, invalid json and etc..)
+    CommonInvalidParam16 = 118,
+
+    # Caller passed invalid value as param 17 (null, invalid json and etc..)
+    CommonInvalidParam17 = 119,
+
+    # Caller passed invalid value as param 18 (null, invalid json and etc..)
+    CommonInvalidParam18 = 120,
+
+    # Caller passed invalid value as param 19 (null, invalid json and etc..)
+    CommonInvalidParam19 = 121,
+
     # Wallet errors
     # Caller passed invalid wallet handle
     WalletInvalidHandle = 200,
@@ -97,6 +118,9 @@ class ErrorCode(IntEnum):
     # Returned if provided wallet query is invalid
     WalletQueryError = 214,
 
+    # Returned if wallet record was changed since the expected version
+    WalletConflict = 215,
+
     # Ledger errors
     # Trying to open pool ledger that wasn't created before
     PoolLedgerNotCreatedError = 300,
@@ -141,6 +165,12 @@ class ErrorCode(IntEnum):
     # Unknown format of DID entity keys
     UnknownCryptoTypeError = 500,
 
+    # Attempt to register crypto type with name used for another registered crypto type
+    CryptoTypeAlreadyRegisteredError = 501,
+
+    # Attempt to register external signer with name used for another registered external signer
+    ExternalSignerAlreadyRegisteredError = 502,
+
     # Attempt to create duplicate did
     DidAlreadyExistsError = 600,
 
@@ -151,7 +181,16 @@ class ErrorCode(IntEnum):
     PaymentIncompatibleMethodsError = 701,
 
     # Insufficient funds on inputs
-    PaymentInsufficientFundsError = 702
+    PaymentInsufficientFundsError = 702,
+
+    # Unknown payment source (receipt or input) was given
+    PaymentSourceDoesNotExistError = 703,
+
+    # Payment method doesn't support requested operation
+    PaymentOperationNotSupportedError = 704,
+
+    # Inputs and outputs of request belong to several payment methods
+    PaymentMixedMethodsError = 705
 
 
 class IndyError(Exception):